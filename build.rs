@@ -0,0 +1,19 @@
+use std::{env, fs, path::PathBuf};
+
+fn main() {
+    // The memory map of the board's MCU, which keeps the image out of the flash the updater and
+    // the settings write to, see `memory-g0.x`.
+    let memory = if env::var_os("CARGO_FEATURE_FAMILY_L4").is_some() {
+        "memory-l4.x"
+    } else {
+        "memory-g0.x"
+    };
+
+    let out = PathBuf::from(env::var_os("OUT_DIR").unwrap());
+    fs::copy(memory, out.join("memory.x")).unwrap();
+    println!("cargo:rustc-link-search={}", out.display());
+    println!("cargo:rerun-if-changed={}", memory);
+
+    // embedded-test's linker script, only for the on-target tests in `tests/`.
+    println!("cargo:rustc-link-arg-tests=-Tembedded-test.x");
+}
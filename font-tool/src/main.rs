@@ -0,0 +1,354 @@
+//! Converts a BDF or TrueType font into a glyph table for `src/font.rs`.
+//!
+//! Every character is drawn into a fixed `WIDTHxHEIGHT` cell, packed one bit per pixel row by row
+//! and run-length encoded with `src/rle.rs`, the same code the firmware decodes with. The table is
+//! written to stdout; save it under `src/fonts/` and `include!` it from `src/font.rs`. Build it for
+//! the host, as `.cargo/config.toml` defaults to the MCU target:
+//!
+//! ```sh
+//! cargo run -p font-tool --target x86_64-unknown-linux-gnu -- \
+//!     Grotesk.ttf GROTESK_24_48 24x48 --px 48 --chars "0123456789.- " > src/fonts/grotesk_24_48.rs
+//! ```
+//!
+//! BDF glyphs are placed by their bounding boxes on the font's baseline. TrueType outlines are
+//! rasterized at `--px` pixels per em, centered on their advance and thresholded at half coverage.
+//! `--chars` defaults to printable ASCII; the first character is what unknown ones fall back to.
+
+use std::{collections::HashMap, env, fmt::Write, fs, path::Path, process};
+
+// Only the encoder is needed here.
+#[allow(dead_code)]
+#[path = "../../src/rle.rs"]
+mod rle;
+
+const USAGE: &str = "usage: font-tool <FONT.bdf|FONT.ttf> <NAME> <WIDTH>x<HEIGHT> [--px SIZE] \
+                     [--chars CHARS]";
+
+struct Args {
+    path: String,
+    name: String,
+    width: usize,
+    height: usize,
+    /// Pixels per em, TrueType only.
+    px: Option<f32>,
+    chars: Vec<char>,
+}
+
+/// One glyph cell, a pixel per entry, row by row.
+struct Cell {
+    width: usize,
+    height: usize,
+    pixels: Vec<bool>,
+}
+
+impl Cell {
+    fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![false; width * height],
+        }
+    }
+
+    /// Draws a `width` x `height` glyph with its top left corner at `x`, `y`. Returns false when
+    /// part of it fell outside the cell.
+    fn place(
+        &mut self,
+        x: i32,
+        y: i32,
+        width: usize,
+        height: usize,
+        pixel: impl Fn(usize, usize) -> bool,
+    ) -> bool {
+        let mut fits = true;
+
+        for row in 0..height {
+            for column in 0..width {
+                if !pixel(column, row) {
+                    continue;
+                }
+
+                let (cx, cy) = (x + column as i32, y + row as i32);
+                if cx < 0 || cy < 0 || cx as usize >= self.width || cy as usize >= self.height {
+                    fits = false;
+                    continue;
+                }
+
+                self.pixels[cy as usize * self.width + cx as usize] = true;
+            }
+        }
+
+        fits
+    }
+
+    /// One bit per pixel, most significant first, as `font.rs` expects before encoding.
+    fn pack(&self) -> Vec<u8> {
+        let mut bytes = vec![0; self.pixels.len() / 8];
+
+        for (i, _) in self.pixels.iter().enumerate().filter(|(_, set)| **set) {
+            bytes[i / 8] |= 0x80 >> (i % 8);
+        }
+
+        bytes
+    }
+}
+
+fn main() {
+    let args = parse_args(env::args().skip(1)).unwrap_or_else(|err| {
+        eprintln!("{err}\n{USAGE}");
+        process::exit(2);
+    });
+
+    let cells = render(&args).unwrap_or_else(|err| {
+        eprintln!("{}: {err}", args.path);
+        process::exit(1);
+    });
+
+    print!("{}", table(&args, &cells));
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let path = args.next().ok_or("missing font")?;
+    let name = args.next().ok_or("missing table name")?;
+    let size = args.next().ok_or("missing cell size")?;
+
+    let (width, height) = size
+        .split_once('x')
+        .and_then(|(w, h)| Some((w.parse::<usize>().ok()?, h.parse::<usize>().ok()?)))
+        .ok_or_else(|| format!("bad cell size {size}"))?;
+
+    if width == 0 || height == 0 || width * height % 8 != 0 {
+        return Err(format!("{size} is not a whole number of bytes"));
+    }
+
+    let mut px = None;
+    let mut chars: Vec<char> = (' '..='~').collect();
+
+    while let Some(arg) = args.next() {
+        let value = args.next().ok_or_else(|| format!("{arg} needs a value"))?;
+
+        match arg.as_str() {
+            "--px" => px = Some(value.parse().map_err(|_| format!("bad size {value}"))?),
+            "--chars" => chars = value.chars().collect(),
+            _ => return Err(format!("unknown option {arg}")),
+        }
+    }
+
+    Ok(Args {
+        path,
+        name,
+        width,
+        height,
+        px,
+        chars,
+    })
+}
+
+fn render(args: &Args) -> Result<Vec<Cell>, String> {
+    let data = fs::read(&args.path).map_err(|err| err.to_string())?;
+
+    let cells = if args.path.to_lowercase().ends_with(".bdf") {
+        render_bdf(&String::from_utf8_lossy(&data), args)?
+    } else {
+        render_ttf(&data, args)?
+    };
+
+    Ok(cells)
+}
+
+/// A BDF glyph: its bounding box and bitmap rows.
+struct BdfGlyph {
+    width: usize,
+    height: usize,
+    x: i32,
+    y: i32,
+    rows: Vec<Vec<u8>>,
+}
+
+fn render_bdf(text: &str, args: &Args) -> Result<Vec<Cell>, String> {
+    let mut ascent = None;
+    let mut glyphs = HashMap::new();
+    let mut encoding = None;
+    let mut glyph: Option<BdfGlyph> = None;
+    let mut in_bitmap = false;
+
+    for line in text.lines() {
+        let mut words = line.split_whitespace();
+        let keyword = words.next().unwrap_or("");
+        let numbers: Vec<i32> = words.filter_map(|w| w.parse().ok()).collect();
+
+        match keyword {
+            "FONT_ASCENT" => ascent = numbers.first().copied(),
+            // The bounding box gives the baseline for fonts without FONT_ASCENT.
+            "FONTBOUNDINGBOX" if ascent.is_none() && numbers.len() == 4 => {
+                ascent = Some(numbers[1] + numbers[3])
+            }
+            "ENCODING" => encoding = numbers.first().and_then(|&n| char::from_u32(n as u32)),
+            "BBX" if numbers.len() == 4 => {
+                glyph = Some(BdfGlyph {
+                    width: numbers[0] as usize,
+                    height: numbers[1] as usize,
+                    x: numbers[2],
+                    y: numbers[3],
+                    rows: Vec::new(),
+                })
+            }
+            "BITMAP" => in_bitmap = true,
+            "ENDCHAR" => {
+                in_bitmap = false;
+                if let (Some(c), Some(g)) = (encoding.take(), glyph.take()) {
+                    glyphs.insert(c, g);
+                }
+            }
+            _ if in_bitmap => {
+                let row = (0..line.len() / 2)
+                    .map(|i| u8::from_str_radix(&line[i * 2..i * 2 + 2], 16))
+                    .collect::<Result<_, _>>()
+                    .map_err(|_| format!("bad bitmap row {line}"))?;
+
+                if let Some(g) = glyph.as_mut() {
+                    g.rows.push(row);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let ascent = ascent.ok_or("no FONT_ASCENT or FONTBOUNDINGBOX")?;
+
+    args.chars
+        .iter()
+        .map(|&c| {
+            let g = glyphs
+                .get(&c)
+                .ok_or_else(|| format!("no glyph for {c:?}"))?;
+            let mut cell = Cell::new(args.width, args.height);
+
+            let fits = cell.place(
+                g.x,
+                ascent - g.y - g.height as i32,
+                g.width,
+                g.height,
+                |x, y| {
+                    g.rows
+                        .get(y)
+                        .and_then(|row| row.get(x / 8))
+                        .is_some_and(|byte| byte & (0x80 >> (x % 8)) != 0)
+                },
+            );
+            if !fits {
+                eprintln!(
+                    "warning: {c:?} is clipped to {}x{}",
+                    args.width, args.height
+                );
+            }
+
+            Ok(cell)
+        })
+        .collect()
+}
+
+fn render_ttf(data: &[u8], args: &Args) -> Result<Vec<Cell>, String> {
+    let px = args.px.ok_or("TrueType fonts need --px")?;
+    let font = fontdue::Font::from_bytes(data, fontdue::FontSettings::default())?;
+    let ascent = font
+        .horizontal_line_metrics(px)
+        .ok_or("no horizontal metrics")?
+        .ascent
+        .round() as i32;
+
+    args.chars
+        .iter()
+        .map(|&c| {
+            if c != ' ' && font.lookup_glyph_index(c) == 0 {
+                return Err(format!("no glyph for {c:?}"));
+            }
+
+            let (metrics, coverage) = font.rasterize(c, px);
+            let mut cell = Cell::new(args.width, args.height);
+
+            let left = (args.width as i32 - metrics.advance_width.round() as i32) / 2;
+            let top = ascent - metrics.ymin - metrics.height as i32;
+
+            let fits = cell.place(
+                left + metrics.xmin,
+                top,
+                metrics.width,
+                metrics.height,
+                |x, y| coverage[y * metrics.width + x] >= 128,
+            );
+            if !fits {
+                eprintln!(
+                    "warning: {c:?} is clipped to {}x{}",
+                    args.width, args.height
+                );
+            }
+
+            Ok(cell)
+        })
+        .collect()
+}
+
+/// The `Font` static, laid out like rustfmt would.
+fn table(args: &Args, cells: &[Cell]) -> String {
+    let source = Path::new(&args.path)
+        .file_name()
+        .map_or(args.path.clone(), |name| {
+            name.to_string_lossy().into_owned()
+        });
+
+    let mut out = String::new();
+
+    writeln!(out, "// @generated by font-tool from {source}.").ok();
+    writeln!(
+        out,
+        "// Regenerate rather than edit, see font-tool/src/main.rs."
+    )
+    .ok();
+    writeln!(out).ok();
+    writeln!(out, "pub static {}: Font = Font {{", args.name).ok();
+    writeln!(out, "    width: {},", args.width).ok();
+    writeln!(out, "    height: {},", args.height).ok();
+    writeln!(out, "    chars: &[").ok();
+
+    let mut line = String::from("       ");
+    for c in &args.chars {
+        let item = format!(" {c:?},");
+        if line.len() + item.len() > 100 {
+            writeln!(out, "{line}").ok();
+            line = String::from("       ");
+        }
+        line.push_str(&item);
+    }
+    writeln!(out, "{line}").ok();
+
+    writeln!(out, "    ],").ok();
+    writeln!(out, "    glyphs: &[").ok();
+
+    for (c, cell) in args.chars.iter().zip(cells) {
+        let mut encoded = Vec::new();
+        rle::encode(&cell.pack(), |b| encoded.push(b));
+
+        match c {
+            ' ' => writeln!(out, "        // <space>").ok(),
+            c => writeln!(out, "        // {c}").ok(),
+        };
+
+        if encoded.is_empty() {
+            writeln!(out, "        &[],").ok();
+            continue;
+        }
+
+        writeln!(out, "        &[").ok();
+        for chunk in encoded.chunks(12) {
+            let bytes: Vec<String> = chunk.iter().map(|b| format!("0x{b:02X},")).collect();
+            writeln!(out, "            {}", bytes.join(" ")).ok();
+        }
+        writeln!(out, "        ],").ok();
+    }
+
+    writeln!(out, "    ],").ok();
+    writeln!(out, "}};").ok();
+
+    out
+}
@@ -0,0 +1,116 @@
+use embassy_time::Duration;
+
+use crate::{
+    average::{AverageInterval, IntervalAverage, AVERAGE_INTERVALS},
+    mock_time::{self, Instant},
+    types::PowerInfo,
+    units::{from_milli, milli},
+};
+
+fn reading(mv: i32, ma: i32) -> PowerInfo {
+    PowerInfo {
+        volts: from_milli(mv),
+        amps: from_milli(ma),
+        watts: from_milli(mv / 1_000 * ma),
+    }
+}
+
+fn update(average: &mut IntervalAverage, mv: i32, ma: i32) -> Option<(i32, i32, i32)> {
+    average
+        .update(Instant::now(), &reading(mv, ma))
+        .map(|power| (milli(power.volts), milli(power.amps), milli(power.watts)))
+}
+
+#[test]
+fn off_reports_nothing() {
+    mock_time::set(Duration::from_secs(1));
+    let mut average = IntervalAverage::new();
+
+    for _ in 0..10 {
+        assert_eq!(update(&mut average, 5_000, 1_000), None);
+        mock_time::advance(Duration::from_secs(1));
+    }
+}
+
+#[test]
+fn reports_the_mean_once_per_interval() {
+    mock_time::set(Duration::from_secs(1));
+    let mut average = IntervalAverage::new();
+    average.set_interval(AverageInterval::OneSecond);
+
+    assert_eq!(update(&mut average, 5_000, 1_000), None);
+    mock_time::advance(Duration::from_millis(500));
+    assert_eq!(update(&mut average, 5_000, 3_000), None);
+    mock_time::advance(Duration::from_millis(500));
+    assert_eq!(
+        update(&mut average, 5_000, 2_000),
+        Some((5_000, 2_000, 10_000))
+    );
+
+    // The next interval starts from the reading that completed the last one.
+    mock_time::advance(Duration::from_millis(500));
+    assert_eq!(update(&mut average, 9_000, 1_000), None);
+    mock_time::advance(Duration::from_millis(500));
+    assert_eq!(
+        update(&mut average, 9_000, 1_000),
+        Some((9_000, 1_000, 9_000))
+    );
+}
+
+#[test]
+fn a_new_interval_starts_over() {
+    mock_time::set(Duration::from_secs(1));
+    let mut average = IntervalAverage::new();
+    average.set_interval(AverageInterval::OneSecond);
+
+    update(&mut average, 20_000, 5_000);
+    mock_time::advance(Duration::from_millis(900));
+
+    // The same interval keeps the running sums.
+    average.set_interval(AverageInterval::OneSecond);
+    assert_eq!(update(&mut average, 20_000, 5_000), None);
+
+    average.set_interval(AverageInterval::TenSeconds);
+    assert_eq!(update(&mut average, 5_000, 1_000), None);
+    mock_time::advance(Duration::from_secs(9));
+    assert_eq!(update(&mut average, 5_000, 1_000), None);
+    mock_time::advance(Duration::from_secs(1));
+    assert_eq!(
+        update(&mut average, 5_000, 1_000),
+        Some((5_000, 1_000, 5_000))
+    );
+}
+
+#[test]
+fn reset_drops_the_running_interval() {
+    mock_time::set(Duration::from_secs(1));
+    let mut average = IntervalAverage::new();
+    average.set_interval(AverageInterval::OneSecond);
+
+    update(&mut average, 20_000, 5_000);
+    mock_time::advance(Duration::from_secs(5));
+
+    average.reset();
+    assert_eq!(average.interval(), AverageInterval::OneSecond);
+    assert_eq!(update(&mut average, 5_000, 1_000), None);
+    mock_time::advance(Duration::from_secs(1));
+    assert_eq!(
+        update(&mut average, 5_000, 1_000),
+        Some((5_000, 1_000, 5_000))
+    );
+}
+
+#[test]
+fn next_and_prev_wrap_around() {
+    for interval in AVERAGE_INTERVALS {
+        assert_eq!(interval.next().prev(), interval);
+    }
+
+    assert_eq!(AverageInterval::OneMinute.next(), AverageInterval::Off);
+    assert_eq!(AverageInterval::Off.prev(), AverageInterval::OneMinute);
+    assert_eq!(AverageInterval::Off.duration(), None);
+    assert_eq!(
+        AverageInterval::OneMinute.duration(),
+        Some(Duration::from_secs(60))
+    );
+}
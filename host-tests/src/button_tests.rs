@@ -0,0 +1,191 @@
+use embassy_futures::block_on;
+use embassy_sync::{blocking_mutex::raw::CriticalSectionRawMutex, channel::Channel};
+use embassy_time::Duration;
+
+use crate::{
+    button::{Button, ButtonState},
+    mock_time::{self, Instant},
+};
+
+type StateChannel = Channel<CriticalSectionRawMutex, ButtonState, 10>;
+
+fn setup() -> StateChannel {
+    mock_time::set(Duration::from_secs(1));
+    Channel::new()
+}
+
+fn wait(ms: u64) {
+    mock_time::advance(Duration::from_millis(ms));
+}
+
+fn received(channel: &StateChannel) -> Vec<ButtonState> {
+    let mut states = Vec::new();
+
+    while let Ok(state) = channel.try_receive() {
+        states.push(state);
+    }
+
+    states
+}
+
+#[test]
+fn click() {
+    let channel = setup();
+    let mut button = Button::new(&channel);
+
+    block_on(button.on_press());
+    wait(100);
+    block_on(button.on_release());
+
+    assert_eq!(
+        received(&channel),
+        [ButtonState::Pressed, ButtonState::Click(Instant::now())]
+    );
+}
+
+#[test]
+fn bounce_is_ignored() {
+    let channel = setup();
+    let mut button = Button::new(&channel);
+
+    block_on(button.on_press());
+    wait(20);
+    block_on(button.on_release());
+
+    assert_eq!(
+        received(&channel),
+        [ButtonState::Pressed, ButtonState::Released]
+    );
+}
+
+#[test]
+fn release_without_press() {
+    let channel = setup();
+    let mut button = Button::new(&channel);
+
+    block_on(button.on_release());
+
+    assert_eq!(received(&channel), [ButtonState::Released]);
+}
+
+#[test]
+fn double_click() {
+    let channel = setup();
+    let mut button = Button::new(&channel);
+
+    block_on(button.on_press());
+    wait(100);
+    block_on(button.on_release());
+    wait(80);
+    block_on(button.on_press());
+    wait(100);
+    block_on(button.on_release());
+
+    assert_eq!(
+        received(&channel).last(),
+        Some(&ButtonState::DoubleClick(Instant::now()))
+    );
+}
+
+#[test]
+fn slow_second_click_is_a_click() {
+    let channel = setup();
+    let mut button = Button::new(&channel);
+
+    block_on(button.on_press());
+    wait(100);
+    block_on(button.on_release());
+    wait(300);
+    block_on(button.on_press());
+    wait(100);
+    block_on(button.on_release());
+
+    assert_eq!(
+        received(&channel).last(),
+        Some(&ButtonState::Click(Instant::now()))
+    );
+}
+
+#[test]
+fn long_press() {
+    let channel = setup();
+    let mut button = Button::new(&channel);
+
+    block_on(button.on_press());
+    wait(150);
+    block_on(button.update());
+
+    assert_eq!(received(&channel), [ButtonState::Pressed]);
+
+    wait(100);
+    block_on(button.update());
+
+    assert_eq!(
+        received(&channel),
+        [ButtonState::LongPressed(Instant::now())]
+    );
+
+    // Reported once, and the release that follows is not a click.
+    wait(500);
+    block_on(button.update());
+    block_on(button.on_release());
+
+    assert_eq!(received(&channel), [ButtonState::Released]);
+}
+
+#[test]
+fn click_after_long_press_is_not_a_double_click() {
+    let channel = setup();
+    let mut button = Button::new(&channel);
+
+    block_on(button.on_press());
+    wait(250);
+    block_on(button.update());
+    block_on(button.on_release());
+    wait(50);
+    received(&channel);
+
+    block_on(button.on_press());
+    wait(100);
+    block_on(button.on_release());
+
+    assert_eq!(
+        received(&channel),
+        [ButtonState::Pressed, ButtonState::Click(Instant::now())]
+    );
+}
+
+#[test]
+fn update_while_released_does_nothing() {
+    let channel = setup();
+    let mut button = Button::new(&channel);
+
+    wait(1000);
+    block_on(button.update());
+
+    assert!(received(&channel).is_empty());
+}
+
+#[test]
+fn returns_the_states_it_sends() {
+    let channel = setup();
+    let mut button = Button::new(&channel);
+
+    assert_eq!(block_on(button.on_press()), ButtonState::Pressed);
+    assert_eq!(block_on(button.update()), None);
+    wait(300);
+    assert_eq!(
+        block_on(button.update()),
+        Some(ButtonState::LongPressed(Instant::now()))
+    );
+    assert_eq!(block_on(button.on_release()), ButtonState::Released);
+
+    assert_eq!(
+        received(&channel),
+        [
+            ButtonState::Pressed,
+            ButtonState::LongPressed(Instant::now()),
+            ButtonState::Released
+        ]
+    );
+}
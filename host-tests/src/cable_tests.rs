@@ -0,0 +1,67 @@
+use crate::{
+    cable::{CableError, CableProbe},
+    units::{from_milli, milli},
+};
+
+fn probe(readings: &[(i32, i32)]) -> CableProbe {
+    let mut probe = CableProbe::new();
+
+    for (mv, ma) in readings {
+        probe.record(from_milli(*mv), from_milli(*ma));
+    }
+
+    probe
+}
+
+#[test]
+fn estimates_from_two_loads() {
+    // 150 mV lost over 1.5 A more.
+    let resistance = probe(&[(5_100, 500), (4_950, 2_000)]).resistance();
+
+    assert_eq!(resistance.map(milli), Ok(100));
+}
+
+#[test]
+fn order_of_loads_does_not_matter() {
+    let resistance = probe(&[(4_950, 2_000), (5_100, 500)]).resistance();
+
+    assert_eq!(resistance.map(milli), Ok(100));
+}
+
+#[test]
+fn needs_two_readings() {
+    assert_eq!(probe(&[]).resistance(), Err(CableError::MissingReading));
+    assert_eq!(
+        probe(&[(5_000, 500)]).resistance(),
+        Err(CableError::MissingReading)
+    );
+}
+
+#[test]
+fn rejects_small_current_step() {
+    assert_eq!(
+        probe(&[(5_000, 500), (4_990, 600)]).resistance(),
+        Err(CableError::CurrentStepTooSmall)
+    );
+}
+
+#[test]
+fn rejects_rising_voltage() {
+    assert_eq!(
+        probe(&[(4_900, 500), (5_000, 2_000)]).resistance(),
+        Err(CableError::VoltageRose)
+    );
+}
+
+#[test]
+fn third_reading_starts_a_new_pair() {
+    let mut probe = probe(&[(5_100, 500), (4_950, 2_000), (5_000, 1_000)]);
+
+    assert_eq!(probe.resistance(), Err(CableError::MissingReading));
+
+    probe.record(from_milli(4_800), from_milli(3_000));
+    assert_eq!(probe.resistance().map(milli), Ok(100));
+
+    probe.clear();
+    assert_eq!(probe.readings(), [None, None]);
+}
@@ -0,0 +1,92 @@
+use crate::{
+    calendar::{civil_from_days, days_from_civil, DateTime, MAX_YEAR, MIN_YEAR},
+    types::ClockField,
+};
+
+fn date(year: u16, month: u8, day: u8) -> DateTime {
+    DateTime {
+        year,
+        month,
+        day,
+        ..DateTime::default()
+    }
+}
+
+#[test]
+fn unix_round_trip() {
+    let time = DateTime::from_unix(1_714_566_896);
+
+    assert_eq!(
+        time,
+        DateTime {
+            year: 2024,
+            month: 5,
+            day: 1,
+            hour: 12,
+            minute: 34,
+            second: 56,
+        }
+    );
+    assert_eq!(time.to_unix(), 1_714_566_896);
+    assert_eq!(DateTime::from_unix(0).to_unix(), 0);
+}
+
+#[test]
+fn days_round_trip() {
+    for days in [-1, 0, 59, 365, 11_016, 19_782, 47_482] {
+        let (year, month, day) = civil_from_days(days);
+
+        assert_eq!(days_from_civil(year, month, day), days);
+    }
+}
+
+#[test]
+fn weekdays() {
+    // Thursday, Monday and Sunday.
+    assert_eq!(date(1970, 1, 1).weekday(), 4);
+    assert_eq!(date(2024, 4, 29).weekday(), 1);
+    assert_eq!(date(2024, 12, 29).weekday(), 7);
+}
+
+#[test]
+fn fields_wrap_around() {
+    let mut time = date(MAX_YEAR, 12, 31);
+
+    time.step(ClockField::Year, true);
+    assert_eq!(time.year, MIN_YEAR);
+    time.step(ClockField::Year, false);
+    assert_eq!(time.year, MAX_YEAR);
+
+    time.step(ClockField::Month, true);
+    assert_eq!(time.month, 1);
+    time.step(ClockField::Day, true);
+    assert_eq!(time.day, 1);
+
+    time.step(ClockField::Minute, false);
+    assert_eq!(time.minute, 59);
+    time.step(ClockField::Hour, false);
+    assert_eq!(time.hour, 23);
+}
+
+#[test]
+fn day_stays_within_the_month() {
+    let mut time = date(2024, 1, 31);
+
+    time.step(ClockField::Month, true);
+    assert_eq!((time.month, time.day), (2, 29));
+
+    time.step(ClockField::Year, true);
+    assert_eq!((time.year, time.day), (2025, 28));
+
+    time.step(ClockField::Day, true);
+    assert_eq!(time.day, 1);
+}
+
+#[test]
+fn stepping_clears_the_seconds() {
+    let mut time = DateTime::from_unix(1_714_566_896);
+
+    time.step(ClockField::Minute, true);
+
+    assert_eq!((time.minute, time.second), (35, 0));
+}
@@ -0,0 +1,99 @@
+use crate::{
+    capture::{Capture, CaptureState, POST_TRIGGER, PRE_TRIGGER},
+    units::{from_milli, milli},
+};
+
+fn armed(threshold_ma: i32) -> Capture {
+    let mut capture = Capture::new();
+    capture.set_threshold(from_milli(threshold_ma));
+    capture
+}
+
+fn feed(capture: &mut Capture, ma: i32, count: usize) -> bool {
+    (0..count).fold(false, |done, _| capture.record(from_milli(ma)) || done)
+}
+
+#[test]
+fn off_without_threshold() {
+    let mut capture = Capture::new();
+
+    assert_eq!(capture.state(), CaptureState::Off);
+    assert!(!feed(&mut capture, 5_000, 10));
+    assert_eq!(capture.samples().count(), 0);
+}
+
+#[test]
+fn keeps_pre_trigger_window_while_armed() {
+    let mut capture = armed(1_000);
+
+    feed(&mut capture, 500, PRE_TRIGGER * 2);
+
+    assert_eq!(capture.state(), CaptureState::Armed);
+    assert_eq!(capture.samples().count(), PRE_TRIGGER);
+    assert_eq!(capture.trigger_at(), None);
+}
+
+#[test]
+fn holds_window_around_rising_crossing() {
+    let mut capture = armed(1_000);
+
+    feed(&mut capture, 500, PRE_TRIGGER * 2);
+    assert!(!capture.record(from_milli(2_000)));
+    assert_eq!(capture.state(), CaptureState::Triggered);
+
+    assert!(feed(&mut capture, 2_000, POST_TRIGGER - 1));
+    assert_eq!(capture.state(), CaptureState::Held);
+    assert_eq!(capture.count(), 1);
+
+    let trigger_at = capture.trigger_at().unwrap();
+    let samples: Vec<i32> = capture.samples().map(milli).collect();
+
+    assert_eq!(trigger_at, PRE_TRIGGER - 1);
+    assert_eq!(samples.len(), trigger_at + POST_TRIGGER);
+    assert_eq!(samples[trigger_at - 1], 500);
+    assert_eq!(samples[trigger_at], 2_000);
+
+    // Held until armed again.
+    assert!(!feed(&mut capture, 0, 10));
+    assert_eq!(capture.samples().count(), trigger_at + POST_TRIGGER);
+}
+
+#[test]
+fn needs_a_reading_below_threshold_first() {
+    let mut capture = armed(1_000);
+
+    feed(&mut capture, 2_000, 10);
+    assert_eq!(capture.state(), CaptureState::Armed);
+
+    capture.record(from_milli(500));
+    capture.record(from_milli(1_000));
+    assert_eq!(capture.state(), CaptureState::Triggered);
+}
+
+#[test]
+fn arm_drops_the_window() {
+    let mut capture = armed(1_000);
+
+    capture.record(from_milli(0));
+    feed(&mut capture, 2_000, POST_TRIGGER);
+    assert_eq!(capture.state(), CaptureState::Held);
+
+    capture.arm();
+    assert_eq!(capture.state(), CaptureState::Armed);
+    assert_eq!(capture.samples().count(), 0);
+    assert_eq!(capture.trigger_at(), None);
+}
+
+#[test]
+fn scale_rounds_up_to_half_amps() {
+    let mut capture = armed(1_200);
+    assert_eq!(milli(capture.scale()), 1_500);
+
+    capture.record(from_milli(0));
+    capture.record(from_milli(3_100));
+    assert_eq!(milli(capture.scale()), 3_500);
+
+    capture.set_threshold(from_milli(0));
+    assert_eq!(capture.state(), CaptureState::Off);
+    assert_eq!(milli(capture.scale()), 500);
+}
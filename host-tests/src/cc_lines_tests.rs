@@ -0,0 +1,45 @@
+use crate::cc_lines::{CcLines, Orientation, Rp};
+
+#[test]
+fn rp_levels() {
+    assert_eq!(Rp::of(0), Rp::Open);
+    assert_eq!(Rp::of(410), Rp::Default);
+    assert_eq!(Rp::of(920), Rp::Current1A5);
+    assert_eq!(Rp::of(1_680), Rp::Current3A0);
+    assert_eq!(Rp::of(3_300), Rp::High);
+}
+
+#[test]
+fn orientation_from_the_loaded_pin() {
+    let flipped = CcLines {
+        cc1_mv: 20,
+        cc2_mv: 1_680,
+    };
+
+    assert_eq!(flipped.orientation(), Orientation::Cc2);
+    assert_eq!(flipped.rp(), Rp::Current3A0);
+
+    let straight = CcLines {
+        cc1_mv: 410,
+        cc2_mv: 0,
+    };
+
+    assert_eq!(straight.orientation(), Orientation::Cc1);
+    assert_eq!(straight.rp(), Rp::Default);
+}
+
+#[test]
+fn unattached_and_both() {
+    let open = CcLines {
+        cc1_mv: 0,
+        cc2_mv: 150,
+    };
+    assert_eq!(open.orientation(), Orientation::Unattached);
+    assert_eq!(open.rp(), Rp::Open);
+
+    let both = CcLines {
+        cc1_mv: 920,
+        cc2_mv: 920,
+    };
+    assert_eq!(both.orientation(), Orientation::Both);
+}
@@ -0,0 +1,67 @@
+use crate::conversion::{Averaging, Conversion, ConversionTime, AVERAGINGS, CONVERSION_TIMES};
+
+#[test]
+fn default_reads_about_every_two_seconds() {
+    let conversion = Conversion::DEFAULT;
+
+    // 128 times 8.244 ms of the shunt and 8.244 ms of the bus voltage.
+    assert_eq!(conversion.period_us(), 2_110_464);
+    assert_eq!(conversion.rate_millihertz(), 473);
+    assert_eq!(conversion.bandwidth_millihertz(), 209);
+}
+
+#[test]
+fn fastest_reads_every_280_us() {
+    let conversion = Conversion {
+        averaging: Averaging::X1,
+        time: ConversionTime::Us140,
+    };
+
+    assert_eq!(conversion.period_us(), 280);
+    assert_eq!(conversion.rate_millihertz(), 3_571_428);
+}
+
+#[test]
+fn slowest_fits_the_units() {
+    let conversion = Conversion {
+        averaging: Averaging::X1024,
+        time: ConversionTime::Us8244,
+    };
+
+    assert_eq!(conversion.period_us(), 16_883_712);
+    assert_eq!(conversion.rate_millihertz(), 59);
+}
+
+#[test]
+fn steps_wrap_around() {
+    assert_eq!(Averaging::X128.step(true), Averaging::X256);
+    assert_eq!(Averaging::X128.step(false), Averaging::X64);
+    assert_eq!(Averaging::X1024.step(true), Averaging::X1);
+    assert_eq!(Averaging::X1.step(false), Averaging::X1024);
+
+    assert_eq!(ConversionTime::Us1100.step(true), ConversionTime::Us2116);
+    assert_eq!(ConversionTime::Us8244.step(true), ConversionTime::Us140);
+    assert_eq!(ConversionTime::Us140.step(false), ConversionTime::Us8244);
+}
+
+#[test]
+fn steps_go_through_every_option_in_order() {
+    for pair in AVERAGINGS.windows(2) {
+        assert_eq!(pair[0].step(true), pair[1]);
+        assert!(pair[0].count() < pair[1].count());
+    }
+    for pair in CONVERSION_TIMES.windows(2) {
+        assert_eq!(pair[0].step(true), pair[1]);
+        assert!(pair[0].micros() < pair[1].micros());
+    }
+}
+
+#[test]
+fn parses_the_chip_values_only() {
+    assert_eq!(Averaging::parse("64"), Some(Averaging::X64));
+    assert_eq!(Averaging::parse("100"), None);
+    assert_eq!(Averaging::parse("x"), None);
+
+    assert_eq!(ConversionTime::parse("588"), Some(ConversionTime::Us588));
+    assert_eq!(ConversionTime::parse("500"), None);
+}
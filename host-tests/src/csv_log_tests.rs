@@ -0,0 +1,75 @@
+use crate::{
+    csv_log::{file_index, file_name, line, LogFile, Record, MAX_FILE_BYTES},
+    types::PowerInfo,
+    units,
+};
+
+#[test]
+fn sample_line() {
+    let power = PowerInfo {
+        volts: units::from_milli(5_012),
+        amps: units::from_milli(1_204),
+        watts: units::from_milli(6_034),
+    };
+
+    assert_eq!(
+        line("+12.345", &Record::Sample(power)),
+        "+12.345,5.012,1.204,6.034,\r\n"
+    );
+}
+
+#[test]
+fn event_lines() {
+    assert_eq!(
+        line("+1.000", &Record::Trip("over current")),
+        "+1.000,,,,trip over current\r\n"
+    );
+    assert_eq!(line("+2.000", &Record::Recover), "+2.000,,,,recover\r\n");
+    assert_eq!(
+        line("+3.000", &Record::Output(true)),
+        "+3.000,,,,output on\r\n"
+    );
+    assert_eq!(
+        line("+4.000", &Record::Output(false)),
+        "+4.000,,,,output off\r\n"
+    );
+    assert_eq!(
+        line("+5.000", &Record::Alarm("dI/dt", units::from_milli(-2_500))),
+        "+5.000,,,,alarm dI/dt -2.500\r\n"
+    );
+    assert_eq!(
+        line("+0.000", &Record::Reset("watchdog")),
+        "+0.000,,,,reset watchdog\r\n"
+    );
+}
+
+#[test]
+fn file_names() {
+    assert_eq!(file_name(0), "LOG0000.CSV");
+    assert_eq!(file_name(42), "LOG0042.CSV");
+
+    assert_eq!(file_index("LOG0042.CSV"), Some(42));
+    assert_eq!(file_index("LOG9999.CSV"), Some(9999));
+    assert_eq!(file_index("LOG42.CSV"), None);
+    assert_eq!(file_index("LOG00A2.CSV"), None);
+    assert_eq!(file_index("LOG0042.TXT"), None);
+    assert_eq!(file_index("README.TXT"), None);
+}
+
+#[test]
+fn rotation() {
+    assert_eq!(LogFile::after(None).index, 0);
+    assert_eq!(LogFile::after(Some(7)).index, 8);
+    assert_eq!(LogFile::after(Some(9999)).index, 0);
+
+    let mut file = LogFile::after(None);
+    assert!(file.fits(MAX_FILE_BYTES as usize + 1));
+
+    file.bytes = MAX_FILE_BYTES - 10;
+    assert!(file.fits(10));
+    assert!(!file.fits(11));
+
+    let next = file.next();
+    assert_eq!(next.index, 1);
+    assert_eq!(next.bytes, 0);
+}
@@ -0,0 +1,49 @@
+use crate::data_lines::{DataLines, Signature};
+
+fn signature(plus_mv: u16, minus_mv: u16) -> Signature {
+    Signature::of(DataLines { plus_mv, minus_mv })
+}
+
+#[test]
+fn bc12_and_quick_charge() {
+    assert_eq!(signature(0, 0), Signature::Idle);
+    assert_eq!(signature(600, 590), Signature::Dcp);
+    assert_eq!(signature(600, 0), Signature::Qc(5));
+    assert_eq!(signature(3_300, 600), Signature::Qc(9));
+    assert_eq!(signature(3_300, 3_300), Signature::Qc(20));
+    assert_eq!(signature(600, 3_300), Signature::QcContinuous);
+}
+
+#[test]
+fn apple_dividers() {
+    assert_eq!(signature(2_000, 2_000), Signature::Apple(500));
+    assert_eq!(signature(2_000, 2_700), Signature::Apple(1_000));
+    assert_eq!(signature(2_700, 2_000), Signature::Apple(2_100));
+    assert_eq!(signature(2_700, 2_700), Signature::Apple(2_400));
+}
+
+#[test]
+fn levels_between_bands() {
+    assert_eq!(signature(1_300, 1_300), Signature::Unknown);
+    assert_eq!(signature(2_000, 0), Signature::Unknown);
+    assert_eq!(signature(3_300, 0), Signature::Unknown);
+}
+
+#[test]
+fn names_fit_the_page() {
+    for signature in [
+        Signature::Idle,
+        Signature::Dcp,
+        Signature::Apple(500),
+        Signature::Apple(1_000),
+        Signature::Apple(2_100),
+        Signature::Apple(2_400),
+        Signature::Qc(5),
+        Signature::Qc(9),
+        Signature::Qc(20),
+        Signature::QcContinuous,
+        Signature::Unknown,
+    ] {
+        assert!(signature.as_str().len() <= 10);
+    }
+}
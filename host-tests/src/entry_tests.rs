@@ -0,0 +1,61 @@
+use crate::entry::NumberEntry;
+
+fn ocp(milli: i32) -> NumberEntry {
+    NumberEntry::new(milli, 50, 10_000)
+}
+
+#[test]
+fn starts_at_the_value_rounded_to_hundredths() {
+    assert_eq!(ocp(1_234).digits(), [0, 1, 2, 3]);
+    assert_eq!(ocp(1_235).digits(), [0, 1, 2, 4]);
+    assert_eq!(ocp(0).digits(), [0, 0, 0, 0]);
+    assert_eq!(ocp(0).cursor(), 0);
+}
+
+#[test]
+fn enters_a_value_off_the_old_steps() {
+    let mut entry = ocp(0);
+
+    entry.next_digit();
+    entry.increment();
+    entry.next_digit();
+    entry.increment();
+    entry.increment();
+
+    assert_eq!(entry.digits(), [0, 1, 2, 0]);
+    assert_eq!(entry.milli(), 1_200);
+}
+
+#[test]
+fn digits_wrap_without_carrying() {
+    let mut entry = ocp(9_990);
+
+    entry.next_digit();
+    entry.increment();
+
+    assert_eq!(entry.digits(), [0, 0, 9, 9]);
+}
+
+#[test]
+fn cursor_wraps_to_the_tens() {
+    let mut entry = ocp(0);
+
+    for _ in 0..4 {
+        entry.next_digit();
+    }
+
+    assert_eq!(entry.cursor(), 0);
+}
+
+#[test]
+fn value_is_clamped_when_taken() {
+    assert_eq!(ocp(10).milli(), 50);
+    assert_eq!(ocp(12_340).milli(), 10_000);
+    assert_eq!(ocp(10_000).milli(), 10_000);
+}
+
+#[test]
+fn zeros_turn_it_off() {
+    assert_eq!(ocp(0).milli(), 0);
+    assert_eq!(ocp(4).milli(), 0);
+}
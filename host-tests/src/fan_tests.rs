@@ -0,0 +1,86 @@
+use embassy_time::Duration;
+
+use crate::{
+    fan::{FanControl, FanCurve, FAN_KICK},
+    mock_time::{self, Instant},
+};
+
+const CURVE: FanCurve = FanCurve {
+    start_c: 40,
+    full_c: 60,
+    min_percent: 20,
+};
+
+fn at(ms: u64) -> Instant {
+    mock_time::set(Duration::from_millis(ms));
+    Instant::now()
+}
+
+/// Past the kick of a fan started at 0 ms.
+fn running(fan: &mut FanControl) {
+    fan.update(&CURVE, CURVE.start_c, at(0));
+}
+
+#[test]
+fn stopped_below_the_start() {
+    let mut fan = FanControl::new();
+    let status = fan.update(&CURVE, 39, at(0));
+
+    assert_eq!(status.percent, 0);
+    assert!(!status.kicking);
+}
+
+#[test]
+fn starts_with_a_kick() {
+    let mut fan = FanControl::new();
+
+    let status = fan.update(&CURVE, 40, at(1_000));
+    assert_eq!(status.percent, 100);
+    assert!(status.kicking);
+
+    let after = 1_000 + FAN_KICK.as_millis();
+    let status = fan.update(&CURVE, 40, at(after));
+    assert_eq!(status.percent, 20);
+    assert!(!status.kicking);
+}
+
+#[test]
+fn duty_follows_the_curve() {
+    let mut fan = FanControl::new();
+    running(&mut fan);
+
+    let later = FAN_KICK.as_millis();
+    assert_eq!(fan.update(&CURVE, 50, at(later)).percent, 60);
+    assert_eq!(fan.update(&CURVE, 60, at(later)).percent, 100);
+    assert_eq!(fan.update(&CURVE, 85, at(later)).percent, 100);
+}
+
+#[test]
+fn keeps_running_within_the_hysteresis() {
+    let mut fan = FanControl::new();
+    running(&mut fan);
+
+    let later = FAN_KICK.as_millis();
+    assert_eq!(fan.update(&CURVE, 38, at(later)).percent, 20);
+    assert_eq!(fan.update(&CURVE, 36, at(later)).percent, 0);
+
+    // Off again, it waits for the start temperature.
+    assert_eq!(fan.update(&CURVE, 39, at(later)).percent, 0);
+    assert!(fan.update(&CURVE, 40, at(later)).kicking);
+}
+
+#[test]
+fn curve_points_must_be_in_order() {
+    assert!(CURVE.is_valid());
+    assert!(!FanCurve {
+        start_c: 60,
+        full_c: 60,
+        min_percent: 20
+    }
+    .is_valid());
+    assert!(!FanCurve {
+        min_percent: 101,
+        ..CURVE
+    }
+    .is_valid());
+}
@@ -0,0 +1,97 @@
+use crate::{
+    filter::{AnyFilter, CombinedFilter, ExponentialMovingAverage, Filter, FilterKind},
+    units::{from_milli, milli},
+};
+
+/// Feeds `inputs` (in thousandths) and checks each output to within one thousandth.
+fn assert_outputs(filter: &mut impl Filter, inputs: &[i32], expected: &[i32]) {
+    for (input, expected) in inputs.iter().zip(expected) {
+        let output = milli(filter.update(Some(from_milli(*input))).unwrap());
+
+        assert!(
+            (output - expected).abs() <= 1,
+            "input {}: got {}, expected {}",
+            input,
+            output,
+            expected
+        );
+    }
+}
+
+#[test]
+fn off_passes_through() {
+    let mut filter = AnyFilter::new(FilterKind::Off);
+
+    assert_outputs(&mut filter, &[5_000, 0, 12_345], &[5_000, 0, 12_345]);
+}
+
+#[test]
+fn ema_starts_at_first_reading() {
+    let mut filter = ExponentialMovingAverage::new(20);
+
+    assert_outputs(&mut filter, &[5_000, 5_000], &[5_000, 5_000]);
+}
+
+#[test]
+fn ema_approaches_step() {
+    let mut filter = ExponentialMovingAverage::new(20);
+
+    assert_outputs(&mut filter, &[0, 1_000, 1_000, 1_000], &[0, 200, 360, 488]);
+}
+
+#[test]
+fn ema_reset_forgets_history() {
+    let mut filter = ExponentialMovingAverage::new(20);
+
+    assert_outputs(&mut filter, &[0, 0], &[0, 0]);
+    filter.reset();
+    assert_outputs(&mut filter, &[9_000], &[9_000]);
+}
+
+#[test]
+fn combined_drops_spike() {
+    let mut filter = CombinedFilter::new(20);
+
+    assert_outputs(
+        &mut filter,
+        &[1_000, 1_000, 1_000, 9_000, 1_000],
+        &[1_000, 1_000, 1_000, 1_000, 1_000],
+    );
+}
+
+#[test]
+fn combined_follows_lasting_step() {
+    let mut filter = CombinedFilter::new(20);
+
+    // The median moves on the second reading after the step, then the average takes over.
+    assert_outputs(
+        &mut filter,
+        &[1_000, 1_000, 1_000, 2_000, 2_000],
+        &[1_000, 1_000, 1_000, 1_000, 1_200],
+    );
+}
+
+#[test]
+fn failed_reading_is_not_averaged_in() {
+    let mut filter = CombinedFilter::new(20);
+
+    assert_outputs(&mut filter, &[1_000, 1_000, 1_000], &[1_000, 1_000, 1_000]);
+    assert_eq!(filter.update(None), None);
+    // Neither the median window nor the average remember the readings before the gap.
+    assert_outputs(&mut filter, &[5_000, 5_000], &[5_000, 5_000]);
+}
+
+#[test]
+fn any_filter_selects_implementation() {
+    let mut filter = AnyFilter::new(FilterKind::Ema);
+
+    assert_outputs(&mut filter, &[0, 1_000], &[0, 200]);
+}
+
+#[test]
+fn filter_kind_round_trip() {
+    for kind in [FilterKind::Off, FilterKind::Ema, FilterKind::Combined] {
+        assert_eq!(FilterKind::parse(kind.as_str()), Some(kind));
+    }
+    assert_eq!(FilterKind::parse("median"), None);
+}
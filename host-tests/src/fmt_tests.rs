@@ -0,0 +1,63 @@
+use crate::fmt::{fixed, fixed_milli};
+
+#[test]
+fn rounds_to_decimals() {
+    assert_eq!(fixed(5.0123, 3, 0), "5.012");
+    assert_eq!(fixed(5.0126, 3, 0), "5.013");
+    assert_eq!(fixed(0.0004, 3, 0), "0.000");
+    assert_eq!(fixed(3.0, 2, 0), "3.00");
+    assert_eq!(fixed(19.96, 1, 0), "20.0");
+    assert_eq!(fixed(7.6, 0, 0), "8");
+}
+
+#[test]
+fn right_aligns() {
+    assert_eq!(fixed(5.0, 3, 7), "  5.000");
+    assert_eq!(fixed(20.0, 3, 7), " 20.000");
+    assert_eq!(fixed(100.0, 3, 7), "100.000");
+    assert_eq!(fixed(5.0, 1, 4), " 5.0");
+}
+
+#[test]
+fn negative() {
+    assert_eq!(fixed(-0.012, 3, 7), " -0.012");
+    assert_eq!(fixed(-1.5, 1, 0), "-1.5");
+    // Rounds to zero, so no sign.
+    assert_eq!(fixed(-0.0001, 3, 0), "0.000");
+}
+
+#[test]
+fn drops_decimals_to_fit() {
+    assert_eq!(fixed(1234.5678, 3, 7), "1234.57");
+    assert_eq!(fixed(123456.7, 3, 7), " 123457");
+}
+
+#[test]
+fn saturates_when_too_wide() {
+    assert_eq!(fixed(12345678.0, 3, 7), "9999999");
+    assert_eq!(fixed(f64::MAX, 3, 4), "9999");
+}
+
+#[test]
+fn nan_is_zero() {
+    assert_eq!(fixed(f64::NAN, 2, 5), " 0.00");
+}
+
+#[test]
+fn milli_rounds_to_decimals() {
+    assert_eq!(fixed_milli(5_012, 3, 0), "5.012");
+    assert_eq!(fixed_milli(5_016, 2, 0), "5.02");
+    assert_eq!(fixed_milli(19_960, 1, 0), "20.0");
+    assert_eq!(fixed_milli(7_600, 0, 0), "8");
+    assert_eq!(fixed_milli(1_234, 4, 0), "1.2340");
+}
+
+#[test]
+fn milli_matches_float() {
+    assert_eq!(fixed_milli(5_000, 3, 7), "  5.000");
+    assert_eq!(fixed_milli(-12, 3, 7), " -0.012");
+    assert_eq!(fixed_milli(-4, 2, 0), "0.00");
+    assert_eq!(fixed_milli(1_234_568, 3, 7), "1234.57");
+    assert_eq!(fixed_milli(99_999_999, 3, 7), " 100000");
+    assert_eq!(fixed_milli(i32::MIN, 3, 0), "-2147483.648");
+}
@@ -0,0 +1,48 @@
+use embassy_time::Duration;
+
+use crate::{
+    heartbeat::{self, Task},
+    mock_time,
+};
+
+fn wait(ms: u64) {
+    mock_time::advance(Duration::from_millis(ms));
+}
+
+// The heartbeats are one global table, so everything touching them stays in this one test.
+#[test]
+fn gaps_between_beats() {
+    mock_time::set(Duration::from_secs(1));
+    heartbeat::reset();
+
+    let stats = heartbeat::stats(Task::Measure);
+    assert_eq!(stats.beats, 0);
+    assert_eq!(stats.age_ms, None);
+    assert_eq!(stats.mean_gap_ms, 0);
+
+    heartbeat::beat(Task::Measure);
+    wait(100);
+    heartbeat::beat(Task::Measure);
+    wait(300);
+    heartbeat::beat(Task::Measure);
+    wait(50);
+
+    let stats = heartbeat::stats(Task::Measure);
+    assert_eq!(stats.beats, 3);
+    assert_eq!(stats.age_ms, Some(50));
+    assert_eq!(stats.mean_gap_ms, 200);
+    assert_eq!(stats.max_gap_ms, 300);
+
+    // Other tasks keep their own slots.
+    assert_eq!(heartbeat::stats(Task::Controller).beats, 0);
+
+    heartbeat::reset();
+    heartbeat::beat(Task::Measure);
+    wait(20);
+    heartbeat::beat(Task::Measure);
+
+    let stats = heartbeat::stats(Task::Measure);
+    assert_eq!(stats.beats, 2);
+    assert_eq!(stats.mean_gap_ms, 20);
+    assert_eq!(stats.max_gap_ms, 20);
+}
@@ -0,0 +1,49 @@
+use crate::{
+    help::{gestures, line, title, HELP_WIDTH},
+    types::{AboutView, ABOUT_VIEWS},
+};
+
+#[test]
+fn up_and_down_go_round_the_views() {
+    for view in ABOUT_VIEWS {
+        assert_eq!(view.next().prev(), view);
+        assert_eq!(view.prev().next(), view);
+    }
+    assert_eq!(AboutView::Anywhere.next(), AboutView::Info);
+    assert_eq!(AboutView::Info.prev(), AboutView::Anywhere);
+}
+
+#[test]
+fn every_cheat_sheet_lists_gestures() {
+    assert!(gestures(AboutView::Info).is_empty());
+
+    for view in &ABOUT_VIEWS[1..] {
+        assert!(!gestures(*view).is_empty(), "{view:?}");
+    }
+}
+
+#[test]
+fn lines_fit_next_to_the_settings_list() {
+    // Nine lines of 14 pixels below the heading, in the 172 rows of the panel.
+    for view in ABOUT_VIEWS {
+        assert!(title(view).len() <= 9, "{view:?}");
+        assert!(gestures(view).len() <= 9, "{view:?}");
+
+        for gesture in gestures(view) {
+            assert!(
+                gesture.buttons.len() + gesture.action.len() < HELP_WIDTH,
+                "{}",
+                gesture.action
+            );
+            assert_eq!(line(gesture).len(), HELP_WIDTH);
+        }
+    }
+}
+
+#[test]
+fn columns_line_up() {
+    let line = line(&gestures(AboutView::Settings)[0]);
+
+    assert!(line.starts_with("Up "));
+    assert_eq!(&line[11..], format!("{:<14}", "next item"));
+}
@@ -0,0 +1,196 @@
+//! Host-side tests for the button handling, the menu state machine, the clock date arithmetic, the
+//! output schedule, the threshold entry, the voltage protection, the OCP derating, the soft fuse,
+//! the slew-rate alarms, the fan curve, the reading filters, the raw and filtered measurement
+//! streams, the replay of recorded readings, the output on-time and switch counters, the relay
+//! sequencing, the power monitor records and address probing, the reset cause decoding and counts,
+//! number formatting, the quantity representation, the task heartbeats, the button cheat-sheet, the
+//! section timing, the stack high-water mark, the telemetry batches, the interval averages, the
+//! INA226 conversion settings, the rolling RMS current, the cable resistance estimate, the cable
+//! and switch drops, the switch dissipation warning, the test session report and its time near the
+//! OCP, the setup wizard steps and record, the triggered current capture, the use of the PD
+//! contract, the change detection on the PD readings, the watts peak hold, the display SPI
+//! chunking, the signature check of firmware updates, the render queue coalescing, the slower small
+//! print while drawing is over budget, the SD card log lines and file rotation, the legacy charger
+//! signatures on D+ and D-, the Type-C CC levels, and the glyph run-length coding.
+//!
+//! The firmware modules are included by path and built with the `mock-time` feature, which swaps
+//! `embassy_time::Instant` for [`mock_time::Instant`] so every test drives its own clock. Run them
+//! on the host, as `.cargo/config.toml` defaults to the MCU target:
+//!
+//! ```sh
+//! cargo test -p host-tests --target x86_64-unknown-linux-gnu
+//! cargo test -p host-tests --target x86_64-unknown-linux-gnu --features fixed-point
+//! ```
+
+// Only the tests use the included modules.
+#![allow(dead_code)]
+
+#[path = "../../src/average.rs"]
+mod average;
+#[path = "../../src/button.rs"]
+mod button;
+#[path = "../../src/cable.rs"]
+mod cable;
+#[path = "../../src/calendar.rs"]
+mod calendar;
+#[path = "../../src/capture.rs"]
+mod capture;
+#[path = "../../src/cc_lines.rs"]
+mod cc_lines;
+#[path = "../../src/conversion.rs"]
+mod conversion;
+#[path = "../../src/csv_log.rs"]
+mod csv_log;
+#[path = "../../src/data_lines.rs"]
+mod data_lines;
+#[path = "../../src/entry.rs"]
+mod entry;
+#[path = "../../src/fan.rs"]
+mod fan;
+#[path = "../../src/filter.rs"]
+mod filter;
+#[path = "../../src/fmt.rs"]
+mod fmt;
+#[path = "../../src/heartbeat.rs"]
+mod heartbeat;
+#[path = "../../src/help.rs"]
+mod help;
+#[path = "../../src/measure.rs"]
+mod measure;
+#[path = "../../src/menu.rs"]
+mod menu;
+#[path = "../../src/output_stats.rs"]
+mod output_stats;
+#[path = "../../src/pd_cache.rs"]
+mod pd_cache;
+#[path = "../../src/power_monitor.rs"]
+mod power_monitor;
+#[path = "../../src/protection.rs"]
+mod protection;
+#[path = "../../src/rails.rs"]
+mod rails;
+#[path = "../../src/relay.rs"]
+mod relay;
+#[path = "../../src/render.rs"]
+mod render;
+#[path = "../../src/render_budget.rs"]
+mod render_budget;
+#[path = "../../src/replay.rs"]
+mod replay;
+#[path = "../../src/reset_cause.rs"]
+mod reset_cause;
+#[path = "../../src/rle.rs"]
+mod rle;
+#[path = "../../src/rms.rs"]
+mod rms;
+#[path = "../../src/schedule.rs"]
+mod schedule;
+#[path = "../../src/session.rs"]
+mod session;
+#[path = "../../src/setup.rs"]
+mod setup;
+#[path = "../../src/signature.rs"]
+mod signature;
+#[path = "../../src/slew.rs"]
+mod slew;
+#[path = "../../src/spi_bus.rs"]
+mod spi_bus;
+#[path = "../../src/stack.rs"]
+mod stack;
+#[path = "../../src/telemetry.rs"]
+mod telemetry;
+#[path = "../../src/timing.rs"]
+mod timing;
+#[path = "../../src/types.rs"]
+mod types;
+#[path = "../../src/units.rs"]
+mod units;
+#[path = "../../src/utilization.rs"]
+mod utilization;
+#[path = "../../src/watts.rs"]
+mod watts;
+
+mod mock_time;
+
+#[cfg(test)]
+mod average_tests;
+#[cfg(test)]
+mod button_tests;
+#[cfg(test)]
+mod cable_tests;
+#[cfg(test)]
+mod calendar_tests;
+#[cfg(test)]
+mod capture_tests;
+#[cfg(test)]
+mod cc_lines_tests;
+#[cfg(test)]
+mod conversion_tests;
+#[cfg(test)]
+mod csv_log_tests;
+#[cfg(test)]
+mod data_lines_tests;
+#[cfg(test)]
+mod entry_tests;
+#[cfg(test)]
+mod fan_tests;
+#[cfg(test)]
+mod filter_tests;
+#[cfg(test)]
+mod fmt_tests;
+#[cfg(test)]
+mod heartbeat_tests;
+#[cfg(test)]
+mod help_tests;
+#[cfg(test)]
+mod measure_tests;
+#[cfg(test)]
+mod menu_tests;
+#[cfg(test)]
+mod output_stats_tests;
+#[cfg(test)]
+mod pd_cache_tests;
+#[cfg(test)]
+mod power_monitor_tests;
+#[cfg(test)]
+mod protection_tests;
+#[cfg(test)]
+mod rails_tests;
+#[cfg(test)]
+mod relay_tests;
+#[cfg(test)]
+mod render_budget_tests;
+#[cfg(test)]
+mod render_tests;
+#[cfg(test)]
+mod replay_tests;
+#[cfg(test)]
+mod reset_cause_tests;
+#[cfg(test)]
+mod rle_tests;
+#[cfg(test)]
+mod rms_tests;
+#[cfg(test)]
+mod schedule_tests;
+#[cfg(test)]
+mod session_tests;
+#[cfg(test)]
+mod setup_tests;
+#[cfg(test)]
+mod signature_tests;
+#[cfg(test)]
+mod slew_tests;
+#[cfg(test)]
+mod spi_bus_tests;
+#[cfg(test)]
+mod stack_tests;
+#[cfg(test)]
+mod telemetry_tests;
+#[cfg(test)]
+mod timing_tests;
+#[cfg(test)]
+mod units_tests;
+#[cfg(test)]
+mod utilization_tests;
+#[cfg(test)]
+mod watts_tests;
@@ -0,0 +1,67 @@
+use crate::{
+    filter::FilterKind,
+    measure::{Reading, ReadingFilters},
+    types::PowerInfo,
+    units::{from_milli, milli},
+};
+
+fn reading(volts: Option<i32>, amps: Option<i32>, watts: Option<i32>) -> Reading {
+    Reading {
+        volts: volts.map(from_milli),
+        amps: amps.map(from_milli),
+        watts: watts.map(from_milli),
+    }
+}
+
+#[test]
+fn raw_stream_is_not_smoothed() {
+    let mut filters = ReadingFilters::new(FilterKind::Ema);
+
+    let raw = reading(Some(5_000), Some(0), Some(0));
+    filters.update(&raw);
+
+    let raw = reading(Some(5_000), Some(3_000), Some(15_000));
+    let filtered = filters.update(&raw);
+
+    // A step that protection must see at once is only a fifth of the way in the filtered stream.
+    assert_eq!(raw.amps.map(milli), Some(3_000));
+    assert_eq!(filtered.amps.map(milli), Some(600));
+    assert_eq!(filtered.watts.map(milli), Some(3_000));
+}
+
+#[test]
+fn failed_read_is_missing_from_both_streams() {
+    let mut filters = ReadingFilters::new(FilterKind::Ema);
+
+    filters.update(&reading(Some(5_000), Some(1_000), Some(5_000)));
+    let filtered = filters.update(&reading(Some(5_000), None, Some(5_000)));
+
+    assert_eq!(filtered.amps, None);
+    assert!(!filtered.is_complete());
+}
+
+#[test]
+fn failed_read_starts_the_filter_over() {
+    let mut filters = ReadingFilters::new(FilterKind::Ema);
+
+    filters.update(&reading(Some(5_000), Some(1_000), Some(5_000)));
+    filters.update(&reading(Some(5_000), None, Some(5_000)));
+    let filtered = filters.update(&reading(Some(5_000), Some(2_000), Some(10_000)));
+
+    assert_eq!(filtered.amps.map(milli), Some(2_000));
+}
+
+#[test]
+fn merge_keeps_the_last_good_values() {
+    let mut power = PowerInfo {
+        volts: from_milli(5_000),
+        amps: from_milli(1_000),
+        watts: from_milli(5_000),
+    };
+
+    reading(Some(9_000), None, Some(9_000)).merge_into(&mut power);
+
+    assert_eq!(milli(power.volts), 9_000);
+    assert_eq!(milli(power.amps), 1_000);
+    assert_eq!(milli(power.watts), 9_000);
+}
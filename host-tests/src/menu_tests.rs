@@ -0,0 +1,815 @@
+use embassy_time::Duration;
+use husb238::SrcPdo;
+
+use crate::{
+    button::ButtonState,
+    menu::{breadcrumb, next_page, scroll_thumb, step_timeout, BtnsState, Gestures, MIN_THUMB},
+    mock_time::{self, Instant},
+    setup::SetupStep,
+    types::{
+        nearest_pdo, AboutView, ClockField, DiagnosticsView, DisplayItem, LimitField,
+        MeasurementItem, Page, SettingItem,
+    },
+};
+
+const ALL_BTNS: [BtnsState; 9] = [
+    BtnsState::Up,
+    BtnsState::Down,
+    BtnsState::UpLong,
+    BtnsState::DownLong,
+    BtnsState::UpDbk,
+    BtnsState::DownDbk,
+    BtnsState::UpAndDown,
+    BtnsState::UpAndDownLong,
+    BtnsState::UpReleased,
+];
+
+const AVAILABLE: [SrcPdo; 3] = [SrcPdo::_5v, SrcPdo::_9v, SrcPdo::_20v];
+
+fn at(ms: u64) -> Instant {
+    mock_time::set(Duration::from_millis(ms));
+    Instant::now()
+}
+
+fn next(page: Page, btns: BtnsState) -> Page {
+    next_page(page, btns, SrcPdo::_9v, &AVAILABLE)
+}
+
+/// Asserts that `page` moves to the given page on each listed gesture and stays on all others.
+fn assert_transitions(page: Page, transitions: &[(BtnsState, Page)]) {
+    for btns in ALL_BTNS {
+        let expected = transitions
+            .iter()
+            .find(|(b, _)| *b == btns)
+            .map(|(_, p)| *p)
+            .unwrap_or(page);
+
+        assert_eq!(next(page, btns), expected, "{:?} on {:?}", btns, page);
+    }
+}
+
+#[test]
+fn gesture_single_buttons() {
+    let mut gestures = Gestures::new();
+
+    assert_eq!(gestures.update(true, ButtonState::Pressed), None);
+    assert_eq!(
+        gestures.update(true, ButtonState::Click(at(1000))),
+        Some(BtnsState::Up)
+    );
+
+    assert_eq!(gestures.update(false, ButtonState::Pressed), None);
+    assert_eq!(
+        gestures.update(false, ButtonState::Click(at(2000))),
+        Some(BtnsState::Down)
+    );
+
+    assert_eq!(gestures.update(true, ButtonState::Pressed), None);
+    assert_eq!(
+        gestures.update(true, ButtonState::LongPressed(at(3000))),
+        Some(BtnsState::UpLong)
+    );
+    assert_eq!(
+        gestures.update(true, ButtonState::Released),
+        Some(BtnsState::UpReleased)
+    );
+
+    assert_eq!(gestures.update(false, ButtonState::Pressed), None);
+    assert_eq!(
+        gestures.update(false, ButtonState::LongPressed(at(4000))),
+        Some(BtnsState::DownLong)
+    );
+    assert_eq!(gestures.update(false, ButtonState::Released), None);
+
+    assert_eq!(
+        gestures.update(true, ButtonState::DoubleClick(at(5000))),
+        Some(BtnsState::UpDbk)
+    );
+    assert_eq!(
+        gestures.update(false, ButtonState::DoubleClick(at(6000))),
+        Some(BtnsState::DownDbk)
+    );
+}
+
+#[test]
+fn gesture_release_only_after_long_press() {
+    let mut gestures = Gestures::new();
+
+    // A bounce is released without a click.
+    assert_eq!(gestures.update(true, ButtonState::Pressed), None);
+    assert_eq!(gestures.update(true, ButtonState::Released), None);
+
+    assert_eq!(gestures.update(false, ButtonState::Pressed), None);
+    assert_eq!(
+        gestures.update(false, ButtonState::LongPressed(at(1000))),
+        Some(BtnsState::DownLong)
+    );
+    assert_eq!(gestures.update(false, ButtonState::Released), None);
+}
+
+#[test]
+fn gesture_both_clicked() {
+    let mut gestures = Gestures::new();
+
+    assert_eq!(gestures.update(true, ButtonState::Pressed), None);
+    assert_eq!(gestures.update(false, ButtonState::Pressed), None);
+    assert_eq!(gestures.update(true, ButtonState::Click(at(1000))), None);
+    assert_eq!(
+        gestures.update(false, ButtonState::Click(at(1050))),
+        Some(BtnsState::UpAndDown)
+    );
+
+    // A later click of one button is a click of its own again.
+    assert_eq!(gestures.update(true, ButtonState::Pressed), None);
+    assert_eq!(
+        gestures.update(true, ButtonState::Click(at(2000))),
+        Some(BtnsState::Up)
+    );
+}
+
+#[test]
+fn gesture_both_clicked_too_far_apart() {
+    let mut gestures = Gestures::new();
+
+    assert_eq!(
+        gestures.update(true, ButtonState::Click(at(1000))),
+        Some(BtnsState::Up)
+    );
+    assert_eq!(
+        gestures.update(false, ButtonState::Click(at(1150))),
+        Some(BtnsState::Down)
+    );
+}
+
+#[test]
+fn gesture_both_long_pressed() {
+    let mut gestures = Gestures::new();
+
+    assert_eq!(gestures.update(true, ButtonState::Pressed), None);
+    assert_eq!(gestures.update(false, ButtonState::Pressed), None);
+    assert_eq!(
+        gestures.update(true, ButtonState::LongPressed(at(1000))),
+        None
+    );
+    assert_eq!(
+        gestures.update(false, ButtonState::LongPressed(at(1010))),
+        Some(BtnsState::UpAndDownLong)
+    );
+
+    // Letting go afterwards completes nothing.
+    assert_eq!(gestures.update(true, ButtonState::Released), None);
+    assert_eq!(gestures.update(false, ButtonState::Released), None);
+}
+
+#[test]
+fn gesture_waits_while_other_button_held() {
+    let mut gestures = Gestures::new();
+
+    assert_eq!(gestures.update(false, ButtonState::Pressed), None);
+    assert_eq!(
+        gestures.update(false, ButtonState::LongPressed(at(1000))),
+        Some(BtnsState::DownLong)
+    );
+    assert_eq!(gestures.update(true, ButtonState::Click(at(1500))), None);
+}
+
+#[test]
+fn monitor_transitions() {
+    assert_transitions(
+        Page::Monitor,
+        &[
+            (BtnsState::UpAndDown, Page::OCP),
+            (
+                BtnsState::UpAndDownLong,
+                Page::Setting(SettingItem::Voltage),
+            ),
+        ],
+    );
+}
+
+#[test]
+fn setting_transitions() {
+    assert_transitions(
+        Page::Setting(SettingItem::Voltage),
+        &[
+            (BtnsState::Up, Page::Setting(SettingItem::UVP)),
+            (BtnsState::Down, Page::Setting(SettingItem::About)),
+            (BtnsState::UpAndDown, Page::Voltage(SrcPdo::_9v)),
+            (BtnsState::UpAndDownLong, Page::Monitor),
+        ],
+    );
+    assert_transitions(
+        Page::Setting(SettingItem::UVP),
+        &[
+            (BtnsState::Up, Page::Setting(SettingItem::OVP)),
+            (BtnsState::Down, Page::Setting(SettingItem::Voltage)),
+            (BtnsState::UpAndDown, Page::UVP(LimitField::Trip)),
+            (BtnsState::UpAndDownLong, Page::Monitor),
+        ],
+    );
+    assert_transitions(
+        Page::Setting(SettingItem::OVP),
+        &[
+            (BtnsState::Up, Page::Setting(SettingItem::OCP)),
+            (BtnsState::Down, Page::Setting(SettingItem::UVP)),
+            (BtnsState::UpAndDown, Page::OVP(LimitField::Trip)),
+            (BtnsState::UpAndDownLong, Page::Monitor),
+        ],
+    );
+    assert_transitions(
+        Page::Setting(SettingItem::OCP),
+        &[
+            (BtnsState::Up, Page::Setting(SettingItem::Output)),
+            (BtnsState::Down, Page::Setting(SettingItem::OVP)),
+            (BtnsState::UpAndDown, Page::OCP),
+            (BtnsState::UpAndDownLong, Page::Monitor),
+        ],
+    );
+    assert_transitions(
+        Page::Setting(SettingItem::Output),
+        &[
+            (BtnsState::Up, Page::Setting(SettingItem::Watts)),
+            (BtnsState::Down, Page::Setting(SettingItem::OCP)),
+            (BtnsState::UpAndDown, Page::Output),
+            (BtnsState::UpAndDownLong, Page::Monitor),
+        ],
+    );
+    assert_transitions(
+        Page::Setting(SettingItem::Watts),
+        &[
+            (BtnsState::Up, Page::Setting(SettingItem::Average)),
+            (BtnsState::Down, Page::Setting(SettingItem::Output)),
+            (BtnsState::UpAndDown, Page::Watts),
+            (BtnsState::UpAndDownLong, Page::Monitor),
+        ],
+    );
+    assert_transitions(
+        Page::Setting(SettingItem::Average),
+        &[
+            (BtnsState::Up, Page::Setting(SettingItem::Measurement)),
+            (BtnsState::Down, Page::Setting(SettingItem::Watts)),
+            (BtnsState::UpAndDown, Page::Average),
+            (BtnsState::UpAndDownLong, Page::Monitor),
+        ],
+    );
+    assert_transitions(
+        Page::Setting(SettingItem::Measurement),
+        &[
+            (BtnsState::Up, Page::Setting(SettingItem::Cable)),
+            (BtnsState::Down, Page::Setting(SettingItem::Average)),
+            (
+                BtnsState::UpAndDown,
+                Page::Measurement(MeasurementItem::Averaging),
+            ),
+            (BtnsState::UpAndDownLong, Page::Monitor),
+        ],
+    );
+    assert_transitions(
+        Page::Setting(SettingItem::Cable),
+        &[
+            (BtnsState::Up, Page::Setting(SettingItem::Capture)),
+            (BtnsState::Down, Page::Setting(SettingItem::Measurement)),
+            (BtnsState::UpAndDown, Page::Cable),
+            (BtnsState::UpAndDownLong, Page::Monitor),
+        ],
+    );
+    assert_transitions(
+        Page::Setting(SettingItem::Capture),
+        &[
+            (BtnsState::Up, Page::Setting(SettingItem::Session)),
+            (BtnsState::Down, Page::Setting(SettingItem::Cable)),
+            (BtnsState::UpAndDown, Page::Capture),
+            (BtnsState::UpAndDownLong, Page::Monitor),
+        ],
+    );
+    assert_transitions(
+        Page::Setting(SettingItem::Session),
+        &[
+            (BtnsState::Up, Page::Setting(SettingItem::Storage)),
+            (BtnsState::Down, Page::Setting(SettingItem::Capture)),
+            (BtnsState::UpAndDown, Page::Session),
+            (BtnsState::UpAndDownLong, Page::Monitor),
+        ],
+    );
+    assert_transitions(
+        Page::Setting(SettingItem::Storage),
+        &[
+            (BtnsState::Up, Page::Setting(SettingItem::Diagnostics)),
+            (BtnsState::Down, Page::Setting(SettingItem::Session)),
+            (BtnsState::UpAndDown, Page::Storage),
+            (BtnsState::UpAndDownLong, Page::Monitor),
+        ],
+    );
+    assert_transitions(
+        Page::Setting(SettingItem::Diagnostics),
+        &[
+            (BtnsState::Up, Page::Setting(SettingItem::Display)),
+            (BtnsState::Down, Page::Setting(SettingItem::Storage)),
+            (
+                BtnsState::UpAndDown,
+                Page::Diagnostics(DiagnosticsView::Tasks),
+            ),
+            (BtnsState::UpAndDownLong, Page::Monitor),
+        ],
+    );
+    assert_transitions(
+        Page::Setting(SettingItem::Display),
+        &[
+            (BtnsState::Up, Page::Setting(SettingItem::Clock)),
+            (BtnsState::Down, Page::Setting(SettingItem::Diagnostics)),
+            (BtnsState::UpAndDown, Page::Display(DisplayItem::Rotation)),
+            (BtnsState::UpAndDownLong, Page::Monitor),
+        ],
+    );
+    assert_transitions(
+        Page::Setting(SettingItem::Clock),
+        &[
+            (BtnsState::Up, Page::Setting(SettingItem::About)),
+            (BtnsState::Down, Page::Setting(SettingItem::Display)),
+            (BtnsState::UpAndDown, Page::Clock(ClockField::Year)),
+            (BtnsState::UpAndDownLong, Page::Monitor),
+        ],
+    );
+    assert_transitions(
+        Page::Setting(SettingItem::About),
+        &[
+            (BtnsState::Up, Page::Setting(SettingItem::Voltage)),
+            (BtnsState::Down, Page::Setting(SettingItem::Clock)),
+            (BtnsState::UpAndDown, Page::About(AboutView::Info)),
+            (BtnsState::UpAndDownLong, Page::Monitor),
+        ],
+    );
+}
+
+#[test]
+fn voltage_transitions() {
+    assert_transitions(
+        Page::Voltage(SrcPdo::_9v),
+        &[
+            (BtnsState::Up, Page::Voltage(SrcPdo::_20v)),
+            (BtnsState::Down, Page::Voltage(SrcPdo::_5v)),
+            (BtnsState::UpAndDown, Page::Setting(SettingItem::UVP)),
+            (BtnsState::UpAndDownLong, Page::Monitor),
+        ],
+    );
+}
+
+#[test]
+fn voltage_wraps_around() {
+    assert_eq!(
+        next(Page::Voltage(SrcPdo::_20v), BtnsState::Up),
+        Page::Voltage(SrcPdo::_5v)
+    );
+    assert_eq!(
+        next(Page::Voltage(SrcPdo::_5v), BtnsState::Down),
+        Page::Voltage(SrcPdo::_20v)
+    );
+}
+
+#[test]
+fn voltage_not_offered_steps_to_its_neighbours() {
+    assert_eq!(
+        next(Page::Voltage(SrcPdo::_15v), BtnsState::Up),
+        Page::Voltage(SrcPdo::_20v)
+    );
+    assert_eq!(
+        next(Page::Voltage(SrcPdo::_15v), BtnsState::Down),
+        Page::Voltage(SrcPdo::_9v)
+    );
+}
+
+#[test]
+fn voltage_page_opens_on_an_offered_pdo() {
+    assert_eq!(
+        next_page(
+            Page::Setting(SettingItem::Voltage),
+            BtnsState::UpAndDown,
+            SrcPdo::_18v,
+            &AVAILABLE
+        ),
+        Page::Voltage(SrcPdo::_9v)
+    );
+}
+
+#[test]
+fn nearest_pdo_never_raises_the_voltage() {
+    assert_eq!(nearest_pdo(SrcPdo::_9v, &AVAILABLE), SrcPdo::_9v);
+    assert_eq!(nearest_pdo(SrcPdo::_12v, &AVAILABLE), SrcPdo::_9v);
+    assert_eq!(nearest_pdo(SrcPdo::_18v, &AVAILABLE), SrcPdo::_9v);
+    assert_eq!(
+        nearest_pdo(
+            SrcPdo::_15v,
+            &[SrcPdo::_5v, SrcPdo::_9v, SrcPdo::_12v, SrcPdo::_20v]
+        ),
+        SrcPdo::_12v
+    );
+    assert_eq!(
+        nearest_pdo(SrcPdo::_9v, &[SrcPdo::_12v, SrcPdo::_20v]),
+        SrcPdo::_5v
+    );
+    assert_eq!(nearest_pdo(SrcPdo::_20v, &[]), SrcPdo::_5v);
+}
+
+#[test]
+fn uvp_transitions() {
+    assert_transitions(
+        Page::UVP(LimitField::Trip),
+        &[(BtnsState::UpAndDown, Page::UVP(LimitField::Recover))],
+    );
+    assert_transitions(
+        Page::UVP(LimitField::Recover),
+        &[(BtnsState::UpAndDown, Page::Setting(SettingItem::UVP))],
+    );
+}
+
+#[test]
+fn ovp_transitions() {
+    assert_transitions(
+        Page::OVP(LimitField::Trip),
+        &[(BtnsState::UpAndDown, Page::OVP(LimitField::Recover))],
+    );
+    assert_transitions(
+        Page::OVP(LimitField::Recover),
+        &[(BtnsState::UpAndDown, Page::Setting(SettingItem::OVP))],
+    );
+}
+
+#[test]
+fn ocp_transitions() {
+    assert_transitions(
+        Page::OCP,
+        &[(BtnsState::UpAndDown, Page::Setting(SettingItem::OCP))],
+    );
+}
+
+#[test]
+fn output_transitions() {
+    assert_transitions(
+        Page::Output,
+        &[(BtnsState::UpAndDown, Page::Setting(SettingItem::Output))],
+    );
+}
+
+#[test]
+fn watts_transitions() {
+    assert_transitions(
+        Page::Watts,
+        &[(BtnsState::UpAndDown, Page::Setting(SettingItem::Watts))],
+    );
+}
+
+#[test]
+fn average_transitions() {
+    assert_transitions(
+        Page::Average,
+        &[(BtnsState::UpAndDown, Page::Setting(SettingItem::Average))],
+    );
+}
+
+#[test]
+fn cable_transitions() {
+    assert_transitions(
+        Page::Cable,
+        &[(BtnsState::UpAndDown, Page::Setting(SettingItem::Cable))],
+    );
+}
+
+#[test]
+fn capture_transitions() {
+    assert_transitions(
+        Page::Capture,
+        &[(BtnsState::UpAndDown, Page::Setting(SettingItem::Capture))],
+    );
+}
+
+#[test]
+fn session_transitions() {
+    assert_transitions(
+        Page::Session,
+        &[
+            (BtnsState::Up, Page::Session),
+            (BtnsState::Down, Page::Session),
+            (BtnsState::UpAndDown, Page::Setting(SettingItem::Session)),
+        ],
+    );
+}
+
+#[test]
+fn storage_transitions() {
+    assert_transitions(
+        Page::Storage,
+        &[
+            (BtnsState::Up, Page::Storage),
+            (BtnsState::Down, Page::Storage),
+            (BtnsState::UpAndDown, Page::Setting(SettingItem::Storage)),
+        ],
+    );
+}
+
+#[test]
+fn diagnostics_transitions() {
+    let back = Page::Setting(SettingItem::Diagnostics);
+    let tasks = Page::Diagnostics(DiagnosticsView::Tasks);
+    let timing = Page::Diagnostics(DiagnosticsView::Timing);
+    let conversion = Page::Diagnostics(DiagnosticsView::Conversion);
+    let data_lines = Page::Diagnostics(DiagnosticsView::DataLines);
+    let cc_lines = Page::Diagnostics(DiagnosticsView::CcLines);
+    let output = Page::Diagnostics(DiagnosticsView::Output);
+    let rails = Page::Diagnostics(DiagnosticsView::Rails);
+    let resets = Page::Diagnostics(DiagnosticsView::Resets);
+    let memory = Page::Diagnostics(DiagnosticsView::Memory);
+
+    assert_transitions(
+        tasks,
+        &[
+            (BtnsState::Up, timing),
+            (BtnsState::Down, memory),
+            (BtnsState::UpLong, back),
+            (BtnsState::DownLong, back),
+            (BtnsState::UpAndDown, back),
+        ],
+    );
+    assert_transitions(
+        timing,
+        &[
+            (BtnsState::Up, conversion),
+            (BtnsState::Down, tasks),
+            (BtnsState::UpLong, back),
+            (BtnsState::DownLong, back),
+            (BtnsState::UpAndDown, back),
+        ],
+    );
+    assert_transitions(
+        conversion,
+        &[
+            (BtnsState::Up, data_lines),
+            (BtnsState::Down, timing),
+            (BtnsState::UpLong, back),
+            (BtnsState::DownLong, back),
+            (BtnsState::UpAndDown, back),
+        ],
+    );
+    assert_transitions(
+        data_lines,
+        &[
+            (BtnsState::Up, cc_lines),
+            (BtnsState::Down, conversion),
+            (BtnsState::UpLong, back),
+            (BtnsState::DownLong, back),
+            (BtnsState::UpAndDown, back),
+        ],
+    );
+    assert_transitions(
+        cc_lines,
+        &[
+            (BtnsState::Up, output),
+            (BtnsState::Down, data_lines),
+            (BtnsState::UpLong, back),
+            (BtnsState::DownLong, back),
+            (BtnsState::UpAndDown, back),
+        ],
+    );
+    assert_transitions(
+        output,
+        &[
+            (BtnsState::Up, rails),
+            (BtnsState::Down, cc_lines),
+            (BtnsState::UpLong, back),
+            (BtnsState::DownLong, back),
+            (BtnsState::UpAndDown, back),
+        ],
+    );
+    assert_transitions(
+        rails,
+        &[
+            (BtnsState::Up, resets),
+            (BtnsState::Down, output),
+            (BtnsState::UpLong, back),
+            (BtnsState::DownLong, back),
+            (BtnsState::UpAndDown, back),
+        ],
+    );
+    assert_transitions(
+        resets,
+        &[
+            (BtnsState::Up, memory),
+            (BtnsState::Down, rails),
+            (BtnsState::UpLong, back),
+            (BtnsState::DownLong, back),
+            (BtnsState::UpAndDown, back),
+        ],
+    );
+    assert_transitions(
+        memory,
+        &[
+            (BtnsState::Up, tasks),
+            (BtnsState::Down, resets),
+            (BtnsState::UpLong, back),
+            (BtnsState::DownLong, back),
+            (BtnsState::UpAndDown, back),
+        ],
+    );
+}
+
+#[test]
+fn display_transitions() {
+    let back = Page::Setting(SettingItem::Display);
+
+    assert_transitions(
+        Page::Display(DisplayItem::Rotation),
+        &[
+            (BtnsState::UpAndDown, Page::Display(DisplayItem::Theme)),
+            (BtnsState::UpAndDownLong, back),
+        ],
+    );
+    assert_transitions(
+        Page::Display(DisplayItem::Theme),
+        &[
+            (BtnsState::UpAndDown, Page::Display(DisplayItem::Brightness)),
+            (BtnsState::UpAndDownLong, back),
+        ],
+    );
+    assert_transitions(
+        Page::Display(DisplayItem::Brightness),
+        &[
+            (BtnsState::UpAndDown, Page::Display(DisplayItem::Timeout)),
+            (BtnsState::UpAndDownLong, back),
+        ],
+    );
+    assert_transitions(
+        Page::Display(DisplayItem::Timeout),
+        &[
+            (BtnsState::UpAndDown, back),
+            (BtnsState::UpAndDownLong, back),
+        ],
+    );
+}
+
+#[test]
+fn measurement_transitions() {
+    let back = Page::Setting(SettingItem::Measurement);
+
+    assert_transitions(
+        Page::Measurement(MeasurementItem::Averaging),
+        &[
+            (
+                BtnsState::UpAndDown,
+                Page::Measurement(MeasurementItem::ConversionTime),
+            ),
+            (BtnsState::UpAndDownLong, back),
+        ],
+    );
+    assert_transitions(
+        Page::Measurement(MeasurementItem::ConversionTime),
+        &[
+            (BtnsState::UpAndDown, back),
+            (BtnsState::UpAndDownLong, back),
+        ],
+    );
+}
+
+#[test]
+fn timeout_steps_wrap_around() {
+    assert_eq!(step_timeout(0, true), 10);
+    assert_eq!(step_timeout(30, true), 60);
+    assert_eq!(step_timeout(300, true), 0);
+    assert_eq!(step_timeout(0, false), 300);
+    assert_eq!(step_timeout(30, false), 10);
+}
+
+#[test]
+fn timeout_off_the_list_steps_to_neighbours() {
+    assert_eq!(step_timeout(45, true), 60);
+    assert_eq!(step_timeout(45, false), 30);
+    assert_eq!(step_timeout(1000, true), 0);
+    assert_eq!(step_timeout(1000, false), 300);
+}
+
+#[test]
+fn clock_steps_through_the_fields() {
+    let mut page = Page::Clock(ClockField::Year);
+
+    for field in [
+        ClockField::Month,
+        ClockField::Day,
+        ClockField::Hour,
+        ClockField::Minute,
+    ] {
+        page = next(page, BtnsState::UpAndDown);
+        assert_eq!(page, Page::Clock(field));
+    }
+
+    assert_eq!(
+        next(page, BtnsState::UpAndDown),
+        Page::Setting(SettingItem::Clock)
+    );
+}
+
+#[test]
+fn clock_transitions() {
+    assert_transitions(
+        Page::Clock(ClockField::Day),
+        &[
+            (BtnsState::Up, Page::Clock(ClockField::Day)),
+            (BtnsState::Down, Page::Clock(ClockField::Day)),
+            (BtnsState::UpAndDown, Page::Clock(ClockField::Hour)),
+            (BtnsState::UpAndDownLong, Page::Setting(SettingItem::Clock)),
+        ],
+    );
+}
+
+#[test]
+fn about_transitions() {
+    let back = Page::Setting(SettingItem::About);
+
+    assert_transitions(
+        Page::About(AboutView::Info),
+        &[
+            (BtnsState::Up, Page::About(AboutView::Monitor)),
+            (BtnsState::Down, Page::About(AboutView::Anywhere)),
+            (BtnsState::UpLong, back),
+            (BtnsState::DownLong, back),
+            (BtnsState::UpAndDown, back),
+        ],
+    );
+    assert_transitions(
+        Page::About(AboutView::Anywhere),
+        &[
+            (BtnsState::Up, Page::About(AboutView::Info)),
+            (BtnsState::Down, Page::About(AboutView::Diagnostics)),
+            (BtnsState::UpLong, back),
+            (BtnsState::DownLong, back),
+            (BtnsState::UpAndDown, back),
+        ],
+    );
+}
+
+#[test]
+fn setup_transitions() {
+    assert_transitions(
+        Page::Setup(SetupStep::Rotation),
+        &[
+            (BtnsState::UpAndDown, Page::Setup(SetupStep::Ocp)),
+            (BtnsState::UpAndDownLong, Page::Monitor),
+        ],
+    );
+    assert_transitions(
+        Page::Setup(SetupStep::Shunt),
+        &[
+            (BtnsState::UpAndDown, Page::Monitor),
+            (BtnsState::UpAndDownLong, Page::Monitor),
+        ],
+    );
+}
+
+#[test]
+fn breadcrumbs_of_setting_pages() {
+    assert_eq!(breadcrumb(Page::Setting(SettingItem::OCP)), ["Settings"]);
+    assert_eq!(breadcrumb(Page::OCP), ["Settings", "OCP"]);
+    assert_eq!(breadcrumb(Page::Voltage(SrcPdo::_9v)), ["Settings", "PDO"]);
+    assert_eq!(
+        breadcrumb(Page::UVP(LimitField::Recover)),
+        ["Settings", "UVP", "Recover"]
+    );
+    assert_eq!(
+        breadcrumb(Page::Display(DisplayItem::Theme)),
+        ["Settings", "Display"]
+    );
+    assert_eq!(
+        breadcrumb(Page::Measurement(MeasurementItem::ConversionTime)),
+        ["Settings", "Measure"]
+    );
+    assert_eq!(
+        breadcrumb(Page::About(AboutView::Tools)),
+        ["Settings", "About"]
+    );
+}
+
+#[test]
+fn full_screen_pages_have_no_breadcrumb() {
+    for page in [
+        Page::Monitor,
+        Page::Cable,
+        Page::Capture,
+        Page::Session,
+        Page::Storage,
+        Page::Diagnostics(DiagnosticsView::Tasks),
+        Page::Setup(SetupStep::Ocp),
+    ] {
+        assert!(breadcrumb(page).is_empty());
+    }
+}
+
+#[test]
+fn scroll_thumb_spans_the_track() {
+    // 14 items on 172 rows: a 12 row thumb, from the top to the bottom.
+    assert_eq!(scroll_thumb(0, 14, 172), (0, 12));
+    assert_eq!(scroll_thumb(13, 14, 172), (160, 12));
+    assert_eq!(scroll_thumb(7, 14, 172), (86, 12));
+
+    // Too many items for the track still leave a visible thumb.
+    assert_eq!(scroll_thumb(0, 100, 148).1, MIN_THUMB);
+    assert_eq!(scroll_thumb(99, 100, 148), (140, MIN_THUMB));
+
+    assert_eq!(scroll_thumb(0, 1, 148), (0, 148));
+}
@@ -0,0 +1,61 @@
+//! Stand-in for `embassy_time::Instant` whose clock only moves when a test says so.
+//!
+//! The clock is per thread. Tests start by [`set`]ting it, as the harness may run several of them
+//! on one thread.
+
+use std::{
+    cell::Cell,
+    ops::{Add, Sub},
+};
+
+use embassy_time::Duration;
+
+thread_local! {
+    static NOW: Cell<u64> = const { Cell::new(0) };
+}
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug, defmt::Format)]
+pub struct Instant {
+    ticks: u64,
+}
+
+impl Instant {
+    pub const MIN: Instant = Instant { ticks: u64::MIN };
+
+    pub fn now() -> Self {
+        Self {
+            ticks: NOW.with(|now| now.get()),
+        }
+    }
+
+    pub fn as_millis(&self) -> u64 {
+        Duration::from_ticks(self.ticks).as_millis()
+    }
+}
+
+impl Add<Duration> for Instant {
+    type Output = Instant;
+
+    fn add(self, rhs: Duration) -> Instant {
+        Self {
+            ticks: self.ticks + rhs.as_ticks(),
+        }
+    }
+}
+
+impl Sub for Instant {
+    type Output = Duration;
+
+    fn sub(self, rhs: Self) -> Duration {
+        Duration::from_ticks(self.ticks - rhs.ticks)
+    }
+}
+
+/// Sets the clock, for a known starting point.
+pub fn set(since_boot: Duration) {
+    NOW.with(|now| now.set(since_boot.as_ticks()));
+}
+
+pub fn advance(duration: Duration) {
+    NOW.with(|now| now.set(now.get() + duration.as_ticks()));
+}
@@ -0,0 +1,144 @@
+use embassy_time::Duration;
+
+use crate::{
+    mock_time::{self, Instant},
+    output_stats::{
+        journal_last, journal_next, OutputStats, OutputStatsTracker, ENTRY_LEN, JOURNAL_ENTRIES,
+        JOURNAL_HEADER, STATS_SAVE_INTERVAL,
+    },
+};
+
+#[test]
+fn stats_round_trip_through_bytes() {
+    let stats = OutputStats {
+        on_secs: 123_456,
+        cycles: 789,
+    };
+
+    assert_eq!(OutputStats::from_bytes(&stats.to_bytes()), stats);
+}
+
+#[test]
+fn counts_cycles_and_whole_seconds_on() {
+    mock_time::set(Duration::from_secs(1));
+    let mut tracker = OutputStatsTracker::new(
+        OutputStats {
+            on_secs: 100,
+            cycles: 2,
+        },
+        Instant::now(),
+    );
+
+    tracker.update(Instant::now(), false);
+    assert_eq!(tracker.stats().cycles, 2);
+
+    tracker.update(Instant::now(), true);
+    mock_time::advance(Duration::from_millis(1_500));
+    tracker.update(Instant::now(), true);
+    assert_eq!(tracker.stats().on_secs, 101);
+
+    // The half second carried over completes the next one.
+    mock_time::advance(Duration::from_millis(600));
+    tracker.update(Instant::now(), false);
+
+    assert_eq!(
+        tracker.stats(),
+        OutputStats {
+            on_secs: 102,
+            cycles: 3,
+        }
+    );
+
+    mock_time::advance(Duration::from_secs(10));
+    tracker.update(Instant::now(), false);
+    tracker.update(Instant::now(), true);
+
+    assert_eq!(tracker.stats().on_secs, 102);
+    assert_eq!(tracker.stats().cycles, 4);
+}
+
+#[test]
+fn saves_are_throttled() {
+    mock_time::set(Duration::from_secs(1));
+    let mut tracker = OutputStatsTracker::new(OutputStats::zero(), Instant::now());
+
+    assert_eq!(tracker.due(Instant::now()), None);
+
+    // Not even the first change is written before an interval has passed since boot.
+    tracker.update(Instant::now(), true);
+    assert_eq!(tracker.due(Instant::now()), None);
+
+    mock_time::advance(STATS_SAVE_INTERVAL);
+    tracker.update(Instant::now(), false);
+    assert_eq!(
+        tracker.due(Instant::now()),
+        Some(OutputStats {
+            on_secs: STATS_SAVE_INTERVAL.as_secs() as u32,
+            cycles: 1,
+        })
+    );
+
+    // Nothing changed since.
+    mock_time::advance(STATS_SAVE_INTERVAL);
+    assert_eq!(tracker.due(Instant::now()), None);
+
+    tracker.update(Instant::now(), true);
+    mock_time::advance(STATS_SAVE_INTERVAL - Duration::from_secs(1));
+    tracker.update(Instant::now(), true);
+    assert_eq!(
+        tracker.due(Instant::now()).map(|stats| stats.cycles),
+        Some(2)
+    );
+
+    mock_time::advance(Duration::from_secs(1));
+    tracker.update(Instant::now(), true);
+    assert_eq!(tracker.due(Instant::now()), None);
+}
+
+fn entry(on_secs: u32, cycles: u32) -> [u8; ENTRY_LEN] {
+    OutputStats { on_secs, cycles }.to_bytes()
+}
+
+#[test]
+fn journal_reads_the_last_entry() {
+    let mut page = [0xFF; JOURNAL_ENTRIES * ENTRY_LEN];
+    page[..ENTRY_LEN].copy_from_slice(&JOURNAL_HEADER);
+
+    assert_eq!(journal_last(&page), None);
+    assert_eq!(journal_next(&page), Some(1));
+
+    page[ENTRY_LEN..2 * ENTRY_LEN].copy_from_slice(&entry(60, 1));
+    page[2 * ENTRY_LEN..3 * ENTRY_LEN].copy_from_slice(&entry(120, 2));
+
+    assert_eq!(
+        journal_last(&page),
+        Some(OutputStats {
+            on_secs: 120,
+            cycles: 2,
+        })
+    );
+    assert_eq!(journal_next(&page), Some(3));
+}
+
+#[test]
+fn full_or_foreign_journal_starts_over() {
+    let mut page = [0x00; JOURNAL_ENTRIES * ENTRY_LEN];
+
+    // Left over from a staged image, or erased.
+    assert_eq!(journal_last(&page), None);
+    assert_eq!(journal_next(&page), None);
+    assert_eq!(journal_next(&[0xFF; JOURNAL_ENTRIES * ENTRY_LEN]), None);
+
+    page[..ENTRY_LEN].copy_from_slice(&JOURNAL_HEADER);
+    let last = (JOURNAL_ENTRIES - 1) * ENTRY_LEN;
+    page[last..].copy_from_slice(&entry(7, 3));
+
+    assert_eq!(journal_next(&page), None);
+    assert_eq!(
+        journal_last(&page),
+        Some(OutputStats {
+            on_secs: 7,
+            cycles: 3,
+        })
+    );
+}
@@ -0,0 +1,66 @@
+use husb238::SrcPdo;
+
+use crate::{pd_cache::PdCache, units::from_milli};
+
+#[test]
+fn sends_the_first_value() {
+    let mut cache = PdCache::new();
+    let mut sent = Vec::new();
+
+    cache.target_volts.publish(from_milli(5_000), |volts| {
+        sent.push(volts);
+        true
+    });
+
+    assert_eq!(sent, [from_milli(5_000)]);
+}
+
+#[test]
+fn sends_only_changes() {
+    let mut cache = PdCache::new();
+    let mut sent = Vec::new();
+
+    for milli in [3_000, 3_000, 3_000, 1_500, 1_500, 3_000] {
+        cache.limit_amps.publish(from_milli(milli), |amps| {
+            sent.push(amps);
+            true
+        });
+    }
+
+    assert_eq!(
+        sent,
+        [from_milli(3_000), from_milli(1_500), from_milli(3_000)]
+    );
+}
+
+#[test]
+fn sends_again_after_a_dropped_command() {
+    let mut cache = PdCache::new();
+    let mut sent = Vec::new();
+
+    for queued in [false, true, true] {
+        cache.selected_pdo.publish(SrcPdo::_9v, |pdo| {
+            sent.push(pdo);
+            queued
+        });
+    }
+
+    assert_eq!(sent, [SrcPdo::_9v, SrcPdo::_9v]);
+}
+
+#[test]
+fn fields_are_kept_apart() {
+    let mut cache = PdCache::new();
+    let mut sent = 0;
+
+    cache.target_volts.publish(from_milli(9_000), |_| {
+        sent += 1;
+        true
+    });
+    cache.limit_amps.publish(from_milli(9_000), |_| {
+        sent += 1;
+        true
+    });
+
+    assert_eq!(sent, 2);
+}
@@ -0,0 +1,101 @@
+use crate::power_monitor::{probe_order, LinkEvent, MonitorLink, PowerMonitorConfig, LOST_AFTER};
+
+const CONFIG: PowerMonitorConfig = PowerMonitorConfig {
+    address: 0x44,
+    shunt_micro_ohms: 2_500,
+    max_milliamps: 15_000,
+};
+
+#[test]
+fn config_round_trips_through_bytes() {
+    assert_eq!(
+        PowerMonitorConfig::from_bytes(&CONFIG.to_bytes()),
+        Some(CONFIG)
+    );
+    assert_eq!(CONFIG.shunt_ohms(), 0.0025);
+    assert_eq!(CONFIG.max_amps(), 15.0);
+}
+
+#[test]
+fn alert_limit_is_in_shunt_voltage_lsbs() {
+    // 2 A through 2.5 mΩ is 5 mV, 2000 LSBs of 2.5 µV.
+    assert_eq!(CONFIG.shunt_limit(2_000), 2_000);
+    assert_eq!(CONFIG.shunt_limit(-100), 0);
+    // Past the 81.9 mV full scale of the shunt input.
+    assert_eq!(CONFIG.shunt_limit(100_000), i16::MAX as u16);
+}
+
+#[test]
+fn rejects_records_out_of_range() {
+    let bad = [
+        PowerMonitorConfig {
+            address: 0x50,
+            ..CONFIG
+        },
+        PowerMonitorConfig {
+            shunt_micro_ohms: 0,
+            ..CONFIG
+        },
+        PowerMonitorConfig {
+            max_milliamps: 50,
+            ..CONFIG
+        },
+    ];
+
+    for config in bad {
+        assert!(!config.is_valid());
+        assert_eq!(PowerMonitorConfig::from_bytes(&config.to_bytes()), None);
+    }
+
+    assert_eq!(PowerMonitorConfig::from_bytes(&[0xff; 8]), None);
+}
+
+#[test]
+fn probes_the_configured_address_first() {
+    let order: Vec<u8> = probe_order(0x44, None).collect();
+
+    assert_eq!(order.len(), 16);
+    assert_eq!(order[0], 0x44);
+    assert_eq!(&order[1..5], &[0x40, 0x41, 0x42, 0x43]);
+    assert_eq!(order[5], 0x45);
+}
+
+#[test]
+fn skips_the_address_of_the_other_channel() {
+    let order: Vec<u8> = probe_order(0x41, Some(0x40)).collect();
+
+    assert_eq!(order.len(), 15);
+    assert_eq!(&order[..3], &[0x41, 0x42, 0x43]);
+    assert!(!order.contains(&0x40));
+}
+
+#[test]
+fn link_is_lost_after_failed_passes_in_a_row() {
+    let mut link = MonitorLink::new(true);
+
+    for _ in 1..LOST_AFTER {
+        assert_eq!(link.update(false), None);
+    }
+    // A good pass starts the count over.
+    assert_eq!(link.update(true), None);
+    for _ in 1..LOST_AFTER {
+        assert_eq!(link.update(false), None);
+    }
+    assert!(link.is_up());
+
+    assert_eq!(link.update(false), Some(LinkEvent::Lost));
+    assert!(!link.is_up());
+    assert_eq!(link.update(false), None);
+}
+
+#[test]
+fn link_comes_back_once() {
+    let mut link = MonitorLink::new(false);
+
+    assert_eq!(link.update(true), None);
+    assert!(!link.is_up());
+
+    assert_eq!(link.reconnected(), Some(LinkEvent::Back));
+    assert!(link.is_up());
+    assert_eq!(link.reconnected(), None);
+}
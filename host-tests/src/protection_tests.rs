@@ -0,0 +1,402 @@
+use embassy_time::Duration;
+
+use crate::{
+    mock_time::{self, Instant},
+    protection::{
+        ramp_fault, DerateEvent, FuseLimit, GuardEvent, OcpDerate, OutputSense, RampFault,
+        SenseHealth, SoftFuse, VoltageFault, VoltageGuard, VoltageLimit, FUSE_MAX_TRIPS,
+        SENSE_MISMATCH_PASSES, SHORT_TEST_RAMP,
+    },
+    units::{from_milli, ZERO},
+};
+
+fn limit(trip_mv: i32, recover_mv: i32) -> VoltageLimit {
+    VoltageLimit {
+        trip: from_milli(trip_mv),
+        recover: from_milli(recover_mv),
+    }
+}
+
+fn update(
+    guard: &mut VoltageGuard,
+    mv: i32,
+    uvp: &VoltageLimit,
+    ovp: &VoltageLimit,
+) -> Option<GuardEvent> {
+    guard.update(from_milli(mv), None, true, uvp, ovp)
+}
+
+#[test]
+fn uvp_recovers_only_past_the_hysteresis() {
+    let mut guard = VoltageGuard::new();
+    let uvp = limit(4_500, 4_800);
+    let ovp = VoltageLimit::off();
+
+    assert_eq!(update(&mut guard, 5_000, &uvp, &ovp), None);
+    assert_eq!(
+        update(&mut guard, 4_400, &uvp, &ovp),
+        Some(GuardEvent::Trip(VoltageFault::Under))
+    );
+
+    // Back above the trip, but not yet at the recovery threshold.
+    assert_eq!(
+        guard.update(ZERO, Some(from_milli(4_600)), false, &uvp, &ovp),
+        None
+    );
+    assert_eq!(guard.held(), Some(VoltageFault::Under));
+
+    assert_eq!(
+        guard.update(ZERO, Some(from_milli(4_800)), false, &uvp, &ovp),
+        Some(GuardEvent::Recover(VoltageFault::Under))
+    );
+    assert_eq!(guard.held(), None);
+}
+
+#[test]
+fn ovp_recovers_below_the_hysteresis() {
+    let mut guard = VoltageGuard::new();
+    let uvp = VoltageLimit::off();
+    let ovp = limit(21_000, 20_500);
+
+    assert_eq!(
+        update(&mut guard, 21_100, &uvp, &ovp),
+        Some(GuardEvent::Trip(VoltageFault::Over))
+    );
+    assert_eq!(
+        guard.update(ZERO, Some(from_milli(20_800)), false, &uvp, &ovp),
+        None
+    );
+    assert_eq!(
+        guard.update(ZERO, Some(from_milli(20_400)), false, &uvp, &ovp),
+        Some(GuardEvent::Recover(VoltageFault::Over))
+    );
+}
+
+#[test]
+fn recovery_waits_for_the_source() {
+    let mut guard = VoltageGuard::new();
+    let uvp = limit(4_500, 4_800);
+    let ovp = VoltageLimit::off();
+
+    update(&mut guard, 4_400, &uvp, &ovp);
+
+    // The output reads nothing past the open switch, whatever it reads does not count.
+    assert_eq!(
+        guard.update(from_milli(5_000), None, false, &uvp, &ovp),
+        None
+    );
+    assert_eq!(
+        guard.update(ZERO, Some(from_milli(4_700)), false, &uvp, &ovp),
+        None
+    );
+    assert_eq!(guard.held(), Some(VoltageFault::Under));
+
+    assert_eq!(
+        guard.update(ZERO, Some(from_milli(4_900)), false, &uvp, &ovp),
+        Some(GuardEvent::Recover(VoltageFault::Under))
+    );
+}
+
+#[test]
+fn recovery_on_the_wrong_side_uses_the_trip() {
+    let mut guard = VoltageGuard::new();
+    let uvp = limit(4_500, 0);
+    let ovp = VoltageLimit::off();
+
+    update(&mut guard, 4_400, &uvp, &ovp);
+
+    assert_eq!(
+        guard.update(ZERO, Some(from_milli(4_500)), false, &uvp, &ovp),
+        Some(GuardEvent::Recover(VoltageFault::Under))
+    );
+}
+
+#[test]
+fn disabled_output_does_not_trip() {
+    let mut guard = VoltageGuard::new();
+    let uvp = limit(4_500, 4_800);
+
+    assert_eq!(
+        guard.update(ZERO, None, false, &uvp, &VoltageLimit::off()),
+        None
+    );
+}
+
+#[test]
+fn released_trip_does_not_recover() {
+    let mut guard = VoltageGuard::new();
+    let uvp = limit(4_500, 4_800);
+    let ovp = VoltageLimit::off();
+
+    update(&mut guard, 4_000, &uvp, &ovp);
+    guard.release();
+
+    assert_eq!(
+        guard.update(ZERO, Some(from_milli(5_000)), false, &uvp, &ovp),
+        None
+    );
+}
+
+#[test]
+fn limit_turned_off_recovers() {
+    let mut guard = VoltageGuard::new();
+
+    update(
+        &mut guard,
+        4_000,
+        &limit(4_500, 4_800),
+        &VoltageLimit::off(),
+    );
+
+    assert_eq!(
+        guard.update(
+            ZERO,
+            Some(from_milli(4_000)),
+            false,
+            &VoltageLimit::off(),
+            &VoltageLimit::off()
+        ),
+        Some(GuardEvent::Recover(VoltageFault::Under))
+    );
+}
+
+#[test]
+fn short_test_ramp_sees_a_collapsed_bus() {
+    let target = from_milli(9_000);
+    let fault = |duty, mv| ramp_fault(duty, from_milli(mv), ZERO, target, ZERO);
+
+    // A capacitive load holds the bus, a resistive one follows the duty.
+    assert_eq!(fault(10, 8_900), None);
+    assert_eq!(fault(10, 900), None);
+    assert_eq!(fault(10, 630), None);
+    assert_eq!(fault(10, 629), Some(RampFault::Collapsed));
+    assert_eq!(fault(70, 4_410), None);
+    assert_eq!(fault(70, 4_409), Some(RampFault::Collapsed));
+    assert_eq!(fault(5, 0), Some(RampFault::Collapsed));
+    assert_eq!(
+        ramp_fault(10, ZERO, ZERO, ZERO, ZERO),
+        None,
+        "no contract to compare against"
+    );
+}
+
+#[test]
+fn short_test_ramp_stops_over_the_ocp() {
+    let target = from_milli(5_000);
+    let fault = |ma, ocp_ma| {
+        ramp_fault(
+            20,
+            from_milli(4_900),
+            from_milli(ma),
+            target,
+            from_milli(ocp_ma),
+        )
+    };
+
+    assert_eq!(fault(1_000, 1_000), None);
+    assert_eq!(fault(1_001, 1_000), Some(RampFault::OverCurrent));
+    assert_eq!(fault(9_000, 0), None);
+}
+
+#[test]
+fn short_test_ramp_rises_below_full_on() {
+    assert!(SHORT_TEST_RAMP.windows(2).all(|w| w[0] < w[1]));
+    assert!(SHORT_TEST_RAMP.iter().all(|duty| (1..100).contains(duty)));
+}
+
+#[test]
+fn sense_follows_after_the_grace_passes() {
+    let mut sense = OutputSense::new();
+
+    // The driver takes a pass or two to switch.
+    for _ in 1..SENSE_MISMATCH_PASSES {
+        assert_eq!(sense.update(true, false), None);
+    }
+    assert_eq!(sense.update(true, true), None);
+
+    let report = sense.report().unwrap();
+    assert_eq!(report.health, SenseHealth::Ok);
+    assert_eq!(report.faults, 0);
+}
+
+#[test]
+fn lasting_mismatch_is_reported_once() {
+    let mut sense = OutputSense::new();
+
+    for _ in 1..SENSE_MISMATCH_PASSES {
+        assert_eq!(sense.update(false, true), None);
+    }
+    assert_eq!(sense.update(false, true), Some(SenseHealth::StuckOn));
+    assert_eq!(sense.update(false, true), None);
+
+    let report = sense.report().unwrap();
+    assert_eq!(report.health, SenseHealth::StuckOn);
+    assert_eq!(report.faults, 1);
+
+    // Agreeing again clears the state but keeps the count.
+    sense.update(false, false);
+    assert_eq!(sense.report().unwrap().health, SenseHealth::Ok);
+
+    for _ in 1..SENSE_MISMATCH_PASSES {
+        sense.update(true, false);
+    }
+    assert_eq!(sense.update(true, false), Some(SenseHealth::StuckOff));
+    assert_eq!(sense.report().unwrap().faults, 2);
+}
+
+#[test]
+fn no_report_before_the_first_reading() {
+    assert_eq!(OutputSense::new().report(), None);
+}
+
+#[test]
+fn ocp_follows_a_derated_contract_and_comes_back() {
+    let mut derate = OcpDerate::new();
+    let user = from_milli(2_500);
+
+    assert_eq!(derate.update(user, from_milli(3_000)), None);
+    assert!(!derate.is_derated(user));
+
+    let event = derate.update(user, from_milli(1_500));
+    assert_eq!(event, Some(DerateEvent::Derated(from_milli(1_500))));
+    assert!(derate.is_derated(from_milli(1_500)));
+
+    // Partly back, still held below the user's.
+    let event = derate.update(from_milli(1_500), from_milli(2_000));
+    assert_eq!(event, Some(DerateEvent::Restored(from_milli(2_000))));
+    assert!(derate.is_derated(from_milli(2_000)));
+
+    let event = derate.update(from_milli(2_000), from_milli(3_000));
+    assert_eq!(event, Some(DerateEvent::Restored(user)));
+    assert!(!derate.is_derated(user));
+}
+
+#[test]
+fn ocp_set_while_derated_becomes_the_users() {
+    let mut derate = OcpDerate::new();
+
+    derate.update(from_milli(2_500), from_milli(1_500));
+
+    // Lowered by hand below the derated value.
+    assert_eq!(derate.update(from_milli(1_000), from_milli(3_000)), None);
+    assert!(!derate.is_derated(from_milli(1_000)));
+
+    // Raised by hand past the contract, which holds it again on the next one.
+    derate.update(from_milli(1_000), from_milli(1_500));
+    assert!(!derate.is_derated(from_milli(4_000)));
+    let event = derate.update(from_milli(4_000), from_milli(2_000));
+    assert_eq!(event, Some(DerateEvent::Derated(from_milli(2_000))));
+}
+
+#[test]
+fn no_ocp_follows_the_contract_without_derating() {
+    let mut derate = OcpDerate::new();
+
+    let event = derate.update(ZERO, from_milli(3_000));
+    assert_eq!(event, Some(DerateEvent::Capped(from_milli(3_000))));
+    assert!(!derate.is_derated(from_milli(3_000)));
+
+    let event = derate.update(from_milli(3_000), from_milli(1_500));
+    assert_eq!(event, Some(DerateEvent::Capped(from_milli(1_500))));
+}
+
+#[test]
+fn ocp_stays_without_a_contract() {
+    let mut derate = OcpDerate::new();
+
+    derate.update(from_milli(2_500), from_milli(1_500));
+
+    assert_eq!(derate.update(from_milli(1_500), ZERO), None);
+    assert!(derate.is_derated(from_milli(1_500)));
+}
+
+const FUSE: FuseLimit = FuseLimit {
+    trips: 3,
+    window_secs: 60,
+};
+
+#[test]
+fn fuse_blows_on_trips_within_the_window() {
+    mock_time::set(Duration::from_secs(100));
+    let mut fuse = SoftFuse::new();
+
+    assert!(!fuse.trip(Instant::now(), &FUSE));
+    mock_time::advance(Duration::from_secs(20));
+    assert!(!fuse.trip(Instant::now(), &FUSE));
+    mock_time::advance(Duration::from_secs(20));
+    assert!(fuse.trip(Instant::now(), &FUSE));
+    assert!(fuse.is_blown());
+
+    // Reported once.
+    assert!(!fuse.trip(Instant::now(), &FUSE));
+    assert!(fuse.is_blown());
+}
+
+#[test]
+fn fuse_forgets_old_trips() {
+    mock_time::set(Duration::from_secs(100));
+    let mut fuse = SoftFuse::new();
+
+    for _ in 0..5 {
+        assert!(!fuse.trip(Instant::now(), &FUSE));
+        mock_time::advance(Duration::from_secs(31));
+    }
+    assert!(!fuse.is_blown());
+}
+
+#[test]
+fn fuse_reset_starts_counting_over() {
+    mock_time::set(Duration::from_secs(100));
+    let mut fuse = SoftFuse::new();
+
+    for _ in 0..3 {
+        fuse.trip(Instant::now(), &FUSE);
+    }
+    assert!(fuse.is_blown());
+
+    fuse.reset();
+    assert!(!fuse.is_blown());
+    assert!(!fuse.trip(Instant::now(), &FUSE));
+    assert!(!fuse.trip(Instant::now(), &FUSE));
+    assert!(fuse.trip(Instant::now(), &FUSE));
+}
+
+#[test]
+fn fuse_blown_before_a_restart_stays_blown() {
+    mock_time::set(Duration::from_secs(100));
+    let mut fuse = SoftFuse::new();
+
+    fuse.blow();
+    assert!(fuse.is_blown());
+    assert!(!fuse.trip(Instant::now(), &FUSE));
+    assert!(fuse.is_blown());
+
+    fuse.reset();
+    assert!(!fuse.is_blown());
+}
+
+#[test]
+fn fuse_off_never_blows() {
+    mock_time::set(Duration::from_secs(100));
+    let mut fuse = SoftFuse::new();
+
+    for _ in 0..20 {
+        assert!(!fuse.trip(Instant::now(), &FuseLimit::off()));
+    }
+    assert!(!fuse.is_blown());
+}
+
+#[test]
+fn fuse_limit_round_trips_through_bytes() {
+    assert_eq!(FuseLimit::from_bytes(&FUSE.to_bytes()), Some(FUSE));
+    assert_eq!(
+        FuseLimit::from_bytes(&FuseLimit::off().to_bytes()),
+        Some(FuseLimit::off())
+    );
+
+    let too_many = FuseLimit {
+        trips: FUSE_MAX_TRIPS + 1,
+        ..FUSE
+    };
+    assert_eq!(FuseLimit::from_bytes(&too_many.to_bytes()), None);
+}
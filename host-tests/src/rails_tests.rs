@@ -0,0 +1,87 @@
+use crate::{
+    rails::{Divider, Rails, SwitchWatch},
+    units::{from_milli, milli, ZERO},
+};
+
+#[test]
+fn divider_scales_the_pin_up() {
+    let divider = Divider {
+        top_ohms: 100_000,
+        bottom_ohms: 10_000,
+    };
+
+    assert_eq!(divider.input_millivolts(0), 0);
+    assert_eq!(divider.input_millivolts(1_818), 19_998);
+    assert_eq!(divider.input_millivolts(3_300), 36_300);
+}
+
+#[test]
+fn drops_split_between_cable_and_switch() {
+    let rails = Rails {
+        contract: from_milli(20_000),
+        source: from_milli(19_500),
+        load: from_milli(19_250),
+    };
+
+    assert_eq!(milli(rails.cable_drop()), 500);
+    assert_eq!(milli(rails.switch_drop()), 250);
+}
+
+#[test]
+fn source_running_high_reads_as_negative_cable_drop() {
+    let rails = Rails {
+        contract: from_milli(5_000),
+        source: from_milli(5_125),
+        load: from_milli(5_000),
+    };
+
+    assert_eq!(milli(rails.cable_drop()), -125);
+    assert_eq!(milli(rails.switch_drop()), 125);
+}
+
+#[test]
+fn switch_dissipates_its_drop_times_the_current() {
+    let rails = Rails {
+        contract: from_milli(20_000),
+        source: from_milli(19_500),
+        load: from_milli(19_250),
+    };
+
+    assert_eq!(milli(rails.switch_watts(from_milli(3_000))), 750);
+    assert_eq!(milli(rails.switch_watts(ZERO)), 0);
+}
+
+#[test]
+fn output_above_vbus_dissipates_nothing() {
+    let rails = Rails {
+        contract: from_milli(5_000),
+        source: from_milli(5_000),
+        load: from_milli(5_125),
+    };
+
+    assert_eq!(milli(rails.switch_watts(from_milli(2_000))), 0);
+}
+
+#[test]
+fn switch_warns_once_per_excursion() {
+    let mut watch = SwitchWatch::new();
+    let limit = from_milli(1_000);
+
+    assert!(!watch.check(from_milli(900), limit));
+    assert!(watch.check(from_milli(1_250), limit));
+    assert!(!watch.check(from_milli(1_500), limit));
+    // Still within the hysteresis.
+    assert!(!watch.check(from_milli(850), limit));
+    assert!(!watch.check(from_milli(1_250), limit));
+    // Back under 80 % re-arms it.
+    assert!(!watch.check(from_milli(750), limit));
+    assert!(watch.check(from_milli(1_250), limit));
+}
+
+#[test]
+fn zero_limit_never_warns() {
+    let mut watch = SwitchWatch::new();
+
+    assert!(!watch.check(from_milli(5_000), ZERO));
+    assert!(!watch.check(from_milli(10_000), ZERO));
+}
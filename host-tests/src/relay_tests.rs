@@ -0,0 +1,86 @@
+use embassy_time::Duration;
+
+use crate::{
+    mock_time::{self, Instant},
+    relay::{Contacts, RelaySequencer, RelayTiming},
+    units::from_milli,
+};
+
+const TIMING: RelayTiming = RelayTiming {
+    pull_in: Duration::from_millis(15),
+    release: Duration::from_millis(10),
+    quiet_amps: from_milli(50),
+    quiet_wait: Duration::from_millis(500),
+};
+
+fn closed_relay() -> RelaySequencer {
+    mock_time::set(Duration::from_secs(1));
+    let mut relay = RelaySequencer::new(TIMING);
+
+    relay.switch(Instant::now(), true);
+    mock_time::advance(TIMING.pull_in);
+
+    relay
+}
+
+#[test]
+fn contacts_follow_the_coil_after_the_delays() {
+    mock_time::set(Duration::from_secs(1));
+    let mut relay = RelaySequencer::new(TIMING);
+    assert_eq!(relay.contacts(Instant::now()), Contacts::Open);
+
+    relay.switch(Instant::now(), true);
+    assert!(relay.coil());
+    assert_eq!(relay.contacts(Instant::now()), Contacts::Closing);
+
+    mock_time::advance(TIMING.pull_in);
+    assert_eq!(relay.contacts(Instant::now()), Contacts::Closed);
+
+    relay.switch(Instant::now(), false);
+    assert_eq!(relay.contacts(Instant::now()), Contacts::Opening);
+
+    mock_time::advance(TIMING.release);
+    assert_eq!(relay.contacts(Instant::now()), Contacts::Open);
+}
+
+#[test]
+fn quiet_open_waits_for_low_current() {
+    let mut relay = closed_relay();
+
+    relay.open_quietly(Instant::now());
+    assert!(!relay.update(Instant::now(), Some(from_milli(2_000))));
+    assert!(relay.coil());
+    assert_eq!(relay.contacts(Instant::now()), Contacts::Opening);
+
+    // A gap in the load lets the contacts open without breaking the current.
+    mock_time::advance(Duration::from_millis(100));
+    assert!(relay.update(Instant::now(), Some(from_milli(30))));
+    assert!(!relay.coil());
+}
+
+#[test]
+fn quiet_open_gives_up_after_the_wait() {
+    let mut relay = closed_relay();
+
+    relay.open_quietly(Instant::now());
+
+    mock_time::advance(TIMING.quiet_wait - Duration::from_millis(1));
+    assert!(!relay.update(Instant::now(), Some(from_milli(2_000))));
+
+    // An unread current does not count as low, but the wait still ends.
+    mock_time::advance(Duration::from_millis(1));
+    assert!(relay.update(Instant::now(), None));
+    assert!(!relay.coil());
+}
+
+#[test]
+fn switching_on_drops_a_pending_open() {
+    let mut relay = closed_relay();
+
+    relay.open_quietly(Instant::now());
+    relay.switch(Instant::now(), true);
+
+    mock_time::advance(TIMING.quiet_wait);
+    assert!(!relay.update(Instant::now(), Some(from_milli(0))));
+    assert_eq!(relay.contacts(Instant::now()), Contacts::Closed);
+}
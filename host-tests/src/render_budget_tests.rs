@@ -0,0 +1,128 @@
+use embassy_time::Duration;
+
+use crate::{
+    mock_time::{self, Instant},
+    render::{Pending, RenderCmd},
+    render_budget::{RenderBudget, DEGRADED_INTERVAL, DEGRADE_AFTER, FLUSH_BUDGET, RECOVER_AFTER},
+    units::from_milli,
+};
+
+const SLOW: Duration = Duration::from_millis(FLUSH_BUDGET.as_millis() + 5);
+const FAST: Duration = Duration::from_millis(2);
+
+fn degraded() -> RenderBudget {
+    let mut budget = RenderBudget::new();
+    for _ in 0..DEGRADE_AFTER {
+        budget.record(SLOW);
+    }
+    assert!(budget.is_degraded());
+
+    budget
+}
+
+fn readings(ma: i32) -> Pending {
+    let mut pending = Pending::new();
+    pending.push(RenderCmd::Volts(Some(from_milli(5_000))));
+    pending.push(RenderCmd::Amps(Some(from_milli(ma))));
+    pending.push(RenderCmd::Watts(Some(from_milli(5 * ma))));
+    pending.push(RenderCmd::Rms(Some(from_milli(ma))));
+
+    pending
+}
+
+#[test]
+fn slows_down_after_a_run_of_slow_drawings() {
+    let mut budget = RenderBudget::new();
+
+    for _ in 1..DEGRADE_AFTER {
+        assert_eq!(budget.record(SLOW), None);
+    }
+    assert_eq!(budget.record(SLOW), Some(true));
+    assert!(budget.is_degraded());
+}
+
+#[test]
+fn a_fast_drawing_breaks_the_run() {
+    let mut budget = RenderBudget::new();
+
+    for _ in 1..DEGRADE_AFTER {
+        budget.record(SLOW);
+    }
+    budget.record(FAST);
+    budget.record(SLOW);
+
+    assert!(!budget.is_degraded());
+}
+
+#[test]
+fn recovers_after_a_longer_run_of_fast_drawings() {
+    let mut budget = degraded();
+
+    for _ in 1..RECOVER_AFTER {
+        assert_eq!(budget.record(FAST), None);
+    }
+    assert_eq!(budget.record(FAST), Some(false));
+    assert!(!budget.is_degraded());
+}
+
+#[test]
+fn passes_everything_while_within_budget() {
+    mock_time::set(Duration::from_secs(1));
+    let mut budget = RenderBudget::new();
+
+    let mut pending = readings(1_000);
+    budget.defer(Instant::now(), &mut pending);
+
+    assert_eq!(pending, readings(1_000));
+}
+
+#[test]
+fn holds_the_small_print_back_while_degraded() {
+    mock_time::set(Duration::from_secs(1));
+    let mut budget = degraded();
+
+    // The first goes out at once, the next wait for the interval.
+    let mut pending = readings(1_000);
+    budget.defer(Instant::now(), &mut pending);
+    assert_eq!(pending, readings(1_000));
+
+    mock_time::advance(DEGRADED_INTERVAL / 2);
+    let mut pending = readings(2_000);
+    budget.defer(Instant::now(), &mut pending);
+    assert_eq!(pending.amps, Some(Some(from_milli(2_000))));
+    assert_eq!(pending.watts, None);
+    assert_eq!(pending.rms, None);
+
+    mock_time::advance(DEGRADED_INTERVAL / 4);
+    let mut pending = readings(3_000);
+    budget.defer(Instant::now(), &mut pending);
+    assert_eq!(pending.watts, None);
+
+    // Then the latest of the held ones.
+    mock_time::advance(DEGRADED_INTERVAL / 4);
+    let mut pending = Pending::new();
+    budget.defer(Instant::now(), &mut pending);
+    assert_eq!(pending.watts, Some(Some(from_milli(15_000))));
+    assert_eq!(pending.rms, Some(Some(from_milli(3_000))));
+    assert_eq!(pending.amps, None);
+}
+
+#[test]
+fn lets_the_held_small_print_out_on_recovery() {
+    mock_time::set(Duration::from_secs(1));
+    let mut budget = degraded();
+
+    let mut pending = readings(1_000);
+    budget.defer(Instant::now(), &mut pending);
+    let mut pending = readings(2_000);
+    budget.defer(Instant::now(), &mut pending);
+    assert_eq!(pending.watts, None);
+
+    for _ in 0..RECOVER_AFTER {
+        budget.record(FAST);
+    }
+
+    let mut pending = Pending::new();
+    budget.defer(Instant::now(), &mut pending);
+    assert_eq!(pending.watts, Some(Some(from_milli(10_000))));
+}
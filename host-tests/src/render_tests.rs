@@ -0,0 +1,57 @@
+use husb238::SrcPdo;
+
+use crate::{
+    render::{Pending, RenderCmd},
+    units::{from_milli, milli},
+};
+
+#[test]
+fn starts_empty() {
+    assert!(Pending::new().is_empty());
+}
+
+#[test]
+fn keeps_the_latest_of_each_kind() {
+    let mut pending = Pending::new();
+
+    pending.push(RenderCmd::Volts(Some(from_milli(5_000))));
+    pending.push(RenderCmd::Amps(Some(from_milli(1_000))));
+    pending.push(RenderCmd::Volts(Some(from_milli(5_100))));
+    pending.push(RenderCmd::Output(true));
+    pending.push(RenderCmd::Output(false));
+    pending.push(RenderCmd::SelectedPdo(SrcPdo::_20v));
+
+    assert!(!pending.is_empty());
+    assert_eq!(pending.volts.flatten().map(milli), Some(5_100));
+    assert_eq!(pending.amps.flatten().map(milli), Some(1_000));
+    assert_eq!(pending.watts, None);
+    assert_eq!(pending.output, Some(false));
+    assert_eq!(pending.selected_pdo, Some(SrcPdo::_20v));
+}
+
+#[test]
+fn clearing_counts_as_an_update() {
+    let mut pending = Pending::new();
+
+    pending.push(RenderCmd::Schedule(None));
+    pending.push(RenderCmd::Average(None));
+    pending.push(RenderCmd::Rms(Some(from_milli(1_000))));
+    pending.push(RenderCmd::Rms(None));
+    pending.push(RenderCmd::Volts(Some(from_milli(5_000))));
+    pending.push(RenderCmd::Volts(None));
+
+    assert_eq!(pending.schedule, Some(None));
+    assert_eq!(pending.average, Some(None));
+    assert_eq!(pending.rms, Some(None));
+    assert_eq!(pending.volts, Some(None));
+}
+
+#[test]
+fn init_is_kept() {
+    let mut pending = Pending::new();
+
+    pending.push(RenderCmd::Init);
+    pending.push(RenderCmd::Volts(Some(from_milli(5_000))));
+
+    assert!(pending.init);
+}
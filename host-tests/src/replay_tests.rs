@@ -0,0 +1,72 @@
+use crate::{
+    measure::Reading,
+    replay::{parse_line, Replay},
+    units::from_milli,
+};
+
+const RECORDING: &str = "\
+# captured on the bench
+ms,mV,mA,mW
+0,5000,100,500
+100,4990,1500,7485
+250,4980,,
+";
+
+fn reading(millivolts: i32, milliamps: i32, milliwatts: i32) -> Reading {
+    Reading {
+        volts: Some(from_milli(millivolts)),
+        amps: Some(from_milli(milliamps)),
+        watts: Some(from_milli(milliwatts)),
+    }
+}
+
+#[test]
+fn parses_readings_and_gaps() {
+    let sample = parse_line("100,4990,1500,7485").unwrap();
+    assert_eq!(sample.at_ms, 100);
+    assert_eq!(sample.reading, reading(4_990, 1_500, 7_485));
+
+    let sample = parse_line("250, 4980, , \r").unwrap();
+    assert_eq!(sample.reading.volts, Some(from_milli(4_980)));
+    assert_eq!(sample.reading.amps, None);
+    assert_eq!(sample.reading.watts, None);
+}
+
+#[test]
+fn skips_lines_that_are_not_readings() {
+    assert_eq!(parse_line("ms,mV,mA,mW"), None);
+    assert_eq!(parse_line("# comment"), None);
+    assert_eq!(parse_line(""), None);
+    assert_eq!(parse_line("0,5000,100"), None);
+    assert_eq!(parse_line("0,5000,100,500,1"), None);
+}
+
+#[test]
+fn holds_each_reading_until_the_next_is_due() {
+    let mut replay = Replay::new(RECORDING);
+
+    assert_eq!(replay.reading(1_000), reading(5_000, 100, 500));
+    assert_eq!(replay.reading(1_099), reading(5_000, 100, 500));
+    assert_eq!(replay.reading(1_100), reading(4_990, 1_500, 7_485));
+    assert_eq!(replay.reading(1_249).amps, Some(from_milli(1_500)));
+    assert_eq!(replay.reading(1_250).amps, None);
+}
+
+#[test]
+fn starts_over_after_the_last_reading() {
+    let mut replay = Replay::new(RECORDING);
+
+    replay.reading(0);
+    assert_eq!(replay.reading(300).amps, None);
+
+    assert_eq!(replay.reading(310), reading(5_000, 100, 500));
+    assert_eq!(replay.reading(410), reading(4_990, 1_500, 7_485));
+}
+
+#[test]
+fn empty_recording_reads_nothing() {
+    let mut replay = Replay::new("ms,mV,mA,mW\n");
+
+    assert_eq!(replay.reading(0), Reading::MISSING);
+    assert_eq!(replay.reading(1_000), Reading::MISSING);
+}
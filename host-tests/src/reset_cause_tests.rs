@@ -0,0 +1,90 @@
+use crate::reset_cause::{ResetCause, ResetCounts};
+
+const PIN: u32 = 1 << 26;
+const POWER: u32 = 1 << 27;
+const SOFTWARE: u32 = 1 << 28;
+const IWDG: u32 = 1 << 29;
+const WWDG: u32 = 1 << 30;
+
+#[test]
+fn power_flag_is_a_brown_out_only_when_ram_survived() {
+    assert_eq!(
+        ResetCause::from_flags(POWER | PIN, false),
+        ResetCause::PowerOn
+    );
+    assert_eq!(
+        ResetCause::from_flags(POWER | PIN, true),
+        ResetCause::BrownOut
+    );
+}
+
+#[test]
+fn pin_counts_only_alone() {
+    assert_eq!(ResetCause::from_flags(PIN, true), ResetCause::Pin);
+    assert_eq!(
+        ResetCause::from_flags(SOFTWARE | PIN, true),
+        ResetCause::Software
+    );
+    assert_eq!(
+        ResetCause::from_flags(IWDG | PIN, true),
+        ResetCause::Watchdog
+    );
+    assert_eq!(
+        ResetCause::from_flags(WWDG | PIN, false),
+        ResetCause::Watchdog
+    );
+}
+
+#[test]
+fn watchdog_wins_over_flags_left_from_earlier_resets() {
+    assert_eq!(
+        ResetCause::from_flags(IWDG | SOFTWARE | POWER | PIN, false),
+        ResetCause::Watchdog
+    );
+}
+
+#[test]
+fn only_watchdog_and_brown_out_are_abnormal() {
+    assert!(ResetCause::Watchdog.is_abnormal());
+    assert!(ResetCause::BrownOut.is_abnormal());
+    assert!(!ResetCause::PowerOn.is_abnormal());
+    assert!(!ResetCause::Pin.is_abnormal());
+    assert!(!ResetCause::Software.is_abnormal());
+}
+
+#[test]
+fn counts_round_trip_through_the_backup_registers() {
+    let mut counts = ResetCounts::empty();
+    counts.record(ResetCause::PowerOn);
+    counts.record(ResetCause::Watchdog);
+    counts.record(ResetCause::Watchdog);
+
+    let restored = ResetCounts::from_registers(&counts.to_registers());
+
+    assert_eq!(restored, counts);
+    assert_eq!(restored.last, Some(ResetCause::Watchdog));
+    assert_eq!(restored.count(ResetCause::Watchdog), 2);
+    assert_eq!(restored.count(ResetCause::PowerOn), 1);
+    assert_eq!(restored.count(ResetCause::Pin), 0);
+}
+
+#[test]
+fn blank_registers_count_nothing() {
+    assert_eq!(ResetCounts::from_registers(&[0; 3]), ResetCounts::empty());
+    assert_eq!(
+        ResetCounts::from_registers(&[u32::MAX; 3]),
+        ResetCounts::empty()
+    );
+}
+
+#[test]
+fn counts_saturate() {
+    let mut counts = ResetCounts::empty();
+
+    for _ in 0..300 {
+        counts.record(ResetCause::BrownOut);
+    }
+
+    assert_eq!(counts.count(ResetCause::BrownOut), 255);
+    assert_eq!(counts.count(ResetCause::OptionBytes), 0);
+}
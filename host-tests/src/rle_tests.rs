@@ -0,0 +1,58 @@
+use crate::rle::{bit, decode, encode};
+
+fn encoded(bitmap: &[u8]) -> Vec<u8> {
+    let mut data = Vec::new();
+    encode(bitmap, |b| data.push(b));
+    data
+}
+
+fn round_trip(bitmap: &[u8]) {
+    let data = encoded(bitmap);
+    let mut out = vec![0xAA; bitmap.len()];
+
+    decode(&data, &mut out);
+
+    assert_eq!(out, bitmap);
+    for i in 0..bitmap.len() * 8 {
+        assert_eq!(
+            bit(&data, i),
+            bitmap[i / 8] & (0x80 >> (i % 8)) != 0,
+            "pixel {i}"
+        );
+    }
+}
+
+#[test]
+fn blank_bitmap_encodes_to_nothing() {
+    assert!(encoded(&[0; 48]).is_empty());
+    round_trip(&[0; 48]);
+}
+
+#[test]
+fn leading_set_pixel_starts_with_an_empty_clear_run() {
+    let data = encoded(&[0x80, 0x00]);
+
+    assert_eq!(data, [0x01]);
+    round_trip(&[0x80, 0x00]);
+}
+
+#[test]
+fn runs_of_fifteen_and_more_continue() {
+    // Clear runs of 15, 16 and 30 pixels between set ones, and a long set run.
+    round_trip(&[
+        0x00, 0x01, 0x00, 0x00, 0x80, 0x00, 0x00, 0x02, 0xFF, 0xFF, 0xFF, 0xF0,
+    ]);
+    round_trip(&[0xFF; 16]);
+}
+
+#[test]
+fn glyph_shaped_bitmap_shrinks() {
+    // A 16x24 bar, rows of 0x07E0.
+    let mut bitmap = [0; 48];
+    for row in bitmap[8..40].chunks_mut(2) {
+        row.copy_from_slice(&[0x07, 0xE0]);
+    }
+
+    assert!(encoded(&bitmap).len() < bitmap.len() / 2);
+    round_trip(&bitmap);
+}
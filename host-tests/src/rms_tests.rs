@@ -0,0 +1,118 @@
+use embassy_time::Duration;
+
+use crate::{
+    mock_time::{self, Instant},
+    rms::{RollingRms, RMS_WINDOW},
+    units::{from_milli, milli},
+};
+
+const PASS: Duration = Duration::from_millis(10);
+
+/// Feeds `ma` every 10 ms for `duration` and returns the last RMS, in milliamps.
+fn feed(rms: &mut RollingRms, ma: i32, duration: Duration) -> Option<i32> {
+    let mut last = None;
+    for _ in 0..duration.as_ticks() / PASS.as_ticks() {
+        mock_time::advance(PASS);
+        last = rms.update(Instant::now(), from_milli(ma)).map(milli);
+    }
+
+    last
+}
+
+fn close_to(ma: Option<i32>, expected: i32) -> bool {
+    ma.is_some_and(|ma| (ma - expected).abs() <= 2)
+}
+
+#[test]
+fn needs_two_readings() {
+    mock_time::set(Duration::from_secs(1));
+    let mut rms = RollingRms::new();
+
+    assert_eq!(rms.update(Instant::now(), from_milli(1_000)), None);
+    assert_eq!(rms.rms(), None);
+
+    mock_time::advance(PASS);
+    assert!(close_to(
+        rms.update(Instant::now(), from_milli(1_000)).map(milli),
+        1_000
+    ));
+}
+
+#[test]
+fn steady_current_is_its_own_rms() {
+    mock_time::set(Duration::from_secs(1));
+    let mut rms = RollingRms::new();
+
+    assert!(close_to(
+        feed(&mut rms, 1_500, Duration::from_secs(3)),
+        1_500
+    ));
+}
+
+#[test]
+fn pulses_read_above_their_average() {
+    mock_time::set(Duration::from_secs(1));
+    let mut rms = RollingRms::new();
+    rms.update(Instant::now(), from_milli(0));
+
+    // 2 A at half duty averages 1 A but heats like 1.41 A.
+    let mut last = None;
+    for pass in 0..300 {
+        mock_time::advance(PASS);
+        let ma = if pass % 2 == 0 { 2_000 } else { 0 };
+        last = rms.update(Instant::now(), from_milli(ma)).map(milli);
+    }
+
+    // Give or take a pulse at the ends of the window.
+    assert!(
+        last.is_some_and(|ma| (1_380..=1_450).contains(&ma)),
+        "{last:?}"
+    );
+}
+
+#[test]
+fn forgets_what_is_older_than_the_window() {
+    mock_time::set(Duration::from_secs(1));
+    let mut rms = RollingRms::new();
+
+    feed(&mut rms, 3_000, Duration::from_secs(2));
+    let mixed = feed(&mut rms, 1_000, RMS_WINDOW / 2).unwrap();
+    assert!(mixed > 1_000 && mixed < 3_000, "{mixed}");
+
+    assert!(close_to(feed(&mut rms, 1_000, RMS_WINDOW), 1_000));
+}
+
+#[test]
+fn counts_reverse_current_by_its_size() {
+    mock_time::set(Duration::from_secs(1));
+    let mut rms = RollingRms::new();
+
+    assert!(close_to(
+        feed(&mut rms, -1_250, Duration::from_secs(2)),
+        1_250
+    ));
+}
+
+#[test]
+fn a_gap_of_a_window_starts_over() {
+    mock_time::set(Duration::from_secs(1));
+    let mut rms = RollingRms::new();
+
+    feed(&mut rms, 3_000, Duration::from_secs(2));
+    mock_time::advance(RMS_WINDOW);
+
+    assert_eq!(rms.update(Instant::now(), from_milli(500)), None);
+    assert!(close_to(feed(&mut rms, 500, PASS), 500));
+}
+
+#[test]
+fn reset_drops_the_window() {
+    mock_time::set(Duration::from_secs(1));
+    let mut rms = RollingRms::new();
+
+    feed(&mut rms, 3_000, Duration::from_secs(2));
+    rms.reset();
+
+    assert_eq!(rms.rms(), None);
+    assert_eq!(rms.update(Instant::now(), from_milli(500)), None);
+}
@@ -0,0 +1,102 @@
+use crate::schedule::{Action, Schedule};
+
+const DAY: u64 = 86_400;
+/// 2024-05-01T00:00:00Z.
+const MIDNIGHT: u64 = 1_714_521_600;
+
+fn at(hour: u64, minute: u64) -> u64 {
+    MIDNIGHT + hour * 3600 + minute * 60
+}
+
+#[test]
+fn rejects_invalid_times() {
+    assert!(Schedule::new(24, 0, 1).is_none());
+    assert!(Schedule::new(7, 60, 1).is_none());
+    assert!(Schedule::new(7, 30, 0).is_none());
+    assert!(Schedule::new(7, 30, 24).is_none());
+}
+
+#[test]
+fn next_switch() {
+    let schedule = Schedule::new(7, 30, 2).unwrap();
+
+    assert_eq!(
+        schedule.next(at(6, 0)),
+        Action {
+            on: true,
+            at: at(7, 30)
+        }
+    );
+    assert_eq!(
+        schedule.next(at(7, 30)),
+        Action {
+            on: false,
+            at: at(9, 30)
+        }
+    );
+    assert_eq!(
+        schedule.next(at(9, 30)),
+        Action {
+            on: true,
+            at: at(7, 30) + DAY
+        }
+    );
+}
+
+#[test]
+fn window_across_midnight() {
+    let schedule = Schedule::new(22, 0, 4).unwrap();
+
+    assert_eq!(
+        schedule.next(at(1, 0)),
+        Action {
+            on: false,
+            at: at(2, 0)
+        }
+    );
+    assert_eq!(
+        schedule.next(at(23, 0)),
+        Action {
+            on: false,
+            at: at(2, 0) + DAY
+        }
+    );
+}
+
+#[test]
+fn due_between_checks() {
+    let schedule = Schedule::new(7, 30, 2).unwrap();
+
+    assert_eq!(schedule.due(at(7, 29), at(7, 29) + 59), None);
+    assert_eq!(
+        schedule.due(at(7, 29) + 59, at(7, 30)),
+        Some(Action {
+            on: true,
+            at: at(7, 30)
+        })
+    );
+    assert_eq!(schedule.due(at(7, 30), at(7, 31)), None);
+    assert_eq!(
+        schedule.due(at(7, 0), at(10, 0)).map(|action| action.on),
+        Some(false)
+    );
+}
+
+#[test]
+fn action_time_of_day() {
+    let action = Action {
+        on: true,
+        at: at(7, 5) + DAY,
+    };
+
+    assert_eq!((action.hour(), action.minute()), (7, 5));
+}
+
+#[test]
+fn bytes_round_trip() {
+    let schedule = Schedule::new(23, 59, 23).unwrap();
+
+    assert_eq!(Schedule::from_bytes(&schedule.to_bytes()), Some(schedule));
+    assert_eq!(Schedule::from_bytes(&[0; 8]), None);
+    assert_eq!(Schedule::from_bytes(&[0xff; 8]), None);
+}
@@ -0,0 +1,163 @@
+use embassy_time::Duration;
+
+use crate::{
+    measure::Reading,
+    mock_time::{self, Instant},
+    session::{hms, Session},
+    units::{self, from_milli, milli, ZERO},
+};
+
+fn reading(volts: i32, amps: i32) -> Reading {
+    Reading {
+        volts: Some(from_milli(volts)),
+        amps: Some(from_milli(amps)),
+        watts: Some(from_milli(volts * amps / 1_000)),
+    }
+}
+
+#[test]
+fn nothing_before_the_first_start() {
+    mock_time::set(Duration::from_secs(1));
+    let mut session = Session::new();
+
+    session.record(Instant::now(), &reading(5_000, 1_000), ZERO);
+    session.fault();
+
+    assert!(!session.is_running());
+    assert_eq!(session.report(), None);
+    assert_eq!(session.stop(Instant::now()), None);
+}
+
+#[test]
+fn keeps_min_mean_and_max() {
+    mock_time::set(Duration::from_secs(1));
+    let mut session = Session::new();
+    session.start(Instant::now());
+
+    for (volts, amps) in [(5_000, 1_000), (4_800, 3_000), (5_200, 2_000)] {
+        mock_time::advance(Duration::from_secs(1));
+        session.record(Instant::now(), &reading(volts, amps), ZERO);
+    }
+
+    let report = session.report().unwrap();
+    let (min, mean, max) = report.volts.summary().unwrap();
+    assert_eq!((milli(min), milli(mean), milli(max)), (4_800, 5_000, 5_200));
+
+    let (min, mean, max) = report.amps.summary().unwrap();
+    assert_eq!((milli(min), milli(mean), milli(max)), (1_000, 2_000, 3_000));
+    assert_eq!(report.duration_ms, 3_000);
+}
+
+#[test]
+fn missing_quantities_are_left_out() {
+    mock_time::set(Duration::from_secs(1));
+    let mut session = Session::new();
+    session.start(Instant::now());
+
+    mock_time::advance(Duration::from_secs(1));
+    session.record(
+        Instant::now(),
+        &Reading {
+            volts: Some(from_milli(5_000)),
+            amps: None,
+            watts: None,
+        },
+        ZERO,
+    );
+
+    let report = session.report().unwrap();
+    assert!(report.volts.summary().is_some());
+    assert_eq!(report.amps.summary(), None);
+    assert_eq!(milli(units::energy_wh(report.energy)), 0);
+}
+
+#[test]
+fn counts_energy_over_the_session() {
+    mock_time::set(Duration::from_secs(1));
+    let mut session = Session::new();
+    session.start(Instant::now());
+
+    // 10 W for six minutes is 1 Wh.
+    for _ in 0..360 {
+        mock_time::advance(Duration::from_secs(1));
+        session.record(Instant::now(), &reading(5_000, 2_000), ZERO);
+    }
+
+    let report = session.stop(Instant::now()).unwrap();
+    assert!((units::to_f64(units::energy_wh(report.energy)) - 1.0).abs() < 1e-6);
+    assert_eq!(hms(report.duration_ms), (0, 6, 0));
+}
+
+#[test]
+fn faults_only_count_while_running() {
+    mock_time::set(Duration::from_secs(1));
+    let mut session = Session::new();
+    session.start(Instant::now());
+
+    session.fault();
+    session.fault();
+    mock_time::advance(Duration::from_secs(5));
+    let report = session.stop(Instant::now()).unwrap();
+    session.fault();
+
+    assert_eq!(report.faults, 2);
+    assert_eq!(report.duration_ms, 5_000);
+    assert_eq!(session.report(), Some(report));
+    assert!(!session.is_running());
+}
+
+#[test]
+fn start_drops_the_previous_report() {
+    mock_time::set(Duration::from_secs(1));
+    let mut session = Session::new();
+    session.start(Instant::now());
+    session.fault();
+    session.stop(Instant::now());
+
+    session.start(Instant::now());
+
+    assert!(session.is_running());
+    assert_eq!(session.report().unwrap().faults, 0);
+}
+
+#[test]
+fn times_the_current_near_the_ocp() {
+    mock_time::set(Duration::from_secs(1));
+    let mut session = Session::new();
+    let ocp = from_milli(3_000);
+    session.start(Instant::now());
+
+    // A second each of 2 A, 2.5 A (above 80 %), and 2.9 A and 3 A (above both).
+    for amps in [2_000, 2_500, 2_900, 3_000] {
+        mock_time::advance(Duration::from_secs(1));
+        session.record(Instant::now(), &reading(5_000, amps), ocp);
+    }
+
+    let report = session.report().unwrap();
+    assert_eq!(report.above_ocp_ms(0), 3_000);
+    assert_eq!(report.above_ocp_ms(1), 2_000);
+    assert_eq!(report.above_ocp_percent(0), 75);
+    assert_eq!(report.above_ocp_percent(1), 50);
+}
+
+#[test]
+fn nothing_near_an_ocp_that_is_off() {
+    mock_time::set(Duration::from_secs(1));
+    let mut session = Session::new();
+    session.start(Instant::now());
+
+    mock_time::advance(Duration::from_secs(1));
+    session.record(Instant::now(), &reading(5_000, 9_000), ZERO);
+
+    let report = session.report().unwrap();
+    assert_eq!(report.above_ocp_ms(0), 0);
+    assert_eq!(report.above_ocp_percent(1), 0);
+}
+
+#[test]
+fn duration_as_hours_minutes_seconds() {
+    assert_eq!(hms(0), (0, 0, 0));
+    assert_eq!(hms(59_999), (0, 0, 59));
+    assert_eq!(hms(3_723_000), (1, 2, 3));
+    assert_eq!(hms(100 * 3_600_000), (100, 0, 0));
+}
@@ -0,0 +1,60 @@
+use crate::{
+    setup::{step_ocp, step_shunt, Setup, SetupStep, SETUP_STEPS, SHUNTS_MICRO_OHMS},
+    types::Direction,
+    units::{from_milli, milli, ZERO},
+};
+
+#[test]
+fn steps_end_with_the_shunt() {
+    assert_eq!(SETUP_STEPS[0].next(), Some(SetupStep::Ocp));
+    assert_eq!(SetupStep::Ocp.next(), Some(SetupStep::Shunt));
+    assert_eq!(SetupStep::Shunt.next(), None);
+}
+
+#[test]
+fn record_round_trips() {
+    let setup = Setup {
+        direction: Direction::Reversed,
+        ocp: from_milli(3_500),
+        shunt_micro_ohms: 5_000,
+    };
+
+    assert_eq!(Setup::from_bytes(&setup.to_bytes()), Some(setup));
+    assert_eq!(
+        Setup::from_bytes(&Setup::new(10_000).to_bytes()),
+        Some(Setup::new(10_000))
+    );
+}
+
+#[test]
+fn unknown_rotation_is_no_record() {
+    let mut buf = Setup::new(10_000).to_bytes();
+    buf[0] = 0xff;
+
+    assert_eq!(Setup::from_bytes(&buf), None);
+}
+
+#[test]
+fn ocp_steps_between_off_and_the_maximum() {
+    let max = from_milli(10_000);
+
+    assert_eq!(milli(step_ocp(ZERO, true, max)), 500);
+    assert_eq!(step_ocp(from_milli(500), false, max), ZERO);
+    assert_eq!(step_ocp(ZERO, false, max), ZERO);
+    assert_eq!(step_ocp(max, true, max), max);
+    assert_eq!(step_ocp(from_milli(9_800), true, max), max);
+}
+
+#[test]
+fn shunt_steps_through_common_values() {
+    assert_eq!(step_shunt(10_000, true), 20_000);
+    assert_eq!(step_shunt(10_000, false), 5_000);
+
+    // An odd shunt goes to the next common one either way.
+    assert_eq!(step_shunt(15_000, true), 20_000);
+    assert_eq!(step_shunt(15_000, false), 10_000);
+
+    let (first, last) = (SHUNTS_MICRO_OHMS[0], SHUNTS_MICRO_OHMS[6]);
+    assert_eq!(step_shunt(first, false), first);
+    assert_eq!(step_shunt(last, true), last);
+}
@@ -0,0 +1,59 @@
+use ed25519_compact::{KeyPair, Seed};
+
+use crate::signature::{image_len, parse_key, verify, KEY_LEN, SIGNATURE_LEN};
+
+const IMAGE: &[u8] = b"\x00\x20\x00\x20firmware image";
+
+fn key_pair() -> KeyPair {
+    KeyPair::from_seed(Seed::new([7; 32]))
+}
+
+fn signed(key_pair: &KeyPair, image: &[u8]) -> Vec<u8> {
+    let mut signed = image.to_vec();
+    signed.extend_from_slice(key_pair.sk.sign(image, None).as_ref());
+
+    signed
+}
+
+fn public_key(key_pair: &KeyPair) -> [u8; KEY_LEN] {
+    *key_pair.pk
+}
+
+#[test]
+fn parses_the_key_from_hex() {
+    let key = parse_key("000102030405060708090a0b0c0d0e0f101112131415161718191A1B1C1D1E1F");
+
+    assert_eq!(key, core::array::from_fn(|i| i as u8));
+}
+
+#[test]
+fn accepts_an_image_signed_with_the_key() {
+    let key_pair = key_pair();
+
+    assert!(verify(&public_key(&key_pair), &signed(&key_pair, IMAGE)));
+}
+
+#[test]
+fn refuses_a_changed_image() {
+    let key_pair = key_pair();
+    let mut signed = signed(&key_pair, IMAGE);
+    signed[4] ^= 1;
+
+    assert!(!verify(&public_key(&key_pair), &signed));
+}
+
+#[test]
+fn refuses_an_image_signed_with_another_key() {
+    let other = KeyPair::from_seed(Seed::new([8; 32]));
+
+    assert!(!verify(&public_key(&key_pair()), &signed(&other, IMAGE)));
+}
+
+#[test]
+fn refuses_an_image_without_a_signature() {
+    let key_pair = key_pair();
+
+    assert_eq!(image_len(SIGNATURE_LEN), None);
+    assert!(!verify(&public_key(&key_pair), IMAGE));
+    assert!(!verify(&public_key(&key_pair), &[0; SIGNATURE_LEN]));
+}
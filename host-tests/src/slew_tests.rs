@@ -0,0 +1,91 @@
+use embassy_time::Duration;
+
+use crate::{
+    mock_time::{self, Instant},
+    slew::{SlewAlarm, SlewKind, SlewLimits, SlewMonitor},
+    units::from_milli,
+};
+
+const LIMITS: SlewLimits = SlewLimits {
+    volts_milli: 2_000,
+    amps_milli: 1_000,
+    trip: false,
+};
+
+fn update(monitor: &mut SlewMonitor, mv: i32, ma: i32) -> Option<SlewAlarm> {
+    mock_time::advance(Duration::from_millis(500));
+    monitor.update(Instant::now(), from_milli(mv), from_milli(ma), &LIMITS)
+}
+
+#[test]
+fn current_step_alarms_once() {
+    mock_time::set(Duration::from_secs(1));
+    let mut monitor = SlewMonitor::new();
+
+    assert_eq!(update(&mut monitor, 5_000, 100), None);
+    assert_eq!(update(&mut monitor, 5_000, 500), None);
+    assert_eq!(
+        update(&mut monitor, 5_000, 1_500),
+        Some(SlewAlarm {
+            kind: SlewKind::Amps,
+            rate_milli: 2_000
+        })
+    );
+    assert_eq!(update(&mut monitor, 5_000, 2_500), None);
+    assert_eq!(update(&mut monitor, 5_000, 2_600), None);
+    assert_eq!(
+        update(&mut monitor, 5_000, 1_000).map(|alarm| alarm.rate_milli),
+        Some(-3_200)
+    );
+}
+
+#[test]
+fn voltage_alarm_first() {
+    mock_time::set(Duration::from_secs(1));
+    let mut monitor = SlewMonitor::new();
+
+    update(&mut monitor, 9_000, 0);
+
+    assert_eq!(
+        update(&mut monitor, 7_000, 2_000).map(|alarm| alarm.kind),
+        Some(SlewKind::Volts)
+    );
+}
+
+#[test]
+fn reset_skips_the_jump() {
+    mock_time::set(Duration::from_secs(1));
+    let mut monitor = SlewMonitor::new();
+
+    update(&mut monitor, 5_000, 0);
+    monitor.reset();
+
+    assert_eq!(update(&mut monitor, 5_000, 3_000), None);
+}
+
+#[test]
+fn zero_limit_is_off() {
+    mock_time::set(Duration::from_secs(1));
+    let mut monitor = SlewMonitor::new();
+    let off = SlewLimits::off();
+
+    monitor.update(Instant::now(), from_milli(5_000), from_milli(0), &off);
+    mock_time::advance(Duration::from_millis(10));
+
+    assert_eq!(
+        monitor.update(Instant::now(), from_milli(20_000), from_milli(5_000), &off),
+        None
+    );
+}
+
+#[test]
+fn bytes_round_trip() {
+    let limits = SlewLimits {
+        volts_milli: 12_340,
+        amps_milli: 655_350,
+        trip: true,
+    };
+
+    assert_eq!(SlewLimits::from_bytes(&limits.to_bytes()), limits);
+    assert_eq!(SlewLimits::from_bytes(&[0; 8]), SlewLimits::off());
+}
@@ -0,0 +1,68 @@
+use core::convert::Infallible;
+
+use embassy_futures::block_on;
+use embedded_hal_async::spi::{ErrorType, Operation, SpiDevice};
+
+use crate::spi_bus::ChunkedSpi;
+
+/// The length of every write and how many transactions were run.
+#[derive(Default)]
+struct Log {
+    transactions: usize,
+    writes: Vec<usize>,
+}
+
+struct Recorder<'a>(&'a mut Log);
+
+impl ErrorType for Recorder<'_> {
+    type Error = Infallible;
+}
+
+impl SpiDevice for Recorder<'_> {
+    async fn transaction(
+        &mut self,
+        operations: &mut [Operation<'_, u8>],
+    ) -> Result<(), Self::Error> {
+        self.0.transactions += 1;
+
+        for operation in operations.iter() {
+            if let Operation::Write(bytes) = operation {
+                self.0.writes.push(bytes.len());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[test]
+fn long_writes_are_split() {
+    let mut log = Log::default();
+
+    block_on(ChunkedSpi::new(Recorder(&mut log), 4).write(&[0; 10])).unwrap();
+
+    assert_eq!(log.writes, [4, 4, 2]);
+    assert_eq!(log.transactions, 3);
+}
+
+#[test]
+fn short_writes_pass_through() {
+    let mut log = Log::default();
+
+    block_on(ChunkedSpi::new(Recorder(&mut log), 4).write(&[0; 4])).unwrap();
+
+    assert_eq!(log.writes, [4]);
+    assert_eq!(log.transactions, 1);
+}
+
+#[test]
+fn reads_are_not_split() {
+    let mut log = Log::default();
+    let mut read = [0; 8];
+    let mut ops = [Operation::Write(&[0; 10]), Operation::Read(&mut read)];
+
+    block_on(ChunkedSpi::new(Recorder(&mut log), 4).transaction(&mut ops)).unwrap();
+
+    assert_eq!(log.writes, [10]);
+    assert_eq!(log.transactions, 1);
+}
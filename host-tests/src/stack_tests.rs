@@ -0,0 +1,26 @@
+use crate::stack::{unused_words, StackUsage, PAINT};
+
+#[test]
+fn counts_the_paint_up_to_the_deepest_frame() {
+    let stack = [PAINT, PAINT, PAINT, 0x2000_1234, PAINT, 0];
+
+    assert_eq!(unused_words(stack.into_iter()), 3);
+}
+
+#[test]
+fn unpainted_stack_has_no_headroom() {
+    assert_eq!(unused_words([0u32, PAINT].into_iter()), 0);
+    assert_eq!(unused_words([PAINT; 4].into_iter()), 4);
+}
+
+#[test]
+fn free_and_percent_follow_the_peak() {
+    let usage = StackUsage {
+        size: 8_192,
+        peak: 2_048,
+        statics: 20_000,
+    };
+
+    assert_eq!(usage.free(), 6_144);
+    assert_eq!(usage.peak_percent(), 25);
+}
@@ -0,0 +1,64 @@
+use crate::{
+    telemetry::{page_name, SampleBatch, BATCH_LEN},
+    types::{AboutView, Page, PowerInfo},
+    units::from_milli,
+};
+
+fn reading(millivolts: i32, milliamps: i32) -> PowerInfo {
+    PowerInfo {
+        volts: from_milli(millivolts),
+        amps: from_milli(milliamps),
+        watts: from_milli(millivolts / 1000 * milliamps),
+    }
+}
+
+#[test]
+fn batch_fills_after_batch_len_readings() {
+    let mut batch = SampleBatch::new();
+
+    for i in 0..BATCH_LEN as u64 - 1 {
+        assert!(!batch.push(1_000 + i * 10, &reading(5_000, 1_500)));
+    }
+    assert!(batch.push(1_090, &reading(12_000, 250)));
+
+    assert_eq!(batch.start_ms, 1_000);
+    assert_eq!(batch.end_ms, 1_090);
+    assert_eq!(batch.millivolts.len(), BATCH_LEN);
+    assert_eq!(batch.millivolts[0], 5_000);
+    assert_eq!(batch.milliamps[BATCH_LEN - 1], 250);
+    assert_eq!(batch.milliwatts[BATCH_LEN - 1], 3_000);
+}
+
+#[test]
+fn next_batch_counts_on_and_starts_empty() {
+    let mut batch = SampleBatch::new();
+
+    for i in 0..BATCH_LEN as u64 {
+        batch.push(i * 10, &reading(5_000, 100));
+    }
+    batch.next();
+
+    assert_eq!(batch.seq, 1);
+    assert!(batch.millivolts.is_empty());
+    assert!(batch.milliamps.is_empty());
+    assert!(batch.milliwatts.is_empty());
+
+    assert!(!batch.push(500, &reading(5_000, 100)));
+    assert_eq!(batch.start_ms, 500);
+}
+
+#[test]
+fn page_names_are_single_words() {
+    for page in [
+        Page::Monitor,
+        Page::OCP,
+        Page::Cable,
+        Page::About(AboutView::Info),
+    ] {
+        let name = page_name(page);
+
+        assert!(!name.is_empty());
+        assert!(!name.contains(' '));
+    }
+    assert_eq!(page_name(Page::Monitor), "monitor");
+}
@@ -0,0 +1,39 @@
+use embassy_time::Duration;
+
+use crate::timing::{self, Section};
+
+// The timings are one global table, so everything touching them stays in this one test.
+#[test]
+fn mean_and_worst_case() {
+    timing::reset();
+
+    let stats = timing::stats(Section::Read);
+    assert_eq!(stats.count, 0);
+    assert_eq!(stats.mean_us, 0);
+    assert_eq!(stats.max_us, 0);
+
+    timing::record(Section::Read, Duration::from_micros(1_000));
+    timing::record(Section::Read, Duration::from_micros(3_000));
+    timing::record(Section::Read, Duration::from_micros(2_000));
+
+    let stats = timing::stats(Section::Read);
+    assert_eq!(stats.count, 3);
+    assert_eq!(stats.mean_us, 2_000);
+    assert_eq!(stats.max_us, 3_000);
+
+    assert_eq!(timing::stats(Section::Display).count, 0);
+
+    // Near overflow the history is halved rather than the total wrapping around.
+    timing::reset();
+    for _ in 0..10 {
+        timing::record(Section::Loop, Duration::from_secs(1_000));
+    }
+
+    let stats = timing::stats(Section::Loop);
+    assert!(stats.count < 10);
+    assert_eq!(stats.mean_us, 1_000_000_000);
+    assert_eq!(stats.max_us, 1_000_000_000);
+
+    timing::reset();
+    assert_eq!(timing::stats(Section::Loop).count, 0);
+}
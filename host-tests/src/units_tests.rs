@@ -0,0 +1,98 @@
+use embassy_time::Duration;
+
+use husb238::SrcPdo;
+
+use crate::{
+    types::{capped_ocp, pdo_matches, ControlSource, OutputMode},
+    units::{self, from_milli, milli, NO_ENERGY, ZERO},
+};
+
+#[test]
+fn milli_round_trip() {
+    assert_eq!(milli(from_milli(5_000)), 5_000);
+    assert_eq!(milli(from_milli(-250)), -250);
+    assert_eq!(milli(ZERO), 0);
+}
+
+#[test]
+fn arithmetic() {
+    assert_eq!(
+        milli(units::mul(from_milli(5_000), from_milli(1_500))),
+        7_500
+    );
+    assert_eq!(milli(units::div(from_milli(9_000), 4)), 2_250);
+    assert_eq!(milli(units::percent(from_milli(20_000), 5)), 1_000);
+}
+
+#[test]
+fn energy_accumulates_short_steps() {
+    let mut energy = NO_ENERGY;
+
+    // 1 W for 50 s in main loop sized steps.
+    for _ in 0..1_000 {
+        units::add_energy(
+            &mut energy,
+            Some(from_milli(1_000)),
+            Duration::from_millis(50),
+        );
+    }
+
+    // 13.9 mWh.
+    assert_eq!(milli(units::energy_wh(energy)), 13);
+}
+
+#[test]
+fn failed_reading_adds_no_energy() {
+    let mut energy = NO_ENERGY;
+
+    units::add_energy(
+        &mut energy,
+        Some(from_milli(1_000)),
+        Duration::from_secs(36),
+    );
+    units::add_energy(&mut energy, None, Duration::from_secs(36));
+
+    assert_eq!(milli(units::energy_wh(energy)), 10);
+}
+
+#[test]
+fn formats_like_float() {
+    assert_eq!(units::fixed(from_milli(12_345), 3, 7), " 12.345");
+    assert_eq!(units::fixed(from_milli(5_000), 1, 4), " 5.0");
+}
+
+#[test]
+fn pdo_matches_within_tolerance() {
+    assert!(pdo_matches(SrcPdo::_9v, from_milli(9_000)));
+    assert!(pdo_matches(SrcPdo::_9v, from_milli(8_600)));
+    assert!(pdo_matches(SrcPdo::_20v, from_milli(21_000)));
+    assert!(!pdo_matches(SrcPdo::_9v, from_milli(5_000)));
+    assert!(!pdo_matches(SrcPdo::_20v, from_milli(15_000)));
+    assert!(!pdo_matches(SrcPdo::_5v, ZERO));
+}
+
+#[test]
+fn momentary_output_goes_on_only_by_hand() {
+    let mode = OutputMode::Momentary;
+
+    assert!(mode.takes(true, ControlSource::Local));
+    assert!(!mode.takes(true, ControlSource::Remote));
+    assert!(!mode.takes(true, ControlSource::Schedule));
+    assert!(mode.takes(false, ControlSource::Remote));
+    assert!(mode.takes(false, ControlSource::Schedule));
+
+    assert!(OutputMode::Latching.takes(true, ControlSource::Remote));
+    assert!(OutputMode::Latching.takes(true, ControlSource::Schedule));
+}
+
+#[test]
+fn ocp_capped_to_contract() {
+    let contract = from_milli(1_500);
+
+    assert_eq!(capped_ocp(from_milli(5_000), contract), contract);
+    assert_eq!(capped_ocp(ZERO, contract), contract);
+    assert_eq!(capped_ocp(from_milli(1_000), contract), from_milli(1_000));
+    assert_eq!(capped_ocp(contract, contract), contract);
+    // No contract reported yet.
+    assert_eq!(capped_ocp(from_milli(5_000), ZERO), from_milli(5_000));
+}
@@ -0,0 +1,78 @@
+use embassy_time::Duration;
+
+use crate::{
+    mock_time::{self, Instant},
+    types::PowerInfo,
+    units::from_milli,
+    utilization::{Utilization, UtilizationMonitor, WARN_AFTER},
+};
+
+fn power(mv: i32, ma: i32) -> PowerInfo {
+    PowerInfo {
+        volts: from_milli(mv),
+        amps: from_milli(ma),
+        watts: from_milli(mv / 1_000 * ma),
+    }
+}
+
+fn of(mv: i32, ma: i32) -> Option<Utilization> {
+    // A 20 V 3 A contract.
+    Utilization::of(&power(mv, ma), from_milli(20_000), from_milli(3_000))
+}
+
+#[test]
+fn percent_of_current_and_power() {
+    assert_eq!(
+        of(20_000, 1_500),
+        Some(Utilization {
+            amps_percent: 50,
+            watts_percent: 50,
+        })
+    );
+
+    // Droop under load lowers the power share, not the current share.
+    let utilization = of(18_000, 3_000).unwrap();
+    assert_eq!(utilization.amps_percent, 100);
+    assert_eq!(utilization.watts_percent, 90);
+    assert_eq!(utilization.peak(), 100);
+}
+
+#[test]
+fn no_contract_no_percent() {
+    assert_eq!(
+        Utilization::of(&power(5_000, 1_000), from_milli(5_000), from_milli(0)),
+        None
+    );
+    assert_eq!(
+        Utilization::of(&power(5_000, 1_000), from_milli(0), from_milli(3_000)),
+        None
+    );
+}
+
+#[test]
+fn clamps_the_percent() {
+    assert_eq!(of(20_000, -100).unwrap().amps_percent, 0);
+    assert_eq!(of(20_000, 100_000).unwrap().amps_percent, 999);
+}
+
+#[test]
+fn warns_only_when_sustained() {
+    mock_time::set(Duration::from_secs(1));
+    let mut monitor = UtilizationMonitor::new();
+
+    assert!(!monitor.update(Instant::now(), of(20_000, 2_850)));
+
+    mock_time::advance(WARN_AFTER - Duration::from_millis(100));
+    assert!(!monitor.update(Instant::now(), of(20_000, 2_850)));
+
+    mock_time::advance(Duration::from_millis(100));
+    assert!(monitor.update(Instant::now(), of(20_000, 2_850)));
+
+    // Dropping to 90% ends the warning and starts the wait over.
+    assert!(!monitor.update(Instant::now(), of(20_000, 2_700)));
+    assert!(!monitor.update(Instant::now(), of(20_000, 2_850)));
+
+    mock_time::advance(WARN_AFTER);
+    assert!(monitor.update(Instant::now(), of(20_000, 2_850)));
+    assert!(!monitor.update(Instant::now(), None));
+}
@@ -0,0 +1,99 @@
+use embassy_time::Duration;
+
+use crate::{
+    mock_time::{self, Instant},
+    units::{from_milli, milli},
+    watts::{PeakHold, SyncedPower, WattsSource, PEAK_HOLD, WATTS_SOURCES},
+};
+
+fn update(peak: &mut PeakHold, mw: i32) -> i32 {
+    milli(peak.update(Instant::now(), from_milli(mw)))
+}
+
+#[test]
+fn holds_the_peak() {
+    mock_time::set(Duration::from_secs(1));
+    let mut peak = PeakHold::new();
+
+    assert_eq!(update(&mut peak, 1_000), 1_000);
+    assert_eq!(update(&mut peak, 5_000), 5_000);
+
+    mock_time::advance(Duration::from_millis(500));
+    assert_eq!(update(&mut peak, 2_000), 5_000);
+    assert_eq!(update(&mut peak, 6_000), 6_000);
+}
+
+#[test]
+fn follows_again_after_the_hold() {
+    mock_time::set(Duration::from_secs(1));
+    let mut peak = PeakHold::new();
+
+    update(&mut peak, 5_000);
+
+    mock_time::advance(PEAK_HOLD);
+    assert_eq!(update(&mut peak, 2_000), 2_000);
+    assert_eq!(update(&mut peak, 1_000), 2_000);
+
+    peak.reset();
+    assert_eq!(update(&mut peak, 1_000), 1_000);
+}
+
+#[test]
+fn sources_cycle() {
+    for source in WATTS_SOURCES {
+        assert_eq!(source.next().prev(), source);
+        assert_eq!(WattsSource::parse(source.as_str()), Some(source));
+    }
+
+    assert_eq!(WattsSource::Peak.next(), WattsSource::Register);
+    assert_eq!(WattsSource::Register.prev(), WattsSource::Peak);
+}
+
+/// Feeds a load drawing `high_ma` for the first `on` of every `period` samples, 10 ms apart, at
+/// 5 V, and returns the last synced watts in milli-watts.
+fn pulsed(synced: &mut SyncedPower, samples: usize, period: usize, on: usize, high_ma: i32) -> i32 {
+    let mut watts = 0;
+
+    for i in 0..samples {
+        let amps = if i % period < on { high_ma } else { 0 };
+
+        mock_time::advance(Duration::from_millis(10));
+        watts = milli(synced.update(Instant::now(), from_milli(5_000), from_milli(amps)));
+    }
+
+    watts
+}
+
+#[test]
+fn synced_averages_whole_periods() {
+    mock_time::set(Duration::from_secs(1));
+    let mut synced = SyncedPower::new();
+
+    // One sample in seven at 7 A is 1 A on average, 5 W.
+    let watts = pulsed(&mut synced, 400, 7, 1, 7_000);
+
+    assert!((watts - 5_000).abs() <= 10, "got {} mW", watts);
+}
+
+#[test]
+fn synced_follows_a_steady_load() {
+    mock_time::set(Duration::from_secs(1));
+    let mut synced = SyncedPower::new();
+
+    assert_eq!(pulsed(&mut synced, 1, 1, 1, 2_000), 10_000);
+
+    let watts = pulsed(&mut synced, 300, 1, 1, 2_000);
+
+    assert_eq!(watts, 10_000);
+}
+
+#[test]
+fn synced_starts_over_on_reset() {
+    mock_time::set(Duration::from_secs(1));
+    let mut synced = SyncedPower::new();
+
+    pulsed(&mut synced, 400, 7, 1, 7_000);
+    synced.reset();
+
+    assert_eq!(pulsed(&mut synced, 1, 1, 1, 1_000), 5_000);
+}
@@ -0,0 +1,7 @@
+/* STM32G071GB. Only the lower 64 KiB of the 128 KiB flash is linked: the upper half holds the
+   update staging area, the statistics journal and the settings, see `src/updater.rs`. */
+MEMORY
+{
+  FLASH : ORIGIN = 0x08000000, LENGTH = 64K
+  RAM   : ORIGIN = 0x20000000, LENGTH = 36K
+}
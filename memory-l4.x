@@ -0,0 +1,7 @@
+/* STM32L432KC. Only the lower 64 KiB of the flash is linked, for the same layout of the staging
+   area, the statistics journal and the settings as on the G0, see `src/updater.rs`. */
+MEMORY
+{
+  FLASH : ORIGIN = 0x08000000, LENGTH = 64K
+  RAM   : ORIGIN = 0x20000000, LENGTH = 64K
+}
@@ -0,0 +1,100 @@
+//! Lines to and from the console UART.
+
+use std::{
+    io::{self, Read, Write},
+    time::{Duration, Instant},
+};
+
+use serialport::SerialPort;
+
+use crate::protocol;
+
+/// The console's baud rate, see `main.rs` of the firmware.
+const BAUD_RATE: u32 = 115_200;
+
+/// How long a read waits for more bytes before it checks the deadline again.
+const POLL: Duration = Duration::from_millis(20);
+
+pub struct Link {
+    port: Box<dyn SerialPort>,
+    /// Bytes of a line not finished yet.
+    pending: Vec<u8>,
+}
+
+impl Link {
+    pub fn open(path: &str) -> io::Result<Self> {
+        let port = serialport::new(path, BAUD_RATE).timeout(POLL).open()?;
+
+        Ok(Self {
+            port,
+            pending: Vec::new(),
+        })
+    }
+
+    pub fn send(&mut self, command: &str) -> io::Result<()> {
+        self.port.write_all(command.as_bytes())?;
+        self.port.write_all(b"\r\n")?;
+        self.port.flush()
+    }
+
+    /// The next line, or `None` when none came in by `deadline`.
+    pub fn read_line(&mut self, deadline: Instant) -> io::Result<Option<String>> {
+        let mut buf = [0u8; 64];
+
+        loop {
+            if let Some(end) = self.pending.iter().position(|b| *b == b'\n') {
+                let line: Vec<u8> = self.pending.drain(..=end).collect();
+                let line = String::from_utf8_lossy(&line).trim().to_string();
+
+                if line.is_empty() {
+                    continue;
+                }
+                return Ok(Some(line));
+            }
+
+            if Instant::now() >= deadline {
+                return Ok(None);
+            }
+
+            match self.port.read(&mut buf) {
+                Ok(len) => self.pending.extend_from_slice(&buf[..len]),
+                Err(err) if err.kind() == io::ErrorKind::TimedOut => {}
+                Err(err) => return Err(err),
+            }
+        }
+    }
+
+    /// Every line until none came in for `quiet`, for commands that end without `OK`.
+    pub fn collect(&mut self, quiet: Duration) -> io::Result<Vec<String>> {
+        let mut lines = Vec::new();
+
+        while let Some(line) = self.read_line(Instant::now() + quiet)? {
+            lines.push(line);
+        }
+
+        Ok(lines)
+    }
+
+    /// Sends `command` and waits up to `timeout` for its `OK` or `ERR` line. Event lines coming in
+    /// meanwhile are printed to stderr.
+    pub fn command(&mut self, command: &str, timeout: Duration) -> Result<String, String> {
+        self.send(command).map_err(|err| err.to_string())?;
+
+        let deadline = Instant::now() + timeout;
+        loop {
+            let line = self
+                .read_line(deadline)
+                .map_err(|err| err.to_string())?
+                .ok_or_else(|| format!("no reply to `{command}`"))?;
+
+            if protocol::is_error(&line) {
+                return Err(line);
+            }
+            if protocol::is_reply(&line) {
+                return Ok(line);
+            }
+
+            eprintln!("{line}");
+        }
+    }
+}
@@ -0,0 +1,228 @@
+//! Drives a PD sink from the host over its console UART.
+//!
+//! Speaks the same line protocol as a terminal would, see `src/console.rs` of the firmware, which
+//! is why it lives in this workspace: a command changed there is changed here in the same commit.
+//! Build it for the host, as `.cargo/config.toml` defaults to the MCU target:
+//!
+//! ```sh
+//! cargo run -p pd-sink-cli --target x86_64-unknown-linux-gnu -- /dev/ttyUSB0 watch
+//! ```
+//!
+//! `apply` sends a file of console commands, one per line with `#` comments, so a set of stored
+//! settings such as `fuse 3 60`, `slew dv 2` or `ina226 shunt 10` can be put on a unit in one go.
+//! `update` streams a signed firmware image, see `src/signature.rs` of the firmware, with
+//! `fw begin`, `fw data` and `fw commit`; `dfu` reboots into the ROM bootloader for a full flash
+//! with `stm32flash` or STM32CubeProgrammer.
+
+mod link;
+mod protocol;
+
+use std::{
+    collections::VecDeque,
+    env, fs,
+    io::{self, Write},
+    process,
+    time::{Duration, Instant},
+};
+
+use link::Link;
+use protocol::{Status, FW_CHUNK};
+
+const USAGE: &str = "usage: pd-sink-cli <PORT> <COMMAND>
+
+commands:
+  watch               live readings and events
+  status              the readings once
+  out on|off          switch the output
+  pdo <volts>         request a PDO
+  ocp <amps>|off      set the OCP
+  history             the last five minutes as CSV, to stdout
+  session start|stop|report
+                      run a test session, or print its report
+  apply <FILE>        send the console commands in FILE
+  update <IMAGE.bin>  stream a signed firmware image and reboot into it
+  dfu                 reboot into the ROM bootloader
+  send <LINE...>      any other console command";
+
+/// How long a reply may pause before it counts as complete.
+const QUIET: Duration = Duration::from_millis(300);
+/// `fw begin` erases the staging area first.
+const ERASE_TIMEOUT: Duration = Duration::from_secs(10);
+const REPLY_TIMEOUT: Duration = Duration::from_secs(2);
+
+const WATCH_INTERVAL: Duration = Duration::from_millis(500);
+/// Event lines kept under the readings.
+const WATCH_EVENTS: usize = 10;
+
+fn main() {
+    let mut args = env::args().skip(1);
+    let (Some(port), Some(command)) = (args.next(), args.next()) else {
+        eprintln!("{USAGE}");
+        process::exit(2);
+    };
+    let args: Vec<String> = args.collect();
+
+    let mut link = Link::open(&port).unwrap_or_else(|err| {
+        eprintln!("{port}: {err}");
+        process::exit(1);
+    });
+
+    let result = match (command.as_str(), args.as_slice()) {
+        ("watch", []) => watch(&mut link, &port),
+        ("status", []) => send(&mut link, "status"),
+        ("out", [state]) if state == "on" || state == "off" => {
+            send(&mut link, &format!("out {state}"))
+        }
+        ("pdo", [volts]) => send(&mut link, &format!("pdo {volts}")),
+        ("ocp", [amps]) => send(&mut link, &format!("ocp {amps}")),
+        ("session", [cmd]) if ["start", "stop", "report"].contains(&cmd.as_str()) => {
+            send(&mut link, &format!("session {cmd}"))
+        }
+        ("history", []) => history(&mut link),
+        ("apply", [path]) => apply(&mut link, path),
+        ("update", [path]) => update(&mut link, path),
+        ("dfu", []) => link
+            .command("bootloader", REPLY_TIMEOUT)
+            .map(|reply| println!("{reply}")),
+        ("send", line) if !line.is_empty() => send(&mut link, &line.join(" ")),
+        _ => {
+            eprintln!("{USAGE}");
+            process::exit(2);
+        }
+    };
+
+    if let Err(err) = result {
+        eprintln!("{err}");
+        process::exit(1);
+    }
+}
+
+/// Sends `line` and prints the reply, failing on an `ERR` line.
+fn send(link: &mut Link, line: &str) -> Result<(), String> {
+    link.send(line).map_err(|err| err.to_string())?;
+
+    let mut failed = None;
+    for reply in link.collect(QUIET).map_err(|err| err.to_string())? {
+        if protocol::is_error(&reply) {
+            failed = Some(reply);
+        } else {
+            println!("{reply}");
+        }
+    }
+
+    failed.map_or(Ok(()), Err)
+}
+
+fn watch(link: &mut Link, port: &str) -> Result<(), String> {
+    let mut events = VecDeque::new();
+
+    loop {
+        link.send("status").map_err(|err| err.to_string())?;
+
+        let mut status = Status::default();
+        let deadline = Instant::now() + WATCH_INTERVAL;
+        while let Some(line) = link.read_line(deadline).map_err(|err| err.to_string())? {
+            if !status.take(&line) {
+                if events.len() == WATCH_EVENTS {
+                    events.pop_front();
+                }
+                events.push_back(line);
+            }
+        }
+
+        draw(port, &status, &events).map_err(|err| err.to_string())?;
+    }
+}
+
+fn draw(port: &str, status: &Status, events: &VecDeque<String>) -> io::Result<()> {
+    let dash = || "-".to_string();
+    let mut out = io::stdout().lock();
+
+    // Clear the screen and go home.
+    write!(out, "\x1b[2J\x1b[H")?;
+    writeln!(out, "pd-sink on {port}, Ctrl-C to quit\n")?;
+    writeln!(
+        out,
+        "  {} V   {} A   {} W",
+        status.volts.clone().unwrap_or_else(dash),
+        status.amps.clone().unwrap_or_else(dash),
+        status.watts.clone().unwrap_or_else(dash)
+    )?;
+    if let Some(contract) = &status.contract {
+        writeln!(out, "  {contract}")?;
+    }
+    for line in &status.extra {
+        writeln!(out, "  {line}")?;
+    }
+    for fault in &status.faults {
+        writeln!(out, "  FAULT {fault}")?;
+    }
+
+    writeln!(out, "\nevents:")?;
+    for event in events {
+        writeln!(out, "  {event}")?;
+    }
+
+    out.flush()
+}
+
+/// Copies `export history` to stdout, up to its `END` line.
+fn history(link: &mut Link) -> Result<(), String> {
+    link.send("export history").map_err(|err| err.to_string())?;
+
+    loop {
+        let line = link
+            .read_line(Instant::now() + REPLY_TIMEOUT)
+            .map_err(|err| err.to_string())?
+            .ok_or("history export stalled")?;
+
+        if line == "END" {
+            return Ok(());
+        }
+        if protocol::is_error(&line) {
+            return Err(line);
+        }
+
+        println!("{line}");
+    }
+}
+
+/// Sends each command in the file at `path`, stopping at the first one that failed.
+fn apply(link: &mut Link, path: &str) -> Result<(), String> {
+    let text = fs::read_to_string(path).map_err(|err| format!("{path}: {err}"))?;
+
+    for (number, line) in text.lines().enumerate() {
+        let line = line.split('#').next().unwrap_or("").trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        println!("> {line}");
+        send(link, line).map_err(|err| format!("{path}:{}: {err}", number + 1))?;
+    }
+
+    Ok(())
+}
+
+fn update(link: &mut Link, path: &str) -> Result<(), String> {
+    let image = fs::read(path).map_err(|err| format!("{path}: {err}"))?;
+
+    link.command(&protocol::fw_begin(&image), ERASE_TIMEOUT)?;
+
+    for (i, chunk) in image.chunks(FW_CHUNK).enumerate() {
+        let offset = i * FW_CHUNK;
+
+        if let Err(err) = link.command(&protocol::fw_data(offset, chunk), REPLY_TIMEOUT) {
+            link.command("fw abort", REPLY_TIMEOUT).ok();
+            return Err(format!("at {offset}: {err}"));
+        }
+
+        eprint!("\r{}/{} bytes", offset + chunk.len(), image.len());
+    }
+    eprintln!();
+
+    let reply = link.command("fw commit", ERASE_TIMEOUT)?;
+    println!("{reply}");
+
+    Ok(())
+}
@@ -0,0 +1,93 @@
+//! The console commands and replies, as `src/console.rs` sends and parses them.
+
+/// Bytes per `fw data` command, the most `src/console.rs` takes in one line.
+pub const FW_CHUNK: usize = 32;
+
+pub fn fw_begin(image: &[u8]) -> String {
+    format!("fw begin {} {:08x}", image.len(), crc32(image))
+}
+
+pub fn fw_data(offset: usize, chunk: &[u8]) -> String {
+    let mut line = format!("fw data {offset} ");
+    for byte in chunk {
+        line.push_str(&format!("{byte:02x}"));
+    }
+
+    line
+}
+
+/// CRC-32/ISO-HDLC, the one `src/updater.rs` checks the staged image with.
+pub fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xffff_ffffu32;
+
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            if crc & 1 != 0 {
+                crc = (crc >> 1) ^ 0xedb8_8320;
+            } else {
+                crc >>= 1;
+            }
+        }
+    }
+
+    !crc
+}
+
+/// `OK ...` or `ERR ...`, the end of a command's reply.
+pub fn is_reply(line: &str) -> bool {
+    line == "OK" || line.starts_with("OK ") || line.starts_with("ERR ")
+}
+
+pub fn is_error(line: &str) -> bool {
+    line.starts_with("ERR ")
+}
+
+/// The `key=value` fields of a `status` line, e.g. `V=5.012 A=1.503 W=7.533`.
+pub fn fields(line: &str) -> impl Iterator<Item = (&str, &str)> {
+    line.split_whitespace()
+        .filter_map(|field| field.split_once('='))
+}
+
+/// What `status` prints, gathered from its lines.
+#[derive(Default)]
+pub struct Status {
+    pub volts: Option<String>,
+    pub amps: Option<String>,
+    pub watts: Option<String>,
+    /// `PDO=20.0V Max=3.00A OCP=2.50A Out=on Remote=no`.
+    pub contract: Option<String>,
+    /// Raw, average, RMS and second channel lines, as sent.
+    pub extra: Vec<String>,
+    pub faults: Vec<String>,
+}
+
+impl Status {
+    /// Takes `line` if it belongs to the `status` reply, returning whether it did.
+    pub fn take(&mut self, line: &str) -> bool {
+        if line.starts_with("V=") {
+            for (key, value) in fields(line) {
+                let value = Some(value.to_string());
+                match key {
+                    "V" => self.volts = value,
+                    "A" => self.amps = value,
+                    "W" => self.watts = value,
+                    _ => {}
+                }
+            }
+        } else if line.starts_with("PDO=") {
+            self.contract = Some(line.to_string());
+        } else if ["RAW ", "AVG ", "RMS ", "CH2 "]
+            .iter()
+            .any(|prefix| line.starts_with(prefix))
+        {
+            self.extra.push(line.to_string());
+        } else if let Some(fault) = line.strip_prefix("FAULT ") {
+            self.faults.push(fault.to_string());
+        } else {
+            return false;
+        }
+
+        true
+    }
+}
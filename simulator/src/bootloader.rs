@@ -0,0 +1,7 @@
+//! Stand-in for the firmware's `src/bootloader.rs`.
+
+/// There is no ROM bootloader on the host, so the simulator just ends.
+pub(crate) fn jump_to_bootloader() -> ! {
+    println!("jump to bootloader requested, exiting");
+    std::process::exit(0)
+}
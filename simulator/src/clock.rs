@@ -0,0 +1,19 @@
+//! Stand-in for the firmware's `src/clock.rs`, without an RTC to keep the time across runs.
+
+use embassy_sync::{blocking_mutex::raw::CriticalSectionRawMutex, mutex::Mutex};
+use embassy_time::Instant;
+
+/// Unix time in milliseconds at start, once set on the clock page.
+static EPOCH_MUTEX: Mutex<CriticalSectionRawMutex, Option<u64>> = Mutex::new(None);
+
+pub(crate) async fn set_unix_time(unix_secs: u64) {
+    let uptime_ms = Instant::now().as_millis();
+
+    *EPOCH_MUTEX.lock().await = Some((unix_secs * 1000).saturating_sub(uptime_ms));
+}
+
+pub(crate) async fn unix_secs() -> Option<u64> {
+    let epoch_ms = (*EPOCH_MUTEX.lock().await)?;
+
+    Some((epoch_ms + Instant::now().as_millis()) / 1000)
+}
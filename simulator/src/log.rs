@@ -0,0 +1,77 @@
+//! Stand-in for the firmware's `src/log.rs` that prints to stdout.
+//!
+//! The shared modules only use `{}` and `{:?}` placeholders, which read the same to defmt and to
+//! `format!`. There are no runtime levels; everything is printed.
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub(crate) enum Module {
+    Controller,
+    Display,
+    Console,
+    Output,
+    Pd,
+    Fieldbus,
+    Wifi,
+    Update,
+    Power,
+    Measure,
+    System,
+}
+
+macro_rules! log {
+    ($level:literal, $module:expr, $($arg:tt)*) => {
+        println!("{} {:?}: {}", $level, $module, format_args!($($arg)*))
+    };
+}
+
+macro_rules! error {
+    (target: $module:expr, $($arg:tt)*) => {
+        $crate::log::log!("ERROR", $module, $($arg)*)
+    };
+    ($($arg:tt)*) => {
+        $crate::log::error!(target: LOG_MODULE, $($arg)*)
+    };
+}
+
+macro_rules! warn {
+    (target: $module:expr, $($arg:tt)*) => {
+        $crate::log::log!("WARN", $module, $($arg)*)
+    };
+    ($($arg:tt)*) => {
+        $crate::log::warn!(target: LOG_MODULE, $($arg)*)
+    };
+}
+
+macro_rules! info {
+    (target: $module:expr, $($arg:tt)*) => {
+        $crate::log::log!("INFO", $module, $($arg)*)
+    };
+    ($($arg:tt)*) => {
+        $crate::log::info!(target: LOG_MODULE, $($arg)*)
+    };
+}
+
+macro_rules! debug {
+    (target: $module:expr, $($arg:tt)*) => {
+        $crate::log::log!("DEBUG", $module, $($arg)*)
+    };
+    ($($arg:tt)*) => {
+        $crate::log::debug!(target: LOG_MODULE, $($arg)*)
+    };
+}
+
+#[allow(unused_macros)]
+macro_rules! trace {
+    (target: $module:expr, $($arg:tt)*) => {
+        $crate::log::log!("TRACE", $module, $($arg)*)
+    };
+    ($($arg:tt)*) => {
+        $crate::log::trace!(target: LOG_MODULE, $($arg)*)
+    };
+}
+
+pub(crate) use log;
+#[allow(unused_imports)]
+pub(crate) use trace;
+#[allow(unused_imports)]
+pub(crate) use {debug, error, info, warn};
@@ -0,0 +1,315 @@
+//! Desktop simulator for the UI.
+//!
+//! Runs the firmware's `Display`, `Controller`, menu and button handling unchanged against an emulated
+//! ST7789 drawn in an SDL window. The Up and Down arrow keys are buttons A and B, and the readings
+//! are synthesized: a slowly varying load that only draws current while the output is on.
+//! `--replay <FILE>` plays a recording instead, see `src/replay.rs`, whatever the output does.
+//!
+//! Build it for the host, as `.cargo/config.toml` defaults to the MCU target, and with SDL2
+//! installed:
+//!
+//! ```sh
+//! cargo run -p simulator --target x86_64-unknown-linux-gnu
+//! cargo run -p simulator --target x86_64-unknown-linux-gnu -- --replay load.csv
+//! ```
+
+// The firmware modules are shared as a whole, not everything in them is used here.
+#![allow(dead_code)]
+
+#[path = "../../src/average.rs"]
+mod average;
+#[path = "../../src/button.rs"]
+mod button;
+#[path = "../../src/cable.rs"]
+mod cable;
+#[path = "../../src/calendar.rs"]
+mod calendar;
+#[path = "../../src/capture.rs"]
+mod capture;
+#[path = "../../src/cc_lines.rs"]
+mod cc_lines;
+#[path = "../../src/controller.rs"]
+mod controller;
+#[path = "../../src/conversion.rs"]
+mod conversion;
+#[path = "../../src/csv_log.rs"]
+mod csv_log;
+#[path = "../../src/data_lines.rs"]
+mod data_lines;
+#[path = "../../src/display.rs"]
+mod display;
+#[path = "../../src/entry.rs"]
+mod entry;
+#[path = "../../src/fan.rs"]
+mod fan;
+#[path = "../../src/fault.rs"]
+mod fault;
+#[path = "../../src/filter.rs"]
+mod filter;
+#[path = "../../src/fmt.rs"]
+mod fmt;
+#[path = "../../src/font.rs"]
+mod font;
+#[path = "../../src/heartbeat.rs"]
+mod heartbeat;
+#[path = "../../src/help.rs"]
+mod help;
+#[path = "../../src/measure.rs"]
+mod measure;
+#[path = "../../src/menu.rs"]
+mod menu;
+#[path = "../../src/output_stats.rs"]
+mod output_stats;
+#[path = "../../src/protection.rs"]
+mod protection;
+#[path = "../../src/rails.rs"]
+mod rails;
+#[path = "../../src/render.rs"]
+mod render;
+#[path = "../../src/replay.rs"]
+mod replay;
+#[path = "../../src/reset_cause.rs"]
+mod reset_cause;
+#[path = "../../src/rle.rs"]
+mod rle;
+#[path = "../../src/rms.rs"]
+mod rms;
+#[path = "../../src/schedule.rs"]
+mod schedule;
+#[path = "../../src/session.rs"]
+mod session;
+#[path = "../../src/setup.rs"]
+mod setup;
+#[path = "../../src/shared.rs"]
+mod shared;
+#[path = "../../src/stack.rs"]
+mod stack;
+#[path = "../../src/telemetry.rs"]
+mod telemetry;
+#[path = "../../src/theme.rs"]
+mod theme;
+#[path = "../../src/timing.rs"]
+mod timing;
+#[path = "../../src/types.rs"]
+mod types;
+#[path = "../../src/units.rs"]
+mod units;
+#[path = "../../src/utilization.rs"]
+mod utilization;
+#[path = "../../src/watts.rs"]
+mod watts;
+
+mod bootloader;
+mod clock;
+mod log;
+mod panel;
+mod screenshot;
+mod setup_settings;
+
+use embassy_executor::Spawner;
+use embassy_time::{Duration, Instant, Timer};
+use embedded_graphics_simulator::{sdl2::Keycode, OutputSettingsBuilder, SimulatorEvent, Window};
+use husb238::Current;
+
+use crate::{
+    average::IntervalAverage,
+    button::Button,
+    controller::Controller,
+    display::Display,
+    measure::Reading,
+    panel::{NoopPin, Panel},
+    replay::Replay,
+    rms::RollingRms,
+    shared::{
+        AVAILABLE_VOLT_CURR_MUTEX, AVERAGE_INTERVAL_MUTEX, BTN_A_STATE_CHANNEL,
+        BTN_B_STATE_CHANNEL, CAPTURE_MUTEX, OUTPUT_MUTEX, OUTPUT_PUBSUB, PDO_MUTEX,
+        POWER_INFO_MUTEX, SYSTEM_STATUS_MUTEX, WATTS_SOURCE_PUBSUB,
+    },
+    types::{pdo_volts, AvailableVoltCurr, PowerInfo, SystemStatus},
+    units,
+    utilization::{Utilization, UtilizationMonitor},
+};
+
+const FRAME_INTERVAL: Duration = Duration::from_millis(20);
+
+/// Current limit shown on the monitor page.
+const LIMIT_AMPS: f64 = 3.0;
+
+/// Source resistance the simulated load sees, for a bit of voltage droop.
+const SOURCE_OHMS: f64 = 0.05;
+
+#[embassy_executor::task]
+async fn controller_exec() {
+    let mut controller = Controller::new();
+    controller.task().await;
+}
+
+/// The recording given with `--replay`, if any.
+fn replay_arg() -> Option<Replay> {
+    let mut args = std::env::args().skip(1);
+
+    match (args.next().as_deref(), args.next()) {
+        (None, _) => None,
+        (Some("--replay"), Some(path)) => match std::fs::read_to_string(&path) {
+            Ok(recording) => Some(Replay::new(Box::leak(recording.into_boxed_str()))),
+            Err(err) => {
+                eprintln!("{path}: {err}");
+                std::process::exit(1);
+            }
+        },
+        _ => {
+            eprintln!("usage: simulator [--replay FILE]");
+            std::process::exit(2);
+        }
+    }
+}
+
+#[embassy_executor::main]
+async fn main(spawner: Spawner) {
+    // A source offering every fixed PDO, so the voltage menu has something to show.
+    *AVAILABLE_VOLT_CURR_MUTEX.lock().await = AvailableVoltCurr {
+        _5v: Some(Current::_3A),
+        _9v: Some(Current::_3A),
+        _12v: Some(Current::_3A),
+        _15v: Some(Current::_3A),
+        _18v: Some(Current::_3A),
+        _20v: Some(Current::_3A),
+    };
+
+    let panel = Panel::new();
+    let st7789 = st7789::ST7789::new(st7789::Config::default(), panel.spi(), panel.dc(), NoopPin);
+    let mut display = Display::new(st7789);
+    display.init().await.ok();
+
+    spawner.spawn(controller_exec()).ok();
+
+    let mut button_a = Button::new(&BTN_A_STATE_CHANNEL);
+    let mut button_b = Button::new(&BTN_B_STATE_CHANNEL);
+
+    let mut output_sub = OUTPUT_PUBSUB.subscriber().unwrap();
+    let mut watts_source_sub = WATTS_SOURCE_PUBSUB.subscriber().unwrap();
+
+    let settings = OutputSettingsBuilder::new().scale(2).build();
+    let mut window = Window::new("PD Sink", &settings);
+
+    let mut replay = replay_arg();
+
+    let started_at = Instant::now();
+    let mut power = PowerInfo::default();
+    let mut average = IntervalAverage::new();
+    let mut rms = RollingRms::new();
+    let mut contract_use = UtilizationMonitor::new();
+
+    loop {
+        heartbeat::beat(heartbeat::Task::Measure);
+
+        display.task().await;
+
+        if let Some(req) = output_sub.try_next_message_pure() {
+            *OUTPUT_MUTEX.lock().await = req.enabled;
+            display.update_output(req.enabled).await;
+        }
+
+        // Volts times amps is all the simulator has; only the unit follows the setting.
+        if let Some(source) = watts_source_sub.try_next_message_pure() {
+            display.update_watts_source(source).await;
+        }
+
+        let target_volts = units::to_f64(pdo_volts(*PDO_MUTEX.lock().await));
+        let millis = (Instant::now() - started_at).as_millis();
+        let reading = match &mut replay {
+            Some(replay) => replay.reading(millis),
+            None => {
+                let seconds = millis as f64 / 1000.0;
+                let amps = if *OUTPUT_MUTEX.lock().await {
+                    1.5 + 0.5 * (seconds / 2.0).sin()
+                } else {
+                    0.0
+                };
+                let volts = target_volts - amps * SOURCE_OHMS;
+
+                Reading {
+                    volts: Some(units::from_f64(volts)),
+                    amps: Some(units::from_f64(amps)),
+                    watts: Some(units::from_f64(volts * amps)),
+                }
+            }
+        };
+
+        reading.merge_into(&mut power);
+        *POWER_INFO_MUTEX.lock().await = power;
+        CAPTURE_MUTEX.lock().await.record(power.amps);
+        *SYSTEM_STATUS_MUTEX.lock().await = SystemStatus {
+            output: *OUTPUT_MUTEX.lock().await,
+            target_volts: units::from_f64(target_volts),
+            limit_amps: units::from_f64(LIMIT_AMPS),
+            ..SystemStatus::default()
+        };
+
+        // Dashes for what the recording has no reading of.
+        display.update_monitor_volts(reading.volts).await;
+        display.update_monitor_amps(reading.amps).await;
+        display.update_monitor_watts(reading.watts).await;
+
+        let interval = *AVERAGE_INTERVAL_MUTEX.lock().await;
+        if interval != average.interval() {
+            average.set_interval(interval);
+            display.update_average(None).await;
+        }
+        if let Some(mean) = average.update(Instant::now(), &power) {
+            display.update_average(Some(mean)).await;
+        }
+        if let Some(amps) = reading.amps {
+            display.update_rms(rms.update(Instant::now(), amps)).await;
+        }
+        display
+            .update_target_volts(units::from_f64(target_volts))
+            .await;
+        display.update_limit_amps(units::from_f64(LIMIT_AMPS)).await;
+
+        let utilization = Utilization::of(
+            &power,
+            units::from_f64(target_volts),
+            units::from_f64(LIMIT_AMPS),
+        );
+        let warning = contract_use.update(Instant::now(), utilization);
+        display.update_utilization(utilization, warning).await;
+        display.update_selected_pdo(*PDO_MUTEX.lock().await).await;
+
+        button_a.update().await;
+        button_b.update().await;
+
+        panel.show(&mut window);
+
+        for event in window.events() {
+            match event {
+                SimulatorEvent::KeyDown {
+                    keycode,
+                    repeat: false,
+                    ..
+                } => match keycode {
+                    Keycode::Up => {
+                        button_a.on_press().await;
+                    }
+                    Keycode::Down => {
+                        button_b.on_press().await;
+                    }
+                    _ => {}
+                },
+                SimulatorEvent::KeyUp { keycode, .. } => match keycode {
+                    Keycode::Up => {
+                        button_a.on_release().await;
+                    }
+                    Keycode::Down => {
+                        button_b.on_release().await;
+                    }
+                    _ => {}
+                },
+                SimulatorEvent::Quit => std::process::exit(0),
+                _ => {}
+            }
+        }
+
+        Timer::after(FRAME_INTERVAL).await;
+    }
+}
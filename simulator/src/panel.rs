@@ -0,0 +1,226 @@
+//! An ST7789 on the other end of a fake SPI bus.
+//!
+//! The firmware's driver is used unchanged; this side decodes the command stream it sends and
+//! paints the pixels into a [`SimulatorDisplay`]. Only what the driver uses is understood: column
+//! and row windows, memory writes, the 180° rotation of `MADCTL` and color inversion.
+//!
+//! Like the real one, the panel shows true colors with inversion on, as `st7789::Config` sets it
+//! up, and their complements with it off.
+
+use std::{cell::RefCell, convert::Infallible, rc::Rc};
+
+use embedded_graphics::{
+    pixelcolor::{raw::RawU16, Rgb565},
+    prelude::*,
+};
+use embedded_graphics_simulator::{SimulatorDisplay, Window};
+use embedded_hal::digital::{ErrorType as PinErrorType, OutputPin};
+use embedded_hal_async::spi::{ErrorType as SpiErrorType, Operation, SpiDevice};
+
+pub const WIDTH: u32 = 320;
+pub const HEIGHT: u32 = 172;
+
+/// Offset of the visible area in the controller's frame memory, see `st7789::Config`.
+const DX: u16 = 0;
+const DY: u16 = 34;
+
+const INVOFF: u8 = 0x20;
+const INVON: u8 = 0x21;
+const CASET: u8 = 0x2A;
+const RASET: u8 = 0x2B;
+const RAMWR: u8 = 0x2C;
+const MADCTL: u8 = 0x36;
+
+/// Row address order bit of `MADCTL`, set by the swapped orientations.
+const MADCTL_MY: u8 = 0x80;
+
+struct State {
+    display: SimulatorDisplay<Rgb565>,
+    data: bool,
+    command: u8,
+    params: Vec<u8>,
+    columns: (u16, u16),
+    rows: (u16, u16),
+    cursor: (u16, u16),
+    pixel: Option<u8>,
+    rotated: bool,
+    inverted: bool,
+}
+
+#[derive(Clone)]
+pub struct Panel(Rc<RefCell<State>>);
+
+impl Panel {
+    pub fn new() -> Self {
+        Self(Rc::new(RefCell::new(State {
+            display: SimulatorDisplay::new(Size::new(WIDTH, HEIGHT)),
+            data: false,
+            command: 0,
+            params: Vec::new(),
+            columns: (0, 0),
+            rows: (0, 0),
+            cursor: (0, 0),
+            pixel: None,
+            rotated: false,
+            inverted: false,
+        })))
+    }
+
+    pub fn spi(&self) -> PanelSpi {
+        PanelSpi(self.clone())
+    }
+
+    pub fn dc(&self) -> PanelDc {
+        PanelDc(self.clone())
+    }
+
+    /// Copies what the panel currently shows into `window`.
+    pub fn show(&self, window: &mut Window) {
+        let state = self.0.borrow();
+
+        if state.inverted {
+            window.update(&state.display);
+            return;
+        }
+
+        let mut complement = SimulatorDisplay::new(Size::new(WIDTH, HEIGHT));
+        for point in state.display.bounding_box().points() {
+            let raw = state.display.get_pixel(point).into_storage();
+            Pixel(point, Rgb565::from(RawU16::new(!raw)))
+                .draw(&mut complement)
+                .ok();
+        }
+        window.update(&complement);
+    }
+}
+
+impl State {
+    fn write(&mut self, bytes: &[u8]) {
+        if !self.data {
+            for byte in bytes {
+                self.command = *byte;
+                self.params.clear();
+                self.pixel = None;
+
+                match self.command {
+                    RAMWR => self.cursor = (self.columns.0, self.rows.0),
+                    INVOFF => self.inverted = false,
+                    INVON => self.inverted = true,
+                    _ => {}
+                }
+            }
+            return;
+        }
+
+        for byte in bytes {
+            match self.command {
+                CASET | RASET => {
+                    self.params.push(*byte);
+
+                    if self.params.len() == 4 {
+                        let start = u16::from_be_bytes([self.params[0], self.params[1]]);
+                        let end = u16::from_be_bytes([self.params[2], self.params[3]]);
+
+                        if self.command == CASET {
+                            self.columns = (start, end);
+                        } else {
+                            self.rows = (start, end);
+                        }
+                    }
+                }
+                MADCTL => self.rotated = byte & MADCTL_MY != 0,
+                RAMWR => match self.pixel.take() {
+                    None => self.pixel = Some(*byte),
+                    Some(high) => self.put(u16::from_be_bytes([high, *byte])),
+                },
+                _ => {}
+            }
+        }
+    }
+
+    fn put(&mut self, raw: u16) {
+        let (x, y) = self.cursor;
+
+        let x = x as i32 - DX as i32;
+        let y = y as i32 - DY as i32;
+        let point = if self.rotated {
+            Point::new(WIDTH as i32 - 1 - x, HEIGHT as i32 - 1 - y)
+        } else {
+            Point::new(x, y)
+        };
+
+        Pixel(point, Rgb565::from(RawU16::new(raw)))
+            .draw(&mut self.display)
+            .ok();
+
+        self.cursor.0 += 1;
+
+        if self.cursor.0 > self.columns.1 {
+            self.cursor.0 = self.columns.0;
+            self.cursor.1 += 1;
+
+            if self.cursor.1 > self.rows.1 {
+                self.cursor.1 = self.rows.0;
+            }
+        }
+    }
+}
+
+pub struct PanelSpi(Panel);
+
+impl SpiErrorType for PanelSpi {
+    type Error = Infallible;
+}
+
+impl SpiDevice for PanelSpi {
+    async fn transaction(
+        &mut self,
+        operations: &mut [Operation<'_, u8>],
+    ) -> Result<(), Self::Error> {
+        let mut state = self.0 .0.borrow_mut();
+
+        for operation in operations {
+            if let Operation::Write(bytes) = operation {
+                state.write(bytes);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The data/command line: high for parameters and pixels.
+pub struct PanelDc(Panel);
+
+impl PinErrorType for PanelDc {
+    type Error = Infallible;
+}
+
+impl OutputPin for PanelDc {
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.0 .0.borrow_mut().data = false;
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        self.0 .0.borrow_mut().data = true;
+        Ok(())
+    }
+}
+
+/// Stands in for the reset line, which the panel model has no use for.
+pub struct NoopPin;
+
+impl PinErrorType for NoopPin {
+    type Error = Infallible;
+}
+
+impl OutputPin for NoopPin {
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
@@ -0,0 +1,8 @@
+//! Stand-in for the firmware's `src/setup_settings.rs`, without flash to keep the choices in.
+
+use crate::setup::Setup;
+
+/// The simulator forgets the choices, so it prints them instead.
+pub(crate) async fn store(setup: Setup) {
+    println!("setup stored: {:?}", setup);
+}
@@ -0,0 +1,147 @@
+//! Averages of the readings over fixed intervals.
+//!
+//! Bench meters integrate over their own gate time, so a pulsed load reads differently on each
+//! instrument. With an interval picked on the average page, the measurement loop sums every raw
+//! reading and, at the end of each interval, publishes their mean, which the monitor page shows
+//! in small print above the units next to the live readings.
+
+use embassy_time::Duration;
+#[cfg(not(feature = "mock-time"))]
+use embassy_time::Instant;
+
+#[cfg(feature = "mock-time")]
+use crate::mock_time::Instant;
+use crate::{
+    types::PowerInfo,
+    units::{self, Value},
+};
+
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) enum AverageInterval {
+    Off,
+    OneSecond,
+    TenSeconds,
+    OneMinute,
+}
+
+pub(crate) const AVERAGE_INTERVALS: [AverageInterval; 4] = [
+    AverageInterval::Off,
+    AverageInterval::OneSecond,
+    AverageInterval::TenSeconds,
+    AverageInterval::OneMinute,
+];
+
+impl AverageInterval {
+    pub fn duration(&self) -> Option<Duration> {
+        match self {
+            AverageInterval::Off => None,
+            AverageInterval::OneSecond => Some(Duration::from_secs(1)),
+            AverageInterval::TenSeconds => Some(Duration::from_secs(10)),
+            AverageInterval::OneMinute => Some(Duration::from_secs(60)),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AverageInterval::Off => "off",
+            AverageInterval::OneSecond => "1s",
+            AverageInterval::TenSeconds => "10s",
+            AverageInterval::OneMinute => "1min",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        AVERAGE_INTERVALS
+            .iter()
+            .copied()
+            .find(|interval| interval.as_str() == s)
+    }
+
+    pub fn next(&self) -> Self {
+        let index = *self as usize;
+
+        AVERAGE_INTERVALS[(index + 1) % AVERAGE_INTERVALS.len()]
+    }
+
+    pub fn prev(&self) -> Self {
+        let index = *self as usize;
+
+        AVERAGE_INTERVALS[(index + AVERAGE_INTERVALS.len() - 1) % AVERAGE_INTERVALS.len()]
+    }
+}
+
+/// Sums the readings of the running interval.
+pub(crate) struct IntervalAverage {
+    interval: AverageInterval,
+    started: Option<Instant>,
+    /// Volts, amps and watts, in milli-units.
+    sums: [i64; 3],
+    count: u32,
+}
+
+impl IntervalAverage {
+    pub const fn new() -> Self {
+        Self {
+            interval: AverageInterval::Off,
+            started: None,
+            sums: [0; 3],
+            count: 0,
+        }
+    }
+
+    /// Starts over with `interval` if it is a different one; the running sums are dropped.
+    pub fn set_interval(&mut self, interval: AverageInterval) {
+        if interval != self.interval {
+            *self = Self {
+                interval,
+                ..Self::new()
+            };
+        }
+    }
+
+    pub fn interval(&self) -> AverageInterval {
+        self.interval
+    }
+
+    /// Drops the running interval, e.g. after the loop was suspended; the next reading starts a
+    /// new one.
+    pub fn reset(&mut self) {
+        *self = Self {
+            interval: self.interval,
+            ..Self::new()
+        };
+    }
+
+    /// Adds a reading taken at `now` and returns the mean of the interval it completes, if any.
+    /// The first reading starts the first interval.
+    pub fn update(&mut self, now: Instant, power: &PowerInfo) -> Option<PowerInfo> {
+        let duration = self.interval.duration()?;
+        let started = *self.started.get_or_insert(now);
+
+        for (sum, value) in self
+            .sums
+            .iter_mut()
+            .zip([power.volts, power.amps, power.watts])
+        {
+            *sum += units::milli(value) as i64;
+        }
+        self.count += 1;
+
+        if now - started < duration {
+            return None;
+        }
+
+        let mean = |sum: i64| -> Value { units::from_milli((sum / self.count as i64) as i32) };
+        let average = PowerInfo {
+            volts: mean(self.sums[0]),
+            amps: mean(self.sums[1]),
+            watts: mean(self.sums[2]),
+        };
+
+        self.started = Some(now);
+        self.sums = [0; 3];
+        self.count = 0;
+
+        Some(average)
+    }
+}
@@ -0,0 +1,164 @@
+//! Backlight PWM on TIM1 CH3, which shares the timer with the output switch, see `pwm_timer.rs`.
+//!
+//! The brightness follows the 0–10 level set with the buttons on the monitor page. Perceived
+//! brightness is roughly logarithmic in light output, so the levels are geometric steps from a
+//! floor that is still readable up to full duty, computed at the timer's full resolution rather
+//! than in percent. It drops to a tenth, but not below the floor, after
+//! `BACKLIGHT_TIMEOUT_MUTEX` seconds without button activity, so the readings stay visible while
+//! the panel draws less, and restores on the next press. The backlight is switched off entirely
+//! while the device is suspended in idle mode.
+
+use embassy_futures::select::{select4, Either4};
+use embassy_stm32::timer::Channel;
+use embassy_sync::{blocking_mutex::raw::CriticalSectionRawMutex, pubsub::Subscriber};
+use embassy_time::{Duration, Instant, Timer};
+use embedded_hal::pwm::SetDutyCycle;
+
+use crate::{
+    heartbeat::{self, Task, HEARTBEAT_INTERVAL},
+    pwm_timer::PwmChannel,
+    shared::{
+        ACTIVITY_PUBSUB, BACKLIGHT_MAX_LEVEL, BACKLIGHT_MUTEX, BACKLIGHT_PUBSUB,
+        BACKLIGHT_TIMEOUT_MUTEX, POWER_STATE_PUBSUB,
+    },
+    types::PowerState,
+};
+
+const CHANNEL: Channel = Channel::Ch3;
+
+/// Full scale of [`LEVEL_DUTY`].
+const DUTY_SCALE: u32 = 65_535;
+
+/// Dimmest duty the panel is still readable at, about 0.4%.
+const MIN_VISIBLE_DUTY: u16 = 262;
+
+/// Duty cycle of each level as a fraction of [`DUTY_SCALE`]: off, then nine equal ratios of about
+/// 1.85 from [`MIN_VISIBLE_DUTY`] to full.
+const LEVEL_DUTY: [u16; BACKLIGHT_MAX_LEVEL as usize + 1] = [
+    0,
+    MIN_VISIBLE_DUTY,
+    484,
+    894,
+    1651,
+    3049,
+    5631,
+    10401,
+    19211,
+    35482,
+    65535,
+];
+
+pub(crate) struct Backlight<'a> {
+    pwm: PwmChannel,
+    level: u16,
+    dimmed: bool,
+
+    activity_sub: Subscriber<'a, CriticalSectionRawMutex, (), 2, 2, 1>,
+    power_state_sub: Subscriber<'a, CriticalSectionRawMutex, PowerState, 2, 2, 1>,
+    level_sub: Subscriber<'a, CriticalSectionRawMutex, u16, 2, 2, 1>,
+}
+
+impl<'a> Backlight<'a> {
+    pub fn new() -> Self {
+        Self {
+            pwm: PwmChannel(CHANNEL),
+            level: 0,
+            dimmed: false,
+
+            activity_sub: ACTIVITY_PUBSUB.subscriber().unwrap(),
+            power_state_sub: POWER_STATE_PUBSUB.subscriber().unwrap(),
+            level_sub: BACKLIGHT_PUBSUB.subscriber().unwrap(),
+        }
+    }
+
+    pub async fn task(&mut self) {
+        let mut active_at = Instant::now();
+
+        self.level = *BACKLIGHT_MUTEX.lock().await;
+        self.set_duty(self.normal_duty());
+
+        loop {
+            heartbeat::beat(Task::Backlight);
+
+            let timeout = *BACKLIGHT_TIMEOUT_MUTEX.lock().await;
+
+            let dim_at = if timeout == 0 || self.dimmed {
+                Instant::MAX
+            } else {
+                active_at + Duration::from_secs(timeout as u64)
+            };
+
+            match select4(
+                self.activity_sub.next_message_pure(),
+                self.power_state_sub.next_message_pure(),
+                self.level_sub.next_message_pure(),
+                Timer::at(dim_at.min(Instant::now() + HEARTBEAT_INTERVAL)),
+            )
+            .await
+            {
+                Either4::First(_) => {
+                    active_at = Instant::now();
+
+                    if self.dimmed {
+                        self.dimmed = false;
+                        self.set_duty(self.normal_duty());
+                    }
+                }
+                Either4::Second(state) => {
+                    active_at = Instant::now();
+
+                    match state {
+                        PowerState::Suspending => self.set_duty(0),
+                        PowerState::Active => {
+                            self.dimmed = false;
+                            self.set_duty(self.normal_duty());
+                        }
+                        PowerState::Suspended => {}
+                    }
+                }
+                Either4::Third(level) => {
+                    active_at = Instant::now();
+
+                    self.level = level.min(BACKLIGHT_MAX_LEVEL);
+                    self.dimmed = false;
+                    self.set_duty(self.normal_duty());
+                }
+                Either4::Fourth(_) => {
+                    if Instant::now() >= dim_at {
+                        self.dimmed = true;
+                        self.set_duty(self.dim_duty());
+                    }
+                }
+            }
+        }
+    }
+
+    fn normal_duty(&self) -> u16 {
+        self.scale(LEVEL_DUTY[self.level.min(BACKLIGHT_MAX_LEVEL) as usize])
+    }
+
+    fn dim_duty(&self) -> u16 {
+        if self.level == 0 {
+            return 0;
+        }
+
+        let duty = LEVEL_DUTY[self.level.min(BACKLIGHT_MAX_LEVEL) as usize] / 10;
+
+        self.scale(duty.max(MIN_VISIBLE_DUTY))
+    }
+
+    /// A fraction of [`DUTY_SCALE`] in timer counts. Anything above zero stays at least one count.
+    fn scale(&self, duty: u16) -> u16 {
+        if duty == 0 {
+            return 0;
+        }
+
+        let counts = self.pwm.max_duty_cycle() as u32 * duty as u32 / DUTY_SCALE;
+
+        counts.max(1) as u16
+    }
+
+    fn set_duty(&mut self, duty: u16) {
+        self.pwm.set_duty_cycle(duty).ok();
+    }
+}
@@ -0,0 +1,71 @@
+use cortex_m::peripheral::{NVIC, SCB};
+use embassy_stm32::pac;
+
+use crate::log::{warn, Module};
+
+const LOG_MODULE: Module = Module::Update;
+
+/// Start of the system memory holding the ROM bootloader, the same on STM32G0 and STM32L4.
+const SYSTEM_MEMORY: u32 = 0x1FFF_0000;
+
+/// Turns the output off, resets every peripheral and enters the ROM bootloader (UART/DFU).
+///
+/// Does not return; the device comes back through the new firmware's reset handler.
+pub(crate) fn jump_to_bootloader() -> ! {
+    warn!("jumping to system bootloader");
+
+    cortex_m::interrupt::disable();
+
+    // Output off first, the peripheral reset below would otherwise leave PA8 floating for a
+    // moment while it still reads as high.
+    pac::GPIOA.bsrr().write(|w| w.set_br(8, true));
+
+    reset_peripherals();
+
+    unsafe {
+        let mut core = cortex_m::Peripherals::steal();
+
+        core.SYST.disable_counter();
+        core.SYST.disable_interrupt();
+
+        (*NVIC::PTR).icer[0].write(0xffff_ffff);
+        (*NVIC::PTR).icpr[0].write(0xffff_ffff);
+
+        SCB::clear_pendsv();
+        SCB::clear_pendst();
+
+        cortex_m::interrupt::enable();
+
+        cortex_m::asm::bootload(SYSTEM_MEMORY as *const u32)
+    }
+}
+
+#[cfg(feature = "family-g0")]
+fn reset_peripherals() {
+    use pac::rcc::regs::{Ahbrstr, Apbrstr1, Apbrstr2, Ioprstr};
+
+    pac::RCC.ioprstr().write_value(Ioprstr(0xffff_ffff));
+    pac::RCC.ahbrstr().write_value(Ahbrstr(0xffff_ffff));
+    pac::RCC.apbrstr1().write_value(Apbrstr1(0xffff_ffff));
+    pac::RCC.apbrstr2().write_value(Apbrstr2(0xffff_ffff));
+    pac::RCC.ioprstr().write_value(Ioprstr(0));
+    pac::RCC.ahbrstr().write_value(Ahbrstr(0));
+    pac::RCC.apbrstr1().write_value(Apbrstr1(0));
+    pac::RCC.apbrstr2().write_value(Apbrstr2(0));
+}
+
+#[cfg(feature = "family-l4")]
+fn reset_peripherals() {
+    use pac::rcc::regs::{Ahb1rstr, Ahb2rstr, Apb1rstr1, Apb1rstr2, Apb2rstr};
+
+    pac::RCC.ahb1rstr().write_value(Ahb1rstr(0xffff_ffff));
+    pac::RCC.ahb2rstr().write_value(Ahb2rstr(0xffff_ffff));
+    pac::RCC.apb1rstr1().write_value(Apb1rstr1(0xffff_ffff));
+    pac::RCC.apb1rstr2().write_value(Apb1rstr2(0xffff_ffff));
+    pac::RCC.apb2rstr().write_value(Apb2rstr(0xffff_ffff));
+    pac::RCC.ahb1rstr().write_value(Ahb1rstr(0));
+    pac::RCC.ahb2rstr().write_value(Ahb2rstr(0));
+    pac::RCC.apb1rstr1().write_value(Apb1rstr1(0));
+    pac::RCC.apb1rstr2().write_value(Apb1rstr2(0));
+    pac::RCC.apb2rstr().write_value(Apb2rstr(0));
+}
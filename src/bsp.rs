@@ -0,0 +1,504 @@
+//! Board support: which MCU pins and peripherals each hardware revision uses.
+//!
+//! Exactly one `board-*` feature selects the map below, and with it the MCU family. Everything
+//! else refers to the aliases here and takes its peripherals from [`Board`], so a new revision
+//! only touches this file. Family differences in clocks and interrupt names live here as well.
+
+#[cfg(any(feature = "modbus", feature = "wifi"))]
+use embassy_stm32::usart::BufferedInterruptHandler;
+use embassy_stm32::{
+    bind_interrupts, i2c, interrupt, peripherals,
+    time::{khz, mhz, Hertz},
+    usart, Peripherals,
+};
+#[cfg(feature = "relay-output")]
+use embassy_time::Duration;
+
+#[cfg(feature = "vbus-sense")]
+use crate::rails::Divider;
+#[cfg(feature = "relay-output")]
+use crate::{relay::RelayTiming, units};
+
+#[cfg(any(
+    all(feature = "board-v1", feature = "board-v2"),
+    all(feature = "board-v1", feature = "board-nucleo-l432kc"),
+    all(feature = "board-v2", feature = "board-nucleo-l432kc"),
+))]
+compile_error!("enable only one `board-*` feature");
+
+#[cfg(not(any(
+    feature = "board-v1",
+    feature = "board-v2",
+    feature = "board-nucleo-l432kc"
+)))]
+compile_error!("enable one of the `board-v1`, `board-v2` and `board-nucleo-l432kc` features");
+
+#[cfg(all(
+    feature = "family-l4",
+    any(feature = "i2c-slave", feature = "modbus", feature = "wifi")
+))]
+compile_error!("the `i2c-slave`, `modbus` and `wifi` features are only mapped on STM32G0 boards");
+
+#[cfg(all(feature = "family-g0", feature = "sd-log", feature = "modbus"))]
+compile_error!("the `sd-log` and `modbus` features both use PA6 on STM32G0 boards");
+
+#[cfg(all(feature = "family-g0", feature = "data-lines", feature = "modbus"))]
+compile_error!("the `data-lines` and `modbus` features both use PA0 and PA1 on STM32G0 boards");
+
+#[cfg(all(feature = "family-l4", feature = "data-lines", feature = "sd-log"))]
+compile_error!("the `data-lines` and `sd-log` features both use PA3 on the NUCLEO-L432KC");
+
+#[cfg(all(feature = "family-l4", feature = "data-lines", feature = "vbus-sense"))]
+compile_error!("the `data-lines` and `vbus-sense` features both use PA0 on the NUCLEO-L432KC");
+
+#[cfg(all(feature = "family-l4", feature = "cc-lines"))]
+compile_error!("the `cc-lines` feature is only mapped on STM32G0 boards");
+
+#[cfg(all(feature = "cc-lines", feature = "i2c-slave"))]
+compile_error!("the `cc-lines` and `i2c-slave` features both use PB10 and PB11");
+
+#[cfg(feature = "family-g0")]
+bind_interrupts!(pub(crate) struct Irqs {
+    I2C1 => i2c::EventInterruptHandler<SensorI2c>, i2c::ErrorInterruptHandler<SensorI2c>;
+    USART2 => usart::InterruptHandler<ConsoleUsart>, ConsoleWakeHandler;
+});
+
+#[cfg(feature = "family-l4")]
+bind_interrupts!(pub(crate) struct Irqs {
+    I2C1_EV => i2c::EventInterruptHandler<SensorI2c>;
+    I2C1_ER => i2c::ErrorInterruptHandler<SensorI2c>;
+    USART2 => usart::InterruptHandler<ConsoleUsart>, ConsoleWakeHandler;
+});
+
+#[cfg(feature = "modbus")]
+bind_interrupts!(pub(crate) struct ModbusIrqs {
+    USART3_4_LPUART1 => BufferedInterruptHandler<ModbusUsart>;
+});
+
+#[cfg(feature = "wifi")]
+bind_interrupts!(pub(crate) struct WifiIrqs {
+    USART1 => BufferedInterruptHandler<WifiUsart>;
+});
+
+// The console is USART2 on every board, laid out the same on STM32G0 and STM32L4; only the
+// kernel clock selection sits elsewhere in the RCC.
+const CONSOLE_CR1: *mut u32 = 0x4000_4400 as *mut u32;
+const CONSOLE_CR3: *mut u32 = 0x4000_4408 as *mut u32;
+const CONSOLE_ICR: *mut u32 = 0x4000_4420 as *mut u32;
+#[cfg(feature = "family-g0")]
+const RCC_CCIPR: *mut u32 = 0x4002_1054 as *mut u32;
+#[cfg(feature = "family-l4")]
+const RCC_CCIPR: *mut u32 = 0x4002_1088 as *mut u32;
+
+const CR1_UE: u32 = 1 << 0;
+const CR3_WUS_START_BIT: u32 = 0b10 << 20;
+const CR3_WUFIE: u32 = 1 << 22;
+const ICR_WUCF: u32 = 1 << 20;
+const CCIPR_USART2SEL: u32 = 0b11 << 2;
+const CCIPR_USART2SEL_HSI16: u32 = 0b10 << 2;
+
+/// Lets a start bit on the console wake the core from STOP, see `idle.rs`. The console then runs
+/// from HSI16, which the USART can request in STOP; the PCLK it ran from is HSI16 as well on every
+/// board, so the baud rate stays. Call it once, right after the console UART is set up.
+pub(crate) fn init_console_wakeup() {
+    unsafe {
+        // The wakeup event can only be chosen while the USART is disabled.
+        let cr1 = core::ptr::read_volatile(CONSOLE_CR1);
+        core::ptr::write_volatile(CONSOLE_CR1, cr1 & !CR1_UE);
+
+        let ccipr = core::ptr::read_volatile(RCC_CCIPR);
+        core::ptr::write_volatile(
+            RCC_CCIPR,
+            (ccipr & !CCIPR_USART2SEL) | CCIPR_USART2SEL_HSI16,
+        );
+
+        let cr3 = core::ptr::read_volatile(CONSOLE_CR3);
+        core::ptr::write_volatile(CONSOLE_CR3, cr3 | CR3_WUS_START_BIT | CR3_WUFIE);
+
+        core::ptr::write_volatile(CONSOLE_CR1, cr1);
+    }
+}
+
+#[cfg(not(any(feature = "i2c-slave", feature = "modbus", feature = "wifi")))]
+const CR1_UESM: u32 = 1 << 1;
+
+/// Whether the console may wake the core, set only while idle, as the wakeup flag is raised on
+/// every start bit meanwhile.
+#[cfg(not(any(feature = "i2c-slave", feature = "modbus", feature = "wifi")))]
+pub(crate) fn set_console_wakeup(enabled: bool) {
+    unsafe {
+        let cr1 = core::ptr::read_volatile(CONSOLE_CR1);
+        let cr1 = match enabled {
+            true => cr1 | CR1_UESM,
+            false => cr1 & !CR1_UESM,
+        };
+        core::ptr::write_volatile(CONSOLE_CR1, cr1);
+    }
+}
+
+/// Clears the console's wakeup flag, which the embassy handler next to it leaves alone.
+pub(crate) struct ConsoleWakeHandler;
+
+impl interrupt::typelevel::Handler<interrupt::typelevel::USART2> for ConsoleWakeHandler {
+    unsafe fn on_interrupt() {
+        core::ptr::write_volatile(CONSOLE_ICR, ICR_WUCF);
+    }
+}
+
+/// Backlight PWM frequency, above hearing and well clear of camera frame and line rates, which
+/// showed the old 1 kHz as banding in videos. On the 16 MHz timer clock this leaves 640 duty steps.
+/// The output pin is channel 1 of the same timer on every board, and ramps at this frequency in the
+/// short test, see `pwm_timer.rs`.
+pub(crate) const BACKLIGHT_PWM_FREQUENCY: Hertz = khz(25);
+
+/// Fan PWM frequency, the 25 kHz that 4-wire fans expect on their PWM input. The fan output
+/// uses channel 2 of its timer on every board.
+#[cfg(feature = "fan")]
+pub(crate) const FAN_PWM_FREQUENCY: Hertz = khz(25);
+
+/// The ADC reference, which every board takes from the 3.3 V rail.
+#[cfg(feature = "adc")]
+pub(crate) const ADC_VREF_MV: u32 = 3_300;
+
+/// The divider from VBUS ahead of the output switch to its ADC pin on `vbus-sense` builds,
+/// 100 kΩ over 10 kΩ, which puts 20 V at 1.8 V on the pin and leaves headroom for a source
+/// running high.
+#[cfg(feature = "vbus-sense")]
+pub(crate) const VBUS_DIVIDER: Divider = Divider {
+    top_ohms: 100_000,
+    bottom_ohms: 10_000,
+};
+
+/// Timing of the relay on `relay-output` builds, for a small signal relay with its coil on the
+/// output pin through a transistor. A slower relay only needs longer delays here.
+#[cfg(feature = "relay-output")]
+pub(crate) const RELAY_TIMING: RelayTiming = RelayTiming {
+    pull_in: Duration::from_millis(15),
+    release: Duration::from_millis(10),
+    quiet_amps: units::from_milli(50),
+    quiet_wait: Duration::from_millis(500),
+};
+
+/// The INA226 of the output, both address straps to GND, across a 10 mΩ shunt and calibrated for
+/// 5 A. Boards strapped or fitted differently store their own with `ina226` on the console, see
+/// `monitor_settings.rs`. Plain values, so this file builds without the rest of the firmware.
+pub(crate) const POWER_MONITOR_ADDRESS: u8 = 0x40;
+pub(crate) const POWER_MONITOR_SHUNT_MICRO_OHMS: u32 = 10_000;
+pub(crate) const POWER_MONITOR_MAX_MILLIAMPS: i32 = 5_000;
+
+/// The INA226 of the second channel on `dual-output` builds, A0 strapped to VS, on the same shunt.
+pub(crate) const POWER_MONITOR_B_ADDRESS: u8 = 0x41;
+
+/// Fastest display SPI clock. The panel is write-only on every board, so nothing tells a picture
+/// garbled by a long FPC cable from a good one; `spi <MHz>` on the console slows it down, and is
+/// kept in flash, see `display_settings.rs`.
+pub(crate) const DISPLAY_SPI_FREQUENCY: Hertz = mhz(16);
+
+/// Brings up the chip on a 16 MHz system clock and splits out the board's peripherals.
+pub(crate) fn init() -> Board {
+    #[allow(unused_mut)]
+    let mut config = embassy_stm32::Config::default();
+
+    // The G0 already runs from HSI16 out of reset; the L4 starts on a 4 MHz MSI.
+    #[cfg(feature = "family-l4")]
+    {
+        config.rcc.hsi = true;
+        config.rcc.sys = embassy_stm32::rcc::Sysclk::HSI;
+    }
+
+    // The RTC runs from the 32.768 kHz crystal the NUCLEO has. The G0 boards have button A on
+    // OSC32_IN, so their RTC runs from the LSI and can drift by a few minutes a day.
+    #[cfg(feature = "board-nucleo-l432kc")]
+    {
+        config.rcc.ls = embassy_stm32::rcc::LsConfig::default_lse();
+    }
+
+    Board::new(embassy_stm32::init(config))
+}
+
+/// Declares an alias per assignment and a [`Board`] holding the matching peripherals.
+macro_rules! board {
+    ($($(#[$meta:meta])* $field:ident: $alias:ident = $periph:ident,)*) => {
+        $(
+            $(#[$meta])*
+            pub(crate) type $alias = peripherals::$periph;
+        )*
+
+        pub(crate) struct Board {
+            $(
+                $(#[$meta])*
+                pub $field: $alias,
+            )*
+        }
+
+        impl Board {
+            pub fn new(p: Peripherals) -> Self {
+                Self {
+                    $(
+                        $(#[$meta])*
+                        $field: p.$periph,
+                    )*
+                }
+            }
+        }
+    };
+}
+
+#[cfg(feature = "board-v1")]
+board! {
+    flash: FlashPeriph = FLASH,
+    rtc: RtcPeriph = RTC,
+
+    output: OutputSwitchPin = PA8,
+    #[cfg(feature = "dual-output")]
+    output_b: OutputSwitchBPin = PA11,
+    #[cfg(feature = "interlock")]
+    interlock: InterlockPin = PB3,
+    #[cfg(feature = "output-sense")]
+    output_sense: OutputSensePin = PC15,
+    #[cfg(feature = "trigger")]
+    trigger: TriggerPin = PB4,
+
+    console_usart: ConsoleUsart = USART2,
+    console_tx: ConsoleTxPin = PA2,
+    console_rx: ConsoleRxPin = PA3,
+    console_tx_dma: ConsoleTxDma = DMA1_CH5,
+    console_rx_dma: ConsoleRxDma = DMA1_CH6,
+
+    display_spi: DisplaySpi = SPI1,
+    display_sck: DisplaySckPin = PA5,
+    display_mosi: DisplayMosiPin = PA7,
+    display_tx_dma: DisplayTxDma = DMA1_CH1,
+    display_rx_dma: DisplayRxDma = DMA1_CH2,
+    display_cs: DisplayCsPin = PA4,
+    display_dc: DisplayDcPin = PA15,
+    display_rst: DisplayRstPin = PA12,
+    #[cfg(feature = "sd-log")]
+    display_miso: DisplayMisoPin = PA6,
+    #[cfg(feature = "sd-log")]
+    sd_cs: SdCsPin = PB2,
+
+    backlight_tim: BacklightTim = TIM1,
+    backlight: BacklightPin = PB6,
+
+    #[cfg(feature = "adc")]
+    adc: AdcPeriph = ADC1,
+    #[cfg(feature = "fan")]
+    fan_tim: FanTim = TIM3,
+    #[cfg(feature = "fan")]
+    fan: FanPin = PB5,
+    #[cfg(feature = "data-lines")]
+    data_plus: DataPlusPin = PA0,
+    #[cfg(feature = "data-lines")]
+    data_minus: DataMinusPin = PA1,
+    #[cfg(feature = "cc-lines")]
+    cc1: Cc1Pin = PB10,
+    #[cfg(feature = "cc-lines")]
+    cc2: Cc2Pin = PB11,
+    #[cfg(feature = "vbus-sense")]
+    vbus: VbusPin = PB1,
+
+    sensor_i2c: SensorI2c = I2C1,
+    sensor_scl: SensorSclPin = PB8,
+    sensor_sda: SensorSdaPin = PB7,
+    sensor_tx_dma: SensorTxDma = DMA1_CH3,
+    sensor_rx_dma: SensorRxDma = DMA1_CH4,
+
+    button_a: ButtonAPin = PC14,
+    button_a_exti: ButtonAExti = EXTI14,
+    button_b: ButtonBPin = PB0,
+    button_b_exti: ButtonBExti = EXTI0,
+
+    #[cfg(feature = "ina226-alert")]
+    ina226_alert: Ina226AlertPin = PB9,
+    #[cfg(feature = "ina226-alert")]
+    ina226_alert_exti: Ina226AlertExti = EXTI9,
+
+    #[cfg(feature = "i2c-slave")]
+    slave_i2c: SlaveI2c = I2C2,
+    #[cfg(feature = "i2c-slave")]
+    slave_scl: SlaveSclPin = PB10,
+    #[cfg(feature = "i2c-slave")]
+    slave_sda: SlaveSdaPin = PB11,
+
+    #[cfg(feature = "modbus")]
+    modbus_usart: ModbusUsart = USART4,
+    #[cfg(feature = "modbus")]
+    modbus_tx: ModbusTxPin = PA0,
+    #[cfg(feature = "modbus")]
+    modbus_rx: ModbusRxPin = PA1,
+    #[cfg(feature = "modbus")]
+    modbus_de: ModbusDePin = PA6,
+
+    #[cfg(feature = "wifi")]
+    wifi_usart: WifiUsart = USART1,
+    #[cfg(feature = "wifi")]
+    wifi_tx: WifiTxPin = PA9,
+    #[cfg(feature = "wifi")]
+    wifi_rx: WifiRxPin = PA10,
+}
+
+// Second revision: INA226/HUSB238 SDA moved to PB9, button B to PB1 and the panel DC line to
+// PA11.
+#[cfg(feature = "board-v2")]
+board! {
+    flash: FlashPeriph = FLASH,
+    rtc: RtcPeriph = RTC,
+
+    output: OutputSwitchPin = PA8,
+    #[cfg(feature = "dual-output")]
+    output_b: OutputSwitchBPin = PA15,
+    #[cfg(feature = "interlock")]
+    interlock: InterlockPin = PB3,
+    #[cfg(feature = "output-sense")]
+    output_sense: OutputSensePin = PC15,
+    #[cfg(feature = "trigger")]
+    trigger: TriggerPin = PB4,
+
+    console_usart: ConsoleUsart = USART2,
+    console_tx: ConsoleTxPin = PA2,
+    console_rx: ConsoleRxPin = PA3,
+    console_tx_dma: ConsoleTxDma = DMA1_CH5,
+    console_rx_dma: ConsoleRxDma = DMA1_CH6,
+
+    display_spi: DisplaySpi = SPI1,
+    display_sck: DisplaySckPin = PA5,
+    display_mosi: DisplayMosiPin = PA7,
+    display_tx_dma: DisplayTxDma = DMA1_CH1,
+    display_rx_dma: DisplayRxDma = DMA1_CH2,
+    display_cs: DisplayCsPin = PA4,
+    display_dc: DisplayDcPin = PA11,
+    display_rst: DisplayRstPin = PA12,
+    #[cfg(feature = "sd-log")]
+    display_miso: DisplayMisoPin = PA6,
+    #[cfg(feature = "sd-log")]
+    sd_cs: SdCsPin = PB2,
+
+    backlight_tim: BacklightTim = TIM1,
+    backlight: BacklightPin = PB6,
+
+    #[cfg(feature = "adc")]
+    adc: AdcPeriph = ADC1,
+    #[cfg(feature = "fan")]
+    fan_tim: FanTim = TIM3,
+    #[cfg(feature = "fan")]
+    fan: FanPin = PB5,
+    #[cfg(feature = "data-lines")]
+    data_plus: DataPlusPin = PA0,
+    #[cfg(feature = "data-lines")]
+    data_minus: DataMinusPin = PA1,
+    #[cfg(feature = "cc-lines")]
+    cc1: Cc1Pin = PB10,
+    #[cfg(feature = "cc-lines")]
+    cc2: Cc2Pin = PB11,
+    #[cfg(feature = "vbus-sense")]
+    vbus: VbusPin = PB0,
+
+    sensor_i2c: SensorI2c = I2C1,
+    sensor_scl: SensorSclPin = PB8,
+    sensor_sda: SensorSdaPin = PB9,
+    sensor_tx_dma: SensorTxDma = DMA1_CH3,
+    sensor_rx_dma: SensorRxDma = DMA1_CH4,
+
+    button_a: ButtonAPin = PC14,
+    button_a_exti: ButtonAExti = EXTI14,
+    button_b: ButtonBPin = PB1,
+    button_b_exti: ButtonBExti = EXTI1,
+
+    #[cfg(feature = "ina226-alert")]
+    ina226_alert: Ina226AlertPin = PB7,
+    #[cfg(feature = "ina226-alert")]
+    ina226_alert_exti: Ina226AlertExti = EXTI7,
+
+    #[cfg(feature = "i2c-slave")]
+    slave_i2c: SlaveI2c = I2C2,
+    #[cfg(feature = "i2c-slave")]
+    slave_scl: SlaveSclPin = PB10,
+    #[cfg(feature = "i2c-slave")]
+    slave_sda: SlaveSdaPin = PB11,
+
+    #[cfg(feature = "modbus")]
+    modbus_usart: ModbusUsart = USART4,
+    #[cfg(feature = "modbus")]
+    modbus_tx: ModbusTxPin = PA0,
+    #[cfg(feature = "modbus")]
+    modbus_rx: ModbusRxPin = PA1,
+    #[cfg(feature = "modbus")]
+    modbus_de: ModbusDePin = PA6,
+
+    #[cfg(feature = "wifi")]
+    wifi_usart: WifiUsart = USART1,
+    #[cfg(feature = "wifi")]
+    wifi_tx: WifiTxPin = PA9,
+    #[cfg(feature = "wifi")]
+    wifi_rx: WifiRxPin = PA10,
+}
+
+// Arduino header names of the NUCLEO-L432KC in the comments. The console goes through the
+// ST-LINK virtual COM port.
+#[cfg(feature = "board-nucleo-l432kc")]
+board! {
+    flash: FlashPeriph = FLASH,
+    rtc: RtcPeriph = RTC,
+
+    output: OutputSwitchPin = PA8, // D9
+    #[cfg(feature = "dual-output")]
+    output_b: OutputSwitchBPin = PB3, // D13
+    #[cfg(feature = "interlock")]
+    interlock: InterlockPin = PB4, // D12
+    #[cfg(feature = "output-sense")]
+    output_sense: OutputSensePin = PA9, // D1
+    #[cfg(feature = "trigger")]
+    trigger: TriggerPin = PB5, // D11
+
+    console_usart: ConsoleUsart = USART2,
+    console_tx: ConsoleTxPin = PA2,
+    console_rx: ConsoleRxPin = PA15,
+    console_tx_dma: ConsoleTxDma = DMA1_CH7,
+    console_rx_dma: ConsoleRxDma = DMA1_CH6,
+
+    display_spi: DisplaySpi = SPI1,
+    display_sck: DisplaySckPin = PA5, // A4
+    display_mosi: DisplayMosiPin = PA7, // A6
+    display_tx_dma: DisplayTxDma = DMA1_CH3,
+    display_rx_dma: DisplayRxDma = DMA1_CH2,
+    display_cs: DisplayCsPin = PA4, // A3
+    display_dc: DisplayDcPin = PA11, // D10
+    display_rst: DisplayRstPin = PA12, // D2
+    #[cfg(feature = "sd-log")]
+    display_miso: DisplayMisoPin = PA6, // A5
+    #[cfg(feature = "sd-log")]
+    sd_cs: SdCsPin = PA3, // A2
+
+    backlight_tim: BacklightTim = TIM1,
+    backlight: BacklightPin = PA10, // D0
+
+    #[cfg(feature = "adc")]
+    adc: AdcPeriph = ADC1,
+    #[cfg(feature = "fan")]
+    fan_tim: FanTim = TIM2,
+    #[cfg(feature = "fan")]
+    fan: FanPin = PA1, // A1
+    #[cfg(feature = "data-lines")]
+    data_plus: DataPlusPin = PA0, // A0
+    #[cfg(feature = "data-lines")]
+    data_minus: DataMinusPin = PA3, // A2
+    #[cfg(feature = "vbus-sense")]
+    vbus: VbusPin = PA0, // A0
+
+    sensor_i2c: SensorI2c = I2C1,
+    sensor_scl: SensorSclPin = PB6, // D5
+    sensor_sda: SensorSdaPin = PB7, // D4
+    sensor_tx_dma: SensorTxDma = DMA2_CH7,
+    sensor_rx_dma: SensorRxDma = DMA2_CH6,
+
+    button_a: ButtonAPin = PB0, // D3
+    button_a_exti: ButtonAExti = EXTI0,
+    button_b: ButtonBPin = PB1, // D6
+    button_b_exti: ButtonBExti = EXTI1,
+
+    #[cfg(feature = "ina226-alert")]
+    ina226_alert: Ina226AlertPin = PC14, // D7
+    #[cfg(feature = "ina226-alert")]
+    ina226_alert_exti: Ina226AlertExti = EXTI14,
+}
@@ -1,7 +1,16 @@
 use embassy_sync::{blocking_mutex::raw::CriticalSectionRawMutex, channel::Channel};
+use embassy_time::Duration;
+#[cfg(not(feature = "mock-time"))]
 use embassy_time::Instant;
 
-use crate::shared::{DOUBLE_CLICK_TIMEOUT, MIN_PRESS_DURATION, SHORT_PRESS_DURATION};
+#[cfg(feature = "mock-time")]
+use crate::mock_time::Instant;
+
+/// Shorter presses are treated as contact bounce.
+pub const MIN_PRESS_DURATION: Duration = Duration::from_millis(50);
+/// Held longer than this, a press becomes a long press without waiting for the release.
+pub const SHORT_PRESS_DURATION: Duration = Duration::from_millis(200);
+pub const DOUBLE_CLICK_TIMEOUT: Duration = Duration::from_millis(200);
 
 #[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
 pub(crate) enum ButtonState {
@@ -12,6 +21,18 @@ pub(crate) enum ButtonState {
     DoubleClick(Instant),
 }
 
+impl ButtonState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ButtonState::Released => "released",
+            ButtonState::Pressed => "pressed",
+            ButtonState::Click(_) => "click",
+            ButtonState::LongPressed(_) => "long",
+            ButtonState::DoubleClick(_) => "double",
+        }
+    }
+}
+
 pub(crate) struct Button<'a> {
     last_press_time: Instant,
     last_release_time: Instant,
@@ -29,25 +50,33 @@ impl<'a> Button<'a> {
         }
     }
 
-    pub async fn on_press(&mut self) {
+    /// Each of these returns the state it sent, for the `buttons` trace on the console.
+    pub async fn on_press(&mut self) -> ButtonState {
         self.last_press_time = Instant::now();
         self.state_channel.send(ButtonState::Pressed).await;
+
+        ButtonState::Pressed
+    }
+
+    pub async fn on_release(&mut self) -> ButtonState {
+        let state = self.release_state();
+        self.state_channel.send(state).await;
+
+        state
     }
 
-    pub async fn on_release(&mut self) {
+    fn release_state(&mut self) -> ButtonState {
         if self.last_press_time == Instant::MIN {
             self.last_release_time = Instant::MIN;
-            self.state_channel.send(ButtonState::Released).await;
             // defmt::info!("bad");
-            return;
+            return ButtonState::Released;
         }
 
         let now = Instant::now();
 
         if now - self.last_press_time < MIN_PRESS_DURATION {
-            self.state_channel.send(ButtonState::Released).await;
             // defmt::info!("threshold");
-            return;
+            return ButtonState::Released;
         }
 
         if now - self.last_release_time < DOUBLE_CLICK_TIMEOUT {
@@ -55,32 +84,35 @@ impl<'a> Button<'a> {
             self.last_press_time = Instant::MIN;
 
             // defmt::info!("double");
-            self.state_channel.send(ButtonState::DoubleClick(now)).await;
-
-            return;
+            return ButtonState::DoubleClick(now);
         }
 
         // defmt::info!("click. duration: {:?}", now - self.last_press_time);
         self.last_release_time = now;
         self.last_press_time = Instant::MIN;
 
-        self.state_channel.send(ButtonState::Click(now)).await;
+        ButtonState::Click(now)
     }
 
-    pub async fn update(&mut self) {
+    pub async fn update(&mut self) -> Option<ButtonState> {
         if self.last_press_time == Instant::MIN {
-            return;
+            return None;
         }
 
         let now = Instant::now();
 
-        if now - self.last_press_time > SHORT_PRESS_DURATION {
-            // defmt::info!("long timeout. {:?}", now - self.last_press_time);
+        if now - self.last_press_time <= SHORT_PRESS_DURATION {
+            return None;
+        }
 
-            self.last_press_time = Instant::MIN;
-            self.last_release_time = Instant::MIN;
+        // defmt::info!("long timeout. {:?}", now - self.last_press_time);
 
-            self.state_channel.send(ButtonState::LongPressed(now)).await;
-        }
+        self.last_press_time = Instant::MIN;
+        self.last_release_time = Instant::MIN;
+
+        let state = ButtonState::LongPressed(now);
+        self.state_channel.send(state).await;
+
+        Some(state)
     }
 }
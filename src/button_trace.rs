@@ -0,0 +1,56 @@
+//! Button states on the console, for tuning the debounce.
+//!
+//! `buttons on` mirrors every state `button.rs` makes of the BTN_A and BTN_B edges as a
+//! `BTN A click t=12345 held=130` line: the state, the uptime in milliseconds and, past the
+//! press, how long since it. Lined up against `MIN_PRESS_DURATION`, `SHORT_PRESS_DURATION` and
+//! `DOUBLE_CLICK_TIMEOUT`, which `buttons` prints, that shows which presses of a switch fall on
+//! the wrong side of them without a build with defmt prints. `buttons off` stops the lines, which
+//! are not kept over a restart.
+
+use embassy_time::Instant;
+
+use crate::{button::ButtonState, console::println, shared::BUTTON_TRACE_MUTEX};
+
+pub(crate) struct ButtonTrace {
+    name: char,
+    /// When the button was last pressed.
+    pressed: Option<Instant>,
+}
+
+impl ButtonTrace {
+    pub const fn new(name: char) -> Self {
+        Self {
+            name,
+            pressed: None,
+        }
+    }
+
+    /// Prints `state` while the trace is on. The press is kept either way, so the first line
+    /// after `buttons on` has its time as well.
+    pub async fn record(&mut self, state: ButtonState) {
+        let now = Instant::now();
+        if state == ButtonState::Pressed {
+            self.pressed = Some(now);
+        }
+
+        if !*BUTTON_TRACE_MUTEX.lock().await {
+            return;
+        }
+
+        match (state, self.pressed) {
+            (ButtonState::Pressed, _) | (_, None) => println(format_args!(
+                "BTN {} {} t={}",
+                self.name,
+                state.as_str(),
+                now.as_millis()
+            )),
+            (_, Some(pressed)) => println(format_args!(
+                "BTN {} {} t={} held={}",
+                self.name,
+                state.as_str(),
+                now.as_millis(),
+                (now - pressed).as_millis()
+            )),
+        }
+    }
+}
@@ -0,0 +1,92 @@
+//! Cable resistance from two readings at different load currents.
+//!
+//! On the cable page, Up records the present volts and amps, once at a light load and once at a
+//! heavy one; Down starts over. The voltage lost between the two readings over the current added is
+//! the resistance of everything between the source's regulation point and the sink: cable,
+//! connectors and the source's own output impedance.
+
+use crate::units::{self, Value};
+
+/// Smallest current difference a resistance is estimated from; below it the INA226 resolution
+/// dominates the result.
+pub(crate) const MIN_CURRENT_STEP: Value = units::from_milli(200);
+
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) enum CableError {
+    MissingReading,
+    CurrentStepTooSmall,
+    /// The voltage went up with the load, so the source or the load changed between readings.
+    VoltageRose,
+}
+
+impl CableError {
+    /// Short enough for a row of the cable page.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CableError::MissingReading => "need 2 readings",
+            CableError::CurrentStepTooSmall => "step too small",
+            CableError::VoltageRose => "volts rose",
+        }
+    }
+}
+
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) struct Reading {
+    pub volts: Value,
+    pub amps: Value,
+}
+
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) struct CableProbe {
+    readings: [Option<Reading>; 2],
+}
+
+impl CableProbe {
+    pub const fn new() -> Self {
+        Self {
+            readings: [None, None],
+        }
+    }
+
+    /// Fills the first free slot; a third reading starts a new pair.
+    pub fn record(&mut self, volts: Value, amps: Value) {
+        let reading = Some(Reading { volts, amps });
+
+        match self.readings {
+            [None, _] => self.readings[0] = reading,
+            [Some(_), None] => self.readings[1] = reading,
+            [Some(_), Some(_)] => self.readings = [reading, None],
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.readings = [None, None];
+    }
+
+    pub fn readings(&self) -> [Option<Reading>; 2] {
+        self.readings
+    }
+
+    /// Resistance in ohms, in whichever order the loads were applied.
+    pub fn resistance(&self) -> Result<Value, CableError> {
+        let [Some(a), Some(b)] = self.readings else {
+            return Err(CableError::MissingReading);
+        };
+
+        let (light, heavy) = if a.amps <= b.amps { (a, b) } else { (b, a) };
+
+        if heavy.amps - light.amps < MIN_CURRENT_STEP {
+            return Err(CableError::CurrentStepTooSmall);
+        }
+
+        if heavy.volts > light.volts {
+            return Err(CableError::VoltageRose);
+        }
+
+        // Millivolts over milliamps, scaled to milliohms.
+        let drop_mv = (units::milli(light.volts) - units::milli(heavy.volts)) as i64;
+        let step_ma = (units::milli(heavy.amps) - units::milli(light.amps)) as i64;
+
+        Ok(units::from_milli((drop_mv * 1_000 / step_ma) as i32))
+    }
+}
@@ -0,0 +1,151 @@
+//! Calendar dates and times of day, for the wall clock and the page that sets it.
+//!
+//! All times are UTC. Only the years the RTC can hold are settable, see [`MIN_YEAR`] and
+//! [`MAX_YEAR`]; converting a Unix time works for any date.
+
+use crate::types::ClockField;
+
+/// The RTC counts years within a century from 2000; a clock reading before this was never set.
+pub(crate) const MIN_YEAR: u16 = 2024;
+pub(crate) const MAX_YEAR: u16 = 2099;
+
+/// A UTC date and time of day, to the second.
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) struct DateTime {
+    pub year: u16,
+    /// 1 to 12.
+    pub month: u8,
+    /// 1 to the length of the month.
+    pub day: u8,
+    pub hour: u8,
+    pub minute: u8,
+    pub second: u8,
+}
+
+impl DateTime {
+    /// Midnight on the first day of [`MIN_YEAR`], what the clock page starts from when the clock
+    /// was never set.
+    pub const fn default() -> Self {
+        Self {
+            year: MIN_YEAR,
+            month: 1,
+            day: 1,
+            hour: 0,
+            minute: 0,
+            second: 0,
+        }
+    }
+
+    pub fn from_unix(secs: u64) -> Self {
+        let (year, month, day) = civil_from_days((secs / 86_400) as i64);
+        let secs_of_day = secs % 86_400;
+
+        Self {
+            year: year as u16,
+            month: month as u8,
+            day: day as u8,
+            hour: (secs_of_day / 3600) as u8,
+            minute: (secs_of_day / 60 % 60) as u8,
+            second: (secs_of_day % 60) as u8,
+        }
+    }
+
+    /// Seconds since the Unix epoch, 0 for a date before it.
+    pub fn to_unix(self) -> u64 {
+        let days = days_from_civil(self.year as i64, self.month as u32, self.day as u32);
+        let secs =
+            days * 86_400 + self.hour as i64 * 3600 + self.minute as i64 * 60 + self.second as i64;
+
+        secs.max(0) as u64
+    }
+
+    /// Day of the week, 1 for Monday to 7 for Sunday as the RTC counts them.
+    pub fn weekday(self) -> u8 {
+        let days = days_from_civil(self.year as i64, self.month as u32, self.day as u32);
+
+        // 1970-01-01 was a Thursday.
+        ((days + 3).rem_euclid(7) + 1) as u8
+    }
+
+    /// Steps `field` one up or down, wrapping around within its range. The seconds go to 0, and
+    /// the day is kept within the month when the month or year changes.
+    pub fn step(&mut self, field: ClockField, up: bool) {
+        match field {
+            ClockField::Year => {
+                self.year = wrap(self.year as u32, MIN_YEAR as u32, MAX_YEAR as u32, up) as u16
+            }
+            ClockField::Month => self.month = wrap(self.month as u32, 1, 12, up) as u8,
+            ClockField::Day => {
+                let last = days_in_month(self.year, self.month) as u32;
+                self.day = wrap(self.day as u32, 1, last, up) as u8;
+            }
+            ClockField::Hour => self.hour = wrap(self.hour as u32, 0, 23, up) as u8,
+            ClockField::Minute => self.minute = wrap(self.minute as u32, 0, 59, up) as u8,
+        }
+
+        self.day = self.day.min(days_in_month(self.year, self.month));
+        self.second = 0;
+    }
+
+    pub fn of(&self, field: ClockField) -> u16 {
+        match field {
+            ClockField::Year => self.year,
+            ClockField::Month => self.month as u16,
+            ClockField::Day => self.day as u16,
+            ClockField::Hour => self.hour as u16,
+            ClockField::Minute => self.minute as u16,
+        }
+    }
+}
+
+fn wrap(value: u32, min: u32, max: u32, up: bool) -> u32 {
+    match (up, value) {
+        (true, v) if v >= max => min,
+        (true, v) => v + 1,
+        (false, v) if v <= min => max,
+        (false, v) => v - 1,
+    }
+}
+
+pub(crate) fn is_leap_year(year: u16) -> bool {
+    year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400))
+}
+
+pub(crate) fn days_in_month(year: u16, month: u8) -> u8 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Converts days since 1970-01-01 into a (year, month, day) triple.
+///
+/// Howard Hinnant's `civil_from_days`, valid for the whole proleptic Gregorian calendar.
+pub(crate) fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    (year, month, day)
+}
+
+/// Converts a (year, month, day) triple into days since 1970-01-01, the inverse of
+/// [`civil_from_days`].
+pub(crate) fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year.rem_euclid(400);
+    let mp = if month > 2 { month - 3 } else { month + 9 } as i64;
+    let doy = (153 * mp + 2) / 5 + day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+
+    era * 146_097 + doe - 719_468
+}
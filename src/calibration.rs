@@ -0,0 +1,79 @@
+//! Calibration data, kept in a record of the settings page (see `settings.rs`).
+//!
+//! A blank or corrupt record reads back as the defaults.
+
+use crate::{
+    log::{info, Module},
+    settings::{self, SettingsError, BODY_LEN, CALIBRATION_RECORD},
+    shared::CALIBRATION_MUTEX,
+    units::{self, Value, ZERO},
+};
+
+const LOG_MODULE: Module = Module::Measure;
+
+const MAGIC: u32 = 0x5044_4341; // "PDCA"
+
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) struct Calibration {
+    /// The board's own draw as seen by the INA226, in amps.
+    pub quiescent_amps: f32,
+    /// Subtract `quiescent_amps` from reported current, power and energy.
+    pub compensate: bool,
+}
+
+impl Calibration {
+    pub const fn default() -> Self {
+        Self {
+            quiescent_amps: 0.0,
+            compensate: false,
+        }
+    }
+
+    /// Current to subtract from readings, zero while compensation is off.
+    pub fn offset_amps(&self) -> Value {
+        if self.compensate {
+            units::from_f64(self.quiescent_amps as f64)
+        } else {
+            ZERO
+        }
+    }
+
+    fn to_bytes(self) -> [u8; BODY_LEN] {
+        let mut buf = [0u8; BODY_LEN];
+
+        buf[0..4].copy_from_slice(&self.quiescent_amps.to_le_bytes());
+        buf[4..8].copy_from_slice(&(self.compensate as u32).to_le_bytes());
+
+        buf
+    }
+
+    fn from_bytes(buf: &[u8; BODY_LEN]) -> Self {
+        let word = |i: usize| u32::from_le_bytes([buf[i], buf[i + 1], buf[i + 2], buf[i + 3]]);
+
+        Self {
+            quiescent_amps: f32::from_bits(word(0)),
+            compensate: word(4) != 0,
+        }
+    }
+}
+
+/// Loads the stored calibration into `CALIBRATION_MUTEX`.
+pub(crate) async fn load() {
+    match settings::read(CALIBRATION_RECORD, MAGIC) {
+        Some(buf) => {
+            let calibration = Calibration::from_bytes(&buf);
+            info!("calibration: {:?}", calibration);
+            *CALIBRATION_MUTEX.lock().await = calibration;
+        }
+        None => info!("calibration: none stored, using defaults"),
+    }
+}
+
+/// Writes `calibration` to flash and makes it the active one.
+pub(crate) async fn store(calibration: Calibration) -> Result<(), SettingsError> {
+    settings::write(CALIBRATION_RECORD, MAGIC, &calibration.to_bytes()).await?;
+
+    *CALIBRATION_MUTEX.lock().await = calibration;
+
+    Ok(())
+}
@@ -0,0 +1,166 @@
+//! Triggered capture of the load current.
+//!
+//! The main loop feeds every raw current reading in. While armed, the last [`PRE_TRIGGER`] readings
+//! are kept; when the current rises through the threshold, [`POST_TRIGGER`] more are taken and the
+//! whole window is held for the scope page and the `capture` console command until it is armed
+//! again. Samples are one main loop pass apart, so the time base follows the power profile.
+
+use heapless::Deque;
+
+use crate::units::{self, Compact, Value, ZERO};
+
+/// Readings kept from before the trigger.
+pub(crate) const PRE_TRIGGER: usize = 64;
+/// Readings taken from the trigger on.
+pub(crate) const POST_TRIGGER: usize = 64;
+pub(crate) const CAPTURE_LEN: usize = PRE_TRIGGER + POST_TRIGGER;
+
+/// Threshold step per button press on the scope page.
+pub(crate) const THRESHOLD_STEP: Value = units::from_milli(250);
+
+/// Smallest full scale of the graph.
+const MIN_SCALE: Value = units::from_milli(500);
+
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) enum CaptureState {
+    /// No threshold set.
+    Off,
+    Armed,
+    /// Triggered, taking the readings after the trigger.
+    Triggered,
+    /// A complete window is held.
+    Held,
+}
+
+impl CaptureState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CaptureState::Off => "off",
+            CaptureState::Armed => "armed",
+            CaptureState::Triggered => "trig",
+            CaptureState::Held => "held",
+        }
+    }
+}
+
+pub(crate) struct Capture {
+    samples: Deque<Compact, CAPTURE_LEN>,
+    threshold: Value,
+    state: CaptureState,
+    /// Index of the reading that crossed the threshold.
+    trigger_at: usize,
+    /// Previous reading while armed; a trigger needs one below the threshold first.
+    last: Option<Value>,
+    /// Windows held since boot, so readers can tell a new one from the one they have shown.
+    count: u32,
+}
+
+impl Capture {
+    pub const fn new() -> Self {
+        Self {
+            samples: Deque::new(),
+            threshold: ZERO,
+            state: CaptureState::Off,
+            trigger_at: 0,
+            last: None,
+            count: 0,
+        }
+    }
+
+    pub fn state(&self) -> CaptureState {
+        self.state
+    }
+
+    pub fn threshold(&self) -> Value {
+        self.threshold
+    }
+
+    /// Sets the trigger threshold and re-arms; zero turns the capture off.
+    pub fn set_threshold(&mut self, threshold: Value) {
+        self.threshold = if threshold > ZERO { threshold } else { ZERO };
+        self.arm();
+    }
+
+    /// Drops the held window and waits for the next trigger.
+    pub fn arm(&mut self) {
+        self.samples.clear();
+        self.last = None;
+        self.state = if self.threshold > ZERO {
+            CaptureState::Armed
+        } else {
+            CaptureState::Off
+        };
+    }
+
+    /// Feeds one reading in. Returns true when it completed a window.
+    pub fn record(&mut self, amps: Value) -> bool {
+        match self.state {
+            CaptureState::Off | CaptureState::Held => false,
+            CaptureState::Armed => {
+                let crossed = matches!(self.last, Some(last) if last < self.threshold)
+                    && amps >= self.threshold;
+                self.last = Some(amps);
+
+                if self.samples.len() == PRE_TRIGGER {
+                    self.samples.pop_front();
+                }
+
+                if crossed {
+                    self.trigger_at = self.samples.len();
+                    self.state = CaptureState::Triggered;
+                }
+
+                self.samples.push_back(units::compact(amps)).ok();
+
+                self.check_complete()
+            }
+            CaptureState::Triggered => {
+                self.samples.push_back(units::compact(amps)).ok();
+
+                self.check_complete()
+            }
+        }
+    }
+
+    fn check_complete(&mut self) -> bool {
+        if self.state != CaptureState::Triggered
+            || self.samples.len() < self.trigger_at + POST_TRIGGER
+        {
+            return false;
+        }
+
+        self.state = CaptureState::Held;
+        self.count = self.count.wrapping_add(1);
+
+        true
+    }
+
+    /// Windows held since boot.
+    pub fn count(&self) -> u32 {
+        self.count
+    }
+
+    /// Index of the trigger among [`samples`](Self::samples), once triggered.
+    pub fn trigger_at(&self) -> Option<usize> {
+        match self.state {
+            CaptureState::Triggered | CaptureState::Held => Some(self.trigger_at),
+            _ => None,
+        }
+    }
+
+    pub fn samples(&self) -> impl Iterator<Item = Value> + '_ {
+        self.samples.iter().map(|s| units::expand(*s))
+    }
+
+    /// Full scale of the graph: the peak or the threshold, whichever is higher, rounded up to a
+    /// multiple of half an amp.
+    pub fn scale(&self) -> Value {
+        let peak = self
+            .samples()
+            .fold(self.threshold, |max, s| if s > max { s } else { max });
+
+        let steps = (units::milli(peak) + units::milli(MIN_SCALE) - 1) / units::milli(MIN_SCALE);
+
+        units::from_milli(steps.max(1) * units::milli(MIN_SCALE))
+    }
+}
@@ -0,0 +1,90 @@
+//! Rp advertisement and plug orientation from the Type-C CC line voltages.
+//!
+//! The source pulls the CC wire of the cable up through Rp and the HUSB238 pulls both CC pins down
+//! through its 5.1 kΩ Rd, so the pin the wire lands on settles at a level that tells how much
+//! current the source offers without a PD contract, and which one it is tells the plug
+//! orientation. The other pin stays near 0 V; a level on it points at VCONN leaking through the
+//! cable, or at a debug accessory that pulls up both. `line_monitor.rs` reads the levels.
+
+/// CC1 and CC2 in millivolts.
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) struct CcLines {
+    pub cc1_mv: u16,
+    pub cc2_mv: u16,
+}
+
+/// What a CC pin sees, using the vRd thresholds of the Type-C spec.
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) enum Rp {
+    /// Below vRa: nothing attached.
+    Open,
+    /// Default USB current, 500 or 900 mA.
+    Default,
+    Current1A5,
+    Current3A0,
+    /// Above the 3.0 A range: the pin is not loaded by Rd, or something drives it.
+    High,
+}
+
+impl Rp {
+    pub fn of(mv: u16) -> Self {
+        match mv {
+            0..=199 => Rp::Open,
+            200..=659 => Rp::Default,
+            660..=1_229 => Rp::Current1A5,
+            1_230..=2_199 => Rp::Current3A0,
+            _ => Rp::High,
+        }
+    }
+
+    /// At most 7 characters, for the diagnostics page.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Rp::Open => "open",
+            Rp::Default => "default",
+            Rp::Current1A5 => "1.5A",
+            Rp::Current3A0 => "3.0A",
+            Rp::High => "high",
+        }
+    }
+}
+
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) enum Orientation {
+    /// Neither pin sees Rp.
+    Unattached,
+    Cc1,
+    Cc2,
+    /// Both pins see Rp.
+    Both,
+}
+
+impl Orientation {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Orientation::Unattached => "none",
+            Orientation::Cc1 => "CC1",
+            Orientation::Cc2 => "CC2",
+            Orientation::Both => "both",
+        }
+    }
+}
+
+impl CcLines {
+    pub fn orientation(&self) -> Orientation {
+        match (Rp::of(self.cc1_mv), Rp::of(self.cc2_mv)) {
+            (Rp::Open, Rp::Open) => Orientation::Unattached,
+            (_, Rp::Open) => Orientation::Cc1,
+            (Rp::Open, _) => Orientation::Cc2,
+            _ => Orientation::Both,
+        }
+    }
+
+    /// What the source advertises, on the pin the cable's CC wire lands on.
+    pub fn rp(&self) -> Rp {
+        match self.orientation() {
+            Orientation::Cc2 => Rp::of(self.cc2_mv),
+            _ => Rp::of(self.cc1_mv),
+        }
+    }
+}
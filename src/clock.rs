@@ -0,0 +1,144 @@
+use core::fmt;
+
+use embassy_stm32::rtc::{self, DayOfWeek, Rtc};
+use embassy_time::Instant;
+
+use crate::{
+    calendar::{DateTime, MAX_YEAR, MIN_YEAR},
+    log::{info, warn, Module},
+    shared::{EPOCH_MUTEX, RTC_MUTEX},
+};
+
+const LOG_MODULE: Module = Module::System;
+
+/// A point in time, either wall-clock once the clock is set or time since boot.
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) enum Timestamp {
+    /// Milliseconds since the Unix epoch.
+    Unix(u64),
+    /// Milliseconds since boot.
+    Uptime(u64),
+}
+
+/// Anchors the wall clock so that `unix_secs` corresponds to the current instant, and sets the RTC
+/// to it so that it survives a reset.
+pub(crate) async fn set_unix_time(unix_secs: u64) {
+    anchor(unix_secs).await;
+
+    let time = DateTime::from_unix(unix_secs);
+
+    if let Some(rtc) = RTC_MUTEX.lock().await.as_mut() {
+        let result = to_rtc(&time).and_then(|time| rtc.set_datetime(time).ok());
+
+        if result.is_none() {
+            warn!("rtc not set to {:?}", time);
+        }
+    }
+}
+
+async fn anchor(unix_secs: u64) {
+    let uptime_ms = Instant::now().as_millis();
+
+    *EPOCH_MUTEX.lock().await = Some((unix_secs * 1000).saturating_sub(uptime_ms));
+}
+
+/// Takes the wall clock from `rtc` if it was set before the reset, and keeps `rtc` for
+/// [`set_unix_time`]. The RTC runs on through resets, and through power cycles with a backup
+/// battery on VBAT.
+pub(crate) async fn restore(rtc: Rtc) {
+    match rtc.now().ok().and_then(from_rtc) {
+        Some(time) => {
+            info!("rtc time {}", time);
+            anchor(time.to_unix()).await;
+        }
+        None => info!("rtc not set"),
+    }
+
+    *RTC_MUTEX.lock().await = Some(rtc);
+}
+
+/// The wall clock in seconds since the Unix epoch, once set.
+pub(crate) async fn unix_secs() -> Option<u64> {
+    match now().await {
+        Timestamp::Unix(ms) => Some(ms / 1000),
+        Timestamp::Uptime(_) => None,
+    }
+}
+
+pub(crate) async fn now() -> Timestamp {
+    at(Instant::now().as_millis()).await
+}
+
+/// Converts a time since boot into a timestamp, wall-clock if synchronized.
+pub(crate) async fn at(uptime_ms: u64) -> Timestamp {
+    match *EPOCH_MUTEX.lock().await {
+        Some(epoch_ms) => Timestamp::Unix(epoch_ms + uptime_ms),
+        None => Timestamp::Uptime(uptime_ms),
+    }
+}
+
+impl fmt::Display for Timestamp {
+    /// Formats as ISO 8601 UTC (`2024-05-01T12:34:56.789Z`) or as `+seconds.millis` since boot.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Timestamp::Unix(ms) => {
+                let time = DateTime::from_unix(ms / 1000);
+
+                write!(
+                    f,
+                    "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+                    time.year,
+                    time.month,
+                    time.day,
+                    time.hour,
+                    time.minute,
+                    time.second,
+                    ms % 1000
+                )
+            }
+            Timestamp::Uptime(ms) => write!(f, "+{}.{:03}", ms / 1000, ms % 1000),
+        }
+    }
+}
+
+/// `time` as the RTC holds it; none outside the century it counts.
+fn to_rtc(time: &DateTime) -> Option<rtc::DateTime> {
+    if !(2000..=MAX_YEAR).contains(&time.year) {
+        return None;
+    }
+
+    let weekday = match time.weekday() {
+        1 => DayOfWeek::Monday,
+        2 => DayOfWeek::Tuesday,
+        3 => DayOfWeek::Wednesday,
+        4 => DayOfWeek::Thursday,
+        5 => DayOfWeek::Friday,
+        6 => DayOfWeek::Saturday,
+        _ => DayOfWeek::Sunday,
+    };
+
+    rtc::DateTime::from(
+        time.year,
+        time.month,
+        time.day,
+        weekday,
+        time.hour,
+        time.minute,
+        time.second,
+    )
+    .ok()
+}
+
+/// The time the RTC holds, or none if it was never set since its backup domain lost power.
+fn from_rtc(time: rtc::DateTime) -> Option<DateTime> {
+    let time = DateTime {
+        year: time.year(),
+        month: time.month(),
+        day: time.day(),
+        hour: time.hour(),
+        minute: time.minute(),
+        second: time.second(),
+    };
+
+    (time.year >= MIN_YEAR).then_some(time)
+}
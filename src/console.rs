@@ -0,0 +1,1437 @@
+use core::fmt::{self, Write};
+
+use embassy_stm32::time::Hertz;
+use embassy_time::{Duration, Instant, Timer};
+use heapless::{String, Vec};
+
+#[cfg(feature = "dual-output")]
+use crate::shared::{OCP_B_MUTEX, OUTPUT_B_MUTEX, SECOND_POWER_MUTEX};
+use crate::{
+    average::AverageInterval,
+    bootloader, bsp,
+    button::{DOUBLE_CLICK_TIMEOUT, MIN_PRESS_DURATION, SHORT_PRESS_DURATION},
+    calibration,
+    capture::CAPTURE_LEN,
+    clock,
+    conversion::{Averaging, Conversion, ConversionTime},
+    display_settings,
+    fan::FanCurve,
+    fault::{self, FAULTS},
+    filter::FilterKind,
+    fmt::fixed_milli,
+    fuse_settings,
+    heartbeat::{self, TASKS},
+    log::{self, error, warn, Level, Module, MODULES},
+    monitor_settings,
+    plotter::{PLOT_DEFAULT_HZ, PLOT_RATES_HZ},
+    power_monitor::PowerMonitorConfig,
+    protection::{FuseLimit, FUSE_MAX_TRIPS},
+    rails::{Rails, SWITCH_LIMIT_MAX},
+    remote,
+    schedule::{Schedule, MAX_HOURS},
+    scheduler, screenshot,
+    session::{self, Report, OCP_BANDS_PERCENT},
+    shared::{
+        ACTIVITY_PUBSUB, AVERAGE_INTERVAL_MUTEX, AVERAGE_MUTEX, BACKLIGHT_TIMEOUT_MUTEX,
+        BUTTON_TRACE_MUTEX, CALIBRATION_MUTEX, CAPTURE_MUTEX, CONSOLE_LINE_LEN, CONSOLE_TX_CHANNEL,
+        CONVERSION_MUTEX, DISPLAY_DEGRADED_MUTEX, DISPLAY_SPI_MAX_MUTEX, DISPLAY_SPI_PUBSUB,
+        FAN_CURVE_MUTEX, FAN_STATUS_MUTEX, FAULTS_MUTEX, FILTER_MUTEX, FILTER_PUBSUB,
+        FUSE_BLOWN_MUTEX, FUSE_LIMIT_MUTEX, HISTORY_MUTEX, LAST_CRASH_MUTEX, MQTT_INTERVAL_MUTEX,
+        NEXT_ACTION_MUTEX, OCP_MAX, OCP_MUTEX, OCP_PUBSUB, OUTPUT_MUTEX, OUTPUT_STATS_MUTEX,
+        PLOT_MUTEX, POWER_INFO_MUTEX, POWER_PROFILE_MUTEX, POWER_PROFILE_PUBSUB, RAW_POWER_MUTEX,
+        REMOTE_MUTEX, RMS_MUTEX, SCHEDULE_MUTEX, SELFTEST_MUTEX, SESSION_MUTEX, SHORT_TEST_MUTEX,
+        SLEW_LIMITS_MUTEX, STATUS_INFO_MUTEX, SWITCH_LIMIT_MUTEX, VBUS_MUTEX, WATTS_SOURCE_MUTEX,
+        WATTS_SOURCE_PUBSUB,
+    },
+    slew::MAX_RATE_MILLI,
+    slew_settings, stack,
+    timing::{self, SECTIONS},
+    types::{Channel, ConsoleRx, PowerProfile},
+    units::{self, fixed, Value, ZERO},
+    updater::Updater,
+    watts::WattsSource,
+};
+#[cfg(feature = "trigger")]
+use crate::{
+    shared::TRIGGER_MUTEX,
+    trigger::{TriggerEvent, TriggerEvents, TriggerMode, TRIGGER_EVENTS},
+};
+
+const LOG_MODULE: Module = Module::Console;
+
+/// Queues a line for the console TX task. Lines are dropped when the queue is full.
+pub(crate) fn println(args: fmt::Arguments) {
+    if CONSOLE_TX_CHANNEL.try_send(format_line(args)).is_err() {
+        warn!("console tx channel full");
+    }
+}
+
+/// A warning or error from the `log` macros on `log-uart` builds, e.g. `WARN output: ...`. Dropped
+/// silently on a full queue, as the warning about that would come back here.
+#[cfg(feature = "log-uart")]
+pub(crate) fn log_line(level: Level, module: Module, args: fmt::Arguments) {
+    let tag = match level {
+        Level::Error => "ERROR",
+        _ => "WARN",
+    };
+
+    let mut line: String<CONSOLE_LINE_LEN> = String::new();
+    write!(line, "{} {}: ", tag, module.as_str()).ok();
+    line.write_fmt(args).ok();
+    line.truncate(CONSOLE_LINE_LEN - 2);
+    line.push_str("\r\n").ok();
+
+    CONSOLE_TX_CHANNEL.try_send(line).ok();
+}
+
+/// Like [`println`], but waits for room in the queue. Used for bulk output.
+pub(crate) async fn write_line(args: fmt::Arguments<'_>) {
+    CONSOLE_TX_CHANNEL.send(format_line(args)).await;
+}
+
+fn format_line(args: fmt::Arguments) -> String<CONSOLE_LINE_LEN> {
+    let mut line: String<CONSOLE_LINE_LEN> = String::new();
+
+    if line.write_fmt(args).is_err() || line.push_str("\r\n").is_err() {
+        warn!("console line truncated");
+    }
+
+    line
+}
+
+pub(crate) struct Console {
+    rx: ConsoleRx,
+    line: Vec<u8, CONSOLE_LINE_LEN>,
+    updater: Updater,
+}
+
+impl Console {
+    pub fn new(rx: ConsoleRx) -> Self {
+        Self {
+            rx,
+            line: Vec::new(),
+            updater: Updater::new(),
+        }
+    }
+
+    pub async fn task(&mut self) {
+        let mut buf = [0u8; 32];
+
+        loop {
+            let len = match self.rx.read_until_idle(&mut buf).await {
+                Ok(len) => len,
+                Err(_) => {
+                    error!("console read error");
+                    continue;
+                }
+            };
+
+            for &byte in &buf[..len] {
+                match byte {
+                    b'\r' | b'\n' => {
+                        if self.line.is_empty() {
+                            continue;
+                        }
+
+                        let line = self.line.clone();
+                        self.line.clear();
+
+                        // A host at the console keeps the unit out of idle, see `idle.rs`.
+                        ACTIVITY_PUBSUB.immediate_publisher().publish_immediate(());
+
+                        match core::str::from_utf8(&line) {
+                            Ok(line) => self.handle_line(line).await,
+                            Err(_) => println(format_args!("ERR invalid utf-8")),
+                        }
+                    }
+                    _ => {
+                        if self.line.push(byte).is_err() {
+                            self.line.clear();
+                            println(format_args!("ERR line too long"));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    async fn handle_line(&mut self, line: &str) {
+        let mut args = line.split_whitespace();
+
+        match (args.next(), args.next()) {
+            (Some("help"), _) => {
+                println(format_args!("status | out on|off | pdo 5|9|12|15|18|20"));
+                println(format_args!("ocp [<A> | off]"));
+                println(format_args!(
+                    "time [unix seconds] | mqtt interval <seconds>"
+                ));
+                println(format_args!("bootloader | fw [begin <size> <crc32>]"));
+                println(format_args!(
+                    "fw data <offset> <hex> | fw commit | fw abort"
+                ));
+                println(format_args!(
+                    "log [module] [off|error|warn|info|debug|trace]"
+                ));
+                println(format_args!("screenshot | export history | export capture"));
+                println(format_args!("plot [<1..50 Hz> | off]"));
+                println(format_args!("buttons [on|off]"));
+                println(format_args!("capture | capture arm"));
+                println(format_args!("session [start | stop | report]"));
+                println(format_args!("backlight timeout <seconds, 0 = never dim>"));
+                println(format_args!("spi [display clock limit in MHz]"));
+                println(format_args!("profile [performance|balanced|eco]"));
+                println(format_args!("filter [off|ema|combined]"));
+                println(format_args!("watts [register|product|synced|peak]"));
+                println(format_args!("avg [off|1s|10s|1min]"));
+                println(format_args!("shorttest [on|off]"));
+                println(format_args!(
+                    "cal | cal quiescent <mA>|measure | cal compensate on|off"
+                ));
+                println(format_args!("faults clear | crash | selftest | stats"));
+                println(format_args!(
+                    "tasks | tasks reset | timing | timing reset | mem"
+                ));
+                println(format_args!("fan | fan curve <start C> <full C> <min %>"));
+                println(format_args!("schedule [<HH:MM UTC> <hours> | off]"));
+                println(format_args!(
+                    "slew | slew dv <V/s> | slew di <A/s> | slew trip on|off"
+                ));
+                println(format_args!(
+                    "ina226 [2] [shunt <mOhm> | max <A> | addr <hex>]"
+                ));
+                println(format_args!("conversion [avg <1..1024> | us <140..8244>]"));
+                println(format_args!(
+                    "fuse | fuse <trips> <seconds> | fuse off | fuse reset"
+                ));
+                println(format_args!("switch | switch limit <W>|off"));
+                #[cfg(feature = "trigger")]
+                println(format_args!(
+                    "trigger [pulse|toggle] | trigger events trip,output,capture|none"
+                ));
+            }
+            (Some("status"), _) => self.print_status().await,
+            (Some("out"), Some("on")) => remote::request_output(true).await,
+            (Some("out"), Some("off")) => remote::request_output(false).await,
+            (Some("pdo"), Some(volts)) => self.request_pdo(volts).await,
+            (Some("ocp"), None) => print_ocp(*OCP_MUTEX.lock().await),
+            (Some("ocp"), Some(amps)) => self.set_ocp(amps).await,
+            (Some("time"), None) => println(format_args!("{}", clock::now().await)),
+            (Some("time"), Some(secs)) => self.set_time(secs).await,
+            (Some("bootloader"), None) => {
+                println(format_args!("OK entering bootloader"));
+
+                // Give the TX task a chance to flush the reply.
+                Timer::after(Duration::from_millis(50)).await;
+
+                bootloader::jump_to_bootloader();
+            }
+            (Some("fw"), cmd) => self.handle_update(cmd, args).await,
+            (Some("mqtt"), Some("interval")) => self.set_mqtt_interval(args.next()).await,
+            (Some("cal"), cmd) => self.handle_calibration(cmd, args.next()).await,
+            (Some("profile"), None) => {
+                let profile = *POWER_PROFILE_MUTEX.lock().await;
+                println(format_args!("profile {}", profile.as_str()));
+            }
+            (Some("profile"), Some(profile)) => self.set_profile(profile).await,
+            (Some("filter"), None) => {
+                let filter = *FILTER_MUTEX.lock().await;
+                println(format_args!("filter {}", filter.as_str()));
+            }
+            (Some("filter"), Some(filter)) => self.set_filter(filter).await,
+            (Some("watts"), None) => {
+                let source = *WATTS_SOURCE_MUTEX.lock().await;
+                println(format_args!("watts {}", source.as_str()));
+            }
+            (Some("watts"), Some(source)) => self.set_watts_source(source).await,
+            (Some("avg"), None) => {
+                let interval = *AVERAGE_INTERVAL_MUTEX.lock().await;
+                println(format_args!("avg {}", interval.as_str()));
+            }
+            (Some("avg"), Some(interval)) => self.set_average_interval(interval).await,
+            (Some("shorttest"), None) => {
+                let on = *SHORT_TEST_MUTEX.lock().await;
+                println(format_args!("shorttest {}", if on { "on" } else { "off" }));
+            }
+            (Some("shorttest"), Some("on")) => self.set_short_test(true).await,
+            (Some("shorttest"), Some("off")) => self.set_short_test(false).await,
+            (Some("spi"), None) => {
+                let max = *DISPLAY_SPI_MAX_MUTEX.lock().await;
+                println(format_args!("spi {} kHz", max.0 / 1_000));
+            }
+            (Some("spi"), Some(mhz)) => self.set_display_spi(mhz).await,
+            (Some("backlight"), Some("timeout")) => self.set_backlight_timeout(args.next()).await,
+            (Some("screenshot"), None) => screenshot::capture().await,
+            (Some("plot"), rate) => self.set_plot(rate).await,
+            (Some("buttons"), None) => {
+                let on = *BUTTON_TRACE_MUTEX.lock().await;
+                println(format_args!(
+                    "buttons {} bounce {} ms long {} ms double {} ms",
+                    if on { "on" } else { "off" },
+                    MIN_PRESS_DURATION.as_millis(),
+                    SHORT_PRESS_DURATION.as_millis(),
+                    DOUBLE_CLICK_TIMEOUT.as_millis()
+                ));
+            }
+            (Some("buttons"), Some("on")) => self.set_button_trace(true).await,
+            (Some("buttons"), Some("off")) => self.set_button_trace(false).await,
+            (Some("export"), Some("history")) => self.export_history().await,
+            (Some("export"), Some("capture")) => self.export_capture().await,
+            (Some("capture"), None) => {
+                let capture = CAPTURE_MUTEX.lock().await;
+                println(format_args!(
+                    "capture {} trig={}A",
+                    capture.state().as_str(),
+                    fixed(capture.threshold(), 2, 0)
+                ));
+            }
+            (Some("capture"), Some("arm")) => {
+                CAPTURE_MUTEX.lock().await.arm();
+                println(format_args!("OK capture armed"));
+            }
+            (Some("session"), None | Some("report")) => print_session(None).await,
+            (Some("session"), Some("start")) => {
+                SESSION_MUTEX.lock().await.start(Instant::now());
+                println(format_args!("OK session started"));
+            }
+            (Some("session"), Some("stop")) => {
+                let report = SESSION_MUTEX.lock().await.stop(Instant::now());
+
+                match report {
+                    Some(report) => print_session(Some(report)).await,
+                    None => println(format_args!("ERR no session running")),
+                }
+            }
+            (Some("faults"), Some("clear")) => {
+                fault::clear().await;
+                println(format_args!("OK faults cleared"));
+            }
+            (Some("crash"), None) => self.print_crash().await,
+            (Some("selftest"), None) => self.print_selftest().await,
+            (Some("stats"), None) => {
+                let stats = *OUTPUT_STATS_MUTEX.lock().await;
+                println(format_args!(
+                    "output on={}s cycles={}",
+                    stats.on_secs, stats.cycles
+                ));
+            }
+            (Some("tasks"), None) => self.print_tasks(),
+            (Some("tasks"), Some("reset")) => {
+                heartbeat::reset();
+                println(format_args!("OK task statistics reset"));
+            }
+            (Some("mem"), None) => print_memory(),
+            (Some("timing"), None) => self.print_timing().await,
+            (Some("timing"), Some("reset")) => {
+                timing::reset();
+                println(format_args!("OK timing reset"));
+            }
+            (Some("log"), arg) => self.handle_log(arg, args.next()),
+            (Some("fan"), None) => self.print_fan().await,
+            (Some("fan"), Some("curve")) => self.set_fan_curve(args).await,
+            (Some("slew"), None) => self.print_slew().await,
+            (Some("slew"), cmd) => self.set_slew(cmd, args.next()).await,
+            (Some("switch"), None) => self.print_switch().await,
+            (Some("switch"), Some("limit")) => self.set_switch_limit(args.next()).await,
+            (Some("fuse"), None) => self.print_fuse().await,
+            (Some("fuse"), Some("reset")) => self.reset_fuse().await,
+            (Some("fuse"), Some("off")) => self.set_fuse(FuseLimit::off()).await,
+            (Some("fuse"), Some(trips)) => self.parse_fuse(trips, args.next()).await,
+            (Some("ina226"), None) => self.print_power_monitors(),
+            #[cfg(feature = "dual-output")]
+            (Some("ina226"), Some("2")) => {
+                self.set_power_monitor(Channel::B, args.next(), args.next())
+                    .await
+            }
+            (Some("ina226"), cmd) => self.set_power_monitor(Channel::A, cmd, args.next()).await,
+            (Some("conversion"), None) => print_conversion(*CONVERSION_MUTEX.lock().await),
+            (Some("conversion"), cmd) => self.set_conversion(cmd, args.next()).await,
+            (Some("schedule"), None) => self.print_schedule().await,
+            (Some("schedule"), Some("off")) => self.set_schedule(None).await,
+            (Some("schedule"), Some(on_at)) => self.parse_schedule(on_at, args.next()).await,
+            #[cfg(feature = "trigger")]
+            (Some("trigger"), arg) => self.handle_trigger(arg, args.next()).await,
+            _ => println(format_args!("ERR unknown command: {}", line)),
+        }
+    }
+
+    async fn print_status(&mut self) {
+        let power = *POWER_INFO_MUTEX.lock().await;
+        let status = *STATUS_INFO_MUTEX.lock().await;
+        let is_remote = *REMOTE_MUTEX.lock().await;
+        let ocp = *OCP_MUTEX.lock().await;
+
+        println(format_args!(
+            "V={} A={} W={}",
+            fixed(power.volts, 3, 0),
+            fixed(power.amps, 3, 0),
+            fixed(power.watts, 3, 0)
+        ));
+        let raw = *RAW_POWER_MUTEX.lock().await;
+        println(format_args!(
+            "RAW V={} A={} W={}",
+            fixed(raw.volts, 3, 0),
+            fixed(raw.amps, 3, 0),
+            fixed(raw.watts, 3, 0)
+        ));
+        if let Some(average) = *AVERAGE_MUTEX.lock().await {
+            let interval = *AVERAGE_INTERVAL_MUTEX.lock().await;
+
+            println(format_args!(
+                "AVG {} V={} A={} W={}",
+                interval.as_str(),
+                fixed(average.volts, 3, 0),
+                fixed(average.amps, 3, 0),
+                fixed(average.watts, 3, 0)
+            ));
+        }
+        if let Some(rms) = *RMS_MUTEX.lock().await {
+            println(format_args!("RMS 1s A={}", fixed(rms, 3, 0)));
+        }
+        println(format_args!(
+            "PDO={}V Max={}A OCP={}A Out={} Remote={}",
+            fixed(status.target_volts, 1, 0),
+            fixed(status.limit_amps, 2, 0),
+            fixed(ocp, 2, 0),
+            if status.output { "on" } else { "off" },
+            if is_remote { "yes" } else { "no" },
+        ));
+
+        if let Some(vbus) = *VBUS_MUTEX.lock().await {
+            println(format_args!("VBUS={}V", fixed(vbus, 3, 0)));
+        }
+
+        #[cfg(feature = "dual-output")]
+        {
+            let ocp = *OCP_B_MUTEX.lock().await;
+            let output = *OUTPUT_B_MUTEX.lock().await;
+
+            match *SECOND_POWER_MUTEX.lock().await {
+                Some(power) => println(format_args!(
+                    "CH2 V={} A={} W={} OCP={}A Out={}",
+                    fixed(power.volts, 3, 0),
+                    fixed(power.amps, 3, 0),
+                    fixed(power.watts, 3, 0),
+                    fixed(ocp, 2, 0),
+                    if output { "on" } else { "off" },
+                )),
+                None => println(format_args!("CH2 no reading")),
+            }
+        }
+
+        let faults = *FAULTS_MUTEX.lock().await;
+        for fault in FAULTS.iter().filter(|f| faults.contains(**f)) {
+            println(format_args!("FAULT {}", fault.as_str()));
+        }
+    }
+
+    /// Reports how the previous run ended, if it crashed.
+    async fn print_crash(&mut self) {
+        match &*LAST_CRASH_MUTEX.lock().await {
+            Some(crash) => {
+                println(format_args!(
+                    "crash {} pc={:08x} lr={:08x}",
+                    crash.kind.as_str(),
+                    crash.pc,
+                    crash.lr
+                ));
+
+                if !crash.message.is_empty() {
+                    println(format_args!("{}", crash.message));
+                }
+            }
+            None => println(format_args!("crash none")),
+        }
+    }
+
+    /// Repeats the power-on self-test results.
+    async fn print_selftest(&mut self) {
+        let Some(selftest) = *SELFTEST_MUTEX.lock().await else {
+            println(format_args!("ERR selftest not run"));
+            return;
+        };
+
+        for (peripheral, result) in selftest.checks() {
+            match result {
+                Ok(()) => println(format_args!("SELFTEST {} ok", peripheral.as_str())),
+                Err(err) => println(format_args!(
+                    "SELFTEST {} FAIL {}",
+                    peripheral.as_str(),
+                    err.as_str()
+                )),
+            }
+        }
+    }
+
+    /// Heartbeat age and loop latency of each monitored task.
+    fn print_tasks(&mut self) {
+        for task in TASKS {
+            let stats = heartbeat::stats(task);
+
+            match stats.age_ms {
+                Some(age) => println(format_args!(
+                    "TASK {} age={}ms mean={}ms max={}ms beats={}",
+                    task.as_str(),
+                    age,
+                    stats.mean_gap_ms,
+                    stats.max_gap_ms,
+                    stats.beats
+                )),
+                None => println(format_args!("TASK {} never ran", task.as_str())),
+            }
+        }
+    }
+
+    /// Mean and worst-case execution time of the measurement loop sections, and whether slow
+    /// drawings hold the small print on the screen back.
+    async fn print_timing(&mut self) {
+        for section in SECTIONS {
+            let stats = timing::stats(section);
+
+            println(format_args!(
+                "TIMING {} mean={}us max={}us count={}",
+                section.as_str(),
+                stats.mean_us,
+                stats.max_us,
+                stats.count
+            ));
+        }
+
+        let degraded = *DISPLAY_DEGRADED_MUTEX.lock().await;
+        println(format_args!(
+            "TIMING render {}",
+            if degraded { "degraded" } else { "normal" }
+        ));
+    }
+
+    async fn request_pdo(&mut self, volts: &str) {
+        let volts = volts.trim_end_matches(['v', 'V']);
+
+        let Ok(volts) = volts.parse::<u16>() else {
+            println(format_args!("ERR unknown pdo: {}", volts));
+            return;
+        };
+
+        match remote::request_pdo(volts).await {
+            Ok(_) => println(format_args!("OK pdo {}V", volts)),
+            Err(err) => println(format_args!("ERR {}", err.as_str())),
+        }
+    }
+
+    /// Sets the OCP of the first channel, as the OCP page does; `off` turns it off.
+    async fn set_ocp(&mut self, amps: &str) {
+        let ocp = match amps.trim_end_matches(['a', 'A']) {
+            "off" => ZERO,
+            amps => match amps.parse::<f32>() {
+                Ok(amps) if amps > 0.0 && units::from_f64(amps as f64) <= OCP_MAX => {
+                    units::from_f64(amps as f64)
+                }
+                _ => {
+                    println(format_args!(
+                        "ERR expected amps up to {} or off",
+                        fixed(OCP_MAX, 0, 0)
+                    ));
+                    return;
+                }
+            },
+        };
+
+        *OCP_MUTEX.lock().await = ocp;
+        OCP_PUBSUB.immediate_publisher().publish_immediate(ocp);
+
+        print_ocp(ocp);
+    }
+
+    /// The drop across the output switch and what it dissipates, on builds that sense VBUS, and
+    /// the dissipation that raises an alarm.
+    async fn print_switch(&mut self) {
+        let limit = *SWITCH_LIMIT_MUTEX.lock().await;
+        let mut line: String<CONSOLE_LINE_LEN> = String::new();
+
+        write!(line, "switch").ok();
+        if let Some(source) = *VBUS_MUTEX.lock().await {
+            let power = *RAW_POWER_MUTEX.lock().await;
+            let rails = Rails {
+                contract: STATUS_INFO_MUTEX.lock().await.target_volts,
+                source,
+                load: power.volts,
+            };
+
+            write!(
+                line,
+                " drop={}V loss={}W",
+                fixed(rails.switch_drop(), 3, 0),
+                fixed(rails.switch_watts(power.amps), 2, 0)
+            )
+            .ok();
+        }
+        if limit > ZERO {
+            write!(line, " limit={}W", fixed(limit, 2, 0)).ok();
+        } else {
+            write!(line, " limit=off").ok();
+        }
+
+        println(format_args!("{}", line));
+    }
+
+    async fn set_switch_limit(&mut self, watts: Option<&str>) {
+        let limit = match watts.map(|w| w.trim_end_matches(['w', 'W'])) {
+            Some("off") => ZERO,
+            Some(watts) => match watts.parse::<f32>() {
+                Ok(watts) if watts > 0.0 && watts <= SWITCH_LIMIT_MAX as f32 => {
+                    units::from_f64(watts as f64)
+                }
+                _ => {
+                    println(format_args!(
+                        "ERR expected watts up to {} or off",
+                        SWITCH_LIMIT_MAX
+                    ));
+                    return;
+                }
+            },
+            None => {
+                println(format_args!("ERR expected watts or off"));
+                return;
+            }
+        };
+
+        *SWITCH_LIMIT_MUTEX.lock().await = limit;
+        self.print_switch().await;
+    }
+
+    /// Dumps the sample history as CSV, oldest first.
+    async fn export_history(&mut self) {
+        let end = Instant::now().as_secs() as u32;
+        let mut last = None;
+
+        write_line(format_args!("time,volts,amps,watts")).await;
+
+        loop {
+            let Some(sample) = HISTORY_MUTEX.lock().await.next_after(last) else {
+                break;
+            };
+
+            if sample.uptime_secs > end {
+                break;
+            }
+
+            write_line(format_args!(
+                "{},{},{},{}",
+                clock::at(sample.uptime_secs as u64 * 1000).await,
+                fixed(units::expand(sample.volts), 3, 0),
+                fixed(units::expand(sample.amps), 3, 0),
+                fixed(units::expand(sample.watts), 3, 0)
+            ))
+            .await;
+
+            last = Some(sample.uptime_secs);
+        }
+
+        write_line(format_args!("END")).await;
+    }
+
+    /// Dumps the captured readings as CSV, numbered from the trigger.
+    async fn export_capture(&mut self) {
+        let capture = CAPTURE_MUTEX.lock().await;
+
+        let Some(trigger_at) = capture.trigger_at() else {
+            println(format_args!("ERR no capture"));
+            return;
+        };
+        let samples: Vec<Value, CAPTURE_LEN> = capture.samples().collect();
+
+        drop(capture);
+
+        write_line(format_args!("sample,amps")).await;
+
+        for (i, amps) in samples.iter().enumerate() {
+            write_line(format_args!(
+                "{},{}",
+                i as i32 - trigger_at as i32,
+                fixed(*amps, 3, 0)
+            ))
+            .await;
+        }
+
+        write_line(format_args!("END")).await;
+    }
+
+    async fn set_mqtt_interval(&mut self, seconds: Option<&str>) {
+        match seconds.and_then(|s| s.parse::<u16>().ok()) {
+            Some(seconds) if seconds > 0 => {
+                *MQTT_INTERVAL_MUTEX.lock().await = seconds;
+                println(format_args!("OK mqtt interval {}s", seconds));
+            }
+            _ => println(format_args!("ERR expected interval in seconds")),
+        }
+    }
+
+    /// `log` lists the levels, `log <level>` sets all modules, `log <module> <level>` sets one.
+    fn handle_log(&mut self, first: Option<&str>, second: Option<&str>) {
+        match (first, second) {
+            (None, _) => {
+                for module in MODULES {
+                    println(format_args!(
+                        "{}={}",
+                        module.as_str(),
+                        log::level(*module).as_str()
+                    ));
+                }
+            }
+            (Some(level), None) => match Level::parse(level) {
+                Some(level) => {
+                    log::set_all_levels(level);
+                    println(format_args!("OK log {}", level.as_str()));
+                }
+                None => println(format_args!("ERR unknown level: {}", level)),
+            },
+            (Some(module), Some(level)) => match (Module::parse(module), Level::parse(level)) {
+                (Some(module), Some(level)) => {
+                    log::set_level(module, level);
+                    println(format_args!(
+                        "OK log {} {}",
+                        module.as_str(),
+                        level.as_str()
+                    ));
+                }
+                (None, _) => println(format_args!("ERR unknown module: {}", module)),
+                (_, None) => println(format_args!("ERR unknown level: {}", level)),
+            },
+        }
+    }
+
+    async fn handle_calibration(&mut self, cmd: Option<&str>, arg: Option<&str>) {
+        let mut calibration = *CALIBRATION_MUTEX.lock().await;
+
+        match (cmd, arg) {
+            (None, _) => {
+                println(format_args!(
+                    "quiescent={}mA compensate={}",
+                    crate::fmt::fixed(calibration.quiescent_amps as f64 * 1000.0, 1, 0),
+                    if calibration.compensate { "on" } else { "off" }
+                ));
+                return;
+            }
+            (Some("quiescent"), Some("measure")) => {
+                calibration.quiescent_amps = self.measure_quiescent_amps().await;
+            }
+            (Some("quiescent"), Some(milliamps)) => match milliamps.parse::<f32>() {
+                Ok(milliamps) => calibration.quiescent_amps = milliamps / 1000.0,
+                Err(_) => {
+                    println(format_args!("ERR expected current in mA"));
+                    return;
+                }
+            },
+            (Some("compensate"), Some("on")) => calibration.compensate = true,
+            (Some("compensate"), Some("off")) => calibration.compensate = false,
+            _ => {
+                println(format_args!("ERR unknown calibration command"));
+                return;
+            }
+        }
+
+        match calibration::store(calibration).await {
+            Ok(_) => println(format_args!(
+                "OK quiescent={}mA compensate={}",
+                crate::fmt::fixed(calibration.quiescent_amps as f64 * 1000.0, 1, 0),
+                if calibration.compensate { "on" } else { "off" }
+            )),
+            Err(err) => println(format_args!("ERR {}", err.as_str())),
+        }
+    }
+
+    /// Averages the uncompensated current for two seconds. Nothing may be connected to the
+    /// output while this runs.
+    async fn measure_quiescent_amps(&mut self) -> f32 {
+        const SAMPLES: u32 = 20;
+
+        let mut sum = ZERO;
+
+        for _ in 0..SAMPLES {
+            Timer::after(Duration::from_millis(100)).await;
+
+            let amps = POWER_INFO_MUTEX.lock().await.amps;
+            sum += amps + CALIBRATION_MUTEX.lock().await.offset_amps();
+        }
+
+        (units::to_f64(sum) / SAMPLES as f64) as f32
+    }
+
+    async fn set_profile(&mut self, profile: &str) {
+        let Some(profile) = PowerProfile::parse(profile) else {
+            println(format_args!("ERR unknown profile: {}", profile));
+            return;
+        };
+
+        *POWER_PROFILE_MUTEX.lock().await = profile;
+        POWER_PROFILE_PUBSUB
+            .immediate_publisher()
+            .publish_immediate(profile);
+
+        println(format_args!("OK profile {}", profile.as_str()));
+    }
+
+    async fn set_filter(&mut self, filter: &str) {
+        let Some(filter) = FilterKind::parse(filter) else {
+            println(format_args!("ERR unknown filter: {}", filter));
+            return;
+        };
+
+        *FILTER_MUTEX.lock().await = filter;
+        FILTER_PUBSUB
+            .immediate_publisher()
+            .publish_immediate(filter);
+
+        println(format_args!("OK filter {}", filter.as_str()));
+    }
+
+    async fn set_short_test(&mut self, on: bool) {
+        *SHORT_TEST_MUTEX.lock().await = on;
+
+        println(format_args!(
+            "OK shorttest {}",
+            if on { "on" } else { "off" }
+        ));
+    }
+
+    async fn set_watts_source(&mut self, source: &str) {
+        let Some(source) = WattsSource::parse(source) else {
+            println(format_args!("ERR unknown watts source: {}", source));
+            return;
+        };
+
+        *WATTS_SOURCE_MUTEX.lock().await = source;
+        WATTS_SOURCE_PUBSUB
+            .immediate_publisher()
+            .publish_immediate(source);
+
+        println(format_args!("OK watts {}", source.as_str()));
+    }
+
+    /// Starts the serial plotter stream at `rate` lines per second, the default without one, or
+    /// stops it. The header goes out in place of `OK`, see `plotter.rs`.
+    async fn set_plot(&mut self, rate: Option<&str>) {
+        let hz = match rate {
+            Some("off") => {
+                *PLOT_MUTEX.lock().await = None;
+                println(format_args!("OK plot off"));
+                return;
+            }
+            Some(hz) => hz.parse().ok().filter(|hz| PLOT_RATES_HZ.contains(hz)),
+            None => Some(PLOT_DEFAULT_HZ),
+        };
+
+        let Some(hz) = hz else {
+            println(format_args!(
+                "ERR plot rate {} to {} Hz",
+                PLOT_RATES_HZ.start(),
+                PLOT_RATES_HZ.end()
+            ));
+            return;
+        };
+
+        *PLOT_MUTEX.lock().await = Some(hz);
+        println(format_args!("V I P"));
+    }
+
+    /// Starts or stops the button states on the console, see `button_trace.rs`.
+    async fn set_button_trace(&mut self, on: bool) {
+        *BUTTON_TRACE_MUTEX.lock().await = on;
+
+        println(format_args!("OK buttons {}", if on { "on" } else { "off" }));
+    }
+
+    async fn set_average_interval(&mut self, interval: &str) {
+        let Some(interval) = AverageInterval::parse(interval) else {
+            println(format_args!("ERR unknown interval: {}", interval));
+            return;
+        };
+
+        *AVERAGE_INTERVAL_MUTEX.lock().await = interval;
+
+        println(format_args!("OK avg {}", interval.as_str()));
+    }
+
+    /// Takes effect on the next pass of the measurement loop, see `conversion.rs`.
+    async fn set_conversion(&mut self, cmd: Option<&str>, arg: Option<&str>) {
+        let mut conversion = *CONVERSION_MUTEX.lock().await;
+
+        match (cmd, arg) {
+            (Some("avg"), Some(count)) => match Averaging::parse(count) {
+                Some(averaging) => conversion.averaging = averaging,
+                None => {
+                    println(format_args!(
+                        "ERR averaging: 1, 4, 16, 64, 128, 256, 512 or 1024"
+                    ));
+                    return;
+                }
+            },
+            (Some("us"), Some(micros)) => match ConversionTime::parse(micros) {
+                Some(time) => conversion.time = time,
+                None => {
+                    println(format_args!(
+                        "ERR conversion time: 140, 204, 332, 588, 1100, 2116, 4156 or 8244"
+                    ));
+                    return;
+                }
+            },
+            _ => {
+                println(format_args!(
+                    "ERR usage: conversion [avg <count> | us <micros>]"
+                ));
+                return;
+            }
+        }
+
+        *CONVERSION_MUTEX.lock().await = conversion;
+
+        print_conversion(conversion);
+    }
+
+    #[cfg(feature = "trigger")]
+    async fn handle_trigger(&mut self, arg: Option<&str>, events: Option<&str>) {
+        let mut config = TRIGGER_MUTEX.lock().await;
+
+        match (arg, events) {
+            (None, _) => {}
+            (Some("events"), Some("none")) => config.events = TriggerEvents::empty(),
+            (Some("events"), Some(list)) => {
+                let mut events = TriggerEvents::empty();
+
+                for name in list.split(',') {
+                    match TriggerEvent::parse(name) {
+                        Some(event) => events = events.with(event),
+                        None => {
+                            println(format_args!("ERR unknown trigger event: {}", name));
+                            return;
+                        }
+                    }
+                }
+
+                config.events = events;
+            }
+            (Some("events"), None) => {
+                println(format_args!("ERR expected trigger events"));
+                return;
+            }
+            (Some(mode), _) => match TriggerMode::parse(mode) {
+                Some(mode) => config.mode = mode,
+                None => {
+                    println(format_args!("ERR unknown trigger mode: {}", mode));
+                    return;
+                }
+            },
+        }
+
+        let mut line: String<CONSOLE_LINE_LEN> = String::new();
+        let ok = if arg.is_some() { "OK " } else { "" };
+        write!(line, "{}trigger {}", ok, config.mode.as_str()).ok();
+
+        for event in TRIGGER_EVENTS
+            .iter()
+            .filter(|e| config.events.contains(**e))
+        {
+            write!(line, " {}", event.as_str()).ok();
+        }
+
+        if config.events.is_empty() {
+            line.push_str(" none").ok();
+        }
+
+        println(format_args!("{}", line));
+    }
+
+    async fn print_fan(&mut self) {
+        let curve = *FAN_CURVE_MUTEX.lock().await;
+
+        println(format_args!(
+            "fan curve {}C {}C {}%",
+            curve.start_c, curve.full_c, curve.min_percent
+        ));
+
+        match *FAN_STATUS_MUTEX.lock().await {
+            Some(fan) => println(format_args!(
+                "fan {}C {}%{}",
+                fan.celsius,
+                fan.percent,
+                if fan.kicking { " kick" } else { "" }
+            )),
+            None => println(format_args!("fan none")),
+        }
+    }
+
+    async fn print_slew(&mut self) {
+        let limits = *SLEW_LIMITS_MUTEX.lock().await;
+        let rate = |milli: u32| fixed(units::from_milli(milli as i32), 2, 0);
+
+        println(format_args!(
+            "slew dv={}V/s di={}A/s trip={}",
+            rate(limits.volts_milli),
+            rate(limits.amps_milli),
+            if limits.trip { "on" } else { "off" }
+        ));
+    }
+
+    /// Sets one of the slew-rate limits, 0 to turn it off, or whether an alarm trips the output.
+    async fn set_slew(&mut self, cmd: Option<&str>, arg: Option<&str>) {
+        let mut limits = *SLEW_LIMITS_MUTEX.lock().await;
+        let rate = arg
+            .and_then(|s| s.parse::<f32>().ok())
+            .filter(|rate| (0.0..=(MAX_RATE_MILLI / 1_000) as f32).contains(rate))
+            .map(|rate| (rate * 1_000.0) as u32);
+
+        match (cmd, arg, rate) {
+            (Some("dv"), _, Some(rate)) => limits.volts_milli = rate,
+            (Some("di"), _, Some(rate)) => limits.amps_milli = rate,
+            (Some("trip"), Some("on"), _) => limits.trip = true,
+            (Some("trip"), Some("off"), _) => limits.trip = false,
+            (Some("dv" | "di"), _, None) => {
+                println(format_args!(
+                    "ERR expected a rate from 0 to {} per second",
+                    MAX_RATE_MILLI / 1_000
+                ));
+                return;
+            }
+            _ => {
+                println(format_args!("ERR expected dv, di or trip"));
+                return;
+            }
+        }
+
+        match slew_settings::store(limits).await {
+            Ok(_) => self.print_slew().await,
+            Err(err) => println(format_args!("ERR {}", err.as_str())),
+        }
+    }
+
+    async fn print_fuse(&mut self) {
+        let limit = *FUSE_LIMIT_MUTEX.lock().await;
+        let blown = if *FUSE_BLOWN_MUTEX.lock().await {
+            " blown"
+        } else {
+            ""
+        };
+
+        if limit.is_off() {
+            println(format_args!("fuse off{}", blown));
+        } else {
+            println(format_args!(
+                "fuse {} trips in {}s{}",
+                limit.trips, limit.window_secs, blown
+            ));
+        }
+    }
+
+    /// `fuse <trips> <seconds>`: blow the fuse after that many over-current trips within the
+    /// window.
+    async fn parse_fuse(&mut self, trips: &str, window: Option<&str>) {
+        let trips = trips
+            .parse::<u8>()
+            .ok()
+            .filter(|trips| (1..=FUSE_MAX_TRIPS).contains(trips));
+        let window_secs = window
+            .and_then(|s| s.parse::<u16>().ok())
+            .filter(|secs| *secs > 0);
+
+        match (trips, window_secs) {
+            (Some(trips), Some(window_secs)) => {
+                self.set_fuse(FuseLimit { trips, window_secs }).await
+            }
+            _ => println(format_args!(
+                "ERR expected 1 to {} trips and a window in seconds",
+                FUSE_MAX_TRIPS
+            )),
+        }
+    }
+
+    /// `fuse reset`: mends the blown fuses, in flash as well so they stay mended after a restart.
+    async fn reset_fuse(&mut self) {
+        let stored = fuse_settings::store_blown(Channel::A, false).await;
+        #[cfg(feature = "dual-output")]
+        let stored = stored.and(fuse_settings::store_blown(Channel::B, false).await);
+
+        match stored {
+            Ok(_) => println(format_args!("OK fuse reset")),
+            Err(err) => println(format_args!("ERR {}", err.as_str())),
+        }
+    }
+
+    async fn set_fuse(&mut self, limit: FuseLimit) {
+        match fuse_settings::store(limit).await {
+            Ok(_) => self.print_fuse().await,
+            Err(err) => println(format_args!("ERR {}", err.as_str())),
+        }
+    }
+
+    /// The stored configurations, in use since boot unless changed since.
+    fn print_power_monitors(&mut self) {
+        print_power_monitor(Channel::A, monitor_settings::load(Channel::A));
+        #[cfg(feature = "dual-output")]
+        print_power_monitor(Channel::B, monitor_settings::load(Channel::B));
+    }
+
+    /// Stores the shunt, full-scale current or address of a channel's INA226 for the next boot.
+    async fn set_power_monitor(&mut self, channel: Channel, cmd: Option<&str>, arg: Option<&str>) {
+        let mut config = monitor_settings::load(channel);
+        let number = arg.and_then(|s| s.parse::<f32>().ok());
+
+        match (cmd, number) {
+            (Some("shunt"), Some(milliohms)) => {
+                config.shunt_micro_ohms = (milliohms * 1_000.0) as u32;
+            }
+            (Some("max"), Some(amps)) => config.max_milliamps = (amps * 1_000.0) as i32,
+            (Some("addr"), _) => {
+                match arg.and_then(|s| u8::from_str_radix(s.trim_start_matches("0x"), 16).ok()) {
+                    Some(address) => config.address = address,
+                    None => {
+                        println(format_args!("ERR expected a hex address"));
+                        return;
+                    }
+                }
+            }
+            (Some("shunt" | "max"), None) => {
+                println(format_args!("ERR expected a number"));
+                return;
+            }
+            _ => {
+                println(format_args!("ERR expected shunt, max or addr"));
+                return;
+            }
+        }
+
+        if !config.is_valid() {
+            println(format_args!(
+                "ERR shunt 0.1 to 1000 mOhm, max 0.1 to 20 A, addr 40 to 4f"
+            ));
+            return;
+        }
+
+        match monitor_settings::store(channel, config).await {
+            Ok(_) => {
+                print_power_monitor(channel, config);
+                println(format_args!("OK applies at the next restart"));
+            }
+            Err(err) => println(format_args!("ERR {}", err.as_str())),
+        }
+    }
+
+    async fn print_schedule(&mut self) {
+        let Some(schedule) = *SCHEDULE_MUTEX.lock().await else {
+            println(format_args!("schedule off"));
+            return;
+        };
+
+        println(format_args!(
+            "schedule {:02}:{:02} {}h",
+            schedule.hour(),
+            schedule.minute(),
+            schedule.hours()
+        ));
+
+        match *NEXT_ACTION_MUTEX.lock().await {
+            Some(action) => println(format_args!(
+                "next {} {}",
+                if action.on { "on" } else { "off" },
+                clock::Timestamp::Unix(action.at * 1000)
+            )),
+            None => println(format_args!("next none, clock not set")),
+        }
+    }
+
+    async fn parse_schedule(&mut self, on_at: &str, hours: Option<&str>) {
+        let time = on_at
+            .split_once(':')
+            .and_then(|(h, m)| Some((h.parse::<u8>().ok()?, m.parse::<u8>().ok()?)));
+        let hours = hours.and_then(|s| s.parse::<u8>().ok());
+
+        let schedule = time
+            .zip(hours)
+            .and_then(|((hour, minute), hours)| Schedule::new(hour, minute, hours));
+
+        match schedule {
+            Some(schedule) => self.set_schedule(Some(schedule)).await,
+            None => println(format_args!(
+                "ERR expected <HH:MM> <1 to {} hours>",
+                MAX_HOURS
+            )),
+        }
+    }
+
+    async fn set_schedule(&mut self, schedule: Option<Schedule>) {
+        match scheduler::store(schedule).await {
+            Ok(_) => self.print_schedule().await,
+            Err(err) => println(format_args!("ERR {}", err.as_str())),
+        }
+    }
+
+    async fn set_fan_curve<'b>(&mut self, mut args: impl Iterator<Item = &'b str>) {
+        let start_c = args.next().and_then(|s| s.parse::<i16>().ok());
+        let full_c = args.next().and_then(|s| s.parse::<i16>().ok());
+        let min_percent = args.next().and_then(|s| s.parse::<u8>().ok());
+
+        let (Some(start_c), Some(full_c), Some(min_percent)) = (start_c, full_c, min_percent)
+        else {
+            println(format_args!("ERR expected <start C> <full C> <min %>"));
+            return;
+        };
+
+        let curve = FanCurve {
+            start_c,
+            full_c,
+            min_percent,
+        };
+
+        if !curve.is_valid() {
+            println(format_args!(
+                "ERR start must be below full, min at most 100"
+            ));
+            return;
+        }
+
+        *FAN_CURVE_MUTEX.lock().await = curve;
+        println(format_args!(
+            "OK fan curve {}C {}C {}%",
+            start_c, full_c, min_percent
+        ));
+    }
+
+    /// Limits the display clock, for panels on long cables. Takes effect at once, holds for every
+    /// power profile and is kept in flash.
+    async fn set_display_spi(&mut self, mhz: &str) {
+        let fastest = bsp::DISPLAY_SPI_FREQUENCY.0 / 1_000_000;
+
+        let Some(mhz) = mhz
+            .parse::<u32>()
+            .ok()
+            .filter(|m| (1..=fastest).contains(m))
+        else {
+            println(format_args!("ERR expected 1 to {} MHz", fastest));
+            return;
+        };
+
+        let max = Hertz(mhz * 1_000_000);
+
+        if let Err(err) = display_settings::store(max).await {
+            println(format_args!("ERR {}", err.as_str()));
+            return;
+        }
+
+        DISPLAY_SPI_PUBSUB
+            .immediate_publisher()
+            .publish_immediate(max);
+
+        println(format_args!("OK spi {} MHz", mhz));
+    }
+
+    async fn set_backlight_timeout(&mut self, seconds: Option<&str>) {
+        match seconds.and_then(|s| s.parse::<u16>().ok()) {
+            Some(seconds) => {
+                *BACKLIGHT_TIMEOUT_MUTEX.lock().await = seconds;
+                println(format_args!("OK backlight timeout {}s", seconds));
+            }
+            None => println(format_args!("ERR expected timeout in seconds")),
+        }
+    }
+
+    async fn set_time(&mut self, secs: &str) {
+        match secs.parse::<u64>() {
+            Ok(secs) => {
+                clock::set_unix_time(secs).await;
+                println(format_args!("OK {}", clock::now().await));
+            }
+            Err(_) => println(format_args!("ERR expected unix time in seconds")),
+        }
+    }
+
+    async fn handle_update<'b>(
+        &mut self,
+        cmd: Option<&str>,
+        mut args: impl Iterator<Item = &'b str>,
+    ) {
+        let result = match cmd {
+            None => {
+                match self.updater.progress() {
+                    Some((written, size)) => println(format_args!("fw {}/{}", written, size)),
+                    None => println(format_args!("fw idle")),
+                }
+                return;
+            }
+            Some("begin") => {
+                if *OUTPUT_MUTEX.lock().await {
+                    println(format_args!("ERR disable output before updating"));
+                    return;
+                }
+
+                let size = args.next().and_then(|s| s.parse::<u32>().ok());
+                let crc = args.next().and_then(|s| u32::from_str_radix(s, 16).ok());
+
+                match (size, crc) {
+                    (Some(size), Some(crc)) => self.updater.begin(size, crc).await,
+                    _ => {
+                        println(format_args!("ERR expected size and hex crc32"));
+                        return;
+                    }
+                }
+            }
+            Some("data") => {
+                let offset = args.next().and_then(|s| s.parse::<u32>().ok());
+                let mut data: Vec<u8, 32> = Vec::new();
+
+                let parsed = args
+                    .next()
+                    .map(|hex| parse_hex(hex, &mut data))
+                    .unwrap_or(false);
+
+                match (offset, parsed) {
+                    (Some(offset), true) => self.updater.write(offset, &data).await,
+                    _ => {
+                        println(format_args!("ERR expected offset and up to 32 hex bytes"));
+                        return;
+                    }
+                }
+            }
+            Some("commit") => match self.updater.commit().await {
+                Ok(_) => {
+                    println(format_args!("OK rebooting into new image"));
+
+                    Timer::after(Duration::from_millis(50)).await;
+
+                    cortex_m::peripheral::SCB::sys_reset();
+                }
+                Err(err) => Err(err),
+            },
+            Some("abort") => {
+                self.updater.abort();
+                Ok(())
+            }
+            Some(other) => {
+                println(format_args!("ERR unknown fw command: {}", other));
+                return;
+            }
+        };
+
+        match result {
+            Ok(_) => println(format_args!("OK")),
+            Err(err) => println(format_args!("ERR {}", err.as_str())),
+        }
+    }
+}
+
+/// The report of `stopped`, or of the running or last session, as `session <state> <H:MM:SS>`, a
+/// `min avg max` line per quantity, the energy and fault count and the time above each band of the
+/// OCP.
+async fn print_session(stopped: Option<Report>) {
+    let (running, report) = match stopped {
+        Some(report) => (false, Some(report)),
+        None => {
+            let session = SESSION_MUTEX.lock().await;
+            (session.is_running(), session.report())
+        }
+    };
+
+    let Some(report) = report else {
+        println(format_args!("session none"));
+        return;
+    };
+
+    let (hours, minutes, seconds) = session::hms(report.duration_ms);
+    println(format_args!(
+        "session {} {}:{:02}:{:02}",
+        if running { "running" } else { "stopped" },
+        hours,
+        minutes,
+        seconds
+    ));
+
+    for (name, extent) in [("V", report.volts), ("A", report.amps), ("W", report.watts)] {
+        match extent.summary() {
+            Some((min, mean, max)) => println(format_args!(
+                "{} min={} avg={} max={}",
+                name,
+                fixed(min, 3, 0),
+                fixed(mean, 3, 0),
+                fixed(max, 3, 0)
+            )),
+            None => println(format_args!("{} none", name)),
+        }
+    }
+
+    println(format_args!(
+        "Wh={} faults={}",
+        fixed(units::energy_wh(report.energy), 3, 0),
+        report.faults
+    ));
+
+    for (band, percent) in OCP_BANDS_PERCENT.iter().enumerate() {
+        let (hours, minutes, seconds) = session::hms(report.above_ocp_ms(band));
+        println(format_args!(
+            "OCP>{}%={}:{:02}:{:02} ({}%)",
+            percent,
+            hours,
+            minutes,
+            seconds,
+            report.above_ocp_percent(band)
+        ));
+    }
+}
+
+fn print_ocp(ocp: Value) {
+    if ocp > ZERO {
+        println(format_args!("ocp {}A", fixed(ocp, 2, 0)));
+    } else {
+        println(format_args!("ocp off"));
+    }
+}
+
+fn parse_hex<const N: usize>(hex: &str, out: &mut Vec<u8, N>) -> bool {
+    if hex.len() % 2 != 0 {
+        return false;
+    }
+
+    for i in (0..hex.len()).step_by(2) {
+        let Some(byte) = hex
+            .get(i..i + 2)
+            .and_then(|b| u8::from_str_radix(b, 16).ok())
+        else {
+            return false;
+        };
+
+        if out.push(byte).is_err() {
+            return false;
+        }
+    }
+
+    true
+}
+
+fn print_conversion(conversion: Conversion) {
+    println(format_args!(
+        "conversion avg={} us={} period={}ms rate={}Hz bw={}Hz",
+        conversion.averaging.count(),
+        conversion.time.micros(),
+        fixed_milli(conversion.period_us() as i32, 3, 0),
+        fixed_milli(conversion.rate_millihertz() as i32, 3, 0),
+        fixed_milli(conversion.bandwidth_millihertz() as i32, 3, 0)
+    ));
+}
+
+fn print_power_monitor(channel: Channel, config: PowerMonitorConfig) {
+    println(format_args!(
+        "{} ina226 addr={:#04x} shunt={}mOhm max={}A",
+        channel.as_str(),
+        config.address,
+        crate::fmt::fixed(config.shunt_ohms() * 1_000.0, 3, 0),
+        crate::fmt::fixed(config.max_amps(), 2, 0)
+    ));
+}
+
+fn print_memory() {
+    if let Some(usage) = stack::usage() {
+        println(format_args!(
+            "MEM stack peak={} of {} ({}%) statics={}",
+            usage.peak,
+            usage.size,
+            usage.peak_percent(),
+            usage.statics
+        ));
+    }
+}
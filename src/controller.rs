@@ -1,509 +1,526 @@
 use embassy_futures::select::{select, Either};
 use embassy_sync::{blocking_mutex::raw::CriticalSectionRawMutex, pubsub::ImmediatePublisher};
-use embassy_time::{Duration, Instant};
-use heapless::Vec;
-use husb238::{SrcPdo, Voltage};
+use embassy_time::{with_timeout, Instant};
 
 use crate::{
-    button::ButtonState,
+    bootloader,
+    calendar::DateTime,
+    capture::THRESHOLD_STEP,
+    clock,
+    entry::NumberEntry,
+    heartbeat::{self, Task, HEARTBEAT_INTERVAL},
+    log::{info, Module},
+    menu::{self, BtnsState, Gestures},
+    protection::VoltageLimit,
+    setup::{self, SetupStep},
+    setup_settings,
     shared::{
-        get_available_voltages, AVAILABLE_VOLT_CURR_MUTEX, BACKLIGHT_MUTEX, BACKLIGHT_PUBSUB,
-        BTN_A_STATE_CHANNEL, BTN_B_STATE_CHANNEL, DISPLAY_DIRECTION_MUTEX,
-        DISPLAY_DIRECTION_PUBSUB, MAX_SIMULTANEOUS_PRESS_DELAY, OCP_MAX, OCP_MUTEX, OCP_PUBSUB,
-        PAGE_MUTEX, PAGE_PUBSUB, PDO_MUTEX, PDO_PUBSUB, SELECTED_VOLTAGE_MUTEX, UVP_MUTEX,
-        UVP_PUBSUB,
+        get_available_voltages, ocp_mutex, output_mutex, select_pdo, tripped_mutex,
+        AVERAGE_INTERVAL_MUTEX, BACKLIGHT_MAX_LEVEL, BACKLIGHT_MUTEX, BACKLIGHT_PUBSUB,
+        BACKLIGHT_TIMEOUT_MUTEX, BTN_A_STATE_CHANNEL, BTN_B_STATE_CHANNEL, CABLE_MUTEX,
+        CAPTURE_MUTEX, CLOCK_ENTRY_MUTEX, CONVERSION_MUTEX, DISPLAY_DIRECTION_MUTEX,
+        DISPLAY_DIRECTION_PUBSUB, ENTRY_MUTEX, OCP_MAX, OCP_PUBSUB, OUTPUT_MODE_MUTEX,
+        OUTPUT_MODE_PUBSUB, OUTPUT_PUBSUB, OVP_MUTEX, OVP_PUBSUB, PAGE_MUTEX, PAGE_PUBSUB,
+        POWER_INFO_MUTEX, REMOTE_MUTEX, SD_MOUNT_PUBSUB, SELECTED_CHANNEL_MUTEX,
+        SELECTED_VOLTAGE_MUTEX, SESSION_MUTEX, SETUP_MUTEX, THEME_MUTEX, THEME_PUBSUB, UVP_MUTEX,
+        UVP_PUBSUB, WATTS_SOURCE_MUTEX, WATTS_SOURCE_PUBSUB,
     },
-    types::{Direction, Page, SettingItem, SETTING_ITEMS},
+    timing,
+    types::{
+        AboutView, Channel, ControlSource, Direction, DisplayItem, MeasurementItem, OutputMode,
+        OutputRequest, Page, PdRequest, Theme,
+    },
+    units::{self, Value},
 };
 
-#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
-pub enum BtnsState {
-    Up,
-    Down,
-    UpLong,
-    DownLong,
-    UpDbk,
-    DownDbk,
-    UpAndDown,
-    UpAndDownLong,
-}
+/// Lowest UVP, OVP and OCP thresholds that can be entered; 0 turns them off.
+const THRESHOLD_MIN: Value = units::from_milli(50);
+/// Highest UVP and OVP thresholds, some way above the highest fixed PDO.
+const VOLTAGE_LIMIT_MAX: Value = units::from_milli(25_000);
+
+const LOG_MODULE: Module = Module::Controller;
 
 pub struct Controller<'a> {
     direction: Direction,
+    /// The output was turned on by a long press in the momentary mode and goes off on release.
+    holding: bool,
 
     page_pubsub: ImmediatePublisher<'a, CriticalSectionRawMutex, Page, 2, 2, 1>,
     backlight_pubsub: ImmediatePublisher<'a, CriticalSectionRawMutex, u16, 2, 2, 1>,
     display_direction_pubsub: ImmediatePublisher<'a, CriticalSectionRawMutex, Direction, 2, 2, 1>,
-    ocp_pubsub: ImmediatePublisher<'a, CriticalSectionRawMutex, f64, 2, 2, 1>,
-    uvp_pubsub: ImmediatePublisher<'a, CriticalSectionRawMutex, f64, 2, 2, 1>,
-    pdo_pubsub: ImmediatePublisher<'a, CriticalSectionRawMutex, SrcPdo, 2, 2, 1>,
+    theme_pubsub: ImmediatePublisher<'a, CriticalSectionRawMutex, Theme, 2, 2, 1>,
+    ocp_pubsub: ImmediatePublisher<'a, CriticalSectionRawMutex, Value, 2, 2, 1>,
+    uvp_pubsub: ImmediatePublisher<'a, CriticalSectionRawMutex, VoltageLimit, 2, 2, 1>,
+    ovp_pubsub: ImmediatePublisher<'a, CriticalSectionRawMutex, VoltageLimit, 2, 2, 1>,
+    output_mode_pubsub: ImmediatePublisher<'a, CriticalSectionRawMutex, OutputMode, 2, 2, 1>,
+    output_pubsub: ImmediatePublisher<'a, CriticalSectionRawMutex, OutputRequest, 2, 2, 1>,
 }
 
 impl<'a> Controller<'a> {
     pub fn new() -> Self {
         Self {
             direction: Direction::Normal,
+            holding: false,
 
             page_pubsub: PAGE_PUBSUB.immediate_publisher(),
             backlight_pubsub: BACKLIGHT_PUBSUB.immediate_publisher(),
             display_direction_pubsub: DISPLAY_DIRECTION_PUBSUB.immediate_publisher(),
+            theme_pubsub: THEME_PUBSUB.immediate_publisher(),
             ocp_pubsub: OCP_PUBSUB.immediate_publisher(),
             uvp_pubsub: UVP_PUBSUB.immediate_publisher(),
-            pdo_pubsub: PDO_PUBSUB.immediate_publisher(),
+            ovp_pubsub: OVP_PUBSUB.immediate_publisher(),
+            output_mode_pubsub: OUTPUT_MODE_PUBSUB.immediate_publisher(),
+            output_pubsub: OUTPUT_PUBSUB.immediate_publisher(),
         }
     }
 
     pub async fn task(&mut self) {
-        let mut btn_up_state = ButtonState::Released;
-        let mut btn_down_state = ButtonState::Released;
-        let mut up_last = true;
+        let mut gestures = Gestures::new();
 
-        loop {
-            let futures = select(BTN_A_STATE_CHANNEL.receive(), BTN_B_STATE_CHANNEL.receive());
-
-            match futures.await {
-                Either::First(s) => {
-                    if matches!(self.direction, Direction::Normal) {
-                        btn_up_state = s;
-                        up_last = true;
-                    } else {
-                        btn_down_state = s;
-                        up_last = false;
-                    }
-                }
-                Either::Second(s) => {
-                    if matches!(self.direction, Direction::Normal) {
-                        btn_down_state = s;
-                        up_last = false;
-                    } else {
-                        btn_up_state = s;
-                        up_last = true;
-                    }
-                }
-            }
+        // The rotation may have been restored at boot, see `setup_settings.rs`.
+        self.direction = *DISPLAY_DIRECTION_MUTEX.lock().await;
 
-            if btn_down_state == ButtonState::Pressed || btn_up_state == ButtonState::Pressed {
-                continue;
-            }
+        loop {
+            let normal = matches!(self.direction, Direction::Normal);
 
-            if let ButtonState::LongPressed(up_at) = btn_up_state {
-                if let ButtonState::LongPressed(down_at) = btn_down_state {
-                    if instant_diff(up_at, down_at) < MAX_SIMULTANEOUS_PRESS_DELAY {
-                        self.handle_input(BtnsState::UpAndDownLong).await;
-                        continue;
-                    }
-                }
-            }
+            heartbeat::beat(Task::Controller);
 
-            if let ButtonState::Click(up_at) = btn_up_state {
-                if let ButtonState::Click(down_at) = btn_down_state {
-                    if instant_diff(up_at, down_at) < MAX_SIMULTANEOUS_PRESS_DELAY {
-                        self.handle_input(BtnsState::UpAndDown).await;
-                        continue;
-                    }
-                }
-            }
+            let received = with_timeout(
+                HEARTBEAT_INTERVAL,
+                select(BTN_A_STATE_CHANNEL.receive(), BTN_B_STATE_CHANNEL.receive()),
+            )
+            .await;
 
-            if up_last {
-                if matches!(
-                    btn_down_state,
-                    ButtonState::Pressed | ButtonState::LongPressed(_)
-                ) {
-                    continue;
-                }
+            let (up, state) = match received {
+                Ok(Either::First(s)) => (normal, s),
+                Ok(Either::Second(s)) => (!normal, s),
+                Err(_) => continue,
+            };
 
-                match btn_up_state {
-                    ButtonState::LongPressed(_) => {
-                        self.handle_input(BtnsState::UpLong).await;
-                    }
-                    ButtonState::Click(_) => {
-                        self.handle_input(BtnsState::Up).await;
-                    }
-                    ButtonState::DoubleClick(_) => {
-                        self.handle_input(BtnsState::UpDbk).await;
-                    }
-                    _ => {}
-                }
-            } else {
-                if matches!(
-                    btn_up_state,
-                    ButtonState::Pressed | ButtonState::LongPressed(_)
-                ) {
-                    continue;
-                }
-
-                match btn_down_state {
-                    ButtonState::LongPressed(_) => {
-                        self.handle_input(BtnsState::DownLong).await;
-                    }
-                    ButtonState::Click(_) => {
-                        self.handle_input(BtnsState::Down).await;
-                    }
-                    ButtonState::DoubleClick(_) => {
-                        self.handle_input(BtnsState::DownDbk).await;
-                    }
-                    _ => {}
-                }
+            if let Some(btns) = gestures.update(up, state) {
+                self.handle_input(btns).await;
             }
         }
     }
 
     async fn handle_input(&mut self, btns: BtnsState) {
-        defmt::info!("btns: {:?}", btns);
+        info!("btns: {:?}", btns);
 
         let mut page = PAGE_MUTEX.lock().await;
+        let prev = *page;
 
-        match *page {
-            Page::Monitor => match btns {
-                BtnsState::Up => {
-                    let mut backlight = BACKLIGHT_MUTEX.lock().await;
-
-                    if *backlight > 10 {
-                        *backlight = 10;
-                    } else {
-                        *backlight += 1;
-                    }
-
-                    let _backlight = *backlight;
+        let selected = *SELECTED_VOLTAGE_MUTEX.lock().await;
+        let available = get_available_voltages().await;
+        let next = menu::next_page(prev, btns, selected, &available);
 
-                    drop(backlight);
+        // Before the next page loads its own value into the entry.
+        if btns == BtnsState::UpAndDown {
+            self.take_entry(prev).await;
+        }
 
-                    self.backlight_pubsub.publish_immediate(_backlight);
+        if next != prev {
+            match next {
+                Page::UVP(field) => {
+                    let limit = *UVP_MUTEX.lock().await;
+                    self.start_entry(field.of(&limit), VOLTAGE_LIMIT_MAX).await
                 }
-                BtnsState::Down => {
-                    let mut backlight = BACKLIGHT_MUTEX.lock().await;
-
-                    if *backlight < 1 {
-                        *backlight = 0;
-                    } else {
-                        *backlight -= 1;
-                    }
-
-                    let _backlight = *backlight;
-
-                    drop(backlight);
+                Page::OVP(field) => {
+                    let limit = *OVP_MUTEX.lock().await;
+                    self.start_entry(field.of(&limit), VOLTAGE_LIMIT_MAX).await
+                }
+                Page::OCP => {
+                    let channel = *SELECTED_CHANNEL_MUTEX.lock().await;
+                    let ocp = *ocp_mutex(channel).lock().await;
+                    self.start_entry(ocp, OCP_MAX).await
+                }
+                Page::Clock(_) if !matches!(prev, Page::Clock(_)) => {
+                    let now = clock::unix_secs().await;
 
-                    self.backlight_pubsub.publish_immediate(_backlight);
+                    *CLOCK_ENTRY_MUTEX.lock().await =
+                        now.map_or(DateTime::default(), DateTime::from_unix);
                 }
-                BtnsState::UpLong => {}
-                BtnsState::DownLong => {
-                    let mut backlight = BACKLIGHT_MUTEX.lock().await;
+                _ => {}
+            }
+
+            *page = next;
+            self.page_pubsub.publish_immediate(next);
+        }
 
-                    *backlight = 0;
+        drop(page);
 
-                    let _backlight = *backlight;
+        match (prev, btns) {
+            (Page::Monitor, BtnsState::Up) => self.step_backlight(true).await,
+            #[cfg(not(feature = "dual-output"))]
+            (Page::Monitor, BtnsState::Down) => self.step_backlight(false).await,
+            #[cfg(feature = "dual-output")]
+            (Page::Monitor, BtnsState::Down) => self.switch_channel().await,
+            (Page::Monitor, BtnsState::UpLong) => {
+                let channel = *SELECTED_CHANNEL_MUTEX.lock().await;
 
-                    drop(backlight);
+                // The first long press after an over-current trip only re-arms the output.
+                let mut tripped = tripped_mutex(channel).lock().await;
+                if *tripped {
+                    info!("over-current trip reset");
 
-                    self.backlight_pubsub.publish_immediate(_backlight);
+                    *tripped = false;
+                    return;
                 }
-                BtnsState::UpDbk | BtnsState::DownDbk => {
-                    let mut direction = DISPLAY_DIRECTION_MUTEX.lock().await;
+                drop(tripped);
 
-                    *direction = match *direction {
-                        Direction::Normal => Direction::Reversed,
-                        Direction::Reversed => Direction::Normal,
-                    };
+                let mode = *OUTPUT_MODE_MUTEX.lock().await;
+                let enabled = match mode {
+                    OutputMode::Latching => !*output_mutex(channel).lock().await,
+                    OutputMode::Momentary => true,
+                };
 
-                    let _direction = *direction;
+                self.holding = mode == OutputMode::Momentary;
+                self.request_output(channel, enabled).await;
+            }
+            (_, BtnsState::UpReleased) if self.holding => {
+                let channel = *SELECTED_CHANNEL_MUTEX.lock().await;
+                self.holding = false;
+                self.request_output(channel, false).await;
+            }
+            (Page::Monitor, BtnsState::DownLong) => {
+                let mut backlight = BACKLIGHT_MUTEX.lock().await;
 
-                    drop(direction);
+                *backlight = 0;
 
-                    self.display_direction_pubsub.publish_immediate(_direction);
-                }
-                BtnsState::UpAndDown => {
-                    *page = Page::OCP;
+                let _backlight = *backlight;
 
-                    let _page = *page;
+                drop(backlight);
 
-                    drop(page);
+                self.backlight_pubsub.publish_immediate(_backlight);
+            }
+            (Page::Voltage(selected), BtnsState::UpAndDown | BtnsState::UpAndDownLong) => {
+                *REMOTE_MUTEX.lock().await = false;
+
+                select_pdo(PdRequest {
+                    pdo: selected,
+                    source: ControlSource::Local,
+                })
+                .await;
+            }
+            (Page::UVP(_) | Page::OVP(_) | Page::OCP, BtnsState::Up | BtnsState::Down) => {
+                let mut entry = ENTRY_MUTEX.lock().await;
 
-                    self.page_pubsub.publish_immediate(_page);
+                match btns {
+                    BtnsState::Up => entry.increment(),
+                    _ => entry.next_digit(),
                 }
-                BtnsState::UpAndDownLong => {
-                    *page = Page::Setting(SettingItem::Voltage);
 
-                    let _page = *page;
+                drop(entry);
 
-                    drop(page);
+                // Redraw the digits.
+                self.page_pubsub.publish_immediate(prev);
+            }
+            (Page::Output, BtnsState::Up | BtnsState::Down) => {
+                let mut mode = OUTPUT_MODE_MUTEX.lock().await;
 
-                    self.page_pubsub.publish_immediate(_page);
-                }
-            },
-            Page::Setting(item) => match btns {
-                BtnsState::Up => {
-                    let next_index = SETTING_ITEMS
-                        .iter()
-                        .enumerate()
-                        .find(|(_, ele)| **ele == item)
-                        .map(|(i, _)| (i + 1) % SETTING_ITEMS.len());
+                *mode = mode.other();
 
-                    *page = Page::Setting(SETTING_ITEMS[next_index.unwrap_or(0)]);
+                let _mode = *mode;
 
-                    let _page = *page;
+                drop(mode);
 
-                    drop(page);
+                self.output_mode_pubsub.publish_immediate(_mode);
+                // Redraw the options with the new selection.
+                self.page_pubsub.publish_immediate(Page::Output);
+            }
+            (Page::Watts, BtnsState::Up | BtnsState::Down) => {
+                let mut source = WATTS_SOURCE_MUTEX.lock().await;
 
-                    self.page_pubsub.publish_immediate(_page);
-                }
-                BtnsState::Down => {
-                    let next_index = SETTING_ITEMS
-                        .iter()
-                        .enumerate()
-                        .find(|(_, ele)| **ele == item)
-                        .map(|(i, _)| (i + SETTING_ITEMS.len() - 1) % SETTING_ITEMS.len());
+                *source = match btns {
+                    BtnsState::Up => source.next(),
+                    _ => source.prev(),
+                };
 
-                    *page = Page::Setting(SETTING_ITEMS[next_index.unwrap_or(0)]);
+                let _source = *source;
 
-                    let _page = *page;
+                drop(source);
 
-                    drop(page);
+                WATTS_SOURCE_PUBSUB
+                    .immediate_publisher()
+                    .publish_immediate(_source);
+                // Redraw the list with the new selection.
+                self.page_pubsub.publish_immediate(Page::Watts);
+            }
+            (Page::Average, BtnsState::Up | BtnsState::Down) => {
+                let mut interval = AVERAGE_INTERVAL_MUTEX.lock().await;
 
-                    self.page_pubsub.publish_immediate(_page);
-                }
-                BtnsState::UpLong => {}
-                BtnsState::DownLong => {}
-                BtnsState::UpDbk | BtnsState::DownDbk => {
-                    self.switch_direction().await;
-                }
-                BtnsState::UpAndDown => {
-                    *page = match item {
-                        SettingItem::Voltage => {
-                            let selected_volt = SELECTED_VOLTAGE_MUTEX.lock().await;
-                            Page::Voltage(*selected_volt)
-                        }
-                        SettingItem::UVP => Page::UVP,
-                        SettingItem::OCP => Page::OCP,
-                        SettingItem::About => Page::About,
-                    };
-
-                    let _page = *page;
-
-                    drop(page);
-
-                    self.page_pubsub.publish_immediate(_page);
-                }
-                BtnsState::UpAndDownLong => {
-                    *page = Page::Monitor;
+                *interval = match btns {
+                    BtnsState::Up => interval.next(),
+                    _ => interval.prev(),
+                };
 
-                    let _page = *page;
+                drop(interval);
 
-                    drop(page);
+                // Redraw the list with the new selection.
+                self.page_pubsub.publish_immediate(Page::Average);
+            }
+            (Page::Measurement(item), BtnsState::Up | BtnsState::Down) => {
+                let up = btns == BtnsState::Up;
+                let mut conversion = CONVERSION_MUTEX.lock().await;
 
-                    self.page_pubsub.publish_immediate(_page);
+                match item {
+                    MeasurementItem::Averaging => {
+                        conversion.averaging = conversion.averaging.step(up)
+                    }
+                    MeasurementItem::ConversionTime => conversion.time = conversion.time.step(up),
                 }
-            },
-            Page::Voltage(selected) => match btns {
-                BtnsState::Up => {
-                    let selected = self.up_voltage(selected).await;
-                    *page = Page::Voltage(selected);
-
-                    let _page = *page;
 
-                    drop(page);
+                drop(conversion);
 
-                    self.page_pubsub.publish_immediate(_page);
+                // Redraw the options with the new value.
+                self.page_pubsub.publish_immediate(Page::Measurement(item));
+            }
+            (Page::Setup(SetupStep::Rotation), BtnsState::Up | BtnsState::Down) => {
+                self.switch_direction().await;
+            }
+            (Page::Setup(step), BtnsState::Up | BtnsState::Down) => {
+                let up = btns == BtnsState::Up;
+                let mut setup = SETUP_MUTEX.lock().await;
+
+                match step {
+                    SetupStep::Rotation => {}
+                    SetupStep::Ocp => setup.ocp = setup::step_ocp(setup.ocp, up, OCP_MAX),
+                    SetupStep::Shunt => {
+                        setup.shunt_micro_ohms = setup::step_shunt(setup.shunt_micro_ohms, up)
+                    }
                 }
-                BtnsState::Down => {
-                    let selected = self.down_voltage(selected).await;
-                    *page = Page::Voltage(selected);
-
-                    let _page = *page;
 
-                    drop(page);
-
-                    self.page_pubsub.publish_immediate(_page);
-                }
-                BtnsState::UpAndDown => {
-                    *page = Page::Setting(SettingItem::UVP);
+                drop(setup);
 
-                    let _page = *page;
+                // Redraw the step with the new value.
+                self.page_pubsub.publish_immediate(Page::Setup(step));
+            }
+            (Page::Cable, BtnsState::Up) => {
+                let power = *POWER_INFO_MUTEX.lock().await;
 
-                    drop(page);
+                CABLE_MUTEX.lock().await.record(power.volts, power.amps);
+            }
+            (Page::Cable, BtnsState::Down) => {
+                CABLE_MUTEX.lock().await.clear();
+            }
+            (Page::Capture, BtnsState::Up) => {
+                let mut capture = CAPTURE_MUTEX.lock().await;
+                let threshold = capture.threshold() + THRESHOLD_STEP;
+
+                capture.set_threshold(if threshold > OCP_MAX {
+                    OCP_MAX
+                } else {
+                    threshold
+                });
+            }
+            (Page::Capture, BtnsState::Down) => {
+                let mut capture = CAPTURE_MUTEX.lock().await;
+                let threshold = capture.threshold() - THRESHOLD_STEP;
 
-                    self.page_pubsub.publish_immediate(_page);
+                capture.set_threshold(threshold);
+            }
+            (Page::Capture, BtnsState::UpAndDownLong) => {
+                CAPTURE_MUTEX.lock().await.arm();
+            }
+            (Page::Session, BtnsState::Up) => {
+                info!("session start");
+                SESSION_MUTEX.lock().await.start(Instant::now());
+            }
+            (Page::Session, BtnsState::Down) => {
+                if SESSION_MUTEX.lock().await.stop(Instant::now()).is_some() {
+                    info!("session stop");
+                }
+            }
+            (Page::Storage, BtnsState::Up | BtnsState::Down) => {
+                let mount = btns == BtnsState::Up;
+                info!("sd card {}", if mount { "mount" } else { "unmount" });
 
-                    let mut pdo = PDO_MUTEX.lock().await;
-                    *pdo = selected;
+                SD_MOUNT_PUBSUB
+                    .immediate_publisher()
+                    .publish_immediate(mount);
+            }
+            (Page::Clock(field), BtnsState::Up | BtnsState::Down) => {
+                CLOCK_ENTRY_MUTEX
+                    .lock()
+                    .await
+                    .step(field, btns == BtnsState::Up);
+
+                // Redraw the fields with the new value.
+                self.page_pubsub.publish_immediate(prev);
+            }
+            (Page::Diagnostics(_), BtnsState::UpAndDownLong) => {
+                heartbeat::reset();
+                timing::reset();
+            }
+            (Page::Display(item), BtnsState::Up | BtnsState::Down) => {
+                let up = btns == BtnsState::Up;
 
-                    self.pdo_pubsub.publish_immediate(selected);
-                }
-                BtnsState::UpAndDownLong => {
-                    *page = Page::Monitor;
+                match item {
+                    DisplayItem::Rotation => self.switch_direction().await,
+                    DisplayItem::Theme => {
+                        let mut theme = THEME_MUTEX.lock().await;
 
-                    let _page = *page;
+                        *theme = theme.other();
 
-                    drop(page);
+                        let _theme = *theme;
 
-                    self.page_pubsub.publish_immediate(_page);
+                        drop(theme);
 
-                    let mut pdo = PDO_MUTEX.lock().await;
-                    *pdo = selected;
+                        self.theme_pubsub.publish_immediate(_theme);
+                    }
+                    DisplayItem::Brightness => self.step_backlight(up).await,
+                    DisplayItem::Timeout => {
+                        let mut timeout = BACKLIGHT_TIMEOUT_MUTEX.lock().await;
 
-                    self.pdo_pubsub.publish_immediate(selected);
-                }
-                BtnsState::UpDbk | BtnsState::DownDbk => {
-                    self.switch_direction().await;
-                }
-                _ => {}
-            },
-            Page::UVP => match btns {
-                BtnsState::Up => {
-                    let mut uvp = UVP_MUTEX.lock().await;
-
-                    if *uvp > OCP_MAX {
-                        *uvp = 10.0;
-                    } else {
-                        *uvp += 0.25;
+                        *timeout = menu::step_timeout(*timeout, up);
                     }
+                }
 
-                    let _uvp = *uvp;
+                // Redraw the options with the new value.
+                self.page_pubsub.publish_immediate(Page::Display(item));
+            }
+            (Page::About(AboutView::Info), BtnsState::UpAndDownLong) => {
+                bootloader::jump_to_bootloader();
+            }
+            (_, BtnsState::UpDbk | BtnsState::DownDbk) => {
+                self.switch_direction().await;
+            }
+            _ => {}
+        }
+    }
 
-                    drop(uvp);
+    /// Asks the main loop to switch the output, taking it back from the console.
+    async fn request_output(&mut self, channel: Channel, enabled: bool) {
+        *REMOTE_MUTEX.lock().await = false;
 
-                    self.uvp_pubsub.publish_immediate(_uvp);
-                }
-                BtnsState::Down => {
-                    let mut uvp = UVP_MUTEX.lock().await;
+        self.output_pubsub.publish_immediate(OutputRequest {
+            enabled,
+            source: ControlSource::Local,
+            channel,
+        });
+    }
 
-                    if *uvp < 10.0 {
-                        *uvp = 0.0;
-                    } else {
-                        *uvp -= 0.25;
-                    }
+    /// Shows and switches the other output on the monitor page.
+    #[cfg(feature = "dual-output")]
+    async fn switch_channel(&mut self) {
+        let mut selected = SELECTED_CHANNEL_MUTEX.lock().await;
 
-                    let _uvp = *uvp;
+        *selected = selected.other();
+        info!("channel {}", selected.as_str());
 
-                    drop(uvp);
+        drop(selected);
 
-                    self.uvp_pubsub.publish_immediate(_uvp);
-                }
-                BtnsState::UpAndDown => {
-                    *page = Page::Setting(SettingItem::UVP);
+        // Redraw the monitor page for the new channel.
+        self.page_pubsub.publish_immediate(Page::Monitor);
+    }
 
-                    let _page = *page;
+    /// Stores the value entered on `page`, if it has an entry.
+    async fn take_entry(&mut self, page: Page) {
+        if let Page::Clock(field) = page {
+            if menu::next_clock_field(field).is_none() {
+                let time = *CLOCK_ENTRY_MUTEX.lock().await;
+                info!("clock set to {}", time);
 
-                    drop(page);
+                clock::set_unix_time(time.to_unix()).await;
+            }
 
-                    self.page_pubsub.publish_immediate(_page);
-                }
-                BtnsState::UpDbk | BtnsState::DownDbk => {
-                    self.switch_direction().await;
-                }
-                _ => {}
-            },
-            Page::OCP => match btns {
-                BtnsState::Up => {
-                    let mut ocp = OCP_MUTEX.lock().await;
-
-                    if *ocp > OCP_MAX {
-                        *ocp = 10.0;
-                    } else {
-                        *ocp += 0.25;
-                    }
+            return;
+        }
 
-                    let _ocp = *ocp;
+        if let Page::Setup(step) = page {
+            if step.next().is_none() {
+                self.finish_setup().await;
+            }
 
-                    drop(ocp);
+            return;
+        }
 
-                    self.ocp_pubsub.publish_immediate(_ocp);
-                }
-                BtnsState::Down => {
-                    let mut ocp = OCP_MUTEX.lock().await;
+        let value = match page {
+            Page::UVP(_) | Page::OVP(_) | Page::OCP => {
+                units::from_milli(ENTRY_MUTEX.lock().await.milli())
+            }
+            _ => return,
+        };
 
-                    if *ocp < 10.0 {
-                        *ocp = 0.0;
-                    } else {
-                        *ocp -= 0.25;
-                    }
+        match page {
+            Page::UVP(field) => {
+                let mut uvp = UVP_MUTEX.lock().await;
 
-                    let _ocp = *ocp;
+                field.set(&mut uvp, value);
 
-                    drop(ocp);
+                let _uvp = *uvp;
 
-                    self.ocp_pubsub.publish_immediate(_ocp);
-                }
-                BtnsState::UpAndDown => {
-                    *page = Page::Setting(SettingItem::OCP);
+                drop(uvp);
 
-                    let _page = *page;
+                self.uvp_pubsub.publish_immediate(_uvp);
+            }
+            Page::OVP(field) => {
+                let mut ovp = OVP_MUTEX.lock().await;
 
-                    drop(page);
+                field.set(&mut ovp, value);
 
-                    self.page_pubsub.publish_immediate(_page);
-                }
-                BtnsState::UpDbk | BtnsState::DownDbk => {
-                    self.switch_direction().await;
-                }
-                _ => {}
-            },
-            Page::About => match btns {
-                BtnsState::UpDbk | BtnsState::DownDbk => {
-                    self.switch_direction().await;
-                }
-                _ => {
-                    *page = Page::Setting(SettingItem::About);
+                let _ovp = *ovp;
 
-                    let _page = *page;
+                drop(ovp);
 
-                    drop(page);
+                self.ovp_pubsub.publish_immediate(_ovp);
+            }
+            Page::OCP => {
+                let channel = *SELECTED_CHANNEL_MUTEX.lock().await;
+                *ocp_mutex(channel).lock().await = value;
 
-                    self.page_pubsub.publish_immediate(_page);
-                }
-            },
+                self.ocp_pubsub.publish_immediate(value);
+            }
+            _ => {}
         }
     }
 
-    async fn switch_direction(&mut self) {
-        let mut direction = DISPLAY_DIRECTION_MUTEX.lock().await;
-
-        *direction = match *direction {
-            Direction::Normal => Direction::Reversed,
-            Direction::Reversed => Direction::Normal,
-        };
-
-        self.direction = *direction;
+    /// Applies the OCP the setup wizard picked and stores its choices with the rotation in use.
+    async fn finish_setup(&mut self) {
+        let mut setup = *SETUP_MUTEX.lock().await;
+        setup.direction = *DISPLAY_DIRECTION_MUTEX.lock().await;
 
-        let _direction = *direction;
+        *ocp_mutex(Channel::A).lock().await = setup.ocp;
+        self.ocp_pubsub.publish_immediate(setup.ocp);
 
-        drop(direction);
+        setup_settings::store(setup).await;
+    }
 
-        self.display_direction_pubsub.publish_immediate(_direction);
+    /// Loads `value` into the entry of the UVP, OVP or OCP page.
+    async fn start_entry(&mut self, value: Value, max: Value) {
+        *ENTRY_MUTEX.lock().await = NumberEntry::new(
+            units::milli(value),
+            units::milli(THRESHOLD_MIN),
+            units::milli(max),
+        );
     }
 
-    async fn up_voltage(&mut self, selected: SrcPdo) -> SrcPdo {
-        let available = get_available_voltages().await;
+    async fn step_backlight(&mut self, up: bool) {
+        let mut backlight = BACKLIGHT_MUTEX.lock().await;
 
-        let index = available.iter().position(|&x| selected == x);
+        *backlight = if up {
+            (*backlight + 1).min(BACKLIGHT_MAX_LEVEL)
+        } else {
+            backlight.saturating_sub(1)
+        };
 
-        if index.is_none() {
-            return available[0];
-        }
+        let _backlight = *backlight;
 
-        let index = index.unwrap();
+        drop(backlight);
 
-        return available[(index + 1) % available.len()];
+        self.backlight_pubsub.publish_immediate(_backlight);
     }
 
-    async fn down_voltage(&mut self, selected: SrcPdo) -> SrcPdo {
-        let available = get_available_voltages().await;
+    async fn switch_direction(&mut self) {
+        let mut direction = DISPLAY_DIRECTION_MUTEX.lock().await;
 
-        let index = available.iter().position(|&x| selected == x);
+        *direction = direction.other();
 
-        if index.is_none() {
-            return available[0];
-        }
+        self.direction = *direction;
 
-        let index = index.unwrap();
+        let _direction = *direction;
 
-        return available[(index + available.len() - 1) % available.len()];
-    }
-}
+        drop(direction);
 
-fn instant_diff(a: Instant, b: Instant) -> Duration {
-    if a > b {
-        a - b
-    } else {
-        b - a
+        self.display_direction_pubsub.publish_immediate(_direction);
     }
 }
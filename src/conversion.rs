@@ -0,0 +1,151 @@
+//! How the INA226 converts: how many samples it averages and how long each conversion of the
+//! shunt and the bus voltage takes.
+//!
+//! The chip converts the shunt and then the bus voltage, again and again, and updates its registers
+//! once every averaged set. The defaults, 128 samples of 8.244 ms, give steady readings about
+//! every two seconds; a short conversion time with little averaging follows a fast load, with more
+//! noise. The Measurement page of the settings menu, or `conversion` on the console, picks both,
+//! and the measurement loop writes them to the chip on its next pass. The diagnostics page shows
+//! them with the reading period and bandwidth they make. They are not kept over a restart.
+
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) enum Averaging {
+    X1,
+    X4,
+    X16,
+    X64,
+    X128,
+    X256,
+    X512,
+    X1024,
+}
+
+pub(crate) const AVERAGINGS: [Averaging; 8] = [
+    Averaging::X1,
+    Averaging::X4,
+    Averaging::X16,
+    Averaging::X64,
+    Averaging::X128,
+    Averaging::X256,
+    Averaging::X512,
+    Averaging::X1024,
+];
+
+impl Averaging {
+    pub fn count(&self) -> u16 {
+        match self {
+            Averaging::X1 => 1,
+            Averaging::X4 => 4,
+            Averaging::X16 => 16,
+            Averaging::X64 => 64,
+            Averaging::X128 => 128,
+            Averaging::X256 => 256,
+            Averaging::X512 => 512,
+            Averaging::X1024 => 1024,
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        let count = s.parse().ok()?;
+
+        AVERAGINGS
+            .iter()
+            .copied()
+            .find(|averaging| averaging.count() == count)
+    }
+
+    /// The next larger (`up`) or smaller count, wrapping around.
+    pub fn step(&self, up: bool) -> Self {
+        step(&AVERAGINGS, *self as usize, up)
+    }
+}
+
+/// Time of one conversion, of the shunt or of the bus voltage.
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) enum ConversionTime {
+    Us140,
+    Us204,
+    Us332,
+    Us588,
+    Us1100,
+    Us2116,
+    Us4156,
+    Us8244,
+}
+
+pub(crate) const CONVERSION_TIMES: [ConversionTime; 8] = [
+    ConversionTime::Us140,
+    ConversionTime::Us204,
+    ConversionTime::Us332,
+    ConversionTime::Us588,
+    ConversionTime::Us1100,
+    ConversionTime::Us2116,
+    ConversionTime::Us4156,
+    ConversionTime::Us8244,
+];
+
+impl ConversionTime {
+    pub fn micros(&self) -> u16 {
+        match self {
+            ConversionTime::Us140 => 140,
+            ConversionTime::Us204 => 204,
+            ConversionTime::Us332 => 332,
+            ConversionTime::Us588 => 588,
+            ConversionTime::Us1100 => 1_100,
+            ConversionTime::Us2116 => 2_116,
+            ConversionTime::Us4156 => 4_156,
+            ConversionTime::Us8244 => 8_244,
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        let micros = s.parse().ok()?;
+
+        CONVERSION_TIMES
+            .iter()
+            .copied()
+            .find(|time| time.micros() == micros)
+    }
+
+    /// The next longer (`up`) or shorter time, wrapping around.
+    pub fn step(&self, up: bool) -> Self {
+        step(&CONVERSION_TIMES, *self as usize, up)
+    }
+}
+
+fn step<T: Copy, const N: usize>(items: &[T; N], index: usize, up: bool) -> T {
+    let index = if up { index + 1 } else { index + N - 1 };
+
+    items[index % N]
+}
+
+/// The same time goes for the shunt and the bus voltage.
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) struct Conversion {
+    pub averaging: Averaging,
+    pub time: ConversionTime,
+}
+
+impl Conversion {
+    /// Slow, heavily averaged conversions for steady readings.
+    pub const DEFAULT: Self = Self {
+        averaging: Averaging::X128,
+        time: ConversionTime::Us8244,
+    };
+
+    /// Microseconds between two updates of the readings: both voltages, as many times as averaged.
+    pub fn period_us(&self) -> u32 {
+        self.averaging.count() as u32 * 2 * self.time.micros() as u32
+    }
+
+    /// Updates of the readings per second, in thousandths.
+    pub fn rate_millihertz(&self) -> u32 {
+        1_000_000_000 / self.period_us()
+    }
+
+    /// Where the averaging over a period has taken 3 dB off a sine, in thousandths of a hertz.
+    pub fn bandwidth_millihertz(&self) -> u32 {
+        // 0.443 over the length of a moving average.
+        443_000_000 / self.period_us()
+    }
+}
@@ -0,0 +1,400 @@
+//! Panic and HardFault handling.
+//!
+//! Both turn the outputs off, paint a red screen with what went wrong and keep a record in RAM
+//! that survives the reset which follows. The next boot picks it up with [`take`], logs it and
+//! leaves it in `LAST_CRASH_MUTEX` for the `crash` console command.
+//!
+//! The handlers can run at any point, with the display task halfway through a DMA transfer and
+//! the executor stopped, so they steal the display's peripherals and drive the panel through a
+//! blocking SPI path of their own. Nothing here may wait on the time driver or an interrupt.
+
+use core::{
+    fmt::{self, Write},
+    mem::MaybeUninit,
+    panic::PanicInfo,
+    ptr::addr_of_mut,
+};
+
+use cortex_m::peripheral::SCB;
+use cortex_m_rt::{exception, ExceptionFrame};
+use embassy_futures::block_on;
+use embassy_stm32::{
+    dma::NoDma,
+    gpio::{Level, Output, Speed},
+    spi::{self, Spi},
+    Peripherals,
+};
+use embedded_graphics::{pixelcolor::Rgb565, prelude::RgbColor};
+use embedded_hal_async::spi::{ErrorType, Operation, SpiDevice};
+use heapless::String;
+use portable_atomic::{AtomicBool, Ordering};
+use st7789::ST7789;
+
+use crate::{
+    bsp,
+    font::{ARIAL_ROUND_16_24, MAX_GLYPH_BYTES},
+    log::{warn, Module},
+    shared::LAST_CRASH_MUTEX,
+    theme::COLOR_ERROR,
+    updater::crc32,
+};
+
+const LOG_MODULE: Module = Module::System;
+
+const MAGIC: u32 = 0x5044_4352; // "PDCR"
+
+/// Characters of the panic message kept, six lines of the crash screen.
+pub(crate) const MESSAGE_LEN: usize = 120;
+
+/// How long the crash screen stays up before the reset, in cycles of the 16 MHz system clock.
+const RESET_DELAY_CYCLES: u32 = 10 * 16_000_000;
+
+const CHAR_WIDTH: u16 = 16;
+const CHAR_HEIGHT: u16 = 24;
+const COLUMNS: usize = 20;
+const ROWS: usize = 7;
+
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) enum CrashKind {
+    Panic,
+    HardFault,
+}
+
+impl CrashKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CrashKind::Panic => "panic",
+            CrashKind::HardFault => "hardfault",
+        }
+    }
+}
+
+/// What the previous run died of.
+#[derive(Clone, Debug)]
+pub(crate) struct Crash {
+    pub kind: CrashKind,
+    /// Faulting instruction and return address for a HardFault, zero for a panic.
+    pub pc: u32,
+    pub lr: u32,
+    /// The panic message with its location, empty for a HardFault.
+    pub message: String<MESSAGE_LEN>,
+}
+
+#[repr(C)]
+struct Record {
+    magic: u32,
+    kind: u32,
+    pc: u32,
+    lr: u32,
+    len: u32,
+    message: [u8; MESSAGE_LEN],
+    crc: u32,
+}
+
+impl Record {
+    fn crc(&self) -> u32 {
+        let bytes = unsafe {
+            core::slice::from_raw_parts(
+                self as *const Self as *const u8,
+                core::mem::offset_of!(Record, crc),
+            )
+        };
+
+        crc32(bytes)
+    }
+}
+
+/// Not zeroed by the reset handler, so it outlives a system reset (but not a power cycle).
+#[link_section = ".uninit.CRASH"]
+static mut RECORD: MaybeUninit<Record> = MaybeUninit::uninit();
+
+/// Set by the first handler to run; a fault while painting skips straight to the reset.
+static CRASHED: AtomicBool = AtomicBool::new(false);
+
+/// Returns the crash recorded by the previous run, if any, and forgets it.
+pub(crate) fn take() -> Option<Crash> {
+    let record = unsafe { &mut *addr_of_mut!(RECORD).cast::<Record>() };
+
+    let valid = record.magic == MAGIC && record.crc == record.crc();
+    record.magic = 0;
+
+    if !valid {
+        return None;
+    }
+
+    let kind = match record.kind {
+        0 => CrashKind::Panic,
+        _ => CrashKind::HardFault,
+    };
+
+    let len = (record.len as usize).min(MESSAGE_LEN);
+    let mut message = String::new();
+    message
+        .push_str(core::str::from_utf8(&record.message[..len]).unwrap_or(""))
+        .ok();
+
+    Some(Crash {
+        kind,
+        pc: record.pc,
+        lr: record.lr,
+        message,
+    })
+}
+
+/// Logs the crash from the previous run and keeps it for the console. Returns whether there was
+/// one: the reset that follows a crash is a software reset, which alone looks like a restart.
+pub(crate) async fn report_previous() -> bool {
+    let Some(crash) = take() else {
+        return false;
+    };
+
+    warn!(
+        "previous run ended in a {}: pc={:#010x} lr={:#010x} {}",
+        crash.kind.as_str(),
+        crash.pc,
+        crash.lr,
+        crash.message.as_str()
+    );
+
+    *LAST_CRASH_MUTEX.lock().await = Some(crash);
+
+    true
+}
+
+#[panic_handler]
+fn panic(info: &PanicInfo) -> ! {
+    defmt::error!("{}", defmt::Display2Format(info));
+
+    let mut message = Message::new();
+    write!(message, "{}", info).ok();
+
+    crash(CrashKind::Panic, 0, 0, &message)
+}
+
+#[exception]
+unsafe fn HardFault(frame: &ExceptionFrame) -> ! {
+    defmt::error!(
+        "HardFault at pc={:#010x} lr={:#010x}",
+        frame.pc(),
+        frame.lr()
+    );
+
+    crash(
+        CrashKind::HardFault,
+        frame.pc(),
+        frame.lr(),
+        &Message::new(),
+    )
+}
+
+fn crash(kind: CrashKind, pc: u32, lr: u32, message: &Message) -> ! {
+    cortex_m::interrupt::disable();
+
+    if !CRASHED.swap(true, Ordering::Relaxed) {
+        store(kind, pc, lr, message);
+        paint(kind, pc, lr, message);
+
+        cortex_m::asm::delay(RESET_DELAY_CYCLES);
+    }
+
+    SCB::sys_reset()
+}
+
+fn store(kind: CrashKind, pc: u32, lr: u32, message: &Message) {
+    let record = unsafe { &mut *addr_of_mut!(RECORD).cast::<Record>() };
+
+    record.magic = MAGIC;
+    record.kind = kind as u32;
+    record.pc = pc;
+    record.lr = lr;
+    record.len = message.len as u32;
+    record.message = message.buf;
+    record.crc = record.crc();
+}
+
+/// Outputs off, backlight full on and the crash screen on the panel.
+fn paint(kind: CrashKind, pc: u32, lr: u32, message: &Message) {
+    let p = bsp::Board::new(unsafe { Peripherals::steal() });
+
+    let _output = Output::new(p.output, Level::Low, Speed::Low);
+    #[cfg(feature = "dual-output")]
+    let _output_b = Output::new(p.output_b, Level::Low, Speed::Low);
+    let _backlight = Output::new(p.backlight, Level::High, Speed::Low);
+
+    let spi = Spi::new_txonly(
+        p.display_spi,
+        p.display_sck,
+        p.display_mosi,
+        NoDma,
+        NoDma,
+        spi::Config::default(),
+    );
+    let spi = BlockingSpi {
+        spi,
+        cs: Output::new(p.display_cs, Level::High, Speed::High),
+    };
+    let dc = Output::new(p.display_dc, Level::Low, Speed::High);
+    let rst = Output::new(p.display_rst, Level::High, Speed::High);
+
+    // The panel keeps the orientation and setup the display task gave it.
+    let mut st7789 = ST7789::new(st7789::Config::default(), spi, dc, rst);
+
+    if block_on(st7789.fill_color(COLOR_ERROR)).is_err() {
+        return;
+    }
+
+    let mut lines = Lines::new();
+
+    match kind {
+        CrashKind::Panic => {
+            lines.push_str("PANIC");
+            lines.next_line();
+            lines.push_str(message.as_str());
+        }
+        CrashKind::HardFault => {
+            lines.push_str("HARD FAULT");
+            lines.next_line();
+            write!(lines, "PC {:08X}", pc).ok();
+            lines.next_line();
+            write!(lines, "LR {:08X}", lr).ok();
+        }
+    }
+
+    for (row, line) in lines.rows.iter().enumerate() {
+        for (column, &c) in line.iter().enumerate().filter(|(_, &c)| c != ' ') {
+            let glyph = ARIAL_ROUND_16_24
+                .chars
+                .iter()
+                .position(|&x| x == c)
+                .unwrap_or(ARIAL_ROUND_16_24.chars.len() - 1);
+            let mut buf = [0; MAX_GLYPH_BYTES];
+
+            block_on(st7789.write_area(
+                column as u16 * CHAR_WIDTH,
+                row as u16 * CHAR_HEIGHT,
+                CHAR_WIDTH,
+                ARIAL_ROUND_16_24.bitmap(glyph).bits(&mut buf),
+                Rgb565::WHITE,
+                COLOR_ERROR,
+            ))
+            .ok();
+        }
+    }
+}
+
+/// The panic message, cut off at [`MESSAGE_LEN`].
+struct Message {
+    buf: [u8; MESSAGE_LEN],
+    len: usize,
+}
+
+impl Message {
+    fn new() -> Self {
+        Self {
+            buf: [0; MESSAGE_LEN],
+            len: 0,
+        }
+    }
+
+    fn as_str(&self) -> &str {
+        // Only whole characters are copied in.
+        core::str::from_utf8(&self.buf[..self.len]).unwrap_or("")
+    }
+}
+
+impl Write for Message {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            let mut utf8 = [0; 4];
+            let bytes = c.encode_utf8(&mut utf8).as_bytes();
+
+            if self.len + bytes.len() > MESSAGE_LEN {
+                return Err(fmt::Error);
+            }
+
+            self.buf[self.len..self.len + bytes.len()].copy_from_slice(bytes);
+            self.len += bytes.len();
+        }
+
+        Ok(())
+    }
+}
+
+/// Screen text wrapped into rows. Characters the font lacks are drawn as spaces.
+struct Lines {
+    rows: [[char; COLUMNS]; ROWS],
+    row: usize,
+    column: usize,
+}
+
+impl Lines {
+    fn new() -> Self {
+        Self {
+            rows: [[' '; COLUMNS]; ROWS],
+            row: 0,
+            column: 0,
+        }
+    }
+
+    fn next_line(&mut self) {
+        self.row += 1;
+        self.column = 0;
+    }
+
+    fn push_str(&mut self, s: &str) {
+        for c in s.chars() {
+            if c == '\n' {
+                self.next_line();
+                continue;
+            }
+
+            if self.column == COLUMNS {
+                self.next_line();
+            }
+
+            if self.row == ROWS {
+                return;
+            }
+
+            self.rows[self.row][self.column] = c;
+            self.column += 1;
+        }
+    }
+}
+
+impl Write for Lines {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.push_str(s);
+        Ok(())
+    }
+}
+
+/// A write-only SPI device on the stolen bus that completes every transfer before returning.
+struct BlockingSpi<'d> {
+    spi: Spi<'d, bsp::DisplaySpi, NoDma, NoDma>,
+    cs: Output<'d, bsp::DisplayCsPin>,
+}
+
+impl ErrorType for BlockingSpi<'_> {
+    type Error = spi::Error;
+}
+
+impl SpiDevice for BlockingSpi<'_> {
+    async fn transaction(
+        &mut self,
+        operations: &mut [Operation<'_, u8>],
+    ) -> Result<(), Self::Error> {
+        self.cs.set_low();
+
+        let result = operations
+            .iter_mut()
+            .try_for_each(|operation| match operation {
+                Operation::Write(bytes) => self.spi.blocking_write(bytes),
+                _ => Ok(()),
+            });
+
+        self.cs.set_high();
+
+        result
+    }
+}
@@ -0,0 +1,165 @@
+//! Samples and events as CSV lines, and the files they go to, for the SD card log.
+//!
+//! Each line holds the time, then volts, amps and watts for a sample or what happened for an
+//! event, e.g. `2024-05-01T12:34:56.789Z,5.012,1.204,6.034,` or `+12.345,,,,trip over current`.
+//! Files are named `LOG0000.CSV` upwards, each starting with [`HEADER`]. A file is left once the
+//! next line would take it past [`MAX_FILE_BYTES`], and logging goes on in the next number. After a
+//! mount it starts past the highest number already on the card. `sd_card.rs` does the card access.
+
+use core::fmt::{self, Write};
+
+use heapless::String;
+
+use crate::{
+    types::PowerInfo,
+    units::{fixed, Value},
+};
+
+pub(crate) const HEADER: &str = "time,volts,amps,watts,event\r\n";
+
+/// About a day of samples at 1 Hz, still small enough to open in a spreadsheet.
+pub(crate) const MAX_FILE_BYTES: u32 = 4 * 1024 * 1024;
+
+/// Longest line, an ISO 8601 time and three readings.
+pub(crate) const LINE_LEN: usize = 64;
+
+/// `LOG` and four digits, which wrap around to 0 after 9999.
+const MAX_FILES: u16 = 10_000;
+
+#[derive(Clone, Copy, Debug, defmt::Format)]
+pub(crate) enum Record {
+    Sample(PowerInfo),
+    /// The output was turned off for the given reason.
+    Trip(&'static str),
+    /// The output came back on after a voltage trip.
+    Recover,
+    /// The output was switched on or off.
+    Output(bool),
+    /// An alarm went off, with the rate, or the switch dissipation, that set it off.
+    Alarm(&'static str, Value),
+    /// The firmware started, with what reset it.
+    Reset(&'static str),
+}
+
+/// `record` at `time` as a CSV line, line ending included.
+pub(crate) fn line(time: impl fmt::Display, record: &Record) -> String<LINE_LEN> {
+    let mut line = String::new();
+
+    match record {
+        Record::Sample(power) => write!(
+            line,
+            "{},{},{},{},\r\n",
+            time,
+            fixed(power.volts, 3, 0),
+            fixed(power.amps, 3, 0),
+            fixed(power.watts, 3, 0)
+        ),
+        Record::Trip(reason) => write!(line, "{},,,,trip {}\r\n", time, reason),
+        Record::Recover => write!(line, "{},,,,recover\r\n", time),
+        Record::Alarm(kind, rate) => write!(
+            line,
+            "{},,,,alarm {} {}\r\n",
+            time,
+            kind,
+            fixed(*rate, 3, 0)
+        ),
+        Record::Reset(cause) => write!(line, "{},,,,reset {}\r\n", time, cause),
+        Record::Output(on) => write!(
+            line,
+            "{},,,,output {}\r\n",
+            time,
+            if *on { "on" } else { "off" }
+        ),
+    }
+    .ok();
+
+    line
+}
+
+/// The 8.3 name of log file `index`.
+pub(crate) fn file_name(index: u16) -> String<12> {
+    let mut name = String::new();
+    write!(name, "LOG{:04}.CSV", index % MAX_FILES).ok();
+
+    name
+}
+
+/// The number of a log file named by [`file_name`], if `name` is one.
+pub(crate) fn file_index(name: &str) -> Option<u16> {
+    let digits = name.strip_prefix("LOG")?.strip_suffix(".CSV")?;
+
+    if digits.len() != 4 || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    digits.parse().ok()
+}
+
+/// The file being written and how long it is.
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) struct LogFile {
+    pub index: u16,
+    pub bytes: u32,
+}
+
+impl LogFile {
+    /// The file after the highest-numbered one found on the card, if any.
+    pub fn after(highest: Option<u16>) -> Self {
+        Self {
+            index: highest.map_or(0, |index| (index + 1) % MAX_FILES),
+            bytes: 0,
+        }
+    }
+
+    /// Whether `len` more bytes keep the file within [`MAX_FILE_BYTES`]. An empty file takes any
+    /// line.
+    pub fn fits(&self, len: usize) -> bool {
+        self.bytes == 0 || self.bytes.saturating_add(len as u32) <= MAX_FILE_BYTES
+    }
+
+    /// The file to go on in once this one is full.
+    pub fn next(&self) -> Self {
+        Self::after(Some(self.index))
+    }
+}
+
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) enum CardState {
+    /// No card answers, or it has no FAT volume.
+    NoCard,
+    Logging,
+    /// Closed from the menu; safe to pull the card.
+    Unmounted,
+    /// A write failed; the card is tried again shortly.
+    Error,
+}
+
+impl CardState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CardState::NoCard => "no card",
+            CardState::Logging => "logging",
+            CardState::Unmounted => "unmounted",
+            CardState::Error => "error",
+        }
+    }
+}
+
+/// What the SD card page shows.
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) struct CardStatus {
+    pub state: CardState,
+    pub file: LogFile,
+    /// Records lost because the card fell behind or was not logging.
+    pub dropped: u32,
+}
+
+impl CardStatus {
+    pub const fn default() -> Self {
+        Self {
+            state: CardState::NoCard,
+            file: LogFile { index: 0, bytes: 0 },
+            dropped: 0,
+        }
+    }
+}
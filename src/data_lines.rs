@@ -0,0 +1,95 @@
+//! Legacy charger signatures on the USB 2.0 data lines.
+//!
+//! Before USB PD, a charger told a device how much it may draw by the levels on D+ and D-: a
+//! BC1.2 dedicated charging port shorts them together, Apple chargers hold them at fixed 2.0 V and
+//! 2.7 V dividers, and a Quick Charge device asks for a higher voltage by driving them to 0.6 V and
+//! 3.3 V. A device charging slowly through the sink often sees none of these because the lines
+//! are not passed through. `line_monitor.rs` reads the levels; this names them.
+
+/// D+ and D- in millivolts.
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) struct DataLines {
+    pub plus_mv: u16,
+    pub minus_mv: u16,
+}
+
+/// Bands around the levels the signatures use.
+#[derive(PartialEq, Clone, Copy, Debug)]
+enum Level {
+    /// Pulled down or floating.
+    Low,
+    /// The 0.6 V BC1.2 and Quick Charge level.
+    Mid,
+    /// Apple's 2.0 V.
+    Apple20,
+    /// Apple's 2.7 V.
+    Apple27,
+    /// Quick Charge's 3.3 V.
+    High,
+    /// Between bands.
+    Other,
+}
+
+impl Level {
+    fn of(mv: u16) -> Self {
+        match mv {
+            0..=299 => Level::Low,
+            300..=1_000 => Level::Mid,
+            1_700..=2_300 => Level::Apple20,
+            2_450..=2_950 => Level::Apple27,
+            3_000.. => Level::High,
+            _ => Level::Other,
+        }
+    }
+}
+
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) enum Signature {
+    /// Both lines low: nothing attached, or a plain data port.
+    Idle,
+    /// Lines shorted at 0.6 V: a BC1.2 dedicated charging port, or the same levels as a Quick
+    /// Charge 12 V request.
+    Dcp,
+    /// An Apple divider, with the current in milliamps it advertises.
+    Apple(u16),
+    /// A Quick Charge 2.0 request for the given volts.
+    Qc(u8),
+    /// Quick Charge 3.0 continuous mode, stepping the voltage in 200 mV steps.
+    QcContinuous,
+    Unknown,
+}
+
+impl Signature {
+    pub fn of(lines: DataLines) -> Self {
+        match (Level::of(lines.plus_mv), Level::of(lines.minus_mv)) {
+            (Level::Low, Level::Low) => Signature::Idle,
+            (Level::Mid, Level::Mid) => Signature::Dcp,
+            (Level::Mid, Level::Low) => Signature::Qc(5),
+            (Level::High, Level::Mid) => Signature::Qc(9),
+            (Level::High, Level::High) => Signature::Qc(20),
+            (Level::Mid, Level::High) => Signature::QcContinuous,
+            (Level::Apple20, Level::Apple20) => Signature::Apple(500),
+            (Level::Apple20, Level::Apple27) => Signature::Apple(1_000),
+            (Level::Apple27, Level::Apple20) => Signature::Apple(2_100),
+            (Level::Apple27, Level::Apple27) => Signature::Apple(2_400),
+            _ => Signature::Unknown,
+        }
+    }
+
+    /// At most 10 characters, for the diagnostics page.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Signature::Idle => "idle",
+            Signature::Dcp => "DCP/QC 12V",
+            Signature::Apple(500) => "Apple 0.5A",
+            Signature::Apple(1_000) => "Apple 1A",
+            Signature::Apple(2_100) => "Apple 2.1A",
+            Signature::Apple(_) => "Apple 2.4A",
+            Signature::Qc(5) => "QC 5V",
+            Signature::Qc(9) => "QC 9V",
+            Signature::Qc(_) => "QC 20V",
+            Signature::QcContinuous => "QC3 cont",
+            Signature::Unknown => "unknown",
+        }
+    }
+}
@@ -1,25 +1,436 @@
-use core::convert::Infallible;
+use core::{convert::Infallible, fmt::Write};
 
 use embassy_sync::{blocking_mutex::raw::CriticalSectionRawMutex, pubsub::Subscriber};
-use embedded_graphics::{pixelcolor::Rgb565, prelude::WebColors};
+use embassy_time::{Duration, Instant};
+use embedded_graphics::{
+    mono_font::{
+        ascii::{FONT_5X8, FONT_6X10},
+        MonoFont,
+    },
+    pixelcolor::Rgb565,
+    prelude::WebColors,
+};
 use embedded_hal::digital::OutputPin;
 use embedded_hal_async::spi::SpiDevice;
+use heapless::String;
 use husb238::SrcPdo;
-use st7789::ST7789;
+use st7789::{Orientation, ST7789};
 
+#[cfg(feature = "dual-output")]
+use crate::shared::SELECTED_CHANNEL_MUTEX;
 use crate::{
-    font::{
-        get_index_by_char, ARIAL_ROUND_16_24, ARIAL_ROUND_16_24_INDEX, GROTESK_24_48,
-        GROTESK_24_48_INDEX,
-    },
+    average::{AverageInterval, AVERAGE_INTERVALS},
+    capture::{CaptureState, CAPTURE_LEN},
+    cc_lines::Rp,
+    csv_log::{file_name, CardState},
+    data_lines::Signature,
+    fault::{self, Fault, Faults},
+    fmt::fixed_milli,
+    font::{Bitmap, Font, ARIAL_ROUND_16_24, GROTESK_24_48, MAX_GLYPH_BYTES},
+    heartbeat::{self, TASKS},
+    help,
+    log::{info, warn, Module},
+    menu::{breadcrumb, scroll_thumb},
+    protection::SenseHealth,
+    rails::Rails,
+    render::Pending,
+    reset_cause::RESET_CAUSES,
+    schedule::Action,
+    session::{self, OCP_BANDS_PERCENT},
+    setup::{SetupStep, SETUP_STEPS},
     shared::{
-        AVAILABLE_VOLT_CURR_MUTEX, COLOR_AMPERAGE, COLOR_BACKGROUND, COLOR_BASE, COLOR_PRIMARY,
+        AVAILABLE_VOLT_CURR_MUTEX, AVERAGE_INTERVAL_MUTEX, BACKLIGHT_MUTEX,
+        BACKLIGHT_TIMEOUT_MUTEX, CABLE_MUTEX, CAPTURE_MUTEX, CC_LINES_MUTEX, CLOCK_ENTRY_MUTEX,
+        CONVERSION_MUTEX, DATA_LINES_MUTEX, DISPLAY_DEGRADED_MUTEX, DISPLAY_DIRECTION_MUTEX,
+        DISPLAY_DIRECTION_PUBSUB, ENTRY_MUTEX, FAN_STATUS_MUTEX, FAULTS_MUTEX, FAULT_PUBSUB,
+        OUTPUT_MODE_MUTEX, OUTPUT_SENSE_MUTEX, OUTPUT_STATS_MUTEX, PAGE_PUBSUB, RESET_COUNTS_MUTEX,
+        SCREEN_MUTEX, SD_LOG_MUTEX, SESSION_MUTEX, SETUP_MUTEX, SWITCH_LIMIT_MUTEX,
+        SYSTEM_STATUS_MUTEX, THEME_MUTEX, THEME_PUBSUB, VBUS_MUTEX, WATTS_SOURCE_MUTEX,
+    },
+    stack, telemetry,
+    theme::{
+        COLOR_AMPERAGE, COLOR_BACKGROUND, COLOR_BASE, COLOR_ERROR, COLOR_INFO, COLOR_PRIMARY,
         COLOR_PRIMARY_CONTENT, COLOR_TEXT, COLOR_TEXT_DISABLED, COLOR_VOLTAGE, COLOR_WATTAGE,
-        PAGE_PUBSUB,
     },
-    types::{Page, PowerInfo, SettingItem, StatusInfo, SETTING_ITEMS, VOLTAGE_ITEMS},
+    timing::{self, Section, SECTIONS},
+    types::{
+        pdo_matches, pdo_volts, AboutView, ClockField, DiagnosticsView, Direction, DisplayItem,
+        LimitField, MeasurementItem, Negotiation, OutputMode, Page, PowerInfo, SettingItem,
+        StatusInfo, SystemStatus, Theme, WifiState, CLOCK_FIELDS, DISPLAY_ITEMS, MEASUREMENT_ITEMS,
+        SETTING_ITEMS, VOLTAGE_ITEMS,
+    },
+    units::{self, fixed, Value, ZERO},
+    utilization::Utilization,
+    watts::{WattsSource, WATTS_SOURCES},
 };
 
+const LOG_MODULE: Module = Module::Display;
+
+/// Vertical separator between the menu and the page content, 2 pixels wide and the panel high. It
+/// doubles as the scrollbar of the settings list.
+static SEPARATOR: [u8; 43] = [0xff; 43];
+
+/// Row of the PD contract on the splash screen, below the self-test results, and its width in
+/// characters.
+const NEGOTIATION_Y: u16 = 140;
+const NEGOTIATION_WIDTH: usize = 18;
+
+/// Scrollbar of the PDO list, at the right edge below the header.
+const PDO_SCROLLBAR_X: u16 = 316;
+const PDO_SCROLLBAR_Y: u16 = 10;
+const PDO_SCROLLBAR_LEN: u16 = 148;
+
+/// Characters and decimals of the volts, amps and watts readings on the monitor page.
+const MONITOR_WIDTH: usize = 7;
+const MONITOR_DECIMALS: u8 = 3;
+/// Shown in a monitor field while its quantity cannot be read.
+const MISSING_READING: &str = "--.--";
+
+/// Characters of the header above the content of the setting pages, the rest of the top row.
+const BREADCRUMB_WIDTH: usize = 30;
+
+/// Characters of the interval averages above the units.
+const AVERAGE_WIDTH: usize = 5;
+
+/// Along the top of the monitor page after an over-current trip; the small font has no arrows.
+const TRIP_HINT: &str = "TRIPPED - hold UP to reset";
+const TRIP_HINT_WIDTH: usize = TRIP_HINT.len();
+/// In place of the trip hint once the trips blew the soft fuse.
+const FUSE_HINT: &str = "FUSE BLOWN - fuse reset";
+
+/// Characters of the output statistics on the About page; a `u32` count has up to 10 digits.
+const ABOUT_STATS_WIDTH: usize = 16;
+
+/// Cells of the target voltage and current limit in the status column, unit included.
+const STATUS_WIDTH: usize = 5;
+
+/// Delay between attempts to bring a failed panel back.
+const REINIT_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Refresh interval of the tables on the diagnostics, cable and SD card pages.
+const DIAGNOSTICS_INTERVAL: Duration = Duration::from_secs(1);
+/// Characters of a row of those tables.
+const DIAGNOSTICS_WIDTH: usize = 20;
+
+/// Scope graph: two pixels per sample, bars up to `GRAPH_HEIGHT`, then a gap and the trigger mark.
+/// The full scale and zero are labelled in the margin to its left.
+const GRAPH_X: u16 = 32;
+const GRAPH_Y: u16 = 66;
+const GRAPH_HEIGHT: usize = 92;
+const GRAPH_ROWS: usize = GRAPH_HEIGHT + 6;
+/// Samples per 8-pixel strip.
+const STRIP_SAMPLES: usize = 4;
+
+/// Panel size in landscape.
+const PANEL_WIDTH: u16 = 320;
+const PANEL_HEIGHT: u16 = 172;
+
+/// Rows cleared per pass while the screen is wiped after a flip, so the picture slides away in
+/// four steps instead of the whole panel flashing at once.
+const FLIP_ROWS: u16 = 43;
+
+/// How often everything on the screen is drawn again, to repair glyph cells an SPI glitch
+/// corrupted. Field updates only repaint the cells whose text changed, so otherwise the damage stays
+/// until the next page change.
+const REFRESH_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+/// Parts of the screen the periodic refresh draws again, one per display pass, so it never holds
+/// the SPI bus for a whole page at once.
+#[derive(Clone, Copy)]
+enum RefreshStep {
+    /// The labels of the monitor page, or a menu page with its options.
+    Page,
+    Readings,
+    /// The averages and the contract use in small print.
+    Details,
+    Contract,
+    Indicators,
+    StatusBar,
+}
+
+const REFRESH_STEPS: [RefreshStep; 6] = [
+    RefreshStep::Page,
+    RefreshStep::Readings,
+    RefreshStep::Details,
+    RefreshStep::Contract,
+    RefreshStep::Indicators,
+    RefreshStep::StatusBar,
+];
+
+/// Status bar along the bottom edge, in 5x8 cells below everything the pages draw.
+const STATUS_BAR_Y: u16 = 164;
+const STATUS_BAR_CELL: u16 = 5;
+
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub enum DisplayError {
+    Init,
+    Write,
+}
+
+impl DisplayError {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DisplayError::Init => "init failed",
+            DisplayError::Write => "write failed",
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum FieldFont {
+    Bitmap(&'static Font),
+    Mono(&'static MonoFont<'static>),
+}
+
+impl FieldFont {
+    fn width(&self) -> u16 {
+        match self {
+            FieldFont::Bitmap(font) => font.width,
+            FieldFont::Mono(font) => font.character_size.width as u16,
+        }
+    }
+
+    /// From one cell to the next.
+    fn advance(&self) -> u16 {
+        match self {
+            FieldFont::Bitmap(font) => font.width,
+            FieldFont::Mono(font) => (font.character_size.width + font.character_spacing) as u16,
+        }
+    }
+
+    fn glyph(&self, c: char) -> Bitmap {
+        match *self {
+            FieldFont::Bitmap(font) => font.glyph(c),
+            FieldFont::Mono(font) => Bitmap::Mono(font, c),
+        }
+    }
+}
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+enum Align {
+    Left,
+    /// Numbers, so the decimal point and any unit stay put as the digits change.
+    Right,
+}
+
+/// `N` glyph cells at a fixed position that remember what they last showed, so an update only
+/// repaints the cells that changed. Cells a shorter string no longer covers are cleared.
+struct TextField<const N: usize> {
+    x: u16,
+    y: u16,
+    font: FieldFont,
+    align: Align,
+    /// The `N` cells as shown, padding included.
+    cells: String<N>,
+    color: Rgb565,
+    /// Whether the panel shows `cells`; false until the first render and after the screen is
+    /// cleared under the field.
+    valid: bool,
+}
+
+impl<const N: usize> TextField<N> {
+    fn new(x: u16, y: u16, font: FieldFont, align: Align) -> Self {
+        Self {
+            x,
+            y,
+            font,
+            align,
+            cells: String::new(),
+            color: COLOR_BACKGROUND,
+            valid: false,
+        }
+    }
+
+    /// Repaints every cell on the next render.
+    fn invalidate(&mut self) {
+        self.valid = false;
+    }
+
+    /// `text` laid out over the `N` cells; what does not fit is cut off on the right.
+    fn layout(&self, text: &str) -> String<N> {
+        let len = text.chars().count().min(N);
+        let pad = match self.align {
+            Align::Left => 0,
+            Align::Right => N - len,
+        };
+
+        let mut cells = String::new();
+        for _ in 0..pad {
+            cells.push(' ').ok();
+        }
+        for c in text.chars().take(len) {
+            cells.push(c).ok();
+        }
+        while cells.len() < N {
+            cells.push(' ').ok();
+        }
+
+        cells
+    }
+
+    /// The cells that differ from what is shown, with the character each should show.
+    fn changes<'t>(
+        &'t self,
+        cells: &'t str,
+        color: Rgb565,
+    ) -> impl Iterator<Item = (u16, char)> + 't {
+        let repaint_all = !self.valid || self.color != color;
+        let mut shown = self.cells.chars();
+
+        cells.chars().enumerate().filter_map(move |(idx, char)| {
+            if shown.next() != Some(char) || repaint_all {
+                Some((idx as u16, char))
+            } else {
+                None
+            }
+        })
+    }
+
+    fn set(&mut self, cells: String<N>, color: Rgb565) {
+        self.cells = cells;
+        self.color = color;
+        self.valid = true;
+    }
+}
+
+/// The changing values of the monitor page.
+struct MonitorFields {
+    volts: TextField<MONITOR_WIDTH>,
+    amps: TextField<MONITOR_WIDTH>,
+    watts: TextField<MONITOR_WIDTH>,
+    watts_unit: TextField<1>,
+    /// Interval averages, in small print above the units.
+    average_volts: TextField<AVERAGE_WIDTH>,
+    average_amps: TextField<AVERAGE_WIDTH>,
+    average_watts: TextField<AVERAGE_WIDTH>,
+    /// RMS of the current, below its average.
+    rms_amps: TextField<AVERAGE_WIDTH>,
+    pdo: TextField<3>,
+    target_volts: TextField<STATUS_WIDTH>,
+    limit_amps: TextField<STATUS_WIDTH>,
+    output: TextField<3>,
+    /// How to re-arm the output after an over-current trip, in small print along the top.
+    trip_hint: TextField<TRIP_HINT_WIDTH>,
+    faults: TextField<3>,
+    wifi: TextField<3>,
+    remote: TextField<3>,
+    schedule: TextField<9>,
+    /// Amps and watts in percent of the PD contract.
+    contract_amps: TextField<5>,
+    contract_watts: TextField<5>,
+}
+
+impl MonitorFields {
+    fn new() -> Self {
+        Self {
+            volts: TextField::new(10, 10, FieldFont::Bitmap(&GROTESK_24_48), Align::Right),
+            amps: TextField::new(10, 60, FieldFont::Bitmap(&GROTESK_24_48), Align::Right),
+            watts: TextField::new(10, 110, FieldFont::Bitmap(&GROTESK_24_48), Align::Right),
+            watts_unit: TextField::new(
+                180,
+                130,
+                FieldFont::Bitmap(&ARIAL_ROUND_16_24),
+                Align::Left,
+            ),
+            average_volts: TextField::new(180, 14, FieldFont::Mono(&FONT_5X8), Align::Left),
+            average_amps: TextField::new(180, 62, FieldFont::Mono(&FONT_5X8), Align::Left),
+            average_watts: TextField::new(180, 110, FieldFont::Mono(&FONT_5X8), Align::Left),
+            rms_amps: TextField::new(180, 72, FieldFont::Mono(&FONT_5X8), Align::Left),
+            pdo: TextField::new(210, 10, FieldFont::Bitmap(&ARIAL_ROUND_16_24), Align::Left),
+            target_volts: TextField::new(
+                210,
+                35,
+                FieldFont::Bitmap(&ARIAL_ROUND_16_24),
+                Align::Right,
+            ),
+            limit_amps: TextField::new(
+                210,
+                85,
+                FieldFont::Bitmap(&ARIAL_ROUND_16_24),
+                Align::Right,
+            ),
+            output: TextField::new(210, 135, FieldFont::Bitmap(&ARIAL_ROUND_16_24), Align::Left),
+            trip_hint: TextField::new(10, 1, FieldFont::Mono(&FONT_5X8), Align::Left),
+            faults: TextField::new(258, 10, FieldFont::Bitmap(&ARIAL_ROUND_16_24), Align::Left),
+            wifi: TextField::new(258, 60, FieldFont::Bitmap(&ARIAL_ROUND_16_24), Align::Left),
+            remote: TextField::new(258, 110, FieldFont::Bitmap(&ARIAL_ROUND_16_24), Align::Left),
+            schedule: TextField::new(262, 143, FieldFont::Mono(&FONT_5X8), Align::Left),
+            contract_amps: TextField::new(262, 88, FieldFont::Mono(&FONT_5X8), Align::Left),
+            contract_watts: TextField::new(262, 98, FieldFont::Mono(&FONT_5X8), Align::Left),
+        }
+    }
+
+    fn invalidate(&mut self) {
+        self.volts.invalidate();
+        self.amps.invalidate();
+        self.watts.invalidate();
+        self.watts_unit.invalidate();
+        self.average_volts.invalidate();
+        self.average_amps.invalidate();
+        self.average_watts.invalidate();
+        self.rms_amps.invalidate();
+        self.pdo.invalidate();
+        self.target_volts.invalidate();
+        self.limit_amps.invalidate();
+        self.output.invalidate();
+        self.trip_hint.invalidate();
+        self.faults.invalidate();
+        self.wifi.invalidate();
+        self.remote.invalidate();
+        self.schedule.invalidate();
+        self.contract_amps.invalidate();
+        self.contract_watts.invalidate();
+    }
+}
+
+/// The status bar, shown on every page.
+struct StatusBar {
+    output: TextField<7>,
+    contract: TextField<11>,
+    locked: TextField<4>,
+    remote: TextField<3>,
+    alarm: TextField<5>,
+    interlock: TextField<8>,
+    derated: TextField<6>,
+    temperature: TextField<4>,
+}
+
+impl StatusBar {
+    fn new() -> Self {
+        // Column 32 stays empty for the separator of the setting pages.
+        Self {
+            output: Self::field(0, Align::Left),
+            contract: Self::field(9, Align::Left),
+            locked: Self::field(22, Align::Left),
+            remote: Self::field(28, Align::Left),
+            alarm: Self::field(34, Align::Left),
+            interlock: Self::field(40, Align::Left),
+            derated: Self::field(49, Align::Left),
+            temperature: Self::field(60, Align::Right),
+        }
+    }
+
+    fn field<const N: usize>(column: u16, align: Align) -> TextField<N> {
+        TextField::new(
+            column * STATUS_BAR_CELL,
+            STATUS_BAR_Y,
+            FieldFont::Mono(&FONT_5X8),
+            align,
+        )
+    }
+
+    fn invalidate(&mut self) {
+        self.output.invalidate();
+        self.contract.invalidate();
+        self.locked.invalidate();
+        self.remote.invalidate();
+        self.alarm.invalidate();
+        self.interlock.invalidate();
+        self.derated.invalidate();
+        self.temperature.invalidate();
+    }
+}
+
 pub struct Display<'a, SPI, DC, RST>
 where
     SPI: SpiDevice,
@@ -27,15 +438,57 @@ where
     RST: OutputPin<Error = Infallible>,
 {
     st7789: ST7789<SPI, DC, RST>,
-    power_info: PowerInfo,
+    /// The last readings, `None` for one that could not be taken.
+    volts: Option<Value>,
+    amps: Option<Value>,
+    watts: Option<Value>,
     status_info: StatusInfo,
-    ryu_buffer: ryu::Buffer,
-    prev_ryu_buffer: ryu::Buffer,
-    force_render: bool,
+    remote: bool,
+    /// An over-current trip waits for a reset.
+    tripped: bool,
+    wifi: WifiState,
+    faults: Faults,
+    /// The next switch of the output schedule, if one is set and the clock is.
+    schedule: Option<Action>,
+    /// Use of the PD contract, and whether it has been too high for too long.
+    utilization: Option<Utilization>,
+    utilization_warning: bool,
+    watts_source: WattsSource,
+    /// Mean of the last completed averaging interval, while averaging is on.
+    average: Option<PowerInfo>,
+    /// RMS of the current over the last second.
+    rms: Option<Value>,
+
+    /// The PDO picked in the menu.
+    selected_pdo: Option<SrcPdo>,
+
+    fields: MonitorFields,
+    system_status: SystemStatus,
+    status_bar: StatusBar,
+
+    /// Set by a failed transfer; rendering is skipped until `task` re-initializes the panel.
+    error: Option<DisplayError>,
+    reinit_at: Instant,
+
+    diagnostics_at: Instant,
+    /// Next row to clear while the old picture is wiped away after a flip.
+    flip: Option<u16>,
+    /// The step of a running periodic refresh, an index into [`REFRESH_STEPS`].
+    refresh: Option<usize>,
+    refresh_at: Instant,
+    /// Which held capture the scope graph shows, if any.
+    capture_shown: Option<u32>,
 
     page: Page,
 
+    /// Applied to the panel again after every reset.
+    direction: Direction,
+    theme: Theme,
+
     page_pubsub: Subscriber<'a, CriticalSectionRawMutex, Page, 2, 2, 1>,
+    fault_sub: Subscriber<'a, CriticalSectionRawMutex, Fault, 2, 2, 1>,
+    direction_sub: Subscriber<'a, CriticalSectionRawMutex, Direction, 2, 2, 1>,
+    theme_sub: Subscriber<'a, CriticalSectionRawMutex, Theme, 2, 2, 1>,
 }
 
 impl<'a, SPI, DC, RST> Display<'a, SPI, DC, RST>
@@ -47,142 +500,324 @@ where
     pub fn new(st7789: ST7789<SPI, DC, RST>) -> Self {
         Self {
             st7789,
-            power_info: PowerInfo::default(),
+            volts: None,
+            amps: None,
+            watts: None,
             status_info: StatusInfo::default(),
-            ryu_buffer: ryu::Buffer::new(),
-            prev_ryu_buffer: ryu::Buffer::new(),
-            force_render: true,
+            remote: false,
+            tripped: false,
+            wifi: WifiState::Disabled,
+            faults: Faults::empty(),
+            schedule: None,
+            utilization: None,
+            utilization_warning: false,
+            watts_source: WattsSource::Register,
+            average: None,
+            rms: None,
+
+            selected_pdo: None,
+
+            fields: MonitorFields::new(),
+            system_status: SystemStatus::default(),
+            status_bar: StatusBar::new(),
+
+            error: None,
+            reinit_at: Instant::MIN,
+
+            diagnostics_at: Instant::MIN,
+            flip: None,
+            refresh: None,
+            refresh_at: Instant::MIN,
+            capture_shown: None,
 
             page: Page::Monitor,
+
+            direction: Direction::Normal,
+            theme: Theme::Light,
+
             page_pubsub: PAGE_PUBSUB.subscriber().unwrap(),
+            fault_sub: FAULT_PUBSUB.subscriber().unwrap(),
+            direction_sub: DISPLAY_DIRECTION_PUBSUB.subscriber().unwrap(),
+            theme_sub: THEME_PUBSUB.subscriber().unwrap(),
         }
     }
 
-    pub async fn init(&mut self) -> Result<(), ()> {
-        self.force_render = true;
-
-        self.st7789.init().await.map_err(|_| ())?;
+    /// Resets the panel and draws the current page.
+    ///
+    /// A failure is also reported as [`Fault::Display`], and `task` keeps retrying afterwards.
+    pub async fn init(&mut self) -> Result<(), DisplayError> {
+        // The whole page is drawn again anyway.
+        self.flip = None;
+
+        match self.st7789.init().await {
+            Ok(_) => {
+                self.error = None;
+                let result = self.configure().await;
+                self.check(result).await;
+            }
+            Err(_) => self.check(Err(DisplayError::Init)).await,
+        }
 
         self.update_layout().await;
 
-        self.update_monitor_amps(0.0).await;
-        self.update_monitor_volts(0.0).await;
-        self.update_monitor_watts(0.0).await;
+        match self.error {
+            Some(err) => Err(err),
+            None => Ok(()),
+        }
+    }
 
-        self.update_target_volts(0.0).await;
-        self.update_limit_amps(0.0).await;
-        self.update_output(false).await;
+    /// Rotation and color inversion, which the panel loses on reset.
+    async fn configure(&mut self) -> Result<(), DisplayError> {
+        let orientation = match self.direction {
+            Direction::Normal => Orientation::Landscape,
+            Direction::Reversed => Orientation::LandscapeSwapped,
+        };
 
-        self.force_render = false;
-        Ok(())
+        self.st7789
+            .set_orientation(orientation)
+            .await
+            .map_err(|_| DisplayError::Write)?;
+        self.st7789
+            .invert_colors(self.theme == Theme::Dark)
+            .await
+            .map_err(|_| DisplayError::Write)
     }
 
-    pub async fn update_monitor_volts(&mut self, volts: f64) {
-        if !matches!(self.page, Page::Monitor) {
+    /// Blanks the panel for idle mode. Its frame memory, and so the screen, survives.
+    pub async fn sleep(&mut self) {
+        let result = self.st7789.sleep().await.map_err(|_| DisplayError::Write);
+        self.check(result).await;
+    }
+
+    pub async fn wake(&mut self) {
+        let result = self.st7789.wake().await.map_err(|_| DisplayError::Write);
+        self.check(result).await;
+    }
+
+    pub async fn update_monitor_volts(&mut self, volts: Option<Value>) {
+        self.volts = volts;
+
+        if self.error.is_some() || !matches!(self.page, Page::Monitor) {
             return;
         }
 
-        let curr = self.ryu_buffer.format(volts);
-        let prev = self.prev_ryu_buffer.format(self.power_info.volts);
-
-        Self::render_monitor(
+        let result = Self::render_reading(
             &mut self.st7789,
-            curr,
-            prev,
-            10,
-            COLOR_BACKGROUND,
+            &mut self.fields.volts,
+            volts,
             COLOR_VOLTAGE,
-            self.force_render,
         )
         .await;
-
-        self.power_info.volts = volts;
+        self.check(result).await;
     }
 
-    pub async fn update_monitor_amps(&mut self, amps: f64) {
-        if !matches!(self.page, Page::Monitor) {
+    pub async fn update_monitor_amps(&mut self, amps: Option<Value>) {
+        self.amps = amps;
+
+        if self.error.is_some() || !matches!(self.page, Page::Monitor) {
             return;
         }
 
-        let curr = self.ryu_buffer.format(amps);
-        let prev = self.prev_ryu_buffer.format(self.power_info.amps);
-
-        Self::render_monitor(
+        let result = Self::render_reading(
             &mut self.st7789,
-            curr,
-            prev,
-            60,
-            COLOR_BACKGROUND,
+            &mut self.fields.amps,
+            amps,
             COLOR_AMPERAGE,
-            self.force_render,
         )
         .await;
-
-        self.power_info.amps = amps;
+        self.check(result).await;
     }
 
-    pub async fn update_monitor_watts(&mut self, watts: f64) {
-        if !matches!(self.page, Page::Monitor) {
+    pub async fn update_monitor_watts(&mut self, watts: Option<Value>) {
+        self.watts = watts;
+
+        if self.error.is_some() || !matches!(self.page, Page::Monitor) {
             return;
         }
 
-        let curr = self.ryu_buffer.format(watts);
-        let prev = self.prev_ryu_buffer.format(self.power_info.watts);
+        let result = Self::render_reading(
+            &mut self.st7789,
+            &mut self.fields.watts,
+            watts,
+            COLOR_WATTAGE,
+        )
+        .await;
+        self.check(result).await;
+    }
+
+    /// Shown as the unit of the watts reading.
+    pub async fn update_watts_source(&mut self, source: WattsSource) {
+        self.watts_source = source;
 
-        Self::render_monitor(
+        if self.error.is_some() || !matches!(self.page, Page::Monitor) {
+            return;
+        }
+
+        let result = Self::render_field(
             &mut self.st7789,
-            curr,
-            prev,
-            110,
-            COLOR_BACKGROUND,
+            &mut self.fields.watts_unit,
+            source.unit(),
             COLOR_WATTAGE,
-            self.force_render,
         )
         .await;
+        self.check(result).await;
+    }
+
+    /// Shown above the units; `None` clears them.
+    pub async fn update_average(&mut self, average: Option<PowerInfo>) {
+        self.average = average;
+
+        if self.error.is_some() || !matches!(self.page, Page::Monitor) {
+            return;
+        }
 
-        self.power_info.watts = watts;
+        let result = self.render_average().await;
+        self.check(result).await;
     }
 
-    pub async fn update_target_volts(&mut self, volts: f64) {
-        if !matches!(self.page, Page::Monitor) {
+    async fn render_average(&mut self) -> Result<(), DisplayError> {
+        let fields = &mut self.fields;
+        let st7789 = &mut self.st7789;
+
+        let Some(average) = self.average else {
+            Self::render_field(st7789, &mut fields.average_volts, "", COLOR_VOLTAGE).await?;
+            Self::render_field(st7789, &mut fields.average_amps, "", COLOR_AMPERAGE).await?;
+            return Self::render_field(st7789, &mut fields.average_watts, "", COLOR_WATTAGE).await;
+        };
+
+        Self::render_number(
+            st7789,
+            &mut fields.average_volts,
+            average.volts,
+            2,
+            "",
+            COLOR_VOLTAGE,
+        )
+        .await?;
+        Self::render_number(
+            st7789,
+            &mut fields.average_amps,
+            average.amps,
+            3,
+            "",
+            COLOR_AMPERAGE,
+        )
+        .await?;
+        Self::render_number(
+            st7789,
+            &mut fields.average_watts,
+            average.watts,
+            1,
+            "",
+            COLOR_WATTAGE,
+        )
+        .await
+    }
+
+    /// Shown as "r1.23" below the average of the amps; `None` clears it.
+    pub async fn update_rms(&mut self, rms: Option<Value>) {
+        self.rms = rms;
+
+        if self.error.is_some() || !matches!(self.page, Page::Monitor) {
             return;
         }
 
+        let mut text: String<AVERAGE_WIDTH> = String::new();
+        if let Some(rms) = rms {
+            write!(text, "r{}", fixed(rms, 2, 0)).ok();
+        }
+
+        let result = Self::render_field(
+            &mut self.st7789,
+            &mut self.fields.rms_amps,
+            &text,
+            COLOR_AMPERAGE,
+        )
+        .await;
+        self.check(result).await;
+    }
+
+    pub async fn update_target_volts(&mut self, volts: Value) {
+        // Kept on the other pages too, as the contract is only sent when it changes.
         self.status_info.target_volts = volts;
 
-        let curr = self.ryu_buffer.format(self.status_info.target_volts);
+        if !matches!(self.page, Page::Monitor) {
+            return;
+        }
+
+        if self.error.is_some() {
+            return;
+        }
 
-        Self::render_status(
+        let result = Self::render_number(
             &mut self.st7789,
-            curr,
-            210,
-            35,
-            COLOR_BACKGROUND,
+            &mut self.fields.target_volts,
+            self.status_info.target_volts,
+            1,
+            "V",
             COLOR_TEXT,
-            4,
         )
         .await;
+        self.check(result).await;
+
+        self.update_pdo_label().await;
     }
 
-    pub async fn update_limit_amps(&mut self, amps: f64) {
-        if !matches!(self.page, Page::Monitor) {
+    /// The PDO picked in the menu. While the source grants another voltage, the "PDO" label is
+    /// replaced by the selected voltage, dimmed, with an arrow to the granted one below it.
+    pub async fn update_selected_pdo(&mut self, pdo: SrcPdo) {
+        self.selected_pdo = Some(pdo);
+
+        self.update_pdo_label().await;
+    }
+
+    async fn update_pdo_label(&mut self) {
+        if self.error.is_some() || !matches!(self.page, Page::Monitor) {
             return;
         }
 
+        let mismatch = self
+            .selected_pdo
+            .filter(|pdo| !pdo_matches(*pdo, self.status_info.target_volts));
+
+        let mut label: String<3> = String::new();
+        let color = match mismatch {
+            Some(pdo) => {
+                write!(label, "{:>2}>", units::milli(pdo_volts(pdo)) / 1000).ok();
+                COLOR_TEXT_DISABLED
+            }
+            None => {
+                label.push_str("PDO").ok();
+                COLOR_BASE
+            }
+        };
+
+        let result =
+            Self::render_field(&mut self.st7789, &mut self.fields.pdo, &label, color).await;
+        self.check(result).await;
+    }
+
+    pub async fn update_limit_amps(&mut self, amps: Value) {
         self.status_info.limit_amps = amps;
 
-        let curr: &str = self.ryu_buffer.format(self.status_info.limit_amps);
+        if !matches!(self.page, Page::Monitor) {
+            return;
+        }
+
+        if self.error.is_some() {
+            return;
+        }
 
-        Self::render_status(
+        let result = Self::render_number(
             &mut self.st7789,
-            curr,
-            210,
-            85,
-            COLOR_BACKGROUND,
+            &mut self.fields.limit_amps,
+            self.status_info.limit_amps,
+            2,
+            "A",
             COLOR_TEXT,
-            4,
         )
         .await;
+        self.check(result).await;
     }
 
     pub async fn update_output(&mut self, output: bool) {
@@ -192,80 +827,586 @@ where
 
         self.status_info.output = output;
 
-        Self::render_status(
+        if self.error.is_some() {
+            return;
+        }
+
+        let (text, color) = match output {
+            true => ("ON", COLOR_TEXT),
+            false if self.tripped => ("TRP", COLOR_ERROR),
+            false => ("OFF", COLOR_TEXT),
+        };
+
+        let result =
+            Self::render_field(&mut self.st7789, &mut self.fields.output, text, color).await;
+        self.check(result).await;
+    }
+
+    /// Shows "TRP" in the output field after an over-current trip, with how to reset it.
+    pub async fn update_tripped(&mut self, tripped: bool) {
+        if !matches!(self.page, Page::Monitor) {
+            return;
+        }
+
+        self.tripped = tripped;
+        self.update_output(self.status_info.output).await;
+
+        if self.error.is_some() {
+            return;
+        }
+
+        let result = Self::render_field(
             &mut self.st7789,
-            if output { "ON" } else { "OFF" },
-            210,
-            135,
-            COLOR_BACKGROUND,
-            COLOR_TEXT,
-            3,
+            &mut self.fields.trip_hint,
+            match (tripped, self.system_status.fuse_blown) {
+                (true, true) => FUSE_HINT,
+                (true, false) => TRIP_HINT,
+                (false, _) => "",
+            },
+            COLOR_ERROR,
         )
         .await;
+        self.check(result).await;
     }
 
-    pub async fn update_layout(&mut self) {
-        self.st7789.fill_color(COLOR_BACKGROUND).await.unwrap();
+    pub async fn update_remote(&mut self, remote: bool) {
+        if !matches!(self.page, Page::Monitor) {
+            return;
+        }
 
-        match self.page {
-            Page::Monitor => {
-                self.update_monitor_layout().await;
-                self.force_render = true;
-                self.update_monitor_amps(0.0).await;
-                self.update_monitor_volts(0.0).await;
-                self.update_monitor_watts(0.0).await;
-                self.update_target_volts(0.0).await;
-                self.update_limit_amps(0.0).await;
-                self.update_output(false).await;
-                self.force_render = false;
-            }
-            Page::Setting(setting_item) => self.update_setting_layout(setting_item).await,
-            Page::Voltage(selected) => {
-                self.update_setting_layout(SettingItem::Voltage).await;
-                self.update_voltage_layout(selected).await;
-            }
-            Page::UVP => self.update_monitor_layout().await,
-            Page::OCP => self.update_monitor_layout().await,
-            Page::About => {
-                self.update_setting_layout(SettingItem::About).await;
-                self.update_about_layout().await;
-            }
+        self.remote = remote;
+
+        if self.error.is_some() {
+            return;
         }
-    }
 
-    pub async fn update_monitor_layout(&mut self) {
-        Self::render_status(
+        let result = Self::render_field(
             &mut self.st7789,
-            "V",
-            180,
-            34,
-            COLOR_BACKGROUND,
-            COLOR_VOLTAGE,
-            1,
+            &mut self.fields.remote,
+            if remote { "REM" } else { "" },
+            COLOR_INFO,
         )
         .await;
+        self.check(result).await;
+    }
 
-        Self::render_status(
+    pub async fn update_wifi(&mut self, wifi: WifiState) {
+        if !matches!(self.page, Page::Monitor) {
+            return;
+        }
+
+        self.wifi = wifi;
+
+        if self.error.is_some() {
+            return;
+        }
+
+        let (text, color) = match wifi {
+            WifiState::Disabled => ("", COLOR_TEXT),
+            WifiState::Disconnected => ("NET", COLOR_ERROR),
+            WifiState::Connecting => ("NET", COLOR_TEXT_DISABLED),
+            WifiState::Connected => ("NET", COLOR_INFO),
+        };
+
+        let result = Self::render_field(&mut self.st7789, &mut self.fields.wifi, text, color).await;
+        self.check(result).await;
+    }
+
+    pub async fn update_faults(&mut self, faults: Faults) {
+        if !matches!(self.page, Page::Monitor) {
+            return;
+        }
+
+        self.faults = faults;
+
+        if self.error.is_some() {
+            return;
+        }
+
+        let result = Self::render_field(
             &mut self.st7789,
-            "A",
-            180,
-            82,
-            COLOR_BACKGROUND,
-            COLOR_AMPERAGE,
-            1,
+            &mut self.fields.faults,
+            if faults.is_empty() { "" } else { "ERR" },
+            COLOR_ERROR,
         )
         .await;
+        self.check(result).await;
+    }
 
-        Self::render_status(
+    pub async fn update_schedule(&mut self, schedule: Option<Action>) {
+        if !matches!(self.page, Page::Monitor) {
+            return;
+        }
+
+        self.schedule = schedule;
+
+        if self.error.is_some() {
+            return;
+        }
+
+        let mut text: String<9> = String::new();
+        if let Some(action) = schedule {
+            let on = if action.on { "ON" } else { "OFF" };
+            write!(text, "{} {:02}:{:02}", on, action.hour(), action.minute()).ok();
+        }
+
+        let result = Self::render_field(
             &mut self.st7789,
-            "W",
-            180,
-            130,
-            COLOR_BACKGROUND,
-            COLOR_WATTAGE,
-            1,
+            &mut self.fields.schedule,
+            &text,
+            COLOR_INFO,
         )
         .await;
+        self.check(result).await;
+    }
+
+    /// Shown as "I 95%" and "P 93%" next to the current limit, red with `warning`.
+    pub async fn update_utilization(&mut self, utilization: Option<Utilization>, warning: bool) {
+        if !matches!(self.page, Page::Monitor) {
+            return;
+        }
+
+        self.utilization = utilization;
+        self.utilization_warning = warning;
+
+        if self.error.is_some() {
+            return;
+        }
+
+        let mut amps: String<5> = String::new();
+        let mut watts: String<5> = String::new();
+        if let Some(utilization) = utilization {
+            write!(amps, "I{:>3}%", utilization.amps_percent).ok();
+            write!(watts, "P{:>3}%", utilization.watts_percent).ok();
+        }
+
+        let color = if warning { COLOR_ERROR } else { COLOR_TEXT };
+
+        let result = Self::render_field(
+            &mut self.st7789,
+            &mut self.fields.contract_amps,
+            &amps,
+            color,
+        )
+        .await;
+        self.check(result).await;
+
+        let result = Self::render_field(
+            &mut self.st7789,
+            &mut self.fields.contract_watts,
+            &watts,
+            color,
+        )
+        .await;
+        self.check(result).await;
+    }
+
+    pub async fn update_layout(&mut self) {
+        if self.error.is_some() {
+            return;
+        }
+
+        let result = self.render_layout().await;
+        self.check(result).await;
+
+        // Everything is drawn afresh, which is what the periodic refresh would do.
+        self.refresh = None;
+        self.refresh_at = Instant::now() + REFRESH_INTERVAL;
+
+        // The screen was cleared under the fields.
+        self.fields.invalidate();
+        self.status_bar.invalidate();
+
+        if matches!(self.page, Page::Monitor) {
+            self.update_monitor_amps(self.amps).await;
+            self.update_monitor_volts(self.volts).await;
+            self.update_monitor_watts(self.watts).await;
+            self.update_watts_source(self.watts_source).await;
+            self.update_average(self.average).await;
+            self.update_target_volts(self.status_info.target_volts)
+                .await;
+            self.update_limit_amps(self.status_info.limit_amps).await;
+            self.update_tripped(self.tripped).await;
+            self.update_remote(self.remote).await;
+            self.update_wifi(self.wifi).await;
+            self.update_faults(self.faults).await;
+            self.update_schedule(self.schedule).await;
+            self.update_utilization(self.utilization, self.utilization_warning)
+                .await;
+        }
+
+        self.update_status_bar(self.system_status).await;
+    }
+
+    /// Output, PD contract, lock, remote control, alarm and temperature, along the bottom of every
+    /// page.
+    pub async fn update_status_bar(&mut self, status: SystemStatus) {
+        let fuse_changed = status.fuse_blown != self.system_status.fuse_blown;
+        self.system_status = status;
+
+        if fuse_changed {
+            self.update_tripped(self.tripped).await;
+        }
+
+        if self.error.is_some() {
+            return;
+        }
+
+        let result = self.render_status_bar().await;
+        self.check(result).await;
+    }
+
+    async fn render_status_bar(&mut self) -> Result<(), DisplayError> {
+        let status = self.system_status;
+        let bar = &mut self.status_bar;
+        let st7789 = &mut self.st7789;
+
+        let (output, color) = match status.output {
+            true => ("OUT ON", COLOR_INFO),
+            false if status.fuse_blown => ("FUSE", COLOR_ERROR),
+            false if status.tripped => ("TRIPPED", COLOR_ERROR),
+            false => ("OUT OFF", COLOR_TEXT_DISABLED),
+        };
+        Self::render_field(st7789, &mut bar.output, output, color).await?;
+
+        let mut contract: String<11> = String::new();
+        write!(
+            contract,
+            "{}V {}A",
+            fixed(status.target_volts, 1, 0),
+            fixed(status.limit_amps, 2, 0)
+        )
+        .ok();
+        Self::render_field(st7789, &mut bar.contract, &contract, COLOR_TEXT).await?;
+
+        let locked = if status.locked { "LOCK" } else { "" };
+        Self::render_field(st7789, &mut bar.locked, locked, COLOR_ERROR).await?;
+
+        let remote = if status.remote { "REM" } else { "" };
+        Self::render_field(st7789, &mut bar.remote, remote, COLOR_INFO).await?;
+
+        let alarm = if status.alarm { "ALARM" } else { "" };
+        Self::render_field(st7789, &mut bar.alarm, alarm, COLOR_ERROR).await?;
+
+        let (interlock, color) = match status.interlock {
+            Some(true) => ("ILK OK", COLOR_TEXT),
+            Some(false) => ("ILK OPEN", COLOR_ERROR),
+            None => ("", COLOR_TEXT),
+        };
+        Self::render_field(st7789, &mut bar.interlock, interlock, color).await?;
+
+        let derated = if status.derated { "DERATE" } else { "" };
+        Self::render_field(st7789, &mut bar.derated, derated, COLOR_ERROR).await?;
+
+        let mut temperature: String<4> = String::new();
+        if let Some(celsius) = status.temperature {
+            write!(temperature, "{}C", celsius).ok();
+        }
+        Self::render_field(st7789, &mut bar.temperature, &temperature, COLOR_TEXT).await
+    }
+
+    /// Draws what the render queue collected since the last call, see `render.rs`.
+    pub async fn apply(&mut self, pending: Pending) {
+        if pending.init {
+            self.init().await.ok();
+        }
+
+        if let Some(source) = pending.watts_source {
+            self.update_watts_source(source).await;
+        }
+        if let Some(volts) = pending.volts {
+            self.update_monitor_volts(volts).await;
+        }
+        if let Some(amps) = pending.amps {
+            self.update_monitor_amps(amps).await;
+        }
+        if let Some(watts) = pending.watts {
+            self.update_monitor_watts(watts).await;
+        }
+        if let Some(average) = pending.average {
+            self.update_average(average).await;
+        }
+        if let Some(rms) = pending.rms {
+            self.update_rms(rms).await;
+        }
+        if let Some((utilization, warning)) = pending.utilization {
+            self.update_utilization(utilization, warning).await;
+        }
+        if let Some(volts) = pending.target_volts {
+            self.update_target_volts(volts).await;
+        }
+        if let Some(amps) = pending.limit_amps {
+            self.update_limit_amps(amps).await;
+        }
+        if let Some(pdo) = pending.selected_pdo {
+            self.update_selected_pdo(pdo).await;
+        }
+        if let Some(output) = pending.output {
+            self.update_output(output).await;
+        }
+        if let Some(tripped) = pending.tripped {
+            self.update_tripped(tripped).await;
+        }
+        if let Some(remote) = pending.remote {
+            self.update_remote(remote).await;
+        }
+        if let Some(wifi) = pending.wifi {
+            self.update_wifi(wifi).await;
+        }
+        if let Some(schedule) = pending.schedule {
+            self.update_schedule(schedule).await;
+        }
+    }
+
+    /// Whether the panel took the last transfer; readings are not shown while it is failed.
+    pub fn is_available(&self) -> bool {
+        self.error.is_none()
+    }
+
+    /// Splash screen with the power-on self-test results, one peripheral per line. It stays up
+    /// until the next `update_layout`.
+    pub async fn show_selftest(&mut self, checks: &[(Fault, bool)]) {
+        if self.error.is_some() {
+            return;
+        }
+
+        let result = self.render_selftest(checks).await;
+        self.check(result).await;
+    }
+
+    async fn render_selftest(&mut self, checks: &[(Fault, bool)]) -> Result<(), DisplayError> {
+        self.st7789
+            .fill_color(COLOR_BACKGROUND)
+            .await
+            .map_err(|_| DisplayError::Write)?;
+        SCREEN_MUTEX.lock().await.clear(COLOR_BACKGROUND);
+        self.fields.invalidate();
+        self.status_bar.invalidate();
+
+        Self::render_status(
+            &mut self.st7789,
+            "Self test",
+            10,
+            10,
+            COLOR_BACKGROUND,
+            COLOR_TEXT,
+            9,
+        )
+        .await?;
+
+        for (i, (peripheral, passed)) in checks.iter().enumerate() {
+            let y = 50 + i as u16 * 30;
+            let label = peripheral.as_str();
+            let (result, color) = if *passed {
+                ("ok", COLOR_TEXT)
+            } else {
+                ("FAIL", COLOR_ERROR)
+            };
+
+            Self::render_status(
+                &mut self.st7789,
+                label,
+                10,
+                y,
+                COLOR_BACKGROUND,
+                COLOR_TEXT,
+                label.len() as u16,
+            )
+            .await?;
+
+            Self::render_status(
+                &mut self.st7789,
+                result,
+                240,
+                y,
+                COLOR_BACKGROUND,
+                color,
+                result.len() as u16,
+            )
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Adds the state of the first PD contract to the splash screen, redrawn in place while
+    /// pending.
+    pub async fn show_negotiation(&mut self, negotiation: Negotiation) {
+        if self.error.is_some() {
+            return;
+        }
+
+        let result = self.render_negotiation(negotiation).await;
+        self.check(result).await;
+    }
+
+    async fn render_negotiation(&mut self, negotiation: Negotiation) -> Result<(), DisplayError> {
+        let mut text: String<NEGOTIATION_WIDTH> = String::new();
+        let color = match negotiation {
+            Negotiation::Pending(frame) => {
+                let dots = ["", ".", "..", "..."][frame as usize % 4];
+                write!(text, "Negotiating{}", dots).ok();
+                COLOR_TEXT
+            }
+            Negotiation::Done(volts, amps) => {
+                write!(text, "PD {}V {}A", fixed(volts, 1, 0), fixed(amps, 2, 0)).ok();
+                COLOR_TEXT
+            }
+            Negotiation::Failed => {
+                write!(text, "PD no contract").ok();
+                COLOR_ERROR
+            }
+        };
+
+        // `render_status` pads with zeros.
+        while text.push(' ').is_ok() {}
+
+        Self::render_status(
+            &mut self.st7789,
+            &text,
+            10,
+            NEGOTIATION_Y,
+            COLOR_BACKGROUND,
+            color,
+            NEGOTIATION_WIDTH as u16,
+        )
+        .await
+    }
+
+    async fn render_layout(&mut self) -> Result<(), DisplayError> {
+        self.st7789
+            .fill_color(COLOR_BACKGROUND)
+            .await
+            .map_err(|_| DisplayError::Write)?;
+        SCREEN_MUTEX.lock().await.clear(COLOR_BACKGROUND);
+
+        self.render_page().await
+    }
+
+    /// Draws the current page over what the screen shows, without clearing it first.
+    async fn render_page(&mut self) -> Result<(), DisplayError> {
+        self.render_breadcrumb().await?;
+
+        match self.page {
+            Page::Monitor => self.render_monitor_layout().await,
+            Page::Setting(setting_item) => self.render_setting_layout(setting_item).await,
+            Page::Voltage(selected) => {
+                self.render_setting_layout(SettingItem::Voltage).await?;
+                self.render_voltage_layout(selected).await
+            }
+            Page::UVP(field) => {
+                self.render_setting_layout(SettingItem::UVP).await?;
+                let (label, zero) = match field {
+                    LimitField::Trip => ("Below", "off"),
+                    LimitField::Recover => ("Recover", "at trip"),
+                };
+                self.render_entry_layout(label, "V", zero).await
+            }
+            Page::OVP(field) => {
+                self.render_setting_layout(SettingItem::OVP).await?;
+                let (label, zero) = match field {
+                    LimitField::Trip => ("Above", "off"),
+                    LimitField::Recover => ("Recover", "at trip"),
+                };
+                self.render_entry_layout(label, "V", zero).await
+            }
+            Page::OCP => {
+                self.render_setting_layout(SettingItem::OCP).await?;
+                self.render_entry_layout("Max", "A", "off").await
+            }
+            Page::Output => {
+                self.render_setting_layout(SettingItem::Output).await?;
+                self.render_output_layout().await
+            }
+            Page::Watts => {
+                self.render_setting_layout(SettingItem::Watts).await?;
+                self.render_watts_layout().await
+            }
+            Page::Average => {
+                self.render_setting_layout(SettingItem::Average).await?;
+                self.render_average_layout().await
+            }
+            Page::Measurement(item) => {
+                self.render_setting_layout(SettingItem::Measurement).await?;
+                self.render_measurement_layout(item).await
+            }
+            Page::Cable => self.render_cable().await,
+            Page::Storage => self.render_storage().await,
+            Page::Capture => {
+                self.capture_shown = None;
+                self.render_capture().await
+            }
+            Page::Session => self.render_session().await,
+            Page::Diagnostics(view) => self.render_diagnostics(view).await,
+            Page::Display(item) => {
+                self.render_setting_layout(SettingItem::Display).await?;
+                self.render_display_layout(item).await
+            }
+            Page::Clock(field) => {
+                self.render_setting_layout(SettingItem::Clock).await?;
+                self.render_clock_layout(field).await
+            }
+            Page::About(AboutView::Info) => {
+                self.render_setting_layout(SettingItem::About).await?;
+                self.render_about_layout().await
+            }
+            Page::About(view) => {
+                self.render_setting_layout(SettingItem::About).await?;
+                self.render_help_layout(view).await
+            }
+            Page::Setup(step) => self.render_setup(step).await,
+        }
+    }
+
+    async fn render_monitor_layout(&mut self) -> Result<(), DisplayError> {
+        #[cfg(feature = "dual-output")]
+        {
+            let channel = *SELECTED_CHANNEL_MUTEX.lock().await;
+
+            Self::render_mono(
+                &mut self.st7789,
+                channel.as_str(),
+                &FONT_5X8,
+                180,
+                1,
+                COLOR_BASE,
+                COLOR_BACKGROUND,
+            )
+            .await?;
+        }
+
+        Self::render_status(
+            &mut self.st7789,
+            "V",
+            180,
+            34,
+            COLOR_BACKGROUND,
+            COLOR_VOLTAGE,
+            1,
+        )
+        .await?;
+
+        Self::render_status(
+            &mut self.st7789,
+            "A",
+            180,
+            82,
+            COLOR_BACKGROUND,
+            COLOR_AMPERAGE,
+            1,
+        )
+        .await?;
+
+        Self::render_status(
+            &mut self.st7789,
+            self.watts_source.unit(),
+            180,
+            130,
+            COLOR_BACKGROUND,
+            COLOR_WATTAGE,
+            1,
+        )
+        .await?;
 
         Self::render_status(
             &mut self.st7789,
@@ -276,7 +1417,7 @@ where
             COLOR_BASE,
             3,
         )
-        .await;
+        .await?;
 
         Self::render_status(
             &mut self.st7789,
@@ -287,7 +1428,7 @@ where
             COLOR_BASE,
             3,
         )
-        .await;
+        .await?;
 
         Self::render_status(
             &mut self.st7789,
@@ -298,23 +1439,15 @@ where
             COLOR_BASE,
             3,
         )
-        .await;
-    }
+        .await?;
 
-    pub async fn update_setting_layout(&mut self, setting_item: SettingItem) {
-        let line_bytes = [0xff_u8; 43];
-        self.st7789
-            .write_area(
-                160,
-                0,
-                2,
-                &line_bytes,
-                Rgb565::CSS_DARK_GRAY,
-                Rgb565::CSS_DARK_GRAY,
-            )
-            .await
-            .unwrap();
+        Ok(())
+    }
 
+    async fn render_setting_layout(
+        &mut self,
+        setting_item: SettingItem,
+    ) -> Result<(), DisplayError> {
         let offset = SETTING_ITEMS
             .iter()
             .enumerate()
@@ -322,6 +1455,16 @@ where
             .map(|(i, _)| i)
             .unwrap_or(0);
 
+        Self::render_scrollbar(
+            &mut self.st7789,
+            160,
+            0,
+            PANEL_HEIGHT,
+            offset,
+            SETTING_ITEMS.len(),
+        )
+        .await?;
+
         for i in 0..SETTING_ITEMS.len().min(5) {
             let idx = (offset + i + SETTING_ITEMS.len() - 2) % SETTING_ITEMS.len();
             let item = SETTING_ITEMS[idx];
@@ -335,7 +1478,19 @@ where
             let text = match item {
                 SettingItem::Voltage => "  PDO  ",
                 SettingItem::UVP => "  UVP  ",
+                SettingItem::OVP => "  OVP  ",
                 SettingItem::OCP => "  OCP  ",
+                SettingItem::Output => " Output",
+                SettingItem::Watts => " Watts ",
+                SettingItem::Average => "Average",
+                SettingItem::Measurement => "Measure",
+                SettingItem::Cable => " Cable ",
+                SettingItem::Capture => " Scope ",
+                SettingItem::Session => "Session",
+                SettingItem::Storage => "SD card",
+                SettingItem::Diagnostics => " Diag  ",
+                SettingItem::Display => "Display",
+                SettingItem::Clock => " Clock ",
                 SettingItem::About => " About ",
             };
 
@@ -351,11 +1506,42 @@ where
                 color,
                 text.len() as u16,
             )
-            .await;
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Where the page sits in the settings menu, e.g. "Settings > OCP", above its content. Padded
+    /// to the full width, as the page is drawn over the previous one.
+    async fn render_breadcrumb(&mut self) -> Result<(), DisplayError> {
+        let crumbs = breadcrumb(self.page);
+        if crumbs.is_empty() {
+            return Ok(());
+        }
+
+        let mut text: String<BREADCRUMB_WIDTH> = String::new();
+        for (i, crumb) in crumbs.iter().enumerate() {
+            if i > 0 {
+                text.push_str(" > ").ok();
+            }
+            text.push_str(crumb).ok();
         }
+        while text.push(' ').is_ok() {}
+
+        Self::render_mono(
+            &mut self.st7789,
+            &text,
+            &FONT_5X8,
+            170,
+            1,
+            COLOR_BASE,
+            COLOR_BACKGROUND,
+        )
+        .await
     }
 
-    pub async fn update_about_layout(&mut self) {
+    async fn render_about_layout(&mut self) -> Result<(), DisplayError> {
         Self::render_status(
             &mut self.st7789,
             "Author:",
@@ -365,7 +1551,7 @@ where
             COLOR_TEXT,
             7,
         )
-        .await;
+        .await?;
 
         Self::render_status(
             &mut self.st7789,
@@ -376,7 +1562,7 @@ where
             COLOR_TEXT,
             9,
         )
-        .await;
+        .await?;
 
         Self::render_status(
             &mut self.st7789,
@@ -387,7 +1573,7 @@ where
             COLOR_TEXT,
             8,
         )
-        .await;
+        .await?;
 
         Self::render_status(
             &mut self.st7789,
@@ -398,24 +1584,798 @@ where
             COLOR_TEXT,
             7,
         )
-        .await;
+        .await?;
+
+        // Lifetime counters of the output, as they were when the page was opened.
+        let stats = *OUTPUT_STATS_MUTEX.lock().await;
+        let mut line: String<ABOUT_STATS_WIDTH> = String::new();
+
+        write!(line, "on    {:>9}h", stats.on_secs / 3600).ok();
+        Self::render_mono(
+            &mut self.st7789,
+            &line,
+            &FONT_6X10,
+            170,
+            124,
+            COLOR_TEXT_DISABLED,
+            COLOR_BACKGROUND,
+        )
+        .await?;
+
+        line.clear();
+        write!(line, "cycles{:>10}", stats.cycles).ok();
+        Self::render_mono(
+            &mut self.st7789,
+            &line,
+            &FONT_6X10,
+            170,
+            138,
+            COLOR_TEXT_DISABLED,
+            COLOR_BACKGROUND,
+        )
+        .await?;
+
+        // What reset the unit before this boot, the counts are on the diagnostics page.
+        let last = RESET_COUNTS_MUTEX.lock().await.last;
+        line.clear();
+        write!(
+            line,
+            "reset{:>11}",
+            last.map_or("none", |cause| cause.as_str())
+        )
+        .ok();
+        Self::render_mono(
+            &mut self.st7789,
+            &line,
+            &FONT_6X10,
+            170,
+            152,
+            COLOR_TEXT_DISABLED,
+            COLOR_BACKGROUND,
+        )
+        .await?;
+
+        Ok(())
     }
 
-    pub async fn update_voltage_layout(&mut self, selected: SrcPdo) {
-        defmt::info!("selected: {:?}", selected);
+    /// The heading of a sub-page of the cheat-sheet and its gestures, see `help.rs`.
+    async fn render_help_layout(&mut self, view: AboutView) -> Result<(), DisplayError> {
+        let mut title: String<9> = String::new();
+        write!(title, "{:<9}", help::title(view)).ok();
+        Self::render_status(
+            &mut self.st7789,
+            &title,
+            170,
+            10,
+            COLOR_BACKGROUND,
+            COLOR_TEXT,
+            9,
+        )
+        .await?;
 
-        let available_volt_curr = AVAILABLE_VOLT_CURR_MUTEX.lock().await;
+        for (i, gesture) in help::gestures(view).iter().enumerate() {
+            // The lines ending in a colon head the ones below them.
+            let color = match gesture.action.is_empty() {
+                true => COLOR_TEXT_DISABLED,
+                false => COLOR_TEXT,
+            };
 
-        let offset = VOLTAGE_ITEMS
-            .iter()
-            .enumerate()
-            .find(|(_, ele)| **ele == selected)
-            .map(|(i, _)| i)
-            .unwrap_or(0);
+            Self::render_mono(
+                &mut self.st7789,
+                &help::line(gesture),
+                &FONT_6X10,
+                170,
+                42 + i as u16 * 14,
+                color,
+                COLOR_BACKGROUND,
+            )
+            .await?;
+        }
 
-        for i in 0..VOLTAGE_ITEMS.len().min(5) {
-            let idx = (offset + i + VOLTAGE_ITEMS.len() - 2) % VOLTAGE_ITEMS.len();
-            let item = VOLTAGE_ITEMS[idx];
+        Ok(())
+    }
+
+    /// A step of the setup wizard, its value and what the buttons do, see `setup.rs`. The big font
+    /// has no symbols, so the hints are in words.
+    async fn render_setup(&mut self, step: SetupStep) -> Result<(), DisplayError> {
+        let setup = *SETUP_MUTEX.lock().await;
+
+        let mut row: String<DIAGNOSTICS_WIDTH> = String::new();
+        write!(
+            row,
+            "{:<14}{} of {}",
+            "Setup",
+            step as usize + 1,
+            SETUP_STEPS.len()
+        )
+        .ok();
+        self.render_diagnostics_row(&row, 0, COLOR_INFO).await?;
+
+        row.clear();
+        write!(row, "{:<DIAGNOSTICS_WIDTH$}", step.as_str()).ok();
+        self.render_diagnostics_row(&row, 1, COLOR_TEXT).await?;
+
+        row.clear();
+        match step {
+            SetupStep::Rotation => {
+                let rotation = match *DISPLAY_DIRECTION_MUTEX.lock().await {
+                    Direction::Normal => "normal",
+                    Direction::Reversed => "flipped",
+                };
+                write!(row, "{:>DIAGNOSTICS_WIDTH$}", rotation).ok()
+            }
+            SetupStep::Ocp if setup.ocp > ZERO => write!(row, "{}A", fixed(setup.ocp, 2, 19)).ok(),
+            SetupStep::Ocp => write!(row, "{:>DIAGNOSTICS_WIDTH$}", "off").ok(),
+            // Micro-ohms are thousandths of a milliohm.
+            SetupStep::Shunt => write!(
+                row,
+                "{}mOhm",
+                fixed_milli(setup.shunt_micro_ohms.min(9_999_999) as i32, 1, 16)
+            )
+            .ok(),
+        };
+        self.render_diagnostics_row(&row, 2, COLOR_PRIMARY).await?;
+
+        let next = match step.next() {
+            Some(_) => "next",
+            None => "save",
+        };
+        for (i, (buttons, action)) in [
+            ("Up or Down", "change"),
+            ("Both", next),
+            ("Hold both", "skip"),
+        ]
+        .iter()
+        .enumerate()
+        {
+            row.clear();
+            write!(row, "{:<14}{:>6}", buttons, action).ok();
+            self.render_diagnostics_row(&row, i + 3, COLOR_TEXT_DISABLED)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Task table with heartbeat age in seconds and mean and worst loop latency in milliseconds,
+    /// section table with mean and worst execution time in milliseconds, the INA226 averaging and
+    /// conversion time with the period, rate and bandwidth of the readings, the D+ and D- levels
+    /// with the charger signature they show, the CC levels with the Rp each pin sees and the
+    /// plug orientation, the output sense, the last reset cause with the count of each, or the
+    /// stack high-water mark.
+    async fn render_diagnostics(&mut self, view: DiagnosticsView) -> Result<(), DisplayError> {
+        self.diagnostics_at = Instant::now() + DIAGNOSTICS_INTERVAL;
+
+        let header = match view {
+            DiagnosticsView::Tasks => "task   age mean  max",
+            DiagnosticsView::Timing => "ms       mean    max",
+            DiagnosticsView::Conversion => "ina226              ",
+            DiagnosticsView::DataLines => "line         volts  ",
+            DiagnosticsView::CcLines => "line  volts  Rp     ",
+            DiagnosticsView::Output => "output              ",
+            DiagnosticsView::Rails => "rail         volts  ",
+            DiagnosticsView::Resets => "resets              ",
+            DiagnosticsView::Memory => "bytes    used  total",
+        };
+        self.render_diagnostics_row(header, 0, COLOR_INFO).await?;
+
+        match view {
+            DiagnosticsView::Tasks => {
+                for (i, task) in TASKS.iter().enumerate() {
+                    let stats = heartbeat::stats(*task);
+
+                    let mut row: String<DIAGNOSTICS_WIDTH> = String::new();
+                    write!(row, "{:<6}", task.as_str()).ok();
+
+                    match stats.age_ms {
+                        Some(ms) => {
+                            write!(row, "{}", fixed_milli(ms.min(99_999) as i32, 1, 4)).ok()
+                        }
+                        None => write!(row, "none").ok(),
+                    };
+
+                    write!(
+                        row,
+                        "{:>5}{:>5}",
+                        stats.mean_gap_ms.min(9_999),
+                        stats.max_gap_ms.min(9_999)
+                    )
+                    .ok();
+
+                    self.render_diagnostics_row(&row, i + 1, COLOR_TEXT).await?;
+                }
+
+                // Below the tasks, on builds with a fan.
+                if let Some(fan) = *FAN_STATUS_MUTEX.lock().await {
+                    let mut row: String<DIAGNOSTICS_WIDTH> = String::new();
+                    write!(row, "{:<6}{:>3}C ", "fan", fan.celsius.clamp(0, 999)).ok();
+
+                    // Same width every time, so a shorter state covers a longer one.
+                    match fan.percent {
+                        _ if fan.kicking => write!(row, "{:>8}", "kick").ok(),
+                        0 => write!(row, "{:>8}", "off").ok(),
+                        percent => write!(row, "{:>4} pct", percent).ok(),
+                    };
+
+                    self.render_diagnostics_row(&row, TASKS.len() + 1, COLOR_TEXT)
+                        .await?;
+                }
+            }
+            DiagnosticsView::Timing => {
+                let degraded = *DISPLAY_DEGRADED_MUTEX.lock().await;
+
+                for (i, section) in SECTIONS.iter().enumerate() {
+                    let stats = timing::stats(*section);
+
+                    // Microseconds are thousandths of a millisecond.
+                    let mut row: String<DIAGNOSTICS_WIDTH> = String::new();
+                    write!(
+                        row,
+                        "{:<6}{}{}",
+                        section.as_str(),
+                        fixed_milli(stats.mean_us.min(9_999_999) as i32, 2, 7),
+                        fixed_milli(stats.max_us.min(9_999_999) as i32, 2, 7)
+                    )
+                    .ok();
+
+                    // The small print on the screen is slowed down, see `render_budget.rs`.
+                    let color = match (section, degraded) {
+                        (Section::Display, true) => COLOR_ERROR,
+                        _ => COLOR_TEXT,
+                    };
+
+                    self.render_diagnostics_row(&row, i + 1, color).await?;
+                }
+            }
+            DiagnosticsView::Conversion => {
+                let conversion = *CONVERSION_MUTEX.lock().await;
+
+                let mut row: String<DIAGNOSTICS_WIDTH> = String::new();
+                write!(row, "{:<8}{:>11}x", "average", conversion.averaging.count()).ok();
+                self.render_diagnostics_row(&row, 1, COLOR_TEXT).await?;
+
+                // Of the shunt and of the bus voltage each.
+                let mut row: String<DIAGNOSTICS_WIDTH> = String::new();
+                write!(row, "{:<8}{:>10}us", "convert", conversion.time.micros()).ok();
+                self.render_diagnostics_row(&row, 2, COLOR_TEXT).await?;
+
+                for (i, (name, milli, unit)) in [
+                    ("period", conversion.period_us(), "ms"),
+                    ("rate", conversion.rate_millihertz(), "Hz"),
+                    ("bw", conversion.bandwidth_millihertz(), "Hz"),
+                ]
+                .into_iter()
+                .enumerate()
+                {
+                    let mut row: String<DIAGNOSTICS_WIDTH> = String::new();
+                    write!(
+                        row,
+                        "{:<6}{}{}",
+                        name,
+                        fixed_milli(milli as i32, 3, 12),
+                        unit
+                    )
+                    .ok();
+
+                    self.render_diagnostics_row(&row, i + 3, COLOR_TEXT).await?;
+                }
+            }
+            DiagnosticsView::DataLines => match *DATA_LINES_MUTEX.lock().await {
+                Some(lines) => {
+                    for (i, (name, mv)) in [("D+", lines.plus_mv), ("D-", lines.minus_mv)]
+                        .into_iter()
+                        .enumerate()
+                    {
+                        let mut row: String<DIAGNOSTICS_WIDTH> = String::new();
+                        write!(row, "{:<6}{}  ", name, fixed_milli(mv as i32, 3, 12)).ok();
+
+                        self.render_diagnostics_row(&row, i + 1, COLOR_TEXT).await?;
+                    }
+
+                    let mut row: String<DIAGNOSTICS_WIDTH> = String::new();
+                    write!(row, "{:<6}{:<14}", "type", Signature::of(lines).as_str()).ok();
+
+                    self.render_diagnostics_row(&row, 3, COLOR_TEXT).await?;
+                }
+                None => {
+                    self.render_diagnostics_row("no D+/D- inputs", 1, COLOR_TEXT_DISABLED)
+                        .await?;
+                }
+            },
+            DiagnosticsView::CcLines => match *CC_LINES_MUTEX.lock().await {
+                Some(lines) => {
+                    for (i, (name, mv)) in [("CC1", lines.cc1_mv), ("CC2", lines.cc2_mv)]
+                        .into_iter()
+                        .enumerate()
+                    {
+                        let mut row: String<DIAGNOSTICS_WIDTH> = String::new();
+                        write!(
+                            row,
+                            "{:<5}{}  {:<7}",
+                            name,
+                            fixed_milli(mv as i32, 3, 6),
+                            Rp::of(mv).as_str()
+                        )
+                        .ok();
+
+                        self.render_diagnostics_row(&row, i + 1, COLOR_TEXT).await?;
+                    }
+
+                    let mut row: String<DIAGNOSTICS_WIDTH> = String::new();
+                    write!(row, "{:<7}{:<13}", "plug", lines.orientation().as_str()).ok();
+
+                    self.render_diagnostics_row(&row, 3, COLOR_TEXT).await?;
+                }
+                None => {
+                    self.render_diagnostics_row("no CC inputs", 1, COLOR_TEXT_DISABLED)
+                        .await?;
+                }
+            },
+            DiagnosticsView::Output => match *OUTPUT_SENSE_MUTEX.lock().await {
+                Some(report) => {
+                    let on_off = |on: bool| if on { "on" } else { "off" };
+
+                    for (i, (name, value)) in [
+                        ("cmd", on_off(report.commanded)),
+                        ("sense", on_off(report.sensed)),
+                    ]
+                    .into_iter()
+                    .enumerate()
+                    {
+                        let mut row: String<DIAGNOSTICS_WIDTH> = String::new();
+                        write!(row, "{:<7}{:<13}", name, value).ok();
+
+                        self.render_diagnostics_row(&row, i + 1, COLOR_TEXT).await?;
+                    }
+
+                    let color = match report.health {
+                        SenseHealth::Ok => COLOR_TEXT,
+                        _ => COLOR_ERROR,
+                    };
+                    let mut row: String<DIAGNOSTICS_WIDTH> = String::new();
+                    write!(row, "{:<7}{:<13}", "state", report.health.as_str()).ok();
+                    self.render_diagnostics_row(&row, 3, color).await?;
+
+                    let mut row: String<DIAGNOSTICS_WIDTH> = String::new();
+                    write!(row, "{:<7}{:<13}", "faults", report.faults).ok();
+                    self.render_diagnostics_row(&row, 4, COLOR_TEXT).await?;
+                }
+                None => {
+                    self.render_diagnostics_row("no output sense", 1, COLOR_TEXT_DISABLED)
+                        .await?;
+                }
+            },
+            DiagnosticsView::Rails => match (*VBUS_MUTEX.lock().await, self.volts) {
+                (Some(source), Some(load)) => {
+                    let rails = Rails {
+                        contract: self.status_info.target_volts,
+                        source,
+                        load,
+                    };
+
+                    // The output itself is on the monitor page.
+                    for (i, (name, volts)) in [
+                        ("pdo", rails.contract),
+                        ("vbus", rails.source),
+                        ("cable", rails.cable_drop()),
+                        ("switch", rails.switch_drop()),
+                    ]
+                    .into_iter()
+                    .enumerate()
+                    {
+                        let mut row: String<DIAGNOSTICS_WIDTH> = String::new();
+                        write!(
+                            row,
+                            "{:<6}{}  ",
+                            name,
+                            fixed_milli(units::milli(volts), 3, 12)
+                        )
+                        .ok();
+
+                        self.render_diagnostics_row(&row, i + 1, COLOR_TEXT).await?;
+                    }
+
+                    // What the switch dissipates, next to the temperature of the MCU beside it, see
+                    // `thermal.rs`.
+                    if let Some(amps) = self.amps {
+                        let watts = rails.switch_watts(amps);
+                        let limit = *SWITCH_LIMIT_MUTEX.lock().await;
+                        let color = if limit > ZERO && watts > limit {
+                            COLOR_ERROR
+                        } else {
+                            COLOR_TEXT
+                        };
+
+                        let mut row: String<DIAGNOSTICS_WIDTH> = String::new();
+                        write!(
+                            row,
+                            "{:<5}{}W",
+                            "loss",
+                            fixed_milli(units::milli(watts), 3, 8)
+                        )
+                        .ok();
+                        match *FAN_STATUS_MUTEX.lock().await {
+                            Some(fan) => write!(row, " {:>4}C", fan.celsius).ok(),
+                            None => write!(row, "{:<6}", "").ok(),
+                        };
+
+                        self.render_diagnostics_row(&row, 5, color).await?;
+                    }
+                }
+                (None, _) => {
+                    self.render_diagnostics_row("no VBUS input", 1, COLOR_TEXT_DISABLED)
+                        .await?;
+                }
+                (Some(_), None) => {
+                    self.render_diagnostics_row("no output reading", 1, COLOR_TEXT_DISABLED)
+                        .await?;
+                }
+            },
+            DiagnosticsView::Resets => {
+                let counts = *RESET_COUNTS_MUTEX.lock().await;
+
+                let mut row: String<DIAGNOSTICS_WIDTH> = String::new();
+                let last = counts.last.map_or("none", |cause| cause.as_str());
+                write!(row, "{:<7}{:<13}", "last", last).ok();
+
+                let color = match counts.last {
+                    Some(cause) if cause.is_abnormal() => COLOR_ERROR,
+                    _ => COLOR_TEXT,
+                };
+                self.render_diagnostics_row(&row, 1, color).await?;
+
+                // Two causes to a row.
+                for (i, pair) in RESET_CAUSES.chunks(2).enumerate() {
+                    let mut row: String<DIAGNOSTICS_WIDTH> = String::new();
+
+                    for cause in pair {
+                        write!(row, "{:<5}{:>4} ", cause.abbr(), counts.count(*cause)).ok();
+                    }
+                    write!(row, "{:<1$}", "", DIAGNOSTICS_WIDTH - row.len()).ok();
+
+                    self.render_diagnostics_row(&row, i + 2, COLOR_TEXT).await?;
+                }
+            }
+            DiagnosticsView::Memory => match stack::usage() {
+                Some(usage) => {
+                    let mut row: String<DIAGNOSTICS_WIDTH> = String::new();
+                    write!(row, "{:<7}{:>6}{:>7}", "stack", usage.peak, usage.size).ok();
+                    self.render_diagnostics_row(&row, 1, COLOR_TEXT).await?;
+
+                    let mut row: String<DIAGNOSTICS_WIDTH> = String::new();
+                    write!(row, "{:<7}{:>6}{:>7}", "free", usage.free(), "").ok();
+                    self.render_diagnostics_row(&row, 2, COLOR_TEXT).await?;
+
+                    let mut row: String<DIAGNOSTICS_WIDTH> = String::new();
+                    write!(row, "{:<7}{:>6}{:>7}", "static", usage.statics, "").ok();
+                    self.render_diagnostics_row(&row, 3, COLOR_TEXT).await?;
+                }
+                None => {
+                    self.render_diagnostics_row("no stack paint", 1, COLOR_TEXT_DISABLED)
+                        .await?;
+                }
+            },
+        }
+
+        Ok(())
+    }
+
+    /// The two readings, the resistance estimated from them and which buttons take them.
+    async fn render_cable(&mut self) -> Result<(), DisplayError> {
+        self.diagnostics_at = Instant::now() + DIAGNOSTICS_INTERVAL;
+
+        let cable = *CABLE_MUTEX.lock().await;
+
+        self.render_diagnostics_row("load   volts    amps", 0, COLOR_INFO)
+            .await?;
+
+        for (i, reading) in cable.readings().iter().enumerate() {
+            let mut row: String<DIAGNOSTICS_WIDTH> = String::new();
+            write!(row, "{:<4}", i + 1).ok();
+
+            match reading {
+                Some(reading) => write!(
+                    row,
+                    "{}{}",
+                    fixed(reading.volts, 3, 8),
+                    fixed(reading.amps, 3, 8)
+                )
+                .ok(),
+                None => write!(row, "{:<16}", "none").ok(),
+            };
+
+            self.render_diagnostics_row(&row, i + 1, COLOR_TEXT).await?;
+        }
+
+        let mut row: String<DIAGNOSTICS_WIDTH> = String::new();
+        let color = match cable.resistance() {
+            Ok(ohms) => {
+                write!(row, "{:<12}{}", "ohms", fixed(ohms, 3, 8)).ok();
+                COLOR_TEXT
+            }
+            Err(err) => {
+                write!(row, "{:<20}", err.as_str()).ok();
+                COLOR_TEXT_DISABLED
+            }
+        };
+        self.render_diagnostics_row(&row, 3, color).await?;
+
+        self.render_diagnostics_row("Up add   Down clear", 5, COLOR_TEXT_DISABLED)
+            .await
+    }
+
+    /// State of the SD card log, the file being written and how far it got.
+    async fn render_storage(&mut self) -> Result<(), DisplayError> {
+        self.diagnostics_at = Instant::now() + DIAGNOSTICS_INTERVAL;
+
+        let Some(status) = *SD_LOG_MUTEX.lock().await else {
+            return self
+                .render_diagnostics_row("no SD card slot", 0, COLOR_TEXT_DISABLED)
+                .await;
+        };
+
+        let color = match status.state {
+            CardState::Logging => COLOR_TEXT,
+            CardState::Error => COLOR_ERROR,
+            CardState::NoCard | CardState::Unmounted => COLOR_TEXT_DISABLED,
+        };
+
+        let mut row: String<DIAGNOSTICS_WIDTH> = String::new();
+        write!(row, "{:<10}{:>10}", "state", status.state.as_str()).ok();
+        self.render_diagnostics_row(&row, 0, color).await?;
+
+        row.clear();
+        write!(row, "{:<9}{:>11}", "file", file_name(status.file.index)).ok();
+        self.render_diagnostics_row(&row, 1, COLOR_TEXT).await?;
+
+        row.clear();
+        write!(row, "{:<12}{:>5} kB", "size", status.file.bytes / 1024).ok();
+        self.render_diagnostics_row(&row, 2, COLOR_TEXT).await?;
+
+        row.clear();
+        write!(row, "{:<10}{:>10}", "dropped", status.dropped).ok();
+        self.render_diagnostics_row(&row, 3, COLOR_TEXT).await?;
+
+        self.render_diagnostics_row("Up mount  Down eject", 5, COLOR_TEXT_DISABLED)
+            .await
+    }
+
+    /// The running or last test session: its state and duration, the lowest, mean and highest
+    /// volts and amps, the energy and the faults, and the share of the time near the OCP. The
+    /// watts are left to `session report` for want of rows.
+    async fn render_session(&mut self) -> Result<(), DisplayError> {
+        self.diagnostics_at = Instant::now() + DIAGNOSTICS_INTERVAL;
+
+        let (running, report) = {
+            let session = SESSION_MUTEX.lock().await;
+            (session.is_running(), session.report())
+        };
+
+        let Some(report) = report else {
+            self.render_diagnostics_row("no session yet", 0, COLOR_TEXT_DISABLED)
+                .await?;
+            return self
+                .render_diagnostics_row("Up start  Down stop", 5, COLOR_TEXT_DISABLED)
+                .await;
+        };
+
+        let (hours, minutes, seconds) = session::hms(report.duration_ms);
+        let mut row: String<DIAGNOSTICS_WIDTH> = String::new();
+        write!(
+            row,
+            "{:<8}{:>6}:{:02}:{:02}",
+            if running { "running" } else { "stopped" },
+            hours,
+            minutes,
+            seconds
+        )
+        .ok();
+        self.render_diagnostics_row(&row, 0, if running { COLOR_TEXT } else { COLOR_INFO })
+            .await?;
+
+        self.render_diagnostics_row("     min   avg   max", 1, COLOR_INFO)
+            .await?;
+
+        for (i, (name, extent)) in [("V", report.volts), ("A", report.amps)]
+            .into_iter()
+            .enumerate()
+        {
+            row.clear();
+            match extent.summary() {
+                Some((min, mean, max)) => write!(
+                    row,
+                    "{:<2}{}{}{}",
+                    name,
+                    fixed(min, 2, 6),
+                    fixed(mean, 2, 6),
+                    fixed(max, 2, 6)
+                )
+                .ok(),
+                None => write!(row, "{:<2}{:<18}", name, "none").ok(),
+            };
+            self.render_diagnostics_row(&row, i + 2, COLOR_TEXT).await?;
+        }
+
+        row.clear();
+        write!(
+            row,
+            "Wh{} faults{:>5}",
+            fixed(units::energy_wh(report.energy), 3, 6),
+            report.faults
+        )
+        .ok();
+        let color = if report.faults > 0 {
+            COLOR_ERROR
+        } else {
+            COLOR_TEXT
+        };
+        self.render_diagnostics_row(&row, 4, color).await?;
+
+        row.clear();
+        write!(
+            row,
+            ">{}%{:>4}%  >{}%{:>4}%",
+            OCP_BANDS_PERCENT[0],
+            report.above_ocp_percent(0),
+            OCP_BANDS_PERCENT[1],
+            report.above_ocp_percent(1)
+        )
+        .ok();
+        self.render_diagnostics_row(&row, 5, COLOR_TEXT).await
+    }
+
+    /// Capture state and threshold, the full scale, and the held window as a bar graph with the
+    /// trigger marked below it. The graph is only redrawn when another window is held.
+    async fn render_capture(&mut self) -> Result<(), DisplayError> {
+        self.diagnostics_at = Instant::now() + DIAGNOSTICS_INTERVAL;
+
+        let mut heights = [0u8; CAPTURE_LEN];
+
+        let capture = CAPTURE_MUTEX.lock().await;
+        let state = capture.state();
+        let threshold = capture.threshold();
+        let scale = capture.scale();
+        let trigger_at = capture.trigger_at();
+        let held = match state {
+            CaptureState::Held => Some(capture.count()),
+            _ => None,
+        };
+
+        if held.is_some() && held != self.capture_shown {
+            let full = units::milli(scale).max(1) as i64;
+
+            for (height, amps) in heights.iter_mut().zip(capture.samples()) {
+                let amps = (units::milli(amps) as i64).clamp(0, full);
+                *height = (amps * GRAPH_HEIGHT as i64 / full) as u8;
+            }
+        }
+        drop(capture);
+
+        let mut row: String<DIAGNOSTICS_WIDTH> = String::new();
+        write!(row, "{:<6}trig{}A", state.as_str(), fixed(threshold, 2, 9)).ok();
+        self.render_diagnostics_row(&row, 0, COLOR_INFO).await?;
+
+        row.clear();
+        write!(row, "{:<10}{}A", "scale", fixed(scale, 2, 9)).ok();
+        self.render_diagnostics_row(&row, 1, COLOR_TEXT_DISABLED)
+            .await?;
+
+        if held != self.capture_shown {
+            // A window that is no longer held is cleared by drawing empty bars.
+            self.render_capture_graph(&heights, held.and(trigger_at))
+                .await?;
+
+            let top = fixed(scale, 1, 4);
+            Self::render_mono(
+                &mut self.st7789,
+                &top,
+                &FONT_6X10,
+                0,
+                GRAPH_Y,
+                COLOR_TEXT_DISABLED,
+                COLOR_BACKGROUND,
+            )
+            .await?;
+            Self::render_mono(
+                &mut self.st7789,
+                "   0",
+                &FONT_6X10,
+                0,
+                GRAPH_Y + GRAPH_HEIGHT as u16 - 10,
+                COLOR_TEXT_DISABLED,
+                COLOR_BACKGROUND,
+            )
+            .await?;
+            self.capture_shown = held;
+        }
+
+        Ok(())
+    }
+
+    /// Draws the bars in 8-pixel wide strips, bypassing the screen model.
+    async fn render_capture_graph(
+        &mut self,
+        heights: &[u8; CAPTURE_LEN],
+        trigger_at: Option<usize>,
+    ) -> Result<(), DisplayError> {
+        SCREEN_MUTEX.lock().await.untracked();
+
+        for (strip, samples) in heights.chunks(STRIP_SAMPLES).enumerate() {
+            let mut bitmap = [0u8; GRAPH_ROWS];
+
+            for (i, height) in samples.iter().enumerate() {
+                let mask = 0b1100_0000 >> (i * 2);
+
+                for row in bitmap[GRAPH_HEIGHT - *height as usize..GRAPH_HEIGHT].iter_mut() {
+                    *row |= mask;
+                }
+
+                if trigger_at == Some(strip * STRIP_SAMPLES + i) {
+                    for row in bitmap[GRAPH_HEIGHT + 2..].iter_mut() {
+                        *row |= mask;
+                    }
+                }
+            }
+
+            self.st7789
+                .write_area(
+                    GRAPH_X + (strip * 8) as u16,
+                    GRAPH_Y,
+                    8,
+                    &bitmap,
+                    COLOR_AMPERAGE,
+                    COLOR_BACKGROUND,
+                )
+                .await
+                .map_err(|_| DisplayError::Write)?;
+        }
+
+        Ok(())
+    }
+
+    async fn render_diagnostics_row(
+        &mut self,
+        row: &str,
+        index: usize,
+        color: Rgb565,
+    ) -> Result<(), DisplayError> {
+        Self::render_status(
+            &mut self.st7789,
+            row,
+            0,
+            10 + index as u16 * 26,
+            COLOR_BACKGROUND,
+            color,
+            row.len() as u16,
+        )
+        .await
+    }
+
+    async fn render_voltage_layout(&mut self, selected: SrcPdo) -> Result<(), DisplayError> {
+        info!("selected: {:?}", selected);
+
+        let available_volt_curr = AVAILABLE_VOLT_CURR_MUTEX.lock().await;
+
+        let offset = VOLTAGE_ITEMS
+            .iter()
+            .enumerate()
+            .find(|(_, ele)| **ele == selected)
+            .map(|(i, _)| i)
+            .unwrap_or(0);
+
+        Self::render_scrollbar(
+            &mut self.st7789,
+            PDO_SCROLLBAR_X,
+            PDO_SCROLLBAR_Y,
+            PDO_SCROLLBAR_LEN,
+            offset,
+            VOLTAGE_ITEMS.len(),
+        )
+        .await?;
+
+        for i in 0..VOLTAGE_ITEMS.len().min(5) {
+            let idx = (offset + i + VOLTAGE_ITEMS.len() - 2) % VOLTAGE_ITEMS.len();
+            let item = VOLTAGE_ITEMS[idx];
 
             let (color, bg_color) = if item == selected {
                 (COLOR_PRIMARY_CONTENT, COLOR_PRIMARY)
@@ -447,8 +2407,9 @@ where
                 _ => "MISSING",
             };
 
+            // Below the header.
             let x = 170;
-            let y = (i as u16) * 38;
+            let y = 10 + (i as u16) * 30;
 
             Self::render_status(
                 &mut self.st7789,
@@ -459,57 +2420,584 @@ where
                 color,
                 text.len() as u16,
             )
-            .await;
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn render_output_layout(&mut self) -> Result<(), DisplayError> {
+        let selected = *OUTPUT_MODE_MUTEX.lock().await;
+
+        for (i, mode) in [OutputMode::Latching, OutputMode::Momentary]
+            .iter()
+            .enumerate()
+        {
+            let (color, bg_color) = if *mode == selected {
+                (COLOR_PRIMARY_CONTENT, COLOR_PRIMARY)
+            } else {
+                (COLOR_TEXT, COLOR_BACKGROUND)
+            };
+
+            let text = match mode {
+                OutputMode::Latching => " Toggle ",
+                OutputMode::Momentary => " Hold   ",
+            };
+
+            Self::render_status(
+                &mut self.st7789,
+                text,
+                170,
+                38 + (i as u16) * 38,
+                bg_color,
+                color,
+                text.len() as u16,
+            )
+            .await?;
         }
+
+        Ok(())
+    }
+
+    async fn render_average_layout(&mut self) -> Result<(), DisplayError> {
+        let selected = *AVERAGE_INTERVAL_MUTEX.lock().await;
+
+        for (i, interval) in AVERAGE_INTERVALS.iter().enumerate() {
+            let (color, bg_color) = if *interval == selected {
+                (COLOR_PRIMARY_CONTENT, COLOR_PRIMARY)
+            } else {
+                (COLOR_TEXT, COLOR_BACKGROUND)
+            };
+
+            let text = match interval {
+                AverageInterval::Off => " Off    ",
+                AverageInterval::OneSecond => " 1 s    ",
+                AverageInterval::TenSeconds => " 10 s   ",
+                AverageInterval::OneMinute => " 1 min  ",
+            };
+
+            Self::render_status(
+                &mut self.st7789,
+                text,
+                170,
+                10 + (i as u16) * 38,
+                bg_color,
+                color,
+                text.len() as u16,
+            )
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn render_watts_layout(&mut self) -> Result<(), DisplayError> {
+        let selected = *WATTS_SOURCE_MUTEX.lock().await;
+
+        for (i, source) in WATTS_SOURCES.iter().enumerate() {
+            let (color, bg_color) = if *source == selected {
+                (COLOR_PRIMARY_CONTENT, COLOR_PRIMARY)
+            } else {
+                (COLOR_TEXT, COLOR_BACKGROUND)
+            };
+
+            let text = match source {
+                WattsSource::Register => " INA226 ",
+                WattsSource::Product => " V x I  ",
+                WattsSource::Synced => " Synced ",
+                WattsSource::Peak => " Peak   ",
+            };
+
+            Self::render_status(
+                &mut self.st7789,
+                text,
+                170,
+                10 + (i as u16) * 38,
+                bg_color,
+                color,
+                text.len() as u16,
+            )
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    /// The threshold being entered, the digit Up counts up highlighted, and what 0 means for it.
+    async fn render_entry_layout(
+        &mut self,
+        label: &str,
+        unit: &str,
+        zero: &str,
+    ) -> Result<(), DisplayError> {
+        let entry = *ENTRY_MUTEX.lock().await;
+
+        Self::render_status(
+            &mut self.st7789,
+            label,
+            170,
+            10,
+            COLOR_BACKGROUND,
+            COLOR_BASE,
+            label.len() as u16,
+        )
+        .await?;
+
+        let [tens, ones, tenths, hundredths] = entry.digits();
+        let mut text: String<5> = String::new();
+        write!(text, "{}{}.{}{}", tens, ones, tenths, hundredths).ok();
+
+        // The decimals sit past the point.
+        let cursor = match entry.cursor() {
+            c if c < 2 => c,
+            c => c + 1,
+        };
+
+        let font = &GROTESK_24_48;
+        let mut x = 170;
+
+        for (i, c) in text.chars().enumerate() {
+            let (color, bg_color) = if i == cursor {
+                (COLOR_PRIMARY_CONTENT, COLOR_PRIMARY)
+            } else {
+                (COLOR_TEXT, COLOR_BACKGROUND)
+            };
+
+            Self::write_area(
+                &mut self.st7789,
+                x,
+                50,
+                font.width,
+                font.glyph(c),
+                color,
+                bg_color,
+            )
+            .await?;
+
+            x += font.width;
+        }
+
+        Self::render_status(
+            &mut self.st7789,
+            unit,
+            x + 4,
+            74,
+            COLOR_BACKGROUND,
+            COLOR_BASE,
+            unit.len() as u16,
+        )
+        .await?;
+
+        if entry.milli() == 0 {
+            Self::render_status(
+                &mut self.st7789,
+                zero,
+                170,
+                110,
+                COLOR_BACKGROUND,
+                COLOR_TEXT_DISABLED,
+                zero.len() as u16,
+            )
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Each option with its value, the one Up and Down change highlighted.
+    async fn render_display_layout(&mut self, selected: DisplayItem) -> Result<(), DisplayError> {
+        let direction = *DISPLAY_DIRECTION_MUTEX.lock().await;
+        let theme = *THEME_MUTEX.lock().await;
+        let backlight = *BACKLIGHT_MUTEX.lock().await;
+        let timeout = *BACKLIGHT_TIMEOUT_MUTEX.lock().await;
+
+        for (i, item) in DISPLAY_ITEMS.iter().enumerate() {
+            let (color, bg_color) = if *item == selected {
+                (COLOR_PRIMARY_CONTENT, COLOR_PRIMARY)
+            } else {
+                (COLOR_TEXT, COLOR_BACKGROUND)
+            };
+
+            let mut text: String<9> = String::new();
+            match item {
+                DisplayItem::Rotation => {
+                    let degrees = match direction {
+                        Direction::Normal => 0,
+                        Direction::Reversed => 180,
+                    };
+                    write!(text, "Rot{:>6}", degrees).ok()
+                }
+                DisplayItem::Theme => {
+                    let dark = match theme {
+                        Theme::Light => "off",
+                        Theme::Dark => "on",
+                    };
+                    write!(text, "Dark{:>5}", dark).ok()
+                }
+                DisplayItem::Brightness => write!(text, "Light{:>4}", backlight).ok(),
+                DisplayItem::Timeout if timeout == 0 => write!(text, "Dim never").ok(),
+                DisplayItem::Timeout => write!(text, "Dim{:>5}s", timeout.min(9_999)).ok(),
+            };
+
+            Self::render_status(
+                &mut self.st7789,
+                &text,
+                170,
+                10 + (i as u16) * 38,
+                bg_color,
+                color,
+                text.len() as u16,
+            )
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    /// The averaging and the conversion time with the one Up and Down change highlighted, and the
+    /// rate of readings they make below.
+    async fn render_measurement_layout(
+        &mut self,
+        selected: MeasurementItem,
+    ) -> Result<(), DisplayError> {
+        let conversion = *CONVERSION_MUTEX.lock().await;
+
+        for (i, item) in MEASUREMENT_ITEMS.iter().enumerate() {
+            let (color, bg_color) = if *item == selected {
+                (COLOR_PRIMARY_CONTENT, COLOR_PRIMARY)
+            } else {
+                (COLOR_TEXT, COLOR_BACKGROUND)
+            };
+
+            let mut text: String<9> = String::new();
+            match item {
+                MeasurementItem::Averaging => {
+                    write!(text, "Avg{:>6}", conversion.averaging.count()).ok()
+                }
+                MeasurementItem::ConversionTime => {
+                    write!(text, "Us{:>7}", conversion.time.micros()).ok()
+                }
+            };
+
+            Self::render_status(
+                &mut self.st7789,
+                &text,
+                170,
+                10 + (i as u16) * 38,
+                bg_color,
+                color,
+                text.len() as u16,
+            )
+            .await?;
+        }
+
+        let mut text: String<9> = String::new();
+        write!(
+            text,
+            "{}Hz",
+            fixed_milli(conversion.rate_millihertz() as i32, 2, 7)
+        )
+        .ok();
+        Self::render_status(
+            &mut self.st7789,
+            &text,
+            170,
+            10 + (MEASUREMENT_ITEMS.len() as u16) * 38,
+            COLOR_BACKGROUND,
+            COLOR_TEXT_DISABLED,
+            text.len() as u16,
+        )
+        .await
+    }
+
+    /// The date and time being entered, UTC, with the field Up and Down change highlighted.
+    async fn render_clock_layout(&mut self, selected: ClockField) -> Result<(), DisplayError> {
+        let time = *CLOCK_ENTRY_MUTEX.lock().await;
+
+        for (i, field) in CLOCK_FIELDS.iter().enumerate() {
+            let (color, bg_color) = if *field == selected {
+                (COLOR_PRIMARY_CONTENT, COLOR_PRIMARY)
+            } else {
+                (COLOR_TEXT, COLOR_BACKGROUND)
+            };
+
+            let value = time.of(*field);
+            let mut text: String<9> = String::new();
+            match field {
+                ClockField::Year => write!(text, "Year {:04}", value).ok(),
+                ClockField::Month => write!(text, "Month  {:02}", value).ok(),
+                ClockField::Day => write!(text, "Day    {:02}", value).ok(),
+                ClockField::Hour => write!(text, "Hour   {:02}", value).ok(),
+                ClockField::Minute => write!(text, "Min    {:02}", value).ok(),
+            };
+
+            Self::render_status(
+                &mut self.st7789,
+                &text,
+                170,
+                10 + (i as u16) * 30,
+                bg_color,
+                color,
+                text.len() as u16,
+            )
+            .await?;
+        }
+
+        Ok(())
     }
 
     pub async fn task(&mut self) {
         let page = self.page_pubsub.try_next_message_pure();
 
         if let Some(page) = page {
+            if page != self.page {
+                telemetry::send_page(page);
+            }
             self.page = page;
+        }
 
+        let direction = self.direction_sub.try_next_message_pure();
+        if let Some(direction) = direction {
+            self.direction = direction;
+        }
+
+        let theme = self.theme_sub.try_next_message_pure();
+        if let Some(theme) = theme {
+            self.theme = theme;
+        }
+
+        if self.error.is_some() {
+            if Instant::now() >= self.reinit_at {
+                info!("reinitializing display");
+                self.init().await.ok();
+            }
+            return;
+        }
+
+        if direction.is_some() || theme.is_some() {
+            let result = self.configure().await;
+            self.check(result).await;
+        }
+
+        // The frame memory does not turn with the panel, so after a new direction the old picture
+        // shows upside down. It is wiped a band per pass, with the readings still drawn over the
+        // cleared part, and the page is drawn again once it is gone.
+        if direction.is_some() {
+            self.fields.invalidate();
+            self.status_bar.invalidate();
+            SCREEN_MUTEX.lock().await.untracked();
+            self.flip = Some(0);
+        }
+
+        if let Some(row) = self.flip {
+            self.wipe(row).await;
+        } else if page.is_some() {
             self.update_layout().await;
+        } else if let Some(step) = self.refresh {
+            self.refresh_step(step).await;
+        } else if Instant::now() >= self.refresh_at {
+            self.refresh = Some(0);
+        } else if Instant::now() >= self.diagnostics_at {
+            let result = match self.page {
+                Page::Diagnostics(view) => self.render_diagnostics(view).await,
+                Page::Cable => self.render_cable().await,
+                Page::Storage => self.render_storage().await,
+                Page::Capture => self.render_capture().await,
+                Page::Session => self.render_session().await,
+                _ => Ok(()),
+            };
+            self.check(result).await;
+        }
+
+        if self.fault_sub.try_next_message_pure().is_some() {
+            let faults = *FAULTS_MUTEX.lock().await;
+            self.update_faults(faults).await;
+        }
+
+        let status = *SYSTEM_STATUS_MUTEX.lock().await;
+        if status != self.system_status {
+            self.update_status_bar(status).await;
         }
     }
 
-    async fn render_monitor(
+    /// Draws one part of the screen again for the periodic refresh. The fields are invalidated and
+    /// rendered from what they last showed, so every one of their cells is repainted.
+    async fn refresh_step(&mut self, step: usize) {
+        let monitor = matches!(self.page, Page::Monitor);
+
+        match REFRESH_STEPS[step] {
+            RefreshStep::Page => {
+                let result = self.render_page().await;
+                self.check(result).await;
+            }
+            RefreshStep::Readings if monitor => {
+                self.fields.volts.invalidate();
+                self.fields.amps.invalidate();
+                self.fields.watts.invalidate();
+                self.fields.watts_unit.invalidate();
+
+                self.update_monitor_volts(self.volts).await;
+                self.update_monitor_amps(self.amps).await;
+                self.update_monitor_watts(self.watts).await;
+                self.update_watts_source(self.watts_source).await;
+            }
+            RefreshStep::Details if monitor => {
+                self.fields.average_volts.invalidate();
+                self.fields.average_amps.invalidate();
+                self.fields.average_watts.invalidate();
+                self.fields.rms_amps.invalidate();
+                self.fields.contract_amps.invalidate();
+                self.fields.contract_watts.invalidate();
+
+                self.update_average(self.average).await;
+                self.update_rms(self.rms).await;
+                self.update_utilization(self.utilization, self.utilization_warning)
+                    .await;
+            }
+            RefreshStep::Contract if monitor => {
+                self.fields.pdo.invalidate();
+                self.fields.target_volts.invalidate();
+                self.fields.limit_amps.invalidate();
+                self.fields.output.invalidate();
+                self.fields.trip_hint.invalidate();
+
+                self.update_pdo_label().await;
+                self.update_target_volts(self.status_info.target_volts)
+                    .await;
+                self.update_limit_amps(self.status_info.limit_amps).await;
+                self.update_tripped(self.tripped).await;
+            }
+            RefreshStep::Indicators if monitor => {
+                self.fields.faults.invalidate();
+                self.fields.wifi.invalidate();
+                self.fields.remote.invalidate();
+                self.fields.schedule.invalidate();
+
+                self.update_faults(self.faults).await;
+                self.update_wifi(self.wifi).await;
+                self.update_remote(self.remote).await;
+                self.update_schedule(self.schedule).await;
+            }
+            RefreshStep::StatusBar => {
+                self.status_bar.invalidate();
+                self.update_status_bar(self.system_status).await;
+            }
+            _ => {}
+        }
+
+        if step + 1 < REFRESH_STEPS.len() {
+            self.refresh = Some(step + 1);
+        } else {
+            self.refresh = None;
+            self.refresh_at = Instant::now() + REFRESH_INTERVAL;
+        }
+    }
+
+    /// Clears the next band of the flip wipe, and draws the page after the last one.
+    async fn wipe(&mut self, row: u16) {
+        let rows = FLIP_ROWS.min(PANEL_HEIGHT - row);
+        let result = self
+            .st7789
+            .fill_area(0, row, PANEL_WIDTH, rows, COLOR_BACKGROUND)
+            .await
+            .map_err(|_| DisplayError::Write);
+        self.check(result).await;
+
+        if self.error.is_some() {
+            // `init` draws the page once the panel is back.
+            self.flip = None;
+        } else if row + rows < PANEL_HEIGHT {
+            self.flip = Some(row + rows);
+        } else {
+            self.flip = None;
+            self.update_layout().await;
+        }
+    }
+
+    /// Records a failed transfer so rendering stops until the panel is re-initialized.
+    async fn check(&mut self, result: Result<(), DisplayError>) {
+        let Err(err) = result else {
+            return;
+        };
+
+        if self.error.is_none() {
+            warn!("display {}", err.as_str());
+            fault::report(Fault::Display).await;
+        }
+
+        self.error = Some(err);
+        self.reinit_at = Instant::now() + REINIT_INTERVAL;
+    }
+
+    /// `value` with `decimals` places and `unit` right after it, right-aligned in `field`. Decimals
+    /// are dropped when the number would not fit otherwise.
+    async fn render_number<const N: usize>(
         st7789: &mut ST7789<SPI, DC, RST>,
-        curr: &str,
-        prev: &str,
-        y: u16,
-        bg_color: Rgb565,
+        field: &mut TextField<N>,
+        value: Value,
+        decimals: u8,
+        unit: &str,
         color: Rgb565,
-        force_render: bool,
-    ) {
-        let mut chars = curr.chars();
-        let mut chars_prev = prev.chars();
+    ) -> Result<(), DisplayError> {
+        let mut text: String<N> = String::new();
+        text.push_str(fixed(value, decimals, N - unit.len()).trim_start())
+            .ok();
+        text.push_str(unit).ok();
 
-        for idx in 0..7 {
-            let char = chars.next();
-            if char == chars_prev.next() {
-                if !force_render {
-                    continue;
-                }
+        Self::render_field(st7789, field, &text, color).await
+    }
+
+    /// A monitor reading, or [`MISSING_READING`] for one that could not be taken.
+    async fn render_reading<const N: usize>(
+        st7789: &mut ST7789<SPI, DC, RST>,
+        field: &mut TextField<N>,
+        value: Option<Value>,
+        color: Rgb565,
+    ) -> Result<(), DisplayError> {
+        match value {
+            Some(value) => {
+                Self::render_number(st7789, field, value, MONITOR_DECIMALS, "", color).await
             }
+            None => Self::render_field(st7789, field, MISSING_READING, color).await,
+        }
+    }
 
-            let char = match char {
-                Some(c) => c,
-                None => '0',
-            };
+    /// Repaints the cells of `field` that differ from `text` in `color`.
+    async fn render_field<const N: usize>(
+        st7789: &mut ST7789<SPI, DC, RST>,
+        field: &mut TextField<N>,
+        text: &str,
+        color: Rgb565,
+    ) -> Result<(), DisplayError> {
+        let width = field.font.width();
+        let advance = field.font.advance();
+        let cells = field.layout(text);
+        let mut result = Ok(());
+
+        for (idx, char) in field.changes(&cells, color) {
+            result = Self::write_area(
+                st7789,
+                field.x + idx * advance,
+                field.y,
+                width,
+                field.font.glyph(char),
+                color,
+                COLOR_BACKGROUND,
+            )
+            .await;
 
-            st7789
-                .write_area(
-                    10 + idx * 24,
-                    y,
-                    24,
-                    GROTESK_24_48[get_index_by_char(GROTESK_24_48_INDEX, char)],
-                    color,
-                    bg_color,
-                )
-                .await
-                .unwrap();
+            if result.is_err() {
+                break;
+            }
         }
+
+        match result {
+            Ok(_) => field.set(cells, color),
+            // Part of it may have been drawn.
+            Err(_) => field.invalidate(),
+        }
+
+        result
     }
 
     async fn render_status(
@@ -520,7 +3008,7 @@ where
         bg_color: Rgb565,
         color: Rgb565,
         len: u16,
-    ) {
+    ) -> Result<(), DisplayError> {
         let mut chars = curr.chars();
 
         for idx in 0..len {
@@ -531,17 +3019,108 @@ where
                 None => '0',
             };
 
-            st7789
-                .write_area(
-                    x + idx * 16,
-                    y,
-                    16,
-                    ARIAL_ROUND_16_24[get_index_by_char(ARIAL_ROUND_16_24_INDEX, char)],
-                    color,
-                    bg_color,
-                )
-                .await
-                .unwrap();
+            Self::write_area(
+                st7789,
+                x + idx * 16,
+                y,
+                16,
+                ARIAL_ROUND_16_24.glyph(char),
+                color,
+                bg_color,
+            )
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Draws `text` in an `embedded-graphics` font, a glyph per `write_area` like the built-in
+    /// fonts.
+    async fn render_mono(
+        st7789: &mut ST7789<SPI, DC, RST>,
+        text: &str,
+        font: &'static MonoFont<'static>,
+        x: u16,
+        y: u16,
+        color: Rgb565,
+        bg_color: Rgb565,
+    ) -> Result<(), DisplayError> {
+        let width = font.character_size.width as u16;
+        let advance = width + font.character_spacing as u16;
+
+        for (idx, c) in text.chars().enumerate() {
+            Self::write_area(
+                st7789,
+                x + idx as u16 * advance,
+                y,
+                width,
+                Bitmap::Mono(font, c),
+                color,
+                bg_color,
+            )
+            .await?;
         }
+
+        Ok(())
+    }
+
+    /// A 2 pixel wide scrollbar `len` rows long, a multiple of four, with the thumb at item
+    /// `index` of `items`. Each byte of [`SEPARATOR`] is four rows of it.
+    async fn render_scrollbar(
+        st7789: &mut ST7789<SPI, DC, RST>,
+        x: u16,
+        y: u16,
+        len: u16,
+        index: usize,
+        items: usize,
+    ) -> Result<(), DisplayError> {
+        let (top, thumb) = scroll_thumb(index, items, len);
+        let rows = |rows: u16| &SEPARATOR[..(rows / 4) as usize];
+
+        Self::write_area(
+            st7789,
+            x,
+            y,
+            2,
+            Bitmap::Raw(rows(len)),
+            Rgb565::CSS_DARK_GRAY,
+            Rgb565::CSS_DARK_GRAY,
+        )
+        .await?;
+
+        Self::write_area(
+            st7789,
+            x,
+            y + top,
+            2,
+            Bitmap::Raw(rows(thumb)),
+            COLOR_PRIMARY,
+            COLOR_PRIMARY,
+        )
+        .await
+    }
+
+    /// Blits a 1-bit bitmap, decoding it first if it is a glyph, and records it for screen
+    /// captures.
+    async fn write_area(
+        st7789: &mut ST7789<SPI, DC, RST>,
+        x: u16,
+        y: u16,
+        width: u16,
+        bitmap: Bitmap,
+        color: Rgb565,
+        bg_color: Rgb565,
+    ) -> Result<(), DisplayError> {
+        SCREEN_MUTEX
+            .lock()
+            .await
+            .blit(x, y, width, bitmap, color, bg_color);
+
+        let mut buf = [0; MAX_GLYPH_BYTES];
+
+        st7789
+            .write_area(x, y, width, bitmap.bits(&mut buf), color, bg_color)
+            .await
+            .map_err(|_| DisplayError::Write)
     }
 }
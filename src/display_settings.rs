@@ -0,0 +1,41 @@
+//! Keeps the display clock limit set with `spi <MHz>` on the console in a record of the settings
+//! page, so a panel on a long cable comes up at a clock it takes.
+
+use embassy_stm32::time::Hertz;
+
+use crate::{
+    bsp,
+    log::{info, Module},
+    settings::{self, SettingsError, BODY_LEN, DISPLAY_RECORD},
+    shared::DISPLAY_SPI_MAX_MUTEX,
+};
+
+const LOG_MODULE: Module = Module::Display;
+
+const MAGIC: u32 = 0x5044_5350; // "PDSP"
+
+/// Loads the stored limit into `DISPLAY_SPI_MAX_MUTEX` and returns it; none stored, or one out of
+/// range, keeps the fastest clock.
+pub(crate) async fn load() -> Hertz {
+    let max = settings::read(DISPLAY_RECORD, MAGIC)
+        .map(|buf| Hertz(u32::from_le_bytes(buf[..4].try_into().unwrap())))
+        .filter(|max| (1_000_000..=bsp::DISPLAY_SPI_FREQUENCY.0).contains(&max.0))
+        .unwrap_or(bsp::DISPLAY_SPI_FREQUENCY);
+
+    info!("display spi max {} Hz", max.0);
+    *DISPLAY_SPI_MAX_MUTEX.lock().await = max;
+
+    max
+}
+
+/// Writes `max` to flash and makes it the active limit.
+pub(crate) async fn store(max: Hertz) -> Result<(), SettingsError> {
+    let mut buf = [0u8; BODY_LEN];
+    buf[..4].copy_from_slice(&max.0.to_le_bytes());
+
+    settings::write(DISPLAY_RECORD, MAGIC, &buf).await?;
+
+    *DISPLAY_SPI_MAX_MUTEX.lock().await = max;
+
+    Ok(())
+}
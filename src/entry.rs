@@ -0,0 +1,77 @@
+//! Digit-by-digit entry of the OCP and UVP thresholds with two buttons.
+//!
+//! The value is shown as `DD.DD`: one button counts the selected digit up, wrapping from 9 to 0,
+//! the other moves the selection to the next digit. Digits change freely and the value is only
+//! brought into range when it is taken, so a detour through an out-of-range value on the way to a
+//! valid one is harmless. All zeros turns the threshold off.
+
+/// Digits of an entry, two on either side of the point.
+pub(crate) const ENTRY_DIGITS: usize = 4;
+
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) struct NumberEntry {
+    /// Hundredths of a unit.
+    hundredths: u16,
+    /// The selected digit, 0 for the tens.
+    cursor: u8,
+    /// Range of a value that is not off, in thousandths.
+    min: i32,
+    max: i32,
+}
+
+impl NumberEntry {
+    /// Starts at `milli` thousandths, rounded to hundredths, with the tens selected.
+    pub const fn new(milli: i32, min: i32, max: i32) -> Self {
+        let hundredths = if milli <= 0 {
+            0
+        } else if milli >= 99_995 {
+            9_999
+        } else {
+            (milli + 5) / 10
+        };
+
+        Self {
+            hundredths: hundredths as u16,
+            cursor: 0,
+            min,
+            max,
+        }
+    }
+
+    /// Counts the selected digit up.
+    pub fn increment(&mut self) {
+        let place = 10u16.pow((ENTRY_DIGITS - 1 - self.cursor as usize) as u32);
+        let digit = self.hundredths / place % 10;
+
+        self.hundredths = self.hundredths - digit * place + (digit + 1) % 10 * place;
+    }
+
+    /// Selects the next digit, back to the tens after the last.
+    pub fn next_digit(&mut self) {
+        self.cursor = (self.cursor + 1) % ENTRY_DIGITS as u8;
+    }
+
+    pub fn cursor(&self) -> usize {
+        self.cursor as usize
+    }
+
+    /// The digits as shown, tens first.
+    pub fn digits(&self) -> [u8; ENTRY_DIGITS] {
+        let h = self.hundredths;
+
+        [
+            (h / 1000) as u8,
+            (h / 100 % 10) as u8,
+            (h / 10 % 10) as u8,
+            (h % 10) as u8,
+        ]
+    }
+
+    /// The entered value in thousandths, clamped to the range, or 0 for off.
+    pub fn milli(&self) -> i32 {
+        match self.hundredths {
+            0 => 0,
+            h => (h as i32 * 10).clamp(self.min, self.max),
+        }
+    }
+}
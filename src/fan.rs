@@ -0,0 +1,112 @@
+//! Fan speed as a function of the board temperature.
+//!
+//! The duty rises linearly from `min_percent` at `start_c` to full at `full_c`. Below `start_c`
+//! the fan stops, but only once the temperature is [`FAN_HYSTERESIS_C`] under it, so it does not
+//! start and stop on every tenth of a degree. A stopped fan may not spin up at a low duty, so every
+//! start runs at full duty for [`FAN_KICK`] first. `thermal.rs` feeds [`FanControl`] and drives the
+//! PWM.
+
+use embassy_time::Duration;
+#[cfg(not(feature = "mock-time"))]
+use embassy_time::Instant;
+
+#[cfg(feature = "mock-time")]
+use crate::mock_time::Instant;
+
+pub(crate) const FAN_HYSTERESIS_C: i16 = 3;
+pub(crate) const FAN_KICK: Duration = Duration::from_secs(2);
+
+/// Set with the `fan curve` console command.
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) struct FanCurve {
+    pub start_c: i16,
+    pub full_c: i16,
+    /// Duty at `start_c`, the slowest the fan runs.
+    pub min_percent: u8,
+}
+
+impl FanCurve {
+    pub const fn default() -> Self {
+        Self {
+            start_c: 45,
+            full_c: 70,
+            min_percent: 30,
+        }
+    }
+
+    /// Whether the points are in order and the duty a percentage.
+    pub fn is_valid(&self) -> bool {
+        self.start_c < self.full_c && self.min_percent <= 100
+    }
+
+    /// The duty of a running fan at `celsius`.
+    fn duty(&self, celsius: i16) -> u8 {
+        if celsius >= self.full_c {
+            return 100;
+        }
+
+        if celsius <= self.start_c {
+            return self.min_percent;
+        }
+
+        let span = (self.full_c - self.start_c) as i32;
+        let above = (celsius - self.start_c) as i32;
+        let min = self.min_percent as i32;
+
+        (min + (100 - min) * above / span) as u8
+    }
+}
+
+/// What the diagnostics page and the `fan` console command show.
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) struct FanStatus {
+    pub celsius: i16,
+    /// 0 while stopped.
+    pub percent: u8,
+    pub kicking: bool,
+}
+
+pub(crate) struct FanControl {
+    running: bool,
+    started_at: Instant,
+}
+
+impl FanControl {
+    pub const fn new() -> Self {
+        Self {
+            running: false,
+            started_at: Instant::MIN,
+        }
+    }
+
+    pub fn update(&mut self, curve: &FanCurve, celsius: i16, now: Instant) -> FanStatus {
+        let stop_below = if self.running {
+            curve.start_c - FAN_HYSTERESIS_C
+        } else {
+            curve.start_c
+        };
+
+        if celsius < stop_below {
+            self.running = false;
+
+            return FanStatus {
+                celsius,
+                percent: 0,
+                kicking: false,
+            };
+        }
+
+        if !self.running {
+            self.running = true;
+            self.started_at = now;
+        }
+
+        let kicking = now - self.started_at < FAN_KICK;
+
+        FanStatus {
+            celsius,
+            percent: if kicking { 100 } else { curve.duty(celsius) },
+            kicking,
+        }
+    }
+}
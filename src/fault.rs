@@ -0,0 +1,80 @@
+//! Latched hardware faults.
+//!
+//! Driver failures are reported here instead of panicking. A fault stays set until it is cleared
+//! from the console, so a transient error still leaves a mark on the status column of the screen.
+
+use crate::{
+    shared::{FAULTS_MUTEX, FAULT_PUBSUB, SESSION_MUTEX},
+    telemetry,
+};
+
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) enum Fault {
+    Display,
+    PowerMonitor,
+    PdController,
+    /// The output sense does not follow the output, see `protection.rs`.
+    OutputDriver,
+}
+
+pub(crate) const FAULTS: [Fault; 4] = [
+    Fault::Display,
+    Fault::PowerMonitor,
+    Fault::PdController,
+    Fault::OutputDriver,
+];
+
+impl Fault {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Fault::Display => "display",
+            Fault::PowerMonitor => "power monitor",
+            Fault::PdController => "pd controller",
+            Fault::OutputDriver => "output driver",
+        }
+    }
+
+    const fn bit(self) -> u8 {
+        1 << self as u8
+    }
+}
+
+/// Set of faults seen since the last clear.
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) struct Faults(u8);
+
+impl Faults {
+    pub const fn empty() -> Self {
+        Self(0)
+    }
+
+    pub fn contains(&self, fault: Fault) -> bool {
+        self.0 & fault.bit() != 0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    fn insert(&mut self, fault: Fault) {
+        self.0 |= fault.bit();
+    }
+}
+
+/// Latches `fault` and publishes it the first time it is seen since the last clear.
+pub(crate) async fn report(fault: Fault) {
+    let mut faults = FAULTS_MUTEX.lock().await;
+
+    if faults.contains(fault) {
+        return;
+    }
+
+    faults.insert(fault);
+    FAULT_PUBSUB.immediate_publisher().publish_immediate(fault);
+    telemetry::send_fault(fault.as_str());
+    SESSION_MUTEX.lock().await.fault();
+}
+
+pub(crate) async fn clear() {
+    *FAULTS_MUTEX.lock().await = Faults::empty();
+}
@@ -0,0 +1,175 @@
+//! Smoothing of the displayed readings.
+//!
+//! The measurement loop runs one [`AnyFilter`] per quantity and rebuilds them when the `filter`
+//! setting changes. Over-current protection and the energy counter keep working on the raw
+//! readings, so a slow filter never delays a trip.
+//!
+//! A reading that could not be taken goes in as `None` and comes out as `None`. It is not averaged
+//! in; it starts the filter over, so the next good reading is not averaged with one from before
+//! the gap either.
+
+use crate::units::{self, Value};
+
+/// Weight of a new reading in the moving averages, in percent.
+const EMA_ALPHA_PERCENT: i32 = 20;
+
+pub(crate) trait Filter {
+    /// Feeds a reading, `None` for one that failed, and returns the filtered value.
+    fn update(&mut self, x: Option<Value>) -> Option<Value> {
+        match x {
+            Some(x) => Some(self.sample(x)),
+            None => {
+                self.reset();
+                None
+            }
+        }
+    }
+
+    /// Feeds a good reading and returns the filtered value.
+    fn sample(&mut self, x: Value) -> Value;
+
+    /// Forgets the history, so the next reading passes through unchanged.
+    fn reset(&mut self);
+}
+
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) enum FilterKind {
+    Off,
+    Ema,
+    Combined,
+}
+
+impl FilterKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FilterKind::Off => "off",
+            FilterKind::Ema => "ema",
+            FilterKind::Combined => "combined",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "off" => Some(FilterKind::Off),
+            "ema" => Some(FilterKind::Ema),
+            "combined" => Some(FilterKind::Combined),
+            _ => None,
+        }
+    }
+}
+
+/// First-order low-pass: each reading moves the output [`EMA_ALPHA_PERCENT`] of the way.
+#[derive(Clone, Copy)]
+pub(crate) struct ExponentialMovingAverage {
+    alpha_percent: i32,
+    value: Option<Value>,
+}
+
+impl ExponentialMovingAverage {
+    pub const fn new(alpha_percent: i32) -> Self {
+        Self {
+            alpha_percent,
+            value: None,
+        }
+    }
+}
+
+impl Filter for ExponentialMovingAverage {
+    fn sample(&mut self, x: Value) -> Value {
+        let value = match self.value {
+            Some(value) => value + units::percent(x - value, self.alpha_percent),
+            None => x,
+        };
+
+        self.value = Some(value);
+        value
+    }
+
+    fn reset(&mut self) {
+        self.value = None;
+    }
+}
+
+/// Median of the last three readings followed by a moving average. The median drops single-sample
+/// spikes, such as the INA226 catching a switching edge, that an average alone would smear out.
+#[derive(Clone, Copy)]
+pub(crate) struct CombinedFilter {
+    window: [Value; 3],
+    len: usize,
+    ema: ExponentialMovingAverage,
+}
+
+impl CombinedFilter {
+    pub const fn new(alpha_percent: i32) -> Self {
+        Self {
+            window: [units::ZERO; 3],
+            len: 0,
+            ema: ExponentialMovingAverage::new(alpha_percent),
+        }
+    }
+}
+
+impl Filter for CombinedFilter {
+    fn sample(&mut self, x: Value) -> Value {
+        self.window.rotate_left(1);
+        self.window[2] = x;
+        self.len = (self.len + 1).min(3);
+
+        // Until the window is full the newest reading stands in for the median.
+        let median = if self.len < 3 {
+            x
+        } else {
+            let [a, b, c] = self.window;
+            if (a <= b) == (b <= c) {
+                b
+            } else if (b <= a) == (a <= c) {
+                a
+            } else {
+                c
+            }
+        };
+
+        self.ema.sample(median)
+    }
+
+    fn reset(&mut self) {
+        self.len = 0;
+        self.ema.reset();
+    }
+}
+
+/// The filter picked by the `filter` setting.
+#[derive(Clone, Copy)]
+pub(crate) enum AnyFilter {
+    Off,
+    Ema(ExponentialMovingAverage),
+    Combined(CombinedFilter),
+}
+
+impl AnyFilter {
+    pub const fn new(kind: FilterKind) -> Self {
+        match kind {
+            FilterKind::Off => AnyFilter::Off,
+            FilterKind::Ema => AnyFilter::Ema(ExponentialMovingAverage::new(EMA_ALPHA_PERCENT)),
+            FilterKind::Combined => AnyFilter::Combined(CombinedFilter::new(EMA_ALPHA_PERCENT)),
+        }
+    }
+}
+
+impl Filter for AnyFilter {
+    fn sample(&mut self, x: Value) -> Value {
+        match self {
+            AnyFilter::Off => x,
+            AnyFilter::Ema(filter) => filter.sample(x),
+            AnyFilter::Combined(filter) => filter.sample(x),
+        }
+    }
+
+    fn reset(&mut self) {
+        match self {
+            AnyFilter::Off => {}
+            AnyFilter::Ema(filter) => filter.reset(),
+            AnyFilter::Combined(filter) => filter.reset(),
+        }
+    }
+}
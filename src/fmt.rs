@@ -0,0 +1,126 @@
+//! Fixed-point number formatting for the screen and the serial outputs.
+//!
+//! A reading is rounded to a fixed number of decimals and right-aligned in a fixed width, so a
+//! quantity always occupies the same character cells and redrawing only the changed characters
+//! leaves nothing stale behind. Digits are produced from an integer, keeping `core::fmt`'s float
+//! formatting out of the image.
+
+use heapless::String;
+
+/// Longest string [`fixed`] produces: a sign, 20 digits and the point.
+pub(crate) const MAX_LEN: usize = 22;
+
+/// More decimals than this would overflow the scaled integer for everyday values.
+const MAX_DECIMALS: u8 = 6;
+
+const POWERS_OF_TEN: [u64; MAX_DECIMALS as usize + 1] =
+    [1, 10, 100, 1_000, 10_000, 100_000, 1_000_000];
+
+pub(crate) type Fixed = String<MAX_LEN>;
+
+/// `value` rounded to `decimals` places and right-aligned with spaces to `width` characters.
+///
+/// A value too wide for `width` loses decimals first; when even the integer part does not fit,
+/// every place shows a 9. A `width` of 0 leaves the number unpadded, for the serial outputs.
+pub(crate) fn fixed(value: f64, decimals: u8, width: usize) -> Fixed {
+    fit(decimals, width, |decimals| {
+        let negative = value < 0.0;
+        let magnitude = if negative { -value } else { value };
+
+        // `as` saturates, and NaN becomes 0.
+        let units = (magnitude * POWERS_OF_TEN[decimals as usize] as f64 + 0.5) as u64;
+
+        (negative, units)
+    })
+}
+
+/// [`fixed`] for a value given in thousandths, without going through floating point.
+///
+/// Decimals beyond the third are always zero.
+pub(crate) fn fixed_milli(milli: i32, decimals: u8, width: usize) -> Fixed {
+    fit(decimals, width, |decimals| {
+        let magnitude = milli.unsigned_abs() as u64;
+
+        let units = if decimals >= 3 {
+            magnitude * POWERS_OF_TEN[decimals as usize - 3]
+        } else {
+            let divisor = POWERS_OF_TEN[3 - decimals as usize];
+            (magnitude + divisor / 2) / divisor
+        };
+
+        (milli < 0, units)
+    })
+}
+
+/// Lays out the number `scaled` returns for a given number of decimals: its sign and magnitude
+/// times ten to the decimals, rounded.
+fn fit(decimals: u8, width: usize, scaled: impl Fn(u8) -> (bool, u64)) -> Fixed {
+    let width = width.min(MAX_LEN);
+    let mut decimals = decimals.min(MAX_DECIMALS);
+
+    loop {
+        let (negative, units) = scaled(decimals);
+        let digits = unpadded(negative, units, decimals);
+
+        if width == 0 || digits.len() <= width {
+            let mut s = Fixed::new();
+
+            for _ in digits.len()..width {
+                s.push(' ').ok();
+            }
+            s.push_str(&digits).ok();
+
+            return s;
+        }
+
+        if decimals == 0 {
+            let mut s = Fixed::new();
+
+            for _ in 0..width {
+                s.push('9').ok();
+            }
+
+            return s;
+        }
+
+        decimals -= 1;
+    }
+}
+
+fn unpadded(negative: bool, mut units: u64, decimals: u8) -> Fixed {
+    let negative = negative && units > 0;
+
+    let mut buf = [0u8; MAX_LEN];
+    let mut pos = MAX_LEN;
+    let mut written = 0;
+
+    // Least significant digit first, and at least one digit before the point.
+    loop {
+        if written == decimals && decimals > 0 {
+            pos -= 1;
+            buf[pos] = b'.';
+        }
+
+        pos -= 1;
+        buf[pos] = b'0' + (units % 10) as u8;
+        units /= 10;
+        written += 1;
+
+        if units == 0 && written > decimals {
+            break;
+        }
+    }
+
+    if negative {
+        pos -= 1;
+        buf[pos] = b'-';
+    }
+
+    let mut s = Fixed::new();
+
+    for &b in &buf[pos..] {
+        s.push(b as char).ok();
+    }
+
+    s
+}
@@ -1,710 +1,136 @@
-pub static DOT_MATRIX_XL_NUM: &[&[u8; 200]; 10] = &[
-    &[
-        0x00, 0x00, 0x00, 0x00, 0x00, 0xE3, 0x8E, 0x00, 0x01, 0xF7, 0xDF, 0x00, 0x01, 0xF7, 0xDF,
-        0x00, 0x01, 0xF7, 0xDF, 0x00, 0x00, 0xE3, 0x8E, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x38, 0x00, 0x00, 0x38, 0x7C, 0x00, 0x00, 0x7C, 0x7C, 0x00, 0x00, 0x7C, 0x7C,
-        0x00, 0x00, 0x7C, 0x38, 0x00, 0x00, 0x38, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x38, 0x00, 0x00, 0x38, 0x7C, 0x00, 0x00, 0x7C, 0x7C, 0x00, 0x00, 0x7C, 0x7C, 0x00, 0x00,
-        0x7C, 0x38, 0x00, 0x00, 0x38, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x38, 0x00,
-        0x00, 0x38, 0x7C, 0x00, 0x00, 0x7C, 0x7C, 0x00, 0x00, 0x7C, 0x7C, 0x00, 0x00, 0x7C, 0x38,
-        0x00, 0x00, 0x38, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x38, 0x00, 0x00, 0x38,
-        0x7C, 0x00, 0x00, 0x7C, 0x7C, 0x00, 0x00, 0x7C, 0x7C, 0x00, 0x00, 0x7C, 0x38, 0x00, 0x00,
-        0x38, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x38, 0x00, 0x00, 0x38, 0x7C, 0x00,
-        0x00, 0x7C, 0x7C, 0x00, 0x00, 0x7C, 0x7C, 0x00, 0x00, 0x7C, 0x38, 0x00, 0x00, 0x38, 0x00,
-        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xE3, 0x8E, 0x00, 0x01, 0xF7, 0xDF, 0x00,
-        0x01, 0xF7, 0xDF, 0x00, 0x01, 0xF7, 0xDF, 0x00, 0x00, 0xE3, 0x8E, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00, 0x00, 0x00,
-    ], // 0
-    &[
-        0x00, 0x00, 0x00, 0x00, 0x00, 0x03, 0x80, 0x00, 0x00, 0x07, 0xC0, 0x00, 0x00, 0x07, 0xC0,
-        0x00, 0x00, 0x07, 0xC0, 0x00, 0x00, 0x03, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00, 0xE3, 0x80, 0x00, 0x01, 0xF7, 0xC0, 0x00, 0x01, 0xF7, 0xC0, 0x00, 0x01,
-        0xF7, 0xC0, 0x00, 0x00, 0xE3, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x03, 0x80, 0x00, 0x00, 0x07, 0xC0, 0x00, 0x00, 0x07, 0xC0, 0x00, 0x00, 0x07, 0xC0,
-        0x00, 0x00, 0x03, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x03,
-        0x80, 0x00, 0x00, 0x07, 0xC0, 0x00, 0x00, 0x07, 0xC0, 0x00, 0x00, 0x07, 0xC0, 0x00, 0x00,
-        0x03, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x03, 0x80, 0x00,
-        0x00, 0x07, 0xC0, 0x00, 0x00, 0x07, 0xC0, 0x00, 0x00, 0x07, 0xC0, 0x00, 0x00, 0x03, 0x80,
-        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x03, 0x80, 0x00, 0x00, 0x07,
-        0xC0, 0x00, 0x00, 0x07, 0xC0, 0x00, 0x00, 0x07, 0xC0, 0x00, 0x00, 0x03, 0x80, 0x00, 0x00,
-        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xE3, 0x8E, 0x00, 0x01, 0xF7, 0xDF, 0x00,
-        0x01, 0xF7, 0xDF, 0x00, 0x01, 0xF7, 0xDF, 0x00, 0x00, 0xE3, 0x8E, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00, 0x00, 0x00,
-    ], // 1
-    &[
-        0x00, 0x00, 0x00, 0x00, 0x38, 0xE3, 0x8E, 0x00, 0x7D, 0xF7, 0xDF, 0x00, 0x7D, 0xF7, 0xDF,
-        0x00, 0x7D, 0xF7, 0xDF, 0x00, 0x38, 0xE3, 0x8E, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00, 0x00, 0x00, 0x38, 0x00, 0x00, 0x00, 0x7C, 0x00, 0x00, 0x00, 0x7C, 0x00,
-        0x00, 0x00, 0x7C, 0x00, 0x00, 0x00, 0x38, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00, 0x38, 0x00, 0x00, 0x00, 0x7C, 0x00, 0x00, 0x00, 0x7C, 0x00, 0x00, 0x00,
-        0x7C, 0x00, 0x00, 0x00, 0x38, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xE3,
-        0x8E, 0x00, 0x01, 0xF7, 0xDF, 0x00, 0x01, 0xF7, 0xDF, 0x00, 0x01, 0xF7, 0xDF, 0x00, 0x00,
-        0xE3, 0x8E, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x38, 0x00, 0x00, 0x00,
-        0x7C, 0x00, 0x00, 0x00, 0x7C, 0x00, 0x00, 0x00, 0x7C, 0x00, 0x00, 0x00, 0x38, 0x00, 0x00,
-        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x38, 0x00, 0x00, 0x00, 0x7C, 0x00,
-        0x00, 0x00, 0x7C, 0x00, 0x00, 0x00, 0x7C, 0x00, 0x00, 0x00, 0x38, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x38, 0xE3, 0x8E, 0x38, 0x7D, 0xF7, 0xDF, 0x7C,
-        0x7D, 0xF7, 0xDF, 0x7C, 0x7D, 0xF7, 0xDF, 0x7C, 0x38, 0xE3, 0x8E, 0x38, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00, 0x00, 0x00,
-    ], // 2
-    &[
-        0x00, 0x00, 0x00, 0x00, 0x38, 0xE3, 0x8E, 0x00, 0x7D, 0xF7, 0xDF, 0x00, 0x7D, 0xF7, 0xDF,
-        0x00, 0x7D, 0xF7, 0xDF, 0x00, 0x38, 0xE3, 0x8E, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00, 0x00, 0x00, 0x38, 0x00, 0x00, 0x00, 0x7C, 0x00, 0x00, 0x00, 0x7C, 0x00,
-        0x00, 0x00, 0x7C, 0x00, 0x00, 0x00, 0x38, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00, 0x38, 0x00, 0x00, 0x00, 0x7C, 0x00, 0x00, 0x00, 0x7C, 0x00, 0x00, 0x00,
-        0x7C, 0x00, 0x00, 0x00, 0x38, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xE3,
-        0x8E, 0x00, 0x01, 0xF7, 0xDF, 0x00, 0x01, 0xF7, 0xDF, 0x00, 0x01, 0xF7, 0xDF, 0x00, 0x00,
-        0xE3, 0x8E, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x38,
-        0x00, 0x00, 0x00, 0x7C, 0x00, 0x00, 0x00, 0x7C, 0x00, 0x00, 0x00, 0x7C, 0x00, 0x00, 0x00,
-        0x38, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x38, 0x00, 0x00,
-        0x00, 0x7C, 0x00, 0x00, 0x00, 0x7C, 0x00, 0x00, 0x00, 0x7C, 0x00, 0x00, 0x00, 0x38, 0x00,
-        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x38, 0xE3, 0x8E, 0x00, 0x7D, 0xF7, 0xDF, 0x00,
-        0x7D, 0xF7, 0xDF, 0x00, 0x7D, 0xF7, 0xDF, 0x00, 0x38, 0xE3, 0x8E, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00, 0x00, 0x00,
-    ], // 3
-    &[
-        0x00, 0x00, 0x00, 0x00, 0x38, 0x00, 0x00, 0x38, 0x7C, 0x00, 0x00, 0x7C, 0x7C, 0x00, 0x00,
-        0x7C, 0x7C, 0x00, 0x00, 0x7C, 0x38, 0x00, 0x00, 0x38, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x38, 0x00, 0x00, 0x38, 0x7C, 0x00, 0x00, 0x7C, 0x7C, 0x00, 0x00, 0x7C, 0x7C,
-        0x00, 0x00, 0x7C, 0x38, 0x00, 0x00, 0x38, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x38, 0x00, 0x00, 0x38, 0x7C, 0x00, 0x00, 0x7C, 0x7C, 0x00, 0x00, 0x7C, 0x7C, 0x00, 0x00,
-        0x7C, 0x38, 0x00, 0x00, 0x38, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x38, 0xE3,
-        0x8E, 0x38, 0x7D, 0xF7, 0xDF, 0x7C, 0x7D, 0xF7, 0xDF, 0x7C, 0x7D, 0xF7, 0xDF, 0x7C, 0x38,
-        0xE3, 0x8E, 0x38, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x38,
-        0x00, 0x00, 0x00, 0x7C, 0x00, 0x00, 0x00, 0x7C, 0x00, 0x00, 0x00, 0x7C, 0x00, 0x00, 0x00,
-        0x38, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x38, 0x00, 0x00,
-        0x00, 0x7C, 0x00, 0x00, 0x00, 0x7C, 0x00, 0x00, 0x00, 0x7C, 0x00, 0x00, 0x00, 0x38, 0x00,
-        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x38, 0x00, 0x00, 0x00, 0x7C,
-        0x00, 0x00, 0x00, 0x7C, 0x00, 0x00, 0x00, 0x7C, 0x00, 0x00, 0x00, 0x38, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00, 0x00, 0x00,
-    ], // 4
-    &[
-        0x00, 0x00, 0x00, 0x00, 0x38, 0xE3, 0x8E, 0x38, 0x7D, 0xF7, 0xDF, 0x7C, 0x7D, 0xF7, 0xDF,
-        0x7C, 0x7D, 0xF7, 0xDF, 0x7C, 0x38, 0xE3, 0x8E, 0x38, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x38, 0x00, 0x00, 0x00, 0x7C, 0x00, 0x00, 0x00, 0x7C, 0x00, 0x00, 0x00, 0x7C,
-        0x00, 0x00, 0x00, 0x38, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x38, 0x00, 0x00, 0x00, 0x7C, 0x00, 0x00, 0x00, 0x7C, 0x00, 0x00, 0x00, 0x7C, 0x00, 0x00,
-        0x00, 0x38, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x38, 0xE3,
-        0x8E, 0x00, 0x7D, 0xF7, 0xDF, 0x00, 0x7D, 0xF7, 0xDF, 0x00, 0x7D, 0xF7, 0xDF, 0x00, 0x38,
-        0xE3, 0x8E, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x38,
-        0x00, 0x00, 0x00, 0x7C, 0x00, 0x00, 0x00, 0x7C, 0x00, 0x00, 0x00, 0x7C, 0x00, 0x00, 0x00,
-        0x38, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x38, 0x00, 0x00,
-        0x00, 0x7C, 0x00, 0x00, 0x00, 0x7C, 0x00, 0x00, 0x00, 0x7C, 0x00, 0x00, 0x00, 0x38, 0x00,
-        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x38, 0xE3, 0x8E, 0x00, 0x7D, 0xF7, 0xDF, 0x00,
-        0x7D, 0xF7, 0xDF, 0x00, 0x7D, 0xF7, 0xDF, 0x00, 0x38, 0xE3, 0x8E, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00, 0x00, 0x00,
-    ], // 5
-    &[
-        0x00, 0x00, 0x00, 0x00, 0x00, 0xE3, 0x8E, 0x00, 0x01, 0xF7, 0xDF, 0x00, 0x01, 0xF7, 0xDF,
-        0x00, 0x01, 0xF7, 0xDF, 0x00, 0x00, 0xE3, 0x8E, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x38, 0x00, 0x00, 0x00, 0x7C, 0x00, 0x00, 0x00, 0x7C, 0x00, 0x00, 0x00, 0x7C,
-        0x00, 0x00, 0x00, 0x38, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x38, 0x00, 0x00, 0x00, 0x7C, 0x00, 0x00, 0x00, 0x7C, 0x00, 0x00, 0x00, 0x7C, 0x00, 0x00,
-        0x00, 0x38, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x38, 0xE3,
-        0x8E, 0x00, 0x7D, 0xF7, 0xDF, 0x00, 0x7D, 0xF7, 0xDF, 0x00, 0x7D, 0xF7, 0xDF, 0x00, 0x38,
-        0xE3, 0x8E, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x38, 0x00, 0x00, 0x38,
-        0x7C, 0x00, 0x00, 0x7C, 0x7C, 0x00, 0x00, 0x7C, 0x7C, 0x00, 0x00, 0x7C, 0x38, 0x00, 0x00,
-        0x38, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x38, 0x00, 0x00, 0x38, 0x7C, 0x00,
-        0x00, 0x7C, 0x7C, 0x00, 0x00, 0x7C, 0x7C, 0x00, 0x00, 0x7C, 0x38, 0x00, 0x00, 0x38, 0x00,
-        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xE3, 0x8E, 0x00, 0x01, 0xF7, 0xDF, 0x00,
-        0x01, 0xF7, 0xDF, 0x00, 0x01, 0xF7, 0xDF, 0x00, 0x00, 0xE3, 0x8E, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00, 0x00, 0x00,
-    ], // 6
-    &[
-        0x00, 0x00, 0x00, 0x00, 0x38, 0xE3, 0x8E, 0x38, 0x7D, 0xF7, 0xDF, 0x7C, 0x7D, 0xF7, 0xDF,
-        0x7C, 0x7D, 0xF7, 0xDF, 0x7C, 0x38, 0xE3, 0x8E, 0x38, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00, 0x00, 0x00, 0x38, 0x00, 0x00, 0x00, 0x7C, 0x00, 0x00, 0x00, 0x7C, 0x00,
-        0x00, 0x00, 0x7C, 0x00, 0x00, 0x00, 0x38, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00, 0x38, 0x00, 0x00, 0x00, 0x7C, 0x00, 0x00, 0x00, 0x7C, 0x00, 0x00, 0x00,
-        0x7C, 0x00, 0x00, 0x00, 0x38, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x0E, 0x00, 0x00, 0x00, 0x1F, 0x00, 0x00, 0x00, 0x1F, 0x00, 0x00, 0x00, 0x1F, 0x00, 0x00,
-        0x00, 0x0E, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x03, 0x80, 0x00,
-        0x00, 0x07, 0xC0, 0x00, 0x00, 0x07, 0xC0, 0x00, 0x00, 0x07, 0xC0, 0x00, 0x00, 0x03, 0x80,
-        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xE0, 0x00, 0x00, 0x01, 0xF0,
-        0x00, 0x00, 0x01, 0xF0, 0x00, 0x00, 0x01, 0xF0, 0x00, 0x00, 0x00, 0xE0, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x38, 0x00, 0x00, 0x00, 0x7C, 0x00, 0x00, 0x00,
-        0x7C, 0x00, 0x00, 0x00, 0x7C, 0x00, 0x00, 0x00, 0x38, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00, 0x00, 0x00,
-    ], // 7
-    &[
-        0x00, 0x00, 0x00, 0x00, 0x00, 0xE3, 0x8E, 0x00, 0x01, 0xF7, 0xDF, 0x00, 0x01, 0xF7, 0xDF,
-        0x00, 0x01, 0xF7, 0xDF, 0x00, 0x00, 0xE3, 0x8E, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x38, 0x00, 0x00, 0x38, 0x7C, 0x00, 0x00, 0x7C, 0x7C, 0x00, 0x00, 0x7C, 0x7C,
-        0x00, 0x00, 0x7C, 0x38, 0x00, 0x00, 0x38, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x38, 0x00, 0x00, 0x38, 0x7C, 0x00, 0x00, 0x7C, 0x7C, 0x00, 0x00, 0x7C, 0x7C, 0x00, 0x00,
-        0x7C, 0x38, 0x00, 0x00, 0x38, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xE3,
-        0x8E, 0x00, 0x01, 0xF7, 0xDF, 0x00, 0x01, 0xF7, 0xDF, 0x00, 0x01, 0xF7, 0xDF, 0x00, 0x00,
-        0xE3, 0x8E, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x38, 0x00, 0x00, 0x38,
-        0x7C, 0x00, 0x00, 0x7C, 0x7C, 0x00, 0x00, 0x7C, 0x7C, 0x00, 0x00, 0x7C, 0x38, 0x00, 0x00,
-        0x38, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x38, 0x00, 0x00, 0x38, 0x7C, 0x00,
-        0x00, 0x7C, 0x7C, 0x00, 0x00, 0x7C, 0x7C, 0x00, 0x00, 0x7C, 0x38, 0x00, 0x00, 0x38, 0x00,
-        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xE3, 0x8E, 0x00, 0x01, 0xF7, 0xDF, 0x00,
-        0x01, 0xF7, 0xDF, 0x00, 0x01, 0xF7, 0xDF, 0x00, 0x00, 0xE3, 0x8E, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00, 0x00, 0x00,
-    ], // 8
-    &[
-        0x00, 0x00, 0x00, 0x00, 0x00, 0xE3, 0x8E, 0x00, 0x01, 0xF7, 0xDF, 0x00, 0x01, 0xF7, 0xDF,
-        0x00, 0x01, 0xF7, 0xDF, 0x00, 0x00, 0xE3, 0x8E, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x38, 0x00, 0x00, 0x38, 0x7C, 0x00, 0x00, 0x7C, 0x7C, 0x00, 0x00, 0x7C, 0x7C,
-        0x00, 0x00, 0x7C, 0x38, 0x00, 0x00, 0x38, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x38, 0x00, 0x00, 0x38, 0x7C, 0x00, 0x00, 0x7C, 0x7C, 0x00, 0x00, 0x7C, 0x7C, 0x00, 0x00,
-        0x7C, 0x38, 0x00, 0x00, 0x38, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xE3,
-        0x8E, 0x38, 0x01, 0xF7, 0xDF, 0x7C, 0x01, 0xF7, 0xDF, 0x7C, 0x01, 0xF7, 0xDF, 0x7C, 0x00,
-        0xE3, 0x8E, 0x38, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x38,
-        0x00, 0x00, 0x00, 0x7C, 0x00, 0x00, 0x00, 0x7C, 0x00, 0x00, 0x00, 0x7C, 0x00, 0x00, 0x00,
-        0x38, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x38, 0x00, 0x00,
-        0x00, 0x7C, 0x00, 0x00, 0x00, 0x7C, 0x00, 0x00, 0x00, 0x7C, 0x00, 0x00, 0x00, 0x38, 0x00,
-        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xE3, 0x8E, 0x00, 0x01, 0xF7, 0xDF, 0x00,
-        0x01, 0xF7, 0xDF, 0x00, 0x01, 0xF7, 0xDF, 0x00, 0x00, 0xE3, 0x8E, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00, 0x00, 0x00,
-    ], // 9
-];
+//! Bitmap fonts for the panel.
+//!
+//! The tables in `src/fonts/` are generated by `font-tool` from BDF or TrueType fonts and store
+//! every glyph run-length encoded, see [`rle`](crate::rle). They are decoded one glyph at a time
+//! on their way to the panel.
+//!
+//! `embedded-graphics` [`MonoFont`]s take the same path: [`Bitmap::Mono`] packs a glyph from the
+//! font's image into the same 1-bit layout, so any size of that ecosystem can be drawn without
+//! converting it first.
 
-pub static DOT_MATRIX_XL_NUM_INDEX: &[char; 10] =
-    &['0', '1', '2', '3', '4', '5', '6', '7', '8', '9'];
+use embedded_graphics::{
+    geometry::{OriginDimensions, Point},
+    image::GetPixel,
+    mono_font::MonoFont,
+    pixelcolor::BinaryColor,
+};
 
-pub static GROTESK_24_48: &[&[u8; 144]; 11] = &[
-    &[
-        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x18, 0x00, 0x00, 0x7F, 0x00,
-        0x01, 0xFF, 0x80, 0x03, 0xFF, 0xC0, 0x03, 0xFF, 0xE0, 0x07, 0xC3, 0xE0, 0x07, 0x81, 0xF0,
-        0x0F, 0x80, 0xF0, 0x0F, 0x00, 0xF0, 0x0F, 0x01, 0xF8, 0x0F, 0x01, 0xF8, 0x1F, 0x01, 0xF8,
-        0x1E, 0x03, 0xF8, 0x1E, 0x03, 0xF8, 0x1E, 0x07, 0x3C, 0x1E, 0x07, 0x7C, 0x1E, 0x0E, 0x3C,
-        0x1E, 0x0E, 0x3C, 0x1E, 0x1C, 0x3C, 0x1E, 0x1C, 0x3C, 0x1E, 0x3C, 0x3C, 0x1E, 0x38, 0x3C,
-        0x1E, 0x78, 0x3C, 0x1E, 0x70, 0x3C, 0x1E, 0xF0, 0x7C, 0x1E, 0xE0, 0x7C, 0x1F, 0xE0, 0x78,
-        0x1F, 0xC0, 0x78, 0x1F, 0xC0, 0x78, 0x1F, 0x80, 0x78, 0x0F, 0x80, 0x78, 0x0F, 0x00, 0xF8,
-        0x0F, 0x00, 0xF0, 0x07, 0x81, 0xF0, 0x07, 0xC1, 0xF0, 0x07, 0xF7, 0xE0, 0x03, 0xFF, 0xC0,
-        0x01, 0xFF, 0xC0, 0x00, 0xFF, 0x80, 0x00, 0x3E, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-    ], // 0
-    &[
-        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x1F, 0x00,
-        0x00, 0x3F, 0x00, 0x00, 0xFF, 0x00, 0x03, 0xFF, 0x00, 0x07, 0xFF, 0x00, 0x0F, 0xFF, 0x00,
-        0x0F, 0xCF, 0x00, 0x0F, 0x0F, 0x00, 0x0E, 0x0F, 0x00, 0x08, 0x0F, 0x00, 0x00, 0x0F, 0x00,
-        0x00, 0x0F, 0x00, 0x00, 0x0F, 0x00, 0x00, 0x0F, 0x00, 0x00, 0x0F, 0x00, 0x00, 0x0F, 0x00,
-        0x00, 0x0F, 0x00, 0x00, 0x0F, 0x00, 0x00, 0x0F, 0x00, 0x00, 0x0F, 0x00, 0x00, 0x0F, 0x00,
-        0x00, 0x0F, 0x00, 0x00, 0x0F, 0x00, 0x00, 0x0F, 0x00, 0x00, 0x0F, 0x00, 0x00, 0x0F, 0x00,
-        0x00, 0x0F, 0x00, 0x00, 0x0F, 0x00, 0x00, 0x0F, 0x00, 0x00, 0x0F, 0x00, 0x00, 0x0F, 0x00,
-        0x00, 0x0F, 0x00, 0x00, 0x0F, 0x00, 0x00, 0x1F, 0x00, 0x07, 0xFF, 0xFC, 0x07, 0xFF, 0xFC,
-        0x07, 0xFF, 0xFC, 0x07, 0xFF, 0xFC, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-    ], // 1
-    &[
-        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x7C, 0x00, 0x03, 0xFF, 0x80,
-        0x0F, 0xFF, 0xE0, 0x1F, 0xFF, 0xF0, 0x1F, 0xFF, 0xF0, 0x1F, 0x81, 0xF8, 0x1C, 0x00, 0xF8,
-        0x18, 0x00, 0x7C, 0x00, 0x00, 0x3C, 0x00, 0x00, 0x3C, 0x00, 0x00, 0x3C, 0x00, 0x00, 0x3C,
-        0x00, 0x00, 0x3C, 0x00, 0x00, 0x3C, 0x00, 0x00, 0x3C, 0x00, 0x00, 0x7C, 0x00, 0x00, 0x78,
-        0x00, 0x00, 0xF8, 0x00, 0x00, 0xF0, 0x00, 0x01, 0xF0, 0x00, 0x03, 0xE0, 0x00, 0x03, 0xC0,
-        0x00, 0x07, 0xC0, 0x00, 0x0F, 0x80, 0x00, 0x1F, 0x00, 0x00, 0x1E, 0x00, 0x00, 0x3E, 0x00,
-        0x00, 0x7C, 0x00, 0x00, 0xF8, 0x00, 0x01, 0xF0, 0x00, 0x01, 0xE0, 0x00, 0x03, 0xE0, 0x00,
-        0x07, 0xC0, 0x00, 0x0F, 0x80, 0x00, 0x1F, 0x80, 0x00, 0x1F, 0xFF, 0xFE, 0x1F, 0xFF, 0xFE,
-        0x1F, 0xFF, 0xFE, 0x1F, 0xFF, 0xFC, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-    ], // 2
-    &[
-        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x7C, 0x00, 0x07, 0xFF, 0x00,
-        0x0F, 0xFF, 0xC0, 0x0F, 0xFF, 0xE0, 0x0F, 0xFF, 0xF0, 0x0E, 0x03, 0xF0, 0x08, 0x00, 0xF8,
-        0x00, 0x00, 0xF8, 0x00, 0x00, 0x78, 0x00, 0x00, 0x78, 0x00, 0x00, 0x78, 0x00, 0x00, 0x78,
-        0x00, 0x00, 0x78, 0x00, 0x00, 0x78, 0x00, 0x00, 0xF8, 0x00, 0x01, 0xF0, 0x00, 0x0F, 0xE0,
-        0x00, 0xFF, 0xC0, 0x00, 0xFF, 0x80, 0x00, 0xFF, 0x80, 0x00, 0xFF, 0xE0, 0x00, 0x03, 0xF0,
-        0x00, 0x01, 0xF0, 0x00, 0x00, 0xF8, 0x00, 0x00, 0x78, 0x00, 0x00, 0x7C, 0x00, 0x00, 0x3C,
-        0x00, 0x00, 0x3C, 0x00, 0x00, 0x3C, 0x00, 0x00, 0x3C, 0x00, 0x00, 0x3C, 0x00, 0x00, 0x7C,
-        0x00, 0x00, 0x78, 0x10, 0x00, 0xF8, 0x1C, 0x01, 0xF8, 0x1F, 0xFF, 0xF0, 0x1F, 0xFF, 0xE0,
-        0x1F, 0xFF, 0xC0, 0x0F, 0xFF, 0x80, 0x01, 0xFE, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-    ], // 3
-    &[
-        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x03, 0xC0,
-        0x00, 0x07, 0xE0, 0x00, 0x07, 0xE0, 0x00, 0x0F, 0xE0, 0x00, 0x0F, 0xE0, 0x00, 0x1F, 0xE0,
-        0x00, 0x1F, 0xE0, 0x00, 0x39, 0xE0, 0x00, 0x3B, 0xE0, 0x00, 0x73, 0xE0, 0x00, 0x73, 0xE0,
-        0x00, 0xF3, 0xE0, 0x00, 0xE3, 0xE0, 0x01, 0xE3, 0xE0, 0x01, 0xC3, 0xE0, 0x03, 0xC3, 0xE0,
-        0x03, 0x83, 0xE0, 0x07, 0x83, 0xE0, 0x07, 0x03, 0xE0, 0x0F, 0x03, 0xE0, 0x0F, 0x03, 0xE0,
-        0x0E, 0x03, 0xE0, 0x1E, 0x03, 0xE0, 0x1C, 0x03, 0xE0, 0x3C, 0x03, 0xE0, 0x3F, 0xFF, 0xFC,
-        0x3F, 0xFF, 0xFE, 0x3F, 0xFF, 0xFE, 0x3F, 0xFF, 0xFC, 0x00, 0x03, 0xE0, 0x00, 0x03, 0xE0,
-        0x00, 0x03, 0xE0, 0x00, 0x03, 0xE0, 0x00, 0x03, 0xE0, 0x00, 0x03, 0xE0, 0x00, 0x03, 0xE0,
-        0x00, 0x03, 0xE0, 0x00, 0x03, 0xE0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-    ], // 4
-    &[
-        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x0F, 0xFF, 0xF0,
-        0x0F, 0xFF, 0xF0, 0x0F, 0xFF, 0xF0, 0x0F, 0xFF, 0xF0, 0x0F, 0xFF, 0xF0, 0x0F, 0x00, 0x00,
-        0x0F, 0x00, 0x00, 0x0F, 0x00, 0x00, 0x0F, 0x00, 0x00, 0x0F, 0x00, 0x00, 0x0F, 0x00, 0x00,
-        0x0F, 0x00, 0x00, 0x0F, 0x00, 0x00, 0x0F, 0xFF, 0x00, 0x0F, 0xFF, 0x80, 0x0F, 0xFF, 0xE0,
-        0x0F, 0xFF, 0xE0, 0x0F, 0x8F, 0xF0, 0x0C, 0x01, 0xF8, 0x00, 0x00, 0xF8, 0x00, 0x00, 0x78,
-        0x00, 0x00, 0x7C, 0x00, 0x00, 0x3C, 0x00, 0x00, 0x3C, 0x00, 0x00, 0x3C, 0x00, 0x00, 0x3C,
-        0x00, 0x00, 0x3C, 0x00, 0x00, 0x3C, 0x00, 0x00, 0x3C, 0x00, 0x00, 0x3C, 0x00, 0x00, 0x7C,
-        0x00, 0x00, 0x78, 0x10, 0x00, 0xF8, 0x1C, 0x01, 0xF0, 0x1F, 0xFF, 0xF0, 0x1F, 0xFF, 0xE0,
-        0x1F, 0xFF, 0xC0, 0x0F, 0xFF, 0x80, 0x03, 0xFC, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-    ], // 5
-    &[
-        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x0F, 0x00, 0x00, 0x7F, 0xE0,
-        0x00, 0xFF, 0xF0, 0x01, 0xFF, 0xF0, 0x03, 0xFF, 0xF0, 0x03, 0xE0, 0x70, 0x07, 0xC0, 0x00,
-        0x07, 0x80, 0x00, 0x0F, 0x00, 0x00, 0x0F, 0x00, 0x00, 0x0F, 0x00, 0x00, 0x1E, 0x00, 0x00,
-        0x1E, 0x00, 0x00, 0x1E, 0x00, 0x00, 0x1E, 0x3F, 0x00, 0x1E, 0x7F, 0xC0, 0x1C, 0xFF, 0xE0,
-        0x1D, 0xFF, 0xF0, 0x1F, 0xE1, 0xF0, 0x1F, 0x80, 0xF8, 0x1F, 0x80, 0x78, 0x1F, 0x00, 0x78,
-        0x1F, 0x00, 0x7C, 0x1F, 0x00, 0x3C, 0x1E, 0x00, 0x3C, 0x1E, 0x00, 0x3C, 0x1E, 0x00, 0x3C,
-        0x1E, 0x00, 0x3C, 0x1E, 0x00, 0x3C, 0x1E, 0x00, 0x3C, 0x0F, 0x00, 0x3C, 0x0F, 0x00, 0x7C,
-        0x0F, 0x00, 0x78, 0x0F, 0x80, 0xF8, 0x07, 0xC0, 0xF8, 0x07, 0xE3, 0xF0, 0x03, 0xFF, 0xE0,
-        0x01, 0xFF, 0xE0, 0x00, 0xFF, 0xC0, 0x00, 0x3F, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-    ], // 6
-    &[
-        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x1F, 0xFF, 0xFC,
-        0x1F, 0xFF, 0xFC, 0x1F, 0xFF, 0xFC, 0x1F, 0xFF, 0xFC, 0x1F, 0xFF, 0xF8, 0x00, 0x00, 0x78,
-        0x00, 0x00, 0xF8, 0x00, 0x00, 0xF0, 0x00, 0x00, 0xF0, 0x00, 0x00, 0xF0, 0x00, 0x01, 0xE0,
-        0x00, 0x01, 0xE0, 0x00, 0x01, 0xE0, 0x00, 0x03, 0xE0, 0x00, 0x03, 0xC0, 0x00, 0x03, 0xC0,
-        0x00, 0x07, 0xC0, 0x00, 0x07, 0x80, 0x00, 0x07, 0x80, 0x00, 0x0F, 0x80, 0x00, 0x0F, 0x00,
-        0x00, 0x0F, 0x00, 0x00, 0x1F, 0x00, 0x00, 0x1F, 0x00, 0x00, 0x1E, 0x00, 0x00, 0x3E, 0x00,
-        0x00, 0x3E, 0x00, 0x00, 0x3C, 0x00, 0x00, 0x3C, 0x00, 0x00, 0x7C, 0x00, 0x00, 0x78, 0x00,
-        0x00, 0x78, 0x00, 0x00, 0xF8, 0x00, 0x00, 0xF0, 0x00, 0x00, 0xF0, 0x00, 0x01, 0xF0, 0x00,
-        0x01, 0xE0, 0x00, 0x01, 0xE0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-    ], // 7
-    &[
-        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x3C, 0x00, 0x00, 0xFF, 0x80,
-        0x03, 0xFF, 0xC0, 0x03, 0xFF, 0xE0, 0x07, 0xFF, 0xF0, 0x0F, 0xC1, 0xF0, 0x0F, 0x80, 0xF8,
-        0x0F, 0x00, 0x78, 0x0F, 0x00, 0x78, 0x1F, 0x00, 0x78, 0x1F, 0x00, 0x78, 0x1F, 0x00, 0x78,
-        0x0F, 0x00, 0x78, 0x0F, 0x00, 0x78, 0x0F, 0x00, 0xF0, 0x07, 0x80, 0xF0, 0x07, 0xC1, 0xE0,
-        0x03, 0xFF, 0xC0, 0x00, 0xFF, 0x80, 0x00, 0xFF, 0x80, 0x03, 0xFF, 0xE0, 0x07, 0xE7, 0xF0,
-        0x0F, 0x80, 0xF0, 0x0F, 0x00, 0x78, 0x1F, 0x00, 0x78, 0x1E, 0x00, 0x7C, 0x1E, 0x00, 0x3C,
-        0x1E, 0x00, 0x3C, 0x1E, 0x00, 0x3C, 0x1E, 0x00, 0x3C, 0x1E, 0x00, 0x3C, 0x1E, 0x00, 0x7C,
-        0x1F, 0x00, 0x7C, 0x1F, 0x00, 0x78, 0x0F, 0x80, 0xF8, 0x0F, 0xE3, 0xF0, 0x07, 0xFF, 0xF0,
-        0x03, 0xFF, 0xE0, 0x01, 0xFF, 0xC0, 0x00, 0x7F, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-    ], // 8
-    &[
-        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x3C, 0x00, 0x01, 0xFF, 0x00,
-        0x03, 0xFF, 0x80, 0x07, 0xFF, 0xC0, 0x07, 0xFF, 0xE0, 0x0F, 0x81, 0xE0, 0x0F, 0x00, 0xF0,
-        0x1F, 0x00, 0xF0, 0x1E, 0x00, 0x78, 0x1E, 0x00, 0x78, 0x1E, 0x00, 0x78, 0x1E, 0x00, 0x78,
-        0x1E, 0x00, 0x78, 0x1E, 0x00, 0x78, 0x1E, 0x00, 0x7C, 0x1E, 0x00, 0x7C, 0x1E, 0x00, 0x7C,
-        0x1E, 0x00, 0x7C, 0x1E, 0x00, 0xFC, 0x1F, 0x00, 0xFC, 0x0F, 0x01, 0xFC, 0x0F, 0x83, 0xFC,
-        0x07, 0xFF, 0xFC, 0x07, 0xFF, 0xBC, 0x03, 0xFF, 0x3C, 0x00, 0xFE, 0x3C, 0x00, 0x00, 0x38,
-        0x00, 0x00, 0x78, 0x00, 0x00, 0x78, 0x00, 0x00, 0x78, 0x00, 0x00, 0x78, 0x00, 0x00, 0xF0,
-        0x00, 0x00, 0xF0, 0x00, 0x01, 0xF0, 0x06, 0x03, 0xE0, 0x07, 0xFF, 0xC0, 0x07, 0xFF, 0xC0,
-        0x07, 0xFF, 0x80, 0x07, 0xFF, 0x00, 0x01, 0xFC, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-    ], // 9
-    &[
-        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xFE, 0x00, 0x00, 0xFE, 0x00, 0x00, 0xFE, 0x00,
-        0x00, 0xFE, 0x00, 0x00, 0xFE, 0x00, 0x00, 0xFE, 0x00, 0x00, 0xFE, 0x00, 0x00, 0xFE, 0x00,
-        0x00, 0xFE, 0x00, 0x00, 0xFE, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-    ], // .
-];
+use crate::{
+    log::{error, Module},
+    rle,
+};
 
-pub static GROTESK_24_48_INDEX: &[char; 11] =
-    &['0', '1', '2', '3', '4', '5', '6', '7', '8', '9', '.'];
+const LOG_MODULE: Module = Module::Display;
 
-pub static ARIAL_ROUND_16_24: &[&[u8; 48]; 65] = &[
-    &[
-        0x00, 0x00, 0x07, 0xE0, 0x0F, 0xF0, 0x1F, 0xF8, 0x1C, 0x38, 0x38, 0x1C, 0x38, 0x1C, 0x38,
-        0x1C, 0x38, 0x1C, 0x38, 0x1C, 0x38, 0x1C, 0x38, 0x1C, 0x38, 0x1C, 0x38, 0x1C, 0x38, 0x1C,
-        0x1C, 0x38, 0x1F, 0xF8, 0x0F, 0xF0, 0x07, 0xE0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00,
-    ], // 0
-    &[
-        0x00, 0x00, 0x00, 0x60, 0x00, 0xE0, 0x01, 0xE0, 0x03, 0xE0, 0x07, 0xE0, 0x1F, 0xE0, 0x1C,
-        0xE0, 0x18, 0xE0, 0x00, 0xE0, 0x00, 0xE0, 0x00, 0xE0, 0x00, 0xE0, 0x00, 0xE0, 0x00, 0xE0,
-        0x00, 0xE0, 0x00, 0xE0, 0x00, 0xE0, 0x00, 0xE0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00,
-    ], // 1
-    &[
-        0x00, 0x00, 0x07, 0xE0, 0x1F, 0xF8, 0x1F, 0xF8, 0x3C, 0x3C, 0x38, 0x1C, 0x38, 0x1C, 0x00,
-        0x1C, 0x00, 0x38, 0x00, 0x78, 0x00, 0xF0, 0x03, 0xE0, 0x07, 0x80, 0x0F, 0x00, 0x1E, 0x00,
-        0x3C, 0x00, 0x3F, 0xF8, 0x3F, 0xFC, 0x3F, 0xFC, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00,
-    ], // 2
-    &[
-        0x00, 0x00, 0x07, 0xE0, 0x0F, 0xF0, 0x1F, 0xF8, 0x3C, 0x78, 0x38, 0x38, 0x00, 0x38, 0x00,
-        0x78, 0x01, 0xF0, 0x01, 0xF0, 0x00, 0xF8, 0x00, 0x3C, 0x30, 0x1C, 0x38, 0x1C, 0x38, 0x3C,
-        0x3C, 0x78, 0x1F, 0xF8, 0x0F, 0xF0, 0x07, 0xE0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00,
-    ], // 3
-    &[
-        0x00, 0x00, 0x00, 0x60, 0x00, 0xF0, 0x00, 0xF0, 0x01, 0xF0, 0x03, 0xF0, 0x03, 0x70, 0x07,
-        0x70, 0x0E, 0x70, 0x0C, 0x70, 0x1C, 0x70, 0x38, 0x70, 0x38, 0x70, 0x3F, 0xFC, 0x3F, 0xFC,
-        0x00, 0x70, 0x00, 0x70, 0x00, 0x70, 0x00, 0x70, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00,
-    ], // 4
-    &[
-        0x00, 0x00, 0x1F, 0xFC, 0x1F, 0xFC, 0x1F, 0xF8, 0x1C, 0x00, 0x1C, 0x00, 0x1C, 0x00, 0x1F,
-        0xE0, 0x1F, 0xF0, 0x1F, 0xF8, 0x1C, 0x3C, 0x00, 0x1C, 0x00, 0x1C, 0x00, 0x1C, 0x38, 0x1C,
-        0x3C, 0x38, 0x3F, 0xF8, 0x1F, 0xF0, 0x07, 0xE0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00,
-    ], // 5
-    &[
-        0x00, 0x00, 0x03, 0xE0, 0x0F, 0xF0, 0x1F, 0xF8, 0x1C, 0x38, 0x3C, 0x18, 0x38, 0x00, 0x38,
-        0x00, 0x3B, 0xE0, 0x3F, 0xF0, 0x3F, 0xF8, 0x3C, 0x3C, 0x38, 0x1C, 0x38, 0x1C, 0x38, 0x1C,
-        0x1C, 0x3C, 0x1F, 0xF8, 0x0F, 0xF0, 0x03, 0xE0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00,
-    ], // 6
-    &[
-        0x00, 0x00, 0x3F, 0xFC, 0x3F, 0xFC, 0x1F, 0xFC, 0x00, 0x38, 0x00, 0x70, 0x00, 0x70, 0x00,
-        0xE0, 0x01, 0xC0, 0x01, 0xC0, 0x03, 0x80, 0x03, 0x80, 0x03, 0x80, 0x07, 0x80, 0x07, 0x00,
-        0x07, 0x00, 0x07, 0x00, 0x07, 0x00, 0x06, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00,
-    ], // 7
-    &[
-        0x00, 0x00, 0x07, 0xE0, 0x0F, 0xF0, 0x1F, 0xF8, 0x1E, 0x78, 0x1C, 0x38, 0x1C, 0x38, 0x1E,
-        0x78, 0x0F, 0xF0, 0x0F, 0xF0, 0x1C, 0x38, 0x38, 0x1C, 0x38, 0x1C, 0x38, 0x1C, 0x38, 0x1C,
-        0x3C, 0x3C, 0x1F, 0xF8, 0x0F, 0xF0, 0x07, 0xE0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00,
-    ], // 8
-    &[
-        0x00, 0x00, 0x07, 0xC0, 0x0F, 0xF0, 0x1F, 0xF8, 0x3C, 0x38, 0x38, 0x1C, 0x38, 0x1C, 0x38,
-        0x1C, 0x3C, 0x3C, 0x1F, 0xFC, 0x0F, 0xFC, 0x07, 0xDC, 0x00, 0x1C, 0x00, 0x1C, 0x18, 0x3C,
-        0x1C, 0x38, 0x1F, 0xF8, 0x0F, 0xF0, 0x07, 0xC0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00,
-    ], // 9
-    &[
-        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x03, 0x80, 0x03, 0x80, 0x03, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00,
-    ], // .
-    &[
-        0x00, 0x00, 0x00, 0x00, 0x01, 0x80, 0x03, 0xC0, 0x03, 0xC0, 0x07, 0xE0, 0x07, 0xE0, 0x0E,
-        0x60, 0x0E, 0x70, 0x0E, 0x70, 0x1C, 0x78, 0x1C, 0x38, 0x1C, 0x38, 0x3F, 0xFC, 0x3F, 0xFC,
-        0x38, 0x1C, 0x70, 0x0E, 0x70, 0x0E, 0x60, 0x0E, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00,
-    ], // A
-    &[
-        0x00, 0x00, 0x00, 0x00, 0x3F, 0xE0, 0x3F, 0xF0, 0x38, 0x78, 0x38, 0x38, 0x38, 0x38, 0x38,
-        0x38, 0x38, 0x38, 0x3F, 0xF0, 0x3F, 0xF0, 0x38, 0x38, 0x38, 0x1C, 0x38, 0x1C, 0x38, 0x1C,
-        0x38, 0x1C, 0x38, 0x3C, 0x3F, 0xF8, 0x3F, 0xF0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00,
-    ], // B
-    &[
-        0x00, 0x00, 0x00, 0x00, 0x07, 0xE0, 0x0F, 0xF0, 0x1E, 0x78, 0x3C, 0x3C, 0x38, 0x1C, 0x78,
-        0x0C, 0x70, 0x00, 0x70, 0x00, 0x70, 0x00, 0x70, 0x00, 0x70, 0x0C, 0x70, 0x0C, 0x38, 0x1C,
-        0x3C, 0x3C, 0x1E, 0x78, 0x0F, 0xF0, 0x07, 0xE0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00,
-    ], // C
-    &[
-        0x00, 0x00, 0x00, 0x00, 0x3F, 0xC0, 0x3F, 0xF0, 0x38, 0xF0, 0x38, 0x78, 0x38, 0x38, 0x38,
-        0x1C, 0x38, 0x1C, 0x38, 0x1C, 0x38, 0x1C, 0x38, 0x1C, 0x38, 0x1C, 0x38, 0x1C, 0x38, 0x38,
-        0x38, 0x78, 0x38, 0xF0, 0x3F, 0xF0, 0x3F, 0xC0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00,
-    ], // D
-    &[
-        0x00, 0x00, 0x00, 0x00, 0x1F, 0xFC, 0x1F, 0xFC, 0x1C, 0x00, 0x1C, 0x00, 0x1C, 0x00, 0x1C,
-        0x00, 0x1C, 0x00, 0x1F, 0xF8, 0x1F, 0xF8, 0x1C, 0x00, 0x1C, 0x00, 0x1C, 0x00, 0x1C, 0x00,
-        0x1C, 0x00, 0x1C, 0x00, 0x1F, 0xFC, 0x1F, 0xFC, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00,
-    ], // E
-    &[
-        0x00, 0x00, 0x00, 0x00, 0x0F, 0xFC, 0x0F, 0xFC, 0x0E, 0x00, 0x0E, 0x00, 0x0E, 0x00, 0x0E,
-        0x00, 0x0E, 0x00, 0x0F, 0xF8, 0x0F, 0xF8, 0x0E, 0x00, 0x0E, 0x00, 0x0E, 0x00, 0x0E, 0x00,
-        0x0E, 0x00, 0x0E, 0x00, 0x0E, 0x00, 0x0E, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00,
-    ], // F
-    &[
-        0x00, 0x00, 0x00, 0x00, 0x03, 0xE0, 0x0F, 0xF8, 0x1C, 0x3C, 0x38, 0x1C, 0x38, 0x0E, 0x70,
-        0x0C, 0x70, 0x00, 0x70, 0x00, 0x70, 0xFE, 0x70, 0xFE, 0x70, 0x0E, 0x70, 0x0E, 0x38, 0x0E,
-        0x38, 0x0E, 0x1C, 0x3E, 0x0F, 0xFC, 0x03, 0xF0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00,
-    ], // G
-    &[
-        0x00, 0x00, 0x00, 0x00, 0x38, 0x1C, 0x38, 0x1C, 0x38, 0x1C, 0x38, 0x1C, 0x38, 0x1C, 0x38,
-        0x1C, 0x38, 0x1C, 0x3F, 0xFC, 0x3F, 0xFC, 0x38, 0x1C, 0x38, 0x1C, 0x38, 0x1C, 0x38, 0x1C,
-        0x38, 0x1C, 0x38, 0x1C, 0x38, 0x1C, 0x38, 0x1C, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00,
-    ], // H
-    &[
-        0x00, 0x00, 0x00, 0x00, 0x03, 0x80, 0x03, 0x80, 0x03, 0x80, 0x03, 0x80, 0x03, 0x80, 0x03,
-        0x80, 0x03, 0x80, 0x03, 0x80, 0x03, 0x80, 0x03, 0x80, 0x03, 0x80, 0x03, 0x80, 0x03, 0x80,
-        0x03, 0x80, 0x03, 0x80, 0x03, 0x80, 0x03, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00,
-    ], // I
-    &[
-        0x00, 0x00, 0x00, 0x00, 0x00, 0xE0, 0x00, 0xE0, 0x00, 0xE0, 0x00, 0xE0, 0x00, 0xE0, 0x00,
-        0xE0, 0x00, 0xE0, 0x00, 0xE0, 0x00, 0xE0, 0x00, 0xE0, 0x70, 0xE0, 0x70, 0xE0, 0x70, 0xE0,
-        0x79, 0xE0, 0x3F, 0xC0, 0x3F, 0xC0, 0x0F, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00,
-    ], // J
-    &[
-        0x00, 0x00, 0x00, 0x00, 0x38, 0x0C, 0x38, 0x1C, 0x38, 0x3C, 0x38, 0x78, 0x38, 0xF0, 0x39,
-        0xE0, 0x3B, 0xC0, 0x3F, 0xC0, 0x3F, 0xE0, 0x3E, 0xF0, 0x3C, 0x70, 0x38, 0x78, 0x38, 0x3C,
-        0x38, 0x3C, 0x38, 0x1E, 0x38, 0x0E, 0x38, 0x0E, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00,
-    ], // K
-    &[
-        0x00, 0x00, 0x00, 0x00, 0x1C, 0x00, 0x1C, 0x00, 0x1C, 0x00, 0x1C, 0x00, 0x1C, 0x00, 0x1C,
-        0x00, 0x1C, 0x00, 0x1C, 0x00, 0x1C, 0x00, 0x1C, 0x00, 0x1C, 0x00, 0x1C, 0x00, 0x1C, 0x00,
-        0x1C, 0x00, 0x1C, 0x00, 0x1F, 0xF8, 0x1F, 0xF8, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00,
-    ], // L
-    &[
-        0x00, 0x00, 0x00, 0x00, 0xF8, 0x1F, 0xF8, 0x1F, 0xFC, 0x3F, 0xFC, 0x3F, 0xFC, 0x3F, 0xEC,
-        0x37, 0xEE, 0x77, 0xEE, 0x77, 0xEE, 0x77, 0xE6, 0x67, 0xE7, 0xE7, 0xE7, 0xE7, 0xE7, 0xE7,
-        0xE3, 0xC7, 0xE3, 0xC7, 0xE3, 0xC7, 0xE1, 0x87, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00,
-    ], // M
-    &[
-        0x00, 0x00, 0x00, 0x00, 0x18, 0x1C, 0x3C, 0x1C, 0x3C, 0x1C, 0x3E, 0x1C, 0x3E, 0x1C, 0x3F,
-        0x1C, 0x3B, 0x1C, 0x3B, 0x9C, 0x39, 0x9C, 0x39, 0xDC, 0x38, 0xDC, 0x38, 0xFC, 0x38, 0x7C,
-        0x38, 0x7C, 0x38, 0x3C, 0x38, 0x3C, 0x38, 0x1C, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00,
-    ], // N
-    &[
-        0x00, 0x00, 0x00, 0x00, 0x07, 0xE0, 0x0F, 0xF0, 0x1F, 0xF8, 0x3C, 0x3C, 0x38, 0x1C, 0x78,
-        0x1E, 0x70, 0x0E, 0x70, 0x0E, 0x70, 0x0E, 0x70, 0x0E, 0x70, 0x0E, 0x78, 0x1E, 0x38, 0x1C,
-        0x3C, 0x3C, 0x1F, 0xF8, 0x0F, 0xF0, 0x07, 0xE0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00,
-    ], // O
-    &[
-        0x00, 0x00, 0x00, 0x00, 0x3F, 0xF0, 0x3F, 0xF8, 0x38, 0x3C, 0x38, 0x1C, 0x38, 0x1C, 0x38,
-        0x1C, 0x38, 0x1C, 0x38, 0x38, 0x3F, 0xF8, 0x3F, 0xE0, 0x38, 0x00, 0x38, 0x00, 0x38, 0x00,
-        0x38, 0x00, 0x38, 0x00, 0x38, 0x00, 0x38, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00,
-    ], // P
-    &[
-        0x00, 0x00, 0x00, 0x00, 0x07, 0xE0, 0x0F, 0xF0, 0x1F, 0xF8, 0x3C, 0x3C, 0x38, 0x1C, 0x78,
-        0x0E, 0x70, 0x0E, 0x70, 0x0E, 0x70, 0x0E, 0x70, 0x0E, 0x70, 0x0E, 0x79, 0x9E, 0x38, 0xFC,
-        0x3C, 0x7C, 0x1F, 0xF8, 0x0F, 0xFE, 0x07, 0xEF, 0x00, 0x07, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00,
-    ], // Q
-    &[
-        0x00, 0x00, 0x00, 0x00, 0x3F, 0xF8, 0x3F, 0xFC, 0x38, 0x1E, 0x38, 0x0E, 0x38, 0x0E, 0x38,
-        0x0E, 0x38, 0x1E, 0x3F, 0xFC, 0x3F, 0xF0, 0x38, 0xE0, 0x38, 0x70, 0x38, 0x78, 0x38, 0x3C,
-        0x38, 0x3C, 0x38, 0x1E, 0x38, 0x0E, 0x38, 0x0E, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00,
-    ], // R
-    &[
-        0x00, 0x00, 0x00, 0x00, 0x07, 0xE0, 0x1F, 0xF0, 0x1C, 0x38, 0x38, 0x38, 0x38, 0x18, 0x3C,
-        0x00, 0x3F, 0x00, 0x1F, 0xE0, 0x0F, 0xF8, 0x01, 0xF8, 0x00, 0x3C, 0x30, 0x1C, 0x38, 0x1C,
-        0x38, 0x1C, 0x3C, 0x38, 0x1F, 0xF8, 0x07, 0xE0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00,
-    ], // S
-    &[
-        0x00, 0x00, 0x00, 0x00, 0x7F, 0xFC, 0x7F, 0xFC, 0x03, 0x80, 0x03, 0x80, 0x03, 0x80, 0x03,
-        0x80, 0x03, 0x80, 0x03, 0x80, 0x03, 0x80, 0x03, 0x80, 0x03, 0x80, 0x03, 0x80, 0x03, 0x80,
-        0x03, 0x80, 0x03, 0x80, 0x03, 0x80, 0x03, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00,
-    ], // T
-    &[
-        0x00, 0x00, 0x00, 0x00, 0x38, 0x1C, 0x38, 0x1C, 0x38, 0x1C, 0x38, 0x1C, 0x38, 0x1C, 0x38,
-        0x1C, 0x38, 0x1C, 0x38, 0x1C, 0x38, 0x1C, 0x38, 0x1C, 0x38, 0x1C, 0x38, 0x1C, 0x38, 0x1C,
-        0x3C, 0x38, 0x1F, 0xF8, 0x0F, 0xF0, 0x07, 0xE0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00,
-    ], // U
-    &[
-        0x00, 0x00, 0x00, 0x00, 0x38, 0x06, 0x38, 0x0E, 0x38, 0x0E, 0x1C, 0x1C, 0x1C, 0x1C, 0x1C,
-        0x1C, 0x0E, 0x18, 0x0E, 0x38, 0x0E, 0x38, 0x0E, 0x30, 0x07, 0x70, 0x07, 0x70, 0x07, 0x60,
-        0x03, 0xE0, 0x03, 0xE0, 0x03, 0xC0, 0x01, 0xC0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00,
-    ], // V
-    &[
-        0x00, 0x00, 0x00, 0x00, 0xC1, 0x83, 0xC3, 0xC3, 0xE3, 0xC7, 0xE3, 0xC7, 0xE3, 0xC7, 0x63,
-        0xC6, 0x66, 0x66, 0x66, 0x66, 0x76, 0x6E, 0x76, 0x6E, 0x36, 0x6C, 0x3C, 0x3C, 0x3C, 0x3C,
-        0x3C, 0x3C, 0x3C, 0x3C, 0x1C, 0x38, 0x18, 0x18, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00,
-    ], // W
-    &[
-        0x00, 0x00, 0x00, 0x00, 0x30, 0x0C, 0x38, 0x1E, 0x3C, 0x1C, 0x1C, 0x3C, 0x1E, 0x78, 0x0F,
-        0xF0, 0x07, 0xF0, 0x07, 0xE0, 0x03, 0xC0, 0x07, 0xE0, 0x0F, 0xF0, 0x1F, 0xF8, 0x1E, 0x78,
-        0x3C, 0x3C, 0x78, 0x1E, 0x78, 0x1E, 0x70, 0x0E, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00,
-    ], // X
-    &[
-        0x00, 0x00, 0x00, 0x00, 0x30, 0x18, 0x78, 0x1C, 0x38, 0x38, 0x3C, 0x38, 0x1C, 0x70, 0x1E,
-        0xF0, 0x0E, 0xE0, 0x07, 0xC0, 0x07, 0xC0, 0x03, 0x80, 0x03, 0x80, 0x03, 0x80, 0x03, 0x80,
-        0x03, 0x80, 0x03, 0x80, 0x03, 0x80, 0x03, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00,
-    ], // Y
-    &[
-        0x00, 0x00, 0x00, 0x00, 0x3F, 0xF8, 0x3F, 0xF8, 0x00, 0x78, 0x00, 0x78, 0x00, 0xF0, 0x01,
-        0xF0, 0x01, 0xE0, 0x03, 0xC0, 0x07, 0x80, 0x07, 0x80, 0x0F, 0x00, 0x1E, 0x00, 0x1E, 0x00,
-        0x3C, 0x00, 0x78, 0x00, 0x7F, 0xFC, 0x7F, 0xFC, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00,
-    ], // Z
-    &[
-        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x0F,
-        0xC0, 0x1F, 0xE0, 0x18, 0x70, 0x10, 0x70, 0x00, 0xF0, 0x0F, 0xF0, 0x1E, 0x70, 0x38, 0x70,
-        0x38, 0x70, 0x38, 0xF0, 0x1F, 0xF0, 0x0E, 0x38, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00,
-    ], // a
-    &[
-        0x00, 0x00, 0x00, 0x00, 0x1C, 0x00, 0x1C, 0x00, 0x1C, 0x00, 0x1C, 0x00, 0x1C, 0x00, 0x1D,
-        0xF0, 0x1F, 0xF8, 0x1E, 0x38, 0x1E, 0x1C, 0x1C, 0x1C, 0x1C, 0x1C, 0x1C, 0x1C, 0x1C, 0x1C,
-        0x1E, 0x1C, 0x1E, 0x38, 0x1F, 0xF8, 0x1D, 0xE0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00,
-    ], // b
-    &[
-        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x07,
-        0xC0, 0x0F, 0xF0, 0x1C, 0x70, 0x3C, 0x38, 0x38, 0x10, 0x38, 0x00, 0x38, 0x00, 0x38, 0x10,
-        0x3C, 0x38, 0x1C, 0x78, 0x0F, 0xF0, 0x07, 0xC0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00,
-    ], // c
-    &[
-        0x00, 0x00, 0x00, 0x00, 0x00, 0x38, 0x00, 0x38, 0x00, 0x38, 0x00, 0x38, 0x00, 0x38, 0x0F,
-        0xB8, 0x1F, 0xF8, 0x1C, 0x78, 0x38, 0x78, 0x38, 0x38, 0x38, 0x38, 0x38, 0x38, 0x38, 0x38,
-        0x38, 0x78, 0x1C, 0x78, 0x1F, 0xF8, 0x07, 0xB8, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00,
-    ], // d
-    &[
-        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x07,
-        0xC0, 0x0F, 0xE0, 0x1C, 0x70, 0x38, 0x38, 0x38, 0x38, 0x3F, 0xF8, 0x3F, 0xF0, 0x38, 0x00,
-        0x38, 0x10, 0x1C, 0x38, 0x0F, 0xF0, 0x07, 0xC0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00,
-    ], // e
-    &[
-        0x00, 0x00, 0x00, 0x00, 0x03, 0xE0, 0x07, 0xE0, 0x07, 0x00, 0x07, 0x00, 0x07, 0x00, 0x1F,
-        0xC0, 0x1F, 0xC0, 0x07, 0x00, 0x07, 0x00, 0x07, 0x00, 0x07, 0x00, 0x07, 0x00, 0x07, 0x00,
-        0x07, 0x00, 0x07, 0x00, 0x07, 0x00, 0x07, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00,
-    ], // f
-    &[
-        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x0F,
-        0xB8, 0x1F, 0xF8, 0x1C, 0x78, 0x38, 0x38, 0x38, 0x38, 0x38, 0x38, 0x38, 0x38, 0x38, 0x38,
-        0x38, 0x38, 0x1C, 0x78, 0x1F, 0xF8, 0x07, 0xB8, 0x10, 0x38, 0x38, 0x38, 0x1C, 0x70, 0x1F,
-        0xF0, 0x07, 0xC0,
-    ], // g
-    &[
-        0x00, 0x00, 0x00, 0x00, 0x38, 0x00, 0x38, 0x00, 0x38, 0x00, 0x38, 0x00, 0x38, 0x00, 0x3B,
-        0xC0, 0x3F, 0xE0, 0x3C, 0xF0, 0x38, 0x70, 0x38, 0x70, 0x38, 0x70, 0x38, 0x70, 0x38, 0x70,
-        0x38, 0x70, 0x38, 0x70, 0x38, 0x70, 0x38, 0x70, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00,
-    ], // h
-    &[
-        0x00, 0x00, 0x00, 0x00, 0x03, 0x80, 0x03, 0x80, 0x03, 0x80, 0x00, 0x00, 0x00, 0x00, 0x03,
-        0x80, 0x03, 0x80, 0x03, 0x80, 0x03, 0x80, 0x03, 0x80, 0x03, 0x80, 0x03, 0x80, 0x03, 0x80,
-        0x03, 0x80, 0x03, 0x80, 0x03, 0x80, 0x03, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00,
-    ], // i
-    &[
-        0x00, 0x00, 0x00, 0x00, 0x03, 0x80, 0x03, 0x80, 0x03, 0x80, 0x00, 0x00, 0x00, 0x00, 0x03,
-        0x80, 0x03, 0x80, 0x03, 0x80, 0x03, 0x80, 0x03, 0x80, 0x03, 0x80, 0x03, 0x80, 0x03, 0x80,
-        0x03, 0x80, 0x03, 0x80, 0x03, 0x80, 0x03, 0x80, 0x03, 0x80, 0x03, 0x80, 0x03, 0x80, 0x1F,
-        0x80, 0x1F, 0x00,
-    ], // j
-    &[
-        0x00, 0x00, 0x00, 0x00, 0x0E, 0x00, 0x0E, 0x00, 0x0E, 0x00, 0x0E, 0x00, 0x0E, 0x00, 0x0E,
-        0x18, 0x0E, 0x38, 0x0E, 0x70, 0x0E, 0xE0, 0x0F, 0xC0, 0x0F, 0xE0, 0x0F, 0x70, 0x0E, 0x70,
-        0x0E, 0x38, 0x0E, 0x38, 0x0E, 0x1C, 0x0E, 0x0C, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00,
-    ], // k
-    &[
-        0x00, 0x00, 0x00, 0x00, 0x03, 0x80, 0x03, 0x80, 0x03, 0x80, 0x03, 0x80, 0x03, 0x80, 0x03,
-        0x80, 0x03, 0x80, 0x03, 0x80, 0x03, 0x80, 0x03, 0x80, 0x03, 0x80, 0x03, 0x80, 0x03, 0x80,
-        0x03, 0x80, 0x03, 0x80, 0x03, 0x80, 0x03, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00,
-    ], // l
-    &[
-        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xEF,
-        0x9E, 0xFF, 0xFF, 0xF1, 0xE7, 0xE1, 0xC7, 0xE1, 0xC7, 0xE1, 0xC7, 0xE1, 0xC7, 0xE1, 0xC7,
-        0xE1, 0xC7, 0xE1, 0xC7, 0xE1, 0xC7, 0xE1, 0xC7, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00,
-    ], // m
-    &[
-        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x1D,
-        0xE0, 0x1F, 0xF0, 0x1E, 0x78, 0x1C, 0x38, 0x1C, 0x38, 0x1C, 0x38, 0x1C, 0x38, 0x1C, 0x38,
-        0x1C, 0x38, 0x1C, 0x38, 0x1C, 0x38, 0x1C, 0x38, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00,
-    ], // n
-    &[
-        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x0F,
-        0x80, 0x1F, 0xC0, 0x38, 0xE0, 0x70, 0x70, 0x70, 0x70, 0x70, 0x70, 0x70, 0x70, 0x70, 0x70,
-        0x70, 0x70, 0x38, 0xE0, 0x1F, 0xC0, 0x0F, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00,
-    ], // o
-    &[
-        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x3B,
-        0xC0, 0x3F, 0xF0, 0x3C, 0x70, 0x3C, 0x38, 0x38, 0x38, 0x38, 0x38, 0x38, 0x38, 0x38, 0x38,
-        0x3C, 0x38, 0x3C, 0x70, 0x3F, 0xF0, 0x3B, 0xC0, 0x38, 0x00, 0x38, 0x00, 0x38, 0x00, 0x38,
-        0x00, 0x38, 0x00,
-    ], // p
-    &[
-        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x07,
-        0xB8, 0x1F, 0xF8, 0x1C, 0x78, 0x38, 0x78, 0x38, 0x38, 0x38, 0x38, 0x38, 0x38, 0x38, 0x38,
-        0x38, 0x78, 0x1C, 0x78, 0x1F, 0xF8, 0x07, 0xB8, 0x00, 0x38, 0x00, 0x38, 0x00, 0x38, 0x00,
-        0x38, 0x00, 0x38,
-    ], // q
-    &[
-        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x0E,
-        0xE0, 0x0F, 0xF0, 0x0F, 0x10, 0x0E, 0x00, 0x0E, 0x00, 0x0E, 0x00, 0x0E, 0x00, 0x0E, 0x00,
-        0x0E, 0x00, 0x0E, 0x00, 0x0E, 0x00, 0x0E, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00,
-    ], // r
-    &[
-        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x07,
-        0xC0, 0x0F, 0xF0, 0x1C, 0x70, 0x1C, 0x30, 0x1F, 0x00, 0x0F, 0xE0, 0x03, 0xF0, 0x00, 0x78,
-        0x1C, 0x38, 0x1E, 0x38, 0x0F, 0xF0, 0x07, 0xE0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00,
-    ], // s
-    &[
-        0x00, 0x00, 0x00, 0x00, 0x03, 0x80, 0x03, 0x80, 0x03, 0x80, 0x03, 0x80, 0x03, 0x80, 0x07,
-        0xE0, 0x0F, 0xE0, 0x03, 0x80, 0x03, 0x80, 0x03, 0x80, 0x03, 0x80, 0x03, 0x80, 0x03, 0x80,
-        0x03, 0x80, 0x03, 0x80, 0x03, 0xF0, 0x01, 0xF0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00,
-    ], // t
-    &[
-        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x1C,
-        0x38, 0x1C, 0x38, 0x1C, 0x38, 0x1C, 0x38, 0x1C, 0x38, 0x1C, 0x38, 0x1C, 0x38, 0x1C, 0x38,
-        0x1C, 0x38, 0x1E, 0x78, 0x0F, 0xF8, 0x07, 0xB8, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00,
-    ], // u
-    &[
-        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x18,
-        0x18, 0x1C, 0x38, 0x1C, 0x38, 0x0C, 0x30, 0x0E, 0x70, 0x0E, 0x70, 0x06, 0x60, 0x07, 0xE0,
-        0x03, 0xC0, 0x03, 0xC0, 0x03, 0xC0, 0x01, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00,
-    ], // v
-    &[
-        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xC1,
-        0x83, 0xE3, 0xC7, 0xE3, 0xC7, 0x63, 0xC6, 0x67, 0xE6, 0x76, 0x6E, 0x36, 0x6C, 0x36, 0x6C,
-        0x3E, 0x7C, 0x1C, 0x38, 0x1C, 0x38, 0x1C, 0x38, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00,
-    ], // w
-    &[
-        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x38,
-        0x38, 0x3C, 0x78, 0x1E, 0xF0, 0x0F, 0xE0, 0x07, 0xC0, 0x07, 0xC0, 0x0F, 0xE0, 0x0E, 0xE0,
-        0x1E, 0xF0, 0x3C, 0x78, 0x38, 0x38, 0x30, 0x38, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00,
-    ], // x
-    &[
-        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x18,
-        0x18, 0x1C, 0x38, 0x1C, 0x38, 0x0C, 0x30, 0x0E, 0x70, 0x0E, 0x70, 0x06, 0x60, 0x07, 0x60,
-        0x07, 0xE0, 0x03, 0xC0, 0x03, 0xC0, 0x03, 0xC0, 0x01, 0x80, 0x03, 0x80, 0x03, 0x80, 0x1F,
-        0x00, 0x1E, 0x00,
-    ], // y
-    &[
-        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x3F,
-        0xF8, 0x3F, 0xF8, 0x00, 0x78, 0x00, 0xF0, 0x01, 0xE0, 0x03, 0xC0, 0x07, 0x80, 0x07, 0x00,
-        0x0E, 0x00, 0x1E, 0x00, 0x3F, 0xF8, 0x3F, 0xF8, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00,
-    ], // z
-    &[
-        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x03, 0x80, 0x03, 0x80, 0x03,
-        0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x03, 0x80,
-        0x03, 0x80, 0x03, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00,
-    ], // :
-    &[
-        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00,
-    ], // <space>
-];
+/// Bytes of the largest glyph once decoded, 24x48.
+pub const MAX_GLYPH_BYTES: usize = 144;
 
-pub static ARIAL_ROUND_16_24_INDEX: &[char; 65] = &[
-    '0', '1', '2', '3', '4', '5', '6', '7', '8', '9', '.', 'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H',
-    'I', 'J', 'K', 'L', 'M', 'N', 'O', 'P', 'Q', 'R', 'S', 'T', 'U', 'V', 'W', 'X', 'Y', 'Z', 'a',
-    'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', 'j', 'k', 'l', 'm', 'n', 'o', 'p', 'q', 'r', 's', 't',
-    'u', 'v', 'w', 'x', 'y', 'z', ':', ' ',
-];
+/// A monospaced font of `width` x `height` glyphs, one per entry of `chars`.
+pub struct Font {
+    pub width: u16,
+    pub height: u16,
+    pub chars: &'static [char],
+    /// Encoded glyphs, in the order of `chars`.
+    pub glyphs: &'static [&'static [u8]],
+}
+
+impl Font {
+    /// The glyph of `c`, or the first one for a character the font does not have.
+    pub fn glyph(&self, c: char) -> Bitmap {
+        self.bitmap(get_index_by_char(self.chars, c))
+    }
+
+    pub fn bitmap(&self, index: usize) -> Bitmap {
+        Bitmap::Encoded(
+            self.glyphs[index],
+            self.width as usize * self.height as usize / 8,
+        )
+    }
+}
+
+/// A 1-bit image, row by row, most significant bit first.
+#[derive(Clone, Copy)]
+pub enum Bitmap {
+    Raw(&'static [u8]),
+    /// Run-length encoded, with its size in bytes once decoded.
+    Encoded(&'static [u8], usize),
+    /// A character of an `embedded-graphics` font. A glyph that is not a whole number of bytes is
+    /// padded with background, which spills onto the first pixels of the row below it.
+    Mono(&'static MonoFont<'static>, char),
+}
+
+impl Bitmap {
+    /// Size in bytes once decoded.
+    pub fn size(&self) -> usize {
+        match self {
+            Bitmap::Raw(data) => data.len(),
+            Bitmap::Encoded(_, len) => *len,
+            Bitmap::Mono(font, _) => {
+                let size = font.character_size;
+                (size.width * size.height).div_ceil(8) as usize
+            }
+        }
+    }
+
+    /// The pixels, decoded into `buf` if they are encoded.
+    pub fn bits<'b>(&self, buf: &'b mut [u8; MAX_GLYPH_BYTES]) -> &'b [u8] {
+        match *self {
+            Bitmap::Raw(data) => data,
+            Bitmap::Encoded(data, len) => {
+                let out = &mut buf[..len.min(MAX_GLYPH_BYTES)];
+                rle::decode(data, out);
+                out
+            }
+            Bitmap::Mono(..) => {
+                let out = &mut buf[..self.size().min(MAX_GLYPH_BYTES)];
+                out.fill(0);
+                for i in 0..out.len() * 8 {
+                    if self.bit(i) {
+                        out[i / 8] |= 0x80 >> (i % 8);
+                    }
+                }
+                out
+            }
+        }
+    }
+
+    /// Whether pixel `index` is set.
+    pub fn bit(&self, index: usize) -> bool {
+        match *self {
+            Bitmap::Raw(data) => data
+                .get(index / 8)
+                .is_some_and(|byte| byte & (0x80 >> (index % 8)) != 0),
+            Bitmap::Encoded(data, _) => rle::bit(data, index),
+            Bitmap::Mono(font, c) => mono_pixel(font, c, index),
+        }
+    }
+}
+
+/// Pixel `index` of the glyph of `c`, found in the font image the way `MonoTextStyle` does.
+fn mono_pixel(font: &MonoFont, c: char, index: usize) -> bool {
+    let size = font.character_size;
+    let (x, y) = (index as u32 % size.width, index as u32 / size.width);
+    if y >= size.height {
+        return false;
+    }
+
+    let columns = (font.image.size().width / size.width).max(1);
+    let glyph = font.glyph_mapping.index(c) as u32;
+    let origin = Point::new(
+        ((glyph % columns) * size.width + x) as i32,
+        ((glyph / columns) * size.height + y) as i32,
+    );
+
+    font.image.pixel(origin) == Some(BinaryColor::On)
+}
+
+include!("fonts/dot_matrix_xl_num.rs");
+include!("fonts/grotesk_24_48.rs");
+include!("fonts/arial_round_16_24.rs");
 
 pub fn get_index_by_char(index: &[char], c: char) -> usize {
     index.iter().position(|&x| x == c).unwrap_or_else(|| {
-        defmt::error!("unknown char: {}", c);
+        error!("unknown char: {}", c);
         0
     })
 }
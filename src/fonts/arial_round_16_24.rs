@@ -0,0 +1,379 @@
+// @generated by font-tool, converted from the hand-maintained bitmaps.
+// Regenerate rather than edit, see font-tool/src/main.rs.
+
+pub static ARIAL_ROUND_16_24: Font = Font {
+    width: 16,
+    height: 24,
+    chars: &[
+        '0', '1', '2', '3', '4', '5', '6', '7', '8', '9', '.', 'A', 'B', 'C', 'D', 'E', 'F', 'G',
+        'H', 'I', 'J', 'K', 'L', 'M', 'N', 'O', 'P', 'Q', 'R', 'S', 'T', 'U', 'V', 'W', 'X', 'Y',
+        'Z', 'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', 'j', 'k', 'l', 'm', 'n', 'o', 'p', 'q',
+        'r', 's', 't', 'u', 'v', 'w', 'x', 'y', 'z', '>', ':', ' ',
+    ],
+    glyphs: &[
+        // 0
+        &[
+            0xF6, 0x69, 0x87, 0xA6, 0x34, 0x35, 0x36, 0x34, 0x36, 0x34, 0x36, 0x34,
+            0x36, 0x34, 0x36, 0x34, 0x36, 0x34, 0x36, 0x34, 0x36, 0x34, 0x36, 0x34,
+            0x36, 0x35, 0x34, 0x36, 0xA7, 0x89, 0x60,
+        ],
+        // 1
+        &[
+            0xFA, 0x2D, 0x3C, 0x4B, 0x5A, 0x68, 0x88, 0x32, 0x38, 0x23, 0x3D, 0x3D,
+            0x3D, 0x3D, 0x3D, 0x3D, 0x3D, 0x3D, 0x3D, 0x3D, 0x30,
+        ],
+        // 2
+        &[
+            0xF6, 0x68, 0xA6, 0xA5, 0x44, 0x44, 0x36, 0x34, 0x36, 0x3D, 0x3C, 0x3C,
+            0x4B, 0x4A, 0x5A, 0x4B, 0x4B, 0x4B, 0x4C, 0xB5, 0xC4, 0xC0,
+        ],
+        // 3
+        &[
+            0xF6, 0x69, 0x87, 0xA5, 0x43, 0x45, 0x35, 0x3D, 0x3C, 0x4A, 0x5B, 0x5C,
+            0x5D, 0x44, 0x27, 0x34, 0x36, 0x34, 0x35, 0x44, 0x43, 0x46, 0xA7, 0x89,
+            0x60,
+        ],
+        // 4
+        &[
+            0xFA, 0x2D, 0x4C, 0x4B, 0x5A, 0x6A, 0x21, 0x39, 0x31, 0x38, 0x32, 0x38,
+            0x23, 0x37, 0x33, 0x36, 0x34, 0x36, 0x34, 0x36, 0xC4, 0xCB, 0x3D, 0x3D,
+            0x3D, 0x30,
+        ],
+        // 5
+        &[
+            0xF4, 0xB5, 0xB5, 0xA6, 0x3D, 0x3D, 0x3D, 0x88, 0x97, 0xA6, 0x34, 0x4D,
+            0x3D, 0x3D, 0x34, 0x36, 0x34, 0x44, 0x35, 0xB6, 0x99, 0x60,
+        ],
+        // 6
+        &[
+            0xF7, 0x59, 0x87, 0xA6, 0x34, 0x35, 0x45, 0x25, 0x3D, 0x3D, 0x31, 0x57,
+            0xA6, 0xB5, 0x44, 0x44, 0x36, 0x34, 0x36, 0x34, 0x36, 0x35, 0x34, 0x45,
+            0xA7, 0x8A, 0x50,
+        ],
+        // 7
+        &[
+            0xF3, 0xC4, 0xC5, 0xBC, 0x3C, 0x3D, 0x3C, 0x3C, 0x3D, 0x3C, 0x3D, 0x3D,
+            0x3C, 0x4C, 0x3D, 0x3D, 0x3D, 0x3D, 0x20,
+        ],
+        // 8
+        &[
+            0xF6, 0x69, 0x87, 0xA6, 0x42, 0x46, 0x34, 0x36, 0x34, 0x36, 0x42, 0x47,
+            0x88, 0x87, 0x34, 0x35, 0x36, 0x34, 0x36, 0x34, 0x36, 0x34, 0x36, 0x34,
+            0x44, 0x45, 0xA7, 0x89, 0x60,
+        ],
+        // 9
+        &[
+            0xF6, 0x5A, 0x87, 0xA5, 0x44, 0x35, 0x36, 0x34, 0x36, 0x34, 0x36, 0x34,
+            0x44, 0x45, 0xB6, 0xA7, 0x51, 0x3D, 0x3D, 0x35, 0x25, 0x45, 0x34, 0x36,
+            0xA7, 0x89, 0x50,
+        ],
+        // .
+        &[
+            0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xF7, 0x3D, 0x3D, 0x30,
+        ],
+        // A
+        &[
+            0xFF, 0x92, 0xD4, 0xC4, 0xB6, 0xA6, 0x93, 0x22, 0x93, 0x23, 0x83, 0x23,
+            0x73, 0x34, 0x63, 0x43, 0x63, 0x43, 0x5C, 0x4C, 0x43, 0x63, 0x33, 0x83,
+            0x23, 0x83, 0x22, 0x93,
+        ],
+        // B
+        &[
+            0xFF, 0x49, 0x7A, 0x63, 0x44, 0x53, 0x53, 0x53, 0x53, 0x53, 0x53, 0x53,
+            0x53, 0x5A, 0x6A, 0x63, 0x53, 0x53, 0x63, 0x43, 0x63, 0x43, 0x63, 0x43,
+            0x63, 0x43, 0x54, 0x4B, 0x5A,
+        ],
+        // C
+        &[
+            0xFF, 0x76, 0x98, 0x74, 0x24, 0x54, 0x44, 0x43, 0x63, 0x34, 0x72, 0x33,
+            0xD3, 0xD3, 0xD3, 0xD3, 0x82, 0x33, 0x82, 0x43, 0x63, 0x44, 0x44, 0x54,
+            0x24, 0x78, 0x96,
+        ],
+        // D
+        &[
+            0xFF, 0x48, 0x8A, 0x63, 0x34, 0x63, 0x44, 0x53, 0x53, 0x53, 0x63, 0x43,
+            0x63, 0x43, 0x63, 0x43, 0x63, 0x43, 0x63, 0x43, 0x63, 0x43, 0x63, 0x43,
+            0x53, 0x53, 0x44, 0x53, 0x34, 0x6A, 0x68,
+        ],
+        // E
+        &[
+            0xFF, 0x5B, 0x5B, 0x53, 0xD3, 0xD3, 0xD3, 0xD3, 0xDA, 0x6A, 0x63, 0xD3,
+            0xD3, 0xD3, 0xD3, 0xD3, 0xDB, 0x5B,
+        ],
+        // F
+        &[
+            0xFF, 0x6A, 0x6A, 0x63, 0xD3, 0xD3, 0xD3, 0xD3, 0xD9, 0x79, 0x73, 0xD3,
+            0xD3, 0xD3, 0xD3, 0xD3, 0xD3, 0xD3,
+        ],
+        // G
+        &[
+            0xFF, 0x85, 0x99, 0x63, 0x44, 0x43, 0x63, 0x43, 0x73, 0x23, 0x82, 0x33,
+            0xD3, 0xD3, 0x47, 0x23, 0x47, 0x23, 0x83, 0x23, 0x83, 0x33, 0x73, 0x33,
+            0x73, 0x43, 0x45, 0x5A, 0x86,
+        ],
+        // H
+        &[
+            0xFF, 0x43, 0x63, 0x43, 0x63, 0x43, 0x63, 0x43, 0x63, 0x43, 0x63, 0x43,
+            0x63, 0x43, 0x63, 0x4C, 0x4C, 0x43, 0x63, 0x43, 0x63, 0x43, 0x63, 0x43,
+            0x63, 0x43, 0x63, 0x43, 0x63, 0x43, 0x63, 0x43, 0x63,
+        ],
+        // I
+        &[
+            0xFF, 0x83, 0xD3, 0xD3, 0xD3, 0xD3, 0xD3, 0xD3, 0xD3, 0xD3, 0xD3, 0xD3,
+            0xD3, 0xD3, 0xD3, 0xD3, 0xD3, 0xD3,
+        ],
+        // J
+        &[
+            0xFF, 0xA3, 0xD3, 0xD3, 0xD3, 0xD3, 0xD3, 0xD3, 0xD3, 0xD3, 0xD3, 0x63,
+            0x43, 0x63, 0x43, 0x63, 0x43, 0x64, 0x24, 0x78, 0x88, 0xA4,
+        ],
+        // K
+        &[
+            0xFF, 0x43, 0x72, 0x43, 0x63, 0x43, 0x54, 0x43, 0x44, 0x53, 0x34, 0x63,
+            0x24, 0x73, 0x14, 0x88, 0x89, 0x75, 0x14, 0x64, 0x33, 0x63, 0x44, 0x53,
+            0x54, 0x43, 0x54, 0x43, 0x64, 0x33, 0x73, 0x33, 0x73,
+        ],
+        // L
+        &[
+            0xFF, 0x53, 0xD3, 0xD3, 0xD3, 0xD3, 0xD3, 0xD3, 0xD3, 0xD3, 0xD3, 0xD3,
+            0xD3, 0xD3, 0xD3, 0xD3, 0xDA, 0x6A,
+        ],
+        // M
+        &[
+            0xFF, 0x25, 0x6A, 0x6B, 0x4C, 0x4C, 0x49, 0x12, 0x42, 0x16, 0x13, 0x23,
+            0x16, 0x13, 0x23, 0x16, 0x13, 0x23, 0x16, 0x22, 0x22, 0x26, 0x26, 0x26,
+            0x26, 0x26, 0x26, 0x26, 0x34, 0x36, 0x34, 0x36, 0x34, 0x36, 0x42, 0x43,
+        ],
+        // N
+        &[
+            0xFF, 0x52, 0x63, 0x44, 0x53, 0x44, 0x53, 0x45, 0x43, 0x45, 0x43, 0x46,
+            0x33, 0x43, 0x12, 0x33, 0x43, 0x13, 0x23, 0x43, 0x22, 0x23, 0x43, 0x23,
+            0x13, 0x43, 0x32, 0x13, 0x43, 0x36, 0x43, 0x45, 0x43, 0x45, 0x43, 0x54,
+            0x43, 0x54, 0x43, 0x63,
+        ],
+        // O
+        &[
+            0xFF, 0x76, 0x98, 0x7A, 0x54, 0x44, 0x43, 0x63, 0x34, 0x64, 0x23, 0x83,
+            0x23, 0x83, 0x23, 0x83, 0x23, 0x83, 0x23, 0x83, 0x24, 0x64, 0x33, 0x63,
+            0x44, 0x44, 0x5A, 0x78, 0x96,
+        ],
+        // P
+        &[
+            0xFF, 0x4A, 0x6B, 0x53, 0x54, 0x43, 0x63, 0x43, 0x63, 0x43, 0x63, 0x43,
+            0x63, 0x43, 0x53, 0x5B, 0x59, 0x73, 0xD3, 0xD3, 0xD3, 0xD3, 0xD3, 0xD3,
+        ],
+        // Q
+        &[
+            0xFF, 0x76, 0x98, 0x7A, 0x54, 0x44, 0x43, 0x63, 0x34, 0x73, 0x23, 0x83,
+            0x23, 0x83, 0x23, 0x83, 0x23, 0x83, 0x23, 0x83, 0x24, 0x22, 0x24, 0x33,
+            0x36, 0x44, 0x35, 0x5A, 0x7B, 0x66, 0x14, 0xD3,
+        ],
+        // R
+        &[
+            0xFF, 0x4B, 0x5C, 0x43, 0x64, 0x33, 0x73, 0x33, 0x73, 0x33, 0x73, 0x33,
+            0x64, 0x3C, 0x4A, 0x63, 0x33, 0x73, 0x43, 0x63, 0x44, 0x53, 0x54, 0x43,
+            0x54, 0x43, 0x64, 0x33, 0x73, 0x33, 0x73,
+        ],
+        // S
+        &[
+            0xFF, 0x76, 0x89, 0x73, 0x43, 0x53, 0x53, 0x53, 0x62, 0x54, 0xC6, 0xB8,
+            0x99, 0xA6, 0xD4, 0x42, 0x73, 0x43, 0x63, 0x43, 0x63, 0x44, 0x43, 0x6A,
+            0x86,
+        ],
+        // T
+        &[
+            0xFF, 0x3D, 0x3D, 0x83, 0xD3, 0xD3, 0xD3, 0xD3, 0xD3, 0xD3, 0xD3, 0xD3,
+            0xD3, 0xD3, 0xD3, 0xD3, 0xD3, 0xD3,
+        ],
+        // U
+        &[
+            0xFF, 0x43, 0x63, 0x43, 0x63, 0x43, 0x63, 0x43, 0x63, 0x43, 0x63, 0x43,
+            0x63, 0x43, 0x63, 0x43, 0x63, 0x43, 0x63, 0x43, 0x63, 0x43, 0x63, 0x43,
+            0x63, 0x43, 0x63, 0x44, 0x43, 0x6A, 0x78, 0x96,
+        ],
+        // V
+        &[
+            0xFF, 0x43, 0x82, 0x33, 0x73, 0x33, 0x73, 0x43, 0x53, 0x53, 0x53, 0x53,
+            0x53, 0x63, 0x42, 0x73, 0x33, 0x73, 0x33, 0x73, 0x32, 0x93, 0x13, 0x93,
+            0x13, 0x93, 0x12, 0xB5, 0xB5, 0xB4, 0xD3,
+        ],
+        // W
+        &[
+            0xFF, 0x22, 0x52, 0x54, 0x44, 0x45, 0x34, 0x36, 0x34, 0x36, 0x34, 0x33,
+            0x12, 0x34, 0x32, 0x22, 0x22, 0x22, 0x22, 0x22, 0x22, 0x22, 0x22, 0x23,
+            0x12, 0x22, 0x13, 0x23, 0x12, 0x22, 0x13, 0x32, 0x12, 0x22, 0x12, 0x44,
+            0x44, 0x44, 0x44, 0x44, 0x44, 0x44, 0x44, 0x53, 0x43, 0x62, 0x62,
+        ],
+        // X
+        &[
+            0xFF, 0x42, 0x82, 0x43, 0x64, 0x34, 0x53, 0x53, 0x44, 0x54, 0x24, 0x78,
+            0x97, 0x96, 0xB4, 0xB6, 0x98, 0x7A, 0x64, 0x24, 0x54, 0x44, 0x34, 0x64,
+            0x24, 0x64, 0x23, 0x83,
+        ],
+        // Y
+        &[
+            0xFF, 0x42, 0x72, 0x44, 0x63, 0x43, 0x53, 0x54, 0x43, 0x63, 0x33, 0x74,
+            0x14, 0x83, 0x13, 0xA5, 0xB5, 0xC3, 0xD3, 0xD3, 0xD3, 0xD3, 0xD3, 0xD3,
+            0xD3,
+        ],
+        // Z
+        &[
+            0xFF, 0x4B, 0x5B, 0xC4, 0xC4, 0xB4, 0xB5, 0xB4, 0xB4, 0xB4, 0xC4, 0xB4,
+            0xB4, 0xC4, 0xB4, 0xB4, 0xCD, 0x3D,
+        ],
+        // a
+        &[
+            0xFF, 0xFF, 0xFF, 0xFB, 0x69, 0x88, 0x24, 0x37, 0x15, 0x3C, 0x48, 0x87,
+            0x42, 0x36, 0x34, 0x36, 0x34, 0x36, 0x33, 0x47, 0x98, 0x33, 0x30,
+        ],
+        // b
+        &[
+            0xFF, 0x53, 0xD3, 0xD3, 0xD3, 0xD3, 0xD3, 0x15, 0x7A, 0x64, 0x33, 0x64,
+            0x43, 0x53, 0x53, 0x53, 0x53, 0x53, 0x53, 0x53, 0x53, 0x54, 0x43, 0x54,
+            0x33, 0x6A, 0x63, 0x14,
+        ],
+        // c
+        &[
+            0xFF, 0xFF, 0xFF, 0xFC, 0x5A, 0x87, 0x33, 0x36, 0x44, 0x35, 0x36, 0x16,
+            0x3D, 0x3D, 0x36, 0x16, 0x44, 0x36, 0x33, 0x47, 0x89, 0x50,
+        ],
+        // d
+        &[
+            0xFF, 0xC3, 0xD3, 0xD3, 0xD3, 0xD3, 0x75, 0x13, 0x6A, 0x63, 0x34, 0x53,
+            0x44, 0x53, 0x53, 0x53, 0x53, 0x53, 0x53, 0x53, 0x53, 0x53, 0x44, 0x63,
+            0x34, 0x6A, 0x84, 0x13,
+        ],
+        // e
+        &[
+            0xFF, 0xFF, 0xFF, 0xFC, 0x5A, 0x78, 0x33, 0x36, 0x35, 0x35, 0x35, 0x35,
+            0xB5, 0xA6, 0x3D, 0x36, 0x17, 0x34, 0x37, 0x89, 0x50,
+        ],
+        // f
+        &[
+            0xFF, 0x85, 0xA6, 0xA3, 0xD3, 0xD3, 0xB7, 0x97, 0xB3, 0xD3, 0xD3, 0xD3,
+            0xD3, 0xD3, 0xD3, 0xD3, 0xD3, 0xD3,
+        ],
+        // g
+        &[
+            0xFF, 0xFF, 0xFF, 0xFB, 0x51, 0x36, 0xA6, 0x33, 0x45, 0x35, 0x35, 0x35,
+            0x35, 0x35, 0x35, 0x35, 0x35, 0x35, 0x35, 0x35, 0x36, 0x33, 0x46, 0xA8,
+            0x41, 0x36, 0x16, 0x35, 0x35, 0x36, 0x33, 0x37, 0x99, 0x50,
+        ],
+        // h
+        &[
+            0xFF, 0x43, 0xD3, 0xD3, 0xD3, 0xD3, 0xD3, 0x14, 0x89, 0x74, 0x24, 0x63,
+            0x43, 0x63, 0x43, 0x63, 0x43, 0x63, 0x43, 0x63, 0x43, 0x63, 0x43, 0x63,
+            0x43, 0x63, 0x43, 0x63, 0x43,
+        ],
+        // i
+        &[
+            0xFF, 0x83, 0xD3, 0xD3, 0xFF, 0xF0, 0x3D, 0x3D, 0x3D, 0x3D, 0x3D, 0x3D,
+            0x3D, 0x3D, 0x3D, 0x3D, 0x3D, 0x30,
+        ],
+        // j
+        &[
+            0xFF, 0x83, 0xD3, 0xD3, 0xFF, 0xF0, 0x3D, 0x3D, 0x3D, 0x3D, 0x3D, 0x3D,
+            0x3D, 0x3D, 0x3D, 0x3D, 0x3D, 0x3D, 0x3D, 0x3D, 0x3A, 0x6A, 0x50,
+        ],
+        // k
+        &[
+            0xFF, 0x63, 0xD3, 0xD3, 0xD3, 0xD3, 0xD3, 0x42, 0x73, 0x33, 0x73, 0x23,
+            0x83, 0x13, 0x96, 0xA7, 0x94, 0x13, 0x83, 0x23, 0x83, 0x33, 0x73, 0x33,
+            0x73, 0x43, 0x63, 0x52,
+        ],
+        // l
+        &[
+            0xFF, 0x83, 0xD3, 0xD3, 0xD3, 0xD3, 0xD3, 0xD3, 0xD3, 0xD3, 0xD3, 0xD3,
+            0xD3, 0xD3, 0xD3, 0xD3, 0xD3, 0xD3,
+        ],
+        // m
+        &[
+            0xFF, 0xFF, 0xFF, 0xF7, 0x31, 0x52, 0x41, 0xF5, 0x34, 0x26, 0x43, 0x36,
+            0x43, 0x36, 0x43, 0x36, 0x43, 0x36, 0x43, 0x36, 0x43, 0x36, 0x43, 0x36,
+            0x43, 0x36, 0x43, 0x33,
+        ],
+        // n
+        &[
+            0xFF, 0xFF, 0xFF, 0xFA, 0x31, 0x48, 0x97, 0x42, 0x46, 0x34, 0x36, 0x34,
+            0x36, 0x34, 0x36, 0x34, 0x36, 0x34, 0x36, 0x34, 0x36, 0x34, 0x36, 0x34,
+            0x36, 0x34, 0x30,
+        ],
+        // o
+        &[
+            0xFF, 0xFF, 0xFF, 0xFB, 0x5A, 0x78, 0x33, 0x36, 0x35, 0x35, 0x35, 0x35,
+            0x35, 0x35, 0x35, 0x35, 0x35, 0x35, 0x35, 0x36, 0x33, 0x38, 0x7A, 0x50,
+        ],
+        // p
+        &[
+            0xFF, 0xFF, 0xFF, 0xF9, 0x31, 0x48, 0xA6, 0x43, 0x36, 0x44, 0x35, 0x35,
+            0x35, 0x35, 0x35, 0x35, 0x35, 0x35, 0x35, 0x44, 0x35, 0x43, 0x36, 0xA6,
+            0x31, 0x48, 0x3D, 0x3D, 0x3D, 0x3D, 0x30,
+        ],
+        // q
+        &[
+            0xFF, 0xFF, 0xFF, 0xFC, 0x41, 0x36, 0xA6, 0x33, 0x45, 0x34, 0x45, 0x35,
+            0x35, 0x35, 0x35, 0x35, 0x35, 0x35, 0x35, 0x34, 0x46, 0x33, 0x46, 0xA8,
+            0x41, 0x3D, 0x3D, 0x3D, 0x3D, 0x3D, 0x30,
+        ],
+        // r
+        &[
+            0xFF, 0xFF, 0xFF, 0xFB, 0x31, 0x39, 0x88, 0x43, 0x18, 0x3D, 0x3D, 0x3D,
+            0x3D, 0x3D, 0x3D, 0x3D, 0x3D, 0x30,
+        ],
+        // s
+        &[
+            0xFF, 0xFF, 0xFF, 0xFC, 0x5A, 0x87, 0x33, 0x37, 0x34, 0x27, 0x5C, 0x7B,
+            0x6D, 0x46, 0x34, 0x36, 0x43, 0x37, 0x89, 0x60,
+        ],
+        // t
+        &[
+            0xFF, 0x83, 0xD3, 0xD3, 0xD3, 0xD3, 0xC6, 0x97, 0xB3, 0xD3, 0xD3, 0xD3,
+            0xD3, 0xD3, 0xD3, 0xD3, 0xD6, 0xB5,
+        ],
+        // u
+        &[
+            0xFF, 0xFF, 0xFF, 0xFA, 0x34, 0x36, 0x34, 0x36, 0x34, 0x36, 0x34, 0x36,
+            0x34, 0x36, 0x34, 0x36, 0x34, 0x36, 0x34, 0x36, 0x34, 0x36, 0x42, 0x47,
+            0x98, 0x41, 0x30,
+        ],
+        // v
+        &[
+            0xFF, 0xFF, 0xFF, 0xFA, 0x26, 0x26, 0x34, 0x36, 0x34, 0x37, 0x24, 0x28,
+            0x32, 0x38, 0x32, 0x39, 0x22, 0x2A, 0x6B, 0x4C, 0x4C, 0x4D, 0x20,
+        ],
+        // w
+        &[
+            0xFF, 0xFF, 0xFF, 0xF7, 0x25, 0x25, 0x53, 0x43, 0x63, 0x43, 0x31, 0x23,
+            0x43, 0x22, 0x22, 0x62, 0x22, 0x31, 0x22, 0x21, 0x33, 0x21, 0x22, 0x21,
+            0x24, 0x21, 0x22, 0x21, 0x24, 0x52, 0x55, 0x34, 0x36, 0x34, 0x36, 0x34,
+            0x30,
+        ],
+        // x
+        &[
+            0xFF, 0xFF, 0xFF, 0xF9, 0x35, 0x35, 0x43, 0x46, 0x41, 0x48, 0x7A, 0x5B,
+            0x5A, 0x79, 0x31, 0x38, 0x41, 0x46, 0x43, 0x45, 0x35, 0x35, 0x26, 0x30,
+        ],
+        // y
+        &[
+            0xFF, 0xFF, 0xFF, 0xFA, 0x26, 0x26, 0x34, 0x36, 0x34, 0x37, 0x24, 0x28,
+            0x32, 0x38, 0x32, 0x39, 0x22, 0x2A, 0x31, 0x2A, 0x6B, 0x4C, 0x4C, 0x4D,
+            0x2D, 0x3D, 0x3A, 0x5B, 0x40,
+        ],
+        // z
+        &[
+            0xFF, 0xFF, 0xFF, 0xF9, 0xB5, 0xBC, 0x4B, 0x4B, 0x4B, 0x4B, 0x4C, 0x3C,
+            0x3C, 0x4B, 0xB5, 0xB0,
+        ],
+        // > (drawn as an arrow)
+        &[
+            0xFF, 0xFF, 0xFA, 0x3E, 0x3E, 0x3E, 0x3E, 0x3E, 0x35, 0xC4, 0xCC, 0x3C,
+            0x3C, 0x3C, 0x3C, 0x3C, 0x30,
+        ],
+        // :
+        &[
+            0xFF, 0xFF, 0xFB, 0x3D, 0x3D, 0x3F, 0xFF, 0xFF, 0xFF, 0x43, 0xD3, 0xD3,
+        ],
+        // <space>
+        &[],
+    ],
+};
@@ -0,0 +1,133 @@
+// @generated by font-tool, converted from the hand-maintained bitmaps.
+// Regenerate rather than edit, see font-tool/src/main.rs.
+
+pub static DOT_MATRIX_XL_NUM: Font = Font {
+    width: 32,
+    height: 50,
+    chars: &[
+        '0', '1', '2', '3', '4', '5', '6', '7', '8', '9',
+    ],
+    glyphs: &[
+        // 0
+        &[
+            0xFF, 0xA3, 0x33, 0x33, 0xF1, 0x51, 0x51, 0x5F, 0x05, 0x15, 0x15, 0xF0,
+            0x51, 0x51, 0x5F, 0x13, 0x33, 0x33, 0xFF, 0xFF, 0xF0, 0x3F, 0x63, 0x45,
+            0xF4, 0x53, 0x5F, 0x45, 0x35, 0xF4, 0x54, 0x3F, 0x63, 0xFF, 0xFF, 0x93,
+            0xF6, 0x34, 0x5F, 0x45, 0x35, 0xF4, 0x53, 0x5F, 0x45, 0x43, 0xF6, 0x3F,
+            0xFF, 0xF9, 0x3F, 0x63, 0x45, 0xF4, 0x53, 0x5F, 0x45, 0x35, 0xF4, 0x54,
+            0x3F, 0x63, 0xFF, 0xFF, 0x93, 0xF6, 0x34, 0x5F, 0x45, 0x35, 0xF4, 0x53,
+            0x5F, 0x45, 0x43, 0xF6, 0x3F, 0xFF, 0xF9, 0x3F, 0x63, 0x45, 0xF4, 0x53,
+            0x5F, 0x45, 0x35, 0xF4, 0x54, 0x3F, 0x63, 0xFF, 0xFF, 0xF0, 0x33, 0x33,
+            0x3F, 0x15, 0x15, 0x15, 0xF0, 0x51, 0x51, 0x5F, 0x05, 0x15, 0x15, 0xF1,
+            0x33, 0x33, 0x30,
+        ],
+        // 1
+        &[
+            0xFF, 0xF1, 0x3F, 0xD5, 0xFC, 0x5F, 0xC5, 0xFD, 0x3F, 0xFF, 0xFF, 0xC3,
+            0x33, 0xF7, 0x51, 0x5F, 0x65, 0x15, 0xF6, 0x51, 0x5F, 0x73, 0x33, 0xFF,
+            0xFF, 0xFF, 0x33, 0xFD, 0x5F, 0xC5, 0xFC, 0x5F, 0xD3, 0xFF, 0xFF, 0xFF,
+            0x33, 0xFD, 0x5F, 0xC5, 0xFC, 0x5F, 0xD3, 0xFF, 0xFF, 0xFF, 0x33, 0xFD,
+            0x5F, 0xC5, 0xFC, 0x5F, 0xD3, 0xFF, 0xFF, 0xFF, 0x33, 0xFD, 0x5F, 0xC5,
+            0xFC, 0x5F, 0xD3, 0xFF, 0xFF, 0xFC, 0x33, 0x33, 0x3F, 0x15, 0x15, 0x15,
+            0xF0, 0x51, 0x51, 0x5F, 0x05, 0x15, 0x15, 0xF1, 0x33, 0x33, 0x30,
+        ],
+        // 2
+        &[
+            0xFF, 0x43, 0x33, 0x33, 0x33, 0xA5, 0x15, 0x15, 0x15, 0x95, 0x15, 0x15,
+            0x15, 0x95, 0x15, 0x15, 0x15, 0xA3, 0x33, 0x33, 0x33, 0xFF, 0xFF, 0xFF,
+            0x93, 0xFD, 0x5F, 0xC5, 0xFC, 0x5F, 0xD3, 0xFF, 0xFF, 0xFF, 0x33, 0xFD,
+            0x5F, 0xC5, 0xFC, 0x5F, 0xD3, 0xFF, 0xFF, 0xF0, 0x33, 0x33, 0x3F, 0x15,
+            0x15, 0x15, 0xF0, 0x51, 0x51, 0x5F, 0x05, 0x15, 0x15, 0xF1, 0x33, 0x33,
+            0x3F, 0xFF, 0xFF, 0x03, 0xFD, 0x5F, 0xC5, 0xFC, 0x5F, 0xD3, 0xFF, 0xFF,
+            0xFF, 0x33, 0xFD, 0x5F, 0xC5, 0xFC, 0x5F, 0xD3, 0xFF, 0xFF, 0xFF, 0x33,
+            0x33, 0x33, 0x33, 0x33, 0x45, 0x15, 0x15, 0x15, 0x15, 0x35, 0x15, 0x15,
+            0x15, 0x15, 0x35, 0x15, 0x15, 0x15, 0x15, 0x43, 0x33, 0x33, 0x33, 0x33,
+        ],
+        // 3
+        &[
+            0xFF, 0x43, 0x33, 0x33, 0x33, 0xA5, 0x15, 0x15, 0x15, 0x95, 0x15, 0x15,
+            0x15, 0x95, 0x15, 0x15, 0x15, 0xA3, 0x33, 0x33, 0x33, 0xFF, 0xFF, 0xFF,
+            0x93, 0xFD, 0x5F, 0xC5, 0xFC, 0x5F, 0xD3, 0xFF, 0xFF, 0xFF, 0x33, 0xFD,
+            0x5F, 0xC5, 0xFC, 0x5F, 0xD3, 0xFF, 0xFF, 0xF0, 0x33, 0x33, 0x3F, 0x15,
+            0x15, 0x15, 0xF0, 0x51, 0x51, 0x5F, 0x05, 0x15, 0x15, 0xF1, 0x33, 0x33,
+            0x3F, 0xFF, 0xFF, 0xF9, 0x3F, 0xD5, 0xFC, 0x5F, 0xC5, 0xFD, 0x3F, 0xFF,
+            0xFF, 0xF3, 0x3F, 0xD5, 0xFC, 0x5F, 0xC5, 0xFD, 0x3F, 0xFF, 0xF9, 0x33,
+            0x33, 0x33, 0x3A, 0x51, 0x51, 0x51, 0x59, 0x51, 0x51, 0x51, 0x59, 0x51,
+            0x51, 0x51, 0x5A, 0x33, 0x33, 0x33, 0x30,
+        ],
+        // 4
+        &[
+            0xFF, 0x43, 0xF6, 0x34, 0x5F, 0x45, 0x35, 0xF4, 0x53, 0x5F, 0x45, 0x43,
+            0xF6, 0x3F, 0xFF, 0xF9, 0x3F, 0x63, 0x45, 0xF4, 0x53, 0x5F, 0x45, 0x35,
+            0xF4, 0x54, 0x3F, 0x63, 0xFF, 0xFF, 0x93, 0xF6, 0x34, 0x5F, 0x45, 0x35,
+            0xF4, 0x53, 0x5F, 0x45, 0x43, 0xF6, 0x3F, 0xFF, 0xF9, 0x33, 0x33, 0x33,
+            0x33, 0x34, 0x51, 0x51, 0x51, 0x51, 0x53, 0x51, 0x51, 0x51, 0x51, 0x53,
+            0x51, 0x51, 0x51, 0x51, 0x54, 0x33, 0x33, 0x33, 0x33, 0x3F, 0xFF, 0xFF,
+            0xF3, 0x3F, 0xD5, 0xFC, 0x5F, 0xC5, 0xFD, 0x3F, 0xFF, 0xFF, 0xF3, 0x3F,
+            0xD5, 0xFC, 0x5F, 0xC5, 0xFD, 0x3F, 0xFF, 0xFF, 0xF3, 0x3F, 0xD5, 0xFC,
+            0x5F, 0xC5, 0xFD, 0x30,
+        ],
+        // 5
+        &[
+            0xFF, 0x43, 0x33, 0x33, 0x33, 0x33, 0x45, 0x15, 0x15, 0x15, 0x15, 0x35,
+            0x15, 0x15, 0x15, 0x15, 0x35, 0x15, 0x15, 0x15, 0x15, 0x43, 0x33, 0x33,
+            0x33, 0x33, 0xFF, 0xFF, 0x93, 0xFD, 0x5F, 0xC5, 0xFC, 0x5F, 0xD3, 0xFF,
+            0xFF, 0xFF, 0x33, 0xFD, 0x5F, 0xC5, 0xFC, 0x5F, 0xD3, 0xFF, 0xFF, 0xFF,
+            0x33, 0x33, 0x33, 0x33, 0xA5, 0x15, 0x15, 0x15, 0x95, 0x15, 0x15, 0x15,
+            0x95, 0x15, 0x15, 0x15, 0xA3, 0x33, 0x33, 0x33, 0xFF, 0xFF, 0xFF, 0x93,
+            0xFD, 0x5F, 0xC5, 0xFC, 0x5F, 0xD3, 0xFF, 0xFF, 0xFF, 0x33, 0xFD, 0x5F,
+            0xC5, 0xFC, 0x5F, 0xD3, 0xFF, 0xFF, 0x93, 0x33, 0x33, 0x33, 0xA5, 0x15,
+            0x15, 0x15, 0x95, 0x15, 0x15, 0x15, 0x95, 0x15, 0x15, 0x15, 0xA3, 0x33,
+            0x33, 0x33,
+        ],
+        // 6
+        &[
+            0xFF, 0xA3, 0x33, 0x33, 0xF1, 0x51, 0x51, 0x5F, 0x05, 0x15, 0x15, 0xF0,
+            0x51, 0x51, 0x5F, 0x13, 0x33, 0x33, 0xFF, 0xFF, 0xF0, 0x3F, 0xD5, 0xFC,
+            0x5F, 0xC5, 0xFD, 0x3F, 0xFF, 0xFF, 0xF3, 0x3F, 0xD5, 0xFC, 0x5F, 0xC5,
+            0xFD, 0x3F, 0xFF, 0xFF, 0xF3, 0x33, 0x33, 0x33, 0x3A, 0x51, 0x51, 0x51,
+            0x59, 0x51, 0x51, 0x51, 0x59, 0x51, 0x51, 0x51, 0x5A, 0x33, 0x33, 0x33,
+            0x3F, 0xFF, 0xFF, 0x03, 0xF6, 0x34, 0x5F, 0x45, 0x35, 0xF4, 0x53, 0x5F,
+            0x45, 0x43, 0xF6, 0x3F, 0xFF, 0xF9, 0x3F, 0x63, 0x45, 0xF4, 0x53, 0x5F,
+            0x45, 0x35, 0xF4, 0x54, 0x3F, 0x63, 0xFF, 0xFF, 0xF0, 0x33, 0x33, 0x3F,
+            0x15, 0x15, 0x15, 0xF0, 0x51, 0x51, 0x5F, 0x05, 0x15, 0x15, 0xF1, 0x33,
+            0x33, 0x30,
+        ],
+        // 7
+        &[
+            0xFF, 0x43, 0x33, 0x33, 0x33, 0x33, 0x45, 0x15, 0x15, 0x15, 0x15, 0x35,
+            0x15, 0x15, 0x15, 0x15, 0x35, 0x15, 0x15, 0x15, 0x15, 0x43, 0x33, 0x33,
+            0x33, 0x33, 0xFF, 0xFF, 0xFF, 0x33, 0xFD, 0x5F, 0xC5, 0xFC, 0x5F, 0xD3,
+            0xFF, 0xFF, 0xFF, 0x33, 0xFD, 0x5F, 0xC5, 0xFC, 0x5F, 0xD3, 0xFF, 0xFF,
+            0xFC, 0x3F, 0xD5, 0xFC, 0x5F, 0xC5, 0xFD, 0x3F, 0xFF, 0xFF, 0xC3, 0xFD,
+            0x5F, 0xC5, 0xFC, 0x5F, 0xD3, 0xFF, 0xFF, 0xFC, 0x3F, 0xD5, 0xFC, 0x5F,
+            0xC5, 0xFD, 0x3F, 0xFF, 0xFF, 0xC3, 0xFD, 0x5F, 0xC5, 0xFC, 0x5F, 0xD3,
+        ],
+        // 8
+        &[
+            0xFF, 0xA3, 0x33, 0x33, 0xF1, 0x51, 0x51, 0x5F, 0x05, 0x15, 0x15, 0xF0,
+            0x51, 0x51, 0x5F, 0x13, 0x33, 0x33, 0xFF, 0xFF, 0xF0, 0x3F, 0x63, 0x45,
+            0xF4, 0x53, 0x5F, 0x45, 0x35, 0xF4, 0x54, 0x3F, 0x63, 0xFF, 0xFF, 0x93,
+            0xF6, 0x34, 0x5F, 0x45, 0x35, 0xF4, 0x53, 0x5F, 0x45, 0x43, 0xF6, 0x3F,
+            0xFF, 0xFF, 0x03, 0x33, 0x33, 0xF1, 0x51, 0x51, 0x5F, 0x05, 0x15, 0x15,
+            0xF0, 0x51, 0x51, 0x5F, 0x13, 0x33, 0x33, 0xFF, 0xFF, 0xF0, 0x3F, 0x63,
+            0x45, 0xF4, 0x53, 0x5F, 0x45, 0x35, 0xF4, 0x54, 0x3F, 0x63, 0xFF, 0xFF,
+            0x93, 0xF6, 0x34, 0x5F, 0x45, 0x35, 0xF4, 0x53, 0x5F, 0x45, 0x43, 0xF6,
+            0x3F, 0xFF, 0xFF, 0x03, 0x33, 0x33, 0xF1, 0x51, 0x51, 0x5F, 0x05, 0x15,
+            0x15, 0xF0, 0x51, 0x51, 0x5F, 0x13, 0x33, 0x33,
+        ],
+        // 9
+        &[
+            0xFF, 0xA3, 0x33, 0x33, 0xF1, 0x51, 0x51, 0x5F, 0x05, 0x15, 0x15, 0xF0,
+            0x51, 0x51, 0x5F, 0x13, 0x33, 0x33, 0xFF, 0xFF, 0xF0, 0x3F, 0x63, 0x45,
+            0xF4, 0x53, 0x5F, 0x45, 0x35, 0xF4, 0x54, 0x3F, 0x63, 0xFF, 0xFF, 0x93,
+            0xF6, 0x34, 0x5F, 0x45, 0x35, 0xF4, 0x53, 0x5F, 0x45, 0x43, 0xF6, 0x3F,
+            0xFF, 0xFF, 0x03, 0x33, 0x33, 0x33, 0xA5, 0x15, 0x15, 0x15, 0x95, 0x15,
+            0x15, 0x15, 0x95, 0x15, 0x15, 0x15, 0xA3, 0x33, 0x33, 0x33, 0xFF, 0xFF,
+            0xFF, 0x33, 0xFD, 0x5F, 0xC5, 0xFC, 0x5F, 0xD3, 0xFF, 0xFF, 0xFF, 0x33,
+            0xFD, 0x5F, 0xC5, 0xFC, 0x5F, 0xD3, 0xFF, 0xFF, 0xF0, 0x33, 0x33, 0x3F,
+            0x15, 0x15, 0x15, 0xF0, 0x51, 0x51, 0x5F, 0x05, 0x15, 0x15, 0xF1, 0x33,
+            0x33, 0x30,
+        ],
+    ],
+};
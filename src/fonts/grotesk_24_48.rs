@@ -0,0 +1,118 @@
+// @generated by font-tool, converted from the hand-maintained bitmaps.
+// Regenerate rather than edit, see font-tool/src/main.rs.
+
+pub static GROTESK_24_48: Font = Font {
+    width: 24,
+    height: 48,
+    chars: &[
+        '0', '1', '2', '3', '4', '5', '6', '7', '8', '9', '.', '-', ' ',
+    ],
+    glyphs: &[
+        // 0
+        &[
+            0xFF, 0xFF, 0xF8, 0x2F, 0x57, 0xF0, 0xAD, 0xCC, 0xDA, 0x54, 0x5A, 0x46,
+            0x58, 0x57, 0x48, 0x48, 0x48, 0x47, 0x67, 0x47, 0x66, 0x57, 0x66, 0x47,
+            0x76, 0x47, 0x76, 0x46, 0x32, 0x45, 0x46, 0x31, 0x55, 0x45, 0x33, 0x45,
+            0x45, 0x33, 0x45, 0x44, 0x34, 0x45, 0x44, 0x34, 0x45, 0x43, 0x44, 0x45,
+            0x43, 0x35, 0x45, 0x42, 0x45, 0x45, 0x42, 0x36, 0x45, 0x41, 0x45, 0x55,
+            0x41, 0x36, 0x55, 0x86, 0x46, 0x77, 0x46, 0x77, 0x46, 0x68, 0x47, 0x58,
+            0x47, 0x48, 0x57, 0x48, 0x49, 0x46, 0x59, 0x55, 0x59, 0x71, 0x6B, 0xCD,
+            0xBE, 0x9F, 0x25,
+        ],
+        // 1
+        &[
+            0xFF, 0xFF, 0xFF, 0xF2, 0x5F, 0x36, 0xF1, 0x8E, 0xAD, 0xBC, 0xCC, 0x62,
+            0x4C, 0x44, 0x4C, 0x35, 0x4C, 0x17, 0x4F, 0x54, 0xF5, 0x4F, 0x54, 0xF5,
+            0x4F, 0x54, 0xF5, 0x4F, 0x54, 0xF5, 0x4F, 0x54, 0xF5, 0x4F, 0x54, 0xF5,
+            0x4F, 0x54, 0xF5, 0x4F, 0x54, 0xF5, 0x4F, 0x54, 0xF5, 0x4F, 0x54, 0xF5,
+            0x4F, 0x54, 0xF5, 0x4F, 0x54, 0xF4, 0x5D, 0xF2, 0x7F, 0x27, 0xF2, 0x7F,
+            0x20,
+        ],
+        // 2
+        &[
+            0xFF, 0xFF, 0xF6, 0x5F, 0x1B, 0xBF, 0x08, 0xF2, 0x7F, 0x27, 0x66, 0x66,
+            0x3A, 0x56, 0x2C, 0x5F, 0x54, 0xF5, 0x4F, 0x54, 0xF5, 0x4F, 0x54, 0xF5,
+            0x4F, 0x54, 0xF4, 0x5F, 0x44, 0xF4, 0x5F, 0x44, 0xF4, 0x5F, 0x35, 0xF4,
+            0x4F, 0x45, 0xF3, 0x5F, 0x35, 0xF4, 0x4F, 0x45, 0xF3, 0x5F, 0x35, 0xF3,
+            0x5F, 0x44, 0xF4, 0x5F, 0x35, 0xF3, 0x5F, 0x36, 0xF3, 0xF5, 0x4F, 0x54,
+            0xF5, 0x4F, 0x40,
+        ],
+        // 3
+        &[
+            0xFF, 0xFF, 0xF6, 0x5F, 0x0B, 0xCE, 0xAF, 0x09, 0xF1, 0x83, 0x76, 0x81,
+            0xB5, 0xF4, 0x5F, 0x54, 0xF5, 0x4F, 0x54, 0xF5, 0x4F, 0x54, 0xF5, 0x4F,
+            0x45, 0xF3, 0x5F, 0x17, 0xDA, 0xE9, 0xF0, 0x9F, 0x0B, 0xF4, 0x6F, 0x45,
+            0xF5, 0x5F, 0x54, 0xF5, 0x5F, 0x54, 0xF5, 0x4F, 0x54, 0xF5, 0x4F, 0x54,
+            0xF4, 0x5F, 0x44, 0x61, 0xC5, 0x63, 0x96, 0x6F, 0x27, 0xF1, 0x8F, 0x0A,
+            0xDE, 0x80,
+        ],
+        // 4
+        &[
+            0xFF, 0xFF, 0xFF, 0xF5, 0x4F, 0x46, 0xF3, 0x6F, 0x27, 0xF2, 0x7F, 0x18,
+            0xF1, 0x8F, 0x03, 0x24, 0xF0, 0x31, 0x5E, 0x32, 0x5E, 0x32, 0x5D, 0x42,
+            0x5D, 0x33, 0x5C, 0x43, 0x5C, 0x34, 0x5B, 0x44, 0x5B, 0x35, 0x5A, 0x45,
+            0x5A, 0x36, 0x59, 0x46, 0x59, 0x46, 0x59, 0x37, 0x58, 0x47, 0x58, 0x38,
+            0x57, 0x48, 0x57, 0xF5, 0x4F, 0x63, 0xF6, 0x3F, 0x5F, 0x15, 0xF4, 0x5F,
+            0x45, 0xF4, 0x5F, 0x45, 0xF4, 0x5F, 0x45, 0xF4, 0x5F, 0x45,
+        ],
+        // 5
+        &[
+            0xFF, 0xFF, 0xFF, 0xAF, 0x18, 0xF1, 0x8F, 0x18, 0xF1, 0x8F, 0x18, 0x4F,
+            0x54, 0xF5, 0x4F, 0x54, 0xF5, 0x4F, 0x54, 0xF5, 0x4F, 0x54, 0xF5, 0xCC,
+            0xDB, 0xF0, 0x9F, 0x09, 0x53, 0x88, 0x29, 0x6F, 0x45, 0xF5, 0x4F, 0x55,
+            0xF5, 0x4F, 0x54, 0xF5, 0x4F, 0x54, 0xF5, 0x4F, 0x54, 0xF5, 0x4F, 0x54,
+            0xF4, 0x5F, 0x44, 0x61, 0xC5, 0x63, 0x95, 0x7F, 0x27, 0xF1, 0x8F, 0x0A,
+            0xDD, 0x80,
+        ],
+        // 6
+        &[
+            0xFF, 0xFF, 0xF9, 0x4F, 0x2A, 0xDC, 0xBD, 0xAE, 0xA5, 0x63, 0x95, 0xF4,
+            0x4F, 0x44, 0xF5, 0x4F, 0x54, 0xF4, 0x4F, 0x54, 0xF5, 0x4F, 0x54, 0x36,
+            0xB4, 0x29, 0x93, 0x2B, 0x83, 0x1D, 0x78, 0x45, 0x76, 0x75, 0x66, 0x84,
+            0x65, 0x94, 0x65, 0x95, 0x55, 0xA4, 0x54, 0xB4, 0x54, 0xB4, 0x54, 0xB4,
+            0x54, 0xB4, 0x54, 0xB4, 0x54, 0xB4, 0x64, 0xA4, 0x64, 0x95, 0x64, 0x94,
+            0x75, 0x75, 0x85, 0x65, 0x86, 0x36, 0xAD, 0xCC, 0xDA, 0xF1, 0x60,
+        ],
+        // 7
+        &[
+            0xFF, 0xFF, 0xFF, 0x9F, 0x45, 0xF4, 0x5F, 0x45, 0xF4, 0x5F, 0x3F, 0x54,
+            0xF4, 0x5F, 0x44, 0xF5, 0x4F, 0x54, 0xF4, 0x4F, 0x54, 0xF5, 0x4F, 0x45,
+            0xF4, 0x4F, 0x54, 0xF4, 0x5F, 0x44, 0xF5, 0x4F, 0x45, 0xF4, 0x4F, 0x54,
+            0xF4, 0x5F, 0x45, 0xF4, 0x4F, 0x45, 0xF4, 0x5F, 0x44, 0xF5, 0x4F, 0x45,
+            0xF4, 0x4F, 0x54, 0xF4, 0x5F, 0x44, 0xF5, 0x4F, 0x45, 0xF4, 0x4F, 0x54,
+        ],
+        // 8
+        &[
+            0xFF, 0xFF, 0xF7, 0x4F, 0x39, 0xDC, 0xCD, 0xAF, 0x08, 0x65, 0x58, 0x57,
+            0x57, 0x49, 0x47, 0x49, 0x46, 0x59, 0x46, 0x59, 0x46, 0x59, 0x47, 0x49,
+            0x47, 0x49, 0x47, 0x48, 0x49, 0x47, 0x49, 0x55, 0x4B, 0xCE, 0x9F, 0x09,
+            0xDD, 0xA6, 0x27, 0x85, 0x74, 0x84, 0x94, 0x65, 0x94, 0x64, 0xA5, 0x54,
+            0xB4, 0x54, 0xB4, 0x54, 0xB4, 0x54, 0xB4, 0x54, 0xB4, 0x54, 0xA5, 0x55,
+            0x95, 0x55, 0x94, 0x75, 0x75, 0x77, 0x36, 0x9F, 0x0A, 0xDC, 0xBF, 0x07,
+        ],
+        // 9
+        &[
+            0xFF, 0xFF, 0xF7, 0x4F, 0x29, 0xEB, 0xCD, 0xBE, 0x95, 0x64, 0x94, 0x84,
+            0x75, 0x84, 0x74, 0xA4, 0x64, 0xA4, 0x64, 0xA4, 0x64, 0xA4, 0x64, 0xA4,
+            0x64, 0xA4, 0x64, 0xA5, 0x54, 0xA5, 0x54, 0xA5, 0x54, 0xA5, 0x54, 0x96,
+            0x55, 0x86, 0x64, 0x77, 0x65, 0x58, 0x7F, 0x27, 0xC1, 0x48, 0xA2, 0x4A,
+            0x73, 0x4F, 0x53, 0xF5, 0x4F, 0x54, 0xF5, 0x4F, 0x54, 0xF4, 0x4F, 0x54,
+            0xF4, 0x59, 0x27, 0x5A, 0xDB, 0xDB, 0xCC, 0xBF, 0x07,
+        ],
+        // .
+        &[
+            0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
+            0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
+            0xFF, 0xFB, 0x7F, 0x27, 0xF2, 0x7F, 0x27, 0xF2, 0x7F, 0x27, 0xF2, 0x7F,
+            0x27, 0xF2, 0x7F, 0x27,
+        ],
+        // -
+        &[
+            0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
+            0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xF7, 0xF1, 0x8F, 0x18, 0xF1, 0x8F, 0x18,
+            0xF1,
+        ],
+        // <space>
+        &[],
+    ],
+};
@@ -0,0 +1,112 @@
+//! Keeps the soft fuse limit of `protection.rs` in a record of the settings page, and with it
+//! which channels have a blown fuse, so a restart does not mend one.
+
+#[cfg(feature = "dual-output")]
+use crate::shared::FUSE_BLOWN_B_MUTEX;
+use crate::{
+    log::{info, warn, Module},
+    protection::FuseLimit,
+    settings::{self, SettingsError, FUSE_RECORD},
+    shared::{FUSE_BLOWN_MUTEX, FUSE_LIMIT_MUTEX},
+    types::Channel,
+};
+use embassy_sync::{blocking_mutex::raw::CriticalSectionRawMutex, signal::Signal};
+
+const LOG_MODULE: Module = Module::Output;
+
+const MAGIC: u32 = 0x5044_4655; // "PDFU"
+
+/// The byte after the limit, with a bit per channel whose fuse is blown; records from before it
+/// have it zero.
+const BLOWN_OFFSET: usize = 3;
+
+/// Raised by [`mark_blown`] for [`write_blown`].
+static BLOWN_SIGNAL: Signal<CriticalSectionRawMutex, ()> = Signal::new();
+
+/// Loads the stored limit into `FUSE_LIMIT_MUTEX`, none stored keeps the default, and the blown
+/// fuses into `FUSE_BLOWN_MUTEX` and `FUSE_BLOWN_B_MUTEX`.
+pub(crate) async fn load() {
+    let buf = settings::read(FUSE_RECORD, MAGIC);
+    let limit = buf.as_ref().and_then(FuseLimit::from_bytes);
+    let blown = buf.map_or(0, |buf| buf[BLOWN_OFFSET]);
+
+    info!("fuse limit: {:?}, blown: {:?}", limit, blown);
+    *FUSE_LIMIT_MUTEX.lock().await = limit.unwrap_or(FuseLimit::default());
+    *FUSE_BLOWN_MUTEX.lock().await = blown & bit(Channel::A) != 0;
+    #[cfg(feature = "dual-output")]
+    {
+        *FUSE_BLOWN_B_MUTEX.lock().await = blown & bit(Channel::B) != 0;
+    }
+}
+
+/// Writes `limit` to flash and makes it the active one.
+pub(crate) async fn store(limit: FuseLimit) -> Result<(), SettingsError> {
+    write(limit, blown_bits().await).await?;
+
+    *FUSE_LIMIT_MUTEX.lock().await = limit;
+
+    Ok(())
+}
+
+/// Marks the fuse of `channel` blown or mended, and writes that to flash.
+pub(crate) async fn store_blown(channel: Channel, blown: bool) -> Result<(), SettingsError> {
+    set_blown(channel, blown).await;
+
+    write(*FUSE_LIMIT_MUTEX.lock().await, blown_bits().await).await
+}
+
+/// Marks the fuse of `channel` blown and leaves the flash write to [`write_blown`], so the
+/// protection loop that blew it does not sit out a page erase.
+pub(crate) async fn mark_blown(channel: Channel) {
+    set_blown(channel, true).await;
+
+    BLOWN_SIGNAL.signal(());
+}
+
+/// Writes the blown fuses to flash each time [`mark_blown`] marks one; runs as its own task.
+pub(crate) async fn write_blown() -> ! {
+    loop {
+        BLOWN_SIGNAL.wait().await;
+
+        if let Err(err) = write(*FUSE_LIMIT_MUTEX.lock().await, blown_bits().await).await {
+            warn!("blown fuse not saved: {:?}", err);
+        }
+    }
+}
+
+async fn set_blown(channel: Channel, blown: bool) {
+    match channel {
+        Channel::A => *FUSE_BLOWN_MUTEX.lock().await = blown,
+        #[cfg(feature = "dual-output")]
+        Channel::B => *FUSE_BLOWN_B_MUTEX.lock().await = blown,
+        #[cfg(not(feature = "dual-output"))]
+        Channel::B => {}
+    }
+}
+
+async fn write(limit: FuseLimit, blown: u8) -> Result<(), SettingsError> {
+    let mut buf = limit.to_bytes();
+    buf[BLOWN_OFFSET] = blown;
+
+    settings::write(FUSE_RECORD, MAGIC, &buf).await
+}
+
+async fn blown_bits() -> u8 {
+    let mut blown = 0;
+    if *FUSE_BLOWN_MUTEX.lock().await {
+        blown |= bit(Channel::A);
+    }
+    #[cfg(feature = "dual-output")]
+    if *FUSE_BLOWN_B_MUTEX.lock().await {
+        blown |= bit(Channel::B);
+    }
+
+    blown
+}
+
+fn bit(channel: Channel) -> u8 {
+    match channel {
+        Channel::A => 1 << 0,
+        Channel::B => 1 << 1,
+    }
+}
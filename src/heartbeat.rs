@@ -0,0 +1,146 @@
+//! Task heartbeats for the diagnostics page.
+//!
+//! Every long-running task calls [`beat`] once per pass of its loop, and waits with a timeout of at
+//! most [`HEARTBEAT_INTERVAL`] where it would otherwise block on input, so a heartbeat that stops
+//! or arrives late points at a stalled or starved task. The gaps between beats are the task's loop
+//! latency; their mean and worst case are kept alongside.
+//!
+//! Each task has its own slot and is its only writer, so plain atomic loads and stores are enough,
+//! which the Cortex-M0+ supports without read-modify-write instructions.
+
+use core::sync::atomic::{AtomicU32, Ordering};
+
+use embassy_time::Duration;
+#[cfg(not(feature = "mock-time"))]
+use embassy_time::Instant;
+
+#[cfg(feature = "mock-time")]
+use crate::mock_time::Instant;
+
+/// Longest an idle task goes without a beat.
+pub(crate) const HEARTBEAT_INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) enum Task {
+    Measure,
+    Controller,
+    Buttons,
+    Backlight,
+}
+
+pub(crate) const TASKS: [Task; 4] = [
+    Task::Measure,
+    Task::Controller,
+    Task::Buttons,
+    Task::Backlight,
+];
+
+impl Task {
+    /// Short enough for the name column of the diagnostics page.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Task::Measure => "meas",
+            Task::Controller => "ctrl",
+            Task::Buttons => "btns",
+            Task::Backlight => "light",
+        }
+    }
+}
+
+struct Heartbeat {
+    beats: AtomicU32,
+    last_ms: AtomicU32,
+    total_gap_ms: AtomicU32,
+    max_gap_ms: AtomicU32,
+}
+
+impl Heartbeat {
+    const fn new() -> Self {
+        Self {
+            beats: AtomicU32::new(0),
+            last_ms: AtomicU32::new(0),
+            total_gap_ms: AtomicU32::new(0),
+            max_gap_ms: AtomicU32::new(0),
+        }
+    }
+}
+
+static HEARTBEATS: [Heartbeat; TASKS.len()] = [
+    Heartbeat::new(),
+    Heartbeat::new(),
+    Heartbeat::new(),
+    Heartbeat::new(),
+];
+
+/// What the diagnostics page and the `tasks` console command show for a task.
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) struct TaskStats {
+    pub task: Task,
+    pub beats: u32,
+    /// Time since the last beat, `None` before the first.
+    pub age_ms: Option<u32>,
+    pub mean_gap_ms: u32,
+    pub max_gap_ms: u32,
+}
+
+/// Records that `task` went once around its loop.
+pub(crate) fn beat(task: Task) {
+    let heartbeat = &HEARTBEATS[task as usize];
+    let now = now_ms();
+    let beats = heartbeat.beats.load(Ordering::Relaxed);
+
+    if beats > 0 {
+        let gap = now.wrapping_sub(heartbeat.last_ms.load(Ordering::Relaxed));
+        let total = heartbeat.total_gap_ms.load(Ordering::Relaxed);
+
+        heartbeat
+            .total_gap_ms
+            .store(total.saturating_add(gap), Ordering::Relaxed);
+
+        if gap > heartbeat.max_gap_ms.load(Ordering::Relaxed) {
+            heartbeat.max_gap_ms.store(gap, Ordering::Relaxed);
+        }
+    }
+
+    heartbeat.last_ms.store(now, Ordering::Relaxed);
+    heartbeat
+        .beats
+        .store(beats.saturating_add(1), Ordering::Relaxed);
+}
+
+pub(crate) fn stats(task: Task) -> TaskStats {
+    let heartbeat = &HEARTBEATS[task as usize];
+    let beats = heartbeat.beats.load(Ordering::Relaxed);
+
+    let age_ms = match beats {
+        0 => None,
+        _ => Some(now_ms().wrapping_sub(heartbeat.last_ms.load(Ordering::Relaxed))),
+    };
+
+    let mean_gap_ms = match beats {
+        0 | 1 => 0,
+        _ => heartbeat.total_gap_ms.load(Ordering::Relaxed) / (beats - 1),
+    };
+
+    TaskStats {
+        task,
+        beats,
+        age_ms,
+        mean_gap_ms,
+        max_gap_ms: heartbeat.max_gap_ms.load(Ordering::Relaxed),
+    }
+}
+
+/// Starts the latency statistics over. A task racing with this may keep one gap from before.
+pub(crate) fn reset() {
+    for heartbeat in HEARTBEATS.iter() {
+        heartbeat.beats.store(0, Ordering::Relaxed);
+        heartbeat.total_gap_ms.store(0, Ordering::Relaxed);
+        heartbeat.max_gap_ms.store(0, Ordering::Relaxed);
+    }
+}
+
+/// Milliseconds since boot, wrapping after 49 days; only differences are used.
+fn now_ms() -> u32 {
+    Instant::now().as_millis() as u32
+}
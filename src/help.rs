@@ -0,0 +1,123 @@
+//! The button cheat-sheet on the About page.
+//!
+//! Two buttons cover the whole menu through presses, long presses, double clicks and both at once,
+//! and what each does depends on the page. Past the unit's own sub-page, Up and Down on the About
+//! page go through a list of the gestures for each part of the menu, so they can be looked up on
+//! the unit instead of in the source. Keep it in step with `menu.rs` and `Controller`.
+
+use core::fmt::Write;
+
+use heapless::String;
+
+use crate::types::AboutView;
+
+/// Characters of a line, what fits right of the settings list in `FONT_6X10`.
+pub(crate) const HELP_WIDTH: usize = 25;
+/// Characters of the gesture column, the action takes the rest.
+const BUTTONS_WIDTH: usize = 11;
+const ACTION_WIDTH: usize = HELP_WIDTH - BUTTONS_WIDTH;
+
+pub(crate) struct Gesture {
+    /// "Up+Dn" is both at once, "x2" a double click.
+    pub buttons: &'static str,
+    pub action: &'static str,
+}
+
+const fn gesture(buttons: &'static str, action: &'static str) -> Gesture {
+    Gesture { buttons, action }
+}
+
+#[cfg(not(feature = "dual-output"))]
+const MONITOR: &[Gesture] = &[
+    gesture("Up", "brighter"),
+    gesture("Dn", "dimmer"),
+    gesture("Up long", "output, re-arm"),
+    gesture("Dn long", "screen off"),
+    gesture("Up+Dn", "OCP"),
+    gesture("Up+Dn long", "settings"),
+];
+
+#[cfg(feature = "dual-output")]
+const MONITOR: &[Gesture] = &[
+    gesture("Dn", "channel A/B"),
+    gesture("Up long", "output, re-arm"),
+    gesture("Dn long", "screen off"),
+    gesture("Up+Dn", "OCP"),
+    gesture("Up+Dn long", "settings"),
+];
+
+const SETTINGS: &[Gesture] = &[
+    gesture("Up", "next item"),
+    gesture("Dn", "previous item"),
+    gesture("Up+Dn", "open"),
+    gesture("Up+Dn long", "monitor"),
+    gesture("In a page:", ""),
+    gesture("Up/Dn", "change value"),
+    gesture("Up+Dn", "confirm, next"),
+    gesture("Up+Dn long", "leave"),
+];
+
+const TOOLS: &[Gesture] = &[
+    gesture("Up", "cable: record"),
+    gesture("Dn", "cable: clear"),
+    gesture("Up/Dn", "scope: trigger"),
+    gesture("Up+Dn long", "scope: arm"),
+    gesture("Up", "session: start"),
+    gesture("Dn", "session: stop"),
+    gesture("Up", "SD: mount"),
+    gesture("Dn", "SD: unmount"),
+    gesture("Up+Dn", "back"),
+];
+
+const DIAGNOSTICS: &[Gesture] = &[
+    gesture("Up/Dn", "diag: view"),
+    gesture("Up+Dn long", "diag: reset"),
+    gesture("Up+Dn", "diag: back"),
+    gesture("Up/Dn", "about: page"),
+    gesture("Up+Dn", "about: back"),
+    gesture("About p. 1:", ""),
+    gesture("Up+Dn long", "bootloader"),
+];
+
+const ANYWHERE: &[Gesture] = &[
+    gesture("Up x2", "flip screen"),
+    gesture("Dn x2", "flip screen"),
+    gesture("Up let go", "momentary off"),
+];
+
+/// The heading of `view`, up to the 9 characters of the status font.
+pub(crate) fn title(view: AboutView) -> &'static str {
+    match view {
+        AboutView::Info => "About",
+        AboutView::Monitor => "Monitor",
+        AboutView::Settings => "Settings",
+        AboutView::Tools => "Tools",
+        AboutView::Diagnostics => "Diag",
+        AboutView::Anywhere => "Anywhere",
+    }
+}
+
+/// The gestures listed on `view`, none on the unit's own sub-page.
+pub(crate) fn gestures(view: AboutView) -> &'static [Gesture] {
+    match view {
+        AboutView::Info => &[],
+        AboutView::Monitor => MONITOR,
+        AboutView::Settings => SETTINGS,
+        AboutView::Tools => TOOLS,
+        AboutView::Diagnostics => DIAGNOSTICS,
+        AboutView::Anywhere => ANYWHERE,
+    }
+}
+
+/// `gesture` as a line of two columns, padded to the full width so it covers a longer one.
+pub(crate) fn line(gesture: &Gesture) -> String<HELP_WIDTH> {
+    let mut line = String::new();
+    write!(
+        line,
+        "{:<BUTTONS_WIDTH$}{:<ACTION_WIDTH$}",
+        gesture.buttons, gesture.action
+    )
+    .ok();
+
+    line
+}
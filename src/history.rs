@@ -0,0 +1,82 @@
+//! Decimated V/I/P history, kept so events that already happened can be exported later.
+//!
+//! The main loop feeds every reading into [`History::record`]; each `HISTORY_INTERVAL` the
+//! readings are averaged into one sample. The oldest sample is dropped once the ring is full.
+
+use embassy_time::{Duration, Instant};
+use heapless::Deque;
+
+use crate::{
+    types::PowerInfo,
+    units::{self, Compact},
+};
+
+pub(crate) const HISTORY_INTERVAL: Duration = Duration::from_secs(1);
+/// Five minutes at one sample per second.
+pub(crate) const HISTORY_LEN: usize = 300;
+
+#[derive(Clone, Copy)]
+pub(crate) struct Sample {
+    /// Seconds since boot at the end of the interval.
+    pub uptime_secs: u32,
+    pub volts: Compact,
+    pub amps: Compact,
+    pub watts: Compact,
+}
+
+pub(crate) struct History {
+    samples: Deque<Sample, HISTORY_LEN>,
+    sum: PowerInfo,
+    count: u32,
+    started: Option<Instant>,
+}
+
+impl History {
+    pub const fn new() -> Self {
+        Self {
+            samples: Deque::new(),
+            sum: PowerInfo::default(),
+            count: 0,
+            started: None,
+        }
+    }
+
+    pub fn record(&mut self, now: Instant, power: &PowerInfo) {
+        let started = *self.started.get_or_insert(now);
+
+        self.sum.volts += power.volts;
+        self.sum.amps += power.amps;
+        self.sum.watts += power.watts;
+        self.count += 1;
+
+        if now - started < HISTORY_INTERVAL {
+            return;
+        }
+
+        let sample = Sample {
+            uptime_secs: now.as_secs() as u32,
+            volts: units::compact(units::div(self.sum.volts, self.count)),
+            amps: units::compact(units::div(self.sum.amps, self.count)),
+            watts: units::compact(units::div(self.sum.watts, self.count)),
+        };
+
+        if self.samples.is_full() {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(sample).ok();
+
+        self.sum = PowerInfo::default();
+        self.count = 0;
+        self.started = Some(now);
+    }
+
+    /// Returns the oldest sample taken after `uptime_secs`, or the oldest one overall.
+    ///
+    /// Lets readers walk the ring one sample per lock while new samples keep arriving.
+    pub fn next_after(&self, uptime_secs: Option<u32>) -> Option<Sample> {
+        self.samples
+            .iter()
+            .find(|s| !matches!(uptime_secs, Some(t) if s.uptime_secs <= t))
+            .copied()
+    }
+}
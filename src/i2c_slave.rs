@@ -0,0 +1,156 @@
+//! I2C slave exposing the register map in `register_map.rs`.
+//!
+//! embassy-stm32 only implements the master side, so the peripheral is driven through the PAC.
+//! The master writes a register pointer, optionally followed by a big-endian 16-bit value, and
+//! reads registers back as big-endian words with an auto-incrementing pointer. Clock stretching
+//! holds the bus while the task is polling, so no interrupt handler is needed.
+
+use embassy_stm32::{
+    gpio::Pin,
+    pac::{
+        self,
+        gpio::vals::{Moder, Ot},
+        i2c::vals::Dir,
+    },
+};
+use embassy_time::{Duration, Timer};
+use heapless::Vec;
+
+use crate::{
+    bsp,
+    log::{warn, Module},
+    register_map::{read_registers, write_register, REG_COUNT},
+};
+
+const LOG_MODULE: Module = Module::Fieldbus;
+
+pub(crate) const I2C_SLAVE_ADDRESS: u8 = 0x42;
+
+const POLL_INTERVAL: Duration = Duration::from_micros(100);
+
+// 100 kHz timings for a 16 MHz kernel clock, see RM0444 table "Examples of timings settings".
+const TIMINGR: u32 = 0x3042_0F13;
+
+pub(crate) struct I2cSlave {
+    regs: pac::i2c::I2c,
+    pointer: u8,
+    rx: Vec<u8, 3>,
+    tx: [u8; REG_COUNT * 2],
+    tx_index: usize,
+}
+
+impl I2cSlave {
+    /// Both lines are expected on port B, where I2C2 is alternate function 6.
+    pub fn new(
+        _i2c: bsp::SlaveI2c,
+        scl: bsp::SlaveSclPin,
+        sda: bsp::SlaveSdaPin,
+        address: u8,
+    ) -> Self {
+        pac::RCC.apbenr1().modify(|w| w.set_i2c2en(true));
+
+        for pin in [scl.pin() as usize, sda.pin() as usize] {
+            pac::GPIOB.otyper().modify(|w| w.set_ot(pin, Ot::OPENDRAIN));
+            pac::GPIOB.afr(pin / 8).modify(|w| w.set_afr(pin % 8, 6));
+            pac::GPIOB
+                .moder()
+                .modify(|w| w.set_moder(pin, Moder::ALTERNATE));
+        }
+
+        let regs = pac::I2C2;
+
+        regs.cr1().modify(|w| w.set_pe(false));
+        regs.timingr().write_value(pac::i2c::regs::Timingr(TIMINGR));
+        regs.oar1().write(|w| {
+            w.set_oa1(u16::from(address) << 1);
+            w.set_oa1en(true);
+        });
+        regs.cr1().modify(|w| w.set_pe(true));
+
+        Self {
+            regs,
+            pointer: 0,
+            rx: Vec::new(),
+            tx: [0; REG_COUNT * 2],
+            tx_index: 0,
+        }
+    }
+
+    pub async fn task(&mut self) {
+        loop {
+            let isr = self.regs.isr().read();
+
+            if isr.addr() {
+                if isr.dir() == Dir::READ {
+                    self.prepare_read().await;
+                } else {
+                    self.rx.clear();
+                }
+
+                self.regs.icr().write(|w| w.set_addrcf(true));
+                continue;
+            }
+
+            if isr.rxne() {
+                let byte = self.regs.rxdr().read().rxdata();
+
+                // Extra bytes beyond pointer + one word are ignored.
+                self.rx.push(byte).ok();
+                continue;
+            }
+
+            if isr.txis() {
+                let byte = self.tx.get(self.tx_index).copied().unwrap_or(0xff);
+                self.tx_index += 1;
+
+                self.regs.txdr().write(|w| w.set_txdata(byte));
+                continue;
+            }
+
+            if isr.nackf() {
+                self.regs.icr().write(|w| w.set_nackcf(true));
+            }
+
+            if isr.stopf() {
+                self.regs.icr().write(|w| w.set_stopcf(true));
+                self.finish_write().await;
+                continue;
+            }
+
+            Timer::after(POLL_INTERVAL).await;
+        }
+    }
+
+    async fn prepare_read(&mut self) {
+        let mut regs = [0u16; REG_COUNT];
+
+        read_registers(&mut regs).await;
+
+        for (i, reg) in regs.iter().enumerate() {
+            self.tx[i * 2..i * 2 + 2].copy_from_slice(&reg.to_be_bytes());
+        }
+
+        self.tx_index = self.pointer as usize * 2;
+
+        // Drop any byte left in TXDR from a previous, NACKed read.
+        self.regs.isr().modify(|w| w.set_txe(true));
+    }
+
+    async fn finish_write(&mut self) {
+        if self.rx.is_empty() {
+            return;
+        }
+
+        self.pointer = self.rx[0];
+
+        if self.rx.len() == 3 {
+            let value = u16::from_be_bytes([self.rx[1], self.rx[2]]);
+
+            if let Err(err) = write_register(self.pointer, value).await {
+                warn!("i2c slave write {:#04x} rejected: {:?}", self.pointer, err);
+            }
+        }
+
+        self.rx.clear();
+    }
+}
@@ -0,0 +1,150 @@
+//! Idle low-power mode.
+//!
+//! Once the output is off and neither a button nor the console has been used for `IDLE_TIMEOUT`,
+//! the main loop is asked to park: it puts the panel to sleep and reports `Suspended`, while the
+//! backlight task switches off on the published `Suspending` state. Only then is SLEEPDEEP set, so
+//! the next time the executor runs out of work the core drops into STOP mode instead of a plain
+//! sleep. The button EXTI lines wake it up again, and so does a start bit on the console, whose
+//! USART runs from HSI16 through STOP, so a command sent to an idle unit wakes it and is answered.
+//!
+//! The timer driving embassy-time is halted in STOP, so no task wakes on its own while idle and
+//! `Instant` does not advance. The plotter stream and a daily schedule, whose next switch would
+//! never come, keep the unit out of idle for that reason.
+//! Idle mode is otherwise never entered with the output on, because over-current protection runs
+//! in the main loop, which is parked. On `ina226-alert` builds it is while the load stays under
+//! `WAKE_LOAD`: the INA226 pulls its ALERT line once the current goes over that, which wakes the
+//! unit and with it the protection, see `ina226_alert.rs`. The parked measurement loop waits on
+//! `WAKE_SIGNAL` rather than a timer, and runs a protection pass before it wakes the panel, which
+//! takes over 120 ms. The second output on `dual-output` builds has no alert, and keeps the unit
+//! awake while it is on.
+
+use embassy_futures::select::{select, Either};
+use embassy_sync::{blocking_mutex::raw::CriticalSectionRawMutex, pubsub::Subscriber};
+use embassy_time::{Duration, Timer};
+
+#[cfg(feature = "dual-output")]
+use crate::shared::OUTPUT_B_MUTEX;
+use crate::{
+    bsp,
+    log::{info, Module},
+    shared::{
+        ACTIVITY_PUBSUB, OUTPUT_MUTEX, PLOT_MUTEX, POWER_STATE_MUTEX, POWER_STATE_PUBSUB,
+        SCHEDULE_MUTEX, WAKE_SIGNAL,
+    },
+    types::PowerState,
+};
+#[cfg(feature = "ina226-alert")]
+use crate::{
+    ina226_alert::WAKE_LOAD,
+    shared::{INA226_ALERT_ARMED_MUTEX, POWER_INFO_MUTEX},
+};
+
+const LOG_MODULE: Module = Module::Power;
+
+pub(crate) const IDLE_TIMEOUT: Duration = Duration::from_secs(120);
+
+pub(crate) struct Idle<'a> {
+    activity_sub: Subscriber<'a, CriticalSectionRawMutex, (), 2, 2, 1>,
+}
+
+impl<'a> Idle<'a> {
+    pub fn new() -> Self {
+        // Keep the debug probe attached through STOP mode in development builds.
+        #[cfg(debug_assertions)]
+        {
+            use embassy_stm32::pac;
+
+            // The L4 keeps the DBGMCU clocked without being asked.
+            #[cfg(feature = "family-g0")]
+            pac::RCC.apbenr1().modify(|w| w.set_dbgen(true));
+            pac::DBGMCU.cr().modify(|w| w.set_dbg_stop(true));
+        }
+
+        Self {
+            activity_sub: ACTIVITY_PUBSUB.subscriber().unwrap(),
+        }
+    }
+
+    pub async fn task(&mut self) {
+        loop {
+            match select(
+                self.activity_sub.next_message_pure(),
+                Timer::after(IDLE_TIMEOUT),
+            )
+            .await
+            {
+                Either::First(_) => continue,
+                Either::Second(_) => {}
+            }
+
+            if !may_suspend().await {
+                continue;
+            }
+
+            set_power_state(PowerState::Suspending).await;
+
+            while *POWER_STATE_MUTEX.lock().await != PowerState::Suspended {
+                Timer::after(Duration::from_millis(10)).await;
+            }
+
+            info!("entering stop mode");
+            bsp::set_console_wakeup(true);
+            set_deep_sleep(true);
+
+            self.activity_sub.next_message_pure().await;
+
+            set_deep_sleep(false);
+            bsp::set_console_wakeup(false);
+            info!("woken up");
+
+            set_power_state(PowerState::Active).await;
+            WAKE_SIGNAL.signal(());
+        }
+    }
+}
+
+/// Whether nothing needs the unit awake: the output is off, or only lightly loaded while the
+/// INA226 can wake it, the second output is off, and neither the plotter nor a schedule, which
+/// both need the timer, is set.
+async fn may_suspend() -> bool {
+    if PLOT_MUTEX.lock().await.is_some() || SCHEDULE_MUTEX.lock().await.is_some() {
+        return false;
+    }
+
+    // The second channel runs its own protection on the timer, and the alert only watches the
+    // first one.
+    #[cfg(feature = "dual-output")]
+    if *OUTPUT_B_MUTEX.lock().await {
+        return false;
+    }
+
+    if !*OUTPUT_MUTEX.lock().await {
+        return true;
+    }
+
+    #[cfg(feature = "ina226-alert")]
+    {
+        *INA226_ALERT_ARMED_MUTEX.lock().await && POWER_INFO_MUTEX.lock().await.amps < WAKE_LOAD
+    }
+    #[cfg(not(feature = "ina226-alert"))]
+    {
+        false
+    }
+}
+
+async fn set_power_state(state: PowerState) {
+    *POWER_STATE_MUTEX.lock().await = state;
+    POWER_STATE_PUBSUB
+        .immediate_publisher()
+        .publish_immediate(state);
+}
+
+fn set_deep_sleep(enabled: bool) {
+    let mut core = unsafe { cortex_m::Peripherals::steal() };
+
+    if enabled {
+        core.SCB.set_sleepdeep();
+    } else {
+        core.SCB.clear_sleepdeep();
+    }
+}
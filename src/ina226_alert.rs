@@ -0,0 +1,48 @@
+//! The INA226 ALERT line as a wakeup source on `ina226-alert` builds.
+//!
+//! Whenever the chip is configured it is also set to compare every shunt voltage conversion
+//! against [`WAKE_LOAD`] and to pull ALERT low as long as the current is over it. The line goes to
+//! an EXTI pin, see `bsp.rs`, and an edge on it counts as activity, which wakes an idle unit, see
+//! `idle.rs`, or lights the screen on an awake one. The driver does not cover the mask/enable and
+//! alert limit registers, so they are written here over the same shared bus.
+
+use embedded_hal_async::i2c::I2c;
+
+use crate::{
+    power_monitor::PowerMonitorConfig,
+    units::{self, Value},
+};
+
+const REG_MASK_ENABLE: u8 = 0x06;
+const REG_ALERT_LIMIT: u8 = 0x07;
+
+/// Load under which the unit may idle with the output on, and over which ALERT wakes it again.
+pub(crate) const WAKE_LOAD: Value = units::from_milli(50);
+
+/// Shunt voltage over-voltage: alert while the shunt voltage is above the limit.
+const MASK_SOL: u16 = 1 << 15;
+
+pub(crate) struct Ina226Alert<I2C> {
+    i2c: I2C,
+    config: PowerMonitorConfig,
+}
+
+impl<I2C: I2c> Ina226Alert<I2C> {
+    pub fn new(i2c: I2C, config: PowerMonitorConfig) -> Self {
+        Self { i2c, config }
+    }
+
+    /// Pulls ALERT low from the first conversion over [`WAKE_LOAD`] on.
+    pub async fn arm(&mut self) -> Result<(), I2C::Error> {
+        let limit = self.config.shunt_limit(units::milli(WAKE_LOAD));
+
+        self.write(REG_ALERT_LIMIT, limit).await?;
+        self.write(REG_MASK_ENABLE, MASK_SOL).await
+    }
+
+    async fn write(&mut self, reg: u8, value: u16) -> Result<(), I2C::Error> {
+        let [high, low] = value.to_be_bytes();
+
+        self.i2c.write(self.config.address, &[reg, high, low]).await
+    }
+}
@@ -0,0 +1,58 @@
+//! Integer readings from the INA226 for the `fixed-point` build.
+//!
+//! The `ina226` driver returns every result as `f64`. Here the bus voltage, current and power
+//! registers are read over the same shared bus and scaled to milli-units with integer arithmetic.
+//! The driver still configures and calibrates the chip, which sets the current register's LSB to
+//! the full-scale current over 2^15.
+
+use embedded_hal_async::i2c::I2c;
+
+const REG_BUS_VOLTAGE: u8 = 0x02;
+const REG_POWER: u8 = 0x03;
+const REG_CURRENT: u8 = 0x04;
+
+/// The current LSB is the full-scale current divided by this.
+const CURRENT_LSB_DIVISOR: i64 = 1 << 15;
+/// The power register's LSB is this many current LSBs.
+const POWER_LSB_RATIO: i64 = 25;
+
+pub(crate) struct Ina226Registers<I2C> {
+    i2c: I2C,
+    address: u8,
+    max_milliamps: i64,
+}
+
+impl<I2C: I2c> Ina226Registers<I2C> {
+    /// `max_milliamps` is the full-scale current the chip at `address` was calibrated for.
+    pub fn new(i2c: I2C, address: u8, max_milliamps: i32) -> Self {
+        Self {
+            i2c,
+            address,
+            max_milliamps: max_milliamps as i64,
+        }
+    }
+
+    pub async fn bus_millivolts(&mut self) -> Result<i32, I2C::Error> {
+        // 1.25 mV per LSB.
+        Ok(self.read(REG_BUS_VOLTAGE).await? as i32 * 5 / 4)
+    }
+
+    pub async fn current_milliamps(&mut self) -> Result<i32, I2C::Error> {
+        let raw = self.read(REG_CURRENT).await? as i16 as i64;
+
+        Ok((raw * self.max_milliamps / CURRENT_LSB_DIVISOR) as i32)
+    }
+
+    pub async fn power_milliwatts(&mut self) -> Result<i32, I2C::Error> {
+        let raw = self.read(REG_POWER).await? as i64;
+
+        Ok((raw * POWER_LSB_RATIO * self.max_milliamps / CURRENT_LSB_DIVISOR) as i32)
+    }
+
+    async fn read(&mut self, reg: u8) -> Result<u16, I2C::Error> {
+        let mut buf = [0u8; 2];
+        self.i2c.write_read(self.address, &[reg], &mut buf).await?;
+
+        Ok(u16::from_be_bytes(buf))
+    }
+}
@@ -0,0 +1,230 @@
+//! Samples the USB D+/D- and Type-C CC lines and VBUS for the diagnostics page.
+//!
+//! Four times a second the task averages a few ADC readings of each line it has pins for into
+//! `DATA_LINES_MUTEX`, `CC_LINES_MUTEX` and `VBUS_MUTEX`, and logs when the charger signature or
+//! the plug orientation changes, see `data_lines.rs`, `cc_lines.rs` and `rails.rs`. The data and
+//! CC lines go straight to the ADC pins, which is fine for the 3.3 V the data line signatures use
+//! at most; CC only goes above that when VCONN is on it, which a sink never asks for. VBUS comes
+//! through the divider in `bsp.rs`; with it the task also watches what the output switch
+//! dissipates and raises an alarm past `SWITCH_LIMIT_MUTEX`.
+
+use embassy_sync::{blocking_mutex::raw::CriticalSectionRawMutex, mutex::Mutex};
+use embassy_time::{Duration, Timer};
+
+#[cfg(feature = "vbus-sense")]
+use crate::{
+    bsp::VBUS_DIVIDER,
+    clock, console,
+    log::warn,
+    rails::{Rails, SwitchWatch},
+    shared::{
+        FAN_STATUS_MUTEX, RAW_POWER_MUTEX, STATUS_INFO_MUTEX, SWITCH_LIMIT_MUTEX, VBUS_MUTEX,
+    },
+    units::{self, fixed, Value},
+};
+use crate::{
+    bsp::{self, ADC_VREF_MV},
+    log::{info, Module},
+    types::AnalogAdc,
+};
+#[cfg(feature = "cc-lines")]
+use crate::{cc_lines::CcLines, shared::CC_LINES_MUTEX};
+#[cfg(all(feature = "vbus-sense", feature = "sd-log"))]
+use crate::{csv_log::Record, sd_card};
+#[cfg(feature = "data-lines")]
+use crate::{
+    data_lines::{DataLines, Signature},
+    shared::DATA_LINES_MUTEX,
+};
+
+const LOG_MODULE: Module = Module::Measure;
+
+const INTERVAL: Duration = Duration::from_millis(250);
+
+/// Readings averaged per line.
+const SAMPLES: u32 = 8;
+
+const ADC_FULL_SCALE: u32 = 4_095;
+
+pub(crate) struct LineMonitor {
+    adc: &'static Mutex<CriticalSectionRawMutex, AnalogAdc>,
+    #[cfg(feature = "data-lines")]
+    plus: bsp::DataPlusPin,
+    #[cfg(feature = "data-lines")]
+    minus: bsp::DataMinusPin,
+    #[cfg(feature = "cc-lines")]
+    cc1: bsp::Cc1Pin,
+    #[cfg(feature = "cc-lines")]
+    cc2: bsp::Cc2Pin,
+    #[cfg(feature = "vbus-sense")]
+    vbus: bsp::VbusPin,
+    #[cfg(feature = "vbus-sense")]
+    switch: SwitchWatch,
+}
+
+impl LineMonitor {
+    pub fn new(
+        adc: &'static Mutex<CriticalSectionRawMutex, AnalogAdc>,
+        #[cfg(feature = "data-lines")] plus: bsp::DataPlusPin,
+        #[cfg(feature = "data-lines")] minus: bsp::DataMinusPin,
+        #[cfg(feature = "cc-lines")] cc1: bsp::Cc1Pin,
+        #[cfg(feature = "cc-lines")] cc2: bsp::Cc2Pin,
+        #[cfg(feature = "vbus-sense")] vbus: bsp::VbusPin,
+    ) -> Self {
+        Self {
+            adc,
+            #[cfg(feature = "data-lines")]
+            plus,
+            #[cfg(feature = "data-lines")]
+            minus,
+            #[cfg(feature = "cc-lines")]
+            cc1,
+            #[cfg(feature = "cc-lines")]
+            cc2,
+            #[cfg(feature = "vbus-sense")]
+            vbus,
+            #[cfg(feature = "vbus-sense")]
+            switch: SwitchWatch::new(),
+        }
+    }
+
+    pub async fn task(&mut self) {
+        #[cfg(feature = "data-lines")]
+        let mut signature = None;
+        #[cfg(feature = "cc-lines")]
+        let mut orientation = None;
+
+        loop {
+            #[cfg(feature = "data-lines")]
+            {
+                let (plus_mv, minus_mv) = self.sample_data_lines().await;
+                let lines = DataLines { plus_mv, minus_mv };
+
+                let current = Signature::of(lines);
+                if signature != Some(current) {
+                    info!("data lines: {} ({:?})", current.as_str(), lines);
+                    signature = Some(current);
+                }
+
+                *DATA_LINES_MUTEX.lock().await = Some(lines);
+            }
+
+            #[cfg(feature = "cc-lines")]
+            {
+                let (cc1_mv, cc2_mv) = self.sample_cc_lines().await;
+                let lines = CcLines { cc1_mv, cc2_mv };
+
+                let current = lines.orientation();
+                if orientation != Some(current) {
+                    info!(
+                        "cc lines: {}, Rp {} ({:?})",
+                        current.as_str(),
+                        lines.rp().as_str(),
+                        lines
+                    );
+                    orientation = Some(current);
+                }
+
+                *CC_LINES_MUTEX.lock().await = Some(lines);
+            }
+
+            #[cfg(feature = "vbus-sense")]
+            {
+                let millivolts = VBUS_DIVIDER.input_millivolts(self.sample_vbus().await as u32);
+                let source = units::from_milli(millivolts as i32);
+
+                *VBUS_MUTEX.lock().await = Some(source);
+                self.watch_switch(source).await;
+            }
+
+            Timer::after(INTERVAL).await;
+        }
+    }
+
+    #[cfg(feature = "data-lines")]
+    async fn sample_data_lines(&mut self) -> (u16, u16) {
+        let mut adc = self.adc.lock().await;
+        let mut plus = 0;
+        let mut minus = 0;
+
+        for _ in 0..SAMPLES {
+            plus += adc.read(&mut self.plus) as u32;
+            minus += adc.read(&mut self.minus) as u32;
+        }
+
+        (millivolts(plus / SAMPLES), millivolts(minus / SAMPLES))
+    }
+
+    #[cfg(feature = "cc-lines")]
+    async fn sample_cc_lines(&mut self) -> (u16, u16) {
+        let mut adc = self.adc.lock().await;
+        let mut cc1 = 0;
+        let mut cc2 = 0;
+
+        for _ in 0..SAMPLES {
+            cc1 += adc.read(&mut self.cc1) as u32;
+            cc2 += adc.read(&mut self.cc2) as u32;
+        }
+
+        (millivolts(cc1 / SAMPLES), millivolts(cc2 / SAMPLES))
+    }
+
+    /// The level on the pin, below the divider.
+    #[cfg(feature = "vbus-sense")]
+    async fn sample_vbus(&mut self) -> u16 {
+        let mut adc = self.adc.lock().await;
+        let mut vbus = 0;
+
+        for _ in 0..SAMPLES {
+            vbus += adc.read(&mut self.vbus) as u32;
+        }
+
+        millivolts(vbus / SAMPLES)
+    }
+
+    /// Raises an alarm when the switch newly dissipates more than its limit, with the board
+    /// temperature the thermal task last read.
+    #[cfg(feature = "vbus-sense")]
+    async fn watch_switch(&mut self, source: Value) {
+        let power = *RAW_POWER_MUTEX.lock().await;
+        let rails = Rails {
+            contract: STATUS_INFO_MUTEX.lock().await.target_volts,
+            source,
+            load: power.volts,
+        };
+        let watts = rails.switch_watts(power.amps);
+
+        if !self.switch.check(watts, *SWITCH_LIMIT_MUTEX.lock().await) {
+            return;
+        }
+
+        let celsius = FAN_STATUS_MUTEX.lock().await.map(|fan| fan.celsius);
+        warn!(
+            "switch dissipating {} mW, drop {} mV, board {:?} C",
+            units::milli(watts),
+            units::milli(rails.switch_drop()),
+            celsius
+        );
+
+        match celsius {
+            Some(celsius) => console::println(format_args!(
+                "{} ALARM switch {}W {}C",
+                clock::now().await,
+                fixed(watts, 2, 0),
+                celsius
+            )),
+            None => console::println(format_args!(
+                "{} ALARM switch {}W",
+                clock::now().await,
+                fixed(watts, 2, 0)
+            )),
+        }
+
+        #[cfg(feature = "sd-log")]
+        sd_card::record(Record::Alarm("switch", watts));
+    }
+}
+
+fn millivolts(raw: u32) -> u16 {
+    (raw * ADC_VREF_MV / ADC_FULL_SCALE) as u16
+}
@@ -0,0 +1,216 @@
+//! Runtime-filtered logging on top of defmt.
+//!
+//! Every module that logs declares `const LOG_MODULE: log::Module = ...;` and uses the macros
+//! below exactly like their defmt counterparts; `info!(target: Module::Pd, ...)` overrides the
+//! module for a single call. Messages are dropped when their level is above the module's runtime
+//! level, which starts at [`DEFAULT_LEVEL`] and can be changed from the console.
+//! `DEFMT_LOG` in `.cargo/config.toml` still decides what gets compiled in at all: `info`, unless
+//! a build sets it in the environment, e.g. `DEFMT_LOG=debug cargo rr`.
+//!
+//! Builds with the `log-uart` feature, for units without a debug probe, send warnings and errors
+//! to the console UART as `WARN <module>: ...` lines instead, under the same runtime levels; the
+//! rest stays on defmt. Those messages then go through `format_args!` as well, so they only use
+//! `{}` and `{:?}` placeholders and the hex hints that both understand, as for the simulator.
+
+use portable_atomic::{AtomicU8, Ordering};
+
+#[derive(PartialEq, PartialOrd, Clone, Copy, Debug, defmt::Format)]
+#[repr(u8)]
+pub(crate) enum Level {
+    Off = 0,
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl Level {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Level::Off => "off",
+            Level::Error => "error",
+            Level::Warn => "warn",
+            Level::Info => "info",
+            Level::Debug => "debug",
+            Level::Trace => "trace",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        LEVELS.iter().copied().find(|level| level.as_str() == s)
+    }
+
+    fn from_u8(value: u8) -> Self {
+        LEVELS.get(value as usize).copied().unwrap_or(Level::Trace)
+    }
+}
+
+const LEVELS: &[Level] = &[
+    Level::Off,
+    Level::Error,
+    Level::Warn,
+    Level::Info,
+    Level::Debug,
+    Level::Trace,
+];
+
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+#[repr(u8)]
+pub(crate) enum Module {
+    Controller,
+    Display,
+    Console,
+    Output,
+    Pd,
+    Fieldbus,
+    Wifi,
+    Update,
+    Power,
+    Measure,
+    Thermal,
+    Storage,
+    System,
+}
+
+impl Module {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Module::Controller => "controller",
+            Module::Display => "display",
+            Module::Console => "console",
+            Module::Output => "output",
+            Module::Pd => "pd",
+            Module::Fieldbus => "fieldbus",
+            Module::Wifi => "wifi",
+            Module::Update => "update",
+            Module::Power => "power",
+            Module::Measure => "measure",
+            Module::Thermal => "thermal",
+            Module::Storage => "storage",
+            Module::System => "system",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        MODULES.iter().copied().find(|module| module.as_str() == s)
+    }
+}
+
+pub(crate) const MODULES: &[Module] = &[
+    Module::Controller,
+    Module::Display,
+    Module::Console,
+    Module::Output,
+    Module::Pd,
+    Module::Fieldbus,
+    Module::Wifi,
+    Module::Update,
+    Module::Power,
+    Module::Measure,
+    Module::Thermal,
+    Module::Storage,
+    Module::System,
+];
+
+pub(crate) const DEFAULT_LEVEL: Level = Level::Info;
+
+static MODULE_LEVELS: [AtomicU8; MODULES.len()] =
+    [const { AtomicU8::new(DEFAULT_LEVEL as u8) }; MODULES.len()];
+
+pub(crate) fn enabled(module: Module, level: Level) -> bool {
+    level <= self::level(module)
+}
+
+pub(crate) fn level(module: Module) -> Level {
+    Level::from_u8(MODULE_LEVELS[module as usize].load(Ordering::Relaxed))
+}
+
+pub(crate) fn set_level(module: Module, level: Level) {
+    MODULE_LEVELS[module as usize].store(level as u8, Ordering::Relaxed);
+}
+
+pub(crate) fn set_all_levels(level: Level) {
+    for module in MODULES {
+        set_level(*module, level);
+    }
+}
+
+/// Where warnings and errors go: defmt by default, the console UART on `log-uart` builds.
+#[cfg(not(feature = "log-uart"))]
+macro_rules! to_backend {
+    ($level:ident, $defmt:ident, $module:expr, $($arg:tt)*) => {
+        if $crate::log::enabled($module, $crate::log::Level::$level) {
+            defmt::$defmt!($($arg)*);
+        }
+    };
+}
+
+#[cfg(feature = "log-uart")]
+macro_rules! to_backend {
+    ($level:ident, $defmt:ident, $module:expr, $($arg:tt)*) => {
+        if $crate::log::enabled($module, $crate::log::Level::$level) {
+            $crate::console::log_line(
+                $crate::log::Level::$level,
+                $module,
+                format_args!($($arg)*),
+            );
+        }
+    };
+}
+
+macro_rules! error {
+    (target: $module:expr, $($arg:tt)*) => {
+        $crate::log::to_backend!(Error, error, $module, $($arg)*)
+    };
+    ($($arg:tt)*) => {
+        $crate::log::error!(target: LOG_MODULE, $($arg)*)
+    };
+}
+
+macro_rules! warn {
+    (target: $module:expr, $($arg:tt)*) => {
+        $crate::log::to_backend!(Warn, warn, $module, $($arg)*)
+    };
+    ($($arg:tt)*) => {
+        $crate::log::warn!(target: LOG_MODULE, $($arg)*)
+    };
+}
+
+macro_rules! info {
+    (target: $module:expr, $($arg:tt)*) => {
+        if $crate::log::enabled($module, $crate::log::Level::Info) {
+            defmt::info!($($arg)*);
+        }
+    };
+    ($($arg:tt)*) => {
+        $crate::log::info!(target: LOG_MODULE, $($arg)*)
+    };
+}
+
+macro_rules! debug {
+    (target: $module:expr, $($arg:tt)*) => {
+        if $crate::log::enabled($module, $crate::log::Level::Debug) {
+            defmt::debug!($($arg)*);
+        }
+    };
+    ($($arg:tt)*) => {
+        $crate::log::debug!(target: LOG_MODULE, $($arg)*)
+    };
+}
+
+#[allow(unused_macros)]
+macro_rules! trace {
+    (target: $module:expr, $($arg:tt)*) => {
+        if $crate::log::enabled($module, $crate::log::Level::Trace) {
+            defmt::trace!($($arg)*);
+        }
+    };
+    ($($arg:tt)*) => {
+        $crate::log::trace!(target: LOG_MODULE, $($arg)*)
+    };
+}
+
+#[allow(unused_imports)]
+pub(crate) use trace;
+pub(crate) use {debug, error, info, to_backend, warn};
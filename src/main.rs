@@ -1,123 +1,506 @@
 #![no_std]
 #![no_main]
 
+use backlight::Backlight;
+use bsp::Irqs;
 use button::Button;
+use button_trace::ButtonTrace;
+use console::Console;
 use controller::Controller;
+#[cfg(feature = "sd-log")]
+use csv_log::Record;
 use display::Display;
-use embassy_embedded_hal::shared_bus::{
-    asynch::{i2c::I2cDevice, spi::SpiDevice},
-    I2cDeviceError,
+use embassy_embedded_hal::{
+    shared_bus::{
+        asynch::{i2c::I2cDevice, spi::SpiDevice},
+        I2cDeviceError,
+    },
+    SetConfig,
 };
 use embassy_executor::Spawner;
-use embassy_futures::select::{select3, Either3};
+use embassy_futures::{
+    select::{select, select3, Either, Either3},
+    yield_now,
+};
+#[cfg(feature = "adc")]
+use embassy_stm32::adc::{Adc, SampleTime};
 use embassy_stm32::{
-    bind_interrupts,
     exti::ExtiInput,
+    flash::Flash,
     gpio::{Input, Level, Output, OutputType, Pull, Speed},
     i2c::{self, I2c},
-    peripherals::{self, DMA1_CH3, DMA1_CH4, I2C1, PB0, PC14},
+    rtc::{Rtc, RtcConfig},
     spi::{self, Spi},
-    time::{khz, Hertz},
-    timer::simple_pwm::{PwmPin, SimplePwm},
+    time::Hertz,
+    timer::{
+        simple_pwm::{PwmPin, SimplePwm},
+        Channel as TimerChannel, CountingMode,
+    },
+    usart::{self, Uart},
 };
 use embassy_sync::{blocking_mutex::raw::CriticalSectionRawMutex, mutex::Mutex};
 
+use average::IntervalAverage;
+use conversion::{Averaging, Conversion, ConversionTime};
 use defmt_rtt as _;
-use embassy_time::{Duration, Ticker};
+use embassy_time::{Duration, Instant, Ticker, Timer};
+use fault::Fault;
+use heartbeat::Task;
 use husb238::{Command, Husb238};
-use ina226::{DEFAULT_ADDRESS, INA226};
-// global logger
-use panic_probe as _;
+use ina226::INA226;
+#[cfg(feature = "ina226-alert")]
+use ina226_alert::Ina226Alert;
+use log::{error, info, warn, Module};
+use measure::{Reading, ReadingFilters};
+#[cfg(not(feature = "relay-output"))]
+use output_controller::PwmFetDriver;
+#[cfg(feature = "relay-output")]
+use output_controller::RelayDriver;
+use output_controller::{OutputController, OutputError, Protection};
+use output_stats::OutputStatsTracker;
+use pd_cache::PdCache;
+use power_monitor::{LinkEvent, MonitorLink, PowerMonitorConfig};
+use protection::{DerateEvent, OcpDerate, RampFault, SHORT_TEST_RAMP};
+#[cfg(not(feature = "relay-output"))]
+use pwm_timer::PwmChannel;
+use render::{Pending, RenderCmd};
+use render_budget::RenderBudget;
+#[cfg(feature = "replay")]
+use replay::Replay;
+use rms::RollingRms;
+use selftest::{ProbeError, SelfTest};
+use setup::{Setup, SETUP_STEPS};
 
+#[cfg(feature = "ina226-alert")]
+use shared::INA226_ALERT_ARMED_MUTEX;
+#[cfg(feature = "dual-output")]
+use shared::OUTPUT_B_MUTEX;
+#[cfg(feature = "trigger")]
+use shared::TRIGGER_MUTEX;
+#[cfg(feature = "vbus-sense")]
+use shared::VBUS_MUTEX;
 use shared::{
-    AVAILABLE_VOLT_CURR_MUTEX, BTN_A_STATE_CHANNEL, BTN_B_STATE_CHANNEL, DISPLAY, PDO_PUBSUB,
+    ocp_mutex, select_pdo, ACTIVITY_PUBSUB, AVAILABLE_VOLT_CURR_MUTEX, AVERAGE_INTERVAL_MUTEX,
+    AVERAGE_MUTEX, BTN_A_STATE_CHANNEL, BTN_B_STATE_CHANNEL, CALIBRATION_MUTEX, CAPTURE_MUTEX,
+    CONSOLE_TX_CHANNEL, CONVERSION_MUTEX, DISPLAY, DISPLAY_AVAILABLE_MUTEX, DISPLAY_DEGRADED_MUTEX,
+    DISPLAY_SPI_MAX_MUTEX, DISPLAY_SPI_PUBSUB, ENERGY_MUTEX, FAN_STATUS_MUTEX, FAULTS_MUTEX,
+    FILTER_MUTEX, FILTER_PUBSUB, FLASH, FUSE_BLOWN_MUTEX, FUSE_LIMIT_MUTEX, HISTORY_MUTEX,
+    NEXT_ACTION_MUTEX, OCP_MUTEX, OCP_PUBSUB, OUTPUT_MODE_MUTEX, OUTPUT_MODE_PUBSUB, OUTPUT_MUTEX,
+    OUTPUT_PUBSUB, OUTPUT_SENSE_MUTEX, OUTPUT_STATS_MUTEX, OVP_MUTEX, PAGE_MUTEX, PAGE_PUBSUB,
+    PDO_MUTEX, PDO_PUBSUB, POWER_INFO_MUTEX, POWER_PROFILE_MUTEX, POWER_PROFILE_PUBSUB,
+    POWER_STATE_MUTEX, RAW_POWER_MUTEX, REMOTE_MUTEX, RENDER_CHANNEL, RMS_MUTEX, SESSION_MUTEX,
+    SETUP_MUTEX, SHORT_TEST_MUTEX, SLEW_LIMITS_MUTEX, STATUS_INFO_MUTEX, SYSTEM_STATUS_MUTEX,
+    TRIPPED_MUTEX, UVP_MUTEX, WAKE_SIGNAL, WATTS_SOURCE_MUTEX, WATTS_SOURCE_PUBSUB,
+    WIFI_STATE_MUTEX,
 };
+use slew::{SlewKind, SlewMonitor};
+use spi_bus::ChunkedSpi;
 use st7789::{self, ST7789};
 use static_cell::StaticCell;
-use types::{AvailableVoltCurr, ST7789Display, SpiBus};
+#[cfg(feature = "telemetry")]
+use telemetry::SampleBatch;
+use timing::Section;
+#[cfg(feature = "trigger")]
+use trigger::{Trigger, TriggerEvent};
+#[cfg(feature = "adc")]
+use types::AnalogAdc;
+use types::{
+    nearest_pdo, pdo_volts, AvailableVoltCurr, Channel, ConsoleRx, ConsoleTx, ControlSource,
+    Negotiation, OutputMode, Page, PdRequest, PowerInfo, PowerProfile, PowerState, ST7789DCPin,
+    ST7789Display, ST7789RstPin, ST7789SpiDev, SensorI2cBus, SpiBus, StatusInfo, SystemStatus,
+};
+use units::{Value, ZERO};
+use utilization::{Utilization, UtilizationMonitor, WARN_PERCENT};
+use watts::{PeakHold, SyncedPower, WattsSource};
 
+mod average;
+mod backlight;
+mod bootloader;
+mod bsp;
 mod button;
+mod button_trace;
+mod cable;
+mod calendar;
+mod calibration;
+mod capture;
+// Only the line monitor reads the levels.
+#[cfg_attr(not(feature = "cc-lines"), allow(dead_code))]
+mod cc_lines;
+mod clock;
+mod console;
 mod controller;
+mod conversion;
+mod crash;
+// Only the SD card log writes the lines.
+#[cfg_attr(not(feature = "sd-log"), allow(dead_code))]
+mod csv_log;
+// Only the line monitor reads the levels.
+#[cfg_attr(not(feature = "data-lines"), allow(dead_code))]
+mod data_lines;
 mod display;
+mod display_settings;
+mod entry;
+// Only the thermal task runs the fan control.
+#[cfg_attr(not(feature = "fan"), allow(dead_code))]
+mod fan;
+mod fault;
+mod filter;
+mod fmt;
 mod font;
+mod fuse_settings;
+mod heartbeat;
+mod help;
+mod history;
+#[cfg(feature = "i2c-slave")]
+mod i2c_slave;
+#[cfg(not(any(feature = "i2c-slave", feature = "modbus", feature = "wifi")))]
+mod idle;
+#[cfg(feature = "ina226-alert")]
+mod ina226_alert;
+#[cfg(feature = "fixed-point")]
+mod ina226_regs;
+#[cfg(any(feature = "data-lines", feature = "cc-lines", feature = "vbus-sense"))]
+mod line_monitor;
+mod log;
+mod measure;
+mod menu;
+#[cfg(feature = "modbus")]
+mod modbus;
+mod monitor_settings;
+mod output_controller;
+mod output_stats;
+mod pd_cache;
+mod plotter;
+mod power_monitor;
+mod protection;
+mod pwm_timer;
+#[cfg_attr(not(feature = "vbus-sense"), allow(dead_code))]
+mod rails;
+#[cfg(any(feature = "i2c-slave", feature = "modbus"))]
+mod register_map;
+#[cfg(feature = "relay-output")]
+mod relay;
+mod remote;
+mod render;
+mod render_budget;
+#[cfg_attr(not(feature = "replay"), allow(dead_code))]
+mod replay;
+mod reset_cause;
+mod rle;
+mod rms;
+mod schedule;
+mod scheduler;
+mod screenshot;
+#[cfg(feature = "sd-log")]
+mod sd_card;
+#[cfg(feature = "dual-output")]
+mod second_output;
+mod selftest;
+mod session;
+mod settings;
+mod setup;
+mod setup_settings;
 mod shared;
+mod signature;
+mod slew;
+mod slew_settings;
+mod spi_bus;
+mod stack;
+mod stats_settings;
+mod telemetry;
+mod theme;
+#[cfg(feature = "fan")]
+mod thermal;
+mod timing;
+#[cfg(feature = "trigger")]
+mod trigger;
 mod types;
+// Not every helper is needed by every feature set.
+#[allow(dead_code)]
+mod units;
+mod updater;
+mod utilization;
+mod watts;
+#[cfg(feature = "wifi")]
+mod wifi;
 
 static SPI_BUS_MUTEX: StaticCell<Mutex<CriticalSectionRawMutex, SpiBus>> = StaticCell::new();
-static HUSB238_I2C_MUTEX: StaticCell<
-    Mutex<CriticalSectionRawMutex, I2c<'_, I2C1, DMA1_CH3, DMA1_CH4>>,
-> = StaticCell::new();
+static HUSB238_I2C_MUTEX: StaticCell<Mutex<CriticalSectionRawMutex, SensorI2cBus>> =
+    StaticCell::new();
+#[cfg(feature = "adc")]
+static ADC_MUTEX: StaticCell<Mutex<CriticalSectionRawMutex, AnalogAdc>> = StaticCell::new();
+
+/// Conversions fast enough for the registers to hold a reading from each step of the short test
+/// ramp.
+const SHORT_TEST_INA226_CONFIG: ina226::Config = ina226::Config {
+    mode: ina226::MODE::ShuntBusVoltageContinuous,
+    avg: ina226::AVG::_1,
+    vbusct: ina226::VBUSCT::_332us,
+    vshct: ina226::VSHCT::_332us,
+};
+
+/// Waits before each retry of a failed INA226 configuration at boot, e.g. for a sense board that
+/// powers up late. Without one the measurement loop runs without readings.
+const INA226_INIT_RETRIES: [Duration; 4] = [
+    Duration::from_millis(10),
+    Duration::from_millis(50),
+    Duration::from_millis(200),
+    Duration::from_millis(1000),
+];
+
+/// How often a lost INA226 is configured again to see whether it is back.
+const INA226_RECONNECT_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Length of each step of the short test ramp, over a conversion of both channels at the fast
+/// configuration.
+const SHORT_TEST_STEP: Duration = Duration::from_millis(1);
 
-bind_interrupts!(struct Irqs {
-    I2C1 => i2c::EventInterruptHandler<peripherals::I2C1>, i2c::ErrorInterruptHandler<peripherals::I2C1>;
-});
+/// Longest the splash screen waits for the first PD contract, and how often it looks.
+const NEGOTIATION_TIMEOUT: Duration = Duration::from_millis(2000);
+const NEGOTIATION_POLL: Duration = Duration::from_millis(100);
+
+/// How long the self-test results stay on the splash screen.
+const SPLASH_TIME: Duration = Duration::from_millis(1000);
+const SPLASH_TIME_FAILED: Duration = Duration::from_millis(3000);
+
+/// Longest the display task waits for a render command before it looks at the page, the
+/// diagnostics tables and the status bar anyway.
+const DISPLAY_TICK: Duration = Duration::from_millis(20);
+
+/// Interval of the console readings printed while the display is failed.
+const TELEMETRY_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Interval of the samples written to the SD card.
+#[cfg(feature = "sd-log")]
+const SD_LOG_INTERVAL: Duration = Duration::from_secs(1);
+
+#[cfg(feature = "wifi")]
+static WIFI_TX_BUF: StaticCell<[u8; 128]> = StaticCell::new();
+#[cfg(feature = "wifi")]
+static WIFI_RX_BUF: StaticCell<[u8; 128]> = StaticCell::new();
+
+#[cfg(feature = "modbus")]
+static MODBUS_TX_BUF: StaticCell<[u8; 64]> = StaticCell::new();
+#[cfg(feature = "modbus")]
+static MODBUS_RX_BUF: StaticCell<[u8; 64]> = StaticCell::new();
 
 // This marks the entrypoint of our application.
 
 #[embassy_executor::main]
 async fn main(spawner: Spawner) {
-    let p = embassy_stm32::init(Default::default());
+    stack::paint();
+    updater::apply_pending_update();
+
+    let p = bsp::init();
+    let reset = reset_cause::take();
 
     defmt::println!("Hello, world!");
 
-    let mut out_ctl_pin = Output::new(p.PA8, Level::Low, Speed::Low);
+    if reset.is_abnormal() {
+        warn!(target: Module::System, "reset by {}, output stays off", reset.as_str());
+    } else {
+        info!(target: Module::System, "reset by {}", reset.as_str());
+    }
+    console::println(format_args!("RESET {}", reset.as_str()));
+    #[cfg(feature = "sd-log")]
+    sd_card::record(Record::Reset(reset.as_str()));
+
+    clock::restore(Rtc::new(p.rtc, RtcConfig::default())).await;
+    reset_cause::record(reset).await;
+
+    let crashed = crash::report_previous().await;
+
+    *FLASH.lock().await = Some(Flash::new_blocking(p.flash));
+    calibration::load().await;
+    scheduler::load().await;
+    slew_settings::load().await;
+    fuse_settings::load().await;
+    stats_settings::load().await;
+    // Before the display comes up, so it starts in the stored rotation.
+    let set_up = setup_settings::load().await.is_some();
+
+    // The output FET is channel 1 of the backlight timer, see `pwm_timer.rs`; both start at zero
+    // duty.
+    #[cfg(not(feature = "relay-output"))]
+    let output_pin = Some(PwmPin::new_ch1(p.output, OutputType::PushPull));
+    #[cfg(feature = "relay-output")]
+    let output_pin = None;
+    let blk_pin = PwmPin::new_ch3(p.backlight, OutputType::PushPull);
+
+    let mut pwm = SimplePwm::new(
+        p.backlight_tim,
+        output_pin,
+        None,
+        Some(blk_pin),
+        None,
+        bsp::BACKLIGHT_PWM_FREQUENCY,
+        CountingMode::EdgeAlignedUp,
+    );
+    #[cfg(not(feature = "relay-output"))]
+    pwm.enable(TimerChannel::Ch1);
+    pwm.enable(TimerChannel::Ch3);
+    pwm_timer::init(pwm);
+
+    #[cfg(not(feature = "relay-output"))]
+    let mut output = OutputController::new(PwmFetDriver::new(PwmChannel(TimerChannel::Ch1)));
+    #[cfg(feature = "relay-output")]
+    let mut output = OutputController::new(RelayDriver::new(
+        Output::new(p.output, Level::Low, Speed::Low),
+        bsp::RELAY_TIMING,
+    ));
+
+    // Pulled low by the rig while it is safe to switch the output on; a broken wire reads open.
+    #[cfg(feature = "interlock")]
+    let interlock = Input::new(p.interlock, Pull::Up);
+    #[cfg(feature = "interlock")]
+    output.interlock(interlock.is_low());
+
+    // Follows the output driver; pulled down, so a broken sense wire reads as an output that
+    // does not come on.
+    #[cfg(feature = "output-sense")]
+    let output_sense = Input::new(p.output_sense, Pull::Down);
+
+    #[cfg(feature = "trigger")]
+    let mut trigger = Trigger::new(Output::new(p.trigger, Level::Low, Speed::Low));
+
+    // init console
+
+    let mut uart_config = usart::Config::default();
+    uart_config.baudrate = 115_200;
+    match Uart::new(
+        p.console_usart,
+        p.console_rx,
+        p.console_tx,
+        Irqs,
+        p.console_tx_dma,
+        p.console_rx_dma,
+        uart_config,
+    ) {
+        Ok(uart) => {
+            bsp::init_console_wakeup();
+
+            let (console_tx, console_rx) = uart.split();
+
+            spawner.spawn(console_tx_exec(console_tx)).ok();
+            spawner.spawn(console_rx_exec(console_rx)).ok();
+        }
+        Err(_) => error!(target: Module::Console, "console uart init error"),
+    }
 
     let mut config = spi::Config::default();
-    config.frequency = Hertz(16_000_000);
-    let spi = Spi::new_txonly(p.SPI1, p.PA5, p.PA7, p.DMA1_CH1, p.DMA1_CH2, config); // SCK is unused.
+    config.frequency = display_settings::load().await;
+    #[cfg(not(feature = "sd-log"))]
+    let spi = Spi::new_txonly(
+        p.display_spi,
+        p.display_sck,
+        p.display_mosi,
+        p.display_tx_dma,
+        p.display_rx_dma,
+        config,
+    ); // SCK is unused.
+       // The SD card shares the bus and reads back on MISO.
+    #[cfg(feature = "sd-log")]
+    let spi = Spi::new(
+        p.display_spi,
+        p.display_sck,
+        p.display_mosi,
+        p.display_miso,
+        p.display_tx_dma,
+        p.display_rx_dma,
+        config,
+    );
     let spi: Mutex<CriticalSectionRawMutex, _> = Mutex::new(spi);
-    let spi = SPI_BUS_MUTEX.init(spi);
+    let spi: &'static Mutex<CriticalSectionRawMutex, SpiBus> = SPI_BUS_MUTEX.init(spi);
 
     // init display
 
-    let cs_pin = Output::new(p.PA4, Level::High, Speed::High);
-    let dc_pin = Output::new(p.PA15, Level::Low, Speed::High);
-    let rst_pin = Output::new(p.PA12, Level::Low, Speed::High);
+    let cs_pin = Output::new(p.display_cs, Level::High, Speed::High);
+    let dc_pin = Output::new(p.display_dc, Level::Low, Speed::High);
+    let rst_pin = Output::new(p.display_rst, Level::Low, Speed::High);
 
     // let cs_pin = ST7789_CS_PIN.init(cs_pin);
     // let dc_pin = ST7789_DC_PIN.init(dc_pin);
     // let rst_pin = ST7789_RST_PIN.init(rst_pin);
 
-    let spi_dev = SpiDevice::new(spi, cs_pin);
+    let spi_dev = ChunkedSpi::new(SpiDevice::new(spi, cs_pin), spi_bus::DISPLAY_CHUNK);
 
     // let spi_dev = ST7789_SPI_DEV.init(spi_dev);
 
     let st7789: ST7789Display = ST7789::new(st7789::Config::default(), spi_dev, dc_pin, rst_pin);
-    let mut _display = Display::new(st7789);
+    let mut display = Display::new(st7789);
 
-    _display.init().await.unwrap();
+    // A failure is latched as a fault and retried by `Display::task`, the display is published
+    // either way.
+    let display_result = display.init().await;
 
-    let mut display = DISPLAY.lock().await;
-    *display = Some(_display);
-    drop(display);
+    DISPLAY.init(Mutex::new(display)).ok();
+    let display = DISPLAY.get().await;
 
-    // init backlight
+    spawner.spawn(backlight_exec(Backlight::new())).ok();
 
-    let blk_pin = PwmPin::new_ch3(p.PB6, OutputType::PushPull);
+    // The thermal and line monitor tasks take turns on the one ADC.
+    #[cfg(feature = "adc")]
+    let adc: &'static Mutex<CriticalSectionRawMutex, AnalogAdc> = {
+        let mut adc = Adc::new(p.adc);
+        // The temperature sensor needs over 5 µs on both families, and the slow sampling also
+        // suits the high-impedance dividers and pull-ups on the USB lines.
+        adc.set_sample_time(SampleTime::Cycles160_5);
 
-    let mut blk_tim = SimplePwm::new(
-        p.TIM1,
-        None,
-        None,
-        Some(blk_pin),
-        None,
-        khz(1),
-        embassy_stm32::timer::CountingMode::EdgeAlignedUp,
-    );
+        ADC_MUTEX.init(Mutex::new(adc))
+    };
 
-    blk_tim.enable(embassy_stm32::timer::Channel::Ch3);
-    blk_tim.set_duty(
-        embassy_stm32::timer::Channel::Ch3,
-        blk_tim.get_max_duty() / 2,
-    );
+    #[cfg(feature = "fan")]
+    {
+        let fan_pin = PwmPin::new_ch2(p.fan, OutputType::PushPull);
+
+        let fan_tim = SimplePwm::new(
+            p.fan_tim,
+            None,
+            Some(fan_pin),
+            None,
+            None,
+            bsp::FAN_PWM_FREQUENCY,
+            CountingMode::EdgeAlignedUp,
+        );
+
+        spawner
+            .spawn(thermal_exec(thermal::Thermal::new(adc, fan_tim)))
+            .ok();
+    }
+
+    #[cfg(any(feature = "data-lines", feature = "cc-lines", feature = "vbus-sense"))]
+    spawner
+        .spawn(line_monitor_exec(line_monitor::LineMonitor::new(
+            adc,
+            #[cfg(feature = "data-lines")]
+            p.data_plus,
+            #[cfg(feature = "data-lines")]
+            p.data_minus,
+            #[cfg(feature = "cc-lines")]
+            p.cc1,
+            #[cfg(feature = "cc-lines")]
+            p.cc2,
+            #[cfg(feature = "vbus-sense")]
+            p.vbus,
+        )))
+        .ok();
+
+    #[cfg(feature = "sd-log")]
+    {
+        let sd_cs = Output::new(p.sd_cs, Level::High, Speed::High);
+
+        spawner
+            .spawn(sd_log_exec(sd_card::SdLog::new(spi, sd_cs)))
+            .ok();
+    }
 
     let i2c = I2c::new(
-        p.I2C1,
-        p.PB8,
-        p.PB7,
+        p.sensor_i2c,
+        p.sensor_scl,
+        p.sensor_sda,
         Irqs,
-        p.DMA1_CH3,
-        p.DMA1_CH4,
+        p.sensor_tx_dma,
+        p.sensor_rx_dma,
         Hertz(100_000),
         Default::default(),
     );
@@ -125,79 +508,821 @@ async fn main(spawner: Spawner) {
     let i2c = Mutex::new(i2c);
     let i2c = HUSB238_I2C_MUTEX.init(i2c);
 
+    // self-test
+
+    let mut monitor = monitor_settings::load(Channel::A);
+    let found = selftest::find_power_monitor(&mut I2cDevice::new(i2c), monitor.address, None).await;
+    monitor.address = found.unwrap_or(monitor.address);
+
+    let results = SelfTest {
+        display: display_result.map_err(|_| ProbeError::NoResponse),
+        power_monitor: found.map(|_| ()),
+        pd_controller: selftest::probe_pd_controller(&mut I2cDevice::new(i2c)).await,
+    };
+
+    selftest::report(results).await;
+
+    display
+        .lock()
+        .await
+        .show_selftest(
+            &results
+                .checks()
+                .map(|(peripheral, result)| (peripheral, result.is_ok())),
+        )
+        .await;
+
+    // Keep the results up for a moment, longer if something is missing, and show the first PD
+    // contract coming in meanwhile.
+    let splash_until = Instant::now()
+        + if results.passed() {
+            SPLASH_TIME
+        } else {
+            SPLASH_TIME_FAILED
+        };
+
+    let i2c_dev = I2cDevice::new(i2c);
+    let mut husb238 = Husb238::new(i2c_dev);
+
+    // Whether the last contract read had a source on the other end.
+    let mut attached = negotiate(&mut husb238, display).await;
+
+    Timer::at(splash_until).await;
+
+    display.lock().await.update_layout().await;
+
+    // From here on the screen is only drawn by the display task.
+    spawner.spawn(display_exec(display)).ok();
+
     // init ina226
 
     let i2c_dev = I2cDevice::new(&i2c);
-    let mut ina226 = INA226::new(i2c_dev, DEFAULT_ADDRESS);
-    ina226
-        .set_configuration(&ina226::Config {
-            mode: ina226::MODE::ShuntBusVoltageContinuous,
-            avg: ina226::AVG::_128,
-            vbusct: ina226::VBUSCT::_8244us,
-            vshct: ina226::VSHCT::_8244us,
-        })
-        .await
-        .unwrap();
+    let mut ina226 = INA226::new(i2c_dev, monitor.address);
 
-    ina226.callibrate(0.01, 5.0).await.unwrap();
+    // Without a configured chip the current register reads zero, which no OCP would ever trip on,
+    // so the loop then runs without readings until the chip can be configured, see
+    // `power_monitor.rs`.
+    let mut link = MonitorLink::new(init_power_monitor(&mut ina226, &monitor).await);
+    if !link.is_up() {
+        error!(target: Module::Measure, "ina226 init error, no measurements");
+        console::println(format_args!("ERR no measurements, ina226 init failed"));
+        fault::report(Fault::PowerMonitor).await;
+    }
+    let mut reconnect_at = Instant::now() + INA226_RECONNECT_INTERVAL;
+
+    #[cfg(feature = "ina226-alert")]
+    let mut ina226_alert = Ina226Alert::new(I2cDevice::new(i2c), monitor);
+    #[cfg(feature = "ina226-alert")]
+    if link.is_up() {
+        arm_ina226_alert(&mut ina226_alert).await;
+    }
+
+    #[cfg(feature = "fixed-point")]
+    let mut ina226_regs = ina226_regs::Ina226Registers::new(
+        I2cDevice::new(i2c),
+        monitor.address,
+        monitor.max_milliamps,
+    );
+
+    #[cfg(feature = "dual-output")]
+    {
+        let mut monitor_b = monitor_settings::load(Channel::B);
+        let found = selftest::find_power_monitor(
+            &mut I2cDevice::new(i2c),
+            monitor_b.address,
+            Some(monitor.address),
+        )
+        .await;
+        monitor_b.address = found.unwrap_or(monitor_b.address);
+
+        spawner
+            .spawn(second_output_exec(second_output::SecondOutput::new(
+                i2c,
+                Output::new(p.output_b, Level::Low, Speed::Low),
+                monitor_b,
+            )))
+            .ok();
+    }
 
     // init buttons
 
-    let button_a = ExtiInput::new(Input::new(p.PC14, Pull::Up), p.EXTI14);
-    let button_b = ExtiInput::new(Input::new(p.PB0, Pull::Up), p.EXTI0);
+    let button_a = ExtiInput::new(Input::new(p.button_a, Pull::Up), p.button_a_exti);
+    let button_b = ExtiInput::new(Input::new(p.button_b, Pull::Up), p.button_b_exti);
+
+    // A unit that has not been set up starts on the wizard, see `setup.rs`.
+    if !set_up {
+        *SETUP_MUTEX.lock().await = Setup::new(monitor.shunt_micro_ohms);
+
+        let page = Page::Setup(SETUP_STEPS[0]);
+        *PAGE_MUTEX.lock().await = page;
+        PAGE_PUBSUB.immediate_publisher().publish_immediate(page);
+    }
+
+    #[cfg(feature = "ina226-alert")]
+    {
+        let alert = ExtiInput::new(Input::new(p.ina226_alert, Pull::Up), p.ina226_alert_exti);
+        spawner.spawn(ina226_alert_exec(alert)).ok();
+    }
 
     spawner.spawn(controller_exec()).ok();
     spawner.spawn(btns_exec(button_a, button_b)).ok();
+    spawner.spawn(scheduler_exec()).ok();
+    spawner.spawn(plotter_exec()).ok();
+    spawner.spawn(fuse_store_exec()).ok();
 
-    out_ctl_pin.set_high();
+    // STOP mode would halt the UARTs and I2C2 serving the fieldbus and WiFi bridge.
+    #[cfg(not(any(feature = "i2c-slave", feature = "modbus", feature = "wifi")))]
+    spawner.spawn(idle_exec()).ok();
 
-    let i2c_dev = I2cDevice::new(i2c);
-    let mut husb238 = Husb238::new(i2c_dev);
+    #[cfg(feature = "i2c-slave")]
+    {
+        let slave = i2c_slave::I2cSlave::new(
+            p.slave_i2c,
+            p.slave_scl,
+            p.slave_sda,
+            i2c_slave::I2C_SLAVE_ADDRESS,
+        );
+        spawner.spawn(i2c_slave_exec(slave)).ok();
+    }
 
+    #[cfg(feature = "modbus")]
     {
-        let mut available_volt_curr = AVAILABLE_VOLT_CURR_MUTEX.lock().await;
+        let mut modbus_config = usart::Config::default();
+        modbus_config.baudrate = modbus::MODBUS_BAUDRATE;
+        modbus_config.parity = usart::Parity::ParityEven;
+
+        match usart::BufferedUart::new(
+            p.modbus_usart,
+            bsp::ModbusIrqs,
+            p.modbus_rx,
+            p.modbus_tx,
+            MODBUS_TX_BUF.init([0; 64]),
+            MODBUS_RX_BUF.init([0; 64]),
+            modbus_config,
+        ) {
+            Ok(uart) => {
+                let de_pin = Output::new(p.modbus_de, Level::Low, Speed::Low);
 
-        *available_volt_curr = get_available_volt_curr(&mut husb238).await.unwrap();
+                spawner
+                    .spawn(modbus_exec(modbus::Modbus::new(uart, de_pin)))
+                    .ok();
+            }
+            Err(_) => error!(target: Module::Fieldbus, "modbus uart init error"),
+        }
     }
 
+    #[cfg(feature = "wifi")]
+    {
+        let mut wifi_config = usart::Config::default();
+        wifi_config.baudrate = 115_200;
+
+        match usart::BufferedUart::new(
+            p.wifi_usart,
+            bsp::WifiIrqs,
+            p.wifi_rx,
+            p.wifi_tx,
+            WIFI_TX_BUF.init([0; 128]),
+            WIFI_RX_BUF.init([0; 128]),
+            wifi_config,
+        ) {
+            Ok(uart) => {
+                spawner.spawn(wifi_exec(wifi::Wifi::new(uart))).ok();
+            }
+            Err(_) => error!(target: Module::Wifi, "wifi uart init error"),
+        }
+    }
+
+    // A fuse blown before the restart stays blown until `fuse reset`.
+    if *FUSE_BLOWN_MUTEX.lock().await {
+        output.blow_fuse();
+        *TRIPPED_MUTEX.lock().await = true;
+        render::send_channel(Channel::A, RenderCmd::Tripped(true)).await;
+    }
+
+    let output_mode = *OUTPUT_MODE_MUTEX.lock().await;
+    // In the momentary mode the output waits for a press, and after a watchdog or brown-out reset
+    // or a crash for one in any mode.
+    let enabled = output_mode == OutputMode::Latching
+        && output.is_interlock_closed()
+        && !reset.is_abnormal()
+        && !crashed
+        && !output.is_fuse_blown()
+        && (link.is_up() || *OCP_MUTEX.lock().await <= ZERO);
+
+    output.set_mode(output_mode);
+    output.set(enabled);
+    *OUTPUT_MUTEX.lock().await = enabled;
+
     let mut pdo_sub = PDO_PUBSUB.subscriber().unwrap();
+    let mut output_sub = OUTPUT_PUBSUB.subscriber().unwrap();
+    let mut profile_sub = POWER_PROFILE_PUBSUB.subscriber().unwrap();
+    let mut filter_sub = FILTER_PUBSUB.subscriber().unwrap();
+    let mut watts_source_sub = WATTS_SOURCE_PUBSUB.subscriber().unwrap();
+    let mut output_mode_sub = OUTPUT_MODE_PUBSUB.subscriber().unwrap();
+    let mut display_spi_sub = DISPLAY_SPI_PUBSUB.subscriber().unwrap();
+
+    let mut profile = *POWER_PROFILE_MUTEX.lock().await;
+    set_sensor_i2c(i2c, profile).await;
+    // When the readings go to the display next, see `PowerProfile`.
+    let mut ui_at = Instant::now();
+    // Set from a wake out of idle mode until the protection has run once, see `idle.rs`.
+    let mut panel_asleep = false;
+
+    let mut filters = ReadingFilters::new(*FILTER_MUTEX.lock().await);
+
+    let mut watts_source = *WATTS_SOURCE_MUTEX.lock().await;
+    let mut peak_watts = PeakHold::new();
+    let mut synced_watts = SyncedPower::new();
+    let mut slew = SlewMonitor::new();
+    let mut average = IntervalAverage::new();
+    let mut rms = RollingRms::new();
+    // What the INA226 was last told, see `conversion.rs`.
+    let mut conversion = *CONVERSION_MUTEX.lock().await;
+    let mut contract_use = UtilizationMonitor::new();
+    let mut contract_warning = false;
+    let mut output_stats =
+        OutputStatsTracker::new(*OUTPUT_STATS_MUTEX.lock().await, Instant::now());
+
+    // The last good value of each quantity in the raw and the filtered stream, see `measure.rs`.
+    let mut raw_power = PowerInfo::default();
+    let mut power = PowerInfo::default();
+    let mut status = StatusInfo::default();
+
+    let mut energy_at = Instant::now();
+    let mut telemetry_at = Instant::now();
+    #[cfg(feature = "telemetry")]
+    let mut samples = SampleBatch::new();
+    #[cfg(feature = "replay")]
+    let mut replay = Replay::new(include_str!(env!("PD_SINK_REPLAY")));
+    #[cfg(feature = "sd-log")]
+    let mut sd_log_at = Instant::now();
 
     let mut count = 0u8;
+    let mut pd_cache = PdCache::new();
+    let mut derate = OcpDerate::new();
+    #[cfg(feature = "dual-output")]
+    let mut derate_b = OcpDerate::new();
 
     loop {
-        let mut display = DISPLAY.lock().await;
+        heartbeat::beat(Task::Measure);
+
+        // The passes are paced by the I2C reads; without a power monitor nothing else lets the
+        // other tasks run.
+        yield_now().await;
+
+        if let Some(changed) = profile_sub.try_next_message_pure() {
+            info!("power profile: {:?}", changed);
+
+            set_display_spi(spi, changed).await;
+            set_sensor_i2c(i2c, changed).await;
 
-        if display.is_none() {
+            profile = changed;
+        }
+
+        if let Some(max) = display_spi_sub.try_next_message_pure() {
+            info!(target: Module::Display, "display spi max {} Hz", max.0);
+
+            set_display_spi(spi, profile).await;
+
+            // Start the panel over at the new clock, as after a failure.
+            render::send(RenderCmd::Init);
+        }
+
+        if let Some(filter) = filter_sub.try_next_message_pure() {
+            info!(target: Module::Measure, "filter: {:?}", filter);
+
+            filters = ReadingFilters::new(filter);
+        }
+
+        if let Some(source) = watts_source_sub.try_next_message_pure() {
+            info!(target: Module::Measure, "watts source: {:?}", source);
+
+            watts_source = source;
+            peak_watts.reset();
+            synced_watts.reset();
+            render::send(RenderCmd::WattsSource(source));
+        }
+
+        if let Some(mode) = output_mode_sub.try_next_message_pure() {
+            info!(target: Module::Output, "output mode: {:?}", mode);
+
+            output.set_mode(mode);
+
+            // Nothing holds the output on while the mode is being changed.
+            if mode == OutputMode::Momentary && output.is_enabled() {
+                output.set(false);
+                *OUTPUT_MUTEX.lock().await = false;
+                render::send_channel(Channel::A, RenderCmd::Output(false)).await;
+            }
+        }
+
+        if *POWER_STATE_MUTEX.lock().await == PowerState::Suspending {
+            // Directly, so the panel is off before the idle task may stop the clocks.
+            display.lock().await.sleep().await;
+
+            WAKE_SIGNAL.reset();
+            *POWER_STATE_MUTEX.lock().await = PowerState::Suspended;
+
+            WAKE_SIGNAL.wait().await;
+
+            // Woken by a load on the output perhaps, which the protection sees first.
+            panel_asleep = true;
+            energy_at = Instant::now();
+            filters.reset();
+            average.reset();
+            rms.reset();
             continue;
         }
-        let display = display.as_mut().unwrap();
 
-        display.task().await;
+        let loop_start = Instant::now();
 
-        match ina226.bus_voltage_millivolts().await {
-            Ok(val) => {
-                display.update_monitor_volts(val / 1000.0).await;
+        let offset_amps = CALIBRATION_MUTEX.lock().await.offset_amps();
+
+        if !link.is_up() && loop_start >= reconnect_at {
+            reconnect_at = loop_start + INA226_RECONNECT_INTERVAL;
+
+            if configure_power_monitor(&mut ina226, &monitor).await {
+                link.reconnected();
+                #[cfg(feature = "ina226-alert")]
+                arm_ina226_alert(&mut ina226_alert).await;
+
+                info!(target: Module::Measure, "ina226 back, measuring again");
+                console::println(format_args!("{} MONITOR BACK", clock::now().await));
+                filters.reset();
+                average.reset();
+                rms.reset();
             }
-            Err(_) => {
-                display.update_monitor_volts(99999.99999).await;
+        }
+
+        if link.is_up() {
+            follow_conversion(&mut ina226, &mut conversion).await;
+        }
+
+        // Read everything and check for an over-current before spending time on the screen.
+        let raw = if link.is_up() {
+            #[cfg(not(feature = "fixed-point"))]
+            let volts = ina226.bus_voltage_millivolts().await.map(|mv| mv / 1000.0);
+            #[cfg(feature = "fixed-point")]
+            let volts = ina226_regs.bus_millivolts().await;
+
+            #[cfg(not(feature = "fixed-point"))]
+            let amps = ina226.current_amps().await.map(|a| a.unwrap_or(0.0));
+            #[cfg(feature = "fixed-point")]
+            let amps = ina226_regs.current_milliamps().await;
+
+            #[cfg(not(feature = "fixed-point"))]
+            let watts = ina226.power_watts().await.map(|w| w.unwrap_or(0.0));
+            #[cfg(feature = "fixed-point")]
+            let watts = ina226_regs.power_milliwatts().await;
+
+            let volts = volts.ok();
+            Reading {
+                volts,
+                amps: amps.ok().map(|amps| amps - offset_amps),
+                // The offset is taken out at the bus voltage of this pass, if there is one.
+                watts: watts.ok().map(|watts| {
+                    volts.map_or(watts, |volts| watts - units::mul(volts, offset_amps))
+                }),
+            }
+        } else {
+            Reading::MISSING
+        };
+        // The recording stands in for whatever was read, see `replay.rs`.
+        #[cfg(feature = "replay")]
+        let raw = replay.reading(loop_start.as_millis());
+        let filtered = filters.update(&raw);
+
+        if link.update(raw.is_complete()) == Some(LinkEvent::Lost) {
+            warn!(target: Module::Measure, "ina226 lost, no measurements");
+            console::println(format_args!("{} MONITOR LOST", clock::now().await));
+            reconnect_at = loop_start + INA226_RECONNECT_INTERVAL;
+
+            // A chip that comes back may have lost its alert setup, it is armed again then.
+            #[cfg(feature = "ina226-alert")]
+            {
+                *INA226_ALERT_ARMED_MUTEX.lock().await = false;
             }
         }
 
-        match ina226.current_amps().await {
-            Ok(val) => {
-                display.update_monitor_amps(val.unwrap_or(0.0)).await;
+        let volts_ok = raw.volts.is_some();
+        let amps_ok = raw.amps.is_some();
+        let watts_ok = raw.watts.is_some();
+
+        raw.merge_into(&mut raw_power);
+        filtered.merge_into(&mut power);
+
+        // The filtered register is already in `power.watts`.
+        match (watts_source, raw.watts) {
+            (WattsSource::Register, _) => {}
+            (WattsSource::Product, _) => power.watts = units::mul(power.volts, power.amps),
+            (WattsSource::Synced, _) => match raw.volts.zip(raw.amps) {
+                Some((volts, amps)) => power.watts = synced_watts.update(loop_start, volts, amps),
+                None => synced_watts.reset(),
+            },
+            (WattsSource::Peak, Some(watts)) => power.watts = peak_watts.update(loop_start, watts),
+            (WattsSource::Peak, None) => {}
+        }
+
+        timing::record(Section::Read, loop_start.elapsed());
+
+        let interval = *AVERAGE_INTERVAL_MUTEX.lock().await;
+        if interval != average.interval() {
+            info!(target: Module::Measure, "average interval: {}", interval.as_str());
+
+            average.set_interval(interval);
+            *AVERAGE_MUTEX.lock().await = None;
+            render::send(RenderCmd::Average(None));
+        }
+
+        // Averages of the raw readings, so they do not depend on the filter.
+        if raw.is_complete() {
+            if let Some(mean) = average.update(loop_start, &raw_power) {
+                *AVERAGE_MUTEX.lock().await = Some(mean);
+                render::send(RenderCmd::Average(Some(mean)));
             }
-            Err(_) => {
-                display.update_monitor_amps(99999.99999).await;
+        }
+
+        if let Some(amps) = raw.amps {
+            let rms = rms.update(loop_start, amps);
+            *RMS_MUTEX.lock().await = rms;
+            render::send_channel(Channel::A, RenderCmd::Rms(rms)).await;
+        }
+
+        let ocp = *OCP_MUTEX.lock().await;
+        let uvp = *UVP_MUTEX.lock().await;
+        let ovp = *OVP_MUTEX.lock().await;
+        #[cfg(feature = "trigger")]
+        trigger.configure(*TRIGGER_MUTEX.lock().await);
+
+        // A relay opening at low current waits for it here, see `relay.rs`.
+        output.poll(raw.amps);
+
+        // Against the state commanded on the previous pass, before anything changes it.
+        #[cfg(feature = "output-sense")]
+        let stuck = output.sense(output_sense.is_high());
+        #[cfg(not(feature = "output-sense"))]
+        let stuck: Option<protection::SenseHealth> = None;
+        if let Some(health) = stuck {
+            warn!(target: Module::Output, "output driver {}", health.as_str());
+            fault::report(Fault::OutputDriver).await;
+        }
+        *OUTPUT_SENSE_MUTEX.lock().await = output.sense_report();
+
+        #[cfg(feature = "interlock")]
+        let opened = output.interlock(interlock.is_low());
+        #[cfg(not(feature = "interlock"))]
+        let opened = None;
+        // Rates only count while the output is on, from the second reading after it went on.
+        let slew_limits = *SLEW_LIMITS_MUTEX.lock().await;
+        let alarm = if output.is_enabled() && volts_ok && amps_ok {
+            slew.update(loop_start, raw_power.volts, raw_power.amps, &slew_limits)
+        } else {
+            slew.reset();
+            None
+        };
+
+        // Recovery from a voltage trip waits for VBUS ahead of the switch, see `protection.rs`.
+        #[cfg(feature = "vbus-sense")]
+        let source = *VBUS_MUTEX.lock().await;
+        #[cfg(not(feature = "vbus-sense"))]
+        let source = None;
+
+        // Once the interlock or a stuck driver turned the output off, the other checks find nothing
+        // to do. They only look at values read in this pass; a failed read is reported as a power
+        // monitor fault, and a lost power monitor turns off an output with an OCP set.
+        let protection = opened
+            .or_else(|| stuck.and_then(|_| output.trip(OutputError::DriverFault)))
+            .or_else(|| {
+                (!link.is_up() && ocp > ZERO)
+                    .then(|| output.trip(OutputError::NoMeasurement))
+                    .flatten()
+            })
+            .or_else(|| {
+                (volts_ok && amps_ok)
+                    .then(|| output.protect(&raw_power, source, ocp, &uvp, &ovp))
+                    .flatten()
+            })
+            .or_else(|| {
+                let alarm = alarm.filter(|_| slew_limits.trip)?;
+
+                output.trip(match alarm.kind {
+                    SlewKind::Volts => OutputError::VoltageSlew,
+                    SlewKind::Amps => OutputError::CurrentSlew,
+                })
+            });
+
+        timing::record(Section::Ocp, loop_start.elapsed());
+
+        if amps_ok && CAPTURE_MUTEX.lock().await.record(raw_power.amps) {
+            info!(target: Module::Measure, "capture held");
+
+            #[cfg(feature = "trigger")]
+            trigger.fire(TriggerEvent::Capture);
+        }
+
+        if !(volts_ok && amps_ok && watts_ok) {
+            fault::report(Fault::PowerMonitor).await;
+        }
+
+        if let Some(alarm) = alarm {
+            let rate = units::from_milli(alarm.rate_milli);
+
+            warn!(
+                target: Module::Measure,
+                "{} alarm: {} milli/s",
+                alarm.kind.as_str(),
+                alarm.rate_milli
+            );
+            console::println(format_args!(
+                "{} ALARM {} {}/s",
+                clock::now().await,
+                alarm.kind.as_str(),
+                units::fixed(rate, 3, 0)
+            ));
+
+            #[cfg(feature = "sd-log")]
+            sd_card::record(Record::Alarm(alarm.kind.as_str(), rate));
+        }
+
+        match protection {
+            Some(Protection::Tripped(err)) => {
+                warn!(target: Module::Output, "output tripped: {:?}", err);
+                console::println(format_args!("{} TRIP {}", clock::now().await, err.as_str()));
+                SESSION_MUTEX.lock().await.fault();
+
+                #[cfg(feature = "trigger")]
+                trigger.fire(TriggerEvent::Trip);
+                #[cfg(feature = "sd-log")]
+                sd_card::record(Record::Trip(err.as_str()));
+
+                *OUTPUT_MUTEX.lock().await = false;
+                render::send_channel(Channel::A, RenderCmd::Output(false)).await;
+
+                if output.is_tripped() {
+                    *TRIPPED_MUTEX.lock().await = true;
+                    render::send_channel(Channel::A, RenderCmd::Tripped(true)).await;
+                }
+                if output.is_fuse_blown() {
+                    fuse_settings::mark_blown(Channel::A).await;
+                }
+            }
+            Some(Protection::Recovered) => {
+                info!(target: Module::Output, "output recovered");
+                console::println(format_args!("{} RECOVER", clock::now().await));
+                #[cfg(feature = "sd-log")]
+                sd_card::record(Record::Recover);
+
+                *OUTPUT_MUTEX.lock().await = true;
+                render::send_channel(Channel::A, RenderCmd::Output(true)).await;
+            }
+            None => {}
+        }
+
+        if panel_asleep {
+            display.lock().await.wake().await;
+            panel_asleep = false;
+        }
+
+        #[cfg(feature = "trigger")]
+        trigger.follow_output(output.is_enabled());
+
+        // Paced by the power profile, everything above ran on this pass regardless.
+        let ui_due = loop_start >= ui_at;
+        if ui_due {
+            ui_at = loop_start + profile.refresh_interval();
+
+            // Dashes for a quantity that failed to read in this pass.
+            let volts = volts_ok.then_some(power.volts);
+            let amps = amps_ok.then_some(power.amps);
+            let watts = watts_ok.then_some(power.watts);
+            render::send_channel(Channel::A, RenderCmd::Volts(volts)).await;
+            render::send_channel(Channel::A, RenderCmd::Amps(amps)).await;
+            render::send_channel(Channel::A, RenderCmd::Watts(watts)).await;
+        }
+
+        // Against the contract of the previous pass; it is read back further down.
+        let utilization = Some(&power)
+            .filter(|_| amps_ok && watts_ok)
+            .and_then(|power| Utilization::of(power, status.target_volts, status.limit_amps));
+        let warning = contract_use.update(loop_start, utilization);
+        if warning != contract_warning {
+            match utilization.filter(|_| warning) {
+                Some(utilization) => {
+                    warn!(
+                        target: Module::Measure,
+                        "above {}% of the contract: {:?}", WARN_PERCENT, utilization
+                    );
+                    console::println(format_args!(
+                        "{} CONTRACT {}%",
+                        clock::now().await,
+                        utilization.peak()
+                    ));
+                }
+                None => info!(target: Module::Measure, "back within the contract"),
             }
+
+            contract_warning = warning;
+        }
+        if ui_due {
+            render::send(RenderCmd::Utilization(utilization, warning));
+        }
+
+        *POWER_INFO_MUTEX.lock().await = power;
+        *RAW_POWER_MUTEX.lock().await = raw_power;
+
+        let now = Instant::now();
+        HISTORY_MUTEX.lock().await.record(now, &power);
+
+        #[cfg(feature = "telemetry")]
+        if samples.push(now.as_millis(), &power) {
+            telemetry::send_samples(&samples);
+            samples.next();
+        }
+
+        // Without a panel the readings are streamed on the console instead.
+        if !*DISPLAY_AVAILABLE_MUTEX.lock().await && now >= telemetry_at {
+            console::println(format_args!(
+                "V={} A={} W={}",
+                units::fixed(power.volts, 3, 0),
+                units::fixed(power.amps, 3, 0),
+                units::fixed(power.watts, 3, 0)
+            ));
+            telemetry_at = now + TELEMETRY_INTERVAL;
         }
 
-        match ina226.power_watts().await {
-            Ok(val) => {
-                display.update_monitor_watts(val.unwrap_or(0.0)).await;
+        #[cfg(feature = "sd-log")]
+        if now >= sd_log_at {
+            sd_card::record(Record::Sample(power));
+            sd_log_at = now + SD_LOG_INTERVAL;
+        }
+
+        // Only what was read in this pass; a gap adds nothing, see `units.rs`.
+        if output.is_enabled() {
+            units::add_energy(&mut *ENERGY_MUTEX.lock().await, raw.watts, now - energy_at);
+        }
+        energy_at = now;
+        SESSION_MUTEX.lock().await.record(now, &raw, ocp);
+
+        output.set_fuse(*FUSE_LIMIT_MUTEX.lock().await);
+
+        // Cleared by `fuse reset` on the console.
+        if output.is_fuse_blown() && !*FUSE_BLOWN_MUTEX.lock().await {
+            info!(target: Module::Output, "soft fuse reset");
+            console::println(format_args!("{} FUSE RESET", clock::now().await));
+
+            output.reset_fuse();
+            *TRIPPED_MUTEX.lock().await = false;
+            render::send_channel(Channel::A, RenderCmd::Tripped(false)).await;
+        }
+
+        // Cleared by a long press of Up on the monitor page, which a blown fuse sets again.
+        if output.is_tripped() && !*TRIPPED_MUTEX.lock().await {
+            if output.reset_trip() {
+                info!(target: Module::Output, "over-current trip reset");
+                console::println(format_args!("{} TRIP RESET", clock::now().await));
+
+                render::send_channel(Channel::A, RenderCmd::Tripped(false)).await;
+            } else {
+                console::println(format_args!("ERR fuse blown, send fuse reset"));
+                *TRIPPED_MUTEX.lock().await = true;
             }
-            Err(_) => {
-                display.update_monitor_watts(99999.99999).await;
+        }
+
+        // Requests for the second channel are taken by its own task.
+        let output_req = output_sub
+            .try_next_message_pure()
+            .filter(|req| req.channel == Channel::A);
+        if let Some(req) = output_req {
+            let selected = *PDO_MUTEX.lock().await;
+            let mut checked = output.check(req.enabled, req.source, selected, &status);
+
+            // An OCP is only a promise with readings behind it.
+            if checked.is_ok() && req.enabled && !link.is_up() && ocp > ZERO {
+                checked = Err(OutputError::NoMeasurement);
+            }
+
+            // Only an output going on is tested; the main loop waits for the ramp, so the
+            // over-current check cannot miss anything. Without readings there is nothing to
+            // check the ramp against.
+            if checked.is_ok()
+                && link.is_up()
+                && req.enabled
+                && !output.is_enabled()
+                && output.can_ramp()
+                && *SHORT_TEST_MUTEX.lock().await
+            {
+                checked = match ina226.set_configuration(&SHORT_TEST_INA226_CONFIG).await {
+                    Ok(_) => Ok(()),
+                    Err(_) => Err(OutputError::ShortTestFailed),
+                };
+
+                // Up the duty while each step holds, see `protection.rs`.
+                for duty in SHORT_TEST_RAMP {
+                    if checked.is_err() {
+                        break;
+                    }
+
+                    output.ramp(duty);
+                    Timer::after(SHORT_TEST_STEP).await;
+
+                    #[cfg(not(feature = "fixed-point"))]
+                    let volts = ina226
+                        .bus_voltage_millivolts()
+                        .await
+                        .map(|mv| units::from_f64(mv / 1000.0));
+                    #[cfg(feature = "fixed-point")]
+                    let volts = ina226_regs.bus_millivolts().await;
+
+                    #[cfg(not(feature = "fixed-point"))]
+                    let amps = ina226
+                        .current_amps()
+                        .await
+                        .map(|a| units::from_f64(a.unwrap_or(0.0)));
+                    #[cfg(feature = "fixed-point")]
+                    let amps = ina226_regs.current_milliamps().await;
+
+                    checked = match (volts, amps) {
+                        (Ok(volts), Ok(amps)) => {
+                            let amps = amps - offset_amps;
+                            info!(
+                                target: Module::Output,
+                                "short test at {}%: {} mV of {} mV, {} mA",
+                                duty,
+                                units::milli(volts),
+                                units::milli(status.target_volts),
+                                units::milli(amps)
+                            );
+
+                            match protection::ramp_fault(
+                                duty,
+                                volts,
+                                amps,
+                                status.target_volts,
+                                ocp,
+                            ) {
+                                Some(RampFault::Collapsed) => Err(OutputError::ShortCircuit),
+                                Some(RampFault::OverCurrent) => Err(OutputError::OverCurrent),
+                                None => Ok(()),
+                            }
+                        }
+                        _ => Err(OutputError::ShortTestFailed),
+                    };
+                }
+
+                // Off until the request below, which switches a passed output fully on.
+                output.ramp(0);
+
+                let restored = ina226.set_configuration(&ina226_config(conversion)).await;
+                if restored.is_err() {
+                    fault::report(Fault::PowerMonitor).await;
+                }
+
+                if let Err(err) = checked {
+                    console::println(format_args!("{} TRIP {}", clock::now().await, err.as_str()));
+
+                    #[cfg(feature = "trigger")]
+                    trigger.fire(TriggerEvent::Trip);
+                    #[cfg(feature = "sd-log")]
+                    sd_card::record(Record::Trip(err.as_str()));
+                }
+            }
+
+            match checked.and_then(|_| output.request(req.enabled, req.source, selected, &status)) {
+                Ok(_) => {
+                    info!(target: Module::Output, "output {} by {:?}", req.enabled, req.source);
+
+                    *OUTPUT_MUTEX.lock().await = req.enabled;
+                    render::send_channel(Channel::A, RenderCmd::Output(req.enabled)).await;
+                    #[cfg(feature = "sd-log")]
+                    sd_card::record(Record::Output(req.enabled));
+
+                    if req.source == ControlSource::Remote {
+                        console::println(format_args!(
+                            "OK out {}",
+                            if req.enabled { "on" } else { "off" }
+                        ));
+                    }
+                }
+                Err(err) => {
+                    warn!(target: Module::Output, "output request refused: {:?}", err);
+
+                    if req.source == ControlSource::Remote {
+                        console::println(format_args!("ERR {}", err.as_str()));
+                    }
+                }
+            }
+
+            #[cfg(feature = "trigger")]
+            trigger.follow_output(output.is_enabled());
+        }
+
+        let now = Instant::now();
+        output_stats.update(now, output.is_enabled());
+        *OUTPUT_STATS_MUTEX.lock().await = output_stats.stats();
+
+        if let Some(stats) = output_stats.due(now) {
+            if let Err(err) = stats_settings::store(stats).await {
+                warn!(target: Module::Output, "output stats not saved: {:?}", err);
             }
         }
 
@@ -206,23 +1331,49 @@ async fn main(spawner: Spawner) {
         if changed_pdo.is_none() {
             count += 1;
             if count < 10 {
+                timing::record(Section::Loop, loop_start.elapsed());
                 continue;
             }
-        } else {
-            match husb238.set_src_pdo(changed_pdo.unwrap()).await {
+        }
+
+        let pd_start = Instant::now();
+
+        if let Some(req) = changed_pdo {
+            let pdo = req.pdo;
+
+            if output.is_enabled() {
+                info!(target: Module::Pd, "disable output before renegotiating");
+
+                output.set(false);
+                *OUTPUT_MUTEX.lock().await = false;
+                render::send_channel(Channel::A, RenderCmd::Output(false)).await;
+
+                #[cfg(feature = "trigger")]
+                trigger.follow_output(false);
+            }
+
+            // The second channel's task sees the new PDO and turns it off within a tick.
+            #[cfg(feature = "dual-output")]
+            if *OUTPUT_B_MUTEX.lock().await {
+                Timer::after(second_output::TICK * 2).await;
+            }
+
+            match husb238.set_src_pdo(pdo).await {
                 Ok(_) => {
                     match husb238.go_command(Command::Request).await {
                         Ok(_) => {
                             count = 0;
-                        },
+                        }
                         Err(_) => {
-                            defmt::error!("go command error");
+                            error!(target: Module::Pd, "go command error");
+                            fault::report(Fault::PdController).await;
                         }
                     }
-                    defmt::info!("set src_pdo: {:?}", changed_pdo.unwrap());
-                },
+                    info!(target: Module::Pd, "set src_pdo: {:?} by {:?}", pdo, req.source);
+                }
                 Err(_) => {
-                    defmt::error!("set src_pdo error");
+                    error!(target: Module::Pd, "set src_pdo error");
+                    fault::report(Fault::PdController).await;
                 }
             }
         }
@@ -231,22 +1382,138 @@ async fn main(spawner: Spawner) {
 
         match husb238.get_actual_voltage_and_current().await {
             Ok((volts, amps)) => {
-                display.update_target_volts(volts.unwrap_or(0.0)).await;
-                display.update_limit_amps(amps).await;
+                // A source attached again may offer other PDOs than the one before.
+                if volts.is_some() && !attached {
+                    refresh_available_pdos(&mut husb238).await;
+                }
+                attached = volts.is_some();
+
+                status.target_volts = units::from_f64(volts.unwrap_or(0.0));
+                let contract_amps = units::from_f64(amps);
+
+                // A new contract; the user can raise the OCP past it again on the OCP page.
+                if contract_amps != status.limit_amps {
+                    if let Some(ocp) = follow_contract(&mut derate, Channel::A, contract_amps).await
+                    {
+                        OCP_PUBSUB.immediate_publisher().publish_immediate(ocp);
+                    }
+
+                    // Both channels share the contract.
+                    #[cfg(feature = "dual-output")]
+                    follow_contract(&mut derate_b, Channel::B, contract_amps).await;
+                }
+
+                status.limit_amps = contract_amps;
+                pd_cache.target_volts.publish(status.target_volts, |volts| {
+                    render::send(RenderCmd::TargetVolts(volts))
+                });
+                pd_cache.limit_amps.publish(status.limit_amps, |amps| {
+                    render::send(RenderCmd::LimitAmps(amps))
+                });
             }
             Err(_) => {
-                defmt::error!("get actual voltage and current error");
+                error!(target: Module::Pd, "get actual voltage and current error");
+                fault::report(Fault::PdController).await;
             }
         }
 
+        pd_cache
+            .selected_pdo
+            .publish(*PDO_MUTEX.lock().await, |pdo| {
+                render::send(RenderCmd::SelectedPdo(pdo))
+            });
+
+        timing::record(Section::Pd, pd_start.elapsed());
+
+        status.output = output.is_enabled();
+        *STATUS_INFO_MUTEX.lock().await = status;
+
+        let remote = *REMOTE_MUTEX.lock().await;
+        let faults = *FAULTS_MUTEX.lock().await;
+
+        *SYSTEM_STATUS_MUTEX.lock().await = SystemStatus {
+            output: status.output,
+            tripped: output.is_tripped(),
+            target_volts: status.target_volts,
+            limit_amps: status.limit_amps,
+            locked: output.is_held(),
+            remote,
+            alarm: !faults.is_empty(),
+            fuse_blown: output.is_fuse_blown(),
+            derated: derate.is_derated(*OCP_MUTEX.lock().await),
+            temperature: FAN_STATUS_MUTEX.lock().await.map(|fan| fan.celsius),
+            #[cfg(feature = "interlock")]
+            interlock: Some(output.is_interlock_closed()),
+            ..SystemStatus::default()
+        };
+
+        // Also repairs an output change whose command fell off a full render queue.
+        render::send_channel(Channel::A, RenderCmd::Output(status.output)).await;
+        render::send_channel(Channel::A, RenderCmd::Tripped(output.is_tripped())).await;
+        render::send(RenderCmd::Remote(remote));
+        render::send(RenderCmd::Wifi(*WIFI_STATE_MUTEX.lock().await));
+        render::send(RenderCmd::Schedule(*NEXT_ACTION_MUTEX.lock().await));
+
+        timing::record(Section::Loop, loop_start.elapsed());
+
         // Timer::after(Duration::from_millis(1000)).await;
     }
 }
 
+/// Moves the OCP of `channel` with a new contract of `contract_amps`, see [`OcpDerate`]. Returns
+/// the new threshold when it changed.
+async fn follow_contract(
+    derate: &mut OcpDerate,
+    channel: Channel,
+    contract_amps: Value,
+) -> Option<Value> {
+    let mut ocp = ocp_mutex(channel).lock().await;
+    let event = derate.update(*ocp, contract_amps)?;
+
+    match event {
+        DerateEvent::Capped(capped) => info!(
+            target: Module::Output,
+            "{} ocp capped to contract {} mA",
+            channel.as_str(),
+            units::milli(capped)
+        ),
+        DerateEvent::Derated(derated) => {
+            warn!(
+                target: Module::Output,
+                "{} ocp {} mA derated to contract {} mA",
+                channel.as_str(),
+                units::milli(*ocp),
+                units::milli(derated)
+            );
+            console::println(format_args!(
+                "{} DERATE {} {} mA",
+                clock::now().await,
+                channel.as_str(),
+                units::milli(derated)
+            ));
+        }
+        DerateEvent::Restored(restored) => {
+            info!(
+                target: Module::Output,
+                "{} ocp restored to {} mA",
+                channel.as_str(),
+                units::milli(restored)
+            );
+            console::println(format_args!(
+                "{} RESTORE {} {} mA",
+                clock::now().await,
+                channel.as_str(),
+                units::milli(restored)
+            ));
+        }
+    }
+
+    *ocp = event.ocp();
+    Some(*ocp)
+}
+
 async fn get_available_volt_curr<'a>(
-    husb238: &mut Husb238<
-        I2cDevice<'a, CriticalSectionRawMutex, I2c<'static, I2C1, DMA1_CH3, DMA1_CH4>>,
-    >,
+    husb238: &mut Husb238<I2cDevice<'a, CriticalSectionRawMutex, SensorI2cBus>>,
 ) -> Result<AvailableVoltCurr, I2cDeviceError<i2c::Error>> {
     Ok(AvailableVoltCurr {
         _5v: husb238.get_5v_status().await?,
@@ -258,12 +1525,144 @@ async fn get_available_volt_curr<'a>(
     })
 }
 
+/// Waits up to [`NEGOTIATION_TIMEOUT`] for the first contract with a spinner on the splash screen,
+/// then reads the source's PDOs and shows the contract. Returns whether a source answered.
+async fn negotiate<'a>(
+    husb238: &mut Husb238<I2cDevice<'a, CriticalSectionRawMutex, SensorI2cBus>>,
+    display: &Mutex<
+        CriticalSectionRawMutex,
+        Display<'static, ST7789SpiDev, ST7789DCPin, ST7789RstPin>,
+    >,
+) -> bool {
+    let deadline = Instant::now() + NEGOTIATION_TIMEOUT;
+    let mut frame = 0u8;
+
+    let contract = loop {
+        display
+            .lock()
+            .await
+            .show_negotiation(Negotiation::Pending(frame))
+            .await;
+
+        match husb238.get_actual_voltage_and_current().await {
+            Ok((Some(volts), amps)) => break Some((volts, amps)),
+            Ok((None, _)) if Instant::now() < deadline => {}
+            Ok((None, _)) => {
+                warn!(
+                    target: Module::Pd,
+                    "no contract after {} ms",
+                    NEGOTIATION_TIMEOUT.as_millis()
+                );
+                break None;
+            }
+            Err(_) => {
+                error!(target: Module::Pd, "get actual voltage and current error");
+                fault::report(Fault::PdController).await;
+                break None;
+            }
+        }
+
+        frame = frame.wrapping_add(1);
+        Timer::after(NEGOTIATION_POLL).await;
+    };
+
+    let Some((volts, amps)) = contract else {
+        display
+            .lock()
+            .await
+            .show_negotiation(Negotiation::Failed)
+            .await;
+        return false;
+    };
+
+    match get_available_volt_curr(husb238).await {
+        Ok(volt_curr) => *AVAILABLE_VOLT_CURR_MUTEX.lock().await = volt_curr,
+        Err(_) => {
+            error!(target: Module::Pd, "get available voltages error");
+            fault::report(Fault::PdController).await;
+        }
+    }
+
+    let (volts, amps) = (units::from_f64(volts), units::from_f64(amps));
+    info!(
+        target: Module::Pd,
+        "contract {} mV {} mA",
+        units::milli(volts),
+        units::milli(amps)
+    );
+    display
+        .lock()
+        .await
+        .show_negotiation(Negotiation::Done(volts, amps))
+        .await;
+
+    true
+}
+
+/// Reads the source's PDOs again and, when the selected one is no longer among them, selects the
+/// nearest one below it that is.
+async fn refresh_available_pdos<'a>(
+    husb238: &mut Husb238<I2cDevice<'a, CriticalSectionRawMutex, SensorI2cBus>>,
+) {
+    let Ok(volt_curr) = get_available_volt_curr(husb238).await else {
+        error!(target: Module::Pd, "get available voltages error");
+        fault::report(Fault::PdController).await;
+        return;
+    };
+    *AVAILABLE_VOLT_CURR_MUTEX.lock().await = volt_curr;
+
+    let available = volt_curr.voltages();
+    let selected = *PDO_MUTEX.lock().await;
+    if available.contains(&selected) {
+        return;
+    }
+
+    let fallback = nearest_pdo(selected, &available);
+    warn!(
+        target: Module::Pd,
+        "{:?} no longer offered, falling back to {:?}", selected, fallback
+    );
+    console::println(format_args!(
+        "{} PDO {} mV gone, using {} mV",
+        clock::now().await,
+        units::milli(pdo_volts(selected)),
+        units::milli(pdo_volts(fallback))
+    ));
+
+    select_pdo(PdRequest {
+        pdo: fallback,
+        source: ControlSource::Local,
+    })
+    .await;
+}
+
+/// Counts a rise of the load over `WAKE_LOAD` as activity, see `ina226_alert.rs`.
+#[cfg(feature = "ina226-alert")]
 #[embassy_executor::task]
-async fn btns_exec(mut btn_a: ExtiInput<'static, PC14>, mut btn_b: ExtiInput<'static, PB0>) {
+async fn ina226_alert_exec(mut alert: ExtiInput<'static, bsp::Ina226AlertPin>) {
+    let activity_pub = ACTIVITY_PUBSUB.immediate_publisher();
+
+    loop {
+        alert.wait_for_falling_edge().await;
+
+        activity_pub.publish_immediate(());
+    }
+}
+
+#[embassy_executor::task]
+async fn btns_exec(
+    mut btn_a: ExtiInput<'static, bsp::ButtonAPin>,
+    mut btn_b: ExtiInput<'static, bsp::ButtonBPin>,
+) {
     let mut button_a = Button::new(&BTN_A_STATE_CHANNEL);
     let mut button_b = Button::new(&BTN_B_STATE_CHANNEL);
+    let mut trace_a = ButtonTrace::new('A');
+    let mut trace_b = ButtonTrace::new('B');
+    let activity_pub = ACTIVITY_PUBSUB.immediate_publisher();
 
     loop {
+        heartbeat::beat(Task::Buttons);
+
         let btn_a_change = btn_a.wait_for_any_edge();
 
         let btn_b_change = btn_b.wait_for_any_edge();
@@ -274,30 +1673,317 @@ async fn btns_exec(mut btn_a: ExtiInput<'static, PC14>, mut btn_b: ExtiInput<'st
 
         match futures.await {
             Either3::First(_) => {
-                if btn_a.is_high() {
-                    button_a.on_release().await;
+                activity_pub.publish_immediate(());
+
+                let state = if btn_a.is_high() {
+                    button_a.on_release().await
                 } else {
-                    button_a.on_press().await;
-                }
+                    button_a.on_press().await
+                };
+                trace_a.record(state).await;
             }
             Either3::Second(_) => {
-                if btn_b.is_high() {
-                    button_b.on_release().await;
+                activity_pub.publish_immediate(());
+
+                let state = if btn_b.is_high() {
+                    button_b.on_release().await
                 } else {
-                    button_b.on_press().await;
-                }
+                    button_b.on_press().await
+                };
+                trace_b.record(state).await;
             }
             Either3::Third(_) => {
-                button_a.update().await;
-                button_b.update().await;
+                if let Some(state) = button_a.update().await {
+                    trace_a.record(state).await;
+                }
+                if let Some(state) = button_b.update().await {
+                    trace_b.record(state).await;
+                }
             }
         };
     }
 }
 
+#[embassy_executor::task]
+async fn fuse_store_exec() {
+    fuse_settings::write_blown().await;
+}
+
+#[cfg(not(any(feature = "i2c-slave", feature = "modbus", feature = "wifi")))]
+#[embassy_executor::task]
+async fn idle_exec() {
+    let mut idle = idle::Idle::new();
+
+    idle.task().await;
+}
+
+#[embassy_executor::task]
+async fn backlight_exec(mut backlight: Backlight<'static>) {
+    backlight.task().await;
+}
+
+/// Clocks the sensor bus for `profile`.
+async fn set_sensor_i2c(i2c: &Mutex<CriticalSectionRawMutex, SensorI2cBus>, profile: PowerProfile) {
+    if i2c
+        .lock()
+        .await
+        .set_config(&profile.i2c_frequency())
+        .is_err()
+    {
+        error!(target: Module::Measure, "i2c reconfiguration error");
+    }
+}
+
+/// Clocks the display bus for `profile`, but no faster than `DISPLAY_SPI_MAX_MUTEX`.
+async fn set_display_spi(spi: &Mutex<CriticalSectionRawMutex, SpiBus>, profile: PowerProfile) {
+    let max = *DISPLAY_SPI_MAX_MUTEX.lock().await;
+
+    let mut config = spi::Config::default();
+    config.frequency = Hertz(profile.spi_frequency().0.min(max.0));
+
+    if spi.lock().await.set_config(&config).is_err() {
+        error!(target: Module::Display, "spi reconfiguration error");
+    }
+}
+
+#[cfg(feature = "fan")]
+#[embassy_executor::task]
+async fn thermal_exec(mut thermal: thermal::Thermal) {
+    thermal.task().await;
+}
+
+#[cfg(any(feature = "data-lines", feature = "cc-lines", feature = "vbus-sense"))]
+#[embassy_executor::task]
+async fn line_monitor_exec(mut line_monitor: line_monitor::LineMonitor) {
+    line_monitor.task().await;
+}
+
+#[cfg(feature = "sd-log")]
+#[embassy_executor::task]
+async fn sd_log_exec(mut sd_log: sd_card::SdLog) {
+    sd_log.task().await;
+}
+
+#[embassy_executor::task]
+async fn console_rx_exec(rx: ConsoleRx) {
+    let mut console = Console::new(rx);
+
+    console.task().await;
+}
+
+#[embassy_executor::task]
+async fn console_tx_exec(mut tx: ConsoleTx) {
+    loop {
+        let line = CONSOLE_TX_CHANNEL.receive().await;
+
+        if tx.write(line.as_bytes()).await.is_err() {
+            error!(target: Module::Console, "console write error");
+        }
+    }
+}
+
+#[cfg(feature = "i2c-slave")]
+#[embassy_executor::task]
+async fn i2c_slave_exec(mut slave: i2c_slave::I2cSlave) {
+    slave.task().await;
+}
+
+#[cfg(feature = "modbus")]
+#[embassy_executor::task]
+async fn modbus_exec(mut modbus: modbus::Modbus) {
+    modbus.task().await;
+}
+
+#[cfg(feature = "wifi")]
+#[embassy_executor::task]
+async fn wifi_exec(mut wifi: wifi::Wifi) {
+    wifi.task().await;
+}
+
+#[cfg(feature = "dual-output")]
+#[embassy_executor::task]
+async fn second_output_exec(mut second_output: second_output::SecondOutput) {
+    second_output.task().await;
+}
+
+#[embassy_executor::task]
+async fn scheduler_exec() {
+    scheduler::task().await;
+}
+
+#[embassy_executor::task]
+async fn plotter_exec() {
+    plotter::task().await;
+}
+
+/// Draws what the other tasks queue on the render channel, see `render.rs`, and runs the display's
+/// own housekeeping at least every `DISPLAY_TICK`.
+#[embassy_executor::task]
+async fn display_exec(
+    display: &'static Mutex<
+        CriticalSectionRawMutex,
+        Display<'static, ST7789SpiDev, ST7789DCPin, ST7789RstPin>,
+    >,
+) {
+    let mut budget = RenderBudget::new();
+
+    loop {
+        let mut pending = Pending::new();
+
+        if let Either::First(cmd) =
+            select(RENDER_CHANNEL.receive(), Timer::after(DISPLAY_TICK)).await
+        {
+            pending.push(cmd);
+        }
+
+        // Everything queued meanwhile goes into the same drawing.
+        while let Ok(cmd) = RENDER_CHANNEL.try_receive() {
+            pending.push(cmd);
+        }
+
+        let display_start = Instant::now();
+        budget.defer(display_start, &mut pending);
+        let mut display = display.lock().await;
+
+        display.task().await;
+        if !pending.is_empty() {
+            display.apply(pending).await;
+
+            if let Some(degraded) = budget.record(display_start.elapsed()) {
+                match degraded {
+                    true => warn!(target: Module::Display, "drawing over budget, slowing down"),
+                    false => info!(target: Module::Display, "drawing within budget again"),
+                }
+                *DISPLAY_DEGRADED_MUTEX.lock().await = degraded;
+            }
+        }
+        display.update_faults(*FAULTS_MUTEX.lock().await).await;
+
+        *DISPLAY_AVAILABLE_MUTEX.lock().await = display.is_available();
+
+        drop(display);
+        timing::record(Section::Display, display_start.elapsed());
+    }
+}
+
 #[embassy_executor::task]
 async fn controller_exec() {
     let mut controller = Controller::new();
 
     controller.task().await;
 }
+
+/// Continuous conversions of the shunt and the bus voltage, as `conversion` says.
+fn ina226_config(conversion: Conversion) -> ina226::Config {
+    let time = conversion.time;
+
+    ina226::Config {
+        mode: ina226::MODE::ShuntBusVoltageContinuous,
+        avg: match conversion.averaging {
+            Averaging::X1 => ina226::AVG::_1,
+            Averaging::X4 => ina226::AVG::_4,
+            Averaging::X16 => ina226::AVG::_16,
+            Averaging::X64 => ina226::AVG::_64,
+            Averaging::X128 => ina226::AVG::_128,
+            Averaging::X256 => ina226::AVG::_256,
+            Averaging::X512 => ina226::AVG::_512,
+            Averaging::X1024 => ina226::AVG::_1024,
+        },
+        vbusct: match time {
+            ConversionTime::Us140 => ina226::VBUSCT::_140us,
+            ConversionTime::Us204 => ina226::VBUSCT::_204us,
+            ConversionTime::Us332 => ina226::VBUSCT::_332us,
+            ConversionTime::Us588 => ina226::VBUSCT::_588us,
+            ConversionTime::Us1100 => ina226::VBUSCT::_1100us,
+            ConversionTime::Us2116 => ina226::VBUSCT::_2116us,
+            ConversionTime::Us4156 => ina226::VBUSCT::_4156us,
+            ConversionTime::Us8244 => ina226::VBUSCT::_8244us,
+        },
+        vshct: match time {
+            ConversionTime::Us140 => ina226::VSHCT::_140us,
+            ConversionTime::Us204 => ina226::VSHCT::_204us,
+            ConversionTime::Us332 => ina226::VSHCT::_332us,
+            ConversionTime::Us588 => ina226::VSHCT::_588us,
+            ConversionTime::Us1100 => ina226::VSHCT::_1100us,
+            ConversionTime::Us2116 => ina226::VSHCT::_2116us,
+            ConversionTime::Us4156 => ina226::VSHCT::_4156us,
+            ConversionTime::Us8244 => ina226::VSHCT::_8244us,
+        },
+    }
+}
+
+/// Gives the INA226 the conversion of `CONVERSION_MUTEX` when it differs from `applied`, the one
+/// it was last given. A chip that does not take it is lost soon and gets it when configured again.
+async fn follow_conversion<I2C: embedded_hal_async::i2c::I2c>(
+    ina226: &mut INA226<I2C>,
+    applied: &mut Conversion,
+) {
+    let wanted = *CONVERSION_MUTEX.lock().await;
+    if wanted == *applied {
+        return;
+    }
+
+    info!(target: Module::Measure, "ina226 conversion: {:?}", wanted);
+    if ina226
+        .set_configuration(&ina226_config(wanted))
+        .await
+        .is_err()
+    {
+        warn!(target: Module::Measure, "ina226 conversion not taken");
+    }
+
+    *applied = wanted;
+}
+
+/// Configures and calibrates the INA226 once, with the conversion of `CONVERSION_MUTEX`. Returns
+/// whether it took.
+async fn configure_power_monitor<I2C: embedded_hal_async::i2c::I2c>(
+    ina226: &mut INA226<I2C>,
+    monitor: &PowerMonitorConfig,
+) -> bool {
+    let conversion = *CONVERSION_MUTEX.lock().await;
+    let configured = ina226.set_configuration(&ina226_config(conversion)).await;
+    let calibrated = ina226
+        .callibrate(monitor.shunt_ohms(), monitor.max_amps())
+        .await;
+
+    configured.is_ok() && calibrated.is_ok()
+}
+
+/// Arms the INA226 ALERT line, see `ina226_alert.rs`. Without it an idle unit would not wake on
+/// the load, so a failure keeps it out of idle with the output on.
+#[cfg(feature = "ina226-alert")]
+async fn arm_ina226_alert<I2C: embedded_hal_async::i2c::I2c>(alert: &mut Ina226Alert<I2C>) {
+    let armed = alert.arm().await.is_ok();
+    if !armed {
+        error!(target: Module::Measure, "ina226 alert setup error");
+    }
+
+    *INA226_ALERT_ARMED_MUTEX.lock().await = armed;
+}
+
+/// [`configure_power_monitor`], retried after each of [`INA226_INIT_RETRIES`] while it fails.
+async fn init_power_monitor<I2C: embedded_hal_async::i2c::I2c>(
+    ina226: &mut INA226<I2C>,
+    monitor: &PowerMonitorConfig,
+) -> bool {
+    let mut retries = INA226_INIT_RETRIES.iter();
+
+    loop {
+        if configure_power_monitor(ina226, monitor).await {
+            return true;
+        }
+
+        let Some(delay) = retries.next() else {
+            return false;
+        };
+
+        warn!(
+            target: Module::Measure,
+            "ina226 at {:#04x} init failed, retry in {} ms",
+            monitor.address,
+            delay.as_millis()
+        );
+        Timer::after(*delay).await;
+    }
+}
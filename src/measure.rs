@@ -0,0 +1,81 @@
+//! The raw and filtered streams of the measurement loop.
+//!
+//! Every pass reads the INA226 once into a raw [`Reading`]. Protection, the slew-rate alarms, the
+//! averages, the current capture and the energy counter work on that stream, so neither the filter
+//! nor the watts source can delay a trip. [`ReadingFilters`] turn it into the filtered stream that
+//! the screen shows and the other tasks read. A quantity that failed to read is missing from both
+//! for that pass; it does not repeat the previous value.
+
+use crate::{
+    filter::{AnyFilter, Filter, FilterKind},
+    types::PowerInfo,
+    units::Value,
+};
+
+/// One pass of the measurement loop, `None` for a quantity that failed to read.
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) struct Reading {
+    pub volts: Option<Value>,
+    pub amps: Option<Value>,
+    pub watts: Option<Value>,
+}
+
+impl Reading {
+    /// Nothing read, e.g. while the power monitor is down.
+    pub const MISSING: Self = Self {
+        volts: None,
+        amps: None,
+        watts: None,
+    };
+
+    pub fn is_complete(&self) -> bool {
+        self.volts.is_some() && self.amps.is_some() && self.watts.is_some()
+    }
+
+    /// Overwrites the quantities of `power` that were read in this pass.
+    pub fn merge_into(&self, power: &mut PowerInfo) {
+        if let Some(volts) = self.volts {
+            power.volts = volts;
+        }
+        if let Some(amps) = self.amps {
+            power.amps = amps;
+        }
+        if let Some(watts) = self.watts {
+            power.watts = watts;
+        }
+    }
+}
+
+/// One filter per quantity, picked by the `filter` setting.
+pub(crate) struct ReadingFilters {
+    volts: AnyFilter,
+    amps: AnyFilter,
+    watts: AnyFilter,
+}
+
+impl ReadingFilters {
+    pub const fn new(kind: FilterKind) -> Self {
+        Self {
+            volts: AnyFilter::new(kind),
+            amps: AnyFilter::new(kind),
+            watts: AnyFilter::new(kind),
+        }
+    }
+
+    /// Forgets the history, e.g. after the loop was suspended.
+    pub fn reset(&mut self) {
+        self.volts.reset();
+        self.amps.reset();
+        self.watts.reset();
+    }
+
+    /// Filters a raw reading. A quantity that failed to read starts its filter over, see
+    /// `filter.rs`.
+    pub fn update(&mut self, raw: &Reading) -> Reading {
+        Reading {
+            volts: self.volts.update(raw.volts),
+            amps: self.amps.update(raw.amps),
+            watts: self.watts.update(raw.watts),
+        }
+    }
+}
@@ -0,0 +1,408 @@
+//! Button gestures and page navigation.
+//!
+//! Both are plain functions of their inputs; [`Controller`](crate::controller::Controller) feeds
+//! them and applies the side effects, which keeps this module buildable on the host for the tests
+//! in `host-tests/`.
+
+use embassy_time::Duration;
+#[cfg(not(feature = "mock-time"))]
+use embassy_time::Instant;
+use heapless::Vec;
+use husb238::SrcPdo;
+
+#[cfg(feature = "mock-time")]
+use crate::mock_time::Instant;
+use crate::{
+    button::ButtonState,
+    types::{
+        nearest_pdo, pdo_volts, AboutView, ClockField, DiagnosticsView, DisplayItem, LimitField,
+        MeasurementItem, Page, SettingItem, CLOCK_FIELDS, DISPLAY_ITEMS, MEASUREMENT_ITEMS,
+        SETTING_ITEMS,
+    },
+};
+
+/// Both buttons count as pressed together when their events are at most this far apart.
+pub(crate) const MAX_SIMULTANEOUS_PRESS_DELAY: Duration = Duration::from_millis(100);
+
+/// Rows of a scrollbar thumb at the least, so it stays visible on long lists.
+pub(crate) const MIN_THUMB: u16 = 8;
+
+/// Backlight timeouts offered on the display page, in seconds; 0 never dims.
+pub(crate) const BACKLIGHT_TIMEOUTS: [u16; 5] = [0, 10, 30, 60, 300];
+
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub enum BtnsState {
+    Up,
+    Down,
+    UpLong,
+    DownLong,
+    UpDbk,
+    DownDbk,
+    UpAndDown,
+    UpAndDownLong,
+    /// Up let go after a long press, for the momentary output mode.
+    UpReleased,
+}
+
+/// Combines the states of the up and down buttons into gestures.
+pub(crate) struct Gestures {
+    up: ButtonState,
+    down: ButtonState,
+}
+
+impl Gestures {
+    pub const fn new() -> Self {
+        Self {
+            up: ButtonState::Released,
+            down: ButtonState::Released,
+        }
+    }
+
+    /// Records a new state of the up (`up == true`) or down button and returns the gesture it
+    /// completes, if any.
+    pub fn update(&mut self, up: bool, state: ButtonState) -> Option<BtnsState> {
+        let long_released =
+            up && state == ButtonState::Released && matches!(self.up, ButtonState::LongPressed(_));
+
+        if up {
+            self.up = state;
+        } else {
+            self.down = state;
+        }
+
+        if self.down == ButtonState::Pressed || self.up == ButtonState::Pressed {
+            return None;
+        }
+
+        if let (ButtonState::LongPressed(up_at), ButtonState::LongPressed(down_at)) =
+            (self.up, self.down)
+        {
+            if instant_diff(up_at, down_at) < MAX_SIMULTANEOUS_PRESS_DELAY {
+                return Some(BtnsState::UpAndDownLong);
+            }
+        }
+
+        if let (ButtonState::Click(up_at), ButtonState::Click(down_at)) = (self.up, self.down) {
+            if instant_diff(up_at, down_at) < MAX_SIMULTANEOUS_PRESS_DELAY {
+                return Some(BtnsState::UpAndDown);
+            }
+        }
+
+        let (state, other) = if up {
+            (self.up, self.down)
+        } else {
+            (self.down, self.up)
+        };
+
+        if matches!(other, ButtonState::Pressed | ButtonState::LongPressed(_)) {
+            return None;
+        }
+
+        if long_released {
+            return Some(BtnsState::UpReleased);
+        }
+
+        match (state, up) {
+            (ButtonState::LongPressed(_), true) => Some(BtnsState::UpLong),
+            (ButtonState::LongPressed(_), false) => Some(BtnsState::DownLong),
+            (ButtonState::Click(_), true) => Some(BtnsState::Up),
+            (ButtonState::Click(_), false) => Some(BtnsState::Down),
+            (ButtonState::DoubleClick(_), true) => Some(BtnsState::UpDbk),
+            (ButtonState::DoubleClick(_), false) => Some(BtnsState::DownDbk),
+            _ => None,
+        }
+    }
+}
+
+/// The page shown after `btns` on `page`.
+///
+/// `selected` is the voltage the menu opens on and `available` the source's fixed voltages,
+/// lowest first.
+pub(crate) fn next_page(
+    page: Page,
+    btns: BtnsState,
+    selected: SrcPdo,
+    available: &[SrcPdo],
+) -> Page {
+    match page {
+        Page::Monitor => match btns {
+            BtnsState::UpAndDown => Page::OCP,
+            BtnsState::UpAndDownLong => Page::Setting(SettingItem::Voltage),
+            _ => page,
+        },
+        Page::Setting(item) => match btns {
+            BtnsState::Up => Page::Setting(next_setting(item)),
+            BtnsState::Down => Page::Setting(prev_setting(item)),
+            BtnsState::UpAndDown => match item {
+                SettingItem::Voltage => Page::Voltage(nearest_pdo(selected, available)),
+                SettingItem::UVP => Page::UVP(LimitField::Trip),
+                SettingItem::OVP => Page::OVP(LimitField::Trip),
+                SettingItem::OCP => Page::OCP,
+                SettingItem::Output => Page::Output,
+                SettingItem::Watts => Page::Watts,
+                SettingItem::Average => Page::Average,
+                SettingItem::Measurement => Page::Measurement(MEASUREMENT_ITEMS[0]),
+                SettingItem::Cable => Page::Cable,
+                SettingItem::Capture => Page::Capture,
+                SettingItem::Session => Page::Session,
+                SettingItem::Storage => Page::Storage,
+                SettingItem::Diagnostics => Page::Diagnostics(DiagnosticsView::Tasks),
+                SettingItem::Display => Page::Display(DISPLAY_ITEMS[0]),
+                SettingItem::Clock => Page::Clock(CLOCK_FIELDS[0]),
+                SettingItem::About => Page::About(AboutView::Info),
+            },
+            BtnsState::UpAndDownLong => Page::Monitor,
+            _ => page,
+        },
+        Page::Voltage(selected) => match btns {
+            BtnsState::Up => Page::Voltage(next_voltage(selected, available)),
+            BtnsState::Down => Page::Voltage(prev_voltage(selected, available)),
+            BtnsState::UpAndDown => Page::Setting(SettingItem::UVP),
+            BtnsState::UpAndDownLong => Page::Monitor,
+            _ => page,
+        },
+        Page::UVP(field) => match btns {
+            BtnsState::UpAndDown if field == LimitField::Trip => Page::UVP(LimitField::Recover),
+            BtnsState::UpAndDown => Page::Setting(SettingItem::UVP),
+            _ => page,
+        },
+        Page::OVP(field) => match btns {
+            BtnsState::UpAndDown if field == LimitField::Trip => Page::OVP(LimitField::Recover),
+            BtnsState::UpAndDown => Page::Setting(SettingItem::OVP),
+            _ => page,
+        },
+        Page::OCP => match btns {
+            BtnsState::UpAndDown => Page::Setting(SettingItem::OCP),
+            _ => page,
+        },
+        // Up and Down switch the mode, see `Controller`.
+        Page::Output => match btns {
+            BtnsState::UpAndDown => Page::Setting(SettingItem::Output),
+            _ => page,
+        },
+        Page::Watts => match btns {
+            BtnsState::UpAndDown => Page::Setting(SettingItem::Watts),
+            _ => page,
+        },
+        Page::Average => match btns {
+            BtnsState::UpAndDown => Page::Setting(SettingItem::Average),
+            _ => page,
+        },
+        // Up and Down step the highlighted option, see `Controller`.
+        Page::Measurement(item) => match btns {
+            BtnsState::UpAndDown => match next_measurement_item(item) {
+                Some(next) => Page::Measurement(next),
+                None => Page::Setting(SettingItem::Measurement),
+            },
+            BtnsState::UpAndDownLong => Page::Setting(SettingItem::Measurement),
+            _ => page,
+        },
+        Page::Cable => match btns {
+            BtnsState::UpAndDown => Page::Setting(SettingItem::Cable),
+            _ => page,
+        },
+        Page::Capture => match btns {
+            BtnsState::UpAndDown => Page::Setting(SettingItem::Capture),
+            _ => page,
+        },
+        // Up starts and Down stops the session, see `Controller`.
+        Page::Session => match btns {
+            BtnsState::UpAndDown => Page::Setting(SettingItem::Session),
+            _ => page,
+        },
+        // Up mounts and Down unmounts the card, see `Controller`.
+        Page::Storage => match btns {
+            BtnsState::UpAndDown => Page::Setting(SettingItem::Storage),
+            _ => page,
+        },
+        Page::Diagnostics(view) => match btns {
+            BtnsState::Up => Page::Diagnostics(view.next()),
+            BtnsState::Down => Page::Diagnostics(view.prev()),
+            BtnsState::UpDbk
+            | BtnsState::DownDbk
+            | BtnsState::UpAndDownLong
+            | BtnsState::UpReleased => page,
+            _ => Page::Setting(SettingItem::Diagnostics),
+        },
+        // Up and Down change the highlighted option, see `Controller`.
+        Page::Display(item) => match btns {
+            BtnsState::UpAndDown => match next_display_item(item) {
+                Some(next) => Page::Display(next),
+                None => Page::Setting(SettingItem::Display),
+            },
+            BtnsState::UpAndDownLong => Page::Setting(SettingItem::Display),
+            _ => page,
+        },
+        // Up and Down step the highlighted field, see `Controller`. Up and Down together on the last
+        // field set the clock, a long press of both leaves without setting it.
+        Page::Clock(field) => match btns {
+            BtnsState::UpAndDown => match next_clock_field(field) {
+                Some(next) => Page::Clock(next),
+                None => Page::Setting(SettingItem::Clock),
+            },
+            BtnsState::UpAndDownLong => Page::Setting(SettingItem::Clock),
+            _ => page,
+        },
+        // Up and Down page through the cheat-sheet, a long press of both on the first sub-page
+        // starts the bootloader, see `Controller`.
+        Page::About(view) => match btns {
+            BtnsState::Up => Page::About(view.next()),
+            BtnsState::Down => Page::About(view.prev()),
+            BtnsState::UpDbk
+            | BtnsState::DownDbk
+            | BtnsState::UpAndDownLong
+            | BtnsState::UpReleased => page,
+            _ => Page::Setting(SettingItem::About),
+        },
+        // Up and Down change the value, see `Controller`, which saves on the last step.
+        Page::Setup(step) => match btns {
+            BtnsState::UpAndDown => step.next().map_or(Page::Monitor, Page::Setup),
+            BtnsState::UpAndDownLong => Page::Monitor,
+            _ => page,
+        },
+    }
+}
+
+fn next_setting(item: SettingItem) -> SettingItem {
+    let index = SETTING_ITEMS
+        .iter()
+        .position(|ele| *ele == item)
+        .map(|i| (i + 1) % SETTING_ITEMS.len());
+
+    SETTING_ITEMS[index.unwrap_or(0)]
+}
+
+fn prev_setting(item: SettingItem) -> SettingItem {
+    let index = SETTING_ITEMS
+        .iter()
+        .position(|ele| *ele == item)
+        .map(|i| (i + SETTING_ITEMS.len() - 1) % SETTING_ITEMS.len());
+
+    SETTING_ITEMS[index.unwrap_or(0)]
+}
+
+/// The option after `item`, or none after the last one.
+fn next_display_item(item: DisplayItem) -> Option<DisplayItem> {
+    let index = DISPLAY_ITEMS.iter().position(|ele| *ele == item)?;
+
+    DISPLAY_ITEMS.get(index + 1).copied()
+}
+
+/// The option after `item`, or none after the last one.
+fn next_measurement_item(item: MeasurementItem) -> Option<MeasurementItem> {
+    let index = MEASUREMENT_ITEMS.iter().position(|ele| *ele == item)?;
+
+    MEASUREMENT_ITEMS.get(index + 1).copied()
+}
+
+/// The field after `field`, or none after the last one, which sets the clock.
+pub(crate) fn next_clock_field(field: ClockField) -> Option<ClockField> {
+    let index = CLOCK_FIELDS.iter().position(|ele| *ele == field)?;
+
+    CLOCK_FIELDS.get(index + 1).copied()
+}
+
+/// Where the thumb of a scrollbar `track` rows long sits for item `index` of `len`, as its first
+/// row and its length. The length is a multiple of four rows, which the two pixel wide bitmaps of
+/// the display come in. The lists wrap around, so the thumb jumping between the ends is the cue
+/// that they did.
+pub(crate) fn scroll_thumb(index: usize, len: usize, track: u16) -> (u16, u16) {
+    let len = len.max(1) as u16;
+    let thumb = ((track / len).max(MIN_THUMB) / 4 * 4).min(track);
+    let travel = track - thumb;
+
+    let top = match len {
+        1 => 0,
+        len => travel * (index as u16).min(len - 1) / (len - 1),
+    };
+
+    (top, thumb)
+}
+
+/// Where `page` sits in the settings menu, outermost first, e.g. `["Settings", "UVP", "Recover"]`,
+/// for the header of the setting pages. Empty for the monitor page and the pages that take the
+/// whole screen.
+pub(crate) fn breadcrumb(page: Page) -> Vec<&'static str, 3> {
+    let (item, field) = match page {
+        Page::Setting(_) => (None, None),
+        Page::Voltage(_) => (Some(SettingItem::Voltage), None),
+        Page::UVP(field) => (Some(SettingItem::UVP), Some(field)),
+        Page::OVP(field) => (Some(SettingItem::OVP), Some(field)),
+        Page::OCP => (Some(SettingItem::OCP), None),
+        Page::Output => (Some(SettingItem::Output), None),
+        Page::Watts => (Some(SettingItem::Watts), None),
+        Page::Average => (Some(SettingItem::Average), None),
+        Page::Measurement(_) => (Some(SettingItem::Measurement), None),
+        Page::Display(_) => (Some(SettingItem::Display), None),
+        Page::Clock(_) => (Some(SettingItem::Clock), None),
+        Page::About(_) => (Some(SettingItem::About), None),
+        Page::Monitor
+        | Page::Cable
+        | Page::Capture
+        | Page::Session
+        | Page::Storage
+        | Page::Diagnostics(_)
+        | Page::Setup(_) => return Vec::new(),
+    };
+
+    let mut crumbs = Vec::new();
+    crumbs.push("Settings").ok();
+    if let Some(item) = item {
+        crumbs.push(item.as_str()).ok();
+    }
+    if let Some(field) = field {
+        crumbs.push(field.as_str()).ok();
+    }
+
+    crumbs
+}
+
+/// The next longer (`up`) or shorter of [`BACKLIGHT_TIMEOUTS`], wrapping around. A timeout set
+/// from the console that is not one of them steps to its neighbours.
+pub(crate) fn step_timeout(seconds: u16, up: bool) -> u16 {
+    let timeouts = &BACKLIGHT_TIMEOUTS;
+
+    if up {
+        timeouts
+            .iter()
+            .copied()
+            .find(|&t| t > seconds)
+            .unwrap_or(timeouts[0])
+    } else {
+        timeouts
+            .iter()
+            .copied()
+            .rev()
+            .find(|&t| t < seconds)
+            .unwrap_or(timeouts[timeouts.len() - 1])
+    }
+}
+
+/// The available voltage above `selected`, wrapping around to the lowest. PDOs the source does not
+/// offer are skipped, and one selected before it stopped offering it steps to its neighbours.
+fn next_voltage(selected: SrcPdo, available: &[SrcPdo]) -> SrcPdo {
+    let volts = pdo_volts(selected);
+
+    match available.iter().find(|&&pdo| pdo_volts(pdo) > volts) {
+        Some(pdo) => *pdo,
+        None => available.first().copied().unwrap_or(selected),
+    }
+}
+
+/// The available voltage below `selected`, wrapping around to the highest, see [`next_voltage`].
+fn prev_voltage(selected: SrcPdo, available: &[SrcPdo]) -> SrcPdo {
+    let volts = pdo_volts(selected);
+
+    match available.iter().rev().find(|&&pdo| pdo_volts(pdo) < volts) {
+        Some(pdo) => *pdo,
+        None => available.last().copied().unwrap_or(selected),
+    }
+}
+
+fn instant_diff(a: Instant, b: Instant) -> Duration {
+    if a > b {
+        a - b
+    } else {
+        b - a
+    }
+}
@@ -0,0 +1,227 @@
+//! Modbus RTU server on an RS-485 transceiver.
+//!
+//! Input (0x04) and holding (0x03) registers both map onto `register_map.rs`; writes (0x06, 0x10)
+//! only succeed for the control registers. The transceiver's DE and /RE pins are tied together
+//! and driven high only while a response is being sent.
+
+use embassy_time::{with_timeout, Duration, Timer};
+use embedded_io_async::{Read, Write};
+use heapless::Vec;
+
+use crate::{
+    log::{debug, error, Module},
+    register_map::{read_registers, write_register, RegisterError, REG_COUNT},
+    types::{ModbusDePin, ModbusUart},
+};
+
+const LOG_MODULE: Module = Module::Fieldbus;
+
+pub(crate) const MODBUS_ADDRESS: u8 = 0x01;
+pub(crate) const MODBUS_BAUDRATE: u32 = 19_200;
+
+/// 3.5 character times at 19200 baud, rounded up. The spec fixes it at 1.75 ms above 19200.
+const FRAME_GAP: Duration = Duration::from_micros(2_000);
+/// One character time, waited after flushing so the stop bit leaves before DE drops.
+const CHAR_TIME: Duration = Duration::from_micros(600);
+
+const MAX_FRAME_LEN: usize = 64;
+
+const FN_READ_HOLDING: u8 = 0x03;
+const FN_READ_INPUT: u8 = 0x04;
+const FN_WRITE_SINGLE: u8 = 0x06;
+const FN_WRITE_MULTIPLE: u8 = 0x10;
+
+const EX_ILLEGAL_FUNCTION: u8 = 0x01;
+const EX_ILLEGAL_ADDRESS: u8 = 0x02;
+const EX_ILLEGAL_VALUE: u8 = 0x03;
+
+pub(crate) struct Modbus {
+    uart: ModbusUart,
+    de: ModbusDePin,
+    frame: Vec<u8, MAX_FRAME_LEN>,
+    response: Vec<u8, MAX_FRAME_LEN>,
+}
+
+impl Modbus {
+    pub fn new(uart: ModbusUart, mut de: ModbusDePin) -> Self {
+        de.set_low();
+
+        Self {
+            uart,
+            de,
+            frame: Vec::new(),
+            response: Vec::new(),
+        }
+    }
+
+    pub async fn task(&mut self) {
+        loop {
+            if !self.receive_frame().await {
+                continue;
+            }
+
+            if self.frame.len() < 4 || crc16(&self.frame) != 0 {
+                debug!("modbus: dropped frame of {} bytes", self.frame.len());
+                continue;
+            }
+
+            // Broadcasts (address 0) are not answered and not supported.
+            if self.frame[0] != MODBUS_ADDRESS {
+                continue;
+            }
+
+            self.handle_frame().await;
+            self.send_response().await;
+        }
+    }
+
+    /// Collects bytes until the line has been idle for a frame gap.
+    async fn receive_frame(&mut self) -> bool {
+        let mut byte = [0u8; 1];
+
+        self.frame.clear();
+
+        if self.uart.read_exact(&mut byte).await.is_err() {
+            return false;
+        }
+        self.frame.push(byte[0]).ok();
+
+        loop {
+            match with_timeout(FRAME_GAP, self.uart.read_exact(&mut byte)).await {
+                Ok(Ok(_)) => {
+                    if self.frame.push(byte[0]).is_err() {
+                        return false;
+                    }
+                }
+                Ok(Err(_)) => return false,
+                Err(_) => return true,
+            }
+        }
+    }
+
+    async fn handle_frame(&mut self) {
+        let function = self.frame[1];
+        let body_len = self.frame.len() - 2;
+
+        self.response.clear();
+        self.response.push(MODBUS_ADDRESS).ok();
+        self.response.push(function).ok();
+
+        let result = match function {
+            FN_READ_HOLDING | FN_READ_INPUT if body_len == 6 => {
+                let start = self.word(2);
+                let count = self.word(4);
+
+                self.read(start, count).await
+            }
+            FN_WRITE_SINGLE if body_len == 6 => {
+                let addr = self.word(2);
+                let value = self.word(4);
+
+                self.write(addr, value).await.map(|_| {
+                    self.response.extend_from_slice(&self.frame[2..6]).ok();
+                })
+            }
+            FN_WRITE_MULTIPLE if body_len >= 7 => {
+                let start = self.word(2);
+                let count = self.word(4);
+
+                if body_len != 7 + count as usize * 2 {
+                    Err(EX_ILLEGAL_VALUE)
+                } else {
+                    let mut result = Ok(());
+
+                    for i in 0..count {
+                        let value = self.word(7 + i as usize * 2);
+
+                        result = self.write(start + i, value).await;
+                        if result.is_err() {
+                            break;
+                        }
+                    }
+
+                    result.map(|_| {
+                        self.response.extend_from_slice(&self.frame[2..6]).ok();
+                    })
+                }
+            }
+            FN_READ_HOLDING | FN_READ_INPUT | FN_WRITE_SINGLE | FN_WRITE_MULTIPLE => {
+                Err(EX_ILLEGAL_VALUE)
+            }
+            _ => Err(EX_ILLEGAL_FUNCTION),
+        };
+
+        if let Err(code) = result {
+            self.response.truncate(1);
+            self.response.push(function | 0x80).ok();
+            self.response.push(code).ok();
+        }
+
+        let crc = crc16(&self.response);
+        self.response.extend_from_slice(&crc.to_le_bytes()).ok();
+    }
+
+    async fn read(&mut self, start: u16, count: u16) -> Result<(), u8> {
+        let end = start as usize + count as usize;
+
+        if count == 0 || end > REG_COUNT {
+            return Err(EX_ILLEGAL_ADDRESS);
+        }
+
+        let mut regs = [0u16; REG_COUNT];
+        read_registers(&mut regs).await;
+
+        self.response.push((count * 2) as u8).ok();
+        for reg in &regs[start as usize..end] {
+            self.response.extend_from_slice(&reg.to_be_bytes()).ok();
+        }
+
+        Ok(())
+    }
+
+    async fn write(&mut self, addr: u16, value: u16) -> Result<(), u8> {
+        let addr = u8::try_from(addr).map_err(|_| EX_ILLEGAL_ADDRESS)?;
+
+        write_register(addr, value).await.map_err(|err| match err {
+            RegisterError::IllegalAddress => EX_ILLEGAL_ADDRESS,
+            RegisterError::IllegalValue | RegisterError::Remote(_) => EX_ILLEGAL_VALUE,
+        })
+    }
+
+    async fn send_response(&mut self) {
+        self.de.set_high();
+
+        if self.uart.write_all(&self.response).await.is_err() {
+            error!("modbus: write error");
+        }
+        self.uart.flush().await.ok();
+        Timer::after(CHAR_TIME).await;
+
+        self.de.set_low();
+    }
+
+    fn word(&self, offset: usize) -> u16 {
+        u16::from_be_bytes([self.frame[offset], self.frame[offset + 1]])
+    }
+}
+
+/// Modbus CRC-16 (polynomial 0xA001, initial value 0xFFFF).
+///
+/// Running it over a frame including its trailing CRC yields zero.
+fn crc16(data: &[u8]) -> u16 {
+    let mut crc = 0xffffu16;
+
+    for &byte in data {
+        crc ^= byte as u16;
+
+        for _ in 0..8 {
+            if crc & 1 != 0 {
+                crc = (crc >> 1) ^ 0xa001;
+            } else {
+                crc >>= 1;
+            }
+        }
+    }
+
+    crc
+}
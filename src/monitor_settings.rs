@@ -0,0 +1,50 @@
+//! Keeps the power monitor configuration of `power_monitor.rs` in a record of the settings page,
+//! one per channel. It is read once at boot, so a stored change takes effect at the next restart.
+
+use crate::{
+    bsp,
+    log::{info, Module},
+    power_monitor::PowerMonitorConfig,
+    settings::{self, SettingsError, MONITOR_B_RECORD, MONITOR_RECORD},
+    types::Channel,
+};
+
+const LOG_MODULE: Module = Module::Measure;
+
+const MAGIC: u32 = 0x5044_494d; // "PDIM"
+
+fn record(channel: Channel) -> usize {
+    match channel {
+        Channel::A => MONITOR_RECORD,
+        Channel::B => MONITOR_B_RECORD,
+    }
+}
+
+/// The board's configuration for `channel` from `bsp.rs`.
+fn default(channel: Channel) -> PowerMonitorConfig {
+    PowerMonitorConfig {
+        address: match channel {
+            Channel::A => bsp::POWER_MONITOR_ADDRESS,
+            Channel::B => bsp::POWER_MONITOR_B_ADDRESS,
+        },
+        shunt_micro_ohms: bsp::POWER_MONITOR_SHUNT_MICRO_OHMS,
+        max_milliamps: bsp::POWER_MONITOR_MAX_MILLIAMPS,
+    }
+}
+
+/// The stored configuration for `channel`, or the board's when none is stored.
+pub(crate) fn load(channel: Channel) -> PowerMonitorConfig {
+    let stored =
+        settings::read(record(channel), MAGIC).and_then(|buf| PowerMonitorConfig::from_bytes(&buf));
+
+    info!("{} power monitor: {:?}", channel.as_str(), stored);
+    stored.unwrap_or(default(channel))
+}
+
+/// Writes `config` for `channel` to flash, used from the next restart on.
+pub(crate) async fn store(
+    channel: Channel,
+    config: PowerMonitorConfig,
+) -> Result<(), SettingsError> {
+    settings::write(record(channel), MAGIC, &config.to_bytes()).await
+}
@@ -0,0 +1,500 @@
+use core::convert::Infallible;
+
+use embassy_time::Instant;
+use embedded_hal::{digital::OutputPin, pwm::SetDutyCycle};
+use husb238::SrcPdo;
+
+#[cfg(feature = "relay-output")]
+use crate::relay::{Contacts, RelaySequencer, RelayTiming};
+use crate::{
+    protection::{
+        FuseLimit, GuardEvent, OutputSense, SenseHealth, SenseReport, SoftFuse, VoltageFault,
+        VoltageGuard, VoltageLimit,
+    },
+    types::{pdo_matches, ControlSource, OutputMode, PowerInfo, StatusInfo},
+    units::{Value, ZERO},
+};
+
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) enum OutputError {
+    VoltageMismatch,
+    OverCurrent,
+    UnderVoltage,
+    OverVoltage,
+    InterlockOpen,
+    /// The bus collapsed during the short test ramp.
+    ShortCircuit,
+    /// The bus could not be read during the short test ramp.
+    ShortTestFailed,
+    VoltageSlew,
+    CurrentSlew,
+    /// An over-current trip was not reset yet.
+    Tripped,
+    /// The sensed output does not follow the commanded one.
+    DriverFault,
+    /// The power monitor is down, so an OCP could not trip.
+    NoMeasurement,
+    /// Too many over-current trips in a row, see `protection.rs`.
+    FuseBlown,
+    /// Only the button turns the output on in the momentary mode.
+    LocalOnly,
+}
+
+impl OutputError {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OutputError::VoltageMismatch => "voltage mismatch",
+            OutputError::OverCurrent => "over current",
+            OutputError::UnderVoltage => "under voltage",
+            OutputError::OverVoltage => "over voltage",
+            OutputError::InterlockOpen => "interlock open",
+            OutputError::ShortCircuit => "short circuit",
+            OutputError::ShortTestFailed => "short test failed",
+            OutputError::VoltageSlew => "dV/dt",
+            OutputError::CurrentSlew => "dI/dt",
+            OutputError::Tripped => "tripped",
+            OutputError::DriverFault => "output driver",
+            OutputError::NoMeasurement => "no measurement",
+            OutputError::FuseBlown => "fuse blown",
+            OutputError::LocalOnly => "momentary mode",
+        }
+    }
+}
+
+/// Switches the load for [`OutputController`]: the output FET, or a relay on builds with
+/// `relay-output`.
+pub(crate) trait OutputDriver {
+    /// Switches the load at once.
+    fn switch(&mut self, on: bool);
+
+    /// Switches the load off for a request rather than a trip, which a driver may put off for a
+    /// moment to spare its contacts.
+    fn switch_off_quietly(&mut self) {
+        self.switch(false);
+    }
+
+    /// Moves a put-off switch along, with the load current of this pass if it was read.
+    fn poll(&mut self, _amps: Option<Value>) {}
+
+    /// Whether the load side follows the last switch yet.
+    fn is_settled(&self) -> bool {
+        true
+    }
+
+    /// Whether the driver can ramp the load in for the short test.
+    fn can_ramp(&self) -> bool {
+        false
+    }
+
+    /// Drives the load at `duty` percent for the short test; nothing on drivers that cannot.
+    fn ramp(&mut self, _duty: u8) {}
+}
+
+/// The output FET on a timer channel, on at full duty, which can ramp the load in, see
+/// `pwm_timer.rs`.
+pub(crate) struct PwmFetDriver<PWM> {
+    pwm: PWM,
+}
+
+impl<PWM> PwmFetDriver<PWM>
+where
+    PWM: SetDutyCycle<Error = Infallible>,
+{
+    pub fn new(pwm: PWM) -> Self {
+        Self { pwm }
+    }
+}
+
+impl<PWM> OutputDriver for PwmFetDriver<PWM>
+where
+    PWM: SetDutyCycle<Error = Infallible>,
+{
+    fn switch(&mut self, on: bool) {
+        if on {
+            self.pwm.set_duty_cycle_fully_on().ok();
+        } else {
+            self.pwm.set_duty_cycle_fully_off().ok();
+        }
+    }
+
+    fn can_ramp(&self) -> bool {
+        true
+    }
+
+    fn ramp(&mut self, duty: u8) {
+        self.pwm.set_duty_cycle_percent(duty).ok();
+    }
+}
+
+/// The FET of the second output, on with the pin high. Its pin is on no timer channel shared by
+/// every board, so it switches straight on.
+#[cfg(all(feature = "dual-output", not(feature = "relay-output")))]
+pub(crate) struct FetDriver<PIN> {
+    pin: PIN,
+}
+
+#[cfg(all(feature = "dual-output", not(feature = "relay-output")))]
+impl<PIN> FetDriver<PIN>
+where
+    PIN: OutputPin<Error = Infallible>,
+{
+    pub fn new(pin: PIN) -> Self {
+        Self { pin }
+    }
+}
+
+#[cfg(all(feature = "dual-output", not(feature = "relay-output")))]
+impl<PIN> OutputDriver for FetDriver<PIN>
+where
+    PIN: OutputPin<Error = Infallible>,
+{
+    fn switch(&mut self, on: bool) {
+        if on {
+            self.pin.set_high().ok();
+        } else {
+            self.pin.set_low().ok();
+        }
+    }
+}
+
+/// A relay with its coil on the output pin, energized with the pin high, see `relay.rs`.
+#[cfg(feature = "relay-output")]
+pub(crate) struct RelayDriver<PIN> {
+    coil: PIN,
+    sequencer: RelaySequencer,
+}
+
+#[cfg(feature = "relay-output")]
+impl<PIN> RelayDriver<PIN>
+where
+    PIN: OutputPin<Error = Infallible>,
+{
+    pub fn new(coil: PIN, timing: RelayTiming) -> Self {
+        Self {
+            coil,
+            sequencer: RelaySequencer::new(timing),
+        }
+    }
+
+    fn drive_coil(&mut self) {
+        if self.sequencer.coil() {
+            self.coil.set_high().ok();
+        } else {
+            self.coil.set_low().ok();
+        }
+    }
+}
+
+#[cfg(feature = "relay-output")]
+impl<PIN> OutputDriver for RelayDriver<PIN>
+where
+    PIN: OutputPin<Error = Infallible>,
+{
+    fn switch(&mut self, on: bool) {
+        self.sequencer.switch(Instant::now(), on);
+        self.drive_coil();
+    }
+
+    fn switch_off_quietly(&mut self) {
+        self.sequencer.open_quietly(Instant::now());
+    }
+
+    fn poll(&mut self, amps: Option<Value>) {
+        if self.sequencer.update(Instant::now(), amps) {
+            self.drive_coil();
+        }
+    }
+
+    fn is_settled(&self) -> bool {
+        matches!(
+            self.sequencer.contacts(Instant::now()),
+            Contacts::Open | Contacts::Closed
+        )
+    }
+}
+
+/// What [`OutputController::protect`] did to the output.
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) enum Protection {
+    Tripped(OutputError),
+    /// Back on after an under- or over-voltage trip.
+    Recovered,
+}
+
+pub(crate) struct OutputController<D>
+where
+    D: OutputDriver,
+{
+    driver: D,
+    enabled: bool,
+    mode: OutputMode,
+    /// The external interlock, always closed on builds without one.
+    interlock_closed: bool,
+    guard: VoltageGuard,
+    /// An over-current trip holds the output off until it is reset.
+    tripped: bool,
+    fuse: SoftFuse,
+    fuse_limit: FuseLimit,
+    sense: OutputSense,
+}
+
+impl<D> OutputController<D>
+where
+    D: OutputDriver,
+{
+    pub fn new(mut driver: D) -> Self {
+        driver.switch(false);
+
+        Self {
+            driver,
+            enabled: false,
+            mode: OutputMode::Latching,
+            interlock_closed: true,
+            guard: VoltageGuard::new(),
+            tripped: false,
+            fuse: SoftFuse::new(),
+            fuse_limit: FuseLimit::default(),
+            sense: OutputSense::new(),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// In the momentary mode a trip is final: the output comes back only with a new press.
+    pub fn set_mode(&mut self, mode: OutputMode) {
+        if mode == OutputMode::Momentary {
+            self.guard.release();
+        }
+
+        self.mode = mode;
+    }
+
+    pub fn set_fuse(&mut self, limit: FuseLimit) {
+        self.fuse_limit = limit;
+    }
+
+    pub fn is_interlock_closed(&self) -> bool {
+        self.interlock_closed
+    }
+
+    /// Follows the external interlock input. Opening it turns the output off and drops a pending
+    /// voltage recovery; closing it again leaves the output off.
+    pub fn interlock(&mut self, closed: bool) -> Option<Protection> {
+        self.interlock_closed = closed;
+
+        if closed {
+            return None;
+        }
+
+        self.guard.release();
+
+        if !self.enabled {
+            return None;
+        }
+
+        self.drive(false);
+        Some(Protection::Tripped(OutputError::InterlockOpen))
+    }
+
+    /// Compares the driver output read back from the sense input with the commanded state, and
+    /// returns the health once they have disagreed for a few passes, see `protection.rs`. Passes
+    /// while a relay is still moving are not compared.
+    pub fn sense(&mut self, sensed: bool) -> Option<SenseHealth> {
+        if !self.driver.is_settled() {
+            return None;
+        }
+
+        self.sense.update(self.enabled, sensed)
+    }
+
+    /// Moves a put-off switch of the driver along, on every pass of the measurement loop.
+    pub fn poll(&mut self, amps: Option<Value>) {
+        self.driver.poll(amps);
+    }
+
+    /// Whether the driver can ramp the load in for the short test.
+    pub fn can_ramp(&self) -> bool {
+        self.driver.can_ramp()
+    }
+
+    /// `None` on builds without a sense input.
+    pub fn sense_report(&self) -> Option<SenseReport> {
+        self.sense.report()
+    }
+
+    /// Whether a voltage trip holds the output off until the voltage recovers.
+    pub fn is_held(&self) -> bool {
+        self.guard.held().is_some()
+    }
+
+    pub fn is_tripped(&self) -> bool {
+        self.tripped
+    }
+
+    pub fn is_fuse_blown(&self) -> bool {
+        self.fuse.is_blown()
+    }
+
+    /// Lets the output go on again after an over-current trip, unless the trips blew the fuse.
+    /// Returns whether the trip was reset.
+    pub fn reset_trip(&mut self) -> bool {
+        if self.fuse.is_blown() {
+            return false;
+        }
+
+        self.tripped = false;
+        true
+    }
+
+    /// Holds the output off with a blown fuse, for one that was blown before a restart.
+    pub fn blow_fuse(&mut self) {
+        self.fuse.blow();
+        self.tripped = true;
+    }
+
+    /// Mends a blown fuse and resets the trip with it.
+    pub fn reset_fuse(&mut self) {
+        self.fuse.reset();
+        self.tripped = false;
+    }
+
+    /// Drives the output pin without any checks. A pending voltage recovery is dropped, so the
+    /// output stays as set.
+    pub fn set(&mut self, enabled: bool) {
+        self.guard.release();
+        self.drive(enabled);
+    }
+
+    /// Drives the switch at `duty` percent for the short test ramp, without changing whether the
+    /// output counts as enabled. The ramp must end with `ramp(0)` before anything else drives the
+    /// switch, and is only for drivers that [`can_ramp`](Self::can_ramp).
+    pub fn ramp(&mut self, duty: u8) {
+        self.driver.ramp(duty);
+    }
+
+    fn drive(&mut self, enabled: bool) {
+        self.driver.switch(enabled);
+        self.enabled = enabled;
+    }
+
+    /// Applies an output request from `source`.
+    ///
+    /// Enabling is refused after an over-current trip until it is reset, while the interlock is
+    /// open, when the negotiated contract does not match the selected PDO, or in the momentary
+    /// mode from anything but the buttons. Disabling lets the driver wait for a low current, see
+    /// [`OutputDriver::switch_off_quietly`].
+    pub fn request(
+        &mut self,
+        enabled: bool,
+        source: ControlSource,
+        selected: SrcPdo,
+        status: &StatusInfo,
+    ) -> Result<(), OutputError> {
+        self.check(enabled, source, selected, status)?;
+
+        if enabled {
+            self.set(true);
+        } else {
+            self.guard.release();
+            self.driver.switch_off_quietly();
+            self.enabled = false;
+        }
+
+        Ok(())
+    }
+
+    /// Whether [`request`](Self::request) would take the request, so the short test only ramps in
+    /// an output that may go on.
+    pub fn check(
+        &self,
+        enabled: bool,
+        source: ControlSource,
+        selected: SrcPdo,
+        status: &StatusInfo,
+    ) -> Result<(), OutputError> {
+        if !self.mode.takes(enabled, source) {
+            return Err(OutputError::LocalOnly);
+        }
+
+        if enabled && self.fuse.is_blown() {
+            return Err(OutputError::FuseBlown);
+        }
+
+        if enabled && self.tripped {
+            return Err(OutputError::Tripped);
+        }
+
+        if enabled && !self.interlock_closed {
+            return Err(OutputError::InterlockOpen);
+        }
+
+        if enabled && !pdo_matches(selected, status.target_volts) {
+            return Err(OutputError::VoltageMismatch);
+        }
+
+        Ok(())
+    }
+
+    /// Turns the output off for a check made outside the controller, like the slew-rate alarms.
+    /// Like an over-current trip, it does not recover.
+    pub fn trip(&mut self, err: OutputError) -> Option<Protection> {
+        if !self.enabled {
+            return None;
+        }
+
+        self.set(false);
+        Some(Protection::Tripped(err))
+    }
+
+    /// Turns the output off when the measured current exceeds the OCP threshold or the voltage
+    /// leaves the UVP and OVP limits, and back on when the `source` voltage ahead of the switch
+    /// recovers.
+    ///
+    /// An OCP of zero disables the check. An over-current trip does not recover and keeps the
+    /// output from going on again until [`reset_trip`](Self::reset_trip). No trip recovers in the
+    /// momentary mode, or without a source reading at the trip. The trip that blows the soft fuse
+    /// is reported as [`OutputError::FuseBlown`].
+    pub fn protect(
+        &mut self,
+        power: &PowerInfo,
+        source: Option<Value>,
+        ocp: Value,
+        uvp: &VoltageLimit,
+        ovp: &VoltageLimit,
+    ) -> Option<Protection> {
+        if self.enabled && ocp > ZERO && power.amps > ocp {
+            self.set(false);
+            self.tripped = true;
+
+            if self.fuse.trip(Instant::now(), &self.fuse_limit) {
+                return Some(Protection::Tripped(OutputError::FuseBlown));
+            }
+            return Some(Protection::Tripped(OutputError::OverCurrent));
+        }
+
+        match self
+            .guard
+            .update(power.volts, source, self.enabled, uvp, ovp)?
+        {
+            GuardEvent::Trip(fault) => {
+                // Without a source reading nothing tells when the source is back.
+                if self.mode == OutputMode::Momentary || source.is_none() {
+                    self.guard.release();
+                }
+
+                self.drive(false);
+
+                Some(Protection::Tripped(match fault {
+                    VoltageFault::Under => OutputError::UnderVoltage,
+                    VoltageFault::Over => OutputError::OverVoltage,
+                }))
+            }
+            GuardEvent::Recover(_) => {
+                self.drive(true);
+                Some(Protection::Recovered)
+            }
+        }
+    }
+}
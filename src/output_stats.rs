@@ -0,0 +1,141 @@
+//! Lifetime statistics of the output switch.
+//!
+//! How long the output has been on and how often it was switched on, over the life of the unit,
+//! to judge the wear on the output FET or, on relay variants, the contacts. The measurement loop
+//! feeds [`OutputStatsTracker`] on every pass; the counters are written at most every
+//! [`STATS_SAVE_INTERVAL`], so a power cut loses what was counted since the last write.
+//!
+//! They live in a flash page of their own, apart from the settings, as a journal: each write
+//! appends an entry after the last one, and only a full page is erased and started over. One erase
+//! every [`JOURNAL_ENTRIES`] writes keeps the page within its endurance for the life of the unit.
+
+use embassy_time::Duration;
+#[cfg(not(feature = "mock-time"))]
+use embassy_time::Instant;
+
+#[cfg(feature = "mock-time")]
+use crate::mock_time::Instant;
+
+/// Shortest time between two writes of the counters to flash, also after boot.
+pub(crate) const STATS_SAVE_INTERVAL: Duration = Duration::from_secs(15 * 60);
+
+/// Length of an entry of the journal, one flash double-word.
+pub(crate) const ENTRY_LEN: usize = 8;
+/// Entries in the 2 KiB journal page.
+pub(crate) const JOURNAL_ENTRIES: usize = 2048 / ENTRY_LEN;
+
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) struct OutputStats {
+    /// Total time the output was on.
+    pub on_secs: u32,
+    /// Times the output was switched on.
+    pub cycles: u32,
+}
+
+impl OutputStats {
+    pub const fn zero() -> Self {
+        Self {
+            on_secs: 0,
+            cycles: 0,
+        }
+    }
+
+    pub fn to_bytes(self) -> [u8; 8] {
+        let mut buf = [0u8; 8];
+
+        buf[0..4].copy_from_slice(&self.on_secs.to_le_bytes());
+        buf[4..8].copy_from_slice(&self.cycles.to_le_bytes());
+
+        buf
+    }
+
+    pub fn from_bytes(buf: &[u8; 8]) -> Self {
+        Self {
+            on_secs: u32::from_le_bytes([buf[0], buf[1], buf[2], buf[3]]),
+            cycles: u32::from_le_bytes([buf[4], buf[5], buf[6], buf[7]]),
+        }
+    }
+}
+
+pub(crate) struct OutputStatsTracker {
+    stats: OutputStats,
+    /// Start of the part of the running on-time not counted yet.
+    on_since: Option<Instant>,
+    saved: OutputStats,
+    save_at: Option<Instant>,
+}
+
+impl OutputStatsTracker {
+    /// Continues from the counters stored in flash at boot, `now`. The first write waits a whole
+    /// interval too, so a unit restarted over and over does not write on every boot.
+    pub fn new(stored: OutputStats, now: Instant) -> Self {
+        Self {
+            stats: stored,
+            on_since: None,
+            saved: stored,
+            save_at: Some(now + STATS_SAVE_INTERVAL),
+        }
+    }
+
+    /// Takes the output state at `now`. Whole seconds of on-time are counted as they pass, the
+    /// rest is carried over to the next call.
+    pub fn update(&mut self, now: Instant, enabled: bool) {
+        if enabled && self.on_since.is_none() {
+            self.stats.cycles = self.stats.cycles.saturating_add(1);
+            self.on_since = Some(now);
+        }
+
+        let Some(since) = self.on_since else {
+            return;
+        };
+
+        let secs = (now - since).as_secs();
+        self.stats.on_secs = self.stats.on_secs.saturating_add(secs as u32);
+
+        self.on_since = enabled.then(|| since + Duration::from_secs(secs));
+    }
+
+    pub fn stats(&self) -> OutputStats {
+        self.stats
+    }
+
+    /// The counters to write to flash, if they changed and the last write was long enough ago.
+    pub fn due(&mut self, now: Instant) -> Option<OutputStats> {
+        if self.stats == self.saved || self.save_at.is_some_and(|at| now < at) {
+            return None;
+        }
+
+        self.saved = self.stats;
+        self.save_at = Some(now + STATS_SAVE_INTERVAL);
+
+        Some(self.stats)
+    }
+}
+
+/// First double-word of a journal page, which tells it from a blank page or whatever else the
+/// page held before.
+pub(crate) const JOURNAL_HEADER: [u8; ENTRY_LEN] = *b"PDSTATS1";
+
+/// The counters of the last entry in the journal `page`, none if it has none or is no journal.
+pub(crate) fn journal_last(page: &[u8]) -> Option<OutputStats> {
+    if !page.starts_with(&JOURNAL_HEADER) {
+        return None;
+    }
+
+    let last = journal_next(page).unwrap_or(JOURNAL_ENTRIES) - 1;
+    let entry = &page[last * ENTRY_LEN..(last + 1) * ENTRY_LEN];
+
+    (last > 0).then(|| OutputStats::from_bytes(entry.try_into().unwrap()))
+}
+
+/// Index of the slot the next entry of the journal `page` goes to, none if the page has to be
+/// erased first, because it is full or no journal. Entries are only appended, so the slot is the
+/// first blank one after the header.
+pub(crate) fn journal_next(page: &[u8]) -> Option<usize> {
+    if !page.starts_with(&JOURNAL_HEADER) {
+        return None;
+    }
+
+    page.chunks_exact(ENTRY_LEN)
+        .position(|entry| entry.iter().all(|&byte| byte == 0xFF))
+}
@@ -0,0 +1,51 @@
+//! The HUSB238 readings as last sent to the screen.
+//!
+//! The main loop reads the contract every tenth pass, but it stays the same for as long as the
+//! source does, so [`PdCache`] keeps what went out last and lets through only what changed. The
+//! render queue drops commands when it is full; a value that did not make it in is sent again on
+//! the next read, so the display never stays on a stale contract. The source's PDOs are read only
+//! when a source attaches, as they cannot change during a contract.
+
+use husb238::SrcPdo;
+
+use crate::units::Value;
+
+/// The last value read from a register and sent on.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub(crate) struct Cached<T> {
+    last: Option<T>,
+}
+
+impl<T: PartialEq + Copy> Cached<T> {
+    pub const fn new() -> Self {
+        Self { last: None }
+    }
+
+    /// Keeps `value` and hands it to `send` when it differs from the last one. `send` returns
+    /// whether it went out; when it did not, the next call sends again.
+    pub fn publish(&mut self, value: T, send: impl FnOnce(T) -> bool) {
+        if self.last == Some(value) {
+            return;
+        }
+
+        self.last = send(value).then_some(value);
+    }
+}
+
+/// What the PD pass last sent to the monitor page.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub(crate) struct PdCache {
+    pub target_volts: Cached<Value>,
+    pub limit_amps: Cached<Value>,
+    pub selected_pdo: Cached<SrcPdo>,
+}
+
+impl PdCache {
+    pub const fn new() -> Self {
+        Self {
+            target_volts: Cached::new(),
+            limit_amps: Cached::new(),
+            selected_pdo: Cached::new(),
+        }
+    }
+}
@@ -0,0 +1,58 @@
+//! Readings for a serial plotter on the console.
+//!
+//! `plot` starts a stream of plain `<volts> <amps> <watts>` lines, the filtered readings as the
+//! screen shows them, at a fixed rate and without timestamps, which the Arduino IDE's Serial
+//! Plotter and the likes draw as they come with nothing to set up. The command answers with a
+//! `V I P` header instead of `OK`, which those take for the names of the traces. Other console
+//! output still goes out in between; the plotters skip lines that are not numbers. `plot off` stops
+//! the stream, which is not kept over a restart.
+
+use core::ops::RangeInclusive;
+
+use embassy_time::{Duration, Ticker};
+
+use crate::{
+    console,
+    shared::{PLOT_MUTEX, POWER_INFO_MUTEX},
+    units::fixed,
+};
+
+/// Rates `plot` takes, in lines per second.
+pub(crate) const PLOT_RATES_HZ: RangeInclusive<u8> = 1..=50;
+pub(crate) const PLOT_DEFAULT_HZ: u8 = 10;
+
+/// How often the task looks for a change of the rate while it is not plotting.
+const IDLE_INTERVAL: Duration = Duration::from_millis(100);
+
+fn period(hz: u8) -> Duration {
+    Duration::from_millis(1_000 / hz as u64)
+}
+
+pub(crate) async fn task() {
+    let mut rate = None;
+    let mut ticker = Ticker::every(IDLE_INTERVAL);
+
+    loop {
+        ticker.next().await;
+
+        // A new rate starts a new ticker, so the lines keep an even pace from the first one.
+        let requested = *PLOT_MUTEX.lock().await;
+        if requested != rate {
+            rate = requested;
+            ticker = Ticker::every(rate.map_or(IDLE_INTERVAL, period));
+            continue;
+        }
+
+        if rate.is_none() {
+            continue;
+        }
+
+        let power = *POWER_INFO_MUTEX.lock().await;
+        console::println(format_args!(
+            "{} {} {}",
+            fixed(power.volts, 3, 0),
+            fixed(power.amps, 3, 0),
+            fixed(power.watts, 3, 0)
+        ));
+    }
+}
@@ -0,0 +1,139 @@
+//! Where each INA226 sits and what shunt it measures across.
+//!
+//! Every board has a default per channel in `bsp.rs`; `ina226` on the console stores an override
+//! in the settings page for boards with other address straps or shunts, see `monitor_settings.rs`.
+//! At boot the configured address is tried first and then the rest of the sixteen the A0 and A1
+//! straps can select, so a board strapped differently still finds its monitor.
+//!
+//! [`MonitorLink`] follows the chip at run time. A sense board that is unplugged fails every read;
+//! after [`LOST_AFTER`] such passes the readings count as gone and are shown as dashes. The chip
+//! comes back unconfigured, so the loop then tries to configure it again now and then rather than
+//! read it, and the readings resume once that works.
+
+use core::ops::RangeInclusive;
+
+/// Every address the INA226 straps can select.
+pub(crate) const INA226_ADDRESSES: RangeInclusive<u8> = 0x40..=0x4f;
+
+/// Passes in a row without a complete reading before the power monitor counts as gone.
+pub(crate) const LOST_AFTER: u8 = 3;
+
+/// Accepted shunt values, from 0.1 mΩ to 1 Ω.
+pub(crate) const SHUNT_MICRO_OHMS: RangeInclusive<u32> = 100..=1_000_000;
+/// Accepted full-scale currents.
+pub(crate) const MAX_MILLIAMPS: RangeInclusive<i32> = 100..=20_000;
+
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) struct PowerMonitorConfig {
+    pub address: u8,
+    pub shunt_micro_ohms: u32,
+    /// The full-scale current the chip is calibrated for, which sets the current resolution.
+    pub max_milliamps: i32,
+}
+
+impl PowerMonitorConfig {
+    pub fn shunt_ohms(&self) -> f64 {
+        self.shunt_micro_ohms as f64 / 1_000_000.0
+    }
+
+    pub fn max_amps(&self) -> f64 {
+        self.max_milliamps as f64 / 1_000.0
+    }
+
+    /// The shunt voltage register value of `milliamps` through the shunt, 2.5 µV per LSB, for the
+    /// alert limit, see `ina226_alert.rs`.
+    pub fn shunt_limit(&self, milliamps: i32) -> u16 {
+        let lsbs = milliamps.max(0) as u64 * self.shunt_micro_ohms as u64 / 2_500;
+
+        lsbs.min(i16::MAX as u64) as u16
+    }
+
+    pub fn is_valid(&self) -> bool {
+        INA226_ADDRESSES.contains(&self.address)
+            && SHUNT_MICRO_OHMS.contains(&self.shunt_micro_ohms)
+            && MAX_MILLIAMPS.contains(&self.max_milliamps)
+    }
+
+    pub fn to_bytes(self) -> [u8; 8] {
+        let mut buf = [0u8; 8];
+
+        buf[0] = self.address;
+        buf[1..5].copy_from_slice(&self.shunt_micro_ohms.to_le_bytes());
+        buf[5..7].copy_from_slice(&(self.max_milliamps as u16).to_le_bytes());
+
+        buf
+    }
+
+    /// `None` for a record out of the accepted ranges.
+    pub fn from_bytes(buf: &[u8; 8]) -> Option<Self> {
+        let config = Self {
+            address: buf[0],
+            shunt_micro_ohms: u32::from_le_bytes([buf[1], buf[2], buf[3], buf[4]]),
+            max_milliamps: u16::from_le_bytes([buf[5], buf[6]]) as i32,
+        };
+
+        config.is_valid().then_some(config)
+    }
+}
+
+/// The addresses to probe, `preferred` first, skipping the one `taken` by another channel.
+pub(crate) fn probe_order(preferred: u8, taken: Option<u8>) -> impl Iterator<Item = u8> {
+    core::iter::once(preferred)
+        .chain(INA226_ADDRESSES.filter(move |address| *address != preferred))
+        .filter(move |address| Some(*address) != taken)
+}
+
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) enum LinkEvent {
+    Lost,
+    Back,
+}
+
+/// Whether the power monitor answers, from the outcome of each pass.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub(crate) struct MonitorLink {
+    up: bool,
+    failures: u8,
+}
+
+impl MonitorLink {
+    /// `up` is whether the chip could be configured at boot.
+    pub const fn new(up: bool) -> Self {
+        Self { up, failures: 0 }
+    }
+
+    /// Whether the chip is read; while it is not, nothing it measured can be relied on.
+    pub fn is_up(&self) -> bool {
+        self.up
+    }
+
+    /// Counts a pass that read everything or not, with [`LinkEvent::Lost`] when it made
+    /// [`LOST_AFTER`] failed ones in a row.
+    pub fn update(&mut self, complete: bool) -> Option<LinkEvent> {
+        if !self.up {
+            return None;
+        }
+
+        if complete {
+            self.failures = 0;
+            return None;
+        }
+
+        self.failures += 1;
+        if self.failures < LOST_AFTER {
+            return None;
+        }
+
+        self.up = false;
+        self.failures = 0;
+        Some(LinkEvent::Lost)
+    }
+
+    /// Marks the chip as configured again, with [`LinkEvent::Back`] if it was gone.
+    pub fn reconnected(&mut self) -> Option<LinkEvent> {
+        let was_up = core::mem::replace(&mut self.up, true);
+        self.failures = 0;
+
+        (!was_up).then_some(LinkEvent::Back)
+    }
+}
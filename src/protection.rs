@@ -0,0 +1,464 @@
+//! Under- and over-voltage protection with recovery hysteresis.
+//!
+//! A limit trips the output once the voltage crosses `trip` and turns it back on once the voltage
+//! is back past `recover`, e.g. UVP tripping at 4.5 V and recovering at 4.8 V. The gap keeps a
+//! sagging source from switching the output on and off with every reading. `OutputController`
+//! drives the output from the events of [`VoltageGuard`].
+//!
+//! The INA226 reads the output, after the switch (see `rails.rs`), so it trips on that, but with
+//! the output off it reads nothing of the source. Recovery waits for VBUS ahead of the switch
+//! instead, on builds that sample it (`vbus-sense`); on the others a voltage trip does not recover
+//! and the output is turned on again by hand.
+//!
+//! With the short test on, the output is not switched straight on but ramped in: the FET sits on a
+//! timer channel (see `pwm_timer.rs`) and is driven at each duty of [`SHORT_TEST_RAMP`] in turn,
+//! with a reading of the bus at every step, see [`ramp_fault`]. The duty bounds the average current
+//! of each step, so a short shows as a collapsed bus, or a current over the OCP, at a fraction of
+//! what the switch fully on would let through, and the ramp stops there. Only a ramp that held up
+//! to its last step switches the output fully on.
+//!
+//! On builds with an output sense input, [`OutputSense`] compares the driver output read back from
+//! it with what the output was told to do, to catch a failed driver or a solder bridge.
+//!
+//! A source that runs hot may renegotiate to a lower current. [`OcpDerate`] then lowers an OCP
+//! above the new contract to it, so the source's own protection does not trip first, and puts the
+//! user's threshold back once the contract grows again. An OCP set by hand in between becomes the
+//! user's threshold.
+//!
+//! [`SoftFuse`] counts over-current trips. A load that trips the OCP [`FuseLimit::trips`] times
+//! within [`FuseLimit::window_secs`] is taken as faulty: the fuse blows and the output stays off
+//! until `fuse reset` on the console, however often the trip is reset with the buttons. The limit
+//! and whether the fuse is blown are kept in a record of the settings page, so a restart does not
+//! mend it either.
+
+use embassy_time::Duration;
+#[cfg(not(feature = "mock-time"))]
+use embassy_time::Instant;
+use heapless::Deque;
+
+#[cfg(feature = "mock-time")]
+use crate::mock_time::Instant;
+use crate::{
+    types::capped_ocp,
+    units::{self, Value, ZERO},
+};
+
+/// Share of the voltage a step of the short test ramp should reach, in percent, the bus has to
+/// hold. A discharged input capacitor pulls it down briefly; a short holds it down at every step.
+pub(crate) const SHORT_TEST_MIN_PERCENT: i32 = 70;
+
+/// Duty of each step of the short test ramp, in percent of the switch fully on.
+pub(crate) const SHORT_TEST_RAMP: [u8; 5] = [5, 10, 20, 40, 70];
+
+/// Passes of the measurement loop the sensed output may disagree with the commanded one before it
+/// counts as a fault, which leaves the driver time to switch.
+pub(crate) const SENSE_MISMATCH_PASSES: u8 = 3;
+
+/// A trip and recovery threshold. A trip of 0 turns the limit off; a recovery of 0, or one on the
+/// wrong side of the trip, recovers at the trip threshold.
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) struct VoltageLimit {
+    pub trip: Value,
+    pub recover: Value,
+}
+
+impl VoltageLimit {
+    pub const fn off() -> Self {
+        Self {
+            trip: ZERO,
+            recover: ZERO,
+        }
+    }
+
+    pub fn is_off(&self) -> bool {
+        self.trip == ZERO
+    }
+}
+
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) enum VoltageFault {
+    Under,
+    Over,
+}
+
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) enum GuardEvent {
+    /// Turn the output off.
+    Trip(VoltageFault),
+    /// The voltage is back; turn the output on again.
+    Recover(VoltageFault),
+}
+
+/// Remembers which limit turned the output off, until it recovers or is released.
+pub(crate) struct VoltageGuard {
+    held: Option<VoltageFault>,
+}
+
+impl VoltageGuard {
+    pub const fn new() -> Self {
+        Self { held: None }
+    }
+
+    /// Checks the output reading `volts` against the limits, and a held trip against the `source`
+    /// voltage ahead of the switch. Only an enabled output trips, and only a tripped one recovers,
+    /// not before there is a source reading; a limit turned off while it holds the output recovers
+    /// at once.
+    pub fn update(
+        &mut self,
+        volts: Value,
+        source: Option<Value>,
+        enabled: bool,
+        uvp: &VoltageLimit,
+        ovp: &VoltageLimit,
+    ) -> Option<GuardEvent> {
+        let fault = match self.held {
+            None if !enabled => return None,
+            None if !uvp.is_off() && volts < uvp.trip => VoltageFault::Under,
+            None if !ovp.is_off() && volts > ovp.trip => VoltageFault::Over,
+            None => return None,
+            Some(fault) => {
+                let recovered = match fault {
+                    VoltageFault::Under => {
+                        uvp.is_off() || source.is_some_and(|volts| volts >= under_recovery(uvp))
+                    }
+                    VoltageFault::Over => {
+                        ovp.is_off() || source.is_some_and(|volts| volts <= over_recovery(ovp))
+                    }
+                };
+
+                if !recovered {
+                    return None;
+                }
+
+                self.held = None;
+                return Some(GuardEvent::Recover(fault));
+            }
+        };
+
+        self.held = Some(fault);
+        Some(GuardEvent::Trip(fault))
+    }
+
+    /// Forgets a trip, so a later recovery does not turn the output back on.
+    pub fn release(&mut self) {
+        self.held = None;
+    }
+
+    pub fn held(&self) -> Option<VoltageFault> {
+        self.held
+    }
+}
+
+fn under_recovery(limit: &VoltageLimit) -> Value {
+    if limit.recover > limit.trip {
+        limit.recover
+    } else {
+        limit.trip
+    }
+}
+
+fn over_recovery(limit: &VoltageLimit) -> Value {
+    if limit.recover > ZERO && limit.recover < limit.trip {
+        limit.recover
+    } else {
+        limit.trip
+    }
+}
+
+/// What stopped the short test ramp.
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) enum RampFault {
+    /// The bus fell below [`SHORT_TEST_MIN_PERCENT`] of what the step should reach.
+    Collapsed,
+    /// The average current of the step went over the OCP.
+    OverCurrent,
+}
+
+/// Checks the readings of the short test ramp at `duty` percent. The INA226 averages the PWM, so
+/// the bus of a resistive load reads the duty's share of the contract voltage and a capacitive
+/// load holds it higher; only a short stays down near zero. Without a contract there is nothing to
+/// compare the bus against, and an OCP of zero checks no current.
+pub(crate) fn ramp_fault(
+    duty: u8,
+    volts: Value,
+    amps: Value,
+    target: Value,
+    ocp: Value,
+) -> Option<RampFault> {
+    let expected = units::percent(target, duty as i32);
+
+    if target > ZERO && volts < units::percent(expected, SHORT_TEST_MIN_PERCENT) {
+        return Some(RampFault::Collapsed);
+    }
+
+    if ocp > ZERO && amps > ocp {
+        return Some(RampFault::OverCurrent);
+    }
+
+    None
+}
+
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) enum DerateEvent {
+    /// No OCP was set, and the contract current stands in for it.
+    Capped(Value),
+    /// The contract fell below the user's OCP, which went down with it.
+    Derated(Value),
+    /// The contract grew again, and the OCP went back up, to the user's value or as far as the
+    /// contract allows.
+    Restored(Value),
+}
+
+impl DerateEvent {
+    /// The OCP to use from now on.
+    pub fn ocp(&self) -> Value {
+        match self {
+            DerateEvent::Capped(ocp) | DerateEvent::Derated(ocp) | DerateEvent::Restored(ocp) => {
+                *ocp
+            }
+        }
+    }
+}
+
+/// An OCP held below what the user set.
+#[derive(PartialEq, Clone, Copy, Debug)]
+struct Derated {
+    user: Value,
+    applied: Value,
+}
+
+/// Follows the OCP of a channel through contract changes.
+pub(crate) struct OcpDerate {
+    derated: Option<Derated>,
+}
+
+impl OcpDerate {
+    pub const fn new() -> Self {
+        Self { derated: None }
+    }
+
+    /// The event for a new contract of `contract_amps` with `ocp` in use, `None` when the OCP
+    /// stays. Without a contract nothing changes.
+    pub fn update(&mut self, ocp: Value, contract_amps: Value) -> Option<DerateEvent> {
+        if contract_amps <= ZERO {
+            return None;
+        }
+
+        let user = match self.derated {
+            Some(derated) if derated.applied == ocp => derated.user,
+            // Set by hand since the last contract.
+            _ => ocp,
+        };
+
+        let applied = capped_ocp(user, contract_amps);
+        self.derated = (applied != user).then_some(Derated { user, applied });
+
+        if applied == ocp {
+            None
+        } else if user <= ZERO {
+            Some(DerateEvent::Capped(applied))
+        } else if applied < ocp {
+            Some(DerateEvent::Derated(applied))
+        } else {
+            Some(DerateEvent::Restored(applied))
+        }
+    }
+
+    /// Whether `ocp` is a threshold the user set, held lower by the contract.
+    pub fn is_derated(&self, ocp: Value) -> bool {
+        self.derated
+            .is_some_and(|derated| derated.user > ZERO && derated.applied == ocp)
+    }
+}
+
+/// Most trips the soft fuse can be set to blow after.
+pub(crate) const FUSE_MAX_TRIPS: u8 = 8;
+
+/// When over-current trips blow the soft fuse.
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) struct FuseLimit {
+    /// Trips that blow the fuse, zero for no fuse.
+    pub trips: u8,
+    /// How recent they have to be.
+    pub window_secs: u16,
+}
+
+impl FuseLimit {
+    pub const fn off() -> Self {
+        Self {
+            trips: 0,
+            window_secs: 0,
+        }
+    }
+
+    /// Three trips within a minute.
+    pub const fn default() -> Self {
+        Self {
+            trips: 3,
+            window_secs: 60,
+        }
+    }
+
+    pub fn is_off(&self) -> bool {
+        self.trips == 0
+    }
+
+    pub fn to_bytes(self) -> [u8; 8] {
+        let mut buf = [0u8; 8];
+
+        buf[0] = self.trips;
+        buf[1..3].copy_from_slice(&self.window_secs.to_le_bytes());
+
+        buf
+    }
+
+    /// `None` for a record with more trips than the fuse counts.
+    pub fn from_bytes(buf: &[u8; 8]) -> Option<Self> {
+        let limit = Self {
+            trips: buf[0],
+            window_secs: u16::from_le_bytes([buf[1], buf[2]]),
+        };
+
+        (limit.trips <= FUSE_MAX_TRIPS).then_some(limit)
+    }
+}
+
+/// Latches the output off after too many over-current trips, see [`FuseLimit`].
+pub(crate) struct SoftFuse {
+    /// When the latest trips were, oldest first.
+    trips: Deque<Instant, { FUSE_MAX_TRIPS as usize }>,
+    blown: bool,
+}
+
+impl SoftFuse {
+    pub const fn new() -> Self {
+        Self {
+            trips: Deque::new(),
+            blown: false,
+        }
+    }
+
+    pub fn is_blown(&self) -> bool {
+        self.blown
+    }
+
+    /// Counts an over-current trip at `now`, and returns whether it blew the fuse.
+    pub fn trip(&mut self, now: Instant, limit: &FuseLimit) -> bool {
+        if limit.is_off() {
+            return false;
+        }
+
+        let window = Duration::from_secs(limit.window_secs as u64);
+        while self.trips.front().is_some_and(|at| now - *at > window) {
+            self.trips.pop_front();
+        }
+
+        if self.trips.is_full() {
+            self.trips.pop_front();
+        }
+        self.trips.push_back(now).ok();
+
+        if !self.blown && self.trips.len() >= limit.trips as usize {
+            self.blown = true;
+            return true;
+        }
+
+        false
+    }
+
+    /// Blows the fuse without a trip, for one that was blown before a restart.
+    pub fn blow(&mut self) {
+        self.blown = true;
+    }
+
+    /// Mends the fuse and forgets the trips counted so far.
+    pub fn reset(&mut self) {
+        self.trips.clear();
+        self.blown = false;
+    }
+}
+
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) enum SenseHealth {
+    Ok,
+    /// Commanded off, but the driver output stays on.
+    StuckOn,
+    /// Commanded on, but the driver output stays off.
+    StuckOff,
+}
+
+impl SenseHealth {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SenseHealth::Ok => "ok",
+            SenseHealth::StuckOn => "stuck on",
+            SenseHealth::StuckOff => "stuck off",
+        }
+    }
+}
+
+/// The last comparison of [`OutputSense`], for the diagnostics page.
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) struct SenseReport {
+    pub commanded: bool,
+    pub sensed: bool,
+    pub health: SenseHealth,
+    /// Mismatches that lasted [`SENSE_MISMATCH_PASSES`] since boot.
+    pub faults: u16,
+}
+
+/// Compares the sensed output with the commanded one on every pass.
+pub(crate) struct OutputSense {
+    report: Option<SenseReport>,
+    mismatches: u8,
+}
+
+impl OutputSense {
+    pub const fn new() -> Self {
+        Self {
+            report: None,
+            mismatches: 0,
+        }
+    }
+
+    /// Takes a reading and returns the health once a mismatch has lasted
+    /// [`SENSE_MISMATCH_PASSES`]; a lasting mismatch is returned only once, a state that agrees
+    /// again makes the output healthy again.
+    pub fn update(&mut self, commanded: bool, sensed: bool) -> Option<SenseHealth> {
+        let last = self.report.unwrap_or(SenseReport {
+            commanded,
+            sensed,
+            health: SenseHealth::Ok,
+            faults: 0,
+        });
+
+        self.mismatches = if commanded == sensed {
+            0
+        } else {
+            self.mismatches.saturating_add(1)
+        };
+
+        let health = match (self.mismatches >= SENSE_MISMATCH_PASSES, sensed) {
+            (false, _) if commanded == sensed => SenseHealth::Ok,
+            // Still within the grace passes.
+            (false, _) => last.health,
+            (true, true) => SenseHealth::StuckOn,
+            (true, false) => SenseHealth::StuckOff,
+        };
+        let failed = health != SenseHealth::Ok && health != last.health;
+
+        self.report = Some(SenseReport {
+            commanded,
+            sensed,
+            health,
+            faults: last.faults.saturating_add(failed as u16),
+        });
+
+        failed.then_some(health)
+    }
+
+    /// `None` before the first reading.
+    pub fn report(&self) -> Option<SenseReport> {
+        self.report
+    }
+}
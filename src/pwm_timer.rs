@@ -0,0 +1,48 @@
+//! The backlight timer, shared with the output switch on FET builds.
+//!
+//! The output pin is channel 1 of the timer whose channel 3 dims the backlight, on every board, so
+//! both take their channel of the one [`BacklightPwm`] kept here. The FET is on at full duty and
+//! off at none, as from a plain pin; only the short test ramps it in between, see `protection.rs`.
+//! The channels run at the backlight's frequency, which the FET follows easily.
+
+use core::{cell::RefCell, convert::Infallible};
+
+use embassy_stm32::timer::Channel;
+use embassy_sync::blocking_mutex::{raw::CriticalSectionRawMutex, Mutex};
+use embedded_hal::pwm::{ErrorType, SetDutyCycle};
+
+use crate::types::BacklightPwm;
+
+static PWM: Mutex<CriticalSectionRawMutex, RefCell<Option<BacklightPwm>>> =
+    Mutex::new(RefCell::new(None));
+
+/// Keeps the timer, with the channels in use enabled, for [`PwmChannel`]. Call it once, right after
+/// the timer is set up.
+pub(crate) fn init(pwm: BacklightPwm) {
+    PWM.lock(|cell| *cell.borrow_mut() = Some(pwm));
+}
+
+/// One channel of the shared timer.
+pub(crate) struct PwmChannel(pub Channel);
+
+impl ErrorType for PwmChannel {
+    type Error = Infallible;
+}
+
+impl SetDutyCycle for PwmChannel {
+    fn max_duty_cycle(&self) -> u16 {
+        PWM.lock(|cell| cell.borrow().as_ref().map(|pwm| pwm.get_max_duty()))
+            .unwrap_or(1)
+    }
+
+    /// Does nothing before [`init`].
+    fn set_duty_cycle(&mut self, duty: u16) -> Result<(), Self::Error> {
+        PWM.lock(|cell| {
+            if let Some(pwm) = cell.borrow_mut().as_mut() {
+                pwm.set_duty(self.0, duty);
+            }
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,94 @@
+//! The source side of VBUS, ahead of the output switch, next to the output voltage.
+//!
+//! The INA226 measures the output after the switch and the shunt, so a low reading alone does not
+//! tell a lossy cable from a switch that does not fully turn on. Boards with a divider from VBUS,
+//! ahead of the switch, to an ADC pin (`vbus-sense`, see `bsp.rs`) sample it with the other analog
+//! lines in `line_monitor.rs`. With the contract voltage that splits the loss in two: from the
+//! source's regulation point to the board, which is the cable, its connectors and the source's own
+//! output impedance, and across the switch and the shunt.
+//!
+//! The drop across the switch times the current is what the pass element turns into heat. A
+//! [`SwitchWatch`] warns once it goes past the limit set with `switch limit` on the console, before
+//! the board temperature the thermal task reads catches up with a FET that is not fully on.
+
+use crate::units::{self, Value, ZERO};
+
+/// The dissipation warned about until `switch limit` sets another, about what a small FET without
+/// a heatsink sheds on the board's copper.
+pub(crate) const SWITCH_LIMIT_DEFAULT: Value = units::from_milli(1_000);
+/// The highest limit `switch limit` takes, in watts.
+pub(crate) const SWITCH_LIMIT_MAX: u32 = 20;
+/// A warning re-arms once the dissipation is back under this share of the limit.
+pub(crate) const SWITCH_REARM_PERCENT: i32 = 80;
+
+/// A resistor divider from a voltage down to an ADC pin.
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) struct Divider {
+    /// From the measured voltage to the pin.
+    pub top_ohms: u32,
+    /// From the pin to ground.
+    pub bottom_ohms: u32,
+}
+
+impl Divider {
+    /// The voltage on the top of the divider for `pin_mv` on the pin.
+    pub const fn input_millivolts(&self, pin_mv: u32) -> u32 {
+        (pin_mv as u64 * (self.top_ohms + self.bottom_ohms) as u64 / self.bottom_ohms as u64) as u32
+    }
+}
+
+/// The voltages along the path from the source to the output.
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) struct Rails {
+    /// What the PD contract regulates to at the source.
+    pub contract: Value,
+    /// VBUS on the board, ahead of the switch.
+    pub source: Value,
+    /// The output, as the INA226 reads it.
+    pub load: Value,
+}
+
+impl Rails {
+    /// Lost on the way from the source to the board.
+    pub fn cable_drop(&self) -> Value {
+        self.contract - self.source
+    }
+
+    /// Lost across the output switch and the shunt.
+    pub fn switch_drop(&self) -> Value {
+        self.source - self.load
+    }
+
+    /// Dissipated in the switch and the shunt at `amps`, zero when the reading puts the output
+    /// above VBUS.
+    pub fn switch_watts(&self, amps: Value) -> Value {
+        units::mul(self.switch_drop(), amps).max(ZERO)
+    }
+}
+
+/// Raises a warning when the switch dissipation goes past its limit, once per excursion.
+pub(crate) struct SwitchWatch {
+    over: bool,
+}
+
+impl SwitchWatch {
+    pub const fn new() -> Self {
+        Self { over: false }
+    }
+
+    /// Whether `watts` newly went past `limit`; a zero limit turns the warning off.
+    pub fn check(&mut self, watts: Value, limit: Value) -> bool {
+        if limit <= ZERO {
+            self.over = false;
+            return false;
+        }
+
+        if self.over {
+            self.over = watts >= units::percent(limit, SWITCH_REARM_PERCENT);
+            return false;
+        }
+
+        self.over = watts > limit;
+        self.over
+    }
+}
@@ -0,0 +1,76 @@
+use crate::{
+    remote::{self, RemoteError},
+    shared::{PDO_MUTEX, POWER_INFO_MUTEX, REMOTE_MUTEX, STATUS_INFO_MUTEX},
+    types::pdo_volts,
+    units,
+};
+
+// 16-bit registers, read-only unless noted.
+
+/// Bus voltage in mV.
+pub(crate) const REG_VOLTS: u8 = 0x00;
+/// Current in mA, two's complement.
+pub(crate) const REG_AMPS: u8 = 0x01;
+/// Power in 10 mW units.
+pub(crate) const REG_WATTS: u8 = 0x02;
+/// Bit 0: output enabled, bit 1: remote controlled.
+pub(crate) const REG_STATUS: u8 = 0x03;
+/// Negotiated contract voltage in mV.
+pub(crate) const REG_TARGET_VOLTS: u8 = 0x04;
+/// Negotiated contract current in mA.
+pub(crate) const REG_LIMIT_AMPS: u8 = 0x05;
+/// Output enable, read/write.
+pub(crate) const REG_OUTPUT: u8 = 0x10;
+/// Selected PDO in volts, read/write.
+pub(crate) const REG_PDO: u8 = 0x11;
+
+pub(crate) const REG_COUNT: usize = 0x12;
+
+pub(crate) const STATUS_OUTPUT: u16 = 1 << 0;
+pub(crate) const STATUS_REMOTE: u16 = 1 << 1;
+
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) enum RegisterError {
+    IllegalAddress,
+    IllegalValue,
+    Remote(RemoteError),
+}
+
+/// Takes a snapshot of the whole register map.
+pub(crate) async fn read_registers(regs: &mut [u16; REG_COUNT]) {
+    let power = *POWER_INFO_MUTEX.lock().await;
+    let status = *STATUS_INFO_MUTEX.lock().await;
+    let remote = *REMOTE_MUTEX.lock().await;
+    let pdo = *PDO_MUTEX.lock().await;
+
+    regs.fill(0);
+
+    regs[REG_VOLTS as usize] = units::milli(power.volts) as u16;
+    regs[REG_AMPS as usize] = units::milli(power.amps) as i16 as u16;
+    regs[REG_WATTS as usize] = (units::milli(power.watts) / 10) as u16;
+    regs[REG_STATUS as usize] =
+        if status.output { STATUS_OUTPUT } else { 0 } | if remote { STATUS_REMOTE } else { 0 };
+    regs[REG_TARGET_VOLTS as usize] = units::milli(status.target_volts) as u16;
+    regs[REG_LIMIT_AMPS as usize] = units::milli(status.limit_amps) as u16;
+    regs[REG_OUTPUT as usize] = status.output as u16;
+    regs[REG_PDO as usize] = (units::milli(pdo_volts(pdo)) / 1000) as u16;
+}
+
+/// Applies a write to one of the control registers.
+pub(crate) async fn write_register(addr: u8, value: u16) -> Result<(), RegisterError> {
+    match addr {
+        REG_OUTPUT => match value {
+            0 => remote::request_output(false).await,
+            1 => remote::request_output(true).await,
+            _ => return Err(RegisterError::IllegalValue),
+        },
+        REG_PDO => {
+            remote::request_pdo(value)
+                .await
+                .map_err(RegisterError::Remote)?;
+        }
+        _ => return Err(RegisterError::IllegalAddress),
+    }
+
+    Ok(())
+}
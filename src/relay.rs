@@ -0,0 +1,108 @@
+//! Sequencing of a relay that replaces the output FET, on builds with `relay-output`.
+//!
+//! Some builds switch the load with a relay for galvanic isolation. Its contacts close
+//! [`RelayTiming::pull_in`] after the coil is energized and open [`RelayTiming::release`] after it
+//! lets go, so the output only counts as switched once they have moved. Breaking a load current is
+//! what wears the contacts, so an off asked for by the buttons or the console waits up to
+//! [`RelayTiming::quiet_wait`] for the current to fall to [`RelayTiming::quiet_amps`], e.g. in the
+//! gap of a pulsed load. Trips open the relay at once.
+
+use embassy_time::Duration;
+#[cfg(not(feature = "mock-time"))]
+use embassy_time::Instant;
+
+#[cfg(feature = "mock-time")]
+use crate::mock_time::Instant;
+use crate::units::{self, Value};
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub(crate) struct RelayTiming {
+    /// From energizing the coil to closed contacts, bounce included.
+    pub pull_in: Duration,
+    /// From releasing the coil to open contacts.
+    pub release: Duration,
+    /// Current at or below which the contacts may open without wear.
+    pub quiet_amps: Value,
+    /// Longest wait for that current before opening anyway.
+    pub quiet_wait: Duration,
+}
+
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) enum Contacts {
+    Open,
+    Closing,
+    Closed,
+    /// Includes the wait for a low current before the coil lets go.
+    Opening,
+}
+
+pub(crate) struct RelaySequencer {
+    timing: RelayTiming,
+    coil: bool,
+    changed_at: Instant,
+    /// When a deferred open gives up waiting for a low current.
+    open_by: Option<Instant>,
+}
+
+impl RelaySequencer {
+    pub const fn new(timing: RelayTiming) -> Self {
+        Self {
+            timing,
+            coil: false,
+            changed_at: Instant::MIN,
+            open_by: None,
+        }
+    }
+
+    pub fn coil(&self) -> bool {
+        self.coil
+    }
+
+    /// Energizes or releases the coil at once, dropping a deferred open.
+    pub fn switch(&mut self, now: Instant, on: bool) {
+        self.open_by = None;
+
+        if self.coil != on {
+            self.coil = on;
+            self.changed_at = now;
+        }
+    }
+
+    /// Releases the coil once the current is low enough, see [`update`](Self::update).
+    pub fn open_quietly(&mut self, now: Instant) {
+        if self.coil && self.open_by.is_none() {
+            self.open_by = Some(now + self.timing.quiet_wait);
+        }
+    }
+
+    /// Takes the load current of this pass, `None` when it could not be read, and releases the
+    /// coil for a deferred open when the current is low or the wait is over. Returns whether it
+    /// did.
+    pub fn update(&mut self, now: Instant, amps: Option<Value>) -> bool {
+        let Some(open_by) = self.open_by else {
+            return false;
+        };
+
+        let quiet = amps
+            .is_some_and(|amps| units::milli(amps).abs() <= units::milli(self.timing.quiet_amps));
+
+        if !quiet && now < open_by {
+            return false;
+        }
+
+        self.switch(now, false);
+        true
+    }
+
+    pub fn contacts(&self, now: Instant) -> Contacts {
+        let moving = now - self.changed_at;
+
+        match self.coil {
+            true if self.open_by.is_some() => Contacts::Opening,
+            true if moving < self.timing.pull_in => Contacts::Closing,
+            true => Contacts::Closed,
+            false if moving < self.timing.release => Contacts::Opening,
+            false => Contacts::Open,
+        }
+    }
+}
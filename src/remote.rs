@@ -0,0 +1,67 @@
+use husb238::SrcPdo;
+
+use crate::{
+    shared::{get_available_voltages, select_pdo, OUTPUT_PUBSUB, REMOTE_MUTEX},
+    types::{Channel, ControlSource, OutputRequest, PdRequest},
+};
+
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) enum RemoteError {
+    UnknownPdo,
+    PdoNotOffered,
+}
+
+impl RemoteError {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RemoteError::UnknownPdo => "unknown pdo",
+            RemoteError::PdoNotOffered => "pdo not offered by source",
+        }
+    }
+}
+
+pub(crate) fn pdo_from_volts(volts: u16) -> Option<SrcPdo> {
+    match volts {
+        5 => Some(SrcPdo::_5v),
+        9 => Some(SrcPdo::_9v),
+        12 => Some(SrcPdo::_12v),
+        15 => Some(SrcPdo::_15v),
+        18 => Some(SrcPdo::_18v),
+        20 => Some(SrcPdo::_20v),
+        _ => None,
+    }
+}
+
+/// Queues an output change on behalf of a remote interface.
+///
+/// The request goes through the same checks in `OutputController` as a button press. Remote
+/// interfaces switch the first channel.
+pub(crate) async fn request_output(enabled: bool) {
+    *REMOTE_MUTEX.lock().await = true;
+
+    OUTPUT_PUBSUB
+        .immediate_publisher()
+        .publish_immediate(OutputRequest {
+            enabled,
+            source: ControlSource::Remote,
+            channel: Channel::A,
+        });
+}
+
+/// Selects a new PDO on behalf of a remote interface.
+pub(crate) async fn request_pdo(volts: u16) -> Result<SrcPdo, RemoteError> {
+    let pdo = pdo_from_volts(volts).ok_or(RemoteError::UnknownPdo)?;
+
+    if !get_available_voltages().await.contains(&pdo) {
+        return Err(RemoteError::PdoNotOffered);
+    }
+
+    *REMOTE_MUTEX.lock().await = true;
+    select_pdo(PdRequest {
+        pdo,
+        source: ControlSource::Remote,
+    })
+    .await;
+
+    Ok(pdo)
+}
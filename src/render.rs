@@ -0,0 +1,152 @@
+//! Render commands from the tasks that feed the screen to the display task.
+//!
+//! Drawing a page or even a few digits keeps the SPI bus busy for milliseconds, which the
+//! measurement loop used to wait out on every reading, so a slow panel clock slowed down the I2C
+//! polling with it. Producers now queue a [`RenderCmd`] and move on; the display task drains the
+//! queue into a [`Pending`] set, where a burst of readings collapses into the latest of each, and
+//! draws that in one go.
+
+use husb238::SrcPdo;
+
+#[cfg(target_os = "none")]
+use crate::{
+    log::{debug, Module},
+    shared::{RENDER_CHANNEL, SELECTED_CHANNEL_MUTEX},
+    types::Channel,
+};
+use crate::{
+    schedule::Action,
+    types::{PowerInfo, WifiState},
+    units::Value,
+    utilization::Utilization,
+    watts::WattsSource,
+};
+
+/// Commands the queue holds before producers start dropping them.
+pub(crate) const RENDER_QUEUE_LEN: usize = 32;
+
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) enum RenderCmd {
+    /// Bring the panel up again and draw the page, e.g. after an SPI clock change.
+    Init,
+    /// A reading, `None` while it cannot be taken.
+    Volts(Option<Value>),
+    Amps(Option<Value>),
+    Watts(Option<Value>),
+    WattsSource(WattsSource),
+    Average(Option<PowerInfo>),
+    /// RMS of the current over the last second, see `rms.rs`.
+    Rms(Option<Value>),
+    Utilization(Option<Utilization>, bool),
+    TargetVolts(Value),
+    LimitAmps(Value),
+    SelectedPdo(SrcPdo),
+    Output(bool),
+    /// An over-current trip waits for a reset, see `output_controller.rs`.
+    Tripped(bool),
+    Remote(bool),
+    Wifi(WifiState),
+    Schedule(Option<Action>),
+}
+
+/// What the queue asked for since the last drawing, the latest value of each kind.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub(crate) struct Pending {
+    pub init: bool,
+    pub volts: Option<Option<Value>>,
+    pub amps: Option<Option<Value>>,
+    pub watts: Option<Option<Value>>,
+    pub watts_source: Option<WattsSource>,
+    pub average: Option<Option<PowerInfo>>,
+    pub rms: Option<Option<Value>>,
+    pub utilization: Option<(Option<Utilization>, bool)>,
+    pub target_volts: Option<Value>,
+    pub limit_amps: Option<Value>,
+    pub selected_pdo: Option<SrcPdo>,
+    pub output: Option<bool>,
+    pub tripped: Option<bool>,
+    pub remote: Option<bool>,
+    pub wifi: Option<WifiState>,
+    pub schedule: Option<Option<Action>>,
+}
+
+impl Pending {
+    pub const fn new() -> Self {
+        Self {
+            init: false,
+            volts: None,
+            amps: None,
+            watts: None,
+            watts_source: None,
+            average: None,
+            rms: None,
+            utilization: None,
+            target_volts: None,
+            limit_amps: None,
+            selected_pdo: None,
+            output: None,
+            tripped: None,
+            remote: None,
+            wifi: None,
+            schedule: None,
+        }
+    }
+
+    pub fn push(&mut self, cmd: RenderCmd) {
+        match cmd {
+            RenderCmd::Init => self.init = true,
+            RenderCmd::Volts(volts) => self.volts = Some(volts),
+            RenderCmd::Amps(amps) => self.amps = Some(amps),
+            RenderCmd::Watts(watts) => self.watts = Some(watts),
+            RenderCmd::WattsSource(source) => self.watts_source = Some(source),
+            RenderCmd::Average(average) => self.average = Some(average),
+            RenderCmd::Rms(rms) => self.rms = Some(rms),
+            RenderCmd::Utilization(utilization, warning) => {
+                self.utilization = Some((utilization, warning))
+            }
+            RenderCmd::TargetVolts(volts) => self.target_volts = Some(volts),
+            RenderCmd::LimitAmps(amps) => self.limit_amps = Some(amps),
+            RenderCmd::SelectedPdo(pdo) => self.selected_pdo = Some(pdo),
+            RenderCmd::Output(output) => self.output = Some(output),
+            RenderCmd::Tripped(tripped) => self.tripped = Some(tripped),
+            RenderCmd::Remote(remote) => self.remote = Some(remote),
+            RenderCmd::Wifi(wifi) => self.wifi = Some(wifi),
+            RenderCmd::Schedule(schedule) => self.schedule = Some(schedule),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == Self::new()
+    }
+
+    /// Moves the watts, the interval average, the RMS and the utilization over to `to`, where they
+    /// replace older values, see `render_budget.rs`.
+    pub fn move_low_priority(&mut self, to: &mut Pending) {
+        to.watts = self.watts.take().or(to.watts);
+        to.average = self.average.take().or(to.average);
+        to.rms = self.rms.take().or(to.rms);
+        to.utilization = self.utilization.take().or(to.utilization);
+    }
+}
+
+/// Queues `cmd` for the display task without waiting. Readings and status go out again on every
+/// pass of the measurement loop, so one dropped on a full queue is soon replaced. Returns whether
+/// it was queued.
+#[cfg(target_os = "none")]
+pub(crate) fn send(cmd: RenderCmd) -> bool {
+    let queued = RENDER_CHANNEL.try_send(cmd).is_ok();
+    if !queued {
+        debug!(target: Module::Display, "render queue full, dropped {:?}", cmd);
+    }
+
+    queued
+}
+
+/// Queues a reading or the output state of `channel`, which the monitor page only shows while
+/// that channel is selected.
+#[cfg(target_os = "none")]
+pub(crate) async fn send_channel(channel: Channel, cmd: RenderCmd) {
+    if *SELECTED_CHANNEL_MUTEX.lock().await == channel {
+        send(cmd);
+    }
+}
@@ -0,0 +1,98 @@
+//! Slower updates of the small print while drawing takes too long.
+//!
+//! A slow SPI clock, a panel that keeps falling back to a lower one or a page full of changing
+//! digits can make a drawing take longer than the display tick. Queued commands then pile up and
+//! the volts and amps, which matter most when the load changes, lag with everything else. The
+//! display task times every drawing against [`FLUSH_BUDGET`]; after [`DEGRADE_AFTER`] slow ones
+//! in a row, [`RenderBudget`] holds the watts, the interval average, the RMS and the utilization
+//! back and lets their latest values through once every [`DEGRADED_INTERVAL`], while the readings
+//! and the status still go out on every pass. [`RECOVER_AFTER`] drawings within the budget in a
+//! row bring the normal rate back. The longer run to recover keeps it from flapping, as the
+//! drawings get faster as soon as the small print slows down.
+
+use embassy_time::Duration;
+#[cfg(not(feature = "mock-time"))]
+use embassy_time::Instant;
+
+#[cfg(feature = "mock-time")]
+use crate::mock_time::Instant;
+use crate::render::Pending;
+
+/// Longest a drawing may take, the display tick.
+pub(crate) const FLUSH_BUDGET: Duration = Duration::from_millis(20);
+/// Drawings over the budget in a row before the small print slows down.
+pub(crate) const DEGRADE_AFTER: u8 = 5;
+/// Drawings within the budget in a row before it speeds up again.
+pub(crate) const RECOVER_AFTER: u8 = 50;
+/// How often the small print is drawn while slowed down.
+pub(crate) const DEGRADED_INTERVAL: Duration = Duration::from_millis(500);
+
+pub(crate) struct RenderBudget {
+    degraded: bool,
+    /// Drawings in a row over the budget, or within it while degraded.
+    run: u8,
+    /// The small print held back, the latest of each.
+    held: Pending,
+    /// When the held small print was last let through.
+    released: Option<Instant>,
+}
+
+impl RenderBudget {
+    pub const fn new() -> Self {
+        Self {
+            degraded: false,
+            run: 0,
+            held: Pending::new(),
+            released: None,
+        }
+    }
+
+    pub fn is_degraded(&self) -> bool {
+        self.degraded
+    }
+
+    /// Takes the small print out of `pending` while degraded and puts what is held back in once
+    /// its interval has passed at `now`. Out of the degraded mode, what is still held goes out at
+    /// once.
+    pub fn defer(&mut self, now: Instant, pending: &mut Pending) {
+        if self.degraded {
+            pending.move_low_priority(&mut self.held);
+
+            let due = self
+                .released
+                .is_none_or(|released| now - released >= DEGRADED_INTERVAL);
+            if !due {
+                return;
+            }
+        }
+
+        if !self.held.is_empty() {
+            self.held.move_low_priority(pending);
+            self.released = Some(now);
+        }
+    }
+
+    /// Counts a drawing that took `elapsed`, and returns the new mode when it changes: `true` when
+    /// the small print slows down, `false` when it is back to normal.
+    pub fn record(&mut self, elapsed: Duration) -> Option<bool> {
+        // While degraded a slow drawing breaks the run towards recovery, and the other way round.
+        if (elapsed > FLUSH_BUDGET) == self.degraded {
+            self.run = 0;
+            return None;
+        }
+
+        self.run += 1;
+        let limit = match self.degraded {
+            true => RECOVER_AFTER,
+            false => DEGRADE_AFTER,
+        };
+        if self.run < limit {
+            return None;
+        }
+
+        self.degraded = !self.degraded;
+        self.run = 0;
+
+        Some(self.degraded)
+    }
+}
@@ -0,0 +1,105 @@
+//! Recorded readings played back in place of the power monitor.
+//!
+//! Builds with the `replay` feature feed the main loop from a recording compiled into the image
+//! instead of the INA226, so the filters, the protection and the screen see a captured workload
+//! the same way on every run; the simulator plays one with `--replay <FILE>`. A recording is text
+//! with one reading per line, `<ms>,<mV>,<mA>,<mW>`: the time since the start of the recording and
+//! the three quantities, an empty field for one that failed to read. Lines that do not parse, such
+//! as a header or `#` comments, are skipped.
+//!
+//! Each reading is held until the next one is due. After the last one the recording starts over,
+//! so a short capture can run for as long as the test needs.
+
+use crate::{measure::Reading, units};
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub(crate) struct Sample {
+    /// Milliseconds since the start of the recording.
+    pub at_ms: u64,
+    pub reading: Reading,
+}
+
+/// One line of a recording, `None` for one that is not a reading.
+pub(crate) fn parse_line(line: &str) -> Option<Sample> {
+    let mut fields = line.trim().split(',');
+
+    let at_ms = fields.next()?.trim().parse::<u64>().ok()?;
+    let mut quantity = || -> Option<Option<_>> {
+        match fields.next()?.trim() {
+            "" => Some(None),
+            milli => milli
+                .parse::<i32>()
+                .ok()
+                .map(|milli| Some(units::from_milli(milli))),
+        }
+    };
+
+    let reading = Reading {
+        volts: quantity()?,
+        amps: quantity()?,
+        watts: quantity()?,
+    };
+
+    fields.next().is_none().then_some(Sample { at_ms, reading })
+}
+
+pub(crate) struct Replay {
+    recording: &'static str,
+    /// The lines not played yet.
+    rest: &'static str,
+    current: Option<Reading>,
+    next: Option<Sample>,
+    /// When the recording started playing, on the caller's clock.
+    started_ms: Option<u64>,
+}
+
+impl Replay {
+    pub fn new(recording: &'static str) -> Self {
+        let mut replay = Self {
+            recording,
+            rest: recording,
+            current: None,
+            next: None,
+            started_ms: None,
+        };
+        replay.next = replay.take();
+
+        replay
+    }
+
+    /// The reading due at `now_ms`, nothing read before the first one or from an empty recording.
+    pub fn reading(&mut self, now_ms: u64) -> Reading {
+        // Over once the last reading was played.
+        if self.next.is_none() && self.current.is_some() {
+            self.rest = self.recording;
+            self.next = self.take();
+            self.started_ms = None;
+        }
+
+        let started_ms = *self.started_ms.get_or_insert(now_ms);
+
+        while let Some(next) = self.next {
+            if next.at_ms > now_ms.saturating_sub(started_ms) {
+                break;
+            }
+
+            self.current = Some(next.reading);
+            self.next = self.take();
+        }
+
+        self.current.unwrap_or(Reading::MISSING)
+    }
+
+    fn take(&mut self) -> Option<Sample> {
+        while !self.rest.is_empty() {
+            let (line, rest) = self.rest.split_once('\n').unwrap_or((self.rest, ""));
+            self.rest = rest;
+
+            if let Some(sample) = parse_line(line) {
+                return Some(sample);
+            }
+        }
+
+        None
+    }
+}
@@ -0,0 +1,237 @@
+//! Why the MCU last came out of reset.
+//!
+//! The reset flags in RCC_CSR sit at the same bits on the G0 and the L4. They are read and cleared
+//! once at boot with [`take`], so the next reset starts from a clean set. A power-on and a
+//! brown-out set the same flag; a marker word in RAM that is not zeroed at boot tells them apart,
+//! as it only survives when the supply dipped rather than went away. A long enough dip loses the
+//! marker and counts as a power-on; a short cycle may keep it and count as a brown-out, which errs
+//! on the side of leaving the output off.
+//!
+//! After a watchdog or brown-out reset the firmware was not shut down in an orderly way, so the
+//! output is left off whatever the boot policy says, see [`ResetCause::is_abnormal`].
+//!
+//! [`ResetCounts`] keeps the last cause and how often each one happened in RTC backup registers,
+//! for units that reboot under load. They survive every reset, and power cycles too with a backup
+//! battery on VBAT; without one a power cycle starts the counts over.
+
+#[cfg(target_os = "none")]
+use core::{mem::MaybeUninit, ptr::addr_of_mut};
+
+#[cfg(target_os = "none")]
+use embassy_stm32::pac::RCC;
+
+#[cfg(target_os = "none")]
+use crate::{
+    log::{warn, Module},
+    shared::{RESET_COUNTS_MUTEX, RTC_MUTEX},
+};
+
+#[cfg(target_os = "none")]
+const LOG_MODULE: Module = Module::System;
+
+#[cfg(target_os = "none")]
+const MARKER_MAGIC: u32 = 0x5044_5752; // "PDWR"
+
+/// In the upper half of the first backup register, above the last cause.
+const COUNTS_MAGIC: u32 = 0x5052; // "PR"
+
+/// Backup registers used, the first one holding the magic and the last cause.
+pub(crate) const COUNT_REGISTERS: usize = 3;
+
+const LPWRRSTF: u32 = 1 << 31;
+const WWDGRSTF: u32 = 1 << 30;
+const IWDGRSTF: u32 = 1 << 29;
+const SFTRSTF: u32 = 1 << 28;
+/// PWRRSTF on the G0, BORRSTF on the L4, both set by a power-on as well.
+const BORRSTF: u32 = 1 << 27;
+const PINRSTF: u32 = 1 << 26;
+const OBLRSTF: u32 = 1 << 25;
+
+/// Every cause, in the order the counts are kept and shown.
+pub(crate) const RESET_CAUSES: [ResetCause; 7] = [
+    ResetCause::PowerOn,
+    ResetCause::BrownOut,
+    ResetCause::Pin,
+    ResetCause::Software,
+    ResetCause::Watchdog,
+    ResetCause::LowPower,
+    ResetCause::OptionBytes,
+];
+
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) enum ResetCause {
+    PowerOn,
+    BrownOut,
+    /// The NRST pin, e.g. the reset button or a debug probe.
+    Pin,
+    /// A software reset, after a firmware update, the bootloader command or a crash.
+    Software,
+    /// The independent or window watchdog.
+    Watchdog,
+    /// An illegal entry to a low-power mode.
+    LowPower,
+    /// Option bytes were reloaded.
+    OptionBytes,
+}
+
+impl ResetCause {
+    /// Decodes the RCC_CSR flags. The pin flag is set along with any other cause, as every reset
+    /// drives NRST low, so it only counts when it is alone. `warm` is whether RAM kept the marker
+    /// from the previous run.
+    pub fn from_flags(csr: u32, warm: bool) -> Self {
+        if csr & (IWDGRSTF | WWDGRSTF) != 0 {
+            ResetCause::Watchdog
+        } else if csr & LPWRRSTF != 0 {
+            ResetCause::LowPower
+        } else if csr & BORRSTF != 0 {
+            if warm {
+                ResetCause::BrownOut
+            } else {
+                ResetCause::PowerOn
+            }
+        } else if csr & SFTRSTF != 0 {
+            ResetCause::Software
+        } else if csr & OBLRSTF != 0 {
+            ResetCause::OptionBytes
+        } else if csr & PINRSTF != 0 {
+            ResetCause::Pin
+        } else {
+            // No flag at all, e.g. after the flags were cleared and the debugger reset the core.
+            ResetCause::Software
+        }
+    }
+
+    /// Whether the previous run ended without a chance to turn the output off.
+    pub fn is_abnormal(&self) -> bool {
+        matches!(self, ResetCause::Watchdog | ResetCause::BrownOut)
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ResetCause::PowerOn => "power-on",
+            ResetCause::BrownOut => "brown-out",
+            ResetCause::Pin => "pin",
+            ResetCause::Software => "software",
+            ResetCause::Watchdog => "watchdog",
+            ResetCause::LowPower => "low-power",
+            ResetCause::OptionBytes => "option load",
+        }
+    }
+
+    /// At most four characters, for the two-column count table.
+    pub fn abbr(&self) -> &'static str {
+        match self {
+            ResetCause::PowerOn => "por",
+            ResetCause::BrownOut => "bor",
+            ResetCause::Pin => "pin",
+            ResetCause::Software => "soft",
+            ResetCause::Watchdog => "wdg",
+            ResetCause::LowPower => "lpwr",
+            ResetCause::OptionBytes => "obl",
+        }
+    }
+
+    fn index(&self) -> usize {
+        RESET_CAUSES.iter().position(|cause| cause == self).unwrap()
+    }
+}
+
+/// The last reset cause and a count per cause, each saturating at 255.
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) struct ResetCounts {
+    pub last: Option<ResetCause>,
+    counts: [u8; RESET_CAUSES.len()],
+}
+
+impl ResetCounts {
+    pub const fn empty() -> Self {
+        Self {
+            last: None,
+            counts: [0; RESET_CAUSES.len()],
+        }
+    }
+
+    /// Counts `cause` and makes it the last one.
+    pub fn record(&mut self, cause: ResetCause) {
+        let count = &mut self.counts[cause.index()];
+        *count = count.saturating_add(1);
+
+        self.last = Some(cause);
+    }
+
+    pub fn count(&self, cause: ResetCause) -> u8 {
+        self.counts[cause.index()]
+    }
+
+    pub fn to_registers(self) -> [u32; COUNT_REGISTERS] {
+        let last = self.last.map_or(0, |cause| cause.index() as u32 + 1);
+        let mut counts = [0u8; 8];
+        counts[..RESET_CAUSES.len()].copy_from_slice(&self.counts);
+
+        [
+            (COUNTS_MAGIC << 16) | last,
+            u32::from_le_bytes([counts[0], counts[1], counts[2], counts[3]]),
+            u32::from_le_bytes([counts[4], counts[5], counts[6], counts[7]]),
+        ]
+    }
+
+    /// Blank or foreign registers read as no resets counted.
+    pub fn from_registers(registers: &[u32; COUNT_REGISTERS]) -> Self {
+        let last = (registers[0] & 0xffff) as usize;
+
+        if registers[0] >> 16 != COUNTS_MAGIC || last > RESET_CAUSES.len() {
+            return Self::empty();
+        }
+
+        let mut bytes = [0u8; 8];
+        bytes[..4].copy_from_slice(&registers[1].to_le_bytes());
+        bytes[4..].copy_from_slice(&registers[2].to_le_bytes());
+
+        let mut counts = [0u8; RESET_CAUSES.len()];
+        counts.copy_from_slice(&bytes[..RESET_CAUSES.len()]);
+
+        Self {
+            last: last.checked_sub(1).map(|i| RESET_CAUSES[i]),
+            counts,
+        }
+    }
+}
+
+/// Not zeroed by the reset handler, like the crash record.
+#[cfg(target_os = "none")]
+#[link_section = ".uninit.RESET"]
+static mut MARKER: MaybeUninit<u32> = MaybeUninit::uninit();
+
+/// Reads and clears the reset flags and sets the marker for the next boot. Call once, early.
+#[cfg(target_os = "none")]
+pub(crate) fn take() -> ResetCause {
+    let csr = RCC.csr().read().0;
+    RCC.csr().modify(|w| w.set_rmvf(true));
+
+    let marker = unsafe { &mut *addr_of_mut!(MARKER).cast::<u32>() };
+    let warm = *marker == MARKER_MAGIC;
+    *marker = MARKER_MAGIC;
+
+    ResetCause::from_flags(csr, warm)
+}
+
+/// Counts `cause` in the backup registers of the RTC that `clock::restore` keeps, and leaves the
+/// counts in `RESET_COUNTS_MUTEX` for the diagnostics and About pages.
+#[cfg(target_os = "none")]
+pub(crate) async fn record(cause: ResetCause) {
+    let rtc = RTC_MUTEX.lock().await;
+    let Some(rtc) = rtc.as_ref() else {
+        warn!("no rtc, reset not counted");
+        return;
+    };
+
+    let registers = core::array::from_fn(|i| rtc.read_backup_register(i).unwrap_or(0));
+    let mut counts = ResetCounts::from_registers(&registers);
+    counts.record(cause);
+
+    for (i, value) in counts.to_registers().into_iter().enumerate() {
+        rtc.write_backup_register(i, value);
+    }
+
+    *RESET_COUNTS_MUTEX.lock().await = counts;
+}
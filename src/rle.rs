@@ -0,0 +1,113 @@
+//! Run-length coding of the 1-bit glyph bitmaps in `font.rs`.
+//!
+//! A bitmap is read row by row, most significant bit first, as alternating runs of clear and set
+//! pixels, starting with a clear run that may be empty. Each run is stored as nibbles, high nibble
+//! first: 15 adds 15 and continues the run, 0 to 14 adds that much and ends it. A trailing clear
+//! run is left out, and an odd nibble count is padded with a 0. The decoded size is not stored; it
+//! comes with the font.
+//!
+//! Glyphs shrink to about half, as most of a cell is background. `font-tool` encodes them with
+//! [`encode`]; the firmware decodes one glyph at a time right before it goes to the panel.
+
+/// Decodes `data` into `out`, which is sized for the whole bitmap. Pixels the data does not cover
+/// are left clear.
+pub fn decode(data: &[u8], out: &mut [u8]) {
+    out.fill(0);
+
+    let total = out.len() * 8;
+    let mut pos = 0;
+    let mut run = 0;
+    let mut set = false;
+
+    for nibble in nibbles(data) {
+        run += nibble as usize;
+        if nibble == 15 {
+            continue;
+        }
+
+        if set {
+            for i in pos..(pos + run).min(total) {
+                out[i / 8] |= 0x80 >> (i % 8);
+            }
+        }
+
+        pos += run;
+        run = 0;
+        set = !set;
+
+        if pos >= total {
+            break;
+        }
+    }
+}
+
+/// Whether pixel `index` is set, without decoding the bitmap into a buffer.
+pub fn bit(data: &[u8], index: usize) -> bool {
+    let mut pos = 0;
+    let mut run = 0;
+    let mut set = false;
+
+    for nibble in nibbles(data) {
+        run += nibble as usize;
+        if nibble == 15 {
+            continue;
+        }
+
+        pos += run;
+        run = 0;
+
+        if index < pos {
+            return set;
+        }
+
+        set = !set;
+    }
+
+    false
+}
+
+/// Encodes `bitmap`, handing the bytes to `push` in order.
+pub fn encode(bitmap: &[u8], mut push: impl FnMut(u8)) {
+    let mut high = None;
+    let mut set = false;
+    let mut run = 0;
+
+    for i in 0..bitmap.len() * 8 {
+        if (bitmap[i / 8] & (0x80 >> (i % 8)) != 0) == set {
+            run += 1;
+            continue;
+        }
+
+        write_run(run, &mut high, &mut push);
+        set = !set;
+        run = 1;
+    }
+
+    if set {
+        write_run(run, &mut high, &mut push);
+    }
+
+    if let Some(high) = high {
+        push(high << 4);
+    }
+}
+
+fn write_run(mut run: usize, high: &mut Option<u8>, push: &mut impl FnMut(u8)) {
+    loop {
+        let nibble = run.min(15) as u8;
+
+        match high.take() {
+            Some(h) => push(h << 4 | nibble),
+            None => *high = Some(nibble),
+        }
+
+        if nibble < 15 {
+            return;
+        }
+        run -= 15;
+    }
+}
+
+fn nibbles(data: &[u8]) -> impl Iterator<Item = u8> + '_ {
+    data.iter().flat_map(|b| [b >> 4, b & 0x0f])
+}
@@ -0,0 +1,107 @@
+//! True RMS of the current over a rolling window.
+//!
+//! The average current of a pulsed load, a radio transmitting in bursts or a motor under PWM,
+//! understates how warm it makes the cable, the shunt and the output switch, which heat with the
+//! square of the current. The measurement loop feeds every raw current reading in, weighted by
+//! the time since the previous one, and [`RollingRms`] keeps the mean square of the last second.
+//! The monitor page shows its root next to the amps, below their interval average, and `status`
+//! prints it.
+//!
+//! The window moves in tenths, so it spans between 0.9 and 1 s. The sums are in milliamps
+//! squared, the same in both representations of `units.rs`.
+
+use embassy_time::Duration;
+#[cfg(not(feature = "mock-time"))]
+use embassy_time::Instant;
+
+#[cfg(feature = "mock-time")]
+use crate::mock_time::Instant;
+use crate::units::{self, Value};
+
+pub(crate) const RMS_WINDOW: Duration = Duration::from_secs(1);
+
+/// Slots the window moves by.
+const SLOTS: usize = 10;
+const SLOT: Duration = Duration::from_millis(RMS_WINDOW.as_millis() / SLOTS as u64);
+
+#[derive(Clone, Copy)]
+struct Slot {
+    /// Milliamps squared times microseconds.
+    squares: u64,
+    micros: u64,
+}
+
+impl Slot {
+    const EMPTY: Self = Self {
+        squares: 0,
+        micros: 0,
+    };
+}
+
+pub(crate) struct RollingRms {
+    slots: [Slot; SLOTS],
+    /// The slot taking the readings, and when it began.
+    current: usize,
+    started: Option<Instant>,
+    last: Option<Instant>,
+}
+
+impl RollingRms {
+    pub const fn new() -> Self {
+        Self {
+            slots: [Slot::EMPTY; SLOTS],
+            current: 0,
+            started: None,
+            last: None,
+        }
+    }
+
+    /// Drops the window, e.g. after the loop was suspended; the next reading starts a new one.
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Adds the current read at `now`, which stands for the time since the previous reading, and
+    /// returns the RMS of the window, none before the second reading. A gap of a whole window
+    /// starts over.
+    pub fn update(&mut self, now: Instant, amps: Value) -> Option<Value> {
+        let (Some(mut started), Some(last)) = (self.started, self.last) else {
+            self.started = Some(now);
+            self.last = Some(now);
+            return None;
+        };
+
+        let elapsed = now - last;
+        if elapsed >= RMS_WINDOW {
+            self.reset();
+            return self.update(now, amps);
+        }
+
+        while now - started >= SLOT {
+            started = started + SLOT;
+            self.current = (self.current + 1) % SLOTS;
+            self.slots[self.current] = Slot::EMPTY;
+        }
+
+        let milliamps = units::milli(amps).unsigned_abs() as u64;
+        let slot = &mut self.slots[self.current];
+        slot.squares += milliamps * milliamps * elapsed.as_micros();
+        slot.micros += elapsed.as_micros();
+
+        self.started = Some(started);
+        self.last = Some(now);
+
+        self.rms()
+    }
+
+    /// The RMS of the window, none while it holds no time.
+    pub fn rms(&self) -> Option<Value> {
+        let (squares, micros) = self.slots.iter().fold((0, 0), |(squares, micros), slot| {
+            (squares + slot.squares, micros + slot.micros)
+        });
+
+        let mean = squares.checked_div(micros)?;
+
+        Some(units::from_milli(mean.isqrt() as i32))
+    }
+}
@@ -0,0 +1,122 @@
+//! A daily output schedule: on at a time of day, off a number of hours later.
+//!
+//! Times are UTC like the wall clock, see `clock.rs`. The schedule only switches at its two
+//! times, so the output is not turned on by a boot or a clock change inside the on window, and
+//! can still be switched by hand in between. `scheduler.rs` runs it and `settings.rs` keeps it.
+
+const DAY_SECS: u64 = 86_400;
+
+/// Longest on window; a day or more would overlap the next day's.
+pub(crate) const MAX_HOURS: u8 = 23;
+
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) struct Schedule {
+    /// Minutes past midnight the output goes on.
+    on_at: u16,
+    /// Hours it stays on.
+    hours: u8,
+}
+
+/// A switch of the output at a wall-clock time.
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) struct Action {
+    pub on: bool,
+    /// Seconds since the Unix epoch.
+    pub at: u64,
+}
+
+impl Action {
+    pub fn hour(&self) -> u8 {
+        (self.at % DAY_SECS / 3600) as u8
+    }
+
+    pub fn minute(&self) -> u8 {
+        (self.at % 3600 / 60) as u8
+    }
+}
+
+impl Schedule {
+    /// On at `hour`:`minute` for `hours` hours, none for an invalid time or duration.
+    pub fn new(hour: u8, minute: u8, hours: u8) -> Option<Self> {
+        if hour > 23 || minute > 59 || !(1..=MAX_HOURS).contains(&hours) {
+            return None;
+        }
+
+        Some(Self {
+            on_at: hour as u16 * 60 + minute as u16,
+            hours,
+        })
+    }
+
+    pub fn hour(&self) -> u8 {
+        (self.on_at / 60) as u8
+    }
+
+    pub fn minute(&self) -> u8 {
+        (self.on_at % 60) as u8
+    }
+
+    pub fn hours(&self) -> u8 {
+        self.hours
+    }
+
+    /// The first switch after `now`: off at the end of the window `now` is in, otherwise on at
+    /// the start of the next one.
+    pub fn next(&self, now: u64) -> Action {
+        let start_today = now - now % DAY_SECS + self.on_at as u64 * 60;
+        let duration = self.hours as u64 * 3600;
+
+        for start in [start_today.checked_sub(DAY_SECS), Some(start_today)]
+            .into_iter()
+            .flatten()
+        {
+            if (start..start + duration).contains(&now) {
+                return Action {
+                    on: false,
+                    at: start + duration,
+                };
+            }
+        }
+
+        Action {
+            on: true,
+            at: if now < start_today {
+                start_today
+            } else {
+                start_today + DAY_SECS
+            },
+        }
+    }
+
+    /// The switch due in `(from, to]`, if any; the later one if there are two.
+    pub fn due(&self, from: u64, to: u64) -> Option<Action> {
+        let mut due = None;
+        let mut action = self.next(from);
+
+        while action.at <= to {
+            due = Some(action);
+            action = self.next(action.at);
+        }
+
+        due
+    }
+
+    pub fn to_bytes(self) -> [u8; 8] {
+        let mut buf = [0u8; 8];
+
+        buf[0..2].copy_from_slice(&self.on_at.to_le_bytes());
+        buf[2] = self.hours;
+
+        buf
+    }
+
+    pub fn from_bytes(buf: &[u8; 8]) -> Option<Self> {
+        let on_at = u16::from_le_bytes([buf[0], buf[1]]);
+
+        if on_at >= 24 * 60 {
+            return None;
+        }
+
+        Self::new((on_at / 60) as u8, (on_at % 60) as u8, buf[2])
+    }
+}
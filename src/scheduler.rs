@@ -0,0 +1,81 @@
+//! Runs the daily output schedule, see `schedule.rs`.
+//!
+//! Once a second the task checks whether a switch of the schedule came due since the last check,
+//! and asks the main loop for it like the buttons and the console do. It does nothing while the
+//! clock is not set. A clock set back, or ahead by more than a minute, starts over from the new
+//! time instead of catching up on the switches in between.
+
+use embassy_time::{Duration, Ticker};
+
+use crate::{
+    clock,
+    log::{info, Module},
+    schedule::Schedule,
+    settings::{self, SettingsError, SCHEDULE_RECORD},
+    shared::{NEXT_ACTION_MUTEX, OUTPUT_PUBSUB, SCHEDULE_MUTEX},
+    types::{Channel, ControlSource, OutputRequest},
+};
+
+const LOG_MODULE: Module = Module::Output;
+
+const MAGIC: u32 = 0x5044_5343; // "PDSC"
+
+const INTERVAL: Duration = Duration::from_secs(1);
+
+/// Longest gap between two checks that still fires the switches in it.
+const MAX_CATCH_UP_SECS: u64 = 60;
+
+/// Loads the stored schedule into `SCHEDULE_MUTEX`.
+pub(crate) async fn load() {
+    let schedule =
+        settings::read(SCHEDULE_RECORD, MAGIC).and_then(|buf| Schedule::from_bytes(&buf));
+
+    info!("schedule: {:?}", schedule);
+    *SCHEDULE_MUTEX.lock().await = schedule;
+}
+
+/// Writes `schedule` to flash and makes it the active one; none clears it.
+pub(crate) async fn store(schedule: Option<Schedule>) -> Result<(), SettingsError> {
+    let buf = schedule.map_or([0; settings::BODY_LEN], Schedule::to_bytes);
+
+    settings::write(SCHEDULE_RECORD, MAGIC, &buf).await?;
+
+    *SCHEDULE_MUTEX.lock().await = schedule;
+
+    Ok(())
+}
+
+pub(crate) async fn task() {
+    let output_pub = OUTPUT_PUBSUB.immediate_publisher();
+    let mut ticker = Ticker::every(INTERVAL);
+    let mut last = clock::unix_secs().await;
+
+    loop {
+        ticker.next().await;
+
+        let now = clock::unix_secs().await;
+        let schedule = *SCHEDULE_MUTEX.lock().await;
+
+        if let (Some(schedule), Some(from), Some(to)) = (schedule, last, now) {
+            let due = if to >= from && to - from <= MAX_CATCH_UP_SECS {
+                schedule.due(from, to)
+            } else {
+                None
+            };
+
+            if let Some(action) = due {
+                info!("schedule: output {}", action.on);
+
+                output_pub.publish_immediate(OutputRequest {
+                    enabled: action.on,
+                    source: ControlSource::Schedule,
+                    channel: Channel::A,
+                });
+            }
+        }
+
+        *NEXT_ACTION_MUTEX.lock().await =
+            schedule.zip(now).map(|(schedule, now)| schedule.next(now));
+        last = now;
+    }
+}
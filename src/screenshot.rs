@@ -0,0 +1,185 @@
+//! Screen capture over the console.
+//!
+//! A frame buffer for the whole panel would not fit in RAM, so `Display` records every glyph it
+//! blits and `screenshot` re-renders them line by line. The image is sent as run-length encoded
+//! RGB565 in raster order; runs continue across rows:
+//!
+//! ```text
+//! IMG 320 172 rgb565
+//! 1450xf7be 24x2a69 296xf7be ...
+//! END
+//! ```
+
+use core::fmt::Write;
+
+use embedded_graphics::{pixelcolor::Rgb565, prelude::IntoStorage};
+use heapless::{String, Vec};
+
+use crate::{
+    console,
+    font::Bitmap,
+    shared::{CONSOLE_LINE_LEN, SCREEN_MUTEX},
+};
+
+pub(crate) const SCREEN_WIDTH: u16 = 320;
+pub(crate) const SCREEN_HEIGHT: u16 = 172;
+
+/// Enough for the busiest page (Diagnostics, 120 glyphs) with some headroom.
+const MAX_BLITS: usize = 128;
+
+#[derive(Clone, Copy)]
+struct Blit {
+    x: u16,
+    y: u16,
+    width: u16,
+    bitmap: Bitmap,
+    color: u16,
+    bg_color: u16,
+}
+
+impl Blit {
+    fn covers_row(&self, y: u16) -> bool {
+        y >= self.y && ((y - self.y) as usize) < self.bitmap.size() * 8 / self.width as usize
+    }
+
+    fn pixel(&self, x: u16, y: u16) -> Option<u16> {
+        if x < self.x || x >= self.x + self.width {
+            return None;
+        }
+
+        let idx = (y - self.y) as usize * self.width as usize + (x - self.x) as usize;
+        if idx / 8 >= self.bitmap.size() {
+            return None;
+        }
+
+        if self.bitmap.bit(idx) {
+            Some(self.color)
+        } else {
+            Some(self.bg_color)
+        }
+    }
+}
+
+/// What is currently on the panel: a background fill and the blits drawn over it since.
+#[derive(Clone)]
+pub(crate) struct Screen {
+    background: u16,
+    blits: Vec<Blit, MAX_BLITS>,
+    overflowed: bool,
+}
+
+impl Screen {
+    pub const fn new() -> Self {
+        Self {
+            background: 0,
+            blits: Vec::new(),
+            overflowed: false,
+        }
+    }
+
+    pub fn clear(&mut self, color: Rgb565) {
+        self.background = color.into_storage();
+        self.blits.clear();
+        self.overflowed = false;
+    }
+
+    pub fn blit(
+        &mut self,
+        x: u16,
+        y: u16,
+        width: u16,
+        bitmap: Bitmap,
+        color: Rgb565,
+        bg_color: Rgb565,
+    ) {
+        let blit = Blit {
+            x,
+            y,
+            width,
+            bitmap,
+            color: color.into_storage(),
+            bg_color: bg_color.into_storage(),
+        };
+
+        // Glyphs are redrawn in place, so replacing the previous blit keeps the list short.
+        if let Some(prev) = self
+            .blits
+            .iter_mut()
+            .find(|b| b.x == x && b.y == y && b.width == width)
+        {
+            *prev = blit;
+            return;
+        }
+
+        if self.blits.push(blit).is_err() {
+            self.overflowed = true;
+        }
+    }
+
+    /// Notes a drawing the model cannot hold, such as the scope graph.
+    pub fn untracked(&mut self) {
+        self.overflowed = true;
+    }
+}
+
+/// Streams the current screen to the console.
+pub(crate) async fn capture() {
+    let screen = SCREEN_MUTEX.lock().await.clone();
+
+    if screen.overflowed {
+        console::write_line(format_args!("WARN screen model incomplete")).await;
+    }
+
+    console::write_line(format_args!(
+        "IMG {} {} rgb565",
+        SCREEN_WIDTH, SCREEN_HEIGHT
+    ))
+    .await;
+
+    let mut line: String<CONSOLE_LINE_LEN> = String::new();
+    let mut run: Option<(u16, u32)> = None;
+
+    for y in 0..SCREEN_HEIGHT {
+        let row: Vec<&Blit, MAX_BLITS> = screen.blits.iter().filter(|b| b.covers_row(y)).collect();
+
+        for x in 0..SCREEN_WIDTH {
+            let color = row
+                .iter()
+                .rev()
+                .find_map(|b| b.pixel(x, y))
+                .unwrap_or(screen.background);
+
+            run = match run {
+                Some((prev, count)) if prev == color => Some((prev, count + 1)),
+                Some((prev, count)) => {
+                    push_run(&mut line, prev, count).await;
+                    Some((color, 1))
+                }
+                None => Some((color, 1)),
+            };
+        }
+    }
+
+    if let Some((color, count)) = run {
+        push_run(&mut line, color, count).await;
+    }
+
+    if !line.is_empty() {
+        console::write_line(format_args!("{}", line.trim_end())).await;
+    }
+
+    console::write_line(format_args!("END")).await;
+}
+
+async fn push_run(line: &mut String<CONSOLE_LINE_LEN>, color: u16, count: u32) {
+    let mut token: String<16> = String::new();
+    write!(token, "{}x{:04x} ", count, color).ok();
+
+    // Leave room for the line terminator added by the console.
+    if line.len() + token.len() > CONSOLE_LINE_LEN - 2 {
+        console::write_line(format_args!("{}", line.trim_end())).await;
+        line.clear();
+    }
+
+    line.push_str(&token).ok();
+}
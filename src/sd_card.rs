@@ -0,0 +1,403 @@
+//! CSV log on an SD card sharing the display SPI bus.
+//!
+//! The main loop queues a sample a second and every trip, recovery and output change with
+//! [`record`]; this task writes them to the card as lines laid out by `csv_log.rs`. The queue is
+//! never waited on, so a slow or missing card costs the main loop nothing, only lost records, which
+//! are counted. The SD card page shows the state, and unmounts the card with Down so it can be
+//! pulled safely, or mounts it again with Up. A card that fails is closed and tried again every
+//! [`RETRY_INTERVAL`] as long as it is mounted.
+//!
+//! embedded-sdmmc only drives a blocking SPI device, which cannot wait for the shared bus in the
+//! middle of a card access. So the task first waits until the bus is free, see
+//! [`SdLog::claim_bus`], and then runs the card access to the end without yielding: no other task
+//! gets to run in between, and every card transaction finds the bus free. The display's writes are
+//! chunked (see `spi_bus.rs`), so that wait is one chunk at most, and the card runs at its own clock,
+//! switched in and out around each of its transactions.
+//!
+//! The card's directory entry only learns the new length of a file when it is closed, so the file
+//! is closed and opened again every [`SYNC_INTERVAL`]; a power cut loses at most that much.
+
+use core::fmt::Write as _;
+
+use embassy_futures::select::{select, Either};
+use embassy_stm32::{
+    spi,
+    time::{khz, mhz, Hertz},
+};
+use embassy_sync::{blocking_mutex::raw::CriticalSectionRawMutex, mutex::Mutex};
+use embassy_time::{block_for, Delay, Duration, Instant};
+use embedded_hal::spi::{ErrorKind, ErrorType, Operation, SpiDevice};
+use embedded_sdmmc::{
+    Mode, RawDirectory, RawFile, RawVolume, SdCard, TimeSource, Timestamp, VolumeIdx, VolumeManager,
+};
+use heapless::String;
+use portable_atomic::{AtomicU32, Ordering};
+
+use crate::{
+    calendar::DateTime,
+    clock,
+    csv_log::{self, CardState, CardStatus, LogFile, Record, HEADER},
+    log::{info, warn, Module},
+    shared::{EPOCH_MUTEX, SD_LOG_CHANNEL, SD_LOG_MUTEX, SD_MOUNT_PUBSUB},
+    types::{SdCs, SpiBus},
+};
+
+const LOG_MODULE: Module = Module::Storage;
+
+/// Clock while the card is brought up, as the SD specification requires.
+const INIT_FREQUENCY: Hertz = khz(400);
+/// Clock once the card is up, well within what any card takes over SPI.
+const CARD_FREQUENCY: Hertz = mhz(8);
+
+const RETRY_INTERVAL: Duration = Duration::from_secs(5);
+const SYNC_INTERVAL: Duration = Duration::from_secs(10);
+
+type Card = SdCard<CardSpi, Delay>;
+type Error = embedded_sdmmc::Error<embedded_sdmmc::SdCardError>;
+
+/// Records that did not fit the queue.
+static OVERFLOWS: AtomicU32 = AtomicU32::new(0);
+
+/// Queues `record` for the card, stamped with the time since boot. Never waits.
+pub(crate) fn record(record: Record) {
+    if SD_LOG_CHANNEL
+        .try_send((Instant::now().as_millis(), record))
+        .is_err()
+    {
+        OVERFLOWS.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// The card's chip select on the shared bus, see the module docs for why it may not wait.
+struct CardSpi {
+    bus: &'static Mutex<CriticalSectionRawMutex, SpiBus>,
+    cs: SdCs,
+    frequency: Hertz,
+}
+
+#[derive(Debug, defmt::Format)]
+enum CardSpiError {
+    Spi(spi::Error),
+    /// The bus was taken; the card was accessed without claiming it first.
+    Busy,
+}
+
+impl embedded_hal::spi::Error for CardSpiError {
+    fn kind(&self) -> ErrorKind {
+        ErrorKind::Other
+    }
+}
+
+impl ErrorType for CardSpi {
+    type Error = CardSpiError;
+}
+
+impl SpiDevice for CardSpi {
+    fn transaction(&mut self, operations: &mut [Operation<'_, u8>]) -> Result<(), Self::Error> {
+        let mut bus = self.bus.try_lock().map_err(|_| CardSpiError::Busy)?;
+
+        let display = bus.get_current_config();
+        let mut config = display;
+        config.frequency = self.frequency;
+        bus.set_config(&config).ok();
+
+        self.cs.set_low();
+
+        let result = operations
+            .iter_mut()
+            .try_for_each(|operation| match operation {
+                Operation::Read(words) => bus.blocking_read(words),
+                Operation::Write(words) => bus.blocking_write(words),
+                Operation::Transfer(read, write) => bus.blocking_transfer(read, write),
+                Operation::TransferInPlace(words) => bus.blocking_transfer_in_place(words),
+                Operation::DelayNs(ns) => {
+                    block_for(Duration::from_micros((*ns as u64).div_ceil(1000)));
+                    Ok(())
+                }
+            });
+
+        self.cs.set_high();
+        bus.set_config(&display).ok();
+
+        result.map_err(CardSpiError::Spi)
+    }
+}
+
+/// Dates the files from the wall clock once it is set, otherwise 1980-01-01, the
+/// earliest FAT date.
+struct Clock;
+
+impl TimeSource for Clock {
+    fn get_timestamp(&self) -> Timestamp {
+        let epoch_ms = EPOCH_MUTEX.try_lock().ok().and_then(|epoch| *epoch);
+
+        let Some(epoch_ms) = epoch_ms else {
+            return Timestamp {
+                year_since_1970: 10,
+                zero_indexed_month: 0,
+                zero_indexed_day: 0,
+                hours: 0,
+                minutes: 0,
+                seconds: 0,
+            };
+        };
+
+        let time = DateTime::from_unix((epoch_ms + Instant::now().as_millis()) / 1000);
+
+        Timestamp {
+            year_since_1970: time.year.saturating_sub(1970).clamp(10, 255) as u8,
+            zero_indexed_month: time.month - 1,
+            zero_indexed_day: time.day - 1,
+            hours: time.hour,
+            minutes: time.minute,
+            seconds: time.second,
+        }
+    }
+}
+
+/// Handles of the open volume, its root directory and the log file in it.
+#[derive(Clone, Copy)]
+struct Open {
+    volume: RawVolume,
+    dir: RawDirectory,
+    file: Option<RawFile>,
+}
+
+pub(crate) struct SdLog {
+    bus: &'static Mutex<CriticalSectionRawMutex, SpiBus>,
+    volumes: VolumeManager<Card, Clock>,
+    open: Option<Open>,
+    status: CardStatus,
+    /// Cleared by an unmount from the menu until the next mount.
+    mounted: bool,
+    retry_at: Instant,
+    synced_at: Instant,
+}
+
+impl SdLog {
+    pub fn new(bus: &'static Mutex<CriticalSectionRawMutex, SpiBus>, cs: SdCs) -> Self {
+        let spi = CardSpi {
+            bus,
+            cs,
+            frequency: INIT_FREQUENCY,
+        };
+
+        Self {
+            bus,
+            volumes: VolumeManager::new(SdCard::new(spi, Delay), Clock),
+            open: None,
+            status: CardStatus::default(),
+            mounted: true,
+            retry_at: Instant::MIN,
+            synced_at: Instant::MIN,
+        }
+    }
+
+    pub async fn task(&mut self) {
+        let mut mount_sub = SD_MOUNT_PUBSUB.subscriber().unwrap();
+
+        self.publish().await;
+
+        loop {
+            match select(SD_LOG_CHANNEL.receive(), mount_sub.next_message_pure()).await {
+                Either::First((uptime_ms, record)) => self.write(uptime_ms, record).await,
+                Either::Second(true) => {
+                    self.mounted = true;
+                    self.retry_at = Instant::MIN;
+
+                    if self.status.state == CardState::Unmounted {
+                        self.status.state = CardState::NoCard;
+                    }
+                }
+                Either::Second(false) => {
+                    self.mounted = false;
+
+                    self.claim_bus().await;
+                    self.status.state = match self.close() {
+                        Ok(()) => {
+                            info!("sd card unmounted");
+                            CardState::Unmounted
+                        }
+                        Err(err) => {
+                            warn!("sd card unmount error: {:?}", err);
+                            CardState::Error
+                        }
+                    };
+                }
+            }
+
+            self.publish().await;
+        }
+    }
+
+    async fn write(&mut self, uptime_ms: u64, record: Record) {
+        if !self.mounted || (self.open.is_none() && Instant::now() < self.retry_at) {
+            self.status.dropped += 1;
+            return;
+        }
+
+        let line = csv_log::line(clock::at(uptime_ms).await, &record);
+
+        self.claim_bus().await;
+
+        if self.open.is_none() {
+            match self.mount() {
+                Ok(()) => info!("sd card logging to file {}", self.status.file.index),
+                Err(err) => {
+                    self.fail(CardState::NoCard, err);
+                    return;
+                }
+            }
+        }
+
+        match self.append(line.as_bytes()) {
+            Ok(()) => self.status.state = CardState::Logging,
+            Err(err) => self.fail(CardState::Error, err),
+        }
+    }
+
+    /// Waits until no other device holds the bus. The card access that follows must not yield;
+    /// see the module docs.
+    async fn claim_bus(&self) {
+        drop(self.bus.lock().await);
+    }
+
+    /// Brings the card up and starts a new file after the last one on it.
+    fn mount(&mut self) -> Result<(), Error> {
+        let card = self.volumes.device();
+        card.mark_card_uninit();
+        card.spi(|spi| spi.frequency = INIT_FREQUENCY);
+        card.num_bytes()
+            .map_err(embedded_sdmmc::Error::DeviceError)?;
+        card.spi(|spi| spi.frequency = CARD_FREQUENCY);
+
+        let volume = self.volumes.open_raw_volume(VolumeIdx(0))?;
+        let dir = match self.volumes.open_root_dir(volume) {
+            Ok(dir) => dir,
+            Err(err) => {
+                self.volumes.close_volume(volume).ok();
+                return Err(err);
+            }
+        };
+        self.open = Some(Open {
+            volume,
+            dir,
+            file: None,
+        });
+
+        let mut highest = None;
+        self.volumes.iterate_dir(dir, |entry| {
+            let mut name: String<12> = String::new();
+            write!(name, "{}", entry.name).ok();
+
+            highest = highest.max(csv_log::file_index(&name));
+        })?;
+
+        self.start(LogFile::after(highest))
+    }
+
+    /// Creates the file `file` names, emptying any old one left there after the numbers wrapped,
+    /// and writes the header.
+    fn start(&mut self, file: LogFile) -> Result<(), Error> {
+        let Some(open) = self.open.as_mut() else {
+            return Ok(());
+        };
+
+        let name = csv_log::file_name(file.index);
+        let handle = self.volumes.open_file_in_dir(
+            open.dir,
+            name.as_str(),
+            Mode::ReadWriteCreateOrTruncate,
+        )?;
+        open.file = Some(handle);
+
+        self.volumes.write(handle, HEADER.as_bytes())?;
+
+        self.status.file = LogFile {
+            index: file.index,
+            bytes: HEADER.len() as u32,
+        };
+        self.synced_at = Instant::now();
+
+        Ok(())
+    }
+
+    fn append(&mut self, bytes: &[u8]) -> Result<(), Error> {
+        if !self.status.file.fits(bytes.len()) {
+            let next = self.status.file.next();
+            self.close_file()?;
+            self.start(next)?;
+        }
+
+        let Some(handle) = self.open.and_then(|open| open.file) else {
+            return Ok(());
+        };
+
+        self.volumes.write(handle, bytes)?;
+        self.status.file.bytes += bytes.len() as u32;
+
+        if Instant::now() - self.synced_at >= SYNC_INTERVAL {
+            self.sync()?;
+        }
+
+        Ok(())
+    }
+
+    /// Closes and reopens the file, which writes its length to the directory.
+    fn sync(&mut self) -> Result<(), Error> {
+        self.close_file()?;
+
+        let Some(open) = self.open.as_mut() else {
+            return Ok(());
+        };
+
+        let name = csv_log::file_name(self.status.file.index);
+        open.file = Some(self.volumes.open_file_in_dir(
+            open.dir,
+            name.as_str(),
+            Mode::ReadWriteAppend,
+        )?);
+        self.synced_at = Instant::now();
+
+        Ok(())
+    }
+
+    fn close_file(&mut self) -> Result<(), Error> {
+        match self.open.as_mut().and_then(|open| open.file.take()) {
+            Some(handle) => self.volumes.close_file(handle),
+            None => Ok(()),
+        }
+    }
+
+    /// Closes whatever is open, as far as the card lets it.
+    fn close(&mut self) -> Result<(), Error> {
+        let file = self.close_file();
+
+        let Some(open) = self.open.take() else {
+            return file;
+        };
+
+        let dir = self.volumes.close_dir(open.dir);
+        let volume = self.volumes.close_volume(open.volume);
+
+        file.and(dir).and(volume)
+    }
+
+    fn fail(&mut self, state: CardState, err: Error) {
+        if self.status.state != state {
+            warn!("sd card {:?}: {:?}", state, err);
+        }
+
+        self.close().ok();
+        self.status.state = state;
+        self.status.dropped += 1;
+        self.retry_at = Instant::now() + RETRY_INTERVAL;
+    }
+
+    async fn publish(&self) {
+        let mut status = self.status;
+        status.dropped += OVERFLOWS.load(Ordering::Relaxed);
+
+        *SD_LOG_MUTEX.lock().await = Some(status);
+    }
+}
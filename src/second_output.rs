@@ -0,0 +1,296 @@
+//! The second output channel of `dual-output` builds.
+//!
+//! Boards with two outputs on the one PD contract give the second its own switch pin, INA226 and
+//! over-current threshold, see `bsp.rs`. This task reads its power monitor every [`TICK`], trips
+//! it on over-current or when the bus leaves the UVP and OVP limits, applies the output requests
+//! for it, and feeds the monitor page while it is selected. The filters, averages, logs,
+//! fieldbus, console and schedule stay with the first channel in the main loop.
+
+use embassy_embedded_hal::shared_bus::asynch::i2c::I2cDevice;
+use embassy_stm32::gpio::Output;
+use embassy_sync::{blocking_mutex::raw::CriticalSectionRawMutex, mutex::Mutex};
+use embassy_time::{Duration, Instant, Timer};
+use husb238::SrcPdo;
+use ina226::INA226;
+
+#[cfg(feature = "fixed-point")]
+use crate::ina226_regs::Ina226Registers;
+#[cfg(not(feature = "relay-output"))]
+use crate::output_controller::FetDriver;
+#[cfg(feature = "relay-output")]
+use crate::output_controller::RelayDriver;
+#[cfg(feature = "vbus-sense")]
+use crate::shared::VBUS_MUTEX;
+use crate::{
+    clock, configure_power_monitor, console,
+    fault::{self, Fault},
+    follow_conversion, fuse_settings, init_power_monitor,
+    log::{error, info, warn, Module},
+    output_controller::{OutputController, OutputError, Protection},
+    power_monitor::{LinkEvent, MonitorLink, PowerMonitorConfig},
+    render::{self, RenderCmd},
+    shared::{
+        CONVERSION_MUTEX, FUSE_BLOWN_B_MUTEX, FUSE_LIMIT_MUTEX, OCP_B_MUTEX, OUTPUT_B_MUTEX,
+        OUTPUT_MODE_MUTEX, OUTPUT_PUBSUB, OVP_MUTEX, PDO_MUTEX, SECOND_POWER_MUTEX,
+        STATUS_INFO_MUTEX, TRIPPED_B_MUTEX, UVP_MUTEX,
+    },
+    types::{Channel, PowerInfo, SensorI2cBus},
+    units::ZERO,
+    INA226_RECONNECT_INTERVAL,
+};
+
+const LOG_MODULE: Module = Module::Output;
+
+/// Interval between two passes, and so the longest the channel stays on after the contract
+/// changed under it.
+pub(crate) const TICK: Duration = Duration::from_millis(100);
+
+type SensorI2cDev = I2cDevice<'static, CriticalSectionRawMutex, SensorI2cBus>;
+
+#[cfg(not(feature = "relay-output"))]
+type Driver = FetDriver<Output<'static>>;
+#[cfg(feature = "relay-output")]
+type Driver = RelayDriver<Output<'static>>;
+
+pub(crate) struct SecondOutput {
+    monitor: PowerMonitorConfig,
+    ina226: INA226<SensorI2cDev>,
+    #[cfg(feature = "fixed-point")]
+    regs: Ina226Registers<SensorI2cDev>,
+    output: OutputController<Driver>,
+    /// The PDO the output was switched on at.
+    pdo: SrcPdo,
+}
+
+impl SecondOutput {
+    /// The channel starts off, whatever the boot policy of the first one. `monitor` has the
+    /// address its INA226 was found at.
+    pub fn new(
+        i2c: &'static Mutex<CriticalSectionRawMutex, SensorI2cBus>,
+        pin: Output<'static>,
+        monitor: PowerMonitorConfig,
+    ) -> Self {
+        #[cfg(not(feature = "relay-output"))]
+        let driver = FetDriver::new(pin);
+        #[cfg(feature = "relay-output")]
+        let driver = RelayDriver::new(pin, crate::bsp::RELAY_TIMING);
+
+        Self {
+            monitor,
+            ina226: INA226::new(I2cDevice::new(i2c), monitor.address),
+            #[cfg(feature = "fixed-point")]
+            regs: Ina226Registers::new(I2cDevice::new(i2c), monitor.address, monitor.max_milliamps),
+            output: OutputController::new(driver),
+            pdo: SrcPdo::_5v,
+        }
+    }
+
+    pub async fn task(&mut self) {
+        let mut link = MonitorLink::new(init_power_monitor(&mut self.ina226, &self.monitor).await);
+        if !link.is_up() {
+            error!(
+                "ina226 init error on {}, no measurements",
+                Channel::B.as_str()
+            );
+            fault::report(Fault::PowerMonitor).await;
+        }
+
+        // A fuse blown before the restart stays blown until `fuse reset`.
+        if *FUSE_BLOWN_B_MUTEX.lock().await {
+            self.output.blow_fuse();
+            *TRIPPED_B_MUTEX.lock().await = true;
+            render::send_channel(Channel::B, RenderCmd::Tripped(true)).await;
+        }
+
+        let mut output_sub = OUTPUT_PUBSUB.subscriber().unwrap();
+        let mut reconnect_at = Instant::now() + INA226_RECONNECT_INTERVAL;
+        let mut conversion = *CONVERSION_MUTEX.lock().await;
+
+        loop {
+            self.output.set_mode(*OUTPUT_MODE_MUTEX.lock().await);
+
+            if !link.is_up() && Instant::now() >= reconnect_at {
+                reconnect_at = Instant::now() + INA226_RECONNECT_INTERVAL;
+
+                if configure_power_monitor(&mut self.ina226, &self.monitor).await {
+                    link.reconnected();
+
+                    info!("{} ina226 back", Channel::B.as_str());
+                    console::println(format_args!(
+                        "{} MONITOR BACK {}",
+                        clock::now().await,
+                        Channel::B.as_str()
+                    ));
+                }
+            }
+
+            if link.is_up() {
+                follow_conversion(&mut self.ina226, &mut conversion).await;
+            }
+
+            let power = if link.is_up() {
+                self.read().await
+            } else {
+                None
+            };
+            self.output.poll(power.map(|power| power.amps));
+
+            if link.update(power.is_some()) == Some(LinkEvent::Lost) {
+                warn!("{} ina226 lost", Channel::B.as_str());
+                console::println(format_args!(
+                    "{} MONITOR LOST {}",
+                    clock::now().await,
+                    Channel::B.as_str()
+                ));
+                reconnect_at = Instant::now() + INA226_RECONNECT_INTERVAL;
+            }
+
+            // Nothing may stay on through a renegotiation; the main loop waits a tick for this.
+            let selected = *PDO_MUTEX.lock().await;
+            if self.output.is_enabled() && selected != self.pdo {
+                info!("{} off for the new contract", Channel::B.as_str());
+
+                self.output.set(false);
+                self.show_output().await;
+            }
+
+            let ocp = *OCP_B_MUTEX.lock().await;
+            if let Some(power) = power {
+                let uvp = *UVP_MUTEX.lock().await;
+                let ovp = *OVP_MUTEX.lock().await;
+                // Both channels hang off the same VBUS, see `protection.rs`.
+                #[cfg(feature = "vbus-sense")]
+                let source = *VBUS_MUTEX.lock().await;
+                #[cfg(not(feature = "vbus-sense"))]
+                let source = None;
+
+                if let Some(protection) = self.output.protect(&power, source, ocp, &uvp, &ovp) {
+                    self.report(protection).await;
+                }
+            } else if !link.is_up() && ocp > ZERO {
+                // No OCP without readings.
+                if let Some(protection) = self.output.trip(OutputError::NoMeasurement) {
+                    self.report(protection).await;
+                }
+            }
+
+            self.output.set_fuse(*FUSE_LIMIT_MUTEX.lock().await);
+
+            // Cleared by `fuse reset` on the console.
+            if self.output.is_fuse_blown() && !*FUSE_BLOWN_B_MUTEX.lock().await {
+                info!("{} soft fuse reset", Channel::B.as_str());
+
+                self.output.reset_fuse();
+                *TRIPPED_B_MUTEX.lock().await = false;
+                render::send_channel(Channel::B, RenderCmd::Tripped(false)).await;
+            }
+
+            // Cleared by a long press of Up on the monitor page with this channel selected, which
+            // a blown fuse sets again.
+            if self.output.is_tripped() && !*TRIPPED_B_MUTEX.lock().await {
+                if self.output.reset_trip() {
+                    info!("{} over-current trip reset", Channel::B.as_str());
+
+                    render::send_channel(Channel::B, RenderCmd::Tripped(false)).await;
+                } else {
+                    *TRIPPED_B_MUTEX.lock().await = true;
+                }
+            }
+
+            let req = output_sub
+                .try_next_message_pure()
+                .filter(|req| req.channel == Channel::B);
+            if let Some(req) = req {
+                let status = *STATUS_INFO_MUTEX.lock().await;
+
+                let requested = if req.enabled && !link.is_up() && ocp > ZERO {
+                    Err(OutputError::NoMeasurement)
+                } else {
+                    self.output
+                        .request(req.enabled, req.source, selected, &status)
+                };
+
+                match requested {
+                    Ok(_) => {
+                        info!(
+                            "{} output {} by {:?}",
+                            Channel::B.as_str(),
+                            req.enabled,
+                            req.source
+                        );
+                        self.pdo = selected;
+                    }
+                    Err(err) => warn!("{} output request failed: {:?}", Channel::B.as_str(), err),
+                }
+            }
+
+            *SECOND_POWER_MUTEX.lock().await = power;
+            self.show_output().await;
+
+            // Dashes while it could not be read.
+            let volts = power.map(|power| power.volts);
+            let amps = power.map(|power| power.amps);
+            let watts = power.map(|power| power.watts);
+            render::send_channel(Channel::B, RenderCmd::Volts(volts)).await;
+            render::send_channel(Channel::B, RenderCmd::Amps(amps)).await;
+            render::send_channel(Channel::B, RenderCmd::Watts(watts)).await;
+
+            Timer::after(TICK).await;
+        }
+    }
+
+    /// One reading of the power monitor, `None` when any of it failed.
+    async fn read(&mut self) -> Option<PowerInfo> {
+        #[cfg(not(feature = "fixed-point"))]
+        let power = PowerInfo {
+            volts: self.ina226.bus_voltage_millivolts().await.ok()? / 1000.0,
+            amps: self.ina226.current_amps().await.ok()?.unwrap_or(0.0),
+            watts: self.ina226.power_watts().await.ok()?.unwrap_or(0.0),
+        };
+        #[cfg(feature = "fixed-point")]
+        let power = PowerInfo {
+            volts: self.regs.bus_millivolts().await.ok()?,
+            amps: self.regs.current_milliamps().await.ok()?,
+            watts: self.regs.power_milliwatts().await.ok()?,
+        };
+
+        Some(power)
+    }
+
+    async fn report(&mut self, protection: Protection) {
+        match protection {
+            Protection::Tripped(err) => {
+                warn!("{} tripped: {:?}", Channel::B.as_str(), err);
+                console::println(format_args!(
+                    "{} TRIP {} {}",
+                    clock::now().await,
+                    Channel::B.as_str(),
+                    err.as_str()
+                ));
+
+                if self.output.is_tripped() {
+                    *TRIPPED_B_MUTEX.lock().await = true;
+                    render::send_channel(Channel::B, RenderCmd::Tripped(true)).await;
+                }
+                if self.output.is_fuse_blown() {
+                    fuse_settings::mark_blown(Channel::B).await;
+                }
+            }
+            Protection::Recovered => {
+                info!("{} recovered", Channel::B.as_str());
+                console::println(format_args!(
+                    "{} RECOVER {}",
+                    clock::now().await,
+                    Channel::B.as_str()
+                ));
+            }
+        }
+    }
+
+    async fn show_output(&mut self) {
+        let enabled = self.output.is_enabled();
+
+        *OUTPUT_B_MUTEX.lock().await = enabled;
+        render::send_channel(Channel::B, RenderCmd::Output(enabled)).await;
+    }
+}
@@ -0,0 +1,164 @@
+//! Power-on self-test.
+//!
+//! Each peripheral is probed once at boot and the results are shown on the splash screen, logged
+//! and printed on the console. A failed check is latched as a fault and the firmware carries on
+//! without that part: readings show as errors without the INA226, the PDO list stays empty without
+//! the HUSB238, and without a panel the measurements go out on the console instead.
+//!
+//! The display is wired write-only, with no MISO on its SPI bus, so the ST7789 ID cannot be read
+//! back. Its check is whether the init sequence could be sent.
+//!
+//! While the results are up, `main` waits up to two seconds for the HUSB238's first contract and
+//! adds it below them, with a spinner until it comes in and `PD no contract` when none does.
+
+use embedded_hal_async::i2c::I2c;
+
+use crate::{
+    console,
+    fault::{self, Fault},
+    log::{info, warn, Module},
+    power_monitor,
+    shared::SELFTEST_MUTEX,
+};
+
+const LOG_MODULE: Module = Module::System;
+
+const INA226_REG_MANUFACTURER_ID: u8 = 0xfe;
+const INA226_REG_DIE_ID: u8 = 0xff;
+/// "TI" in ASCII.
+const INA226_MANUFACTURER_ID: u16 = 0x5449;
+/// Upper 12 bits of the die ID register; the low nibble is the die revision.
+const INA226_DEVICE_ID: u16 = 0x226;
+
+const HUSB238_ADDRESS: u8 = 0x08;
+/// PD_STATUS0, readable in every state. The HUSB238 has no ID register.
+const HUSB238_REG_PD_STATUS0: u8 = 0x00;
+
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) enum ProbeError {
+    NoResponse,
+    UnexpectedId,
+}
+
+impl ProbeError {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProbeError::NoResponse => "no response",
+            ProbeError::UnexpectedId => "unexpected id",
+        }
+    }
+}
+
+/// Outcome of each check, kept for the `selftest` console command.
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) struct SelfTest {
+    pub display: Result<(), ProbeError>,
+    pub power_monitor: Result<(), ProbeError>,
+    pub pd_controller: Result<(), ProbeError>,
+}
+
+impl SelfTest {
+    /// The checks in the order they are shown, named by the fault a failure latches.
+    pub fn checks(&self) -> [(Fault, Result<(), ProbeError>); 3] {
+        [
+            (Fault::Display, self.display),
+            (Fault::PowerMonitor, self.power_monitor),
+            (Fault::PdController, self.pd_controller),
+        ]
+    }
+
+    pub fn passed(&self) -> bool {
+        self.checks().iter().all(|(_, result)| result.is_ok())
+    }
+}
+
+/// Reads the manufacturer and die ID registers of the INA226 at `address`.
+pub(crate) async fn probe_power_monitor<I2C: I2c>(
+    i2c: &mut I2C,
+    address: u8,
+) -> Result<(), ProbeError> {
+    let manufacturer = read_u16(i2c, address, INA226_REG_MANUFACTURER_ID).await?;
+    let die = read_u16(i2c, address, INA226_REG_DIE_ID).await?;
+
+    if manufacturer != INA226_MANUFACTURER_ID || die >> 4 != INA226_DEVICE_ID {
+        warn!(
+            target: Module::Measure,
+            "ina226 id mismatch: manufacturer={:#06x} die={:#06x}", manufacturer, die
+        );
+        return Err(ProbeError::UnexpectedId);
+    }
+
+    Ok(())
+}
+
+/// Looks for an INA226 at `preferred`, then at the other strappable addresses except `taken`, and
+/// returns where it answered. The error is that of `preferred` when none did.
+pub(crate) async fn find_power_monitor<I2C: I2c>(
+    i2c: &mut I2C,
+    preferred: u8,
+    taken: Option<u8>,
+) -> Result<u8, ProbeError> {
+    let mut first_err = None;
+
+    for address in power_monitor::probe_order(preferred, taken) {
+        match probe_power_monitor(i2c, address).await {
+            Ok(()) => {
+                if address != preferred {
+                    warn!(
+                        target: Module::Measure,
+                        "ina226 found at {:#04x} instead of {:#04x}", address, preferred
+                    );
+                }
+                return Ok(address);
+            }
+            Err(err) => {
+                first_err.get_or_insert(err);
+            }
+        }
+    }
+
+    Err(first_err.unwrap_or(ProbeError::NoResponse))
+}
+
+/// Checks that the HUSB238 acknowledges a register read.
+pub(crate) async fn probe_pd_controller<I2C: I2c>(i2c: &mut I2C) -> Result<(), ProbeError> {
+    let mut buf = [0u8; 1];
+
+    i2c.write_read(HUSB238_ADDRESS, &[HUSB238_REG_PD_STATUS0], &mut buf)
+        .await
+        .map_err(|_| ProbeError::NoResponse)
+}
+
+/// Logs and prints the results, latches a fault for every failed check and keeps them for the
+/// console.
+pub(crate) async fn report(selftest: SelfTest) {
+    for (peripheral, result) in selftest.checks() {
+        match result {
+            Ok(()) => {
+                info!("selftest {}: ok", peripheral.as_str());
+                console::println(format_args!("SELFTEST {} ok", peripheral.as_str()));
+            }
+            Err(err) => {
+                warn!("selftest {}: {}", peripheral.as_str(), err.as_str());
+                console::println(format_args!(
+                    "SELFTEST {} FAIL {}",
+                    peripheral.as_str(),
+                    err.as_str()
+                ));
+                fault::report(peripheral).await;
+            }
+        }
+    }
+
+    *SELFTEST_MUTEX.lock().await = Some(selftest);
+}
+
+async fn read_u16<I2C: I2c>(i2c: &mut I2C, address: u8, reg: u8) -> Result<u16, ProbeError> {
+    let mut buf = [0u8; 2];
+
+    i2c.write_read(address, &[reg], &mut buf)
+        .await
+        .map_err(|_| ProbeError::NoResponse)?;
+
+    Ok(u16::from_be_bytes(buf))
+}
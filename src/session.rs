@@ -0,0 +1,199 @@
+//! Test sessions, a summary of a run from its start to its stop.
+//!
+//! A session is started and stopped from the Session page of the settings menu, Up and Down, or
+//! with `session start` and `session stop` on the console. While it runs the measurement loop feeds
+//! every raw reading in, and [`Session`] keeps the lowest, mean and highest volts, amps and watts,
+//! the energy drawn, the trips and faults seen and how long it ran. The report stays after the
+//! stop, on the page and for `session report`, until the next start. It is the result of a soak
+//! test for a person to read, next to the history and the SD card log for plotting.
+//!
+//! The report also counts how long the current was above 80 % and 95 % of the OCP in force, from
+//! the raw reading of every pass rather than the filtered or averaged ones, so short peaks count.
+//! A load that spends a lot of time close to the limit is one a repeated test will trip sooner or
+//! later; one that never gets near it can do with a tighter limit.
+
+#[cfg(not(feature = "mock-time"))]
+use embassy_time::Instant;
+
+#[cfg(feature = "mock-time")]
+use crate::mock_time::Instant;
+use crate::{
+    measure::Reading,
+    units::{self, Energy, Value, NO_ENERGY, ZERO},
+};
+
+/// The shares of the OCP whose time above it the report counts, in percent.
+pub(crate) const OCP_BANDS_PERCENT: [i32; 2] = [80, 95];
+
+/// The lowest, highest and mean of one quantity.
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) struct Extent {
+    min: Value,
+    max: Value,
+    /// In thousandths, so the mean is the same in both representations of `units.rs`.
+    sum_milli: i64,
+    count: u32,
+}
+
+impl Extent {
+    pub const fn new() -> Self {
+        Self {
+            min: ZERO,
+            max: ZERO,
+            sum_milli: 0,
+            count: 0,
+        }
+    }
+
+    fn add(&mut self, value: Value) {
+        if self.count == 0 || value < self.min {
+            self.min = value;
+        }
+        if self.count == 0 || value > self.max {
+            self.max = value;
+        }
+
+        self.sum_milli += units::milli(value) as i64;
+        self.count = self.count.saturating_add(1);
+    }
+
+    /// The lowest, the mean and the highest, none before the first reading.
+    pub fn summary(&self) -> Option<(Value, Value, Value)> {
+        let mean = self.sum_milli.checked_div(self.count as i64)?;
+
+        Some((self.min, units::from_milli(mean as i32), self.max))
+    }
+}
+
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) struct Report {
+    pub volts: Extent,
+    pub amps: Extent,
+    pub watts: Extent,
+    pub energy: Energy,
+    /// Output trips and hardware faults.
+    pub faults: u32,
+    /// From the start to the last reading, or to the stop.
+    pub duration_ms: u64,
+    /// Time with the current above each of [`OCP_BANDS_PERCENT`] of the OCP.
+    above_ocp_us: [u64; OCP_BANDS_PERCENT.len()],
+}
+
+impl Report {
+    const fn new() -> Self {
+        Self {
+            volts: Extent::new(),
+            amps: Extent::new(),
+            watts: Extent::new(),
+            energy: NO_ENERGY,
+            faults: 0,
+            duration_ms: 0,
+            above_ocp_us: [0; OCP_BANDS_PERCENT.len()],
+        }
+    }
+
+    /// Milliseconds with the current above band `band` of [`OCP_BANDS_PERCENT`].
+    pub fn above_ocp_ms(&self, band: usize) -> u64 {
+        self.above_ocp_us[band] / 1_000
+    }
+
+    /// The share of the session in band `band`, in whole percent.
+    pub fn above_ocp_percent(&self, band: usize) -> u8 {
+        (self.above_ocp_ms(band) * 100)
+            .checked_div(self.duration_ms)
+            .map_or(0, |percent| percent.min(100) as u8)
+    }
+}
+
+pub(crate) struct Session {
+    report: Option<Report>,
+    /// Set while a session runs.
+    started: Option<Instant>,
+    last: Option<Instant>,
+}
+
+impl Session {
+    pub const fn new() -> Self {
+        Self {
+            report: None,
+            started: None,
+            last: None,
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.started.is_some()
+    }
+
+    /// The running session so far or the last one, none before the first start.
+    pub fn report(&self) -> Option<Report> {
+        self.report
+    }
+
+    /// Starts over at `now`, dropping the previous report.
+    pub fn start(&mut self, now: Instant) {
+        self.report = Some(Report::new());
+        self.started = Some(now);
+        self.last = Some(now);
+    }
+
+    /// Ends the running session at `now` and returns its report, none when none was running.
+    pub fn stop(&mut self, now: Instant) -> Option<Report> {
+        let started = self.started.take()?;
+        self.last = None;
+
+        let report = self.report.as_mut()?;
+        report.duration_ms = (now - started).as_millis();
+
+        Some(*report)
+    }
+
+    /// Takes the reading of a pass at `now` with `ocp` in force. A quantity that failed to read
+    /// adds nothing, and the energy of a pass without watts is left out, as for the energy
+    /// counter. The time since the previous pass counts towards the bands of the OCP the current
+    /// is above; none does without an OCP.
+    pub fn record(&mut self, now: Instant, reading: &Reading, ocp: Value) {
+        let (Some(started), Some(last), Some(report)) =
+            (self.started, self.last, self.report.as_mut())
+        else {
+            return;
+        };
+
+        if let Some(volts) = reading.volts {
+            report.volts.add(volts);
+        }
+        if let Some(amps) = reading.amps {
+            report.amps.add(amps);
+        }
+        if let Some(watts) = reading.watts {
+            report.watts.add(watts);
+        }
+
+        let elapsed = now - last;
+        if let Some(amps) = reading.amps.filter(|_| ocp > ZERO) {
+            for (above_us, percent) in report.above_ocp_us.iter_mut().zip(OCP_BANDS_PERCENT) {
+                if amps > units::percent(ocp, percent) {
+                    *above_us += elapsed.as_micros();
+                }
+            }
+        }
+
+        units::add_energy(&mut report.energy, reading.watts, elapsed);
+        report.duration_ms = (now - started).as_millis();
+        self.last = Some(now);
+    }
+
+    /// Counts a trip or a fault, while a session runs.
+    pub fn fault(&mut self) {
+        if let (true, Some(report)) = (self.is_running(), self.report.as_mut()) {
+            report.faults = report.faults.saturating_add(1);
+        }
+    }
+}
+
+/// `ms` as `H:MM:SS`.
+pub(crate) fn hms(ms: u64) -> (u64, u8, u8) {
+    let secs = ms / 1_000;
+
+    (secs / 3_600, (secs / 60 % 60) as u8, (secs % 60) as u8)
+}
@@ -0,0 +1,94 @@
+//! The settings page in flash (see the layout in `updater.rs`).
+//!
+//! The page holds fixed-size records at fixed offsets, the calibration, the output schedule, the
+//! slew-rate limits, the power monitor of each channel, the soft fuse, the choices of the setup
+//! wizard and the display clock limit, each starting with its own magic and ending in a CRC32 over the
+//! rest, so a blank or corrupt record reads back as missing. Writing one record rewrites the page
+//! with the others kept.
+
+use crate::{
+    log::{warn, Module},
+    shared::FLASH,
+    updater::{crc32, PAGE_SIZE, SETTINGS_START},
+};
+
+const LOG_MODULE: Module = Module::System;
+
+pub(crate) const RECORD_LEN: usize = 16;
+/// What a record holds between its magic and its CRC.
+pub(crate) const BODY_LEN: usize = RECORD_LEN - 8;
+
+/// Offsets of the records in the page.
+pub(crate) const CALIBRATION_RECORD: usize = 0;
+pub(crate) const SCHEDULE_RECORD: usize = RECORD_LEN;
+pub(crate) const SLEW_RECORD: usize = 2 * RECORD_LEN;
+/// The output statistics before they moved to a journal, only read, see `stats_settings.rs`.
+pub(crate) const STATS_RECORD: usize = 3 * RECORD_LEN;
+pub(crate) const MONITOR_RECORD: usize = 4 * RECORD_LEN;
+pub(crate) const MONITOR_B_RECORD: usize = 5 * RECORD_LEN;
+pub(crate) const FUSE_RECORD: usize = 6 * RECORD_LEN;
+pub(crate) const SETUP_RECORD: usize = 7 * RECORD_LEN;
+pub(crate) const DISPLAY_RECORD: usize = 8 * RECORD_LEN;
+
+/// The records in use, rewritten together.
+const RECORDS_LEN: usize = 9 * RECORD_LEN;
+
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) enum SettingsError {
+    Unavailable,
+    Flash,
+}
+
+impl SettingsError {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SettingsError::Unavailable => "flash unavailable",
+            SettingsError::Flash => "flash error",
+        }
+    }
+}
+
+/// The body of the record at `offset`, if one with `magic` is stored there.
+pub(crate) fn read(offset: usize, magic: u32) -> Option<[u8; BODY_LEN]> {
+    let page = read_page();
+    let record = &page[offset..offset + RECORD_LEN];
+    let word = |i: usize| u32::from_le_bytes(record[i..i + 4].try_into().unwrap());
+
+    if word(0) != magic || word(RECORD_LEN - 4) != crc32(&record[..RECORD_LEN - 4]) {
+        return None;
+    }
+
+    record[4..4 + BODY_LEN].try_into().ok()
+}
+
+/// Stores `body` as the record at `offset`, keeping the other records.
+pub(crate) async fn write(
+    offset: usize,
+    magic: u32,
+    body: &[u8; BODY_LEN],
+) -> Result<(), SettingsError> {
+    let mut page = read_page();
+    let record = &mut page[offset..offset + RECORD_LEN];
+
+    record[..4].copy_from_slice(&magic.to_le_bytes());
+    record[4..4 + BODY_LEN].copy_from_slice(body);
+    let crc = crc32(&record[..RECORD_LEN - 4]);
+    record[RECORD_LEN - 4..].copy_from_slice(&crc.to_le_bytes());
+
+    let mut flash = FLASH.lock().await;
+    let flash = flash.as_mut().ok_or(SettingsError::Unavailable)?;
+
+    let start = SETTINGS_START - embassy_stm32::flash::FLASH_BASE as u32;
+
+    flash
+        .blocking_erase(start, start + PAGE_SIZE)
+        .and_then(|_| flash.blocking_write(start, &page))
+        .map_err(|err| {
+            warn!("settings write failed: {:?}", err);
+            SettingsError::Flash
+        })
+}
+
+fn read_page() -> [u8; RECORDS_LEN] {
+    unsafe { core::ptr::read_volatile(SETTINGS_START as *const [u8; RECORDS_LEN]) }
+}
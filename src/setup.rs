@@ -0,0 +1,118 @@
+//! The setup wizard of a new unit.
+//!
+//! A unit without a setup record in the settings page starts on the wizard instead of the monitor
+//! page. Its steps pick the screen rotation, the OCP the unit comes up with and the shunt of the
+//! output's INA226: Up and Down change the value, both together go on to the next step and save on
+//! the last one. A long press of both leaves without saving, and the wizard comes back at the next
+//! boot. The rotation and the OCP are kept in the setup record and applied at every boot, the shunt
+//! goes into the power monitor record of `monitor_settings.rs`, used from the next restart on.
+//!
+//! The screen only speaks English, so there is no language to pick.
+
+use crate::{
+    types::Direction,
+    units::{self, Value, ZERO},
+};
+
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) enum SetupStep {
+    Rotation,
+    Ocp,
+    Shunt,
+}
+
+pub(crate) const SETUP_STEPS: [SetupStep; 3] =
+    [SetupStep::Rotation, SetupStep::Ocp, SetupStep::Shunt];
+
+impl SetupStep {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SetupStep::Rotation => "Rotation",
+            SetupStep::Ocp => "Default OCP",
+            SetupStep::Shunt => "Shunt",
+        }
+    }
+
+    /// The step after this one, or none after the last, which saves.
+    pub fn next(&self) -> Option<Self> {
+        SETUP_STEPS.get(*self as usize + 1).copied()
+    }
+}
+
+/// What Up and Down add to or take from the OCP.
+pub(crate) const OCP_STEP_MA: i32 = 500;
+
+/// Common shunts, in micro-ohms, that Up and Down go through.
+pub(crate) const SHUNTS_MICRO_OHMS: [u32; 7] =
+    [1_000, 2_000, 5_000, 10_000, 20_000, 50_000, 100_000];
+
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) struct Setup {
+    pub direction: Direction,
+    /// Zero for none.
+    pub ocp: Value,
+    pub shunt_micro_ohms: u32,
+}
+
+impl Setup {
+    /// What the wizard starts from: the normal rotation, no OCP and the shunt in use.
+    pub const fn new(shunt_micro_ohms: u32) -> Self {
+        Self {
+            direction: Direction::Normal,
+            ocp: ZERO,
+            shunt_micro_ohms,
+        }
+    }
+
+    pub fn to_bytes(self) -> [u8; 8] {
+        let mut buf = [0u8; 8];
+
+        buf[0] = match self.direction {
+            Direction::Normal => 0,
+            Direction::Reversed => 1,
+        };
+        buf[1..3].copy_from_slice(&(units::milli(self.ocp).max(0) as u16).to_le_bytes());
+        buf[3..7].copy_from_slice(&self.shunt_micro_ohms.to_le_bytes());
+
+        buf
+    }
+
+    /// `None` for a record with an unknown rotation.
+    pub fn from_bytes(buf: &[u8; 8]) -> Option<Self> {
+        let direction = match buf[0] {
+            0 => Direction::Normal,
+            1 => Direction::Reversed,
+            _ => return None,
+        };
+
+        Some(Self {
+            direction,
+            ocp: units::from_milli(u16::from_le_bytes([buf[1], buf[2]]) as i32),
+            shunt_micro_ohms: u32::from_le_bytes([buf[3], buf[4], buf[5], buf[6]]),
+        })
+    }
+}
+
+/// `ocp` a step up or down, from none up to `max`.
+pub(crate) fn step_ocp(ocp: Value, up: bool, max: Value) -> Value {
+    let milli = match up {
+        true => units::milli(ocp) + OCP_STEP_MA,
+        false => units::milli(ocp) - OCP_STEP_MA,
+    };
+
+    units::from_milli(milli.clamp(0, units::milli(max)))
+}
+
+/// The next larger (`up`) or smaller of [`SHUNTS_MICRO_OHMS`] than `micro_ohms`, which need not
+/// be one of them; the ends stay.
+pub(crate) fn step_shunt(micro_ohms: u32, up: bool) -> u32 {
+    let next = match up {
+        true => SHUNTS_MICRO_OHMS.iter().find(|shunt| **shunt > micro_ohms),
+        false => SHUNTS_MICRO_OHMS
+            .iter()
+            .rev()
+            .find(|shunt| **shunt < micro_ohms),
+    };
+
+    next.copied().unwrap_or(micro_ohms)
+}
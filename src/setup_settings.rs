@@ -0,0 +1,46 @@
+//! Keeps what the setup wizard of `setup.rs` picked in a record of the settings page.
+
+use crate::{
+    log::{info, warn, Module},
+    monitor_settings,
+    settings::{self, SETUP_RECORD},
+    setup::Setup,
+    shared::{DISPLAY_DIRECTION_MUTEX, OCP_MUTEX},
+    types::Channel,
+};
+
+const LOG_MODULE: Module = Module::System;
+
+const MAGIC: u32 = 0x5044_5355; // "PDSU"
+
+/// Applies the stored rotation and OCP; none stored means the wizard has not run yet, which this
+/// returns.
+pub(crate) async fn load() -> Option<Setup> {
+    let setup = settings::read(SETUP_RECORD, MAGIC).and_then(|buf| Setup::from_bytes(&buf));
+
+    info!("setup: {:?}", setup);
+    if let Some(setup) = setup {
+        *DISPLAY_DIRECTION_MUTEX.lock().await = setup.direction;
+        *OCP_MUTEX.lock().await = setup.ocp;
+    }
+
+    setup
+}
+
+/// Writes `setup` to flash, and its shunt to the power monitor record of the output when it
+/// changed. A failure is logged, the wizard comes back at the next boot.
+pub(crate) async fn store(setup: Setup) {
+    let mut monitor = monitor_settings::load(Channel::A);
+    if monitor.shunt_micro_ohms != setup.shunt_micro_ohms {
+        monitor.shunt_micro_ohms = setup.shunt_micro_ohms;
+
+        if let Err(err) = monitor_settings::store(Channel::A, monitor).await {
+            warn!("shunt not stored: {}", err.as_str());
+        }
+    }
+
+    match settings::write(SETUP_RECORD, MAGIC, &setup.to_bytes()).await {
+        Ok(()) => info!("setup stored: {:?}", setup),
+        Err(err) => warn!("setup not stored: {}", err.as_str()),
+    }
+}
@@ -1,47 +1,95 @@
+//! State shared between the tasks. The simulator includes this file as well; what it has no
+//! hardware or module for is only built for the target.
+
+#[cfg(target_os = "none")]
+use embassy_stm32::{
+    flash::{Blocking, Flash},
+    rtc::Rtc,
+    time::Hertz,
+};
+#[cfg(target_os = "none")]
+use embassy_sync::once_lock::OnceLock;
 use embassy_sync::{
-    blocking_mutex::raw::CriticalSectionRawMutex, channel::Channel, mutex::Mutex,
-    pubsub::PubSubChannel,
+    blocking_mutex::raw::CriticalSectionRawMutex, channel, mutex::Mutex, pubsub::PubSubChannel,
+    signal::Signal,
 };
-use embassy_time::Duration;
-use embedded_graphics::{pixelcolor::Rgb565, prelude::WebColors};
-use heapless::Vec;
+use heapless::{String, Vec};
 use husb238::SrcPdo;
 
+#[cfg(feature = "sd-log")]
+use crate::csv_log::Record;
+#[cfg(feature = "trigger")]
+use crate::trigger::TriggerConfig;
 use crate::{
+    average::AverageInterval,
     button::ButtonState,
+    cable::CableProbe,
+    calendar::DateTime,
+    capture::Capture,
+    cc_lines::CcLines,
+    conversion::Conversion,
+    csv_log::CardStatus,
+    data_lines::DataLines,
+    entry::NumberEntry,
+    fan::{FanCurve, FanStatus},
+    fault::{Fault, Faults},
+    filter::FilterKind,
+    output_stats::OutputStats,
+    protection::{FuseLimit, SenseReport, VoltageLimit},
+    rails::SWITCH_LIMIT_DEFAULT,
+    render::{RenderCmd, RENDER_QUEUE_LEN},
+    reset_cause::ResetCounts,
+    schedule::{Action, Schedule},
+    screenshot::Screen,
+    session::Session,
+    setup::Setup,
+    types::{
+        AvailableVoltCurr, Channel, Direction, OutputMode, OutputRequest, Page, PdRequest,
+        PowerInfo, PowerProfile, PowerState, StatusInfo, SystemStatus, Theme, WifiState,
+    },
+    units::{self, Energy, Value, NO_ENERGY, ZERO},
+    watts::WattsSource,
+};
+#[cfg(target_os = "none")]
+use crate::{
+    bsp,
+    calibration::Calibration,
+    crash::Crash,
     display::Display,
-    types::{AvailableVoltCurr, Direction, Page, ST7789DCPin, ST7789RstPin, ST7789SpiDev},
+    history::History,
+    selftest::SelfTest,
+    slew::SlewLimits,
+    types::{ST7789DCPin, ST7789RstPin, ST7789SpiDev},
 };
 
-pub const MIN_PRESS_DURATION: Duration = Duration::from_millis(50);
-pub const SHORT_PRESS_DURATION: Duration = Duration::from_millis(200);
-pub const DOUBLE_CLICK_TIMEOUT: Duration = Duration::from_millis(200);
-pub const MAX_SIMULTANEOUS_PRESS_DELAY: Duration = Duration::from_millis(100);
-
-pub const OCP_MAX: f64 = 10.0;
-
-pub const COLOR_PRIMARY: Rgb565 = Rgb565::CSS_DODGER_BLUE;
-pub const COLOR_SECONDARY: Rgb565 = Rgb565::CSS_TURQUOISE;
-pub const COLOR_BACKGROUND: Rgb565 = Rgb565::CSS_WHITE_SMOKE;
-pub const COLOR_PRIMARY_CONTENT: Rgb565 = Rgb565::CSS_WHITE;
-pub const COLOR_BASE: Rgb565 = WebColors::CSS_DARK_SLATE_GRAY;
-pub const COLOR_TEXT: Rgb565 = WebColors::CSS_DARK_SLATE_GRAY;
-pub const COLOR_TEXT_DISABLED: Rgb565 = WebColors::CSS_DARK_GRAY;
-pub const COLOR_VOLTAGE: Rgb565 = WebColors::CSS_DARK_ORANGE;
-pub const COLOR_AMPERAGE: Rgb565 = WebColors::CSS_ORANGE_RED;
-pub const COLOR_WATTAGE: Rgb565 = WebColors::CSS_FOREST_GREEN;
-pub const COLOR_ERROR: Rgb565 = WebColors::CSS_DARK_RED;
-pub const COLOR_INFO: Rgb565 = WebColors::CSS_STEEL_BLUE;
-
-pub static DISPLAY: Mutex<
-    CriticalSectionRawMutex,
-    Option<Display<ST7789SpiDev, ST7789DCPin, ST7789RstPin>>,
-> = Mutex::new(None);
+pub const OCP_MAX: Value = units::from_milli(10_000);
+
+pub const CONSOLE_LINE_LEN: usize = 96;
 
-pub(crate) static BTN_A_STATE_CHANNEL: Channel<CriticalSectionRawMutex, ButtonState, 10> =
-    Channel::new();
-pub(crate) static BTN_B_STATE_CHANNEL: Channel<CriticalSectionRawMutex, ButtonState, 10> =
-    Channel::new();
+/// Set once at boot, whether or not the panel came up; a failed panel is retried by
+/// `Display::task`. Users wait for it with `DISPLAY.get().await`.
+#[cfg(target_os = "none")]
+pub static DISPLAY: OnceLock<
+    Mutex<CriticalSectionRawMutex, Display<ST7789SpiDev, ST7789DCPin, ST7789RstPin>>,
+> = OnceLock::new();
+
+/// Shadow of what is on the panel, used by the `screenshot` console command.
+pub(crate) static SCREEN_MUTEX: Mutex<CriticalSectionRawMutex, Screen> = Mutex::new(Screen::new());
+
+#[cfg(target_os = "none")]
+pub static FLASH: Mutex<CriticalSectionRawMutex, Option<Flash<'static, Blocking>>> =
+    Mutex::new(None);
+
+/// Keeps the wall clock across resets, see `clock::restore`.
+#[cfg(target_os = "none")]
+pub(crate) static RTC_MUTEX: Mutex<CriticalSectionRawMutex, Option<Rtc>> = Mutex::new(None);
+
+pub(crate) static BTN_A_STATE_CHANNEL: channel::Channel<CriticalSectionRawMutex, ButtonState, 10> =
+    channel::Channel::new();
+pub(crate) static BTN_B_STATE_CHANNEL: channel::Channel<CriticalSectionRawMutex, ButtonState, 10> =
+    channel::Channel::new();
+/// Whether the button states go out on the console, see `button_trace.rs`.
+pub(crate) static BUTTON_TRACE_MUTEX: Mutex<CriticalSectionRawMutex, bool> = Mutex::new(false);
 
 pub(crate) static PAGE_PUBSUB: PubSubChannel<CriticalSectionRawMutex, Page, 2, 2, 1> =
     PubSubChannel::new();
@@ -54,20 +102,246 @@ pub(crate) static DISPLAY_DIRECTION_PUBSUB: PubSubChannel<
     2,
     1,
 > = PubSubChannel::new();
-pub(crate) static OCP_PUBSUB: PubSubChannel<CriticalSectionRawMutex, f64, 2, 2, 1> =
+pub(crate) static THEME_PUBSUB: PubSubChannel<CriticalSectionRawMutex, Theme, 2, 2, 1> =
+    PubSubChannel::new();
+pub(crate) static OCP_PUBSUB: PubSubChannel<CriticalSectionRawMutex, Value, 2, 2, 1> =
+    PubSubChannel::new();
+pub(crate) static UVP_PUBSUB: PubSubChannel<CriticalSectionRawMutex, VoltageLimit, 2, 2, 1> =
+    PubSubChannel::new();
+pub(crate) static OVP_PUBSUB: PubSubChannel<CriticalSectionRawMutex, VoltageLimit, 2, 2, 1> =
+    PubSubChannel::new();
+/// Published only by [`select_pdo`], so every request also lands in `PDO_MUTEX`.
+pub(crate) static PDO_PUBSUB: PubSubChannel<CriticalSectionRawMutex, PdRequest, 2, 2, 1> =
+    PubSubChannel::new();
+
+/// Published on every button edge, for anything that reacts to user activity.
+pub(crate) static ACTIVITY_PUBSUB: PubSubChannel<CriticalSectionRawMutex, (), 2, 2, 1> =
+    PubSubChannel::new();
+pub(crate) static POWER_STATE_PUBSUB: PubSubChannel<CriticalSectionRawMutex, PowerState, 2, 2, 1> =
+    PubSubChannel::new();
+pub(crate) static POWER_PROFILE_PUBSUB: PubSubChannel<
+    CriticalSectionRawMutex,
+    PowerProfile,
+    2,
+    2,
+    1,
+> = PubSubChannel::new();
+pub(crate) static FILTER_PUBSUB: PubSubChannel<CriticalSectionRawMutex, FilterKind, 2, 2, 1> =
     PubSubChannel::new();
-pub(crate) static UVP_PUBSUB: PubSubChannel<CriticalSectionRawMutex, f64, 2, 2, 1> =
+pub(crate) static WATTS_SOURCE_PUBSUB: PubSubChannel<
+    CriticalSectionRawMutex,
+    WattsSource,
+    2,
+    2,
+    1,
+> = PubSubChannel::new();
+pub(crate) static FAULT_PUBSUB: PubSubChannel<CriticalSectionRawMutex, Fault, 2, 2, 1> =
     PubSubChannel::new();
-pub(crate) static PDO_PUBSUB: PubSubChannel<CriticalSectionRawMutex, SrcPdo, 2, 2, 1> =
+pub(crate) static OUTPUT_PUBSUB: PubSubChannel<CriticalSectionRawMutex, OutputRequest, 2, 2, 1> =
     PubSubChannel::new();
+/// A new display clock limit from the console.
+#[cfg(target_os = "none")]
+pub(crate) static DISPLAY_SPI_PUBSUB: PubSubChannel<CriticalSectionRawMutex, Hertz, 2, 2, 1> =
+    PubSubChannel::new();
+pub(crate) static OUTPUT_MODE_PUBSUB: PubSubChannel<CriticalSectionRawMutex, OutputMode, 2, 2, 1> =
+    PubSubChannel::new();
+/// Mount (`true`) or safely unmount the SD card, from its page.
+pub(crate) static SD_MOUNT_PUBSUB: PubSubChannel<CriticalSectionRawMutex, bool, 2, 2, 1> =
+    PubSubChannel::new();
+
+pub(crate) static CONSOLE_TX_CHANNEL: channel::Channel<
+    CriticalSectionRawMutex,
+    String<CONSOLE_LINE_LEN>,
+    8,
+> = channel::Channel::new();
+/// Screen updates on their way to the display task, see `render.rs`.
+pub(crate) static RENDER_CHANNEL: channel::Channel<
+    CriticalSectionRawMutex,
+    RenderCmd,
+    RENDER_QUEUE_LEN,
+> = channel::Channel::new();
+/// Samples and events on their way to the SD card, with their time since boot in milliseconds.
+#[cfg(feature = "sd-log")]
+pub(crate) static SD_LOG_CHANNEL: channel::Channel<CriticalSectionRawMutex, (u64, Record), 16> =
+    channel::Channel::new();
 
 pub(crate) static PAGE_MUTEX: Mutex<CriticalSectionRawMutex, Page> = Mutex::new(Page::Monitor);
-pub(crate) static BACKLIGHT_MUTEX: Mutex<CriticalSectionRawMutex, u16> = Mutex::new(255);
+/// Highest backlight level; 0 turns it off.
+pub const BACKLIGHT_MAX_LEVEL: u16 = 10;
+
+/// Backlight level set with the buttons on the monitor page.
+pub(crate) static BACKLIGHT_MUTEX: Mutex<CriticalSectionRawMutex, u16> = Mutex::new(7);
+/// Seconds without button activity before the backlight dims, 0 to never dim.
+pub(crate) static BACKLIGHT_TIMEOUT_MUTEX: Mutex<CriticalSectionRawMutex, u16> = Mutex::new(30);
 pub(crate) static DISPLAY_DIRECTION_MUTEX: Mutex<CriticalSectionRawMutex, Direction> =
     Mutex::new(Direction::Normal);
-pub(crate) static OCP_MUTEX: Mutex<CriticalSectionRawMutex, f64> = Mutex::new(0.0);
-pub(crate) static UVP_MUTEX: Mutex<CriticalSectionRawMutex, f64> = Mutex::new(0.0);
+pub(crate) static THEME_MUTEX: Mutex<CriticalSectionRawMutex, Theme> = Mutex::new(Theme::Light);
+/// Fastest display SPI clock, as set from the console. The power profiles only ever slow the bus
+/// down from here.
+#[cfg(target_os = "none")]
+pub(crate) static DISPLAY_SPI_MAX_MUTEX: Mutex<CriticalSectionRawMutex, Hertz> =
+    Mutex::new(bsp::DISPLAY_SPI_FREQUENCY);
+/// Over-current threshold, 0 for none. Capped to the contract current on every new contract.
+pub(crate) static OCP_MUTEX: Mutex<CriticalSectionRawMutex, Value> = Mutex::new(ZERO);
+/// Over-current threshold of the second channel on `dual-output` builds, 0 for none.
+pub(crate) static OCP_B_MUTEX: Mutex<CriticalSectionRawMutex, Value> = Mutex::new(ZERO);
+/// Under- and over-voltage limits, see `protection.rs`.
+pub(crate) static UVP_MUTEX: Mutex<CriticalSectionRawMutex, VoltageLimit> =
+    Mutex::new(VoltageLimit::off());
+pub(crate) static OVP_MUTEX: Mutex<CriticalSectionRawMutex, VoltageLimit> =
+    Mutex::new(VoltageLimit::off());
+/// dV/dt and dI/dt alarm limits, see `slew.rs`.
+#[cfg(target_os = "none")]
+pub(crate) static SLEW_LIMITS_MUTEX: Mutex<CriticalSectionRawMutex, SlewLimits> =
+    Mutex::new(SlewLimits::off());
 pub(crate) static PDO_MUTEX: Mutex<CriticalSectionRawMutex, SrcPdo> = Mutex::new(SrcPdo::_5v);
+pub(crate) static OUTPUT_MUTEX: Mutex<CriticalSectionRawMutex, bool> = Mutex::new(false);
+/// Set by an over-current trip; holding Up on the monitor page clears it to re-arm the output.
+pub(crate) static TRIPPED_MUTEX: Mutex<CriticalSectionRawMutex, bool> = Mutex::new(false);
+/// Output and trip state of the second channel on `dual-output` builds.
+pub(crate) static OUTPUT_B_MUTEX: Mutex<CriticalSectionRawMutex, bool> = Mutex::new(false);
+pub(crate) static TRIPPED_B_MUTEX: Mutex<CriticalSectionRawMutex, bool> = Mutex::new(false);
+/// Over-current trips that blow the soft fuse, see `protection.rs`.
+pub(crate) static FUSE_LIMIT_MUTEX: Mutex<CriticalSectionRawMutex, FuseLimit> =
+    Mutex::new(FuseLimit::default());
+/// Set when the soft fuse of a channel blows; `fuse reset` on the console clears them to mend it.
+pub(crate) static FUSE_BLOWN_MUTEX: Mutex<CriticalSectionRawMutex, bool> = Mutex::new(false);
+#[cfg(feature = "dual-output")]
+pub(crate) static FUSE_BLOWN_B_MUTEX: Mutex<CriticalSectionRawMutex, bool> = Mutex::new(false);
+/// The channel the monitor page shows and the buttons switch.
+pub(crate) static SELECTED_CHANNEL_MUTEX: Mutex<CriticalSectionRawMutex, Channel> =
+    Mutex::new(Channel::A);
+/// Last readings of the second channel.
+#[cfg(feature = "dual-output")]
+pub(crate) static SECOND_POWER_MUTEX: Mutex<CriticalSectionRawMutex, Option<PowerInfo>> =
+    Mutex::new(None);
+/// The last comparison of the output sense with the output, on builds with a sense input.
+pub(crate) static OUTPUT_SENSE_MUTEX: Mutex<CriticalSectionRawMutex, Option<SenseReport>> =
+    Mutex::new(None);
+/// Lifetime on-time and switch count of the output, see `output_stats.rs`.
+pub(crate) static OUTPUT_STATS_MUTEX: Mutex<CriticalSectionRawMutex, OutputStats> =
+    Mutex::new(OutputStats::zero());
+/// The last reset cause and the resets counted per cause, see `reset_cause.rs`.
+pub(crate) static RESET_COUNTS_MUTEX: Mutex<CriticalSectionRawMutex, ResetCounts> =
+    Mutex::new(ResetCounts::empty());
+pub(crate) static OUTPUT_MODE_MUTEX: Mutex<CriticalSectionRawMutex, OutputMode> =
+    Mutex::new(OutputMode::Latching);
+/// Whether enabling the output starts with the short test ramp, see `protection.rs`.
+pub(crate) static SHORT_TEST_MUTEX: Mutex<CriticalSectionRawMutex, bool> = Mutex::new(false);
+pub(crate) static REMOTE_MUTEX: Mutex<CriticalSectionRawMutex, bool> = Mutex::new(false);
+/// The daily output schedule, if one is set.
+pub(crate) static SCHEDULE_MUTEX: Mutex<CriticalSectionRawMutex, Option<Schedule>> =
+    Mutex::new(None);
+/// The next switch of the schedule, shown on the monitor page.
+pub(crate) static NEXT_ACTION_MUTEX: Mutex<CriticalSectionRawMutex, Option<Action>> =
+    Mutex::new(None);
+/// Unix time in milliseconds at boot, once the clock is set.
+pub(crate) static EPOCH_MUTEX: Mutex<CriticalSectionRawMutex, Option<u64>> = Mutex::new(None);
+/// How the previous run ended, if it crashed.
+#[cfg(target_os = "none")]
+pub(crate) static LAST_CRASH_MUTEX: Mutex<CriticalSectionRawMutex, Option<Crash>> =
+    Mutex::new(None);
+/// Results of the power-on self-test, once it has run.
+#[cfg(target_os = "none")]
+pub(crate) static SELFTEST_MUTEX: Mutex<CriticalSectionRawMutex, Option<SelfTest>> =
+    Mutex::new(None);
+/// Readings taken on the cable page.
+pub(crate) static CABLE_MUTEX: Mutex<CriticalSectionRawMutex, CableProbe> =
+    Mutex::new(CableProbe::new());
+/// Load-current capture shown on the scope page.
+pub(crate) static CAPTURE_MUTEX: Mutex<CriticalSectionRawMutex, Capture> =
+    Mutex::new(Capture::new());
+/// The running or last test session, see `session.rs`.
+pub(crate) static SESSION_MUTEX: Mutex<CriticalSectionRawMutex, Session> =
+    Mutex::new(Session::new());
+/// The choices of the setup wizard so far, see `setup.rs`; the shunt in use is filled in at boot.
+pub(crate) static SETUP_MUTEX: Mutex<CriticalSectionRawMutex, Setup> = Mutex::new(Setup::new(0));
+/// The value being entered on the UVP or OCP page.
+pub(crate) static ENTRY_MUTEX: Mutex<CriticalSectionRawMutex, NumberEntry> =
+    Mutex::new(NumberEntry::new(0, 0, 0));
+/// The date and time being entered on the clock page.
+pub(crate) static CLOCK_ENTRY_MUTEX: Mutex<CriticalSectionRawMutex, DateTime> =
+    Mutex::new(DateTime::default());
+pub(crate) static ENERGY_MUTEX: Mutex<CriticalSectionRawMutex, Energy> = Mutex::new(NO_ENERGY);
+pub(crate) static WIFI_STATE_MUTEX: Mutex<CriticalSectionRawMutex, WifiState> =
+    Mutex::new(WifiState::Disabled);
+/// MQTT publish interval in seconds.
+pub(crate) static MQTT_INTERVAL_MUTEX: Mutex<CriticalSectionRawMutex, u16> = Mutex::new(5);
+/// Whether the INA226 pulls its ALERT line on a load, see `ina226_alert.rs`.
+#[cfg(feature = "ina226-alert")]
+pub(crate) static INA226_ALERT_ARMED_MUTEX: Mutex<CriticalSectionRawMutex, bool> =
+    Mutex::new(false);
+/// Lines per second of the serial plotter stream, none while it is off, see `plotter.rs`.
+pub(crate) static PLOT_MUTEX: Mutex<CriticalSectionRawMutex, Option<u8>> = Mutex::new(None);
+/// Curve the fan follows, and what it did last, on builds with a fan.
+pub(crate) static FAN_CURVE_MUTEX: Mutex<CriticalSectionRawMutex, FanCurve> =
+    Mutex::new(FanCurve::default());
+pub(crate) static FAN_STATUS_MUTEX: Mutex<CriticalSectionRawMutex, Option<FanStatus>> =
+    Mutex::new(None);
+/// Last D+ and D- levels, on builds that sample them.
+pub(crate) static DATA_LINES_MUTEX: Mutex<CriticalSectionRawMutex, Option<DataLines>> =
+    Mutex::new(None);
+/// Last CC1 and CC2 levels, on builds that sample them.
+pub(crate) static CC_LINES_MUTEX: Mutex<CriticalSectionRawMutex, Option<CcLines>> =
+    Mutex::new(None);
+/// VBUS ahead of the output switch, on builds that sample it.
+pub(crate) static VBUS_MUTEX: Mutex<CriticalSectionRawMutex, Option<Value>> = Mutex::new(None);
+/// Dissipation in the output switch that raises an alarm, zero for none.
+pub(crate) static SWITCH_LIMIT_MUTEX: Mutex<CriticalSectionRawMutex, Value> =
+    Mutex::new(SWITCH_LIMIT_DEFAULT);
+/// What the SD card log is doing, on builds with one.
+pub(crate) static SD_LOG_MUTEX: Mutex<CriticalSectionRawMutex, Option<CardStatus>> =
+    Mutex::new(None);
+/// Events and mode of the trigger output, taken up by the main loop on every pass.
+#[cfg(feature = "trigger")]
+pub(crate) static TRIGGER_MUTEX: Mutex<CriticalSectionRawMutex, TriggerConfig> =
+    Mutex::new(TriggerConfig::default());
+/// The filtered readings as the screen shows them, see `measure.rs`.
+pub(crate) static POWER_INFO_MUTEX: Mutex<CriticalSectionRawMutex, PowerInfo> =
+    Mutex::new(PowerInfo::default());
+/// The readings as the INA226 reported them, which protection works on.
+pub(crate) static RAW_POWER_MUTEX: Mutex<CriticalSectionRawMutex, PowerInfo> =
+    Mutex::new(PowerInfo::default());
+pub(crate) static STATUS_INFO_MUTEX: Mutex<CriticalSectionRawMutex, StatusInfo> =
+    Mutex::new(StatusInfo::default());
+/// Shown in the status bar, see `Display::task`.
+pub(crate) static SYSTEM_STATUS_MUTEX: Mutex<CriticalSectionRawMutex, SystemStatus> =
+    Mutex::new(SystemStatus::default());
+pub(crate) static POWER_PROFILE_MUTEX: Mutex<CriticalSectionRawMutex, PowerProfile> =
+    Mutex::new(PowerProfile::Performance);
+/// Smoothing of the displayed readings, see `filter.rs`.
+pub(crate) static FILTER_MUTEX: Mutex<CriticalSectionRawMutex, FilterKind> =
+    Mutex::new(FilterKind::Off);
+/// Where the displayed watts come from, see `watts.rs`.
+pub(crate) static WATTS_SOURCE_MUTEX: Mutex<CriticalSectionRawMutex, WattsSource> =
+    Mutex::new(WattsSource::Register);
+/// Interval the readings are averaged over, see `average.rs`.
+pub(crate) static AVERAGE_INTERVAL_MUTEX: Mutex<CriticalSectionRawMutex, AverageInterval> =
+    Mutex::new(AverageInterval::Off);
+/// Mean of the raw readings over the last completed interval.
+pub(crate) static AVERAGE_MUTEX: Mutex<CriticalSectionRawMutex, Option<PowerInfo>> =
+    Mutex::new(None);
+/// What the INA226 averages and how long it converts, see `conversion.rs`.
+pub(crate) static CONVERSION_MUTEX: Mutex<CriticalSectionRawMutex, Conversion> =
+    Mutex::new(Conversion::DEFAULT);
+/// RMS of the raw current over the last second, see `rms.rs`.
+pub(crate) static RMS_MUTEX: Mutex<CriticalSectionRawMutex, Option<Value>> = Mutex::new(None);
+pub(crate) static POWER_STATE_MUTEX: Mutex<CriticalSectionRawMutex, PowerState> =
+    Mutex::new(PowerState::Active);
+/// Raised by the idle task as the unit wakes from STOP, for the parked measurement loop.
+pub(crate) static WAKE_SIGNAL: Signal<CriticalSectionRawMutex, ()> = Signal::new();
+#[cfg(target_os = "none")]
+pub(crate) static CALIBRATION_MUTEX: Mutex<CriticalSectionRawMutex, Calibration> =
+    Mutex::new(Calibration::default());
+/// Whether the panel took the last transfer, as of the display task's last pass.
+pub(crate) static DISPLAY_AVAILABLE_MUTEX: Mutex<CriticalSectionRawMutex, bool> = Mutex::new(false);
+/// Whether slow drawings hold the small print back, see `render_budget.rs`.
+pub(crate) static DISPLAY_DEGRADED_MUTEX: Mutex<CriticalSectionRawMutex, bool> = Mutex::new(false);
+pub(crate) static FAULTS_MUTEX: Mutex<CriticalSectionRawMutex, Faults> =
+    Mutex::new(Faults::empty());
+#[cfg(target_os = "none")]
+pub(crate) static HISTORY_MUTEX: Mutex<CriticalSectionRawMutex, History> =
+    Mutex::new(History::new());
 
 pub(crate) static AVAILABLE_VOLT_CURR_MUTEX: Mutex<CriticalSectionRawMutex, AvailableVoltCurr> =
     Mutex::new(AvailableVoltCurr::default());
@@ -75,31 +349,33 @@ pub(crate) static SELECTED_VOLTAGE_MUTEX: Mutex<CriticalSectionRawMutex, SrcPdo>
     Mutex::new(SrcPdo::_5v);
 
 pub(crate) async fn get_available_voltages() -> Vec<SrcPdo, 6> {
-    let available_voltage = AVAILABLE_VOLT_CURR_MUTEX.lock().await;
-
-    let mut vec: Vec<SrcPdo, 6> = Vec::new();
-
-    vec.push(SrcPdo::_5v).unwrap();
+    AVAILABLE_VOLT_CURR_MUTEX.lock().await.voltages()
+}
 
-    if available_voltage._9v.is_some() {
-        vec.push(SrcPdo::_9v).unwrap();
-    }
+/// Makes `request.pdo` the selected PDO and queues the renegotiation.
+pub(crate) async fn select_pdo(request: PdRequest) {
+    *PDO_MUTEX.lock().await = request.pdo;
 
-    if available_voltage._12v.is_some() {
-        vec.push(SrcPdo::_12v).unwrap();
-    }
+    PDO_PUBSUB.immediate_publisher().publish_immediate(request);
+}
 
-    if available_voltage._15v.is_some() {
-        vec.push(SrcPdo::_15v).unwrap();
+pub(crate) fn ocp_mutex(channel: Channel) -> &'static Mutex<CriticalSectionRawMutex, Value> {
+    match channel {
+        Channel::A => &OCP_MUTEX,
+        Channel::B => &OCP_B_MUTEX,
     }
+}
 
-    if available_voltage._18v.is_some() {
-        vec.push(SrcPdo::_18v).unwrap();
+pub(crate) fn output_mutex(channel: Channel) -> &'static Mutex<CriticalSectionRawMutex, bool> {
+    match channel {
+        Channel::A => &OUTPUT_MUTEX,
+        Channel::B => &OUTPUT_B_MUTEX,
     }
+}
 
-    if available_voltage._20v.is_some() {
-        vec.push(SrcPdo::_20v).unwrap();
+pub(crate) fn tripped_mutex(channel: Channel) -> &'static Mutex<CriticalSectionRawMutex, bool> {
+    match channel {
+        Channel::A => &TRIPPED_MUTEX,
+        Channel::B => &TRIPPED_B_MUTEX,
     }
-
-    return vec;
 }
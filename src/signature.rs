@@ -0,0 +1,63 @@
+//! Ed25519 signatures of the firmware images `updater.rs` takes.
+//!
+//! A signed image is the firmware binary with the 64-byte signature of it appended, which OpenSSL
+//! makes without extra tools:
+//!
+//! ```sh
+//! openssl genpkey -algorithm ed25519 -out update.pem
+//! openssl pkey -in update.pem -pubout -outform DER | tail -c 32 | xxd -p -c 32
+//! openssl pkeyutl -sign -rawin -inkey update.pem -in fw.bin -out fw.sig
+//! cat fw.bin fw.sig > fw.signed.bin
+//! ```
+//!
+//! The public key, the 64 hex digits of the second line, is built into the firmware from
+//! `PD_SINK_UPDATE_KEY`. A build without one takes no images at all.
+
+use ed25519_compact::{PublicKey, Signature};
+
+pub(crate) const KEY_LEN: usize = 32;
+pub(crate) const SIGNATURE_LEN: usize = 64;
+
+/// The key `hex` spells, at build time.
+pub(crate) const fn parse_key(hex: &str) -> [u8; KEY_LEN] {
+    let hex = hex.as_bytes();
+    assert!(hex.len() == 2 * KEY_LEN, "update key must be 64 hex digits");
+
+    let mut key = [0u8; KEY_LEN];
+    let mut i = 0;
+    while i < KEY_LEN {
+        key[i] = (nibble(hex[2 * i]) << 4) | nibble(hex[2 * i + 1]);
+        i += 1;
+    }
+
+    key
+}
+
+const fn nibble(digit: u8) -> u8 {
+    match digit {
+        b'0'..=b'9' => digit - b'0',
+        b'a'..=b'f' => digit - b'a' + 10,
+        b'A'..=b'F' => digit - b'A' + 10,
+        _ => panic!("update key must be 64 hex digits"),
+    }
+}
+
+/// Length of the firmware in a signed image of `len` bytes, none if it is too short to hold a
+/// signature.
+pub(crate) fn image_len(len: usize) -> Option<usize> {
+    len.checked_sub(SIGNATURE_LEN).filter(|len| *len > 0)
+}
+
+/// Whether the signature at the end of `signed` is `key`'s over the rest.
+pub(crate) fn verify(key: &[u8; KEY_LEN], signed: &[u8]) -> bool {
+    let Some(len) = image_len(signed.len()) else {
+        return false;
+    };
+    let (image, signature) = signed.split_at(len);
+
+    let Ok(signature) = Signature::from_slice(signature) else {
+        return false;
+    };
+
+    PublicKey::new(*key).verify(image, &signature).is_ok()
+}
@@ -0,0 +1,153 @@
+//! Voltage and current slew-rate alarms.
+//!
+//! The measurement loop feeds every reading to [`SlewMonitor`], which works out dV/dt and dI/dt
+//! against the previous one and raises an alarm when either goes past its limit, e.g. a load
+//! hot-plugged into the live output or a source starting to fold back, well before an absolute
+//! OCP or UVP limit is reached. An alarm fires once per excursion and re-arms once the rate is back
+//! under the limit. With `trip` set it also turns the output off like an OCP trip. The limits are
+//! kept in a record of the settings page.
+
+#[cfg(not(feature = "mock-time"))]
+use embassy_time::Instant;
+
+#[cfg(feature = "mock-time")]
+use crate::mock_time::Instant;
+use crate::units::{self, Value};
+
+/// Highest limit the settings record holds, in milli-units per second.
+pub(crate) const MAX_RATE_MILLI: u32 = 655_350;
+
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) struct SlewLimits {
+    /// Largest dV/dt in mV/s, zero for no limit.
+    pub volts_milli: u32,
+    /// Largest dI/dt in mA/s, zero for no limit.
+    pub amps_milli: u32,
+    /// Turn the output off on an alarm, not just report it.
+    pub trip: bool,
+}
+
+impl SlewLimits {
+    pub const fn off() -> Self {
+        Self {
+            volts_milli: 0,
+            amps_milli: 0,
+            trip: false,
+        }
+    }
+
+    /// Rates are stored in steps of 10 milli-units per second.
+    pub fn to_bytes(self) -> [u8; 8] {
+        let mut buf = [0u8; 8];
+
+        buf[0..2].copy_from_slice(&((self.volts_milli / 10) as u16).to_le_bytes());
+        buf[2..4].copy_from_slice(&((self.amps_milli / 10) as u16).to_le_bytes());
+        buf[4] = self.trip as u8;
+
+        buf
+    }
+
+    pub fn from_bytes(buf: &[u8; 8]) -> Self {
+        Self {
+            volts_milli: u16::from_le_bytes([buf[0], buf[1]]) as u32 * 10,
+            amps_milli: u16::from_le_bytes([buf[2], buf[3]]) as u32 * 10,
+            trip: buf[4] != 0,
+        }
+    }
+}
+
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) enum SlewKind {
+    Volts,
+    Amps,
+}
+
+impl SlewKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SlewKind::Volts => "dV/dt",
+            SlewKind::Amps => "dI/dt",
+        }
+    }
+}
+
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) struct SlewAlarm {
+    pub kind: SlewKind,
+    /// The rate that set it off, in milli-units per second; negative when falling.
+    pub rate_milli: i32,
+}
+
+pub(crate) struct SlewMonitor {
+    last: Option<(Instant, Value, Value)>,
+    volts_alarmed: bool,
+    amps_alarmed: bool,
+}
+
+impl SlewMonitor {
+    pub const fn new() -> Self {
+        Self {
+            last: None,
+            volts_alarmed: false,
+            amps_alarmed: false,
+        }
+    }
+
+    /// Forgets the previous reading, so the next one starts over instead of counting the jump
+    /// from it, e.g. while the output is off.
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Takes a reading and returns the alarm it sets off, if any; a voltage alarm when both do.
+    pub fn update(
+        &mut self,
+        now: Instant,
+        volts: Value,
+        amps: Value,
+        limits: &SlewLimits,
+    ) -> Option<SlewAlarm> {
+        let (at, last_volts, last_amps) = self.last.replace((now, volts, amps))?;
+
+        let elapsed_ms = (now - at).as_millis() as i64;
+        if elapsed_ms == 0 {
+            return None;
+        }
+
+        let rate = |from: Value, to: Value| {
+            let delta = units::milli(to) as i64 - units::milli(from) as i64;
+            (delta * 1_000 / elapsed_ms).clamp(i32::MIN as i64, i32::MAX as i64) as i32
+        };
+
+        let amps_alarm = check(
+            &mut self.amps_alarmed,
+            rate(last_amps, amps),
+            limits.amps_milli,
+        )
+        .map(|rate_milli| SlewAlarm {
+            kind: SlewKind::Amps,
+            rate_milli,
+        });
+        let volts_alarm = check(
+            &mut self.volts_alarmed,
+            rate(last_volts, volts),
+            limits.volts_milli,
+        )
+        .map(|rate_milli| SlewAlarm {
+            kind: SlewKind::Volts,
+            rate_milli,
+        });
+
+        volts_alarm.or(amps_alarm)
+    }
+}
+
+/// The rate if it newly exceeds `limit`; `alarmed` remembers an excursion until it ends.
+fn check(alarmed: &mut bool, rate_milli: i32, limit: u32) -> Option<i32> {
+    let over = limit > 0 && rate_milli.unsigned_abs() > limit;
+    let fire = over && !*alarmed;
+
+    *alarmed = over;
+
+    fire.then_some(rate_milli)
+}
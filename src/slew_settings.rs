@@ -0,0 +1,29 @@
+//! Keeps the slew-rate limits of `slew.rs` in a record of the settings page.
+
+use crate::{
+    log::{info, Module},
+    settings::{self, SettingsError, SLEW_RECORD},
+    shared::SLEW_LIMITS_MUTEX,
+    slew::SlewLimits,
+};
+
+const LOG_MODULE: Module = Module::Measure;
+
+const MAGIC: u32 = 0x5044_534c; // "PDSL"
+
+/// Loads the stored limits into `SLEW_LIMITS_MUTEX`; none stored leaves the alarms off.
+pub(crate) async fn load() {
+    let limits = settings::read(SLEW_RECORD, MAGIC).map(|buf| SlewLimits::from_bytes(&buf));
+
+    info!("slew limits: {:?}", limits);
+    *SLEW_LIMITS_MUTEX.lock().await = limits.unwrap_or(SlewLimits::off());
+}
+
+/// Writes `limits` to flash and makes them the active ones.
+pub(crate) async fn store(limits: SlewLimits) -> Result<(), SettingsError> {
+    settings::write(SLEW_RECORD, MAGIC, &limits.to_bytes()).await?;
+
+    *SLEW_LIMITS_MUTEX.lock().await = limits;
+
+    Ok(())
+}
@@ -0,0 +1,73 @@
+//! Arbitration on the shared display SPI bus.
+//!
+//! The bus is a mutex that each device locks for a whole transaction, so a device streaming a
+//! long write holds every other device off until it is done: a full-screen fill or frame flush is
+//! tens of kilobytes, several milliseconds even at the fastest clock. [`ChunkedSpi`] splits long
+//! writes into transactions of at most `chunk` bytes and yields between them, so a second device
+//! on the bus (e.g. an SPI flash for logging) waits for one chunk at most, and its own short
+//! transactions slot in between the display's chunks instead of delaying a frame by a whole one.
+//!
+//! CS is released between chunks, which only suits devices that carry on where they left off: the
+//! ST7789 keeps writing RAM until the next command, with DC held by the driver. A second device
+//! that needs CS held across a long write must not be wrapped, and should keep its transactions
+//! short instead. Transactions that read, or mix writes with delays, are passed through whole.
+//!
+//! The bus is set up TX-only for the display; a device that reads needs MISO mapped in `bsp.rs`.
+
+use embassy_futures::yield_now;
+use embedded_hal_async::spi::{ErrorType, Operation, SpiDevice};
+
+/// Largest display write done in one bus transaction, 0.7 ms at 16 MHz. The same size as the
+/// ST7789 fill buffer, so fills are not split further.
+pub(crate) const DISPLAY_CHUNK: usize = 1440;
+
+/// Splits long writes on a shared-bus device into bounded transactions, see the module docs.
+pub(crate) struct ChunkedSpi<D> {
+    inner: D,
+    chunk: usize,
+}
+
+impl<D> ChunkedSpi<D> {
+    pub fn new(inner: D, chunk: usize) -> Self {
+        Self { inner, chunk }
+    }
+}
+
+impl<D: ErrorType> ErrorType for ChunkedSpi<D> {
+    type Error = D::Error;
+}
+
+impl<D: SpiDevice> SpiDevice for ChunkedSpi<D> {
+    async fn transaction(
+        &mut self,
+        operations: &mut [Operation<'_, u8>],
+    ) -> Result<(), Self::Error> {
+        let writes_only = operations
+            .iter()
+            .all(|operation| matches!(operation, Operation::Write(_)));
+
+        if !writes_only {
+            return self.inner.transaction(operations).await;
+        }
+
+        let mut first = true;
+
+        for operation in operations.iter() {
+            let Operation::Write(bytes) = operation else {
+                continue;
+            };
+
+            for chunk in bytes.chunks(self.chunk) {
+                if !first {
+                    // Lets a device waiting on the bus lock take it before the next chunk.
+                    yield_now().await;
+                }
+                first = false;
+
+                self.inner.write(chunk).await?;
+            }
+        }
+
+        Ok(())
+    }
+}
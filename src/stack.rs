@@ -0,0 +1,94 @@
+//! Stack high-water mark.
+//!
+//! The embassy tasks keep their state in the executor's task arena and all run on the one main
+//! stack, together with the interrupt handlers, so that stack is where the headroom for new
+//! buffers goes. [`paint`] fills the free part of it with [`PAINT`] at boot; the deepest point it
+//! has been down to since is where the paint first stops, counted from the end of the statics.
+//! [`usage`] scans for it on every call, which takes well under a millisecond on this RAM size.
+//! The simulator has no such stack and reports none.
+
+/// The pattern the free stack is filled with.
+pub(crate) const PAINT: u32 = 0xcccc_cccc;
+
+/// Bytes left unpainted below the stack pointer at boot, for the frames of [`paint`] itself.
+#[cfg(target_os = "none")]
+const PAINT_MARGIN: usize = 64;
+
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) struct StackUsage {
+    /// From the top of RAM down to the end of the statics.
+    pub size: usize,
+    /// The most the stack has taken since boot.
+    pub peak: usize,
+    /// RAM taken by statics, `.data`, `.bss` and `.uninit`.
+    pub statics: usize,
+}
+
+impl StackUsage {
+    pub fn free(&self) -> usize {
+        self.size - self.peak
+    }
+
+    pub fn peak_percent(&self) -> usize {
+        (self.peak * 100).checked_div(self.size).unwrap_or(0)
+    }
+}
+
+/// Words from the bottom of the stack, given bottom up, that still hold the paint.
+pub(crate) fn unused_words(stack: impl Iterator<Item = u32>) -> usize {
+    stack.take_while(|word| *word == PAINT).count()
+}
+
+#[cfg(target_os = "none")]
+extern "C" {
+    static mut __sdata: u32;
+    static mut __sheap: u32;
+    static mut _stack_start: u32;
+}
+
+/// The stack area, from the end of the statics up to the top of RAM.
+#[cfg(target_os = "none")]
+fn area() -> (*mut u32, usize) {
+    use core::ptr::addr_of_mut;
+
+    let bottom = unsafe { addr_of_mut!(__sheap) };
+    let top = unsafe { addr_of_mut!(_stack_start) };
+
+    (bottom, (top as usize - bottom as usize) / 4)
+}
+
+/// Fills the stack below the current frame with [`PAINT`]. Call once, early in `main`.
+#[cfg(target_os = "none")]
+pub(crate) fn paint() {
+    let (bottom, _) = area();
+    let end = cortex_m::register::msp::read() as usize - PAINT_MARGIN;
+
+    let mut word = bottom;
+    while (word as usize) < end {
+        unsafe {
+            word.write_volatile(PAINT);
+            word = word.add(1);
+        }
+    }
+}
+
+#[cfg(target_os = "none")]
+pub(crate) fn usage() -> Option<StackUsage> {
+    use core::ptr::addr_of_mut;
+
+    let (bottom, len) = area();
+    // Volatile reads, as the top of the area is the live stack.
+    let stack = (0..len).map(|i| unsafe { bottom.add(i).read_volatile() });
+    let statics = bottom as usize - unsafe { addr_of_mut!(__sdata) } as usize;
+
+    Some(StackUsage {
+        size: len * 4,
+        peak: (len - unused_words(stack)) * 4,
+        statics,
+    })
+}
+
+#[cfg(not(target_os = "none"))]
+pub(crate) fn usage() -> Option<StackUsage> {
+    None
+}
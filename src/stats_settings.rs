@@ -0,0 +1,55 @@
+//! Keeps the output statistics of `output_stats.rs` in the journal page below the settings page.
+//!
+//! Units from before the journal kept them in a record of the settings page, which is still read
+//! while the journal is empty.
+
+use embassy_stm32::flash::FLASH_BASE;
+
+use crate::{
+    log::{info, warn, Module},
+    output_stats::{journal_last, journal_next, OutputStats, ENTRY_LEN, JOURNAL_HEADER},
+    settings::{self, SettingsError, STATS_RECORD},
+    shared::{FLASH, OUTPUT_STATS_MUTEX},
+    updater::{PAGE_SIZE, STATS_START},
+};
+
+const LOG_MODULE: Module = Module::Output;
+
+const MAGIC: u32 = 0x5044_5354; // "PDST"
+
+/// Loads the stored counters into `OUTPUT_STATS_MUTEX`; none stored starts from zero.
+pub(crate) async fn load() {
+    let stats = journal_last(journal())
+        .or_else(|| settings::read(STATS_RECORD, MAGIC).map(|buf| OutputStats::from_bytes(&buf)));
+
+    info!("output stats: {:?}", stats);
+    *OUTPUT_STATS_MUTEX.lock().await = stats.unwrap_or(OutputStats::zero());
+}
+
+/// Appends `stats` to the journal, starting it over once the page is full; the measurement loop
+/// calls it at most every [`STATS_SAVE_INTERVAL`](crate::output_stats::STATS_SAVE_INTERVAL).
+pub(crate) async fn store(stats: OutputStats) -> Result<(), SettingsError> {
+    let next = journal_next(journal());
+
+    let mut flash = FLASH.lock().await;
+    let flash = flash.as_mut().ok_or(SettingsError::Unavailable)?;
+
+    let start = STATS_START - FLASH_BASE as u32;
+
+    let written = match next {
+        Some(next) => flash.blocking_write(start + (next * ENTRY_LEN) as u32, &stats.to_bytes()),
+        None => flash
+            .blocking_erase(start, start + PAGE_SIZE)
+            .and_then(|_| flash.blocking_write(start, &JOURNAL_HEADER))
+            .and_then(|_| flash.blocking_write(start + ENTRY_LEN as u32, &stats.to_bytes())),
+    };
+
+    written.map_err(|err| {
+        warn!("output stats write failed: {:?}", err);
+        SettingsError::Flash
+    })
+}
+
+fn journal() -> &'static [u8] {
+    unsafe { core::slice::from_raw_parts(STATS_START as *const u8, PAGE_SIZE as usize) }
+}
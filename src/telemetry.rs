@@ -0,0 +1,135 @@
+//! Telemetry events over defmt, for plotting on the host through the debug probe.
+//!
+//! Builds with the `telemetry` feature print the readings, faults and page changes as defmt lines
+//! in a fixed schema, so a tool on top of `probe-rs` can follow a unit on the bench without the
+//! console UART wired up. Every line starts with `tm` and [`SCHEMA`]; a change to the fields
+//! below bumps it, and host tools should skip lines of a schema they do not know.
+//!
+//! - `tm1 samples <seq> <start ms> <end ms> [mV, ...] [mA, ...] [mW, ...]`, one batch of
+//!   [`BATCH_LEN`] readings from the main loop, timestamps in milliseconds since boot. `seq` counts
+//!   the batches, so the host can tell when RTT dropped one.
+//! - `tm1 fault <fault>`, a fault latched for the first time since the last clear, by its
+//!   console name, which may contain spaces.
+//! - `tm1 page <page>`, the page the display changed to, see [`page_name`].
+//!
+//! The lines go out with `defmt::println!`, so they do not depend on the log levels or
+//! `DEFMT_LOG`. Without the feature the functions here do nothing.
+
+use heapless::Vec;
+
+use crate::{
+    types::{Page, PowerInfo},
+    units,
+};
+
+/// Version of the line format, the number after `tm`.
+pub(crate) const SCHEMA: u8 = 1;
+
+/// Readings per `samples` line.
+pub(crate) const BATCH_LEN: usize = 10;
+
+/// The readings of the main loop, collected for the next `samples` line.
+#[derive(PartialEq, Clone, Debug)]
+pub(crate) struct SampleBatch {
+    pub seq: u32,
+    pub start_ms: u64,
+    pub end_ms: u64,
+    pub millivolts: Vec<i32, BATCH_LEN>,
+    pub milliamps: Vec<i32, BATCH_LEN>,
+    pub milliwatts: Vec<i32, BATCH_LEN>,
+}
+
+impl SampleBatch {
+    pub const fn new() -> Self {
+        Self {
+            seq: 0,
+            start_ms: 0,
+            end_ms: 0,
+            millivolts: Vec::new(),
+            milliamps: Vec::new(),
+            milliwatts: Vec::new(),
+        }
+    }
+
+    /// Adds a reading taken at `uptime_ms`, returning whether the batch is full.
+    pub fn push(&mut self, uptime_ms: u64, power: &PowerInfo) -> bool {
+        if self.millivolts.is_empty() {
+            self.start_ms = uptime_ms;
+        }
+        self.end_ms = uptime_ms;
+
+        // All three have the same length.
+        self.millivolts.push(units::milli(power.volts)).ok();
+        self.milliamps.push(units::milli(power.amps)).ok();
+        self.milliwatts.push(units::milli(power.watts)).ok();
+
+        self.millivolts.is_full()
+    }
+
+    /// Starts the next batch, once this one is sent.
+    pub fn next(&mut self) {
+        self.seq = self.seq.wrapping_add(1);
+        self.millivolts.clear();
+        self.milliamps.clear();
+        self.milliwatts.clear();
+    }
+}
+
+/// The name of `page` in `page` lines, without the item or field it is at.
+pub(crate) fn page_name(page: Page) -> &'static str {
+    match page {
+        Page::Monitor => "monitor",
+        Page::Setting(_) => "settings",
+        Page::Voltage(_) => "voltage",
+        Page::UVP(_) => "uvp",
+        Page::OVP(_) => "ovp",
+        Page::OCP => "ocp",
+        Page::Output => "output",
+        Page::Watts => "watts",
+        Page::Average => "average",
+        Page::Measurement(_) => "measurement",
+        Page::Cable => "cable",
+        Page::Capture => "capture",
+        Page::Session => "session",
+        Page::Storage => "storage",
+        Page::Diagnostics(_) => "diagnostics",
+        Page::Display(_) => "display",
+        Page::Clock(_) => "clock",
+        Page::About(_) => "about",
+        Page::Setup(_) => "setup",
+    }
+}
+
+#[cfg(feature = "telemetry")]
+pub(crate) fn send_samples(batch: &SampleBatch) {
+    defmt::println!(
+        "tm{=u8} samples {=u32} {=u64} {=u64} {=[i32]} {=[i32]} {=[i32]}",
+        SCHEMA,
+        batch.seq,
+        batch.start_ms,
+        batch.end_ms,
+        &batch.millivolts[..],
+        &batch.milliamps[..],
+        &batch.milliwatts[..]
+    );
+}
+
+/// `name` is the console name of the fault.
+#[cfg(feature = "telemetry")]
+pub(crate) fn send_fault(name: &str) {
+    defmt::println!("tm{=u8} fault {=str}", SCHEMA, name);
+}
+
+#[cfg(feature = "telemetry")]
+pub(crate) fn send_page(page: Page) {
+    defmt::println!("tm{=u8} page {=str}", SCHEMA, page_name(page));
+}
+
+#[cfg(not(feature = "telemetry"))]
+pub(crate) fn send_samples(_batch: &SampleBatch) {}
+
+#[cfg(not(feature = "telemetry"))]
+pub(crate) fn send_fault(_name: &str) {}
+
+#[cfg(not(feature = "telemetry"))]
+pub(crate) fn send_page(_page: Page) {}
@@ -0,0 +1,16 @@
+//! Colors of the UI.
+
+use embedded_graphics::{pixelcolor::Rgb565, prelude::WebColors};
+
+pub const COLOR_PRIMARY: Rgb565 = Rgb565::CSS_DODGER_BLUE;
+pub const COLOR_SECONDARY: Rgb565 = Rgb565::CSS_TURQUOISE;
+pub const COLOR_BACKGROUND: Rgb565 = Rgb565::CSS_WHITE_SMOKE;
+pub const COLOR_PRIMARY_CONTENT: Rgb565 = Rgb565::CSS_WHITE;
+pub const COLOR_BASE: Rgb565 = WebColors::CSS_DARK_SLATE_GRAY;
+pub const COLOR_TEXT: Rgb565 = WebColors::CSS_DARK_SLATE_GRAY;
+pub const COLOR_TEXT_DISABLED: Rgb565 = WebColors::CSS_DARK_GRAY;
+pub const COLOR_VOLTAGE: Rgb565 = WebColors::CSS_DARK_ORANGE;
+pub const COLOR_AMPERAGE: Rgb565 = WebColors::CSS_ORANGE_RED;
+pub const COLOR_WATTAGE: Rgb565 = WebColors::CSS_FOREST_GREEN;
+pub const COLOR_ERROR: Rgb565 = WebColors::CSS_DARK_RED;
+pub const COLOR_INFO: Rgb565 = WebColors::CSS_STEEL_BLUE;
@@ -0,0 +1,89 @@
+//! Board temperature and the fan.
+//!
+//! Reads the MCU's internal temperature sensor once a second, publishes it for the status bar and
+//! sets the fan PWM from the curve in `FAN_CURVE_MUTEX`, see `fan.rs`. The MCU sits next to the
+//! output FET on the board, close enough to track its heating under load. The ADC is shared with
+//! the line monitor.
+
+use embassy_stm32::timer::Channel;
+use embassy_sync::{blocking_mutex::raw::CriticalSectionRawMutex, mutex::Mutex};
+use embassy_time::{Duration, Instant, Timer};
+
+use crate::{
+    bsp::ADC_VREF_MV,
+    fan::FanControl,
+    log::{info, Module},
+    shared::{FAN_CURVE_MUTEX, FAN_STATUS_MUTEX},
+    types::{AnalogAdc, FanPwm},
+};
+
+const LOG_MODULE: Module = Module::Thermal;
+
+const CHANNEL: Channel = Channel::Ch2;
+
+const INTERVAL: Duration = Duration::from_secs(1);
+
+/// Factory calibration of the sensor, the same address on the G0 and the L4: the reading at 30 °C
+/// with a 3.0 V reference.
+const TS_CAL1: *const u16 = 0x1FFF_75A8 as *const u16;
+const TS_CAL1_CELSIUS: i32 = 30;
+const TS_CAL_VDDA_MV: i32 = 3_000;
+/// Typical sensor slope, in µV per °C.
+const TS_SLOPE_UV: i32 = 2_500;
+const ADC_FULL_SCALE: i32 = 4_095;
+
+pub(crate) struct Thermal {
+    adc: &'static Mutex<CriticalSectionRawMutex, AnalogAdc>,
+    pwm: FanPwm,
+    control: FanControl,
+}
+
+impl Thermal {
+    pub fn new(adc: &'static Mutex<CriticalSectionRawMutex, AnalogAdc>, mut pwm: FanPwm) -> Self {
+        pwm.enable(CHANNEL);
+        pwm.set_duty(CHANNEL, 0);
+
+        Self {
+            adc,
+            pwm,
+            control: FanControl::new(),
+        }
+    }
+
+    pub async fn task(&mut self) {
+        let mut sensor = self.adc.lock().await.enable_temperature();
+
+        loop {
+            let raw = self.adc.lock().await.read(&mut sensor);
+            let celsius = celsius(raw);
+            let curve = *FAN_CURVE_MUTEX.lock().await;
+            let status = self.control.update(&curve, celsius, Instant::now());
+
+            let previous = FAN_STATUS_MUTEX.lock().await.replace(status);
+
+            if previous.map(|p| p.percent == 0) != Some(status.percent == 0) {
+                info!(
+                    "fan {} at {} C",
+                    if status.percent > 0 { "on" } else { "off" },
+                    celsius
+                );
+            }
+
+            let duty = self.pwm.get_max_duty() as u32 * status.percent as u32 / 100;
+            self.pwm.set_duty(CHANNEL, duty as u16);
+
+            Timer::after(INTERVAL).await;
+        }
+    }
+}
+
+fn celsius(raw: u16) -> i16 {
+    // SAFETY: a read-only word in the system memory, present on every part.
+    let cal = unsafe { core::ptr::read_volatile(TS_CAL1) } as i32;
+
+    // The reading as it would have been with the calibration's reference.
+    let at_cal_vdda = raw as i32 * ADC_VREF_MV as i32 / TS_CAL_VDDA_MV;
+    let uv = (at_cal_vdda - cal) * TS_CAL_VDDA_MV * 1_000 / ADC_FULL_SCALE;
+
+    (TS_CAL1_CELSIUS + uv / TS_SLOPE_UV) as i16
+}
@@ -0,0 +1,129 @@
+//! Execution time of the sections of the measurement loop.
+//!
+//! The main loop times each section with `Instant` and records it here; the diagnostics page and
+//! the `timing` console command show the mean and the worst case. `ocp` runs from the start of the
+//! INA226 reads to the protection decision, so with the chip's conversion time it bounds how long
+//! an over-current takes to trip the output.
+//!
+//! Only the main loop records, so as in [`heartbeat`](crate::heartbeat) plain atomic loads and
+//! stores are enough.
+
+use core::sync::atomic::{AtomicU32, Ordering};
+
+use embassy_time::Duration;
+
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) enum Section {
+    /// One pass of the main loop, without the sleep between passes.
+    Loop,
+    /// Volts, amps and watts from the INA226.
+    Read,
+    /// From the start of the reads to the over-current check.
+    Ocp,
+    /// Display task and the reading updates on the screen.
+    Display,
+    /// Contract polling and renegotiation on the HUSB238.
+    Pd,
+}
+
+pub(crate) const SECTIONS: [Section; 5] = [
+    Section::Loop,
+    Section::Read,
+    Section::Ocp,
+    Section::Display,
+    Section::Pd,
+];
+
+impl Section {
+    /// Short enough for the name column of the diagnostics page.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Section::Loop => "loop",
+            Section::Read => "read",
+            Section::Ocp => "ocp",
+            Section::Display => "disp",
+            Section::Pd => "pd",
+        }
+    }
+}
+
+struct Timing {
+    count: AtomicU32,
+    total_us: AtomicU32,
+    max_us: AtomicU32,
+}
+
+impl Timing {
+    const fn new() -> Self {
+        Self {
+            count: AtomicU32::new(0),
+            total_us: AtomicU32::new(0),
+            max_us: AtomicU32::new(0),
+        }
+    }
+}
+
+static TIMINGS: [Timing; SECTIONS.len()] = [
+    Timing::new(),
+    Timing::new(),
+    Timing::new(),
+    Timing::new(),
+    Timing::new(),
+];
+
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) struct SectionStats {
+    pub section: Section,
+    pub count: u32,
+    pub mean_us: u32,
+    pub max_us: u32,
+}
+
+/// Records one run of `section` that took `elapsed`.
+pub(crate) fn record(section: Section, elapsed: Duration) {
+    let timing = &TIMINGS[section as usize];
+    let us = elapsed.as_micros().min(u32::MAX as u64) as u32;
+
+    let mut count = timing.count.load(Ordering::Relaxed);
+    let mut total = timing.total_us.load(Ordering::Relaxed);
+
+    // Halving both keeps the mean while making room; older runs simply weigh less.
+    if total.checked_add(us).is_none() || count == u32::MAX {
+        count /= 2;
+        total /= 2;
+    }
+
+    timing.count.store(count + 1, Ordering::Relaxed);
+    timing
+        .total_us
+        .store(total.saturating_add(us), Ordering::Relaxed);
+
+    if us > timing.max_us.load(Ordering::Relaxed) {
+        timing.max_us.store(us, Ordering::Relaxed);
+    }
+}
+
+pub(crate) fn stats(section: Section) -> SectionStats {
+    let timing = &TIMINGS[section as usize];
+    let count = timing.count.load(Ordering::Relaxed);
+
+    let mean_us = match count {
+        0 => 0,
+        _ => timing.total_us.load(Ordering::Relaxed) / count,
+    };
+
+    SectionStats {
+        section,
+        count,
+        mean_us,
+        max_us: timing.max_us.load(Ordering::Relaxed),
+    }
+}
+
+pub(crate) fn reset() {
+    for timing in TIMINGS.iter() {
+        timing.count.store(0, Ordering::Relaxed);
+        timing.total_us.store(0, Ordering::Relaxed);
+        timing.max_us.store(0, Ordering::Relaxed);
+    }
+}
@@ -0,0 +1,185 @@
+//! Trigger output for synchronizing an external scope or logger.
+//!
+//! The pin pulses high for [`PULSE_WIDTH`], or toggles, when one of the selected events happens.
+//! The main loop reports the events as it sees them, so the edge lags the cause by at most the
+//! rest of a loop pass. Events and mode are set with the `trigger` console command.
+
+use core::convert::Infallible;
+
+use embassy_time::{block_for, Duration};
+use embedded_hal::digital::OutputPin;
+
+/// Long enough for a logger polling at 10 kHz, short enough to busy-wait in the main loop.
+const PULSE_WIDTH: Duration = Duration::from_micros(100);
+
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) enum TriggerEvent {
+    /// The OCP, UVP, OVP or interlock turned the output off.
+    Trip,
+    /// The output went on or off, for any reason.
+    Output,
+    /// The load-current capture triggered.
+    Capture,
+}
+
+pub(crate) const TRIGGER_EVENTS: [TriggerEvent; 3] = [
+    TriggerEvent::Trip,
+    TriggerEvent::Output,
+    TriggerEvent::Capture,
+];
+
+impl TriggerEvent {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TriggerEvent::Trip => "trip",
+            TriggerEvent::Output => "output",
+            TriggerEvent::Capture => "capture",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "trip" => Some(TriggerEvent::Trip),
+            "output" => Some(TriggerEvent::Output),
+            "capture" => Some(TriggerEvent::Capture),
+            _ => None,
+        }
+    }
+
+    const fn bit(self) -> u8 {
+        1 << self as u8
+    }
+}
+
+/// Set of events the pin reacts to.
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) struct TriggerEvents(u8);
+
+impl TriggerEvents {
+    pub const fn empty() -> Self {
+        Self(0)
+    }
+
+    pub const fn with(self, event: TriggerEvent) -> Self {
+        Self(self.0 | event.bit())
+    }
+
+    pub fn contains(&self, event: TriggerEvent) -> bool {
+        self.0 & event.bit() != 0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+}
+
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) enum TriggerMode {
+    Pulse,
+    Toggle,
+}
+
+impl TriggerMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TriggerMode::Pulse => "pulse",
+            TriggerMode::Toggle => "toggle",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "pulse" => Some(TriggerMode::Pulse),
+            "toggle" => Some(TriggerMode::Toggle),
+            _ => None,
+        }
+    }
+}
+
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) struct TriggerConfig {
+    pub events: TriggerEvents,
+    pub mode: TriggerMode,
+}
+
+impl TriggerConfig {
+    /// Pulses on trips only.
+    pub const fn default() -> Self {
+        Self {
+            events: TriggerEvents::empty().with(TriggerEvent::Trip),
+            mode: TriggerMode::Pulse,
+        }
+    }
+}
+
+pub(crate) struct Trigger<PIN>
+where
+    PIN: OutputPin<Error = Infallible>,
+{
+    pin: PIN,
+    high: bool,
+    config: TriggerConfig,
+    /// The output state last reported, to turn [`follow_output`](Self::follow_output) into events.
+    output: bool,
+}
+
+impl<PIN> Trigger<PIN>
+where
+    PIN: OutputPin<Error = Infallible>,
+{
+    pub fn new(mut pin: PIN) -> Self {
+        pin.set_low().ok();
+
+        Self {
+            pin,
+            high: false,
+            config: TriggerConfig::default(),
+            output: false,
+        }
+    }
+
+    /// Takes a new config from the console. Switching the mode starts again from a low pin, so
+    /// the first pulse after a toggle is not lost.
+    pub fn configure(&mut self, config: TriggerConfig) {
+        if config.mode != self.config.mode {
+            self.set(false);
+        }
+
+        self.config = config;
+    }
+
+    /// Pulses or toggles the pin if the config selects `event`.
+    pub fn fire(&mut self, event: TriggerEvent) {
+        if !self.config.events.contains(event) {
+            return;
+        }
+
+        match self.config.mode {
+            TriggerMode::Pulse => {
+                self.set(true);
+                block_for(PULSE_WIDTH);
+                self.set(false);
+            }
+            TriggerMode::Toggle => self.set(!self.high),
+        }
+    }
+
+    /// Fires [`TriggerEvent::Output`] when `enabled` differs from the last call. Cheap enough to
+    /// call after everything that may switch the output.
+    pub fn follow_output(&mut self, enabled: bool) {
+        if enabled != self.output {
+            self.output = enabled;
+            self.fire(TriggerEvent::Output);
+        }
+    }
+
+    fn set(&mut self, high: bool) {
+        if high {
+            self.pin.set_high().ok();
+        } else {
+            self.pin.set_low().ok();
+        }
+
+        self.high = high;
+    }
+}
@@ -1,86 +1,389 @@
-use embassy_embedded_hal::shared_bus::asynch::spi::SpiDevice;
-use embassy_stm32::peripherals;
-use embassy_stm32::{gpio::Output, spi::Spi};
-use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
+#[cfg(target_os = "none")]
+use embassy_stm32::time::Hertz;
+use embassy_time::Duration;
+use heapless::Vec;
 use husb238::{Current, SrcPdo, Voltage};
-use st7789::ST7789;
 
-#[derive(Debug, Clone, Copy, defmt::Format)]
+use crate::{
+    protection::VoltageLimit,
+    setup::SetupStep,
+    units::{self, Value, ZERO},
+};
+
+#[cfg(target_os = "none")]
+pub(crate) use hw::*;
+
+#[derive(PartialEq, Debug, Clone, Copy, defmt::Format)]
 pub struct PowerInfo {
-    pub amps: f64,
-    pub volts: f64,
-    pub watts: f64,
+    pub amps: Value,
+    pub volts: Value,
+    pub watts: Value,
 }
 
-impl Default for PowerInfo {
-    fn default() -> Self {
+impl PowerInfo {
+    pub const fn default() -> Self {
         Self {
-            amps: 0.0,
-            volts: 0.0,
-            watts: 0.0,
+            amps: ZERO,
+            volts: ZERO,
+            watts: ZERO,
         }
     }
 }
 
 #[derive(Debug, Clone, Copy, defmt::Format)]
 pub struct StatusInfo {
-    pub target_volts: f64,
-    pub limit_amps: f64,
+    pub target_volts: Value,
+    pub limit_amps: Value,
     pub output: bool,
 }
 
-impl Default for StatusInfo {
-    fn default() -> Self {
+impl StatusInfo {
+    pub const fn default() -> Self {
         Self {
-            target_volts: 0.0,
-            limit_amps: 0.0,
+            target_volts: ZERO,
+            limit_amps: ZERO,
             output: false,
         }
     }
 }
 
-pub(crate) type SpiBus =
-    Spi<'static, peripherals::SPI1, peripherals::DMA1_CH1, peripherals::DMA1_CH2>;
-
-pub(crate) type ST7789CSPin = Output<'static, embassy_stm32::peripherals::PA4>;
-pub(crate) type ST7789DCPin = Output<'static, embassy_stm32::peripherals::PA15>;
-pub(crate) type ST7789RstPin = Output<'static, embassy_stm32::peripherals::PA12>;
+/// How far the first PD contract got, shown on the splash screen at boot.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub(crate) enum Negotiation {
+    /// Still waiting for the source, with the spinner frame.
+    Pending(u8),
+    /// The contract's volts and amps.
+    Done(Value, Value),
+    /// No source answered in time.
+    Failed,
+}
 
-pub(crate) type ST7789SpiDev = SpiDevice<'static, CriticalSectionRawMutex, SpiBus, ST7789CSPin>;
+/// What the status bar shows on every page, gathered by the main loop on each pass.
+#[derive(PartialEq, Debug, Clone, Copy, defmt::Format)]
+pub struct SystemStatus {
+    pub output: bool,
+    /// An over-current trip holds the output off until it is reset.
+    pub tripped: bool,
+    /// The PD contract.
+    pub target_volts: Value,
+    pub limit_amps: Value,
+    /// The output is held off by something other than the user.
+    pub locked: bool,
+    pub remote: bool,
+    /// Any latched fault.
+    pub alarm: bool,
+    /// Too many over-current trips latched the output off, see `protection.rs`.
+    pub fuse_blown: bool,
+    /// The OCP is held below the user's by a smaller contract.
+    pub derated: bool,
+    /// Board temperature in degrees Celsius, on builds with a sensor.
+    pub temperature: Option<i16>,
+    /// Whether the external interlock is closed, on builds with one.
+    pub interlock: Option<bool>,
+}
 
-pub(crate) type ST7789Display = ST7789<ST7789SpiDev, ST7789DCPin, ST7789RstPin>;
+impl SystemStatus {
+    pub const fn default() -> Self {
+        Self {
+            output: false,
+            tripped: false,
+            target_volts: ZERO,
+            limit_amps: ZERO,
+            locked: false,
+            remote: false,
+            alarm: false,
+            fuse_blown: false,
+            derated: false,
+            temperature: None,
+            interlock: None,
+        }
+    }
+}
 
 #[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
 pub(crate) enum Page {
     Monitor,
     Setting(SettingItem),
     Voltage(SrcPdo),
-    UVP,
+    UVP(LimitField),
+    OVP(LimitField),
     OCP,
-    About,
+    Output,
+    Watts,
+    Average,
+    Measurement(MeasurementItem),
+    Cable,
+    Capture,
+    Session,
+    Storage,
+    Diagnostics(DiagnosticsView),
+    Display(DisplayItem),
+    Clock(ClockField),
+    About(AboutView),
+    /// The setup wizard of a new unit, see `setup.rs`.
+    Setup(SetupStep),
+}
+
+/// Which threshold of a voltage limit the UVP or OVP page edits; Up and Down together go from the
+/// trip to the recovery threshold.
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) enum LimitField {
+    Trip,
+    Recover,
+}
+
+impl LimitField {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LimitField::Trip => "Trip",
+            LimitField::Recover => "Recover",
+        }
+    }
+
+    pub fn of(&self, limit: &VoltageLimit) -> Value {
+        match self {
+            LimitField::Trip => limit.trip,
+            LimitField::Recover => limit.recover,
+        }
+    }
+
+    pub fn set(&self, limit: &mut VoltageLimit, value: Value) {
+        match self {
+            LimitField::Trip => limit.trip = value,
+            LimitField::Recover => limit.recover = value,
+        }
+    }
+}
+
+/// Fields of the clock page, in the order Up and Down together step through them.
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) enum ClockField {
+    Year,
+    Month,
+    Day,
+    Hour,
+    Minute,
+}
+
+pub(crate) const CLOCK_FIELDS: &[ClockField] = &[
+    ClockField::Year,
+    ClockField::Month,
+    ClockField::Day,
+    ClockField::Hour,
+    ClockField::Minute,
+];
+
+/// What the diagnostics page lists; Up and Down switch between them.
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) enum DiagnosticsView {
+    /// Heartbeat age and loop latency of each task.
+    Tasks,
+    /// Execution time of the sections of the measurement loop.
+    Timing,
+    /// The INA226 averaging and conversion time, with the reading rate and bandwidth they make.
+    Conversion,
+    /// D+ and D- levels and the legacy charger signature they show.
+    DataLines,
+    /// CC1 and CC2 levels, the Rp advertisement and the plug orientation.
+    CcLines,
+    /// The commanded and sensed output and whether they agree.
+    Output,
+    /// The contract and VBUS voltages, the drops on the way to the output and the switch loss.
+    Rails,
+    /// The last reset cause and the resets counted per cause.
+    Resets,
+    /// The stack high-water mark and the RAM taken by statics.
+    Memory,
+}
+
+impl DiagnosticsView {
+    pub fn next(&self) -> Self {
+        match self {
+            DiagnosticsView::Tasks => DiagnosticsView::Timing,
+            DiagnosticsView::Timing => DiagnosticsView::Conversion,
+            DiagnosticsView::Conversion => DiagnosticsView::DataLines,
+            DiagnosticsView::DataLines => DiagnosticsView::CcLines,
+            DiagnosticsView::CcLines => DiagnosticsView::Output,
+            DiagnosticsView::Output => DiagnosticsView::Rails,
+            DiagnosticsView::Rails => DiagnosticsView::Resets,
+            DiagnosticsView::Resets => DiagnosticsView::Memory,
+            DiagnosticsView::Memory => DiagnosticsView::Tasks,
+        }
+    }
+
+    pub fn prev(&self) -> Self {
+        match self {
+            DiagnosticsView::Tasks => DiagnosticsView::Memory,
+            DiagnosticsView::Timing => DiagnosticsView::Tasks,
+            DiagnosticsView::Conversion => DiagnosticsView::Timing,
+            DiagnosticsView::DataLines => DiagnosticsView::Conversion,
+            DiagnosticsView::CcLines => DiagnosticsView::DataLines,
+            DiagnosticsView::Output => DiagnosticsView::CcLines,
+            DiagnosticsView::Rails => DiagnosticsView::Output,
+            DiagnosticsView::Resets => DiagnosticsView::Rails,
+            DiagnosticsView::Memory => DiagnosticsView::Resets,
+        }
+    }
+}
+
+/// The sub-pages of the About page, which Up and Down page through: the unit itself, then a
+/// cheat-sheet of the button gestures for each part of the menu, see `help.rs`.
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) enum AboutView {
+    /// Author, version, the output's lifetime counters and the last reset cause.
+    Info,
+    Monitor,
+    /// The settings list and the option pages.
+    Settings,
+    /// Cable, Scope, Session and SD card.
+    Tools,
+    /// Diagnostics and About.
+    Diagnostics,
+    /// What works on every page.
+    Anywhere,
+}
+
+pub(crate) const ABOUT_VIEWS: [AboutView; 6] = [
+    AboutView::Info,
+    AboutView::Monitor,
+    AboutView::Settings,
+    AboutView::Tools,
+    AboutView::Diagnostics,
+    AboutView::Anywhere,
+];
+
+impl AboutView {
+    pub fn next(&self) -> Self {
+        ABOUT_VIEWS[(*self as usize + 1) % ABOUT_VIEWS.len()]
+    }
+
+    pub fn prev(&self) -> Self {
+        ABOUT_VIEWS[(*self as usize + ABOUT_VIEWS.len() - 1) % ABOUT_VIEWS.len()]
+    }
 }
 
 #[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
 pub(crate) enum SettingItem {
     Voltage,
     UVP,
+    OVP,
     OCP,
+    Output,
+    Watts,
+    Average,
+    /// The INA226 averaging and conversion time.
+    Measurement,
+    Cable,
+    Capture,
+    Session,
+    /// The SD card log, on builds with one.
+    Storage,
+    Diagnostics,
+    Display,
+    Clock,
     About,
 }
 
+impl SettingItem {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SettingItem::Voltage => "PDO",
+            SettingItem::UVP => "UVP",
+            SettingItem::OVP => "OVP",
+            SettingItem::OCP => "OCP",
+            SettingItem::Output => "Output",
+            SettingItem::Watts => "Watts",
+            SettingItem::Average => "Average",
+            SettingItem::Measurement => "Measure",
+            SettingItem::Cable => "Cable",
+            SettingItem::Capture => "Scope",
+            SettingItem::Session => "Session",
+            SettingItem::Storage => "SD card",
+            SettingItem::Diagnostics => "Diagnostics",
+            SettingItem::Display => "Display",
+            SettingItem::Clock => "Clock",
+            SettingItem::About => "About",
+        }
+    }
+}
+
 pub(crate) const SETTING_ITEMS: &[SettingItem] = &[
     SettingItem::Voltage,
     SettingItem::UVP,
+    SettingItem::OVP,
     SettingItem::OCP,
+    SettingItem::Output,
+    SettingItem::Watts,
+    SettingItem::Average,
+    SettingItem::Measurement,
+    SettingItem::Cable,
+    SettingItem::Capture,
+    SettingItem::Session,
+    SettingItem::Storage,
+    SettingItem::Diagnostics,
+    SettingItem::Display,
+    SettingItem::Clock,
     SettingItem::About,
 ];
 
+/// Options of the display page, in the order Up and Down together step through them.
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) enum DisplayItem {
+    Rotation,
+    Theme,
+    Brightness,
+    /// Seconds before the backlight dims.
+    Timeout,
+}
+
+pub(crate) const DISPLAY_ITEMS: &[DisplayItem] = &[
+    DisplayItem::Rotation,
+    DisplayItem::Theme,
+    DisplayItem::Brightness,
+    DisplayItem::Timeout,
+];
+
+/// Options of the measurement page, in the order Up and Down together step through them.
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) enum MeasurementItem {
+    Averaging,
+    ConversionTime,
+}
+
+pub(crate) const MEASUREMENT_ITEMS: &[MeasurementItem] =
+    &[MeasurementItem::Averaging, MeasurementItem::ConversionTime];
+
 #[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
 pub(crate) enum Direction {
     Normal,
     Reversed,
 }
 
+impl Direction {
+    pub fn other(&self) -> Self {
+        match self {
+            Direction::Normal => Direction::Reversed,
+            Direction::Reversed => Direction::Normal,
+        }
+    }
+}
+
+/// Dark inverts the panel, so the light background turns near black.
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) enum Theme {
+    Light,
+    Dark,
+}
+
+impl Theme {
+    pub fn other(&self) -> Self {
+        match self {
+            Theme::Light => Theme::Dark,
+            Theme::Dark => Theme::Light,
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, defmt::Format)]
 pub(crate) struct AvailableVoltCurr {
     pub _5v: Option<Current>,
@@ -102,6 +405,35 @@ impl AvailableVoltCurr {
             _20v: None,
         }
     }
+
+    /// The source's fixed supply voltages, lowest first. 5 V is always offered.
+    pub fn voltages(&self) -> Vec<SrcPdo, 6> {
+        let mut vec: Vec<SrcPdo, 6> = Vec::new();
+
+        vec.push(SrcPdo::_5v).unwrap();
+
+        if self._9v.is_some() {
+            vec.push(SrcPdo::_9v).unwrap();
+        }
+
+        if self._12v.is_some() {
+            vec.push(SrcPdo::_12v).unwrap();
+        }
+
+        if self._15v.is_some() {
+            vec.push(SrcPdo::_15v).unwrap();
+        }
+
+        if self._18v.is_some() {
+            vec.push(SrcPdo::_18v).unwrap();
+        }
+
+        if self._20v.is_some() {
+            vec.push(SrcPdo::_20v).unwrap();
+        }
+
+        vec
+    }
 }
 
 pub(crate) static VOLTAGE_ITEMS: &[SrcPdo] = &[
@@ -112,3 +444,259 @@ pub(crate) static VOLTAGE_ITEMS: &[SrcPdo] = &[
     SrcPdo::_18v,
     SrcPdo::_20v,
 ];
+
+/// Allowed deviation between the selected PDO and the negotiated contract voltage, in percent.
+pub const PDO_VOLTAGE_TOLERANCE_PERCENT: i32 = 5;
+
+/// The OCP threshold to use on a new contract of `contract_amps`: anything above it, or no
+/// threshold at all, would let the source's own protection trip first, so both become the contract
+/// current. A lower threshold is kept.
+pub(crate) fn capped_ocp(ocp: Value, contract_amps: Value) -> Value {
+    if contract_amps <= ZERO || (ocp > ZERO && ocp <= contract_amps) {
+        return ocp;
+    }
+
+    contract_amps
+}
+
+/// Whether the source granted `selected`, judged by the contract voltage.
+pub(crate) fn pdo_matches(selected: SrcPdo, target_volts: Value) -> bool {
+    let expected = pdo_volts(selected);
+    let diff = target_volts - expected;
+    let tolerance = units::percent(expected, PDO_VOLTAGE_TOLERANCE_PERCENT);
+
+    diff <= tolerance && diff >= -tolerance
+}
+
+pub(crate) fn pdo_volts(pdo: SrcPdo) -> Value {
+    units::from_milli(match pdo {
+        SrcPdo::_5v => 5_000,
+        SrcPdo::_9v => 9_000,
+        SrcPdo::_12v => 12_000,
+        SrcPdo::_15v => 15_000,
+        SrcPdo::_18v => 18_000,
+        SrcPdo::_20v => 20_000,
+        _ => 0,
+    })
+}
+
+/// The highest of `available` at or below `pdo` in voltage, for a selection the source no longer
+/// offers, so that the fallback never raises the voltage on the load. `pdo` itself when it is
+/// available, 5 V when nothing at or below it is.
+pub(crate) fn nearest_pdo(pdo: SrcPdo, available: &[SrcPdo]) -> SrcPdo {
+    let volts = pdo_volts(pdo);
+
+    available
+        .iter()
+        .copied()
+        .filter(|candidate| pdo_volts(*candidate) <= volts)
+        .max_by_key(|candidate| units::milli(pdo_volts(*candidate)))
+        .unwrap_or(SrcPdo::_5v)
+}
+
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) enum ControlSource {
+    Local,
+    Remote,
+    /// The daily output schedule, see `schedule.rs`.
+    Schedule,
+}
+
+/// How the output follows a long press of Up on the monitor page.
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) enum OutputMode {
+    /// Each long press toggles the output.
+    Latching,
+    /// The output is on only while Up is held, like a dead man's switch.
+    Momentary,
+}
+
+impl OutputMode {
+    /// Whether a request from `source` may turn the output on. Only the hand on the button holds
+    /// a momentary output, so nothing else turns it on; anything may turn it off.
+    pub fn takes(&self, enabled: bool, source: ControlSource) -> bool {
+        !enabled || *self == OutputMode::Latching || source == ControlSource::Local
+    }
+}
+
+impl OutputMode {
+    pub fn other(&self) -> Self {
+        match self {
+            OutputMode::Latching => OutputMode::Momentary,
+            OutputMode::Momentary => OutputMode::Latching,
+        }
+    }
+}
+
+/// An output of the board. Only `dual-output` builds have the second one, see
+/// `second_output.rs`.
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) enum Channel {
+    A,
+    B,
+}
+
+impl Channel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Channel::A => "CH1",
+            Channel::B => "CH2",
+        }
+    }
+
+    pub fn other(&self) -> Self {
+        match self {
+            Channel::A => Channel::B,
+            Channel::B => Channel::A,
+        }
+    }
+}
+
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) struct OutputRequest {
+    pub enabled: bool,
+    pub source: ControlSource,
+    pub channel: Channel,
+}
+
+/// A PDO selection for the main loop to negotiate. The HUSB238 requests a fixed PDO at the
+/// current the source advertises for it, so there is no current to ask for.
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) struct PdRequest {
+    pub pdo: SrcPdo,
+    pub source: ControlSource,
+}
+
+/// Handshake between the idle task and the main loop, see `idle.rs`.
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) enum PowerState {
+    Active,
+    /// Requested by the idle task; the main loop blanks the display and parks.
+    Suspending,
+    /// The main loop is parked, STOP mode may be entered.
+    Suspended,
+}
+
+/// Trades display refresh rate for self-consumption.
+///
+/// SYSCLK stays at 16 MHz in every profile: embassy-stm32 derives the time driver prescaler and
+/// all peripheral timings from it once at init, so a clock changed at runtime would stretch every
+/// timer. Slower profiles update the display less often and clock its SPI down, and leave the
+/// sensor I2C at 100 kHz. The readings and the protection checks on them never wait for the
+/// display, they run at the same rate in every profile.
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) enum PowerProfile {
+    Performance,
+    Balanced,
+    Eco,
+}
+
+impl PowerProfile {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PowerProfile::Performance => "performance",
+            PowerProfile::Balanced => "balanced",
+            PowerProfile::Eco => "eco",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "performance" => Some(PowerProfile::Performance),
+            "balanced" => Some(PowerProfile::Balanced),
+            "eco" => Some(PowerProfile::Eco),
+            _ => None,
+        }
+    }
+
+    /// Least time between two updates of the readings on the display.
+    pub fn refresh_interval(&self) -> Duration {
+        match self {
+            PowerProfile::Performance => Duration::from_millis(0),
+            PowerProfile::Balanced => Duration::from_millis(50),
+            PowerProfile::Eco => Duration::from_millis(250),
+        }
+    }
+
+    #[cfg(target_os = "none")]
+    pub fn spi_frequency(&self) -> Hertz {
+        match self {
+            PowerProfile::Performance => Hertz(16_000_000),
+            PowerProfile::Balanced => Hertz(8_000_000),
+            PowerProfile::Eco => Hertz(2_000_000),
+        }
+    }
+
+    /// Clock of the sensor bus; the INA226 and the HUSB238 both take fast mode.
+    #[cfg(target_os = "none")]
+    pub fn i2c_frequency(&self) -> Hertz {
+        match self {
+            PowerProfile::Performance => Hertz(400_000),
+            PowerProfile::Balanced | PowerProfile::Eco => Hertz(100_000),
+        }
+    }
+}
+
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) enum WifiState {
+    Disabled,
+    Disconnected,
+    Connecting,
+    Connected,
+}
+
+/// Concrete driver types. Only built for the target; the simulator reuses the rest of this file.
+#[cfg(target_os = "none")]
+mod hw {
+    use embassy_embedded_hal::shared_bus::asynch::spi::SpiDevice;
+    #[cfg(feature = "adc")]
+    use embassy_stm32::adc::Adc;
+    #[cfg(any(feature = "modbus", feature = "wifi"))]
+    use embassy_stm32::usart::BufferedUart;
+    use embassy_stm32::{
+        gpio::Output,
+        i2c::I2c,
+        spi::Spi,
+        timer::simple_pwm::SimplePwm,
+        usart::{UartRx, UartTx},
+    };
+    use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
+    use st7789::ST7789;
+
+    use crate::{bsp, spi_bus::ChunkedSpi};
+
+    pub(crate) type SpiBus = Spi<'static, bsp::DisplaySpi, bsp::DisplayTxDma, bsp::DisplayRxDma>;
+
+    pub(crate) type ST7789CSPin = Output<'static, bsp::DisplayCsPin>;
+    pub(crate) type ST7789DCPin = Output<'static, bsp::DisplayDcPin>;
+    pub(crate) type ST7789RstPin = Output<'static, bsp::DisplayRstPin>;
+    #[cfg(feature = "sd-log")]
+    pub(crate) type SdCs = Output<'static, bsp::SdCsPin>;
+
+    pub(crate) type ST7789SpiDev =
+        ChunkedSpi<SpiDevice<'static, CriticalSectionRawMutex, SpiBus, ST7789CSPin>>;
+
+    pub(crate) type ST7789Display = ST7789<ST7789SpiDev, ST7789DCPin, ST7789RstPin>;
+
+    /// Also drives the output FET, see `pwm_timer.rs`.
+    pub(crate) type BacklightPwm = SimplePwm<'static, bsp::BacklightTim>;
+
+    #[cfg(feature = "fan")]
+    pub(crate) type FanPwm = SimplePwm<'static, bsp::FanTim>;
+    /// Shared by the thermal and line monitor tasks.
+    #[cfg(feature = "adc")]
+    pub(crate) type AnalogAdc = Adc<'static, bsp::AdcPeriph>;
+
+    pub(crate) type SensorI2cBus = I2c<'static, bsp::SensorI2c, bsp::SensorTxDma, bsp::SensorRxDma>;
+
+    pub(crate) type ConsoleTx = UartTx<'static, bsp::ConsoleUsart, bsp::ConsoleTxDma>;
+    pub(crate) type ConsoleRx = UartRx<'static, bsp::ConsoleUsart, bsp::ConsoleRxDma>;
+
+    #[cfg(feature = "modbus")]
+    pub(crate) type ModbusUart = BufferedUart<'static, bsp::ModbusUsart>;
+    #[cfg(feature = "modbus")]
+    pub(crate) type ModbusDePin = Output<'static, bsp::ModbusDePin>;
+
+    #[cfg(feature = "wifi")]
+    pub(crate) type WifiUart = BufferedUart<'static, bsp::WifiUsart>;
+}
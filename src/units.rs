@@ -0,0 +1,157 @@
+//! Representation of measured and configured quantities.
+//!
+//! Volts, amps and watts are `f64` by default. With the `fixed-point` feature they are `i32`
+//! thousandths (mV, mA, mW) from the INA226 registers through protection, history and the screen,
+//! which keeps soft-float routines out of the Cortex-M0+ image. Code handling readings goes through
+//! [`Value`] and the helpers here rather than assuming either form; conversions from `f64` are
+//! left to the edges that only have floats, such as the HUSB238 driver and the console.
+
+#[cfg(not(feature = "fixed-point"))]
+mod repr {
+    use embassy_time::Duration;
+
+    use crate::fmt::{fixed as format, Fixed};
+
+    /// Volts, amps or watts.
+    pub(crate) type Value = f64;
+    /// [`Value`] as kept in the history ring.
+    pub(crate) type Compact = f32;
+    /// Watt-hours.
+    pub(crate) type Energy = f64;
+
+    pub(crate) const ZERO: Value = 0.0;
+    pub(crate) const NO_ENERGY: Energy = 0.0;
+
+    /// The value of `milli` thousandths of a unit.
+    pub(crate) const fn from_milli(milli: i32) -> Value {
+        milli as f64 / 1000.0
+    }
+
+    /// `value` in thousandths of a unit, truncated.
+    pub(crate) fn milli(value: Value) -> i32 {
+        (value * 1000.0) as i32
+    }
+
+    pub(crate) fn from_f64(value: f64) -> Value {
+        value
+    }
+
+    pub(crate) fn to_f64(value: Value) -> f64 {
+        value
+    }
+
+    /// Product of two quantities, e.g. volts times amps.
+    pub(crate) fn mul(a: Value, b: Value) -> Value {
+        a * b
+    }
+
+    /// `value` divided by a count, for averages.
+    pub(crate) fn div(value: Value, n: u32) -> Value {
+        value / n as f64
+    }
+
+    pub(crate) fn percent(value: Value, percent: i32) -> Value {
+        value * percent as f64 / 100.0
+    }
+
+    pub(crate) fn compact(value: Value) -> Compact {
+        value as f32
+    }
+
+    pub(crate) fn expand(value: Compact) -> Value {
+        value as f64
+    }
+
+    /// Adds `watts` drawn for `elapsed` to `energy`. A pass whose power could not be read adds
+    /// nothing, rather than the last good reading over the gap.
+    pub(crate) fn add_energy(energy: &mut Energy, watts: Option<Value>, elapsed: Duration) {
+        if let Some(watts) = watts {
+            *energy += watts * elapsed.as_micros() as f64 / 3_600_000_000.0;
+        }
+    }
+
+    /// `energy` in watt-hours.
+    pub(crate) fn energy_wh(energy: Energy) -> Value {
+        energy
+    }
+
+    /// [`fmt::fixed`](crate::fmt::fixed) for a [`Value`]. The fixed-point build has no more
+    /// than three significant decimals; further places are zeros.
+    pub(crate) fn fixed(value: Value, decimals: u8, width: usize) -> Fixed {
+        format(value, decimals, width)
+    }
+}
+
+#[cfg(feature = "fixed-point")]
+mod repr {
+    use embassy_time::Duration;
+
+    use crate::fmt::{fixed_milli, Fixed};
+
+    /// Millivolts, milliamps or milliwatts.
+    pub(crate) type Value = i32;
+    pub(crate) type Compact = i32;
+    /// Nanojoules (mW·µs), fine enough that the short steps of the main loop do not truncate to
+    /// nothing.
+    pub(crate) type Energy = i64;
+
+    pub(crate) const ZERO: Value = 0;
+    pub(crate) const NO_ENERGY: Energy = 0;
+
+    /// Nanojoules in a milliwatt-hour.
+    const NJ_PER_MWH: i64 = 3_600_000_000;
+
+    pub(crate) const fn from_milli(milli: i32) -> Value {
+        milli
+    }
+
+    pub(crate) fn milli(value: Value) -> i32 {
+        value
+    }
+
+    pub(crate) fn from_f64(value: f64) -> Value {
+        let half = if value < 0.0 { -0.5 } else { 0.5 };
+
+        (value * 1000.0 + half) as i32
+    }
+
+    pub(crate) fn to_f64(value: Value) -> f64 {
+        value as f64 / 1000.0
+    }
+
+    pub(crate) fn mul(a: Value, b: Value) -> Value {
+        (a as i64 * b as i64 / 1000) as i32
+    }
+
+    pub(crate) fn div(value: Value, n: u32) -> Value {
+        value / n as i32
+    }
+
+    pub(crate) fn percent(value: Value, percent: i32) -> Value {
+        (value as i64 * percent as i64 / 100) as i32
+    }
+
+    pub(crate) fn compact(value: Value) -> Compact {
+        value
+    }
+
+    pub(crate) fn expand(value: Compact) -> Value {
+        value
+    }
+
+    pub(crate) fn add_energy(energy: &mut Energy, watts: Option<Value>, elapsed: Duration) {
+        if let Some(watts) = watts {
+            *energy += watts as i64 * elapsed.as_micros() as i64;
+        }
+    }
+
+    pub(crate) fn energy_wh(energy: Energy) -> Value {
+        (energy / NJ_PER_MWH) as i32
+    }
+
+    pub(crate) fn fixed(value: Value, decimals: u8, width: usize) -> Fixed {
+        fixed_milli(value, decimals, width)
+    }
+}
+
+pub(crate) use repr::*;
@@ -0,0 +1,344 @@
+//! Staged firmware update over the console.
+//!
+//! The STM32G071 has a single 128 KiB bank, so the upper half serves as staging area:
+//!
+//! ```text
+//! 0x0800_0000  active image (linked into the 64 KiB below STAGING_START, see `memory-g0.x`)
+//! 0x0801_0000  staging area
+//! 0x0801_E800  output statistics journal, see `stats_settings.rs`
+//! 0x0801_F000  settings page, see `settings.rs`
+//! 0x0801_F800  state page (pending marker, image length and CRC32)
+//! ```
+//!
+//! Images are streamed with `fw begin`, `fw data` and `fw commit`. They must carry an Ed25519
+//! signature of the key built in from `PD_SINK_UPDATE_KEY`, see `signature.rs`; `fw commit`
+//! refuses an image whose signature does not match. After a reset the pending image is checked
+//! again, CRC32 and signature, and copied over the active one by a routine running from RAM. If
+//! power fails during the copy the device has to be recovered through the ROM bootloader (BOOT0
+//! or `bootloader`).
+
+use embassy_stm32::flash::{Blocking, Flash, FLASH_BASE};
+
+use crate::{
+    log::{error, warn, Module},
+    shared::FLASH,
+    signature::{self, KEY_LEN},
+};
+
+const LOG_MODULE: Module = Module::Update;
+
+pub(crate) const PAGE_SIZE: u32 = 2048;
+
+const ACTIVE_START: u32 = FLASH_BASE as u32;
+const STAGING_START: u32 = FLASH_BASE as u32 + 0x1_0000;
+pub(crate) const STATS_START: u32 = FLASH_BASE as u32 + 0x1_E800;
+pub(crate) const SETTINGS_START: u32 = FLASH_BASE as u32 + 0x1_F000;
+const STATE_START: u32 = FLASH_BASE as u32 + 0x1_F800;
+pub(crate) const STAGING_SIZE: u32 = STATS_START - STAGING_START;
+
+const PENDING_MAGIC: u32 = 0x5044_5550; // "PDUP"
+
+/// The key images are signed with, none on a build without `PD_SINK_UPDATE_KEY`.
+const UPDATE_KEY: Option<[u8; KEY_LEN]> = match option_env!("PD_SINK_UPDATE_KEY") {
+    Some(hex) => Some(signature::parse_key(hex)),
+    None => None,
+};
+
+extern "C" {
+    static __sidata: u32;
+    static __sdata: u32;
+    static __edata: u32;
+}
+
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) enum UpdateError {
+    NotStarted,
+    TooLarge,
+    ActiveTooLarge,
+    Unaligned,
+    Flash,
+    Incomplete,
+    CrcMismatch,
+    NoKey,
+    BadSignature,
+}
+
+impl UpdateError {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UpdateError::NotStarted => "no update in progress",
+            UpdateError::TooLarge => "image larger than staging area",
+            UpdateError::ActiveTooLarge => "running image overlaps staging area",
+            UpdateError::Unaligned => "offset must be a multiple of 8",
+            UpdateError::Flash => "flash error",
+            UpdateError::Incomplete => "image incomplete",
+            UpdateError::CrcMismatch => "crc mismatch",
+            UpdateError::NoKey => "no update key in this build",
+            UpdateError::BadSignature => "signature invalid",
+        }
+    }
+}
+
+struct Session {
+    size: u32,
+    crc: u32,
+    written: u32,
+}
+
+pub(crate) struct Updater {
+    session: Option<Session>,
+}
+
+impl Updater {
+    pub const fn new() -> Self {
+        Self { session: None }
+    }
+
+    /// Erases the staging area and starts receiving a signed image of `size` bytes.
+    pub async fn begin(&mut self, size: u32, crc: u32) -> Result<(), UpdateError> {
+        if UPDATE_KEY.is_none() {
+            return Err(UpdateError::NoKey);
+        }
+
+        if signature::image_len(size as usize).is_none() || size > STAGING_SIZE {
+            return Err(UpdateError::TooLarge);
+        }
+
+        if active_image_end() > STAGING_START {
+            return Err(UpdateError::ActiveTooLarge);
+        }
+
+        self.session = None;
+
+        with_flash(|flash| {
+            let from = STAGING_START - ACTIVE_START;
+            let to = STATS_START - ACTIVE_START;
+            let state = STATE_START - ACTIVE_START;
+
+            // Skip the statistics and settings pages in between.
+            flash.blocking_erase(from, to)?;
+            flash.blocking_erase(state, state + PAGE_SIZE)
+        })
+        .await?;
+
+        self.session = Some(Session {
+            size,
+            crc,
+            written: 0,
+        });
+
+        Ok(())
+    }
+
+    /// Writes a chunk at `offset`. Chunks must arrive in order; the last one may be short.
+    pub async fn write(&mut self, offset: u32, data: &[u8]) -> Result<(), UpdateError> {
+        let session = self.session.as_mut().ok_or(UpdateError::NotStarted)?;
+
+        if offset % 8 != 0 || offset != session.written {
+            return Err(UpdateError::Unaligned);
+        }
+
+        if offset + data.len() as u32 > session.size {
+            return Err(UpdateError::TooLarge);
+        }
+
+        // Flash is programmed in double words, pad the tail with the erased value.
+        let mut buf = [0xffu8; 64];
+        let padded = (data.len() + 7) / 8 * 8;
+        if padded > buf.len() {
+            return Err(UpdateError::TooLarge);
+        }
+        buf[..data.len()].copy_from_slice(data);
+
+        with_flash(|flash| {
+            flash.blocking_write(STAGING_START - ACTIVE_START + offset, &buf[..padded])
+        })
+        .await?;
+
+        session.written += data.len() as u32;
+
+        Ok(())
+    }
+
+    /// Verifies the staged image and marks it for installation on the next reset.
+    pub async fn commit(&mut self) -> Result<(), UpdateError> {
+        let session = self.session.take().ok_or(UpdateError::NotStarted)?;
+
+        if session.written != session.size {
+            return Err(UpdateError::Incomplete);
+        }
+
+        if staged_crc(session.size) != session.crc {
+            return Err(UpdateError::CrcMismatch);
+        }
+
+        if !staged_signature_ok(session.size) {
+            return Err(UpdateError::BadSignature);
+        }
+
+        let mut state = [0u8; 16];
+        state[0..4].copy_from_slice(&PENDING_MAGIC.to_le_bytes());
+        state[4..8].copy_from_slice(&session.size.to_le_bytes());
+        state[8..12].copy_from_slice(&session.crc.to_le_bytes());
+
+        with_flash(|flash| flash.blocking_write(STATE_START - ACTIVE_START, &state)).await
+    }
+
+    pub fn abort(&mut self) {
+        self.session = None;
+    }
+
+    pub fn progress(&self) -> Option<(u32, u32)> {
+        self.session.as_ref().map(|s| (s.written, s.size))
+    }
+}
+
+/// Installs a committed image, if any. Must run before anything else touches flash.
+pub(crate) fn apply_pending_update() {
+    let magic = read_u32(STATE_START);
+
+    if magic != PENDING_MAGIC {
+        return;
+    }
+
+    let size = read_u32(STATE_START + 4);
+    let crc = read_u32(STATE_START + 8);
+
+    let Some(len) = signature::image_len(size as usize) else {
+        error!("staged image invalid, discarding");
+        return;
+    };
+
+    if size > STAGING_SIZE || staged_crc(size) != crc || !staged_signature_ok(size) {
+        error!("staged image invalid, discarding");
+        return;
+    }
+
+    warn!("installing staged image ({} bytes)", len);
+
+    cortex_m::interrupt::disable();
+
+    // Only the firmware goes over the active image, not its signature.
+    unsafe { install(len as u32) }
+}
+
+async fn with_flash<F>(f: F) -> Result<(), UpdateError>
+where
+    F: FnOnce(&mut Flash<'static, Blocking>) -> Result<(), embassy_stm32::flash::Error>,
+{
+    let mut flash = FLASH.lock().await;
+    let flash = flash.as_mut().ok_or(UpdateError::Flash)?;
+
+    f(flash).map_err(|err| {
+        error!("flash: {:?}", err);
+        UpdateError::Flash
+    })
+}
+
+fn active_image_end() -> u32 {
+    unsafe {
+        let sidata = &__sidata as *const u32 as u32;
+        let sdata = &__sdata as *const u32 as u32;
+        let edata = &__edata as *const u32 as u32;
+
+        sidata + (edata - sdata)
+    }
+}
+
+fn read_u32(addr: u32) -> u32 {
+    unsafe { core::ptr::read_volatile(addr as *const u32) }
+}
+
+fn staged(size: u32) -> &'static [u8] {
+    unsafe { core::slice::from_raw_parts(STAGING_START as *const u8, size as usize) }
+}
+
+fn staged_crc(size: u32) -> u32 {
+    crc32(staged(size))
+}
+
+/// Whether the staged image of `size` bytes, signature included, is signed with the built-in
+/// key. Takes a while on the G0, which has no hardware for it.
+fn staged_signature_ok(size: u32) -> bool {
+    UPDATE_KEY.is_some_and(|key| signature::verify(&key, staged(size)))
+}
+
+/// CRC-32/ISO-HDLC, the one used by zlib and `crc32` on the command line.
+pub(crate) fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xffff_ffffu32;
+
+    for &byte in data {
+        crc ^= byte as u32;
+
+        for _ in 0..8 {
+            if crc & 1 != 0 {
+                crc = (crc >> 1) ^ 0xedb8_8320;
+            } else {
+                crc >>= 1;
+            }
+        }
+    }
+
+    !crc
+}
+
+// The FLASH registers and bits used here are laid out the same on STM32G0 and STM32L4.
+const FLASH_KEYR: *mut u32 = 0x4002_2008 as *mut u32;
+const FLASH_SR: *mut u32 = 0x4002_2010 as *mut u32;
+const FLASH_CR: *mut u32 = 0x4002_2014 as *mut u32;
+const SCB_AIRCR: *mut u32 = 0xe000_ed0c as *mut u32;
+
+const CR_PG: u32 = 1 << 0;
+const CR_PER: u32 = 1 << 1;
+const CR_STRT: u32 = 1 << 16;
+const SR_BSY1: u32 = 1 << 16;
+const SR_ERRORS: u32 = 0xc3fa;
+
+/// Copies the staged image over the active one and resets.
+///
+/// Runs from RAM and must not call anything that lives in flash (including compiler-generated
+/// `memcpy` and panics), since the code it was loaded from is being erased.
+#[link_section = ".data.updater"]
+#[inline(never)]
+unsafe fn install(size: u32) -> ! {
+    core::ptr::write_volatile(FLASH_KEYR, 0x4567_0123);
+    core::ptr::write_volatile(FLASH_KEYR, 0xcdef_89ab);
+
+    let pages = (size + PAGE_SIZE - 1) / PAGE_SIZE;
+    let mut page = 0;
+
+    while page < pages {
+        core::ptr::write_volatile(FLASH_SR, SR_ERRORS);
+        core::ptr::write_volatile(FLASH_CR, CR_PER | (page << 3));
+        core::ptr::write_volatile(FLASH_CR, CR_PER | (page << 3) | CR_STRT);
+        while core::ptr::read_volatile(FLASH_SR) & SR_BSY1 != 0 {}
+
+        page += 1;
+    }
+
+    core::ptr::write_volatile(FLASH_CR, CR_PG);
+
+    let mut offset = 0;
+    while offset < size {
+        let src = (STAGING_START + offset) as *const u32;
+        let dst = (ACTIVE_START + offset) as *mut u32;
+
+        core::ptr::write_volatile(dst, core::ptr::read_volatile(src));
+        core::ptr::write_volatile(dst.add(1), core::ptr::read_volatile(src.add(1)));
+        while core::ptr::read_volatile(FLASH_SR) & SR_BSY1 != 0 {}
+
+        offset += 8;
+    }
+
+    // Drop the pending marker so the copy doesn't repeat.
+    let state_page = (STATE_START - ACTIVE_START) / PAGE_SIZE;
+    core::ptr::write_volatile(FLASH_CR, CR_PER | (state_page << 3));
+    core::ptr::write_volatile(FLASH_CR, CR_PER | (state_page << 3) | CR_STRT);
+    while core::ptr::read_volatile(FLASH_SR) & SR_BSY1 != 0 {}
+
+    core::ptr::write_volatile(FLASH_CR, 1 << 31);
+
+    core::ptr::write_volatile(SCB_AIRCR, 0x05fa_0004);
+
+    loop {}
+}
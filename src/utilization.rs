@@ -0,0 +1,93 @@
+//! How much of the PD contract the load uses.
+//!
+//! The source negotiated a voltage and a current; the load's amps against that current, and its
+//! watts against their product, say how close it runs to what the source promised. A source
+//! pushed past its contract may fold back or reset without warning, so a draw that stays above
+//! [`WARN_PERCENT`] for [`WARN_AFTER`] is flagged on the monitor page and logged, while a short
+//! inrush is not.
+
+use embassy_time::Duration;
+#[cfg(not(feature = "mock-time"))]
+use embassy_time::Instant;
+
+#[cfg(feature = "mock-time")]
+use crate::mock_time::Instant;
+use crate::{
+    types::PowerInfo,
+    units::{self, Value},
+};
+
+pub(crate) const WARN_PERCENT: u16 = 90;
+
+/// How long the draw has to stay above [`WARN_PERCENT`] before the warning.
+pub(crate) const WARN_AFTER: Duration = Duration::from_secs(5);
+
+/// Largest percentage shown; three digits fit the monitor page.
+const MAX_PERCENT: u16 = 999;
+
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) struct Utilization {
+    /// Amps in percent of the contract current.
+    pub amps_percent: u16,
+    /// Watts in percent of the contract voltage times its current.
+    pub watts_percent: u16,
+}
+
+impl Utilization {
+    /// `None` without a contract to compare against.
+    pub fn of(power: &PowerInfo, target_volts: Value, limit_amps: Value) -> Option<Self> {
+        let limit_ma = units::milli(limit_amps) as i64;
+        let target_mv = units::milli(target_volts) as i64;
+
+        if limit_ma <= 0 || target_mv <= 0 {
+            return None;
+        }
+
+        let contract_mw = target_mv * limit_ma / 1_000;
+
+        Some(Self {
+            amps_percent: percent(units::milli(power.amps) as i64, limit_ma),
+            watts_percent: percent(units::milli(power.watts) as i64, contract_mw),
+        })
+    }
+
+    /// The higher of the two.
+    pub fn peak(&self) -> u16 {
+        self.amps_percent.max(self.watts_percent)
+    }
+}
+
+fn percent(value: i64, limit: i64) -> u16 {
+    if limit <= 0 {
+        return 0;
+    }
+
+    (value * 100 / limit).clamp(0, MAX_PERCENT as i64) as u16
+}
+
+/// Tracks how long the draw has been above [`WARN_PERCENT`].
+pub(crate) struct UtilizationMonitor {
+    above_since: Option<Instant>,
+}
+
+impl UtilizationMonitor {
+    pub const fn new() -> Self {
+        Self { above_since: None }
+    }
+
+    /// Takes a reading and returns whether the draw has been above [`WARN_PERCENT`] for at least
+    /// [`WARN_AFTER`]; a reading at or below it, or without a contract, starts over.
+    pub fn update(&mut self, now: Instant, utilization: Option<Utilization>) -> bool {
+        match utilization {
+            Some(utilization) if utilization.peak() > WARN_PERCENT => {
+                let since = *self.above_since.get_or_insert(now);
+
+                now - since >= WARN_AFTER
+            }
+            _ => {
+                self.above_since = None;
+                false
+            }
+        }
+    }
+}
@@ -0,0 +1,235 @@
+//! Where the displayed and published watts come from.
+//!
+//! The INA226 power register, the product of the filtered volts and amps, and a peak hold of the
+//! register disagree under pulsed loads: the register multiplies each conversion pair before the
+//! filter, the product multiplies two averages, and the peak shows what the source had to deliver.
+//! The energy counter always integrates the raw register.
+//!
+//! The register's conversion time can beat against the PWM of a pulsed load, so its readings drift
+//! up and down with the phase. The synced source multiplies the volts and amps read back to back in
+//! each pass instead and averages those products over whole periods of the load, from one rising
+//! crossing of the current through its mean to the next, see [`SyncedPower`].
+
+use embassy_time::Duration;
+#[cfg(not(feature = "mock-time"))]
+use embassy_time::Instant;
+
+#[cfg(feature = "mock-time")]
+use crate::mock_time::Instant;
+use crate::units::{self, Value};
+
+/// How long a peak stays on the screen unless a higher one replaces it.
+pub(crate) const PEAK_HOLD: Duration = Duration::from_secs(1);
+
+/// Shortest span of whole load periods the synced watts average over.
+pub(crate) const SYNC_WINDOW: Duration = Duration::from_millis(500);
+/// Without a crossing for this long, e.g. under a steady load, every sample of it is averaged.
+pub(crate) const SYNC_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Weight of a new reading in the mean current the crossings are taken against, in percent.
+const LEVEL_ALPHA_PERCENT: i32 = 5;
+/// Band around the mean current the current has to leave to count as crossing it, in percent of
+/// the mean and at least [`MIN_HYSTERESIS`], so noise on a steady load is not taken for a period.
+const HYSTERESIS_PERCENT: i32 = 10;
+const MIN_HYSTERESIS: Value = units::from_milli(10);
+
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+pub(crate) enum WattsSource {
+    /// The filtered power register.
+    Register,
+    /// Filtered volts times filtered amps.
+    Product,
+    /// Mean of raw volts times raw amps over whole load periods.
+    Synced,
+    /// Highest raw power register reading of the last [`PEAK_HOLD`].
+    Peak,
+}
+
+pub(crate) const WATTS_SOURCES: [WattsSource; 4] = [
+    WattsSource::Register,
+    WattsSource::Product,
+    WattsSource::Synced,
+    WattsSource::Peak,
+];
+
+impl WattsSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WattsSource::Register => "register",
+            WattsSource::Product => "product",
+            WattsSource::Synced => "synced",
+            WattsSource::Peak => "peak",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "register" => Some(WattsSource::Register),
+            "product" => Some(WattsSource::Product),
+            "synced" => Some(WattsSource::Synced),
+            "peak" => Some(WattsSource::Peak),
+            _ => None,
+        }
+    }
+
+    /// Unit shown next to the watts on the monitor page, so the source is visible at a glance.
+    pub fn unit(&self) -> &'static str {
+        match self {
+            WattsSource::Register => "W",
+            WattsSource::Product => "w",
+            WattsSource::Synced => "S",
+            WattsSource::Peak => "P",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        let index = *self as usize;
+
+        WATTS_SOURCES[(index + 1) % WATTS_SOURCES.len()]
+    }
+
+    pub fn prev(&self) -> Self {
+        let index = *self as usize;
+
+        WATTS_SOURCES[(index + WATTS_SOURCES.len() - 1) % WATTS_SOURCES.len()]
+    }
+}
+
+/// Holds the highest reading for [`PEAK_HOLD`], then follows the readings again.
+pub(crate) struct PeakHold {
+    peak: Option<(Value, Instant)>,
+}
+
+impl PeakHold {
+    pub const fn new() -> Self {
+        Self { peak: None }
+    }
+
+    pub fn update(&mut self, now: Instant, x: Value) -> Value {
+        match self.peak {
+            Some((peak, at)) if peak >= x && now - at < PEAK_HOLD => peak,
+            _ => {
+                self.peak = Some((x, now));
+                x
+            }
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.peak = None;
+    }
+}
+
+/// Sums of the power samples in milli-watts, and how many there are.
+#[derive(Clone, Copy)]
+struct Sums {
+    milli: i64,
+    count: u32,
+}
+
+impl Sums {
+    const fn new() -> Self {
+        Self { milli: 0, count: 0 }
+    }
+
+    fn add(&mut self, other: Sums) {
+        self.milli += other.milli;
+        self.count += other.count;
+    }
+
+    fn mean(&self) -> Option<Value> {
+        (self.count > 0).then(|| units::from_milli((self.milli / self.count as i64) as i32))
+    }
+}
+
+/// Averages volts times amps over whole periods of a pulsed load.
+///
+/// The current's mean is tracked slowly, and each time the current rises through it the samples
+/// since the previous rise make up one period. Once the periods cover [`SYNC_WINDOW`], their mean
+/// is the new result, so a partial period never tips it by the phase it was cut at.
+pub(crate) struct SyncedPower {
+    level: Option<Value>,
+    above: bool,
+    /// Whether a rise was seen, so `open` starts at the beginning of a period.
+    synced: bool,
+    /// Samples since the last rise.
+    open: Sums,
+    /// Whole periods since the last result.
+    closed: Sums,
+    started: Option<Instant>,
+    result: Option<Value>,
+}
+
+impl SyncedPower {
+    pub const fn new() -> Self {
+        Self {
+            level: None,
+            above: false,
+            synced: false,
+            open: Sums::new(),
+            closed: Sums::new(),
+            started: None,
+            result: None,
+        }
+    }
+
+    /// Takes a pair of readings from the same pass and returns the mean over the last completed
+    /// window, or the product of this pair until there is one.
+    pub fn update(&mut self, now: Instant, volts: Value, amps: Value) -> Value {
+        let product = units::mul(volts, amps);
+
+        let level = match self.level {
+            Some(level) => level + units::percent(amps - level, LEVEL_ALPHA_PERCENT),
+            None => amps,
+        };
+        self.level = Some(level);
+
+        let hysteresis = units::percent(level, HYSTERESIS_PERCENT).max(MIN_HYSTERESIS);
+        let rising = !self.above && amps > level + hysteresis;
+        if rising {
+            self.above = true;
+        } else if self.above && amps < level - hysteresis {
+            self.above = false;
+        }
+
+        if rising {
+            if self.synced {
+                self.closed.add(self.open);
+            }
+
+            self.open = Sums::new();
+            self.synced = true;
+        }
+
+        self.open.add(Sums {
+            milli: units::milli(product) as i64,
+            count: 1,
+        });
+
+        let started = *self.started.get_or_insert(now);
+        let elapsed = now - started;
+
+        if elapsed >= SYNC_WINDOW && self.closed.count > 0 {
+            self.result = self.closed.mean();
+            self.closed = Sums::new();
+            self.started = Some(now);
+        } else if elapsed >= SYNC_TIMEOUT {
+            let mut all = self.closed;
+            all.add(self.open);
+
+            self.result = all.mean();
+            *self = Self {
+                level: self.level,
+                result: self.result,
+                started: Some(now),
+                ..Self::new()
+            };
+        }
+
+        self.result.unwrap_or(product)
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+}
@@ -0,0 +1,206 @@
+//! MQTT publishing through an ESP8266/ESP32 running Espressif AT firmware (v2.2+ MQTT commands).
+//!
+//! Credentials and broker are taken from the build environment:
+//! `WIFI_SSID`, `WIFI_PASSWORD`, `MQTT_HOST`, `MQTT_PORT` and `MQTT_TOPIC`.
+
+use core::fmt::{self, Write as _};
+
+use embassy_time::{with_timeout, Duration, Instant, Timer};
+use embedded_io_async::{Read, Write};
+use heapless::String;
+
+use crate::{
+    log::{warn, Module},
+    shared::{ENERGY_MUTEX, MQTT_INTERVAL_MUTEX, POWER_INFO_MUTEX, WIFI_STATE_MUTEX},
+    types::{WifiState, WifiUart},
+    units::{self, fixed},
+};
+
+const LOG_MODULE: Module = Module::Wifi;
+
+const WIFI_SSID: &str = match option_env!("WIFI_SSID") {
+    Some(ssid) => ssid,
+    None => "",
+};
+const WIFI_PASSWORD: &str = match option_env!("WIFI_PASSWORD") {
+    Some(password) => password,
+    None => "",
+};
+const MQTT_HOST: &str = match option_env!("MQTT_HOST") {
+    Some(host) => host,
+    None => "",
+};
+const MQTT_PORT: &str = match option_env!("MQTT_PORT") {
+    Some(port) => port,
+    None => "1883",
+};
+const MQTT_TOPIC: &str = match option_env!("MQTT_TOPIC") {
+    Some(topic) => topic,
+    None => "pd-sink",
+};
+
+const COMMAND_TIMEOUT: Duration = Duration::from_secs(2);
+const JOIN_TIMEOUT: Duration = Duration::from_secs(20);
+const RETRY_DELAY: Duration = Duration::from_secs(10);
+
+const LINE_LEN: usize = 128;
+
+#[derive(PartialEq, Clone, Copy, Debug, defmt::Format)]
+enum WifiError {
+    Timeout,
+    Uart,
+    Rejected,
+}
+
+pub(crate) struct Wifi {
+    uart: WifiUart,
+    line: String<LINE_LEN>,
+}
+
+impl Wifi {
+    pub fn new(uart: WifiUart) -> Self {
+        Self {
+            uart,
+            line: String::new(),
+        }
+    }
+
+    pub async fn task(&mut self) {
+        if WIFI_SSID.is_empty() || MQTT_HOST.is_empty() {
+            warn!("wifi: WIFI_SSID or MQTT_HOST not set at build time, bridge disabled");
+            return;
+        }
+
+        loop {
+            self.set_state(WifiState::Connecting).await;
+
+            if let Err(err) = self.connect().await {
+                warn!("wifi: connect failed: {:?}", err);
+                self.set_state(WifiState::Disconnected).await;
+                Timer::after(RETRY_DELAY).await;
+                continue;
+            }
+
+            self.set_state(WifiState::Connected).await;
+
+            if let Err(err) = self.publish_loop().await {
+                warn!("wifi: publish failed: {:?}", err);
+            }
+
+            self.set_state(WifiState::Disconnected).await;
+            Timer::after(RETRY_DELAY).await;
+        }
+    }
+
+    async fn set_state(&mut self, state: WifiState) {
+        *WIFI_STATE_MUTEX.lock().await = state;
+    }
+
+    async fn connect(&mut self) -> Result<(), WifiError> {
+        self.command(format_args!("AT"), COMMAND_TIMEOUT).await?;
+        self.command(format_args!("ATE0"), COMMAND_TIMEOUT).await?;
+        self.command(format_args!("AT+CWMODE=1"), COMMAND_TIMEOUT)
+            .await?;
+        self.command(
+            format_args!("AT+CWJAP=\"{}\",\"{}\"", WIFI_SSID, WIFI_PASSWORD),
+            JOIN_TIMEOUT,
+        )
+        .await?;
+        self.command(
+            format_args!("AT+MQTTUSERCFG=0,1,\"pd-sink\",\"\",\"\",0,0,\"\""),
+            COMMAND_TIMEOUT,
+        )
+        .await?;
+        self.command(
+            format_args!("AT+MQTTCONN=0,\"{}\",{},1", MQTT_HOST, MQTT_PORT),
+            JOIN_TIMEOUT,
+        )
+        .await
+    }
+
+    async fn publish_loop(&mut self) -> Result<(), WifiError> {
+        loop {
+            let started_at = Instant::now();
+
+            let power = *POWER_INFO_MUTEX.lock().await;
+            let energy = *ENERGY_MUTEX.lock().await;
+
+            // The payload is a quoted AT string parameter, so quotes and commas inside the JSON
+            // have to be escaped.
+            self.command(
+                format_args!(
+                    "AT+MQTTPUB=0,\"{}\",\"{{\\\"v\\\":{}\\,\\\"a\\\":{}\\,\\\"w\\\":{}\\,\\\"wh\\\":{}}}\",0,0",
+                    MQTT_TOPIC,
+                    fixed(power.volts, 3, 0),
+                    fixed(power.amps, 3, 0),
+                    fixed(power.watts, 3, 0),
+                    fixed(units::energy_wh(energy), 4, 0)
+                ),
+                COMMAND_TIMEOUT,
+            )
+            .await?;
+
+            let interval = Duration::from_secs(*MQTT_INTERVAL_MUTEX.lock().await as u64);
+            let elapsed = Instant::now() - started_at;
+
+            if interval > elapsed {
+                Timer::after(interval - elapsed).await;
+            }
+        }
+    }
+
+    /// Sends an AT command and waits for its final `OK` or `ERROR`.
+    async fn command(
+        &mut self,
+        cmd: fmt::Arguments<'_>,
+        timeout: Duration,
+    ) -> Result<(), WifiError> {
+        let mut buf: String<LINE_LEN> = String::new();
+
+        buf.write_fmt(cmd).map_err(|_| WifiError::Rejected)?;
+        buf.push_str("\r\n").map_err(|_| WifiError::Rejected)?;
+
+        self.uart
+            .write_all(buf.as_bytes())
+            .await
+            .map_err(|_| WifiError::Uart)?;
+
+        with_timeout(timeout, self.wait_result())
+            .await
+            .map_err(|_| WifiError::Timeout)?
+    }
+
+    async fn wait_result(&mut self) -> Result<(), WifiError> {
+        loop {
+            self.read_line().await?;
+
+            match self.line.as_str() {
+                "OK" => return Ok(()),
+                "ERROR" | "FAIL" => return Err(WifiError::Rejected),
+                _ => {}
+            }
+        }
+    }
+
+    async fn read_line(&mut self) -> Result<(), WifiError> {
+        let mut byte = [0u8; 1];
+
+        self.line.clear();
+
+        loop {
+            self.uart
+                .read_exact(&mut byte)
+                .await
+                .map_err(|_| WifiError::Uart)?;
+
+            match byte[0] {
+                b'\n' => return Ok(()),
+                b'\r' => {}
+                b => {
+                    // Overlong lines are only ever URCs we don't care about.
+                    self.line.push(b as char).ok();
+                }
+            }
+        }
+    }
+}
@@ -237,6 +237,33 @@ where
         Ok(())
     }
 
+    /// Shows the complement of every color while `invert` is set, on top of `Config::inverted`.
+    /// A reset or `init` clears it.
+    pub async fn invert_colors(&mut self, invert: bool) -> Result<(), Error<E>> {
+        let instruction = if self.config.inverted != invert {
+            Instruction::INVON
+        } else {
+            Instruction::INVOFF
+        };
+
+        self.write_command(instruction, &[]).await
+    }
+
+    /// Turns the panel off and enters sleep mode. The frame memory is retained.
+    pub async fn sleep(&mut self) -> Result<(), Error<E>> {
+        self.write_command(Instruction::DISPOFF, &[]).await?;
+        self.write_command(Instruction::SLPIN, &[]).await?;
+        Delay.delay_ms(5).await;
+        Ok(())
+    }
+
+    /// Leaves sleep mode and turns the panel back on.
+    pub async fn wake(&mut self) -> Result<(), Error<E>> {
+        self.write_command(Instruction::SLPOUT, &[]).await?;
+        Delay.delay_ms(120).await;
+        self.write_command(Instruction::DISPON, &[]).await
+    }
+
     async fn write_command(
         &mut self,
         instruction: Instruction,
@@ -326,6 +353,34 @@ where
         Ok(())
     }
 
+    /// Fills a rectangle with `color`, leaving the rest of the frame memory as it is.
+    pub async fn fill_area(
+        &mut self,
+        x: u16,
+        y: u16,
+        width: u16,
+        height: u16,
+        color: Rgb565,
+    ) -> Result<(), Error<E>> {
+        self.set_address_window(x, y, x + width - 1, y + height - 1)
+            .await?;
+        let color = RawU16::from(color).into_inner();
+        let bytes = color.to_be_bytes();
+        let mut buf = [0_u8; 1440];
+        for pixel in buf.chunks_exact_mut(2) {
+            pixel.copy_from_slice(&bytes);
+        }
+        self.write_command(Instruction::RAMWR, &[]).await?;
+        self.start_data()?;
+        let mut remaining = width as usize * height as usize * 2;
+        while remaining > 0 {
+            let len = remaining.min(buf.len());
+            self.spi.write(&buf[..len]).await.map_err(Error::Comm)?;
+            remaining -= len;
+        }
+        Ok(())
+    }
+
     pub async fn write_area(
         &mut self,
         x: u16,
@@ -335,7 +390,7 @@ where
         color: Rgb565,
         bg_color: Rgb565,
     ) -> Result<(), Error<E>> {
-        const BUF_SIZE: usize = 24*48*2;
+        const BUF_SIZE: usize = 24 * 48 * 2;
         const MAX_DATA_LEN: usize = BUF_SIZE / 2;
 
         let height = MAX_DATA_LEN as u16 / width
@@ -370,7 +425,10 @@ where
         //     buff[i * 2] = back_bytes[1];
         //     buff[i * 2 + 1] = back_bytes[0];
         // }
-        self.spi.write(&buff[..data.len() * 8 * 2]).await.map_err(Error::Comm)?;
+        self.spi
+            .write(&buff[..data.len() * 8 * 2])
+            .await
+            .map_err(Error::Comm)?;
         Ok(())
     }
 }
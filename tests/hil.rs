@@ -0,0 +1,144 @@
+//! Hardware-in-the-loop tests of the driver bring-up sequences.
+//!
+//! Runs on a bench board through probe-rs, each test after a fresh reset:
+//! `cargo test --test hil` (add the board features and target for anything but `board-v1`).
+//! The board needs its panel fitted and a USB PD source on the input.
+
+#![no_std]
+#![no_main]
+
+// Only part of the board is exercised here.
+#[allow(dead_code)]
+#[path = "../src/bsp.rs"]
+mod bsp;
+
+use defmt_rtt as _;
+use panic_probe as _;
+
+#[cfg(test)]
+#[embedded_test::tests]
+mod tests {
+    use embassy_embedded_hal::shared_bus::asynch::spi::SpiDevice;
+    use embassy_stm32::{
+        gpio::{Level, Output, Speed},
+        i2c::I2c,
+        spi::{self, Spi},
+        time::Hertz,
+    };
+    use embassy_sync::{blocking_mutex::raw::CriticalSectionRawMutex, mutex::Mutex};
+    use embassy_time::Timer;
+    use embedded_graphics::{pixelcolor::Rgb565, prelude::RgbColor};
+    use husb238::Husb238;
+    use ina226::INA226;
+    use st7789::ST7789;
+
+    use crate::bsp::{self, Board, Irqs};
+
+    #[init]
+    fn init() -> Board {
+        bsp::init()
+    }
+
+    #[test]
+    async fn st7789_init(p: Board) {
+        let mut config = spi::Config::default();
+        // `PowerProfile::Performance`, what the panel runs at after boot.
+        config.frequency = Hertz(16_000_000);
+        let spi = Spi::new_txonly(
+            p.display_spi,
+            p.display_sck,
+            p.display_mosi,
+            p.display_tx_dma,
+            p.display_rx_dma,
+            config,
+        );
+        let spi: Mutex<CriticalSectionRawMutex, _> = Mutex::new(spi);
+
+        let cs_pin = Output::new(p.display_cs, Level::High, Speed::High);
+        let dc_pin = Output::new(p.display_dc, Level::Low, Speed::High);
+        let rst_pin = Output::new(p.display_rst, Level::Low, Speed::High);
+
+        let mut st7789 = ST7789::new(
+            st7789::Config::default(),
+            SpiDevice::new(&spi, cs_pin),
+            dc_pin,
+            rst_pin,
+        );
+
+        // The panel has no read line, so a transfer error is all that can go wrong here.
+        assert!(st7789.init().await.is_ok());
+        assert!(st7789.fill_color(Rgb565::BLACK).await.is_ok());
+        assert!(st7789.sleep().await.is_ok());
+        assert!(st7789.wake().await.is_ok());
+    }
+
+    #[test]
+    async fn ina226_configuration(p: Board) {
+        let i2c = I2c::new(
+            p.sensor_i2c,
+            p.sensor_scl,
+            p.sensor_sda,
+            Irqs,
+            p.sensor_tx_dma,
+            p.sensor_rx_dma,
+            Hertz(100_000),
+            Default::default(),
+        );
+        let mut ina226 = INA226::new(i2c, bsp::POWER_MONITOR_ADDRESS);
+
+        // Same settings as `main` with the board's shunt, see `monitor_settings.rs`.
+        let configured = ina226
+            .set_configuration(&ina226::Config {
+                mode: ina226::MODE::ShuntBusVoltageContinuous,
+                avg: ina226::AVG::_128,
+                vbusct: ina226::VBUSCT::_8244us,
+                vshct: ina226::VSHCT::_8244us,
+            })
+            .await;
+        assert!(configured.is_ok());
+        let calibrated = ina226
+            .callibrate(
+                bsp::POWER_MONITOR_SHUNT_MICRO_OHMS as f64 / 1_000_000.0,
+                bsp::POWER_MONITOR_MAX_MILLIAMPS as f64 / 1_000.0,
+            )
+            .await;
+        assert!(calibrated.is_ok());
+
+        // 128 samples of both channels at 8.244 ms take about 2.1 s to average.
+        Timer::after_millis(2500).await;
+
+        let millivolts = ina226.bus_voltage_millivolts().await;
+        assert!(matches!(millivolts, Ok(mv) if (4500.0..21000.0).contains(&mv)));
+
+        // Without the calibration register the current reads back as `None`.
+        assert!(matches!(ina226.current_amps().await, Ok(Some(_))));
+    }
+
+    #[test]
+    async fn husb238_capabilities(p: Board) {
+        let i2c = I2c::new(
+            p.sensor_i2c,
+            p.sensor_scl,
+            p.sensor_sda,
+            Irqs,
+            p.sensor_tx_dma,
+            p.sensor_rx_dma,
+            Hertz(100_000),
+            Default::default(),
+        );
+        let mut husb238 = Husb238::new(i2c);
+
+        // Every PD source offers 5 V; the rest only has to read back.
+        assert!(matches!(husb238.get_5v_status().await, Ok(Some(_))));
+        assert!(husb238.get_9v_status().await.is_ok());
+        assert!(husb238.get_12v_status().await.is_ok());
+        assert!(husb238.get_15v_status().await.is_ok());
+        assert!(husb238.get_18v_status().await.is_ok());
+        assert!(husb238.get_20v_status().await.is_ok());
+
+        assert!(matches!(
+            husb238.get_actual_voltage_and_current().await,
+            Ok((Some(_), _))
+        ));
+    }
+}